@@ -0,0 +1,319 @@
+use serde_json::Value;
+
+use crate::internal::keccak;
+
+use super::{error::AstError, query};
+
+/// A function's canonical ABI signature (`"transfer(address,uint256)"`) together with the 4-byte
+/// calldata selector `keccak256` of that signature hashes to. Unlike
+/// [`super::stitcher::function_signature`], which keys parameters off solc's internal
+/// `typeIdentifier`/`typeString` (fine for detecting same-shape overloads within a single stitch
+/// operation, but not a value anything outside this crate would recognise), this is the same
+/// signature/selector downstream ABI tooling, block explorers, and calldata decoders derive --
+/// computed by walking each parameter's `typeName` and normalizing it the way `solc`'s own ABI
+/// encoder does (`uint` -> `uint256`, enums -> their underlying `uint8`, structs -> tuple form,
+/// contracts -> `address`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionSelector {
+  pub signature: String,
+  pub selector: [u8; 4],
+}
+
+impl FunctionSelector {
+  /// Renders [`Self::selector`] as a `0x`-prefixed 8-hex-digit string, the form most EVM tooling
+  /// (ABIs, traces, debuggers) displays a selector in.
+  pub fn to_hex(&self) -> String {
+    format!("0x{}", hex::encode(self.selector))
+  }
+}
+
+/// Computes the [`FunctionSelector`] for `function`, a `FunctionDefinition` node. `unit` is the
+/// tree `function`'s parameter types are resolved against -- a full `SourceUnit` when instrumenting
+/// a real contract, or just the enclosing fragment contract when canonicalizing a one-off parsed
+/// signature, since `UserDefinedTypeName`/`referencedDeclaration` lookups only need to reach the
+/// node they point at.
+pub fn function_selector(
+  unit: &Value,
+  function: &Value,
+  source: Option<&str>,
+) -> Result<FunctionSelector, AstError> {
+  let name = function.get("name").and_then(Value::as_str).unwrap_or_default();
+  let types = canonical_parameter_types(unit, function, source)?;
+  let signature = format!("{name}({})", types.join(","));
+  let selector = keccak::keccak256(signature.as_bytes());
+  Ok(FunctionSelector {
+    signature,
+    selector: [selector[0], selector[1], selector[2], selector[3]],
+  })
+}
+
+/// Computes the canonical ABI type of each of `function`'s parameters, in declaration order.
+pub(crate) fn canonical_parameter_types(
+  unit: &Value,
+  function: &Value,
+  source: Option<&str>,
+) -> Result<Vec<String>, AstError> {
+  let parameters = function
+    .get("parameters")
+    .and_then(|value| value.get("parameters"))
+    .and_then(|value| value.as_array())
+    .ok_or_else(|| {
+      AstError::invalid_contract_structure_at(
+        "FunctionDefinition parameters list is missing",
+        function,
+        source,
+      )
+    })?;
+
+  parameters
+    .iter()
+    .map(|parameter| {
+      let type_name = parameter.get("typeName").ok_or_else(|| {
+        AstError::invalid_contract_structure_at(
+          "Parameter is missing a typeName",
+          parameter,
+          source,
+        )
+      })?;
+      canonical_abi_type(unit, type_name, source)
+    })
+    .collect()
+}
+
+/// Resolves a single `typeName` subtree into its canonical ABI type string.
+fn canonical_abi_type(unit: &Value, type_name: &Value, source: Option<&str>) -> Result<String, AstError> {
+  match node_type(type_name) {
+    Some("ElementaryTypeName") => {
+      let name = type_name
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| AstError::invalid_contract_structure_at("ElementaryTypeName is missing a name", type_name, source))?;
+      Ok(normalize_elementary_type(name))
+    }
+    Some("UserDefinedTypeName") => {
+      let declaration = resolve_referenced_declaration(unit, type_name, source)?;
+      canonical_abi_type_for_declaration(unit, declaration, source)
+    }
+    Some("ArrayTypeName") => {
+      let base = type_name.get("baseType").ok_or_else(|| {
+        AstError::invalid_contract_structure_at("ArrayTypeName is missing a baseType", type_name, source)
+      })?;
+      let base_type = canonical_abi_type(unit, base, source)?;
+      let length = type_name
+        .get("length")
+        .filter(|value| !value.is_null())
+        .and_then(|expr| expr.get("value"))
+        .and_then(Value::as_str);
+      match length {
+        Some(length) => Ok(format!("{base_type}[{length}]")),
+        None => Ok(format!("{base_type}[]")),
+      }
+    }
+    _ => Err(AstError::invalid_contract_structure_at(
+      format!(
+        "Cannot derive a canonical ABI type for parameter type {:?}",
+        node_type(type_name)
+      ),
+      type_name,
+      source,
+    )),
+  }
+}
+
+/// Maps a declaration a `UserDefinedTypeName` points at to its canonical ABI type: enums become
+/// their underlying `uint8`, structs become a tuple of their members' canonical types, contracts
+/// become `address`, and user-defined value types recurse into their `underlyingType`.
+fn canonical_abi_type_for_declaration(unit: &Value, declaration: &Value, source: Option<&str>) -> Result<String, AstError> {
+  match node_type(declaration) {
+    Some("EnumDefinition") => Ok("uint8".to_string()),
+    Some("ContractDefinition") => Ok("address".to_string()),
+    Some("StructDefinition") => {
+      let members = declaration
+        .get("members")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+          AstError::invalid_contract_structure_at("StructDefinition is missing its members", declaration, source)
+        })?;
+      let types = members
+        .iter()
+        .map(|member| {
+          let type_name = member.get("typeName").ok_or_else(|| {
+            AstError::invalid_contract_structure_at("Struct member is missing a typeName", member, source)
+          })?;
+          canonical_abi_type(unit, type_name, source)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(format!("({})", types.join(",")))
+    }
+    Some("UserDefinedValueTypeDefinition") => {
+      let underlying = declaration.get("underlyingType").ok_or_else(|| {
+        AstError::invalid_contract_structure_at(
+          "UserDefinedValueTypeDefinition is missing an underlyingType",
+          declaration,
+          source,
+        )
+      })?;
+      canonical_abi_type(unit, underlying, source)
+    }
+    _ => Err(AstError::invalid_contract_structure_at(
+      format!(
+        "Cannot derive a canonical ABI type for declaration {:?}",
+        node_type(declaration)
+      ),
+      declaration,
+      source,
+    )),
+  }
+}
+
+/// Follows a `UserDefinedTypeName`'s `referencedDeclaration` id to the node it names, searching
+/// `unit` for it via [`query::find_path_by_id`].
+fn resolve_referenced_declaration<'a>(
+  unit: &'a Value,
+  type_name: &Value,
+  source: Option<&str>,
+) -> Result<&'a Value, AstError> {
+  let id = type_name
+    .get("referencedDeclaration")
+    .and_then(Value::as_i64)
+    .ok_or_else(|| {
+      AstError::invalid_contract_structure_at(
+        "UserDefinedTypeName is missing a referencedDeclaration",
+        type_name,
+        source,
+      )
+    })?;
+  let path = query::find_path_by_id(unit, id).ok_or_else(|| {
+    AstError::invalid_contract_structure_at(
+      format!("Could not resolve referencedDeclaration {id} to a node"),
+      type_name,
+      source,
+    )
+  })?;
+  unit.pointer(&path).ok_or_else(|| {
+    AstError::invalid_contract_structure_at(
+      format!("referencedDeclaration {id} resolved to an invalid path"),
+      type_name,
+      source,
+    )
+  })
+}
+
+/// Normalizes an `ElementaryTypeName`'s `name` into its canonical ABI spelling: bare `uint`/`int`
+/// become `uint256`/`int256` (Solidity's own aliasing rule). `address payable` needs no handling
+/// here: solc records payability in a separate `stateMutability` field (see
+/// [`super::emitter::render_type_name`]), so `name` is already plain `"address"` -- the ABI itself
+/// draws no distinction between payable and non-payable addresses anyway.
+fn normalize_elementary_type(name: &str) -> String {
+  match name {
+    "uint" => "uint256".to_string(),
+    "int" => "int256".to_string(),
+    other => other.to_string(),
+  }
+}
+
+fn node_type(value: &Value) -> Option<&str> {
+  value.get("nodeType").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn elementary(name: &str) -> Value {
+    json!({"nodeType": "ElementaryTypeName", "name": name})
+  }
+
+  fn function_with_params(name: &str, params: Vec<Value>) -> Value {
+    json!({
+      "nodeType": "FunctionDefinition",
+      "name": name,
+      "parameters": {
+        "parameters": params
+          .into_iter()
+          .map(|type_name| json!({"typeName": type_name}))
+          .collect::<Vec<_>>(),
+      },
+    })
+  }
+
+  #[test]
+  fn function_selector_matches_known_transfer_vector() {
+    let unit = json!({"nodeType": "SourceUnit", "nodes": []});
+    let function = function_with_params("transfer", vec![elementary("address"), elementary("uint256")]);
+    let selector = function_selector(&unit, &function, None).unwrap();
+    assert_eq!(selector.signature, "transfer(address,uint256)");
+    assert_eq!(selector.to_hex(), "0xa9059cbb");
+  }
+
+  #[test]
+  fn function_selector_normalizes_bare_uint_alias_and_ignores_payable_mutability() {
+    let unit = json!({"nodeType": "SourceUnit", "nodes": []});
+    let function = function_with_params(
+      "pay",
+      vec![
+        elementary("uint"),
+        json!({"nodeType": "ElementaryTypeName", "name": "address", "stateMutability": "payable"}),
+      ],
+    );
+    let selector = function_selector(&unit, &function, None).unwrap();
+    assert_eq!(selector.signature, "pay(uint256,address)");
+  }
+
+  #[test]
+  fn function_selector_expands_enum_to_uint8_and_struct_to_tuple() {
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "nodes": [
+        {"nodeType": "EnumDefinition", "id": 1, "name": "Color", "members": []},
+        {
+          "nodeType": "StructDefinition",
+          "id": 2,
+          "name": "Point",
+          "members": [
+            {"typeName": elementary("uint256")},
+            {"typeName": elementary("uint256")},
+          ],
+        },
+      ],
+    });
+    let function = function_with_params(
+      "record",
+      vec![
+        json!({"nodeType": "UserDefinedTypeName", "name": "Color", "referencedDeclaration": 1}),
+        json!({"nodeType": "UserDefinedTypeName", "name": "Point", "referencedDeclaration": 2}),
+      ],
+    );
+    let selector = function_selector(&unit, &function, None).unwrap();
+    assert_eq!(selector.signature, "record(uint8,(uint256,uint256))");
+  }
+
+  #[test]
+  fn function_selector_handles_fixed_and_dynamic_arrays() {
+    let unit = json!({"nodeType": "SourceUnit", "nodes": []});
+    let function = function_with_params(
+      "batch",
+      vec![
+        json!({
+          "nodeType": "ArrayTypeName",
+          "baseType": elementary("uint256"),
+          "length": {"value": "3"},
+        }),
+        json!({"nodeType": "ArrayTypeName", "baseType": elementary("address"), "length": Value::Null}),
+      ],
+    );
+    let selector = function_selector(&unit, &function, None).unwrap();
+    assert_eq!(selector.signature, "batch(uint256[3],address[])");
+  }
+
+  #[test]
+  fn function_selector_errors_on_unresolvable_referenced_declaration() {
+    let unit = json!({"nodeType": "SourceUnit", "nodes": []});
+    let function = function_with_params(
+      "missing",
+      vec![json!({"nodeType": "UserDefinedTypeName", "name": "Ghost", "referencedDeclaration": 99})],
+    );
+    assert!(function_selector(&unit, &function, None).is_err());
+  }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-  use crate::ast::{Ast, FragmentTarget, SourceTarget};
+  use crate::ast::{
+    Ast, FragmentTarget, Repl, ReplOutcome, SourceMapRegionKind, SourceTarget, StitchAction,
+  };
+  use crate::internal::config::{AstConfigOptions, MergePlacement, ResolveConflictStrategy};
   use serde_json::Value;
 
   const SAMPLE_CONTRACT: &str = r#"
@@ -170,6 +173,206 @@ contract Sample {
     );
   }
 
+  #[test]
+  fn source_map_tags_injected_edges_and_mirrors_untouched_spans() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow_at_edges(
+        "read()",
+        &["require(true);".to_string()],
+        &["require(true);".to_string()],
+        None,
+      )
+      .expect("inject edges");
+
+    let regions = ast.source_map().expect("source map");
+
+    assert!(
+      regions
+        .iter()
+        .any(|region| region.kind == SourceMapRegionKind::InjectedBefore
+          && region.original_span.is_none()),
+      "expected an injected-before region with no original counterpart"
+    );
+    assert!(
+      regions
+        .iter()
+        .any(|region| region.kind == SourceMapRegionKind::InjectedAfter
+          && region.original_span.is_none()),
+      "expected an injected-after region with no original counterpart"
+    );
+    assert!(
+      regions.iter().any(|region| region.kind == SourceMapRegionKind::Original
+        && region.original_span.as_deref() == Some(region.instrumented_span.as_str())),
+      "expected untouched nodes to mirror their original span"
+    );
+  }
+
+  #[test]
+  fn inject_shadow_as_modifier_reuses_modifier_across_functions() {
+    const CONTRACT: &str = r#"
+pragma solidity ^0.8.13;
+
+contract Sample {
+  uint256 internal stored;
+
+  function read() internal view returns (uint256) {
+    return stored;
+  }
+
+  function readAgain() internal view returns (uint256) {
+    return stored;
+  }
+}
+"#;
+
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(CONTRACT.into()), None)
+      .expect("load source");
+
+    ast
+      .inject_shadow_as_modifier(
+        "read()",
+        &["require(true);".to_string()],
+        &[],
+        None,
+      )
+      .expect("inject modifier");
+    ast
+      .inject_shadow_as_modifier(
+        "readAgain()",
+        &["require(true);".to_string()],
+        &[],
+        None,
+      )
+      .expect("inject modifier for second function");
+
+    let unit = ast.source_unit().expect("loaded ast");
+    let read = find_function(unit, "read").expect("read function");
+    let read_again = find_function(unit, "readAgain").expect("readAgain function");
+
+    let modifier_ref = |function: &Value| {
+      function
+        .get("modifiers")
+        .and_then(Value::as_array)
+        .and_then(|modifiers| modifiers.first())
+        .and_then(|invocation| invocation.get("modifierName"))
+        .and_then(|name| name.get("referencedDeclaration"))
+        .and_then(Value::as_i64)
+        .expect("modifier invocation")
+    };
+
+    assert_eq!(modifier_ref(read), modifier_ref(read_again));
+
+    let contract = unit["nodes"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .find(|node| node.get("name").and_then(Value::as_str) == Some("Sample"))
+      .expect("contract node");
+    let modifier_count = contract["nodes"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .filter(|node| node.get("nodeType").and_then(Value::as_str) == Some("ModifierDefinition"))
+      .count();
+    assert_eq!(modifier_count, 1, "expected a single shared modifier");
+  }
+
+  #[test]
+  fn inject_shadow_at_edges_resolves_target_by_selector_hash() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+
+    let digest = crate::internal::keccak::keccak256(b"read()");
+    let selector = format!("0x{}", hex::encode(&digest[..4]));
+
+    ast
+      .inject_shadow_at_edges(&selector, &["require(true);".to_string()], &[], None)
+      .expect("inject via selector hash");
+
+    let unit = ast.source_unit().expect("loaded ast");
+    let function = find_function(unit, "read").expect("read function");
+    assert!(json_contains_value(function, "nodeType", "ExpressionStatement"));
+  }
+
+  #[test]
+  fn inject_shadow_at_edges_resolves_overloaded_function_by_true_abi_selector() {
+    const OVERLOADED_CONTRACT: &str = r#"
+pragma solidity ^0.8.13;
+
+contract Token {
+  function transfer(address to, uint256 amount) public pure returns (bool) {
+    return true;
+  }
+
+  function transfer(address to) public pure returns (bool) {
+    return true;
+  }
+}
+"#;
+
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(OVERLOADED_CONTRACT.into()), None)
+      .expect("load source");
+
+    // The real on-chain selector for `transfer(address,uint256)`, picking out the two-argument
+    // overload specifically -- a solc `typeIdentifier`-based hash of this string would never have
+    // matched either overload.
+    ast
+      .inject_shadow_at_edges("0xa9059cbb", &["require(true);".to_string()], &[], None)
+      .expect("inject via true ABI selector");
+
+    let unit = ast.source_unit().expect("loaded ast");
+    let nodes = unit["nodes"][0]["nodes"].as_array().unwrap();
+    let two_arg = nodes
+      .iter()
+      .find(|node| {
+        node["name"] == "transfer"
+          && node["parameters"]["parameters"].as_array().unwrap().len() == 2
+      })
+      .expect("two-argument transfer overload");
+    let one_arg = nodes
+      .iter()
+      .find(|node| {
+        node["name"] == "transfer"
+          && node["parameters"]["parameters"].as_array().unwrap().len() == 1
+      })
+      .expect("one-argument transfer overload");
+
+    assert!(json_contains_value(two_arg, "nodeType", "ExpressionStatement"));
+    assert!(!json_contains_value(one_arg, "nodeType", "ExpressionStatement"));
+  }
+
+  #[test]
+  fn function_selector_derives_canonical_signature_for_named_function() {
+    const TOKEN_CONTRACT: &str = r#"
+pragma solidity ^0.8.13;
+
+contract Token {
+  function transfer(address to, uint256 amount) public pure returns (bool) {
+    return true;
+  }
+}
+"#;
+
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(TOKEN_CONTRACT.into()), None)
+      .expect("load source");
+
+    let selector = ast.function_selector("transfer", None).expect("derive selector");
+    assert_eq!(selector.signature, "transfer(address,uint256)");
+    assert_eq!(selector.to_hex(), "0xa9059cbb");
+  }
+
   #[test]
   fn inject_shadow_at_edges_requires_signature_when_ambiguous() {
     const AMBIGUOUS_CONTRACT: &str = r#"
@@ -196,7 +399,36 @@ contract Ambiguous {
   }
 
   #[test]
-  fn inject_shadow_at_edges_rejects_inline_assembly() {
+  fn inject_shadow_at_edges_instruments_functions_with_inline_assembly_by_default() {
+    const ASSEMBLY_CONTRACT: &str = r#"
+pragma solidity ^0.8.13;
+
+contract WithAssembly {
+  function useAsm(uint256 value) public pure returns (uint256 result) {
+    assembly {
+      result := add(value, 1)
+    }
+  }
+}
+"#;
+
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(ASSEMBLY_CONTRACT.into()), None)
+      .expect("load source");
+
+    ast
+      .inject_shadow_at_edges(
+        "useAsm(uint256)",
+        &["uint256 __before = value;".to_string()],
+        &[],
+        None,
+      )
+      .expect("assembly without an exit op should be instrumentable by default");
+  }
+
+  #[test]
+  fn inject_shadow_at_edges_reject_inline_assembly_flag_restores_strict_behavior() {
     const ASSEMBLY_CONTRACT: &str = r#"
 pragma solidity ^0.8.13;
 
@@ -218,15 +450,129 @@ contract WithAssembly {
       "useAsm(uint256)",
       &["uint256 __before = value;".to_string()],
       &[],
-      None,
+      Some(crate::internal::config::AstConfigOptions {
+        reject_inline_assembly: Some(true),
+        ..Default::default()
+      }),
     );
 
     assert!(
       result.is_err(),
-      "expected inline assembly instrumentation to fail"
+      "expected reject_inline_assembly to restore the strict rejection"
     );
   }
 
+  #[test]
+  fn inject_shadow_at_edges_lifts_after_snippet_before_assembly_exit() {
+    const ASSEMBLY_EXIT_CONTRACT: &str = r#"
+pragma solidity ^0.8.13;
+
+contract WithAssemblyExit {
+  function useAsm(uint256 value) public pure returns (uint256 result) {
+    assembly {
+      if iszero(value) { revert(0, 0) }
+      result := add(value, 1)
+    }
+  }
+}
+"#;
+
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(ASSEMBLY_EXIT_CONTRACT.into()), None)
+      .expect("load source");
+
+    ast
+      .inject_shadow_at_edges(
+        "useAsm(uint256)",
+        &[],
+        &["uint256 __after = value;".to_string()],
+        None,
+      )
+      .expect("assembly with an exit op should still be instrumentable");
+
+    let unit = ast.source_unit().expect("loaded ast");
+    let function = find_function(unit, "useAsm").expect("useAsm function");
+    let statements = function["body"]["statements"]
+      .as_array()
+      .expect("statements list");
+
+    assert_eq!(
+      statements.first().and_then(|stmt| stmt["nodeType"].as_str()),
+      Some("VariableDeclarationStatement"),
+      "expected the after snippet to be lifted before the assembly block"
+    );
+  }
+
+  #[test]
+  fn repl_accumulates_multiline_snippet_until_balanced() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+
+    let mut repl = Repl::new(ast);
+    assert_eq!(
+      repl.feed_line(":target read()"),
+      ReplOutcome::Output("target set to `read()`".to_string())
+    );
+
+    assert_eq!(repl.feed_line(":before if (stored > 0) {"), ReplOutcome::Pending);
+    assert_eq!(repl.feed_line("  require(true);"), ReplOutcome::Pending);
+    match repl.feed_line("}") {
+      ReplOutcome::Output(message) => assert!(message.contains("before snippet applied")),
+      ReplOutcome::Pending => panic!("expected the balanced snippet to dispatch"),
+    }
+
+    let unit = repl.ast().source_unit().expect("loaded ast");
+    let function = find_function(unit, "read").expect("read function");
+    assert!(json_contains_value(function, "nodeType", "IfStatement"));
+  }
+
+  #[test]
+  fn repl_undo_restores_previous_snapshot() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+
+    let mut repl = Repl::new(ast);
+    repl.feed_line(":target read()");
+    repl.feed_line(":before require(true);");
+
+    let function = find_function(repl.ast().source_unit().expect("ast"), "read").expect("fn");
+    let statements_before_undo = function["body"]["statements"]
+      .as_array()
+      .expect("statements")
+      .len();
+
+    match repl.feed_line(":undo") {
+      ReplOutcome::Output(message) => assert!(message.contains("Reverted")),
+      ReplOutcome::Pending => panic!("`:undo` should never be pending"),
+    }
+
+    let function = find_function(repl.ast().source_unit().expect("ast"), "read").expect("fn");
+    let statements_after_undo = function["body"]["statements"]
+      .as_array()
+      .expect("statements")
+      .len();
+    assert!(statements_after_undo < statements_before_undo);
+  }
+
+  #[test]
+  fn repl_snippet_without_target_reports_missing_target() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+
+    let mut repl = Repl::new(ast);
+    match repl.feed_line(":before require(true);") {
+      ReplOutcome::Output(message) => assert!(message.contains("No target set")),
+      ReplOutcome::Pending => panic!("missing target should fail immediately"),
+    }
+  }
+
   #[test]
   fn inject_shadow_at_edges_errors_on_missing_function() {
     let mut ast = Ast::new(None).expect("create ast");
@@ -239,4 +585,209 @@ contract WithAssembly {
 
     assert!(result.is_err(), "expected missing function to error");
   }
+
+  fn strategy_options(strategy: ResolveConflictStrategy) -> AstConfigOptions {
+    AstConfigOptions {
+      resolve_conflict_strategy: Some(strategy),
+      ..Default::default()
+    }
+  }
+
+  fn merge_options(placement: MergePlacement) -> AstConfigOptions {
+    AstConfigOptions {
+      resolve_conflict_strategy: Some(ResolveConflictStrategy::Merge),
+      merge_placement: Some(placement),
+      ..Default::default()
+    }
+  }
+
+  fn count_functions(unit: &Value, name: &str) -> usize {
+    unit["nodes"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .filter_map(|node| node["nodes"].as_array())
+      .flatten()
+      .filter(|member| {
+        member["nodeType"].as_str() == Some("FunctionDefinition")
+          && member["name"].as_str() == Some(name)
+      })
+      .count()
+  }
+
+  #[test]
+  fn inject_shadow_overwrite_replaces_same_named_member() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read() internal view returns (uint256) { return stored + 1; }".into(),
+        ),
+        Some(strategy_options(ResolveConflictStrategy::Overwrite)),
+      )
+      .expect("inject fragment");
+
+    let unit = ast.source_unit().expect("loaded ast").clone();
+    assert_eq!(count_functions(&unit, "read"), 1, "overwrite should not duplicate the member");
+    assert!(json_contains_value(&unit, "value", "1"), "expected the fragment's body to win");
+    assert_eq!(ast.stitch_conflicts().len(), 1);
+    assert!(matches!(
+      ast.stitch_conflicts()[0].action,
+      StitchAction::Overwritten
+    ));
+  }
+
+  #[test]
+  fn inject_shadow_rename_suffixes_colliding_member() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read() internal view returns (uint256) { return stored + 1; }".into(),
+        ),
+        Some(strategy_options(ResolveConflictStrategy::Rename)),
+      )
+      .expect("inject fragment");
+
+    let unit = ast.source_unit().expect("loaded ast").clone();
+    assert_eq!(count_functions(&unit, "read"), 1, "original member is untouched");
+    assert_eq!(count_functions(&unit, "read_1"), 1, "renamed fragment member was inserted");
+    assert!(matches!(
+      ast.stitch_conflicts()[0].action,
+      StitchAction::Renamed { ref new_name } if new_name == "read_1"
+    ));
+  }
+
+  #[test]
+  fn inject_shadow_keep_both_allows_legitimate_overload() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read(uint256 offset) internal view returns (uint256) { return stored + offset; }"
+            .into(),
+        ),
+        Some(strategy_options(ResolveConflictStrategy::KeepBoth)),
+      )
+      .expect("inject fragment");
+
+    let unit = ast.source_unit().expect("loaded ast").clone();
+    assert_eq!(count_functions(&unit, "read"), 2, "distinct overload should be kept alongside");
+    assert!(matches!(
+      ast.stitch_conflicts()[0].action,
+      StitchAction::KeptBoth
+    ));
+  }
+
+  fn function_body_statements<'a>(unit: &'a Value, name: &str) -> &'a Vec<Value> {
+    unit["nodes"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .filter_map(|node| node["nodes"].as_array())
+      .flatten()
+      .find(|member| {
+        member["nodeType"].as_str() == Some("FunctionDefinition")
+          && member["name"].as_str() == Some(name)
+      })
+      .and_then(|member| member["body"]["statements"].as_array())
+      .expect("function body statements")
+  }
+
+  #[test]
+  fn inject_shadow_merge_wraps_target_body_by_default() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read() internal view returns (uint256) { stored + 1; }".into(),
+        ),
+        Some(merge_options(MergePlacement::Around)),
+      )
+      .expect("inject fragment");
+
+    let unit = ast.source_unit().expect("loaded ast").clone();
+    assert_eq!(count_functions(&unit, "read"), 1, "merge should not duplicate the member");
+
+    let statements = function_body_statements(&unit, "read");
+    assert_eq!(
+      statements.len(),
+      4,
+      "expected the fragment spliced in both before and after the target's own statement"
+    );
+    assert_eq!(
+      statements
+        .iter()
+        .filter(|statement| statement["nodeType"] == "Return")
+        .count(),
+      1,
+      "the target's own return should be untouched, not duplicated"
+    );
+  }
+
+  #[test]
+  fn inject_shadow_merge_before_only_prepends_fragment_statements() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read() internal view returns (uint256) { stored + 1; }".into(),
+        ),
+        Some(merge_options(MergePlacement::Before)),
+      )
+      .expect("inject fragment");
+
+    let unit = ast.source_unit().expect("loaded ast").clone();
+    let statements = function_body_statements(&unit, "read");
+    assert_eq!(
+      statements.len(),
+      2,
+      "expected only a single fragment statement prepended ahead of the target's own"
+    );
+    assert_eq!(
+      statements.last().unwrap()["nodeType"],
+      "Return",
+      "the target's own return should remain last"
+    );
+  }
+
+  #[test]
+  fn stitch_report_tracks_appended_and_replaced_members_across_injects() {
+    let mut ast = Ast::new(None).expect("create ast");
+    ast
+      .from_source(SourceTarget::Text(SAMPLE_CONTRACT.into()), None)
+      .expect("load source");
+    ast
+      .inject_shadow(FragmentTarget::Text(SHADOW_FRAGMENT.into()), None)
+      .expect("inject fragment");
+    ast
+      .inject_shadow(
+        FragmentTarget::Text(
+          "function read() internal view returns (uint256) { return stored + 1; }".into(),
+        ),
+        Some(strategy_options(ResolveConflictStrategy::Overwrite)),
+      )
+      .expect("inject fragment");
+
+    let report = ast.stitch_report();
+    assert_eq!(report.appended.len(), 1, "expose() should be reported as appended");
+    assert_eq!(report.replaced.len(), 1, "the overwritten read() should be reported as replaced");
+    assert!(report.appended.iter().any(|member| member.name == "expose"));
+    assert!(report.replaced.iter().any(|member| member.name == "read"));
+  }
 }
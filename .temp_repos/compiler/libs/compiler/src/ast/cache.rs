@@ -0,0 +1,148 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use serde_json::Value;
+
+use crate::compiler::output::CompileOutput;
+use crate::internal::config::AstConfig;
+
+/// Most fingerprints a [`CompileCache`] keeps around before evicting the oldest one. Small on
+/// purpose: callers typically bounce between a handful of AST shapes (pre/post instrumentation,
+/// an undo) rather than accumulating an unbounded edit history.
+const MAX_ENTRIES: usize = 8;
+
+/// Hashes everything that changes what `compile_output_internal` produces for a given AST: the
+/// AST itself (with object keys sorted so two structurally identical trees fingerprint the same
+/// regardless of `serde_json`'s map ordering) plus the resolved solc version and settings. An
+/// operation that leaves all three unchanged (e.g. re-exposing already-public functions) hits the
+/// cache instead of re-invoking solc.
+pub(crate) fn fingerprint(ast: &Value, config: &AstConfig) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  hash_value(ast, &mut hasher);
+  config.solc.version.to_string().hash(&mut hasher);
+  serde_json::to_string(&config.solc.settings)
+    .unwrap_or_default()
+    .hash(&mut hasher);
+  hasher.finish()
+}
+
+fn hash_value(value: &Value, hasher: &mut impl Hasher) {
+  match value {
+    Value::Null => 0u8.hash(hasher),
+    Value::Bool(flag) => {
+      1u8.hash(hasher);
+      flag.hash(hasher);
+    }
+    Value::Number(number) => {
+      2u8.hash(hasher);
+      number.to_string().hash(hasher);
+    }
+    Value::String(text) => {
+      3u8.hash(hasher);
+      text.hash(hasher);
+    }
+    Value::Array(items) => {
+      4u8.hash(hasher);
+      items.len().hash(hasher);
+      for item in items {
+        hash_value(item, hasher);
+      }
+    }
+    Value::Object(map) => {
+      5u8.hash(hasher);
+      let mut keys: Vec<&String> = map.keys().collect();
+      keys.sort();
+      keys.len().hash(hasher);
+      for key in keys {
+        key.hash(hasher);
+        hash_value(&map[key], hasher);
+      }
+    }
+  }
+}
+
+/// Bounded cache of compile output keyed by [`fingerprint`], replacing the single-slot
+/// `Option<CompileOutput>` `State` used to carry. Since the key already encodes the AST shape and
+/// solc configuration, a lookup miss simply means "never compiled this exact shape before" rather
+/// than "something changed since the last compile" -- so operations that leave the AST
+/// byte-for-byte identical (an undo, a no-op `expose_internal_functions`) hit the cache even
+/// across intervening edits that were since reverted.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CompileCache {
+  entries: HashMap<u64, CompileOutput>,
+  order: VecDeque<u64>,
+}
+
+impl CompileCache {
+  pub fn get(&self, key: u64) -> Option<&CompileOutput> {
+    self.entries.get(&key)
+  }
+
+  pub fn insert(&mut self, key: u64, output: CompileOutput) {
+    if !self.entries.contains_key(&key) {
+      self.order.push_back(key);
+      while self.order.len() > MAX_ENTRIES {
+        if let Some(oldest) = self.order.pop_front() {
+          self.entries.remove(&oldest);
+        }
+      }
+    }
+    self.entries.insert(key, output);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn config() -> AstConfig {
+    AstConfig::from_options(
+      &foundry_compilers::solc::SolcLanguage::Solidity,
+      &Default::default(),
+      None,
+    )
+    .expect("default ast config")
+  }
+
+  #[test]
+  fn fingerprint_is_stable_regardless_of_object_key_order() {
+    let config = config();
+    let a = json!({"nodeType": "SourceUnit", "id": 1});
+    let b = json!({"id": 1, "nodeType": "SourceUnit"});
+    assert_eq!(fingerprint(&a, &config), fingerprint(&b, &config));
+  }
+
+  #[test]
+  fn fingerprint_changes_with_ast_content() {
+    let config = config();
+    let a = json!({"nodeType": "SourceUnit", "id": 1});
+    let b = json!({"nodeType": "SourceUnit", "id": 2});
+    assert_ne!(fingerprint(&a, &config), fingerprint(&b, &config));
+  }
+
+  fn sample_output() -> CompileOutput {
+    CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: Default::default(),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: Default::default(),
+    }
+  }
+
+  #[test]
+  fn cache_evicts_oldest_entry_past_capacity() {
+    let mut cache = CompileCache::default();
+    for key in 0..(MAX_ENTRIES as u64 + 1) {
+      cache.insert(key, sample_output());
+    }
+    assert!(cache.get(0).is_none());
+    assert!(cache.get(MAX_ENTRIES as u64).is_some());
+  }
+}
@@ -1,8 +1,18 @@
 use foundry_compilers::solc::SolcLanguage;
 use log::{error, info};
+use rayon::prelude::*;
+use semver::Version;
 use serde_json::Value;
 
-use super::{instrumenter, orchestrator::AstOrchestrator, stitcher};
+use super::{
+  abi,
+  cache::{self, CompileCache},
+  coverage, emitter, error::AstError, instrumenter, orchestrator::AstOrchestrator,
+  passes::{self, AstPass},
+  pipeline_cache,
+  provenance::{self, InjectedSpan, SpanOrigin},
+  query, stitcher,
+};
 use crate::compiler::{
   core::SourceTarget as CompilerSourceTarget,
   output::{CompileOutput, SeverityLevel},
@@ -10,10 +20,12 @@ use crate::compiler::{
 };
 use crate::internal::{
   config::{
-    AstConfig, AstConfigOptions, CompilerConfigOptions, CompilerLanguage, ResolveConflictStrategy,
+    AstConfig, AstConfigOptions, CompilerConfigOptions, CompilerLanguage, MergePlacement,
+    ResolveConflictStrategy,
   },
   errors::{map_err_with_context, Error, Result},
   logging::{ensure_rust_logger, update_level},
+  project::default_cache_dir,
   settings::default_output_selection,
   solc,
 };
@@ -25,7 +37,40 @@ const LOG_TARGET: &str = "tevm::ast";
 pub struct State {
   pub config: AstConfig,
   pub ast: Option<Value>,
-  pub cached_compile_output: Option<CompileOutput>,
+  /// Compile output keyed by a fingerprint of the current AST and resolved solc config. See
+  /// [`cache::CompileCache`]. Unlike the single-slot cache this replaced, nothing here needs to be
+  /// explicitly cleared by `inject_*`/`from_source`/etc. -- a lookup simply misses once the AST
+  /// shape moves on, and an edit that lands back on a previously-seen shape (an undo, a redundant
+  /// `expose_internal_functions`) hits the cache again without recompiling.
+  pub compile_cache: CompileCache,
+  /// Provenance of every node inserted by `inject_shadow*`, re-resolved by [`validate`]. See
+  /// [`provenance`] (the free function below, not the module).
+  pub injected_spans: Vec<InjectedSpan>,
+  /// Every name collision an `inject_fragment*` call resolved under
+  /// [`ResolveConflictStrategy::Overwrite`]/[`ResolveConflictStrategy::Rename`]/
+  /// [`ResolveConflictStrategy::KeepBoth`]. See [`stitch_conflicts`] (the free function below).
+  pub stitch_conflicts: Vec<stitcher::StitchConflict>,
+  /// Structured audit of every `inject_fragment*` call so far: which members were appended,
+  /// replaced, or skipped, and how many ids were reassigned along the way. See [`stitch_report`]
+  /// (the free function below).
+  pub stitch_report: stitcher::StitchReport,
+  /// The original source text `ast` was parsed from, when [`from_source`] was called with
+  /// [`SourceTarget::Text`]. `None` when loaded from a pre-built [`SourceTarget::Ast`], since
+  /// there's then no source text to resolve a node's `src` field against. Used to attach a
+  /// [`error::SourceSpan`] to structural-failure errors (see
+  /// [`error::AstError::invalid_contract_structure_at`]).
+  pub source_text: Option<String>,
+  /// Text (or, for [`FragmentTarget::Ast`], the serialized AST) of every fragment applied via
+  /// [`inject_shadow`] since the current source was loaded, in application order. Cleared by
+  /// [`from_source`]/[`set_source_unit`] alongside `injected_spans`/`stitch_conflicts`. Fed into
+  /// [`pipeline_cache::fingerprint`] so two runs that apply the same fragments in the same order
+  /// hit the same cache entry.
+  pub fragment_texts: Vec<String>,
+  /// Whether [`expose_internal_variables`]/[`expose_internal_functions`] ran against the current
+  /// source. Reset alongside `fragment_texts`; part of the [`pipeline_cache`] fingerprint since
+  /// either pass changes what `validate` produces without adding a fragment.
+  pub exposed_variables: bool,
+  pub exposed_functions: bool,
 }
 
 #[derive(Clone)]
@@ -41,21 +86,24 @@ pub enum FragmentTarget {
 }
 
 pub fn init(options: Option<AstConfigOptions>) -> Result<State> {
-  let default_settings = AstOrchestrator::sanitize_settings(None).map_err(Error::from)?;
+  let default_version = solc::default_version()?;
+  let default_settings =
+    AstOrchestrator::sanitize_settings(None, &default_version).map_err(Error::from)?;
   let default_language = solc::default_language();
   let mut config = AstConfig::from_options(&default_language, &default_settings, options.as_ref())
     .map_err(Error::from)?;
   ensure_rust_logger(config.logging_level)?;
   info!(target: LOG_TARGET, "initialising AST state with language {:?}", default_language);
   config.solc.settings =
-    AstOrchestrator::sanitize_settings(Some(config.solc.settings.clone())).map_err(Error::from)?;
+    AstOrchestrator::sanitize_settings(Some(config.solc.settings.clone()), &config.solc.version)
+      .map_err(Error::from)?;
   if config.solc.language != SolcLanguage::Solidity {
     error!(target: LOG_TARGET, "Ast helpers only support solcLanguage \"Solidity\"");
     return Err(Error::new(
       "Ast helpers only support solcLanguage \"Solidity\".",
     ));
   }
-  solc::ensure_installed(&config.solc.version)?;
+  solc::ensure_installed_for(&config.solc)?;
   info!(
     target: LOG_TARGET,
     "AST ready (instrumented_contract={:?})",
@@ -65,7 +113,14 @@ pub fn init(options: Option<AstConfigOptions>) -> Result<State> {
   Ok(State {
     config,
     ast: None,
-    cached_compile_output: None,
+    compile_cache: CompileCache::default(),
+    injected_spans: Vec::new(),
+    stitch_conflicts: Vec::new(),
+    stitch_report: stitcher::StitchReport::default(),
+    source_text: None,
+    fragment_texts: Vec::new(),
+    exposed_variables: false,
+    exposed_functions: false,
   })
 }
 
@@ -74,7 +129,12 @@ pub fn from_source(
   target: SourceTarget,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
-  state.cached_compile_output = None;
+  state.injected_spans.clear();
+  state.stitch_conflicts.clear();
+  state.stitch_report = stitcher::StitchReport::default();
+  state.fragment_texts.clear();
+  state.exposed_variables = false;
+  state.exposed_functions = false;
   match target {
     SourceTarget::Text(source) => {
       info!(
@@ -112,7 +172,6 @@ pub fn inject_shadow(
       inject_fragment_ast(state, unit, overrides)?;
     }
   }
-  state.cached_compile_output = None;
   info!(target: LOG_TARGET, "AST fragment injected");
   Ok(())
 }
@@ -133,23 +192,36 @@ pub fn inject_shadow_at_edges(
   );
 
   let config = resolve_config(state, overrides)?;
-  let solc = solc::ensure_installed(&config.solc.version)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
 
   let idx = {
     let target_ast = target_ast(state)?;
     find_contract_index(state, target_ast, contract.as_deref())?
   };
 
+  let source = state.source_text.clone();
   let unit = target_ast_mut(state)?;
-  instrumenter::inject_edges(
+  let injected = instrumenter::inject_edges(
     unit,
     idx,
     selector,
     before,
     after,
+    config.reject_inline_assembly,
     &solc,
     &config.solc.settings,
+    source.as_deref(),
   )?;
+  provenance::record(
+    &mut state.injected_spans,
+    SpanOrigin::EdgeBefore(selector.to_string()),
+    &injected.before,
+  );
+  provenance::record(
+    &mut state.injected_spans,
+    SpanOrigin::EdgeAfter(selector.to_string()),
+    &injected.after,
+  );
 
   info!(
     target: LOG_TARGET,
@@ -158,10 +230,134 @@ pub fn inject_shadow_at_edges(
     contract
   );
 
-  state.cached_compile_output = None;
   Ok(())
 }
 
+/// Instruments a function the same way as [`inject_shadow_at_edges`], but factors the
+/// before/after statements into a single reusable `modifier` rather than duplicating them at
+/// every exit point. Unlike edge injection, statements placed after `_;` only run on a normal
+/// return—a `revert` inside the function body skips them.
+pub fn inject_shadow_as_modifier(
+  state: &mut State,
+  selector: &str,
+  before: &[String],
+  after: &[String],
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let contract = contract_override(state, overrides).map(|name| name.to_owned());
+  info!(
+    target: LOG_TARGET,
+    "injecting modifier instrumentation (selector={}, contract={:?})",
+    selector,
+    contract
+  );
+
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
+
+  let idx = {
+    let target_ast = target_ast(state)?;
+    find_contract_index(state, target_ast, contract.as_deref())?
+  };
+
+  let source = state.source_text.clone();
+  let unit = target_ast_mut(state)?;
+  let injected = instrumenter::inject_modifier(
+    unit,
+    idx,
+    selector,
+    before,
+    after,
+    config.reject_inline_assembly,
+    &solc,
+    &config.solc.settings,
+    source.as_deref(),
+  )?;
+  provenance::record(
+    &mut state.injected_spans,
+    SpanOrigin::EdgeBefore(selector.to_string()),
+    &injected.before,
+  );
+  provenance::record(
+    &mut state.injected_spans,
+    SpanOrigin::EdgeAfter(selector.to_string()),
+    &injected.after,
+  );
+
+  info!(
+    target: LOG_TARGET,
+    "modifier instrumentation applied (selector={}, contract={:?})",
+    selector,
+    contract
+  );
+
+  Ok(())
+}
+
+/// Derives the canonical ABI signature and 4-byte calldata selector of the function `selector`
+/// resolves to, using the same locator syntax (a bare name, a full `name(types)` signature, a
+/// `0x`-prefixed selector, or `fallback`/`receive`/`constructor`) as [`inject_shadow_at_edges`].
+/// See [`abi::function_selector`].
+pub fn function_selector(
+  state: &State,
+  selector: &str,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<abi::FunctionSelector> {
+  let contract_name = contract_override(state, overrides).map(|name| name.to_owned());
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
+
+  let unit = target_ast(state)?;
+  let idx = find_contract_index(state, unit, contract_name.as_deref())?;
+  let contract = unit
+    .get("nodes")
+    .and_then(|value| value.as_array())
+    .and_then(|nodes| nodes.get(idx))
+    .ok_or_else(|| Error::new("Invalid contract index"))?;
+
+  let source = state.source_text.clone();
+  let selector_kind = instrumenter::parse_selector(selector, &solc, &config.solc.settings)?;
+  let function = instrumenter::resolve_function(unit, contract, &selector_kind, source.as_deref())?;
+
+  abi::function_selector(unit, function, source.as_deref()).map_err(Error::from)
+}
+
+/// Instruments every function in the target contract with coverage probes, reusing the same
+/// edge-injection machinery as [`inject_shadow_at_edges`]. See [`coverage::instrument`] for the
+/// sites covered and [`coverage::CoverageProbeKind`] for how probes report.
+pub fn instrument_coverage(
+  state: &mut State,
+  mode: coverage::CoverageProbeKind,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<coverage::CoverageMap> {
+  let contract = contract_override(state, overrides).map(|name| name.to_owned());
+  info!(
+    target: LOG_TARGET,
+    "instrumenting coverage (mode={}, contract={:?})",
+    mode,
+    contract
+  );
+
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
+
+  let idx = {
+    let target_ast = target_ast(state)?;
+    find_contract_index(state, target_ast, contract.as_deref())?
+  };
+
+  let unit = target_ast_mut(state)?;
+  let map = coverage::instrument(unit, idx, mode, &solc, &config.solc.settings)?;
+
+  info!(
+    target: LOG_TARGET,
+    "coverage instrumentation applied (mode={}, probes={})",
+    mode,
+    map.probes.len()
+  );
+  Ok(map)
+}
+
 pub fn expose_internal_variables(
   state: &mut State,
   overrides: Option<&AstConfigOptions>,
@@ -172,8 +368,8 @@ pub fn expose_internal_variables(
     "exposing internal variables (contract={})",
     contract
   );
-  expose_variables_internal(state, overrides)?;
-  state.cached_compile_output = None;
+  run_passes(state, &mut [Box::new(passes::ExposeVariablesPass)], overrides)?;
+  state.exposed_variables = true;
   info!(target: LOG_TARGET, "internal variables exposed");
   Ok(())
 }
@@ -188,8 +384,8 @@ pub fn expose_internal_functions(
     "exposing internal functions (contract={})",
     contract
   );
-  expose_functions_internal(state, overrides)?;
-  state.cached_compile_output = None;
+  run_passes(state, &mut [Box::new(passes::ExposeFunctionsPass)], overrides)?;
+  state.exposed_functions = true;
   info!(target: LOG_TARGET, "internal functions exposed");
   Ok(())
 }
@@ -202,6 +398,143 @@ pub fn source_unit_mut(state: &mut State) -> Option<&mut Value> {
   state.ast.as_mut()
 }
 
+/// Provenance of every node inserted so far by `inject_shadow`/`inject_shadow_at_edges`/
+/// `inject_shadow_as_modifier`, so a caller can tell which parts of the final compiled artifact
+/// are synthetic. Spans are re-resolved against the current AST by [`validate`]; between
+/// `inject_*` calls and the next `validate()` their `src` reflects where they landed at insertion
+/// time.
+pub fn provenance(state: &State) -> &[InjectedSpan] {
+  &state.injected_spans
+}
+
+/// Maps every node of the current (instrumented) source unit back to its counterpart in the
+/// source the caller originally loaded via `from_source`, via [`provenance::source_map`]. See
+/// [`provenance::SourceMapRegion`] for what each entry carries.
+pub fn source_map(state: &State) -> Result<Vec<provenance::SourceMapRegion>> {
+  let unit = source_unit(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+  Ok(provenance::source_map(&state.injected_spans, unit))
+}
+
+/// Name collisions resolved so far by `inject_fragment*` under [`ResolveConflictStrategy::Overwrite`]/
+/// [`ResolveConflictStrategy::Rename`]/[`ResolveConflictStrategy::KeepBoth`]. Empty when the
+/// configured strategy is [`ResolveConflictStrategy::Safe`] or [`ResolveConflictStrategy::Replace`],
+/// since neither of those can produce a [`stitcher::StitchConflict`].
+pub fn stitch_conflicts(state: &State) -> &[stitcher::StitchConflict] {
+  &state.stitch_conflicts
+}
+
+/// Structured audit of every `inject_fragment*` call so far: which members were appended,
+/// replaced, or skipped, and how many ids were reassigned along the way. Unlike
+/// [`stitch_conflicts`], this covers every [`ResolveConflictStrategy`], since appends and
+/// replacements happen under all of them -- only collisions are strategy-specific.
+pub fn stitch_report(state: &State) -> &stitcher::StitchReport {
+  &state.stitch_report
+}
+
+/// Collects every node in the current source unit matching `selector`. See [`query::query`].
+pub fn query_nodes(state: &State, selector: &query::NodeSelector) -> Result<Vec<query::QueryMatch>> {
+  let unit = source_unit(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+  Ok(query::query(unit, selector))
+}
+
+/// Runs `callback` over every node matching `selector`, splicing back any replacement the
+/// callback returns. See [`query::visit`].
+pub fn visit_nodes(
+  state: &mut State,
+  selector: &query::NodeSelector,
+  callback: impl FnMut(&query::QueryMatch) -> Result<query::VisitAction, AstError>,
+) -> Result<usize> {
+  let unit = source_unit_mut(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+  query::visit(unit, selector, callback).map_err(Error::from)
+}
+
+/// Pretty-prints the current source unit back into Solidity source, so instrumentation applied
+/// through [`inject_shadow`]/[`expose_internal_functions`]/etc. can be inspected or exported
+/// without recompiling. `overrides` only affects config resolution (e.g. `logging_level`); the
+/// rendered text is derived purely from the AST. See [`emitter::emit_source_unit`] for the node
+/// types this currently understands.
+pub fn emit_source(state: &State, overrides: Option<&AstConfigOptions>) -> Result<String> {
+  resolve_config(state, overrides)?;
+  let unit = target_ast(state)?;
+  emitter::emit_source_unit(unit).map_err(Error::from)
+}
+
+/// Keys a scoped edit to a single node, either by its stable solc `id` or by the RFC 6901 pointer
+/// path [`query_nodes`]/[`query::find_path_by_id`] report.
+#[derive(Clone, Debug)]
+pub enum NodeLocator {
+  Id(i64),
+  Path(String),
+}
+
+fn resolve_locator(unit: &Value, locator: &NodeLocator) -> Result<String> {
+  match locator {
+    NodeLocator::Path(path) => Ok(path.clone()),
+    NodeLocator::Id(id) => query::find_path_by_id(unit, *id)
+      .ok_or_else(|| Error::new(format!("No node with id `{id}` found in source unit"))),
+  }
+}
+
+/// Replaces the whole source unit, re-validating that the configured contract still exists before
+/// committing it -- the same structural check [`from_source`]'s `SourceTarget::Ast` path runs --
+/// and invalidates any cached compile output so the next `validate()`/`compile()` sees the edit.
+pub fn set_source_unit(
+  state: &mut State,
+  unit: Value,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  load_source_ast(state, unit, overrides)?;
+  state.injected_spans.clear();
+  state.stitch_conflicts.clear();
+  state.stitch_report = stitcher::StitchReport::default();
+  state.fragment_texts.clear();
+  state.exposed_variables = false;
+  state.exposed_functions = false;
+  Ok(())
+}
+
+/// Replaces a single node, keyed by [`NodeLocator`]. Rejects the edit if the replacement's
+/// `nodeType` doesn't match the node currently at that location, so a caller can't accidentally
+/// turn a `FunctionDefinition` into a `VariableDeclaration` by splicing the wrong object in.
+pub fn set_node_at(state: &mut State, locator: &NodeLocator, node: Value) -> Result<()> {
+  let unit = source_unit(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+  let path = resolve_locator(unit, locator)?;
+
+  let unit = source_unit_mut(state).expect("presence checked above");
+  let slot = unit
+    .pointer_mut(&path)
+    .ok_or_else(|| Error::new(format!("No node at path `{path}`")))?;
+
+  let existing_type = slot.get("nodeType").and_then(Value::as_str).map(str::to_owned);
+  let incoming_type = node.get("nodeType").and_then(Value::as_str).map(str::to_owned);
+  if existing_type != incoming_type {
+    return Err(Error::new(format!(
+      "Replacement nodeType `{}` does not match existing nodeType `{}` at `{}`",
+      incoming_type.as_deref().unwrap_or("<none>"),
+      existing_type.as_deref().unwrap_or("<none>"),
+      path
+    )));
+  }
+
+  *slot = node;
+  Ok(())
+}
+
+/// Removes a single node, keyed by [`NodeLocator`]. See [`query::remove_at_path`].
+pub fn remove_node(state: &mut State, locator: &NodeLocator) -> Result<()> {
+  let unit = source_unit(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+  let path = resolve_locator(unit, locator)?;
+
+  let unit = source_unit_mut(state).expect("presence checked above");
+  query::remove_at_path(unit, &path).map_err(Error::from)?;
+  Ok(())
+}
+
 fn contract_override<'a>(
   state: &'a State,
   overrides: Option<&'a AstConfigOptions>,
@@ -219,7 +552,7 @@ fn resolve_config(state: &State, overrides: Option<&AstConfigOptions>) -> Result
     ));
   }
   config.solc.settings = map_err_with_context(
-    AstOrchestrator::sanitize_settings(Some(config.solc.settings.clone())),
+    AstOrchestrator::sanitize_settings(Some(config.solc.settings.clone()), &config.solc.version),
     "Failed to sanitize compiler settings",
   )?;
   update_level(config.logging_level);
@@ -261,23 +594,31 @@ fn inject_fragment_contract(
   fragment_contract: Value,
   overrides: Option<&AstConfigOptions>,
   strategy: ResolveConflictStrategy,
+  merge_placement: MergePlacement,
 ) -> Result<()> {
   let contract_name = contract_override(state, overrides).map(|name| name.to_owned());
   let contract_idx = {
     let target_ast = target_ast(state)?;
     find_contract_index(state, target_ast, contract_name.as_deref())?
   };
+  let source = state.source_text.clone();
 
   let target_ast = target_ast_mut(state)?;
-  map_err_with_context(
+  let result = map_err_with_context(
     AstOrchestrator::stitch_fragment_into_contract(
       target_ast,
       contract_idx,
       &fragment_contract,
       strategy,
+      merge_placement,
+      source.as_deref(),
     ),
     "Failed to stitch AST nodes",
-  )
+  )?;
+  provenance::record(&mut state.injected_spans, SpanOrigin::ShadowFragment, &result.nodes);
+  state.stitch_conflicts.extend(result.conflicts);
+  state.stitch_report.merge(result.report);
+  Ok(())
 }
 
 fn contract_indices(
@@ -341,8 +682,14 @@ where
   Ok(())
 }
 
-fn expose_variables_internal(
+/// Drives [`mutate_contracts`] once and dispatches every direct member of the targeted
+/// contract(s) to every pass in `passes`, in order. The extension point
+/// [`expose_internal_variables`]/[`expose_internal_functions`] are themselves built on: a caller
+/// that needs a rewrite beyond those two (stripping `virtual`, renaming state variables, adding
+/// getters, ...) implements [`AstPass`] instead of forking `mutate_contracts`.
+pub fn run_passes(
   state: &mut State,
+  passes: &mut [Box<dyn AstPass>],
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   mutate_contracts(state, overrides, |contract| {
@@ -351,76 +698,71 @@ fn expose_variables_internal(
       .and_then(|value| value.as_array_mut())
     {
       for member in members {
-        if member
-          .get("nodeType")
-          .and_then(|value| value.as_str())
-          .map(|node_type| node_type == "VariableDeclaration")
-          .unwrap_or(false)
-        {
-          if let Some(object) = member.as_object_mut() {
-            match object.get_mut("visibility") {
-              Some(value) => {
-                if !matches!(value.as_str(), Some("public")) {
-                  *value = Value::String("public".to_string());
-                }
-              }
-              None => {
-                object.insert(
-                  "visibility".to_string(),
-                  Value::String("public".to_string()),
-                );
-              }
-            }
-          }
+        for pass in passes.iter_mut() {
+          pass.visit_contract_member(member);
         }
       }
     }
   })
 }
 
-fn expose_functions_internal(
-  state: &mut State,
-  overrides: Option<&AstConfigOptions>,
-) -> Result<()> {
-  mutate_contracts(state, overrides, |contract| {
-    if let Some(members) = contract
-      .get_mut("nodes")
-      .and_then(|value| value.as_array_mut())
-    {
-      for member in members {
-        if member
-          .get("nodeType")
-          .and_then(|value| value.as_str())
-          .map(|node_type| node_type == "FunctionDefinition")
-          .unwrap_or(false)
-        {
-          if let Some(object) = member.as_object_mut() {
-            match object.get_mut("visibility") {
-              Some(value) => {
-                if !matches!(value.as_str(), Some("public")) {
-                  *value = Value::String("public".to_string());
-                }
-              }
-              None => {
-                object.insert(
-                  "visibility".to_string(),
-                  Value::String("public".to_string()),
-                );
-              }
-            }
-          }
-        }
-      }
-    }
-  })
+/// Validation's inputs, under [`pipeline_cache::fingerprint`], when caching is worth attempting
+/// (no per-call `overrides`, caching enabled, and a source text to hash -- a pre-built
+/// [`SourceTarget::Ast`] has no text, so it always falls through to `compile_output_internal`).
+fn pipeline_cache_key(state: &State, config: &AstConfig, overrides: Option<&AstConfigOptions>) -> Option<String> {
+  if overrides.is_some() || !config.cache_enabled {
+    return None;
+  }
+  let source = state.source_text.as_deref()?;
+  // Edge, modifier and coverage instrumentation, passes and direct node edits change the AST
+  // without adding to `fragment_texts`, so the current AST is hashed in as a final entry.
+  let mut fragments = state.fragment_texts.clone();
+  fragments.push(serde_json::to_string(state.ast.as_ref()?).ok()?);
+  Some(pipeline_cache::fingerprint(&pipeline_cache::PipelineInputs {
+    source,
+    fragments: &fragments,
+    strategy: config.resolve_conflict_strategy,
+    merge_placement: config.merge_placement,
+    exposed_variables: state.exposed_variables,
+    exposed_functions: state.exposed_functions,
+  }))
 }
 
+/// Compiles the current AST and, on success, refreshes `state.ast` from the instrumented output
+/// solc returns -- or, on a [`pipeline_cache_key`] hit, replays a previous run's outcome without
+/// invoking solc at all. A cached validation failure still returns `Err` with the original
+/// messages, so a caller retrying the exact same pipeline sees the exact same error every time.
 pub fn validate(state: &mut State, overrides: Option<&AstConfigOptions>) -> Result<()> {
   info!(
     target: LOG_TARGET,
     "validating AST (current_contract={:?})",
     state.config.instrumented_contract()
   );
+
+  let config = resolve_config(state, overrides)?;
+  let cache_key = pipeline_cache_key(state, &config, overrides);
+  let cache_dir = pipeline_cache::cache_dir(&default_cache_dir());
+
+  if let Some(key) = &cache_key {
+    if let Some(cached) = pipeline_cache::read(&cache_dir, key) {
+      if cached.validation_errors.is_empty() {
+        state.ast = Some(cached.source_unit);
+        provenance::reresolve(&mut state.injected_spans, state.ast.as_ref().expect("just set"));
+        info!(target: LOG_TARGET, "AST validation succeeded (pipeline cache hit)");
+        return Ok(());
+      }
+      error!(
+        target: LOG_TARGET,
+        "AST validation failed with {} error(s) (pipeline cache hit)",
+        cached.validation_errors.len()
+      );
+      return Err(Error::new(format!(
+        "AST validation failed:\n{}",
+        cached.validation_errors.join("\n")
+      )));
+    }
+  }
+
   let output = compile_output_internal(state, overrides)?;
 
   let mut messages = Vec::new();
@@ -440,6 +782,16 @@ pub fn validate(state: &mut State, overrides: Option<&AstConfigOptions>) -> Resu
       "AST validation failed with {} error(s)",
       messages.len()
     );
+    if let Some(key) = &cache_key {
+      let _ = pipeline_cache::write(
+        &cache_dir,
+        key,
+        &pipeline_cache::CachedPipelineResult {
+          source_unit: Value::Null,
+          validation_errors: messages.clone(),
+        },
+      );
+    }
     return Err(Error::new(format!(
       "AST validation failed:\n{}",
       messages.join("\n")
@@ -455,6 +807,19 @@ pub fn validate(state: &mut State, overrides: Option<&AstConfigOptions>) -> Resu
     .ok_or_else(|| Error::new("Validation succeeded but AST output was missing"))?;
 
   state.ast = Some(next_ast_value);
+  provenance::reresolve(&mut state.injected_spans, state.ast.as_ref().expect("just set"));
+
+  if let Some(key) = &cache_key {
+    let _ = pipeline_cache::write(
+      &cache_dir,
+      key,
+      &pipeline_cache::CachedPipelineResult {
+        source_unit: state.ast.clone().expect("just set"),
+        validation_errors: Vec::new(),
+      },
+    );
+  }
+
   info!(target: LOG_TARGET, "AST validation succeeded");
   Ok(())
 }
@@ -467,18 +832,24 @@ fn compile_output_internal(
   state: &mut State,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<CompileOutput> {
+  let config = resolve_config(state, overrides)?;
   let use_cache = overrides.is_none();
-  if use_cache {
-    if let Some(cached) = &state.cached_compile_output {
+  let key = if use_cache {
+    Some(cache::fingerprint(target_ast(state)?, &config))
+  } else {
+    None
+  };
+
+  if let Some(key) = key {
+    if let Some(cached) = state.compile_cache.get(key) {
       return Ok(cached.clone());
     }
   }
 
-  let config = resolve_config(state, overrides)?;
   let output = run_compiler(state, &config)?;
 
-  if use_cache {
-    state.cached_compile_output = Some(output.clone());
+  if let Some(key) = key {
+    state.compile_cache.insert(key, output.clone());
   }
 
   Ok(output)
@@ -512,23 +883,73 @@ fn compiler_options_from_ast(config: &AstConfig) -> CompilerConfigOptions {
   options
 }
 
+/// Compiles the current (cached) AST against every version in `versions`, in parallel, pinning
+/// each compile to that version instead of `config.solc.version`. Unlike [`compile_output`], which
+/// answers "is this AST valid against the configured compiler", this answers "which of these
+/// compilers accept it" in one call -- useful for asserting a compatibility range across shadow
+/// code before publishing it. Each version is looked up with [`solc::ensure_installed`] (never
+/// downloaded, same guarantee the rest of the AST module relies on), so a version missing from the
+/// svm install directory fails the whole matrix rather than silently skipping it.
+pub fn validate_matrix(
+  state: &mut State,
+  versions: &[Version],
+  overrides: Option<&AstConfigOptions>,
+) -> Result<Vec<(Version, CompileOutput)>> {
+  let config = resolve_config(state, overrides)?;
+  let ast = state
+    .ast
+    .clone()
+    .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))?;
+
+  versions
+    .par_iter()
+    .map(|version| {
+      solc::ensure_installed(version)?;
+      let mut options = compiler_options_from_ast(&config);
+      options.solc.version = Some(version.clone());
+      let compiler = Compiler::new(Some(options))?;
+      let output = compiler.compile_source(CompilerSourceTarget::Ast(ast.clone()), None)?;
+      Ok((version.clone(), output))
+    })
+    .collect()
+}
+
+/// Narrows a [`validate_matrix`] result down to the versions that produced at least one
+/// `SeverityLevel::Error` diagnostic (or, when `CompileOutput::deny_warnings` is set for that
+/// compile, a warning), so a caller can assert a compatibility range in one check rather than
+/// re-walking every `CompileOutput`'s `errors` by hand.
+pub fn incompatible_versions(results: &[(Version, CompileOutput)]) -> Vec<Version> {
+  results
+    .iter()
+    .filter(|(_, output)| output.has_compiler_errors())
+    .map(|(version, _)| version.clone())
+    .collect()
+}
+
 fn load_source_text(
   state: &mut State,
   source: &str,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
-  let solc = solc::ensure_installed(&config.solc.version)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
 
   let mut settings = config.solc.settings.clone();
   settings.stop_after = None;
 
   let ast = map_err_with_context(
-    AstOrchestrator::parse_source_unit(source, VIRTUAL_SOURCE_PATH, &solc, &settings),
+    AstOrchestrator::parse_source_unit(
+      source,
+      VIRTUAL_SOURCE_PATH,
+      &solc,
+      &settings,
+      config.cache_enabled,
+    ),
     "Failed to parse target source",
   )?;
 
   state.ast = Some(ast);
+  state.source_text = Some(source.to_string());
   Ok(())
 }
 
@@ -538,7 +959,7 @@ fn load_source_ast(
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
-  solc::ensure_installed(&config.solc.version)?;
+  solc::ensure_installed_for(&config.solc)?;
 
   map_err_with_context(
     stitcher::find_instrumented_contract_index(&target_ast, contract_override(state, overrides)),
@@ -546,6 +967,7 @@ fn load_source_ast(
   )?;
 
   state.ast = Some(target_ast);
+  state.source_text = None;
   Ok(())
 }
 
@@ -555,7 +977,7 @@ fn inject_fragment_string(
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
-  let solc = solc::ensure_installed(&config.solc.version)?;
+  let solc = solc::ensure_installed_for(&config.solc)?;
 
   let strategy = config.resolve_conflict_strategy;
   let fragment_contract = map_err_with_context(
@@ -563,7 +985,8 @@ fn inject_fragment_string(
     "Failed to parse AST fragment",
   )?;
 
-  inject_fragment_contract(state, fragment_contract, overrides, strategy)
+  state.fragment_texts.push(fragment_source.to_string());
+  inject_fragment_contract(state, fragment_contract, overrides, strategy, config.merge_placement)
 }
 
 fn inject_fragment_ast(
@@ -572,7 +995,7 @@ fn inject_fragment_ast(
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
-  solc::ensure_installed(&config.solc.version)?;
+  solc::ensure_installed_for(&config.solc)?;
 
   let strategy = config.resolve_conflict_strategy;
   let fragment_contract = map_err_with_context(
@@ -580,5 +1003,7 @@ fn inject_fragment_ast(
     "Failed to locate fragment contract",
   )?;
 
-  inject_fragment_contract(state, fragment_contract, overrides, strategy)
+  let fragment_text = serde_json::to_string(&fragment_ast).unwrap_or_default();
+  state.fragment_texts.push(fragment_text);
+  inject_fragment_contract(state, fragment_contract, overrides, strategy, config.merge_placement)
 }
@@ -0,0 +1,509 @@
+use foundry_compilers::artifacts::Settings;
+use foundry_compilers::solc::Solc;
+use serde_json::Value;
+
+use super::{error::AstError, instrumenter, orchestrator::AstOrchestrator, utils};
+
+/// How a coverage probe reports that it executed. ABI-preserving like [`instrumenter::inject_edges`]:
+/// neither mode changes any existing function's signature, they only append new contract
+/// members.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoverageProbeKind {
+  /// Probes increment a per-id slot in a generated fixed-size `uint256[N]` storage array (one slot
+  /// per probe), exposed through a
+  /// generated `coverageHits()` view getter.
+  #[default]
+  Counter,
+  /// Probes `emit` a generated `__TevmCoverageHit(uint256 probeId)` event instead, for tooling
+  /// that prefers to watch hits via logs/traces rather than polling a getter.
+  Event,
+}
+
+impl std::str::FromStr for CoverageProbeKind {
+  type Err = AstError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "counter" => Ok(Self::Counter),
+      "event" => Ok(Self::Event),
+      other => Err(AstError::InvalidContractStructure(format!(
+        "Unknown coverage probe kind `{other}`"
+      ))),
+    }
+  }
+}
+
+impl std::fmt::Display for CoverageProbeKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let value = match self {
+      Self::Counter => "counter",
+      Self::Event => "event",
+    };
+    f.write_str(value)
+  }
+}
+
+/// The kind of source construct a probe was injected at. Deliberately scoped to the sites named
+/// in the request this implements -- function entry, `if`/`else` arms, loop bodies, and
+/// `require`/`assert` calls -- rather than every statement, so a coverage report reads as
+/// "which decisions ran" rather than a line-by-line trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoverageSiteKind {
+  FunctionEntry,
+  BranchTrue,
+  BranchFalse,
+  LoopBody,
+  Require,
+  Assert,
+}
+
+impl std::fmt::Display for CoverageSiteKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let value = match self {
+      Self::FunctionEntry => "functionEntry",
+      Self::BranchTrue => "branchTrue",
+      Self::BranchFalse => "branchFalse",
+      Self::LoopBody => "loopBody",
+      Self::Require => "require",
+      Self::Assert => "assert",
+    };
+    f.write_str(value)
+  }
+}
+
+/// One injected coverage probe, associating its id with the source span it watches. `id` is the
+/// index into the generated counter array (or the `probeId` emitted in event mode), and is stable
+/// across recompiles of the instrumented unit since it's baked into the AST as a literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoverageProbe {
+  pub id: u32,
+  pub contract: String,
+  pub function: String,
+  pub kind: CoverageSiteKind,
+  /// The raw solc `"start:length:fileIndex"` triple of the instrumented statement/block.
+  pub src: String,
+}
+
+/// The full set of probes injected by one [`instrument`] call, plus the mode they report through.
+/// Carries no references into the instrumented AST, so it survives recompilation unchanged --
+/// callers persist it alongside the compiled bytecode to resolve hits back to source.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageMap {
+  pub mode: CoverageProbeKind,
+  pub probes: Vec<CoverageProbe>,
+}
+
+struct Context<'a> {
+  mode: CoverageProbeKind,
+  contract: String,
+  function: String,
+  next_probe_id: &'a mut u32,
+  next_node_id: &'a mut i64,
+  solc: &'a Solc,
+  settings: &'a Settings,
+  probes: &'a mut Vec<CoverageProbe>,
+}
+
+impl Context<'_> {
+  /// Parses and splices in a single probe statement (an array-slot increment or an event emit,
+  /// depending on [`Self::mode`]), reusing the same snippet-parsing pipeline
+  /// [`instrumenter::inject_edges`] uses for its `before`/`after` statements so the probe gets a
+  /// properly-formed statement node without hand-rolling one.
+  fn build_probe(&mut self, kind: CoverageSiteKind, src: &str) -> Result<Value, AstError> {
+    let id = *self.next_probe_id;
+    *self.next_probe_id += 1;
+
+    let snippet = match self.mode {
+      CoverageProbeKind::Counter => format!("__tevmCoverageHits[{id}] += 1;"),
+      CoverageProbeKind::Event => format!("emit __TevmCoverageHit({id});"),
+    };
+    let parsed = instrumenter::parse_statement_snippet(&snippet, self.solc, self.settings)
+      .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+    let statement = parsed
+      .into_iter()
+      .next()
+      .ok_or_else(|| AstError::ParseFailed("Coverage probe snippet produced no statement".to_string()))?;
+
+    self.probes.push(CoverageProbe {
+      id,
+      contract: self.contract.clone(),
+      function: self.function.clone(),
+      kind,
+      src: src.to_string(),
+    });
+
+    Ok(utils::clone_with_new_ids(&statement, self.next_node_id))
+  }
+}
+
+/// Walks every function in the contract at `contract_idx`, injecting a coverage probe at each
+/// site named in [`CoverageSiteKind`], and appends the generated counter array/getter (or event
+/// declaration) the probes report through. Returns the probe map describing what was injected, or
+/// an empty map with no contract changes if the contract has no instrumentable sites.
+pub fn instrument(
+  unit: &mut Value,
+  contract_idx: usize,
+  mode: CoverageProbeKind,
+  solc: &Solc,
+  settings: &Settings,
+) -> Result<CoverageMap, AstError> {
+  let mut next_id = utils::max_id(unit);
+  let mut next_probe_id: u32 = 0;
+  let mut probes = Vec::new();
+
+  let contract = instrumenter::contract_mut_at(unit, contract_idx)
+    .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+  let contract_name = node_name(contract).unwrap_or_default().to_string();
+  let member_indices = function_indices(contract);
+
+  for idx in member_indices {
+    let contract = instrumenter::contract_mut_at(unit, contract_idx)
+      .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+    let members = contract
+      .get_mut("nodes")
+      .and_then(Value::as_array_mut)
+      .ok_or_else(|| AstError::InvalidContractStructure("Contract has no members to instrument".to_string()))?;
+    let function = members
+      .get_mut(idx)
+      .ok_or_else(|| AstError::InvalidContractStructure("Invalid function index after resolution".to_string()))?;
+
+    let function_name = function_label(function);
+    let function_src = function.get("src").and_then(Value::as_str).unwrap_or("0:0:0").to_string();
+
+    let mut ctx = Context {
+      mode,
+      contract: contract_name.clone(),
+      function: function_name,
+      next_probe_id: &mut next_probe_id,
+      next_node_id: &mut next_id,
+      solc,
+      settings,
+      probes: &mut probes,
+    };
+
+    let body = match function.get_mut("body") {
+      Some(body) if !body.is_null() => body,
+      _ => continue,
+    };
+    let statements = body
+      .get_mut("statements")
+      .and_then(Value::as_array_mut)
+      .ok_or_else(|| AstError::InvalidContractStructure("Function body missing statements array".to_string()))?;
+
+    let entry_probe = ctx.build_probe(CoverageSiteKind::FunctionEntry, &function_src)?;
+    statements.insert(0, entry_probe);
+    instrument_statements(statements, &mut ctx)?;
+  }
+
+  if probes.is_empty() {
+    return Ok(CoverageMap { mode, probes });
+  }
+
+  append_reporting_members(unit, contract_idx, mode, probes.len(), solc, settings, &mut next_id)?;
+
+  Ok(CoverageMap { mode, probes })
+}
+
+/// Walks one statement list, recursing into blocks/branches/loops/try-clauses and injecting
+/// probes at the sites [`CoverageSiteKind`] covers. Mirrors the index-based in-place mutation
+/// shape of [`instrumenter`]'s own statement walkers.
+fn instrument_statements(statements: &mut Vec<Value>, ctx: &mut Context) -> Result<(), AstError> {
+  let mut idx = 0;
+  while idx < statements.len() {
+    match node_type(&statements[idx]) {
+      Some("Block") | Some("UncheckedBlock") => {
+        let inner = statements[idx]
+          .get_mut("statements")
+          .and_then(Value::as_array_mut)
+          .ok_or_else(|| AstError::InvalidContractStructure("Block missing statements array".to_string()))?;
+        instrument_statements(inner, ctx)?;
+        idx += 1;
+      }
+      Some("IfStatement") => {
+        if let Some(true_body) = statements[idx].get_mut("trueBody") {
+          instrument_branch(true_body, CoverageSiteKind::BranchTrue, ctx)?;
+        }
+        let has_false_body = statements[idx]
+          .get("falseBody")
+          .map(|value| !value.is_null())
+          .unwrap_or(false);
+        if has_false_body {
+          if let Some(false_body) = statements[idx].get_mut("falseBody") {
+            instrument_branch(false_body, CoverageSiteKind::BranchFalse, ctx)?;
+          }
+        }
+        idx += 1;
+      }
+      Some("WhileStatement") | Some("ForStatement") | Some("DoWhileStatement") => {
+        if let Some(body) = statements[idx].get_mut("body") {
+          instrument_branch(body, CoverageSiteKind::LoopBody, ctx)?;
+        }
+        idx += 1;
+      }
+      Some("TryStatement") => {
+        if let Some(clauses) = statements[idx].get_mut("clauses").and_then(Value::as_array_mut) {
+          for clause in clauses {
+            if let Some(block) = clause.get_mut("block") {
+              let inner = block
+                .get_mut("statements")
+                .and_then(Value::as_array_mut)
+                .ok_or_else(|| AstError::InvalidContractStructure("Try clause block missing statements array".to_string()))?;
+              instrument_statements(inner, ctx)?;
+            }
+          }
+        }
+        idx += 1;
+      }
+      Some("ExpressionStatement") => {
+        if let Some(kind) = require_or_assert_kind(&statements[idx]) {
+          let src = statements[idx].get("src").and_then(Value::as_str).unwrap_or("0:0:0").to_string();
+          let probe = ctx.build_probe(kind, &src)?;
+          statements.insert(idx, probe);
+          idx += 2;
+        } else {
+          idx += 1;
+        }
+      }
+      _ => {
+        idx += 1;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Injects a probe at the top of an `if`/loop arm, converting a bare single-statement arm into a
+/// `Block` first (see [`instrumenter::ensure_block`]) so the probe has somewhere to live, then
+/// recurses into the (now-block) body to find nested branches.
+fn instrument_branch(body: &mut Value, kind: CoverageSiteKind, ctx: &mut Context) -> Result<(), AstError> {
+  instrumenter::ensure_block(body, ctx.next_node_id)
+    .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+  let src = body.get("src").and_then(Value::as_str).unwrap_or("0:0:0").to_string();
+  let probe = ctx.build_probe(kind, &src)?;
+  let statements = body
+    .get_mut("statements")
+    .and_then(Value::as_array_mut)
+    .ok_or_else(|| AstError::InvalidContractStructure("Block missing statements array".to_string()))?;
+  statements.insert(0, probe);
+  instrument_statements(statements, ctx)
+}
+
+/// Appends the probe-reporting contract members once every function has been walked, so the
+/// counter array/event is sized correctly on the first (and only) pass.
+#[allow(clippy::too_many_arguments)]
+fn append_reporting_members(
+  unit: &mut Value,
+  contract_idx: usize,
+  mode: CoverageProbeKind,
+  probe_count: usize,
+  solc: &Solc,
+  settings: &Settings,
+  next_id: &mut i64,
+) -> Result<(), AstError> {
+  let fragment_source = match mode {
+    CoverageProbeKind::Counter => format!(
+      "uint256[{probe_count}] private __tevmCoverageHits;\n\n  function coverageHits() external view returns (uint256[] memory result) {{\n    result = new uint256[]({probe_count});\n    for (uint256 i = 0; i < {probe_count}; i++) {{\n      result[i] = __tevmCoverageHits[i];\n    }}\n  }}"
+    ),
+    CoverageProbeKind::Event => "event __TevmCoverageHit(uint256 probeId);".to_string(),
+  };
+
+  let fragment_contract = AstOrchestrator::parse_fragment_contract(&fragment_source, solc, settings)?;
+  let members = fragment_contract
+    .get("nodes")
+    .and_then(Value::as_array)
+    .ok_or_else(|| AstError::ParseFailed("Coverage fragment produced no members".to_string()))?
+    .to_vec();
+
+  let contract = instrumenter::contract_mut_at(unit, contract_idx)
+    .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+  let nodes = contract
+    .get_mut("nodes")
+    .and_then(Value::as_array_mut)
+    .ok_or_else(|| AstError::InvalidContractStructure("Contract has no members to instrument".to_string()))?;
+  for member in members {
+    nodes.push(utils::clone_with_new_ids(&member, next_id));
+  }
+
+  Ok(())
+}
+
+fn function_indices(contract: &Value) -> Vec<usize> {
+  contract
+    .get("nodes")
+    .and_then(Value::as_array)
+    .map(|nodes| {
+      nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| {
+          node_type(node) == Some("FunctionDefinition")
+            && node.get("body").map(|body| !body.is_null()).unwrap_or(false)
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn function_label(function: &Value) -> String {
+  if let Some(name) = node_name(function).filter(|name| !name.is_empty()) {
+    return name.to_string();
+  }
+  match function.get("kind").and_then(Value::as_str) {
+    Some("fallback") => "<fallback>".to_string(),
+    Some("receive") => "<receive>".to_string(),
+    Some("constructor") => "<constructor>".to_string(),
+    _ => "<anonymous>".to_string(),
+  }
+}
+
+/// Recognises `require(...)`/`assert(...)` call statements by callee name. The parse-only AST
+/// these helpers operate on (see [`super::orchestrator::AstOrchestrator::sanitize_settings`])
+/// never resolves `referencedDeclaration`, so name matching is the only signal available -- same
+/// tradeoff [`instrumenter::parse_selector`] accepts for canonical signatures.
+fn require_or_assert_kind(statement: &Value) -> Option<CoverageSiteKind> {
+  let call = statement.get("expression")?;
+  if node_type(call) != Some("FunctionCall") {
+    return None;
+  }
+  let callee_name = call.get("expression")?.get("name")?.as_str()?;
+  match callee_name {
+    "require" => Some(CoverageSiteKind::Require),
+    "assert" => Some(CoverageSiteKind::Assert),
+    _ => None,
+  }
+}
+
+fn node_type(value: &Value) -> Option<&str> {
+  value.get("nodeType").and_then(Value::as_str)
+}
+
+fn node_name(value: &Value) -> Option<&str> {
+  value.get("name").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::internal::solc;
+  use serde_json::json;
+
+  fn find_default_solc() -> Option<Solc> {
+    let version = solc::default_version().ok()?;
+    Solc::find_svm_installed_version(&version).ok().flatten()
+  }
+
+  const SAMPLE_CONTRACT: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Example {
+  function pick(uint256 value) public pure returns (uint256) {
+    if (value > 10) {
+      return 1;
+    } else {
+      return 2;
+    }
+  }
+
+  function loopy(uint256 count) public pure {
+    require(count < 100);
+    for (uint256 i = 0; i < count; i++) {
+      assert(i < count);
+    }
+  }
+}
+"#;
+
+  fn parse_example(solc: &Solc) -> Value {
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
+    AstOrchestrator::parse_source_unit(SAMPLE_CONTRACT, "Example.sol", solc, &settings, true)
+      .expect("parse example contract")
+  }
+
+  #[test]
+  fn instrument_counter_mode_covers_every_site_kind() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
+    let mut unit = parse_example(&solc);
+
+    let map = instrument(&mut unit, 0, CoverageProbeKind::Counter, &solc, &settings)
+      .expect("instrument coverage");
+
+    let kinds: Vec<CoverageSiteKind> = map.probes.iter().map(|probe| probe.kind).collect();
+    assert!(kinds.contains(&CoverageSiteKind::FunctionEntry));
+    assert!(kinds.contains(&CoverageSiteKind::BranchTrue));
+    assert!(kinds.contains(&CoverageSiteKind::BranchFalse));
+    assert!(kinds.contains(&CoverageSiteKind::LoopBody));
+    assert!(kinds.contains(&CoverageSiteKind::Require));
+    assert!(kinds.contains(&CoverageSiteKind::Assert));
+
+    let ids: std::collections::HashSet<u32> = map.probes.iter().map(|probe| probe.id).collect();
+    assert_eq!(ids.len(), map.probes.len(), "probe ids must be unique");
+
+    let contract = unit["nodes"][0]["nodes"].as_array().expect("contract members");
+    assert!(contract.iter().any(|node| {
+      node_type(node) == Some("VariableDeclaration") && node_name(node) == Some("__tevmCoverageHits")
+    }));
+    assert!(contract.iter().any(|node| {
+      node_type(node) == Some("FunctionDefinition") && node_name(node) == Some("coverageHits")
+    }));
+  }
+
+  #[test]
+  fn instrument_event_mode_declares_event_instead_of_counter() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
+    let mut unit = parse_example(&solc);
+
+    instrument(&mut unit, 0, CoverageProbeKind::Event, &solc, &settings).expect("instrument coverage");
+
+    let contract = unit["nodes"][0]["nodes"].as_array().expect("contract members");
+    assert!(contract
+      .iter()
+      .any(|node| node_type(node) == Some("EventDefinition") && node_name(node) == Some("__TevmCoverageHit")));
+    assert!(!contract
+      .iter()
+      .any(|node| node_type(node) == Some("VariableDeclaration") && node_name(node) == Some("__tevmCoverageHits")));
+  }
+
+  #[test]
+  fn instrument_reports_no_probes_for_contract_without_functions() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
+    let mut unit = AstOrchestrator::parse_source_unit(
+      "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract Empty {}\n",
+      "Empty.sol",
+      &solc,
+      &settings,
+      true,
+    )
+    .expect("parse empty contract");
+
+    let map = instrument(&mut unit, 0, CoverageProbeKind::Counter, &solc, &settings)
+      .expect("instrument coverage");
+    assert!(map.probes.is_empty());
+  }
+
+  #[test]
+  fn coverage_probe_kind_round_trips_through_display_and_from_str() {
+    assert_eq!("counter".parse::<CoverageProbeKind>().unwrap(), CoverageProbeKind::Counter);
+    assert_eq!("event".parse::<CoverageProbeKind>().unwrap(), CoverageProbeKind::Event);
+    assert_eq!(CoverageProbeKind::Counter.to_string(), "counter");
+    assert!("bogus".parse::<CoverageProbeKind>().is_err());
+  }
+}
@@ -0,0 +1,183 @@
+use std::ops::Range;
+
+use serde_json::Value;
+
+use crate::internal::errors::Error;
+
+const FRAGMENT_FILE: &str = "__AstFragment.sol";
+
+/// Turns solc's `errors` array for a wrapped `__AstFragment.sol` compile into an [`Error`].
+///
+/// `snippet` is the verbatim user-supplied text and `prologue_offset` is the byte offset at
+/// which it begins inside the wrapper (see [`super::parser::wrap_fragment_source_with_offset`]).
+/// When a failing `sourceLocation` falls inside the snippet, the offsets are translated back and
+/// an [`Error::Diagnostic`] carrying a caret-annotated excerpt is returned; otherwise (no
+/// location, or the location falls in the synthetic wrapper rather than the user's text) a plain
+/// message is returned instead. Returns `None` if `errors` contains no entries at error severity.
+pub(crate) fn diagnostic_from_solc_errors(
+  errors: &[Value],
+  snippet: &str,
+  prologue_offset: usize,
+) -> Option<Error> {
+  let error_entries: Vec<&Value> = errors
+    .iter()
+    .filter(|error| is_error_severity(error))
+    .collect();
+
+  if error_entries.is_empty() {
+    return None;
+  }
+
+  let messages: Vec<&str> = error_entries
+    .iter()
+    .map(|error| error_message(error))
+    .collect();
+  let joined = messages.join("\n");
+
+  let spanned = error_entries
+    .iter()
+    .find_map(|error| span_within_snippet(error, snippet, prologue_offset));
+
+  match spanned {
+    Some((span, message)) => {
+      let rendered = render_snippet(snippet, span.clone(), message);
+      Some(Error::diagnostic(joined, span, rendered))
+    }
+    None => Some(Error::new(joined)),
+  }
+}
+
+fn is_error_severity(error: &Value) -> bool {
+  error
+    .get("severity")
+    .and_then(Value::as_str)
+    .map(|severity| severity.eq_ignore_ascii_case("error"))
+    .unwrap_or(false)
+}
+
+fn error_message(error: &Value) -> &str {
+  error
+    .get("formattedMessage")
+    .and_then(Value::as_str)
+    .or_else(|| error.get("message").and_then(Value::as_str))
+    .unwrap_or("Compilation error")
+}
+
+fn span_within_snippet<'a>(
+  error: &'a Value,
+  snippet: &str,
+  prologue_offset: usize,
+) -> Option<(Range<usize>, &'a str)> {
+  let location = error.get("sourceLocation")?;
+  let file = location.get("file").and_then(Value::as_str)?;
+  if file != FRAGMENT_FILE {
+    return None;
+  }
+
+  let start = location.get("start").and_then(Value::as_i64)?;
+  let end = location.get("end").and_then(Value::as_i64)?;
+  if start < 0 || end < start {
+    return None;
+  }
+
+  let start = (start as usize).checked_sub(prologue_offset)?;
+  let end = (end as usize).checked_sub(prologue_offset)?;
+  if end > snippet.len() || !snippet.is_char_boundary(start) || !snippet.is_char_boundary(end) {
+    return None;
+  }
+
+  Some((start..end, error_message(error)))
+}
+
+/// Renders a caret-annotated excerpt of `snippet` highlighting `span`, e.g. for a span-aware
+/// [`crate::internal::errors::Error::diagnostic`] built from a node located via
+/// [`super::utils::locate_span`].
+pub(crate) fn render_snippet(snippet: &str, span: Range<usize>, message: &str) -> String {
+  let mut line_start = 0;
+  let mut line_number: usize = 1;
+  for (index, ch) in snippet.char_indices() {
+    if index >= span.start {
+      break;
+    }
+    if ch == '\n' {
+      line_number += 1;
+      line_start = index + 1;
+    }
+  }
+
+  let line_end = snippet[line_start..]
+    .find('\n')
+    .map(|offset| line_start + offset)
+    .unwrap_or(snippet.len());
+  let line_text = &snippet[line_start..line_end];
+
+  let column = snippet[line_start..span.start].chars().count();
+  let width = snippet[span.start..span.end].chars().count().max(1);
+  let gutter = line_number.to_string();
+  let padding = " ".repeat(gutter.len());
+
+  format!(
+    "error: {message}\n{padding} |\n{gutter} | {line_text}\n{padding} | {}{}",
+    " ".repeat(column),
+    "^".repeat(width)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn maps_solc_error_onto_snippet_span() {
+    let snippet = "uint256 x = ;";
+    let (_, offset) = super::super::parser::wrap_fragment_source_with_offset(snippet);
+    let errors = vec![json!({
+      "severity": "error",
+      "message": "Expected expression.",
+      "formattedMessage": "ParserError: Expected expression.",
+      "sourceLocation": {
+        "file": "__AstFragment.sol",
+        "start": offset + 12,
+        "end": offset + 13,
+      }
+    })];
+
+    let err = diagnostic_from_solc_errors(&errors, snippet, offset).expect("diagnostic");
+    assert_eq!(err.span(), Some(12..13));
+    let rendered = err.rendered().expect("rendered excerpt");
+    assert!(rendered.contains("ParserError: Expected expression."));
+    assert!(rendered.contains(snippet));
+    assert!(rendered.contains('^'));
+  }
+
+  #[test]
+  fn falls_back_to_plain_message_for_wrapper_only_errors() {
+    let snippet = "uint256 x = 1;";
+    let errors = vec![json!({
+      "severity": "error",
+      "message": "Unexpected token.",
+      "formattedMessage": "ParserError: Unexpected token.",
+      "sourceLocation": {
+        "file": "__AstFragment.sol",
+        "start": 0,
+        "end": 1,
+      }
+    })];
+
+    let err = diagnostic_from_solc_errors(&errors, snippet, 50).expect("error");
+    assert!(err.span().is_none());
+    assert!(err.rendered().is_none());
+    assert_eq!(err.message(), "ParserError: Unexpected token.");
+  }
+
+  #[test]
+  fn ignores_warnings_without_errors() {
+    let errors = vec![json!({
+      "severity": "warning",
+      "message": "unused variable",
+    })];
+
+    assert!(diagnostic_from_solc_errors(&errors, "uint256 x;", 0).is_none());
+  }
+}
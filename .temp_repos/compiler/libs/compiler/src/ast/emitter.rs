@@ -0,0 +1,889 @@
+use serde_json::Value;
+
+use super::error::AstError;
+
+/// Walks a solc standard-JSON `SourceUnit` and pretty-prints it back into Solidity source, so a
+/// caller can inspect, export, or diff a contract after `injectShadow`/`exposeInternal*`
+/// instrumentation without recompiling. Mirrors the location-free AST-driver pattern used by the
+/// Fe/roc toolchains: every node is rendered purely from its own fields, never from stored source
+/// text. Node types the emitter doesn't yet reconstruct (inline assembly, try/catch, and other
+/// rarely-instrumented shapes) are never silently dropped -- the walk stops and reports the
+/// offending `nodeType` and `src` span, since `State` keeps no pre-instrumentation source to fall
+/// back to once a node has been replaced.
+pub fn emit_source_unit(unit: &Value) -> Result<String, AstError> {
+  let nodes = array_field(unit, "nodes")?;
+  let mut parts = Vec::with_capacity(nodes.len());
+  for node in nodes {
+    parts.push(emit_unit_member(node)?);
+  }
+  Ok(format!("{}\n", parts.join("\n\n")))
+}
+
+fn unsupported(node: &Value) -> AstError {
+  AstError::AnalysisFailed(format!(
+    "emit_source cannot reconstruct `{}` nodes yet (src `{}`)",
+    node_type(node),
+    src_of(node)
+  ))
+}
+
+fn node_type(node: &Value) -> &str {
+  node
+    .get("nodeType")
+    .and_then(Value::as_str)
+    .unwrap_or("<unknown>")
+}
+
+fn src_of(node: &Value) -> &str {
+  node.get("src").and_then(Value::as_str).unwrap_or("<unknown>")
+}
+
+fn pad(indent: usize) -> String {
+  "  ".repeat(indent)
+}
+
+fn array_field<'a>(node: &'a Value, field: &str) -> Result<&'a Vec<Value>, AstError> {
+  node.get(field).and_then(Value::as_array).ok_or_else(|| {
+    AstError::InvalidContractStructure(format!("`{}` missing `{field}` array", node_type(node)))
+  })
+}
+
+fn str_field<'a>(node: &'a Value, field: &str) -> Result<&'a str, AstError> {
+  node.get(field).and_then(Value::as_str).ok_or_else(|| {
+    AstError::InvalidContractStructure(format!("`{}` missing `{field}` string", node_type(node)))
+  })
+}
+
+fn missing(node: &Value, field: &str) -> AstError {
+  AstError::InvalidContractStructure(format!("`{}` missing `{field}`", node_type(node)))
+}
+
+fn emit_unit_member(node: &Value) -> Result<String, AstError> {
+  match node_type(node) {
+    "PragmaDirective" => emit_pragma(node),
+    "ImportDirective" => emit_import(node),
+    "ContractDefinition" => emit_contract(node),
+    "StructDefinition" => emit_struct(node, 0),
+    "EnumDefinition" => emit_enum(node, 0),
+    "ErrorDefinition" => emit_error_def(node, 0),
+    "UserDefinedValueTypeDefinition" => emit_user_defined_value_type(node, 0),
+    "FunctionDefinition" => emit_function(node, 0),
+    "VariableDeclaration" => Ok(format!("{};", emit_variable_declaration(node, true)?)),
+    "UsingForDirective" => emit_using_for(node, 0),
+    _ => Err(unsupported(node)),
+  }
+}
+
+fn emit_contract_member(node: &Value, indent: usize) -> Result<String, AstError> {
+  match node_type(node) {
+    "FunctionDefinition" => emit_function(node, indent),
+    "ModifierDefinition" => emit_modifier(node, indent),
+    "VariableDeclaration" => Ok(format!("{}{};", pad(indent), emit_variable_declaration(node, true)?)),
+    "EventDefinition" => emit_event(node, indent),
+    "ErrorDefinition" => emit_error_def(node, indent),
+    "StructDefinition" => emit_struct(node, indent),
+    "EnumDefinition" => emit_enum(node, indent),
+    "UsingForDirective" => emit_using_for(node, indent),
+    "UserDefinedValueTypeDefinition" => emit_user_defined_value_type(node, indent),
+    _ => Err(unsupported(node)),
+  }
+}
+
+fn emit_pragma(node: &Value) -> Result<String, AstError> {
+  let literals = array_field(node, "literals")?;
+  let mut tokens = Vec::with_capacity(literals.len());
+  for literal in literals {
+    tokens.push(
+      literal
+        .as_str()
+        .ok_or_else(|| AstError::InvalidContractStructure("PragmaDirective literal is not a string".to_string()))?,
+    );
+  }
+  let (keyword, rest) = tokens
+    .split_first()
+    .ok_or_else(|| AstError::InvalidContractStructure("PragmaDirective has no literals".to_string()))?;
+  Ok(format!("pragma {keyword} {};", rest.join("")))
+}
+
+fn emit_import(node: &Value) -> Result<String, AstError> {
+  let file = str_field(node, "file")?;
+  let unit_alias = node
+    .get("unitAlias")
+    .and_then(Value::as_str)
+    .filter(|alias| !alias.is_empty());
+  let symbol_aliases = node
+    .get("symbolAliases")
+    .and_then(Value::as_array)
+    .filter(|aliases| !aliases.is_empty());
+
+  if let Some(aliases) = symbol_aliases {
+    let mut items = Vec::with_capacity(aliases.len());
+    for alias in aliases {
+      let foreign_name = alias
+        .get("foreign")
+        .and_then(|foreign| foreign.get("name"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| missing(node, "symbolAliases[].foreign.name"))?;
+      match alias.get("local").and_then(Value::as_str) {
+        Some(local) => items.push(format!("{foreign_name} as {local}")),
+        None => items.push(foreign_name.to_string()),
+      }
+    }
+    return Ok(format!("import {{{}}} from \"{file}\";", items.join(", ")));
+  }
+
+  match unit_alias {
+    Some(alias) => Ok(format!("import \"{file}\" as {alias};")),
+    None => Ok(format!("import \"{file}\";")),
+  }
+}
+
+fn emit_contract(node: &Value) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let kind = node.get("contractKind").and_then(Value::as_str).unwrap_or("contract");
+  let is_abstract = node.get("abstract").and_then(Value::as_bool).unwrap_or(false);
+
+  let mut header = String::new();
+  if is_abstract {
+    header.push_str("abstract ");
+  }
+  header.push_str(kind);
+  header.push(' ');
+  header.push_str(name);
+
+  if let Some(bases) = node.get("baseContracts").and_then(Value::as_array) {
+    if !bases.is_empty() {
+      let rendered = bases
+        .iter()
+        .map(emit_inheritance_specifier)
+        .collect::<Result<Vec<_>, _>>()?;
+      header.push_str(" is ");
+      header.push_str(&rendered.join(", "));
+    }
+  }
+
+  let members = array_field(node, "nodes")?;
+  let body = members
+    .iter()
+    .map(|member| emit_contract_member(member, 1))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  if body.is_empty() {
+    Ok(format!("{header} {{}}"))
+  } else {
+    Ok(format!("{header} {{\n{}\n}}", body.join("\n\n")))
+  }
+}
+
+fn emit_inheritance_specifier(node: &Value) -> Result<String, AstError> {
+  let base = node.get("baseName").ok_or_else(|| missing(node, "baseName"))?;
+  let base_name = user_defined_name(base).ok_or_else(|| missing(node, "baseName.name"))?;
+  match node.get("arguments").and_then(Value::as_array) {
+    Some(args) if !args.is_empty() => {
+      let rendered = args.iter().map(emit_expression).collect::<Result<Vec<_>, _>>()?;
+      Ok(format!("{base_name}({})", rendered.join(", ")))
+    }
+    _ => Ok(base_name.to_string()),
+  }
+}
+
+fn user_defined_name(node: &Value) -> Option<&str> {
+  node
+    .get("pathNode")
+    .and_then(|path| path.get("name"))
+    .and_then(Value::as_str)
+    .or_else(|| node.get("name").and_then(Value::as_str))
+}
+
+fn emit_function(node: &Value, indent: usize) -> Result<String, AstError> {
+  let kind = node.get("kind").and_then(Value::as_str).unwrap_or("function");
+  let name = node.get("name").and_then(Value::as_str).unwrap_or("");
+
+  let mut header = pad(indent);
+  match kind {
+    "constructor" => header.push_str("constructor"),
+    "receive" => header.push_str("receive"),
+    "fallback" => header.push_str("fallback"),
+    _ => {
+      header.push_str("function ");
+      header.push_str(name);
+    }
+  }
+
+  let params = array_field(node.get("parameters").ok_or_else(|| missing(node, "parameters"))?, "parameters")?;
+  let rendered_params = params
+    .iter()
+    .map(|param| emit_variable_declaration(param, false))
+    .collect::<Result<Vec<_>, _>>()?;
+  header.push('(');
+  header.push_str(&rendered_params.join(", "));
+  header.push(')');
+
+  if kind != "constructor" {
+    let visibility = node.get("visibility").and_then(Value::as_str).unwrap_or("public");
+    header.push(' ');
+    header.push_str(visibility);
+  }
+
+  if node.get("virtual").and_then(Value::as_bool).unwrap_or(false) {
+    header.push_str(" virtual");
+  }
+  if node.get("overrides").map(|value| !value.is_null()).unwrap_or(false) {
+    header.push_str(" override");
+  }
+
+  let state_mutability = node.get("stateMutability").and_then(Value::as_str).unwrap_or("nonpayable");
+  if state_mutability != "nonpayable" {
+    header.push(' ');
+    header.push_str(state_mutability);
+  }
+
+  if let Some(modifiers) = node.get("modifiers").and_then(Value::as_array) {
+    for modifier in modifiers {
+      header.push(' ');
+      header.push_str(&emit_modifier_invocation(modifier)?);
+    }
+  }
+
+  let return_params = node
+    .get("returnParameters")
+    .and_then(|value| value.get("parameters"))
+    .and_then(Value::as_array);
+  if let Some(returns) = return_params.filter(|returns| !returns.is_empty()) {
+    let rendered_returns = returns
+      .iter()
+      .map(|param| emit_variable_declaration(param, false))
+      .collect::<Result<Vec<_>, _>>()?;
+    header.push_str(" returns (");
+    header.push_str(&rendered_returns.join(", "));
+    header.push(')');
+  }
+
+  match node.get("body").filter(|body| !body.is_null()) {
+    Some(body) => Ok(format!("{header} {}", emit_block(body, indent)?)),
+    None => Ok(format!("{header};")),
+  }
+}
+
+fn emit_modifier_invocation(node: &Value) -> Result<String, AstError> {
+  let modifier_name = node.get("modifierName").ok_or_else(|| missing(node, "modifierName"))?;
+  let name = user_defined_name(modifier_name).ok_or_else(|| missing(node, "modifierName.name"))?;
+  match node.get("arguments").and_then(Value::as_array) {
+    Some(args) => {
+      let rendered = args.iter().map(emit_expression).collect::<Result<Vec<_>, _>>()?;
+      Ok(format!("{name}({})", rendered.join(", ")))
+    }
+    None => Ok(name.to_string()),
+  }
+}
+
+fn emit_modifier(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let params = array_field(node.get("parameters").ok_or_else(|| missing(node, "parameters"))?, "parameters")?;
+  let rendered_params = params
+    .iter()
+    .map(|param| emit_variable_declaration(param, false))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let mut header = format!("{}modifier {name}({})", pad(indent), rendered_params.join(", "));
+  if node.get("virtual").and_then(Value::as_bool).unwrap_or(false) {
+    header.push_str(" virtual");
+  }
+  if node.get("overrides").map(|value| !value.is_null()).unwrap_or(false) {
+    header.push_str(" override");
+  }
+
+  match node.get("body").filter(|body| !body.is_null()) {
+    Some(body) => Ok(format!("{header} {}", emit_block(body, indent)?)),
+    None => Ok(format!("{header};")),
+  }
+}
+
+fn emit_event(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let params = array_field(node.get("parameters").ok_or_else(|| missing(node, "parameters"))?, "parameters")?;
+  let rendered = params.iter().map(emit_event_parameter).collect::<Result<Vec<_>, _>>()?;
+  let anonymous = if node.get("anonymous").and_then(Value::as_bool).unwrap_or(false) {
+    " anonymous"
+  } else {
+    ""
+  };
+  Ok(format!("{}event {name}({}){anonymous};", pad(indent), rendered.join(", ")))
+}
+
+fn emit_event_parameter(node: &Value) -> Result<String, AstError> {
+  let type_name = type_name_of(node)?;
+  let mut out = render_type_name(type_name)?;
+  if node.get("indexed").and_then(Value::as_bool).unwrap_or(false) {
+    out.push_str(" indexed");
+  }
+  if let Some(name) = node.get("name").and_then(Value::as_str).filter(|name| !name.is_empty()) {
+    out.push(' ');
+    out.push_str(name);
+  }
+  Ok(out)
+}
+
+fn emit_error_def(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let params = array_field(node.get("parameters").ok_or_else(|| missing(node, "parameters"))?, "parameters")?;
+  let rendered = params
+    .iter()
+    .map(|param| emit_variable_declaration(param, false))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(format!("{}error {name}({});", pad(indent), rendered.join(", ")))
+}
+
+fn emit_struct(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let members = array_field(node, "members")?;
+  let rendered = members
+    .iter()
+    .map(|member| Ok(format!("{}{};", pad(indent + 1), emit_variable_declaration(member, false)?)))
+    .collect::<Result<Vec<_>, AstError>>()?;
+  Ok(format!(
+    "{}struct {name} {{\n{}\n{}}}",
+    pad(indent),
+    rendered.join("\n"),
+    pad(indent)
+  ))
+}
+
+fn emit_enum(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let members = array_field(node, "members")?;
+  let names = members
+    .iter()
+    .map(|member| str_field(member, "name"))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(format!("{}enum {name} {{ {} }}", pad(indent), names.join(", ")))
+}
+
+fn emit_user_defined_value_type(node: &Value, indent: usize) -> Result<String, AstError> {
+  let name = str_field(node, "name")?;
+  let underlying = node.get("underlyingType").ok_or_else(|| missing(node, "underlyingType"))?;
+  Ok(format!("{}type {name} is {};", pad(indent), render_type_name(underlying)?))
+}
+
+fn emit_using_for(node: &Value, indent: usize) -> Result<String, AstError> {
+  let target = match node.get("typeName").filter(|value| !value.is_null()) {
+    Some(type_name) => render_type_name(type_name)?,
+    None => "*".to_string(),
+  };
+  let suffix = if node.get("global").and_then(Value::as_bool).unwrap_or(false) {
+    " global"
+  } else {
+    ""
+  };
+
+  if let Some(library_name) = node.get("libraryName") {
+    let name = user_defined_name(library_name).ok_or_else(|| missing(node, "libraryName.name"))?;
+    return Ok(format!("{}using {name} for {target}{suffix};", pad(indent)));
+  }
+
+  if let Some(function_list) = node.get("functionList").and_then(Value::as_array) {
+    let names = function_list
+      .iter()
+      .map(|entry| {
+        entry
+          .get("function")
+          .and_then(|function| function.get("name"))
+          .and_then(Value::as_str)
+          .ok_or_else(|| missing(node, "functionList[].function.name"))
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    return Ok(format!("{}using {{{}}} for {target}{suffix};", pad(indent), names.join(", ")));
+  }
+
+  Err(missing(node, "libraryName/functionList"))
+}
+
+fn type_name_of(node: &Value) -> Result<&Value, AstError> {
+  node
+    .get("typeName")
+    .filter(|value| !value.is_null())
+    .ok_or_else(|| missing(node, "typeName"))
+}
+
+/// Renders a `typeName` subtree back into a Solidity type annotation. Elementary, user-defined,
+/// array, and mapping types are reconstructed structurally; anything else (function types and
+/// other rarely-declared shapes) falls back to solc's own `typeDescriptions.typeString`, which is
+/// itself AST data rather than recovered source text.
+fn render_type_name(type_name: &Value) -> Result<String, AstError> {
+  match node_type(type_name) {
+    "ElementaryTypeName" => {
+      let name = str_field(type_name, "name")?;
+      match type_name.get("stateMutability").and_then(Value::as_str) {
+        Some("payable") if name == "address" => Ok("address payable".to_string()),
+        _ => Ok(name.to_string()),
+      }
+    }
+    "UserDefinedTypeName" => user_defined_name(type_name)
+      .map(str::to_string)
+      .ok_or_else(|| missing(type_name, "name")),
+    "ArrayTypeName" => {
+      let base = type_name.get("baseType").ok_or_else(|| missing(type_name, "baseType"))?;
+      let length = match type_name.get("length").filter(|value| !value.is_null()) {
+        Some(expr) => emit_expression(expr)?,
+        None => String::new(),
+      };
+      Ok(format!("{}[{length}]", render_type_name(base)?))
+    }
+    "Mapping" => {
+      let key = type_name.get("keyType").ok_or_else(|| missing(type_name, "keyType"))?;
+      let value = type_name.get("valueType").ok_or_else(|| missing(type_name, "valueType"))?;
+      Ok(format!("mapping({} => {})", render_type_name(key)?, render_type_name(value)?))
+    }
+    _ => type_name
+      .get("typeDescriptions")
+      .and_then(|descriptions| descriptions.get("typeString"))
+      .and_then(Value::as_str)
+      .map(str::to_string)
+      .ok_or_else(|| unsupported(type_name)),
+  }
+}
+
+fn emit_variable_declaration(node: &Value, allow_value: bool) -> Result<String, AstError> {
+  let type_name = type_name_of(node)?;
+  let mut out = render_type_name(type_name)?;
+
+  if let Some(location) = node.get("storageLocation").and_then(Value::as_str) {
+    if location != "default" {
+      out.push(' ');
+      out.push_str(location);
+    }
+  }
+
+  if node.get("stateVariable").and_then(Value::as_bool).unwrap_or(false) {
+    if let Some(visibility) = node.get("visibility").and_then(Value::as_str) {
+      if visibility != "internal" {
+        out.push(' ');
+        out.push_str(visibility);
+      }
+    }
+    match node.get("mutability").and_then(Value::as_str) {
+      Some("constant") => out.push_str(" constant"),
+      Some("immutable") => out.push_str(" immutable"),
+      _ => {}
+    }
+  }
+
+  if let Some(name) = node.get("name").and_then(Value::as_str).filter(|name| !name.is_empty()) {
+    out.push(' ');
+    out.push_str(name);
+  }
+
+  if allow_value {
+    if let Some(value) = node.get("value").filter(|value| !value.is_null()) {
+      out.push_str(" = ");
+      out.push_str(&emit_expression(value)?);
+    }
+  }
+
+  Ok(out)
+}
+
+fn emit_block(node: &Value, indent: usize) -> Result<String, AstError> {
+  let statements = node.get("statements").and_then(Value::as_array);
+  let Some(statements) = statements.filter(|statements| !statements.is_empty()) else {
+    return Ok("{}".to_string());
+  };
+  let lines = statements
+    .iter()
+    .map(|statement| emit_statement(statement, indent + 1))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(format!("{{\n{}\n{}}}", lines.join("\n"), pad(indent)))
+}
+
+fn emit_inline_or_block(node: &Value, indent: usize) -> Result<String, AstError> {
+  if node_type(node) == "Block" {
+    emit_block(node, indent)
+  } else {
+    Ok(emit_statement(node, indent + 1)?.trim_start().to_string())
+  }
+}
+
+fn emit_statement(node: &Value, indent: usize) -> Result<String, AstError> {
+  let prefix = pad(indent);
+  match node_type(node) {
+    "Block" => Ok(format!("{prefix}{}", emit_block(node, indent)?)),
+    "UncheckedBlock" => Ok(format!("{prefix}unchecked {}", emit_block(node, indent)?)),
+    "ExpressionStatement" => {
+      let expression = node.get("expression").ok_or_else(|| missing(node, "expression"))?;
+      Ok(format!("{prefix}{};", emit_expression(expression)?))
+    }
+    "VariableDeclarationStatement" => {
+      let declarations = array_field(node, "declarations")?;
+      let rendered = declarations
+        .iter()
+        .map(|declaration| {
+          if declaration.is_null() {
+            Ok(String::new())
+          } else {
+            emit_variable_declaration(declaration, false)
+          }
+        })
+        .collect::<Result<Vec<_>, AstError>>()?;
+      let lhs = if declarations.len() > 1 {
+        format!("({})", rendered.join(", "))
+      } else {
+        rendered.into_iter().next().unwrap_or_default()
+      };
+      match node.get("initialValue").filter(|value| !value.is_null()) {
+        Some(value) => Ok(format!("{prefix}{lhs} = {};", emit_expression(value)?)),
+        None => Ok(format!("{prefix}{lhs};")),
+      }
+    }
+    "IfStatement" => {
+      let condition = node.get("condition").ok_or_else(|| missing(node, "condition"))?;
+      let true_body = node.get("trueBody").ok_or_else(|| missing(node, "trueBody"))?;
+      let mut out = format!(
+        "{prefix}if ({}) {}",
+        emit_expression(condition)?,
+        emit_inline_or_block(true_body, indent)?
+      );
+      if let Some(false_body) = node.get("falseBody").filter(|value| !value.is_null()) {
+        out.push_str(&format!(" else {}", emit_inline_or_block(false_body, indent)?));
+      }
+      Ok(out)
+    }
+    "WhileStatement" => {
+      let condition = node.get("condition").ok_or_else(|| missing(node, "condition"))?;
+      let body = node.get("body").ok_or_else(|| missing(node, "body"))?;
+      Ok(format!(
+        "{prefix}while ({}) {}",
+        emit_expression(condition)?,
+        emit_inline_or_block(body, indent)?
+      ))
+    }
+    "DoWhileStatement" => {
+      let condition = node.get("condition").ok_or_else(|| missing(node, "condition"))?;
+      let body = node.get("body").ok_or_else(|| missing(node, "body"))?;
+      Ok(format!(
+        "{prefix}do {} while ({});",
+        emit_inline_or_block(body, indent)?,
+        emit_expression(condition)?
+      ))
+    }
+    "ForStatement" => {
+      let init = match node.get("initializationExpression").filter(|value| !value.is_null()) {
+        Some(statement) => emit_statement(statement, 0)?.trim_end_matches(';').to_string(),
+        None => String::new(),
+      };
+      let condition = match node.get("condition").filter(|value| !value.is_null()) {
+        Some(expression) => emit_expression(expression)?,
+        None => String::new(),
+      };
+      let update = match node.get("loopExpression").filter(|value| !value.is_null()) {
+        Some(statement) => emit_statement(statement, 0)?.trim_end_matches(';').to_string(),
+        None => String::new(),
+      };
+      let body = node.get("body").ok_or_else(|| missing(node, "body"))?;
+      Ok(format!(
+        "{prefix}for ({init}; {condition}; {update}) {}",
+        emit_inline_or_block(body, indent)?
+      ))
+    }
+    "Return" => match node.get("expression").filter(|value| !value.is_null()) {
+      Some(expression) => Ok(format!("{prefix}return {};", emit_expression(expression)?)),
+      None => Ok(format!("{prefix}return;")),
+    },
+    "EmitStatement" => {
+      let call = node.get("eventCall").ok_or_else(|| missing(node, "eventCall"))?;
+      Ok(format!("{prefix}emit {};", emit_expression(call)?))
+    }
+    "RevertStatement" => {
+      let call = node.get("errorCall").ok_or_else(|| missing(node, "errorCall"))?;
+      Ok(format!("{prefix}revert {};", emit_expression(call)?))
+    }
+    "Break" => Ok(format!("{prefix}break;")),
+    "Continue" => Ok(format!("{prefix}continue;")),
+    "PlaceholderStatement" => Ok(format!("{prefix}_;")),
+    _ => Err(unsupported(node)),
+  }
+}
+
+fn emit_expression(node: &Value) -> Result<String, AstError> {
+  match node_type(node) {
+    "Identifier" => Ok(str_field(node, "name")?.to_string()),
+    "Literal" => emit_literal(node),
+    "MemberAccess" => {
+      let base = node.get("expression").ok_or_else(|| missing(node, "expression"))?;
+      let member = str_field(node, "memberName")?;
+      Ok(format!("{}.{member}", emit_expression(base)?))
+    }
+    "IndexAccess" => {
+      let base = node.get("baseExpression").ok_or_else(|| missing(node, "baseExpression"))?;
+      let index = match node.get("indexExpression").filter(|value| !value.is_null()) {
+        Some(expression) => emit_expression(expression)?,
+        None => String::new(),
+      };
+      Ok(format!("{}[{index}]", emit_expression(base)?))
+    }
+    "FunctionCall" => {
+      let callee = node.get("expression").ok_or_else(|| missing(node, "expression"))?;
+      let args = node.get("arguments").and_then(Value::as_array).cloned().unwrap_or_default();
+      let names = node.get("names").and_then(Value::as_array).cloned().unwrap_or_default();
+      let rendered_args = args.iter().map(emit_expression).collect::<Result<Vec<_>, _>>()?;
+
+      if !names.is_empty() && names.len() == rendered_args.len() {
+        let mut pairs = Vec::with_capacity(names.len());
+        for (name, value) in names.iter().zip(rendered_args.iter()) {
+          let name = name
+            .as_str()
+            .ok_or_else(|| AstError::InvalidContractStructure("FunctionCall name is not a string".to_string()))?;
+          pairs.push(format!("{name}: {value}"));
+        }
+        Ok(format!("{}({{{}}})", emit_expression(callee)?, pairs.join(", ")))
+      } else {
+        Ok(format!("{}({})", emit_expression(callee)?, rendered_args.join(", ")))
+      }
+    }
+    "BinaryOperation" => {
+      let left = node.get("leftExpression").ok_or_else(|| missing(node, "leftExpression"))?;
+      let right = node.get("rightExpression").ok_or_else(|| missing(node, "rightExpression"))?;
+      let operator = str_field(node, "operator")?;
+      Ok(format!("({} {operator} {})", emit_expression(left)?, emit_expression(right)?))
+    }
+    "UnaryOperation" => {
+      let sub = node.get("subExpression").ok_or_else(|| missing(node, "subExpression"))?;
+      let operator = str_field(node, "operator")?;
+      let prefix = node.get("prefix").and_then(Value::as_bool).unwrap_or(true);
+      let rendered = emit_expression(sub)?;
+      Ok(if prefix {
+        format!("{operator}{rendered}")
+      } else {
+        format!("{rendered}{operator}")
+      })
+    }
+    "Assignment" => {
+      let left = node.get("leftHandSide").ok_or_else(|| missing(node, "leftHandSide"))?;
+      let right = node.get("rightHandSide").ok_or_else(|| missing(node, "rightHandSide"))?;
+      let operator = str_field(node, "operator")?;
+      Ok(format!("{} {operator} {}", emit_expression(left)?, emit_expression(right)?))
+    }
+    "TupleExpression" => {
+      let components = node.get("components").and_then(Value::as_array).cloned().unwrap_or_default();
+      let rendered = components
+        .iter()
+        .map(|component| {
+          if component.is_null() {
+            Ok(String::new())
+          } else {
+            emit_expression(component)
+          }
+        })
+        .collect::<Result<Vec<_>, AstError>>()?;
+      if node.get("isInlineArray").and_then(Value::as_bool).unwrap_or(false) {
+        Ok(format!("[{}]", rendered.join(", ")))
+      } else {
+        Ok(format!("({})", rendered.join(", ")))
+      }
+    }
+    "Conditional" => {
+      let condition = node.get("condition").ok_or_else(|| missing(node, "condition"))?;
+      let true_expr = node.get("trueExpression").ok_or_else(|| missing(node, "trueExpression"))?;
+      let false_expr = node.get("falseExpression").ok_or_else(|| missing(node, "falseExpression"))?;
+      Ok(format!(
+        "({} ? {} : {})",
+        emit_expression(condition)?,
+        emit_expression(true_expr)?,
+        emit_expression(false_expr)?
+      ))
+    }
+    "ElementaryTypeNameExpression" => {
+      let type_name = node.get("typeName").ok_or_else(|| missing(node, "typeName"))?;
+      render_type_name(type_name)
+    }
+    "NewExpression" => {
+      let type_name = node.get("typeName").ok_or_else(|| missing(node, "typeName"))?;
+      Ok(format!("new {}", render_type_name(type_name)?))
+    }
+    _ => Err(unsupported(node)),
+  }
+}
+
+fn emit_literal(node: &Value) -> Result<String, AstError> {
+  match node.get("kind").and_then(Value::as_str).unwrap_or("number") {
+    "string" => {
+      let value = node.get("value").and_then(Value::as_str).unwrap_or_default();
+      Ok(format!("{value:?}"))
+    }
+    "unicodeString" => {
+      let value = node.get("value").and_then(Value::as_str).unwrap_or_default();
+      Ok(format!("unicode{value:?}"))
+    }
+    "hexString" => {
+      let hex_value = node.get("hexValue").and_then(Value::as_str).unwrap_or_default();
+      Ok(format!("hex\"{hex_value}\""))
+    }
+    "bool" => Ok(node.get("value").and_then(Value::as_str).unwrap_or("false").to_string()),
+    _ => {
+      let value = str_field(node, "value")?;
+      match node.get("subdenomination").and_then(Value::as_str) {
+        Some(sub) => Ok(format!("{value} {sub}")),
+        None => Ok(value.to_string()),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn emits_pragma_and_minimal_contract() {
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "nodes": [
+        { "nodeType": "PragmaDirective", "literals": ["solidity", "^0.8", ".0"] },
+        {
+          "nodeType": "ContractDefinition",
+          "name": "Empty",
+          "contractKind": "contract",
+          "abstract": false,
+          "nodes": []
+        }
+      ]
+    });
+
+    let source = emit_source_unit(&unit).expect("emit");
+    assert_eq!(source, "pragma solidity ^0.8.0;\n\ncontract Empty {}\n");
+  }
+
+  #[test]
+  fn emits_import_with_symbol_aliases() {
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "nodes": [{
+        "nodeType": "ImportDirective",
+        "file": "./Other.sol",
+        "symbolAliases": [
+          { "foreign": { "name": "Foo" }, "local": "Bar" },
+          { "foreign": { "name": "Baz" } }
+        ]
+      }]
+    });
+
+    let source = emit_source_unit(&unit).expect("emit");
+    assert_eq!(source, "import {Foo as Bar, Baz} from \"./Other.sol\";\n");
+  }
+
+  fn elementary(name: &str) -> Value {
+    json!({ "nodeType": "ElementaryTypeName", "name": name })
+  }
+
+  fn state_variable(name: &str, type_name: Value, visibility: &str) -> Value {
+    json!({
+      "nodeType": "VariableDeclaration",
+      "name": name,
+      "typeName": type_name,
+      "stateVariable": true,
+      "visibility": visibility,
+      "mutability": "mutable",
+    })
+  }
+
+  #[test]
+  fn emits_state_variable_with_visibility_and_constant() {
+    let node = state_variable("count", elementary("uint256"), "public");
+    assert_eq!(
+      emit_variable_declaration(&node, false).expect("render"),
+      "uint256 public count"
+    );
+
+    let mut constant = state_variable("MAX", elementary("uint256"), "internal");
+    constant["mutability"] = json!("constant");
+    constant["value"] = json!({ "nodeType": "Literal", "kind": "number", "value": "100" });
+    assert_eq!(
+      emit_variable_declaration(&constant, true).expect("render"),
+      "uint256 constant MAX = 100"
+    );
+  }
+
+  #[test]
+  fn emits_function_with_params_modifiers_and_returns() {
+    let node = json!({
+      "nodeType": "FunctionDefinition",
+      "kind": "function",
+      "name": "add",
+      "visibility": "public",
+      "stateMutability": "view",
+      "parameters": { "parameters": [
+        { "nodeType": "VariableDeclaration", "name": "a", "typeName": elementary("uint256") },
+        { "nodeType": "VariableDeclaration", "name": "b", "typeName": elementary("uint256") },
+      ]},
+      "modifiers": [{ "modifierName": { "name": "onlyOwner" } }],
+      "returnParameters": { "parameters": [
+        { "nodeType": "VariableDeclaration", "name": "", "typeName": elementary("uint256") },
+      ]},
+      "body": {
+        "nodeType": "Block",
+        "statements": [{
+          "nodeType": "Return",
+          "expression": {
+            "nodeType": "BinaryOperation",
+            "operator": "+",
+            "leftExpression": { "nodeType": "Identifier", "name": "a" },
+            "rightExpression": { "nodeType": "Identifier", "name": "b" },
+          }
+        }]
+      }
+    });
+
+    let source = emit_function(&node, 0).expect("emit");
+    assert_eq!(
+      source,
+      "function add(uint256 a, uint256 b) public view onlyOwner returns (uint256) {\n  return (a + b);\n}"
+    );
+  }
+
+  #[test]
+  fn emits_mapping_and_array_type_names() {
+    let mapping = json!({
+      "nodeType": "Mapping",
+      "keyType": elementary("address"),
+      "valueType": elementary("uint256"),
+    });
+    assert_eq!(render_type_name(&mapping).expect("render"), "mapping(address => uint256)");
+
+    let array = json!({
+      "nodeType": "ArrayTypeName",
+      "baseType": elementary("uint256"),
+    });
+    assert_eq!(render_type_name(&array).expect("render"), "uint256[]");
+  }
+
+  #[test]
+  fn errors_on_unsupported_node_type_with_src_span() {
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "nodes": [{ "nodeType": "InlineAssembly", "src": "10:5:0" }]
+    });
+
+    let err = emit_source_unit(&unit).unwrap_err();
+    assert!(err.to_string().contains("InlineAssembly"));
+    assert!(err.to_string().contains("10:5:0"));
+  }
+
+  #[test]
+  fn emits_if_statement_with_inline_else() {
+    let node = json!({
+      "nodeType": "IfStatement",
+      "condition": { "nodeType": "Identifier", "name": "ok" },
+      "trueBody": {
+        "nodeType": "ExpressionStatement",
+        "expression": { "nodeType": "Identifier", "name": "doThing" }
+      },
+      "falseBody": {
+        "nodeType": "ExpressionStatement",
+        "expression": { "nodeType": "Identifier", "name": "doOther" }
+      }
+    });
+
+    let source = emit_statement(&node, 0).expect("emit");
+    assert_eq!(source, "if (ok) doThing; else doOther;");
+  }
+}
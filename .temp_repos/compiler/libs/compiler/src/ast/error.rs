@@ -1,16 +1,57 @@
 use foundry_compilers::error::SolcError;
+use serde_json::Value;
 
+use crate::compiler::output::CompilerError;
 use crate::internal::errors::Error as CoreError;
 
+use super::utils;
+
+/// A node's solc `src` field (`"start:length:fileIndex"`), resolved against the original source
+/// text into a 1-based line/column. See [`AstError::invalid_contract_structure_at`] and
+/// [`utils::locate_span`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+  pub start: u32,
+  pub length: u32,
+  pub file: u32,
+  pub line: u32,
+  pub column: u32,
+}
+
 #[derive(Debug)]
 pub enum AstError {
   ParseFailed(String),
   AnalysisFailed(String),
   NoNodesFound,
   InvalidContractStructure(String),
+  /// Like [`Self::InvalidContractStructure`], but pinpoints the offending node's location in the
+  /// original source. Produced by [`Self::invalid_contract_structure_at`] when source text was
+  /// available to resolve the node's `src` field against.
+  InvalidContractStructureAt(String, SourceSpan),
   JsonError(String),
   CompilerError(String),
   ConfigError(String),
+  /// Solc reported error-severity diagnostics for a parse/fragment compile. Carries the
+  /// structured diagnostics (see [`CoreError::CompilationFailed`]) rather than a pre-joined
+  /// string, so callers can inspect `severity`/`source_location`/etc.
+  CompilationFailed(Vec<CompilerError>),
+}
+
+impl AstError {
+  /// Like [`Self::InvalidContractStructure`], but additionally resolves `node`'s `src` field
+  /// against `source` into a [`SourceSpan`] when both are available (see [`utils::locate_span`]),
+  /// falling back to the unlocated variant otherwise.
+  pub(crate) fn invalid_contract_structure_at(
+    message: impl Into<String>,
+    node: &Value,
+    source: Option<&str>,
+  ) -> Self {
+    let message = message.into();
+    match source.and_then(|source| utils::locate_span(node, source)) {
+      Some(span) => Self::InvalidContractStructureAt(message, span),
+      None => Self::InvalidContractStructure(message),
+    }
+  }
 }
 
 impl std::fmt::Display for AstError {
@@ -20,9 +61,22 @@ impl std::fmt::Display for AstError {
       Self::AnalysisFailed(msg) => write!(f, "Analysis failed: {}", msg),
       Self::NoNodesFound => write!(f, "No nodes found in AST"),
       Self::InvalidContractStructure(msg) => write!(f, "Invalid contract structure: {}", msg),
+      Self::InvalidContractStructureAt(msg, span) => write!(
+        f,
+        "Invalid contract structure: {} (at line {}, column {})",
+        msg, span.line, span.column
+      ),
       Self::JsonError(msg) => write!(f, "JSON error: {}", msg),
       Self::CompilerError(msg) => write!(f, "Compiler error: {}", msg),
       Self::ConfigError(msg) => write!(f, "Invalid AST configuration: {}", msg),
+      Self::CompilationFailed(diagnostics) => {
+        let joined = diagnostics
+          .iter()
+          .map(|diagnostic| diagnostic.formatted_message.as_deref().unwrap_or(&diagnostic.message))
+          .collect::<Vec<_>>()
+          .join("\n");
+        write!(f, "Compiler error: {joined}")
+      }
     }
   }
 }
@@ -43,6 +97,11 @@ impl From<serde_json::Error> for AstError {
 
 impl From<AstError> for CoreError {
   fn from(err: AstError) -> Self {
-    CoreError::new(err.to_string())
+    let message = err.to_string();
+    match err {
+      AstError::ConfigError(_) => CoreError::project_config(message),
+      AstError::CompilationFailed(diagnostics) => CoreError::compilation_failed(message, diagnostics),
+      _ => CoreError::ast(message),
+    }
   }
 }
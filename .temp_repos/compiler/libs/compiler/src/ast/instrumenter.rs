@@ -7,6 +7,7 @@ use serde_json::{Map, Value};
 use crate::internal::errors::{map_err_with_context, Error, Result};
 
 use super::{
+  abi, diagnostics,
   orchestrator::AstOrchestrator,
   parser, stitcher,
   utils::{self},
@@ -18,21 +19,52 @@ enum FunctionSelectorKind {
     name: String,
     signature: Vec<String>,
   },
+  SelectorHash([u8; 4]),
   Name(String),
   Fallback,
   Receive,
   Constructor,
 }
 
+/// Nodes inserted by a single [`inject_edges`]/[`inject_modifier`] call, split by which side of
+/// the targeted function they landed on. Callers use this to record
+/// [`super::provenance::SpanOrigin::EdgeBefore`]/[`super::provenance::SpanOrigin::EdgeAfter`]
+/// provenance without having to re-diff the AST for node ids the instrumenter already knows it
+/// just created.
+#[derive(Default)]
+pub struct InjectedStatements {
+  pub before: Vec<Value>,
+  pub after: Vec<Value>,
+}
+
+/// Builds a span-aware [`Error::diagnostic`] pointing at `node`'s location within `source` (see
+/// [`utils::locate_span`]), falling back to a plain [`Error::new`] when no source text was
+/// supplied or `node`'s `src` field can't be resolved against it.
+fn node_error(message: impl Into<String>, node: &Value, source: Option<&str>) -> Error {
+  let message = message.into();
+  let Some(source) = source else {
+    return Error::new(message);
+  };
+  let Some(span) = utils::locate_span(node, source) else {
+    return Error::new(message);
+  };
+  let start = span.start as usize;
+  let end = start + span.length as usize;
+  let rendered = diagnostics::render_snippet(source, start..end, &message);
+  Error::diagnostic(message, start..end, rendered)
+}
+
 pub fn inject_edges(
   unit: &mut Value,
   contract_idx: usize,
   selector: &str,
   before_snippets: &[String],
   after_snippets: &[String],
+  reject_inline_assembly: bool,
   solc: &Solc,
   settings: &Settings,
-) -> Result<()> {
+  source: Option<&str>,
+) -> Result<InjectedStatements> {
   if before_snippets.is_empty() && after_snippets.is_empty() {
     return Err(Error::new(
       "injectShadowAtEdges requires a `before` and/or `after` snippet.",
@@ -41,49 +73,299 @@ pub fn inject_edges(
 
   let mut next_id = utils::max_id(unit);
 
+  let unit_snapshot = unit.clone();
   let contract = contract_mut_at(unit, contract_idx)?;
   let selector_kind = parse_selector(selector, solc, settings)?;
-  let function = resolve_function_mut(contract, &selector_kind)?;
-
-  let body = function
-    .get_mut("body")
-    .ok_or_else(|| Error::new("Cannot instrument a function without an implementation"))?;
+  let function = resolve_function_mut(&unit_snapshot, contract, &selector_kind, source)?;
 
-  if body.is_null() {
-    return Err(Error::new(
+  if function.get("body").map(Value::is_null).unwrap_or(true) {
+    return Err(node_error(
       "Cannot instrument a function without an implementation",
+      function,
+      source,
     ));
   }
+  let body = function
+    .get_mut("body")
+    .expect("body presence checked above");
 
-  ensure_no_inline_assembly(body)?;
+  if reject_inline_assembly {
+    ensure_no_inline_assembly(body, source)?;
+  }
 
   let before_statements = parse_statements(before_snippets, solc, settings)?;
   let after_statements = parse_statements(after_snippets, solc, settings)?;
 
+  let mut injected = InjectedStatements::default();
+
   if !before_statements.is_empty() || !after_statements.is_empty() {
+    if body.get("statements").and_then(Value::as_array).is_none() {
+      return Err(node_error(
+        "Function body missing statements array",
+        body,
+        source,
+      ));
+    }
     let statements = body
       .get_mut("statements")
       .and_then(|value| value.as_array_mut())
-      .ok_or_else(|| Error::new("Function body missing statements array"))?;
+      .expect("statements array checked above");
 
     if !before_statements.is_empty() {
       let mut clones = clone_statements(&before_statements, &mut next_id);
+      injected.before.extend(clones.iter().cloned());
       for (offset, statement) in clones.drain(..).enumerate() {
         statements.insert(offset, statement);
       }
     }
 
     if !after_statements.is_empty() {
-      inject_after(statements, &after_statements, &mut next_id)?;
+      inject_after(statements, &after_statements, &mut next_id, &mut injected.after)?;
       let mut tail = clone_statements(&after_statements, &mut next_id);
+      injected.after.extend(tail.iter().cloned());
       statements.append(&mut tail);
     }
   }
 
+  Ok(injected)
+}
+
+/// Instruments a function by factoring the before/after statements into a single reusable
+/// `modifier` instead of duplicating them at every exit point (see [`inject_edges`]). When an
+/// identical `before`/`after` pair already targets another function in the same contract, the
+/// existing modifier is reused rather than generating a duplicate.
+pub fn inject_modifier(
+  unit: &mut Value,
+  contract_idx: usize,
+  selector: &str,
+  before_snippets: &[String],
+  after_snippets: &[String],
+  reject_inline_assembly: bool,
+  solc: &Solc,
+  settings: &Settings,
+  source: Option<&str>,
+) -> Result<InjectedStatements> {
+  if before_snippets.is_empty() && after_snippets.is_empty() {
+    return Err(Error::new(
+      "injectShadowAsModifier requires a `before` and/or `after` snippet.",
+    ));
+  }
+
+  let mut next_id = utils::max_id(unit);
+
+  let unit_snapshot = unit.clone();
+  let contract = contract_mut_at(unit, contract_idx)?;
+  let selector_kind = parse_selector(selector, solc, settings)?;
+
+  {
+    let function = resolve_function_mut(&unit_snapshot, contract, &selector_kind, source)?;
+    match function.get("body") {
+      Some(body) if !body.is_null() => {
+        if reject_inline_assembly {
+          ensure_no_inline_assembly(body, source)?;
+        }
+      }
+      _ => {
+        return Err(node_error(
+          "Cannot instrument a function without an implementation",
+          function,
+          source,
+        ));
+      }
+    }
+  }
+
+  let before_statements = parse_statements(before_snippets, solc, settings)?;
+  let after_statements = parse_statements(after_snippets, solc, settings)?;
+
+  let (modifier_name, modifier_id, injected) =
+    find_or_create_modifier(contract, &before_statements, &after_statements, &mut next_id)?;
+
+  let function = resolve_function_mut(&unit_snapshot, contract, &selector_kind, source)?;
+  append_modifier_invocation(function, &modifier_name, modifier_id, &mut next_id)?;
+
+  Ok(injected)
+}
+
+/// Returns the reusable modifier's name and id, plus the statements actually inserted into the
+/// AST on this call -- empty if an existing modifier with matching before/after halves was reused,
+/// since nothing new landed in the tree in that case.
+fn find_or_create_modifier(
+  contract: &mut Value,
+  before: &[Value],
+  after: &[Value],
+  next_id: &mut i64,
+) -> Result<(String, i64, InjectedStatements)> {
+  let nodes = contract
+    .get("nodes")
+    .and_then(|value| value.as_array())
+    .ok_or_else(|| Error::new("Contract has no members to instrument"))?;
+
+  for node in nodes {
+    if node_type(node) != Some("ModifierDefinition") {
+      continue;
+    }
+    let Some((existing_before, existing_after)) = modifier_statement_halves(node) else {
+      continue;
+    };
+    if statements_match(existing_before, before) && statements_match(existing_after, after) {
+      let name = node_name(node).unwrap_or_default().to_string();
+      let id = node.get("id").and_then(Value::as_i64).unwrap_or_default();
+      return Ok((name, id, InjectedStatements::default()));
+    }
+  }
+
+  let existing_names: std::collections::HashSet<&str> =
+    nodes.iter().filter_map(node_name).collect();
+  let mut index = nodes
+    .iter()
+    .filter(|node| node_type(node) == Some("ModifierDefinition"))
+    .count();
+  let mut name = format!("__tevmShadow_{index}");
+  while existing_names.contains(name.as_str()) {
+    index += 1;
+    name = format!("__tevmShadow_{index}");
+  }
+
+  let (modifier, modifier_id, injected) = build_modifier_definition(&name, before, after, next_id);
+  let nodes_mut = contract
+    .get_mut("nodes")
+    .and_then(|value| value.as_array_mut())
+    .ok_or_else(|| Error::new("Contract has no members to instrument"))?;
+  nodes_mut.push(modifier);
+
+  Ok((name, modifier_id, injected))
+}
+
+fn modifier_statement_halves(modifier: &Value) -> Option<(&[Value], &[Value])> {
+  let statements = modifier.get("body")?.get("statements")?.as_array()?;
+  let placeholder_idx = statements
+    .iter()
+    .position(|statement| node_type(statement) == Some("PlaceholderStatement"))?;
+  Some((&statements[..placeholder_idx], &statements[placeholder_idx + 1..]))
+}
+
+fn statements_match(existing: &[Value], candidate: &[Value]) -> bool {
+  if existing.len() != candidate.len() {
+    return false;
+  }
+  existing.iter().zip(candidate.iter()).all(|(a, b)| {
+    match (
+      stitcher::serialise_without_ids(a),
+      stitcher::serialise_without_ids(b),
+    ) {
+      (Ok(a), Ok(b)) => a == b,
+      _ => false,
+    }
+  })
+}
+
+fn build_modifier_definition(
+  name: &str,
+  before: &[Value],
+  after: &[Value],
+  next_id: &mut i64,
+) -> (Value, i64, InjectedStatements) {
+  let before_clones = clone_statements(before, next_id);
+  let mut statements = before_clones.clone();
+  *next_id += 1;
+  statements.push(Value::Object(Map::from_iter([
+    (
+      "nodeType".to_string(),
+      Value::String("PlaceholderStatement".to_string()),
+    ),
+    ("id".to_string(), Value::Number((*next_id).into())),
+    ("src".to_string(), Value::String("0:0:0".to_string())),
+  ])));
+  let after_clones = clone_statements(after, next_id);
+  statements.extend(after_clones.iter().cloned());
+
+  *next_id += 1;
+  let body = serde_json::json!({
+    "nodeType": "Block",
+    "id": *next_id,
+    "src": "0:0:0",
+    "statements": statements,
+  });
+
+  *next_id += 1;
+  let parameters = serde_json::json!({
+    "nodeType": "ParameterList",
+    "id": *next_id,
+    "src": "0:0:0",
+    "parameters": [],
+  });
+
+  *next_id += 1;
+  let modifier_id = *next_id;
+  let modifier = serde_json::json!({
+    "nodeType": "ModifierDefinition",
+    "id": modifier_id,
+    "src": "0:0:0",
+    "name": name,
+    "nameLocation": "0:0:0",
+    "visibility": "internal",
+    "virtual": false,
+    "overrides": Value::Null,
+    "baseModifiers": Value::Array(Vec::new()),
+    "parameters": parameters,
+    "body": body,
+    "documentation": Value::Null,
+  });
+
+  let injected = InjectedStatements {
+    before: before_clones,
+    after: after_clones,
+  };
+  (modifier, modifier_id, injected)
+}
+
+fn append_modifier_invocation(
+  function: &mut Value,
+  modifier_name: &str,
+  modifier_id: i64,
+  next_id: &mut i64,
+) -> Result<()> {
+  let modifiers = function
+    .get_mut("modifiers")
+    .and_then(|value| value.as_array_mut())
+    .ok_or_else(|| Error::new("Function is missing a modifiers array"))?;
+
+  let already_applied = modifiers.iter().any(|invocation| {
+    invocation
+      .get("modifierName")
+      .and_then(|name| name.get("referencedDeclaration"))
+      .and_then(Value::as_i64)
+      == Some(modifier_id)
+  });
+  if already_applied {
+    return Ok(());
+  }
+
+  *next_id += 1;
+  let identifier_id = *next_id;
+  *next_id += 1;
+  let invocation_id = *next_id;
+
+  modifiers.push(serde_json::json!({
+    "nodeType": "ModifierInvocation",
+    "id": invocation_id,
+    "src": "0:0:0",
+    "kind": "modifierInvocation",
+    "arguments": Value::Null,
+    "modifierName": {
+      "nodeType": "IdentifierPath",
+      "id": identifier_id,
+      "name": modifier_name,
+      "referencedDeclaration": modifier_id,
+      "src": "0:0:0",
+    },
+  }));
+
   Ok(())
 }
 
-fn parse_selector(
+pub(crate) fn parse_selector(
   signature: &str,
   solc: &Solc,
   settings: &Settings,
@@ -99,6 +381,10 @@ fn parse_selector(
     return Ok(FunctionSelectorKind::Constructor);
   }
 
+  if let Some(hash) = parse_selector_hash(trimmed) {
+    return Ok(FunctionSelectorKind::SelectorHash(hash));
+  }
+
   if let Some(open) = trimmed.find('(') {
     let close = trimmed
       .rfind(')')
@@ -115,7 +401,10 @@ fn parse_selector(
     )?;
     let function = first_function_definition(&contract)
       .ok_or_else(|| Error::new("Failed to parse function signature"))?;
-    let signature = stitcher::function_signature(function)
+    // The parsed fragment is its own self-contained root: a signature written against a struct or
+    // enum declared on the real target contract can't resolve here, only types spelled out in the
+    // signature itself (elementary types, or a type the fragment happens to redeclare).
+    let signature = abi::canonical_parameter_types(&contract, function, None)
       .map_err(|err| Error::new(format!("Failed to compute function signature: {}", err)))?;
     return Ok(FunctionSelectorKind::Canonical { name, signature });
   }
@@ -123,6 +412,20 @@ fn parse_selector(
   Ok(FunctionSelectorKind::Name(trimmed.to_string()))
 }
 
+/// Parses a 4-byte EVM calldata selector in `0x`-prefixed hex form (e.g. `0xa9059cbb`), the form
+/// most EVM tooling (ABIs, traces, debuggers) identifies a function by.
+fn parse_selector_hash(trimmed: &str) -> Option<[u8; 4]> {
+  let hex_digits = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X"))?;
+  if hex_digits.len() != 8 || !hex_digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+    return None;
+  }
+  let mut selector = [0u8; 4];
+  for (byte, chunk) in selector.iter_mut().zip(hex_digits.as_bytes().chunks(2)) {
+    *byte = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+  }
+  Some(selector)
+}
+
 fn first_function_definition(contract: &Value) -> Option<&Value> {
   contract
     .get("nodes")
@@ -138,13 +441,20 @@ fn first_function_definition(contract: &Value) -> Option<&Value> {
     })
 }
 
-fn resolve_function_mut<'a>(
-  contract: &'a mut Value,
+/// Finds every `FunctionDefinition` index directly under `contract` matching `selector`. `unit` is
+/// the tree used to resolve canonical ABI types for [`FunctionSelectorKind::Canonical`]/
+/// [`FunctionSelectorKind::SelectorHash`] (see [`abi::function_selector`]) -- a
+/// `UserDefinedTypeName` parameter may point at an enum or struct declared outside `contract`
+/// itself, so matching needs the whole source unit, not just the one contract being instrumented.
+fn matching_function_indices(
+  unit: &Value,
+  contract: &Value,
   selector: &FunctionSelectorKind,
-) -> Result<&'a mut Value> {
+  source: Option<&str>,
+) -> Result<Vec<usize>> {
   let nodes = contract
-    .get_mut("nodes")
-    .and_then(|value| value.as_array_mut())
+    .get("nodes")
+    .and_then(|value| value.as_array())
     .ok_or_else(|| Error::new("Contract has no members to instrument"))?;
 
   let mut matches: Vec<usize> = Vec::new();
@@ -171,13 +481,20 @@ fn resolve_function_mut<'a>(
       }
       FunctionSelectorKind::Canonical { name, signature } => {
         if node_name(node) == Some(name.as_str()) {
-          let current_signature =
-            stitcher::function_signature(node).map_err(|err| Error::new(err.to_string()))?;
+          let current_signature = abi::canonical_parameter_types(unit, node, source)
+            .map_err(|err| Error::new(err.to_string()))?;
           if &current_signature == signature {
             matches.push(idx);
           }
         }
       }
+      FunctionSelectorKind::SelectorHash(expected) => {
+        let current = abi::function_selector(unit, node, source)
+          .map_err(|err| Error::new(err.to_string()))?;
+        if current.selector == *expected {
+          matches.push(idx);
+        }
+      }
       FunctionSelectorKind::Name(name) => {
         if node_name(node) == Some(name.as_str()) {
           matches.push(idx);
@@ -186,68 +503,105 @@ fn resolve_function_mut<'a>(
     }
   }
 
+  Ok(matches)
+}
+
+pub(crate) fn resolve_function<'a>(
+  unit: &Value,
+  contract: &'a Value,
+  selector: &FunctionSelectorKind,
+  source: Option<&str>,
+) -> Result<&'a Value> {
+  let matches = matching_function_indices(unit, contract, selector, source)?;
+  let idx = single_match(&matches, contract, source)?;
+  contract
+    .get("nodes")
+    .and_then(|value| value.as_array())
+    .and_then(|nodes| nodes.get(idx))
+    .ok_or_else(|| Error::new("Invalid function index after resolution"))
+}
+
+fn resolve_function_mut<'a>(
+  unit: &Value,
+  contract: &'a mut Value,
+  selector: &FunctionSelectorKind,
+  source: Option<&str>,
+) -> Result<&'a mut Value> {
+  let matches = matching_function_indices(unit, contract, selector, source)?;
+  let idx = single_match(&matches, contract, source)?;
+  contract
+    .get_mut("nodes")
+    .and_then(|value| value.as_array_mut())
+    .and_then(|nodes| nodes.get_mut(idx))
+    .ok_or_else(|| Error::new("Invalid function index after resolution"))
+}
+
+/// Reduces a [`matching_function_indices`] result to the single match it must be, reporting an
+/// empty result as "not found" and reporting more than one as an ambiguous-overload error pointing
+/// at the first match.
+fn single_match(matches: &[usize], contract: &Value, source: Option<&str>) -> Result<usize> {
   if matches.is_empty() {
-    return Err(Error::new(
-      "Target function not found for injectShadowAtEdges.",
-    ));
+    return Err(Error::new("Target function not found."));
   }
 
   if matches.len() > 1 {
-    return Err(Error::new(
-      "Function name is ambiguous. Please provide a full function signature.",
-    ));
+    let message = "Function name is ambiguous. Please provide a full function signature.";
+    let nodes = contract.get("nodes").and_then(|value| value.as_array());
+    return Err(match nodes.and_then(|nodes| nodes.get(matches[0])) {
+      Some(node) => node_error(message, node, source),
+      None => Error::new(message),
+    });
   }
 
-  let idx = matches[0];
-  nodes
-    .get_mut(idx)
-    .ok_or_else(|| Error::new("Invalid function index after resolution"))
+  Ok(matches[0])
 }
 
-fn ensure_no_inline_assembly(body: &Value) -> Result<()> {
+fn ensure_no_inline_assembly(body: &Value, source: Option<&str>) -> Result<()> {
   let statements = body
     .get("statements")
     .and_then(|value| value.as_array())
-    .ok_or_else(|| Error::new("Function body missing statements array"))?;
+    .ok_or_else(|| node_error("Function body missing statements array", body, source))?;
   for statement in statements {
-    ensure_no_inline_assembly_in_statement(statement)?;
+    ensure_no_inline_assembly_in_statement(statement, source)?;
   }
   Ok(())
 }
 
-fn ensure_no_inline_assembly_in_statement(statement: &Value) -> Result<()> {
+fn ensure_no_inline_assembly_in_statement(statement: &Value, source: Option<&str>) -> Result<()> {
   match node_type(statement) {
-    Some("InlineAssembly") => Err(Error::new(
+    Some("InlineAssembly") => Err(node_error(
       "injectShadowAtEdges does not support functions that contain inline assembly.",
+      statement,
+      source,
     )),
     Some("Block") | Some("UncheckedBlock") => {
       let statements = statement
         .get("statements")
         .and_then(|value| value.as_array())
-        .ok_or_else(|| Error::new("Block missing statements array"))?;
+        .ok_or_else(|| node_error("Block missing statements array", statement, source))?;
       for child in statements {
-        ensure_no_inline_assembly_in_statement(child)?;
+        ensure_no_inline_assembly_in_statement(child, source)?;
       }
       Ok(())
     }
     Some("IfStatement") => {
       if let Some(true_body) = statement.get("trueBody") {
-        ensure_no_inline_assembly_in_statement(true_body)?;
+        ensure_no_inline_assembly_in_statement(true_body, source)?;
       }
       if let Some(false_body) = statement.get("falseBody") {
-        ensure_no_inline_assembly_in_statement(false_body)?;
+        ensure_no_inline_assembly_in_statement(false_body, source)?;
       }
       Ok(())
     }
     Some("WhileStatement") | Some("ForStatement") => {
       if let Some(body) = statement.get("body") {
-        ensure_no_inline_assembly_in_statement(body)?;
+        ensure_no_inline_assembly_in_statement(body, source)?;
       }
       Ok(())
     }
     Some("DoWhileStatement") => {
       if let Some(body) = statement.get("body") {
-        ensure_no_inline_assembly_in_statement(body)?;
+        ensure_no_inline_assembly_in_statement(body, source)?;
       }
       Ok(())
     }
@@ -255,7 +609,7 @@ fn ensure_no_inline_assembly_in_statement(statement: &Value) -> Result<()> {
       if let Some(clauses) = statement.get("clauses").and_then(|value| value.as_array()) {
         for clause in clauses {
           if let Some(block) = clause.get("block") {
-            ensure_no_inline_assembly_in_statement(block)?;
+            ensure_no_inline_assembly_in_statement(block, source)?;
           }
         }
       }
@@ -265,33 +619,85 @@ fn ensure_no_inline_assembly_in_statement(statement: &Value) -> Result<()> {
   }
 }
 
-fn parse_statements(snippets: &[String], solc: &Solc, settings: &Settings) -> Result<Vec<Value>> {
-  if snippets.is_empty() {
-    return Ok(Vec::new());
+/// Walks a Yul `InlineAssembly` node's structured `AST` looking for a statement that can exit the
+/// enclosing Solidity function (`return`/`revert`/`stop`/`leave`). Mirrors the recursive shape of
+/// [`inject_after`] so nested `YulIf`/`YulSwitch`/`YulForLoop` bodies are covered the same way
+/// nested Solidity blocks are.
+fn contains_yul_exit(yul_node: &Value) -> bool {
+  match yul_node.get("nodeType").and_then(Value::as_str) {
+    Some("YulBlock") => yul_node
+      .get("statements")
+      .and_then(Value::as_array)
+      .map(|statements| statements.iter().any(contains_yul_exit))
+      .unwrap_or(false),
+    Some("YulExpressionStatement") => yul_node
+      .get("expression")
+      .map(contains_yul_exit)
+      .unwrap_or(false),
+    Some("YulFunctionCall") => matches!(
+      yul_node
+        .get("functionName")
+        .and_then(|name| name.get("name"))
+        .and_then(Value::as_str),
+      Some("return") | Some("revert") | Some("stop")
+    ),
+    Some("YulLeave") => true,
+    Some("YulIf") => yul_node
+      .get("body")
+      .map(contains_yul_exit)
+      .unwrap_or(false),
+    Some("YulSwitch") => yul_node
+      .get("cases")
+      .and_then(Value::as_array)
+      .map(|cases| {
+        cases
+          .iter()
+          .filter_map(|case| case.get("body"))
+          .any(contains_yul_exit)
+      })
+      .unwrap_or(false),
+    Some("YulForLoop") => yul_node
+      .get("body")
+      .map(contains_yul_exit)
+      .unwrap_or(false),
+    _ => false,
   }
+}
 
-  let joined = snippets
-    .iter()
-    .map(|snippet| snippet.trim())
-    .filter(|snippet| !snippet.is_empty())
-    .collect::<Vec<_>>();
+/// Parses every snippet in `snippets` as its own fragment rather than bailing on the first
+/// failure, so a caller with several `before`/`after` entries learns about every broken one in a
+/// single pass instead of just the first. Statement lists from the successful snippets are only
+/// stitched together (in order) once every snippet has parsed; otherwise an aggregate error
+/// enumerating each failing snippet's index and message is returned.
+fn parse_statements(snippets: &[String], solc: &Solc, settings: &Settings) -> Result<Vec<Value>> {
+  let mut statements = Vec::new();
+  let mut failures: Vec<(usize, Error)> = Vec::new();
+
+  for (index, snippet) in snippets.iter().enumerate() {
+    let trimmed = snippet.trim();
+    if trimmed.is_empty() {
+      continue;
+    }
 
-  if joined.is_empty() {
-    return Ok(Vec::new());
+    match parse_statement_snippet(trimmed, solc, settings) {
+      Ok(mut parsed) => statements.append(&mut parsed),
+      Err(err) => failures.push((index, err)),
+    }
   }
 
-  let mut fragment_lines = Vec::new();
-  fragment_lines.push("  function __TevmShadow() internal {".to_string());
-  fragment_lines.push(
-    joined
-      .iter()
-      .map(|snippet| format!("    {}", snippet))
-      .collect::<Vec<_>>()
-      .join("\n"),
-  );
-  fragment_lines.push("  }".to_string());
+  if !failures.is_empty() {
+    return Err(aggregate_snippet_errors(&failures));
+  }
 
-  let fragment = fragment_lines.join("\n");
+  Ok(statements)
+}
+
+pub(crate) fn parse_statement_snippet(
+  snippet: &str,
+  solc: &Solc,
+  settings: &Settings,
+) -> Result<Vec<Value>> {
+  let fragment = format!("  function __TevmShadow() internal {{\n    {}\n  }}", snippet);
 
   let contract = parse_fragment_contract(&fragment, solc, settings)?;
   let function = first_function_definition(&contract)
@@ -311,46 +717,82 @@ fn parse_statements(snippets: &[String], solc: &Solc, settings: &Settings) -> Re
   Ok(statements.to_vec())
 }
 
-fn clone_statements(statements: &[Value], next_id: &mut i64) -> Vec<Value> {
+fn aggregate_snippet_errors(failures: &[(usize, Error)]) -> Error {
+  let detail = failures
+    .iter()
+    .map(|(index, err)| format!("  snippet #{index}: {err}"))
+    .collect::<Vec<_>>()
+    .join("\n");
+  Error::new(format!(
+    "{} instrumentation snippet(s) failed to parse:\n{}",
+    failures.len(),
+    detail
+  ))
+}
+
+pub(crate) fn clone_statements(statements: &[Value], next_id: &mut i64) -> Vec<Value> {
   statements
     .iter()
     .map(|statement| utils::clone_with_new_ids(statement, next_id))
     .collect()
 }
 
-fn inject_after(statements: &mut Vec<Value>, template: &[Value], next_id: &mut i64) -> Result<()> {
+pub(crate) fn inject_after(
+  statements: &mut Vec<Value>,
+  template: &[Value],
+  next_id: &mut i64,
+  injected: &mut Vec<Value>,
+) -> Result<()> {
   let mut idx = 0;
   while idx < statements.len() {
     let node_type = node_type(&statements[idx]);
     match node_type {
       Some("Return") => {
         let clones = clone_statements(template, next_id);
+        injected.extend(clones.iter().cloned());
         let len = clones.len();
         for (offset, clone) in clones.into_iter().enumerate() {
           statements.insert(idx + offset, clone);
         }
         idx += len + 1;
       }
+      Some("InlineAssembly") => {
+        let exits = statements[idx]
+          .get("AST")
+          .map(contains_yul_exit)
+          .unwrap_or(false);
+        if exits {
+          let clones = clone_statements(template, next_id);
+          injected.extend(clones.iter().cloned());
+          let len = clones.len();
+          for (offset, clone) in clones.into_iter().enumerate() {
+            statements.insert(idx + offset, clone);
+          }
+          idx += len + 1;
+        } else {
+          idx += 1;
+        }
+      }
       Some("Block") | Some("UncheckedBlock") => {
         let block_statements = statements[idx]
           .get_mut("statements")
           .and_then(|value| value.as_array_mut())
           .ok_or_else(|| Error::new("Block missing statements array"))?;
-        inject_after(block_statements, template, next_id)?;
+        inject_after(block_statements, template, next_id, injected)?;
         idx += 1;
       }
       Some("IfStatement") => {
         if let Some(true_body) = statements[idx].get_mut("trueBody") {
-          inject_into_block_or_statement(true_body, template, next_id)?;
+          inject_into_block_or_statement(true_body, template, next_id, injected)?;
         }
         if let Some(false_body) = statements[idx].get_mut("falseBody") {
-          inject_into_block_or_statement(false_body, template, next_id)?;
+          inject_into_block_or_statement(false_body, template, next_id, injected)?;
         }
         idx += 1;
       }
       Some("WhileStatement") | Some("ForStatement") => {
         if let Some(body) = statements[idx].get_mut("body") {
-          inject_into_block_or_statement(body, template, next_id)?;
+          inject_into_block_or_statement(body, template, next_id, injected)?;
         }
         idx += 1;
       }
@@ -360,7 +802,7 @@ fn inject_after(statements: &mut Vec<Value>, template: &[Value], next_id: &mut i
             .get_mut("statements")
             .and_then(|value| value.as_array_mut())
             .ok_or_else(|| Error::new("DoWhile body missing statements array"))?;
-          inject_after(block_statements, template, next_id)?;
+          inject_after(block_statements, template, next_id, injected)?;
         }
         idx += 1;
       }
@@ -375,7 +817,7 @@ fn inject_after(statements: &mut Vec<Value>, template: &[Value], next_id: &mut i
                 .get_mut("statements")
                 .and_then(|value| value.as_array_mut())
                 .ok_or_else(|| Error::new("Try clause block missing statements array"))?;
-              inject_after(block_statements, template, next_id)?;
+              inject_after(block_statements, template, next_id, injected)?;
             }
           }
         }
@@ -393,24 +835,25 @@ fn inject_into_block_or_statement(
   node: &mut Value,
   template: &[Value],
   next_id: &mut i64,
+  injected: &mut Vec<Value>,
 ) -> Result<()> {
   if node_type(node) == Some("Block") || node_type(node) == Some("UncheckedBlock") {
     let statements = node
       .get_mut("statements")
       .and_then(|value| value.as_array_mut())
       .ok_or_else(|| Error::new("Block missing statements array"))?;
-    inject_after(statements, template, next_id)
+    inject_after(statements, template, next_id, injected)
   } else {
     ensure_block(node, next_id)?;
     let statements = node
       .get_mut("statements")
       .and_then(|value| value.as_array_mut())
       .ok_or_else(|| Error::new("Converted block missing statements array"))?;
-    inject_after(statements, template, next_id)
+    inject_after(statements, template, next_id, injected)
   }
 }
 
-fn ensure_block(node: &mut Value, next_id: &mut i64) -> Result<()> {
+pub(crate) fn ensure_block(node: &mut Value, next_id: &mut i64) -> Result<()> {
   if node_type(node) == Some("Block") || node_type(node) == Some("UncheckedBlock") {
     return Ok(());
   }
@@ -434,7 +877,7 @@ fn ensure_block(node: &mut Value, next_id: &mut i64) -> Result<()> {
 }
 
 fn parse_fragment_contract(fragment: &str, solc: &Solc, settings: &Settings) -> Result<Value> {
-  let wrapped = parser::wrap_fragment_source(fragment);
+  let (wrapped, prologue_offset) = parser::wrap_fragment_source_with_offset(fragment);
   let mut sources = Sources::new();
   sources.insert(PathBuf::from("__AstFragment.sol"), Source::new(&wrapped));
 
@@ -446,6 +889,16 @@ fn parse_fragment_contract(fragment: &str, solc: &Solc, settings: &Settings) ->
     "Failed to parse instrumented snippet",
   )?;
 
+  if let Some(errors) = compiler_output
+    .get("errors")
+    .and_then(|value| value.as_array())
+  {
+    if let Some(err) = diagnostics::diagnostic_from_solc_errors(errors, fragment, prologue_offset)
+    {
+      return Err(err);
+    }
+  }
+
   let ast_value = compiler_output
     .get("sources")
     .and_then(|sources| sources.get("__AstFragment.sol"))
@@ -458,7 +911,7 @@ fn parse_fragment_contract(fragment: &str, solc: &Solc, settings: &Settings) ->
   Ok(contract)
 }
 
-fn contract_mut_at<'a>(unit: &'a mut Value, idx: usize) -> Result<&'a mut Value> {
+pub(crate) fn contract_mut_at<'a>(unit: &'a mut Value, idx: usize) -> Result<&'a mut Value> {
   let nodes = unit
     .get_mut("nodes")
     .and_then(|value| value.as_array_mut())
@@ -521,7 +974,7 @@ mod tests {
     })];
 
     let mut next_id = 0;
-    inject_after(&mut statements, &template, &mut next_id).expect("inject");
+    inject_after(&mut statements, &template, &mut next_id, &mut Vec::new()).expect("inject");
 
     assert_eq!(statements.len(), 2);
     assert_eq!(statements[0]["nodeType"], "ExpressionStatement");
@@ -539,16 +992,194 @@ mod tests {
       ]
     });
 
-    let err = ensure_no_inline_assembly(&block);
+    let err = ensure_no_inline_assembly(&block, None);
     assert!(err.is_err());
   }
 
+  #[test]
+  fn ensure_no_inline_assembly_reports_span_when_source_is_available() {
+    let source = "contract C {\n  function f() public {\n    assembly {}\n  }\n}\n";
+    let block = json!({
+      "nodeType": "Block",
+      "statements": [
+        { "nodeType": "InlineAssembly", "src": "41:11:0" }
+      ]
+    });
+
+    let err = ensure_no_inline_assembly(&block, Some(source)).unwrap_err();
+    assert_eq!(err.span(), Some(41..52));
+    assert!(err.rendered().unwrap().contains("assembly {}"));
+    assert!(err
+      .message()
+      .contains("does not support functions that contain inline assembly"));
+  }
+
+  #[test]
+  fn resolve_function_mut_reports_span_for_ambiguous_overload() {
+    let source = "contract C {\n  function dup() public {}\n  function dup() public {}\n}\n";
+    let mut contract = json!({
+      "nodeType": "ContractDefinition",
+      "nodes": [
+        { "nodeType": "FunctionDefinition", "name": "dup", "src": "15:24:0" },
+        { "nodeType": "FunctionDefinition", "name": "dup", "src": "42:24:0" },
+      ]
+    });
+
+    let unit = json!({ "nodeType": "SourceUnit", "nodes": [contract.clone()] });
+    let err = resolve_function_mut(
+      &unit,
+      &mut contract,
+      &FunctionSelectorKind::Name("dup".to_string()),
+      Some(source),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.span(), Some(15..39));
+    assert!(err.message().contains("ambiguous"));
+  }
+
+  #[test]
+  fn resolve_function_mut_matches_real_on_chain_selector_hash() {
+    let mut contract = json!({
+      "nodeType": "ContractDefinition",
+      "nodes": [
+        {
+          "nodeType": "FunctionDefinition",
+          "name": "transfer",
+          "parameters": {
+            "parameters": [
+              { "typeName": { "nodeType": "ElementaryTypeName", "name": "address" } },
+              { "typeName": { "nodeType": "ElementaryTypeName", "name": "uint256" } },
+            ],
+          },
+        },
+        {
+          "nodeType": "FunctionDefinition",
+          "name": "approve",
+          "parameters": {
+            "parameters": [
+              { "typeName": { "nodeType": "ElementaryTypeName", "name": "address" } },
+              { "typeName": { "nodeType": "ElementaryTypeName", "name": "uint256" } },
+            ],
+          },
+        },
+      ]
+    });
+    let unit = json!({ "nodeType": "SourceUnit", "nodes": [contract.clone()] });
+
+    let function = resolve_function_mut(
+      &unit,
+      &mut contract,
+      &FunctionSelectorKind::SelectorHash([0xa9, 0x05, 0x9c, 0xbb]),
+      None,
+    )
+    .expect("selector hash should resolve to `transfer`");
+
+    assert_eq!(function["name"], "transfer");
+  }
+
+  #[test]
+  fn contains_yul_exit_detects_leave_and_exit_builtins() {
+    let leave = json!({
+      "nodeType": "YulBlock",
+      "statements": [
+        { "nodeType": "YulLeave" }
+      ]
+    });
+    assert!(contains_yul_exit(&leave));
+
+    let revert = json!({
+      "nodeType": "YulBlock",
+      "statements": [
+        {
+          "nodeType": "YulExpressionStatement",
+          "expression": {
+            "nodeType": "YulFunctionCall",
+            "functionName": { "name": "revert" }
+          }
+        }
+      ]
+    });
+    assert!(contains_yul_exit(&revert));
+
+    let plain = json!({
+      "nodeType": "YulBlock",
+      "statements": [
+        {
+          "nodeType": "YulAssignment",
+          "value": {
+            "nodeType": "YulFunctionCall",
+            "functionName": { "name": "add" }
+          }
+        }
+      ]
+    });
+    assert!(!contains_yul_exit(&plain));
+  }
+
+  #[test]
+  fn inject_after_skips_inline_assembly_without_exit() {
+    let mut statements = vec![
+      json!({
+        "nodeType": "InlineAssembly",
+        "AST": {
+          "nodeType": "YulBlock",
+          "statements": [
+            {
+              "nodeType": "YulAssignment",
+              "value": { "nodeType": "YulFunctionCall", "functionName": { "name": "add" } }
+            }
+          ]
+        }
+      }),
+      json!({ "nodeType": "Return" }),
+    ];
+    let template = vec![json!({
+      "nodeType": "ExpressionStatement",
+      "expression": { "nodeType": "Identifier", "name": "probe" }
+    })];
+
+    let mut next_id = 0;
+    inject_after(&mut statements, &template, &mut next_id, &mut Vec::new()).expect("inject");
+
+    assert_eq!(statements.len(), 3);
+    assert_eq!(statements[0]["nodeType"], "InlineAssembly");
+    assert_eq!(statements[1]["nodeType"], "ExpressionStatement");
+    assert_eq!(statements[2]["nodeType"], "Return");
+  }
+
+  #[test]
+  fn inject_after_lifts_template_before_exiting_assembly() {
+    let mut statements = vec![json!({
+      "nodeType": "InlineAssembly",
+      "AST": {
+        "nodeType": "YulBlock",
+        "statements": [
+          { "nodeType": "YulLeave" }
+        ]
+      }
+    })];
+    let template = vec![json!({
+      "nodeType": "ExpressionStatement",
+      "expression": { "nodeType": "Identifier", "name": "probe" }
+    })];
+
+    let mut next_id = 0;
+    inject_after(&mut statements, &template, &mut next_id, &mut Vec::new()).expect("inject");
+
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0]["nodeType"], "ExpressionStatement");
+    assert_eq!(statements[1]["nodeType"], "InlineAssembly");
+  }
+
   #[test]
   fn parse_selector_parses_canonical_signature() {
     let Some(solc) = find_default_solc() else {
       return;
     };
-    let settings = AstOrchestrator::sanitize_settings(None).expect("default settings");
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("default settings");
 
     let selector =
       parse_selector("tapStored(uint256 value)", &solc, &settings).expect("parse selector");
@@ -564,4 +1195,85 @@ mod tests {
       other => panic!("expected canonical selector, found {:?}", other),
     }
   }
+
+  #[test]
+  fn parse_selector_hash_parses_0x_prefixed_four_byte_hex() {
+    assert_eq!(parse_selector_hash("0xa9059cbb"), Some([0xa9, 0x05, 0x9c, 0xbb]));
+    assert_eq!(parse_selector_hash("0XA9059CBB"), Some([0xa9, 0x05, 0x9c, 0xbb]));
+    assert_eq!(parse_selector_hash("0xa9059c"), None, "too short");
+    assert_eq!(parse_selector_hash("0xa9059cbbff"), None, "too long");
+    assert_eq!(parse_selector_hash("0xgggggggg"), None, "non-hex digits");
+    assert_eq!(parse_selector_hash("transfer"), None, "not hex-prefixed");
+  }
+
+  #[test]
+  fn parse_selector_resolves_selector_hash_variant() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("default settings");
+
+    let selector = parse_selector("0xa9059cbb", &solc, &settings).expect("parse selector");
+    assert!(matches!(
+      selector,
+      FunctionSelectorKind::SelectorHash([0xa9, 0x05, 0x9c, 0xbb])
+    ));
+  }
+
+  #[test]
+  fn parse_statements_reports_every_failing_snippet() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("default settings");
+
+    let snippets = vec![
+      "uint256 __ok = 1;".to_string(),
+      "uint256 __bad = ;".to_string(),
+      "another bad snippet !!".to_string(),
+    ];
+
+    let err = parse_statements(&snippets, &solc, &settings).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("snippet #1"), "missing snippet #1: {message}");
+    assert!(message.contains("snippet #2"), "missing snippet #2: {message}");
+    assert!(!message.contains("snippet #0"), "snippet #0 should have parsed fine: {message}");
+  }
+
+  #[test]
+  fn parse_statements_stitches_successful_snippets_in_order() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("default settings");
+
+    let snippets = vec![
+      "uint256 __first = 1;".to_string(),
+      "uint256 __second = 2;".to_string(),
+    ];
+
+    let statements = parse_statements(&snippets, &solc, &settings).expect("parse statements");
+    assert_eq!(statements.len(), 2);
+    assert!(json_contains_name(&statements[0], "__first"));
+    assert!(json_contains_name(&statements[1], "__second"));
+  }
+
+  fn json_contains_name(value: &Value, name: &str) -> bool {
+    match value {
+      Value::Object(map) => {
+        if map.get("name").and_then(Value::as_str) == Some(name) {
+          return true;
+        }
+        map.values().any(|child| json_contains_name(child, name))
+      }
+      Value::Array(items) => items.iter().any(|child| json_contains_name(child, name)),
+      _ => false,
+    }
+  }
 }
@@ -1,12 +1,26 @@
 use napi::bindgen_prelude::*;
-use napi::{Env, JsObject, JsUnknown, ValueType};
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction, JsObject, JsUnknown, Task, ValueType};
+use semver::Version;
 use serde_json::Value;
 
+mod abi;
+mod cache;
 pub mod core;
+mod coverage;
+mod diagnostics;
+mod emitter;
 mod error;
 mod instrumenter;
+#[allow(dead_code)]
+mod node;
 pub(crate) mod orchestrator;
 pub(crate) mod parser;
+mod passes;
+mod pipeline_cache;
+mod provenance;
+mod query;
+mod repl;
 mod stitcher;
 pub(crate) mod utils;
 
@@ -14,10 +28,22 @@ pub(crate) mod utils;
 mod ast_tests;
 
 use core::{
-  compile_output, expose_internal_functions, expose_internal_variables, from_source, init,
-  inject_shadow, inject_shadow_at_edges, source_unit, source_unit_mut, validate,
+  compile_output, emit_source, expose_internal_functions, expose_internal_variables, from_source,
+  init, inject_shadow, inject_shadow_at_edges, inject_shadow_as_modifier, instrument_coverage,
+  provenance as state_provenance, query_nodes, run_passes, source_map as state_source_map,
+  source_unit, source_unit_mut, stitch_conflicts as state_stitch_conflicts,
+  stitch_report as state_stitch_report, validate, validate_matrix, visit_nodes,
 };
-pub use core::{FragmentTarget, SourceTarget, State};
+pub use abi::FunctionSelector;
+pub use core::{incompatible_versions, FragmentTarget, NodeLocator, SourceTarget, State};
+pub use coverage::{CoverageMap, CoverageProbe, CoverageProbeKind, CoverageSiteKind};
+use error::AstError;
+pub use parser::decode_source_map;
+pub use passes::{AstPass, ExposeFunctionsPass, ExposeVariablesPass};
+pub use provenance::{InjectedSpan, SourceMapRegion, SourceMapRegionKind, SpanOrigin};
+pub use query::{NodeKind, NodeSelector, NodeVisibility, QueryMatch, VisitAction};
+pub use repl::{JsRepl, Repl, ReplOutcome};
+pub use stitcher::{StitchAction, StitchConflict, StitchReport, StitchedMember};
 use utils::{from_js_value, to_js_value};
 
 use crate::compiler::output::{into_js_compile_output, CompileOutput, JsCompileOutput};
@@ -65,6 +91,32 @@ impl Ast {
     Ok(self)
   }
 
+  /// Derives the canonical ABI signature and 4-byte calldata selector for the function `selector`
+  /// resolves to, accepting the same locator syntax as [`Self::inject_shadow_at_edges`] -- a bare
+  /// name, a full `name(types)` signature, a `0x`-prefixed selector, or
+  /// `fallback`/`receive`/`constructor`. See [`core::function_selector`].
+  pub fn function_selector(
+    &self,
+    selector: &str,
+    options: Option<AstConfigOptions>,
+  ) -> Result<abi::FunctionSelector> {
+    core::function_selector(&self.state, selector, options.as_ref())
+  }
+
+  /// Factors `before`/`after` statements into a single reusable `modifier` instead of duplicating
+  /// them at every exit point. See [`Self::inject_shadow_at_edges`] for the edge-injection
+  /// alternative; unlike edges, statements after `_;` are skipped on `revert`.
+  pub fn inject_shadow_as_modifier(
+    &mut self,
+    selector: &str,
+    before: &[String],
+    after: &[String],
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    inject_shadow_as_modifier(&mut self.state, selector, before, after, options.as_ref())?;
+    Ok(self)
+  }
+
   pub fn expose_internal_variables(
     &mut self,
     options: Option<AstConfigOptions>,
@@ -81,6 +133,19 @@ impl Ast {
     Ok(self)
   }
 
+  /// Runs `passes` over every direct member of the targeted contract(s), dispatching each member
+  /// to every pass in order. See [`core::run_passes`] and [`AstPass`] for the extension point
+  /// [`Self::expose_internal_variables`]/[`Self::expose_internal_functions`] are themselves built
+  /// on top of.
+  pub fn run_passes(
+    &mut self,
+    passes: &mut [Box<dyn AstPass>],
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    run_passes(&mut self.state, passes, options.as_ref())?;
+    Ok(self)
+  }
+
   /// Compile the current AST to ensure it represents a valid contract and refresh its references.
   /// This is optional—`sourceUnit()` already returns the parsed tree you can work with directly.
   pub fn validate(&mut self) -> Result<&mut Self> {
@@ -92,6 +157,14 @@ impl Ast {
     compile_output(&mut self.state)
   }
 
+  /// Compiles the current AST against every version in `versions`, in parallel, pinning each
+  /// compile to that version instead of the configured `solcVersion`. See [`core::validate_matrix`]
+  /// and [`core::incompatible_versions`] for narrowing the result down to the versions that
+  /// rejected it.
+  pub fn validate_matrix(&mut self, versions: &[Version]) -> Result<Vec<(Version, CompileOutput)>> {
+    validate_matrix(&mut self.state, versions, None)
+  }
+
   pub fn source_unit(&self) -> Result<&Value> {
     source_unit(&self.state).ok_or_else(|| {
       crate::internal::errors::Error::new("Ast has no target unit. Call from_source first.")
@@ -104,6 +177,53 @@ impl Ast {
     })
   }
 
+  /// Collects every node matching `selector`. See [`query::query`].
+  pub fn query(&self, selector: &NodeSelector) -> Result<Vec<QueryMatch>> {
+    query_nodes(&self.state, selector)
+  }
+
+  /// Runs `callback` over every node matching `selector`, splicing back replacements in place.
+  /// See [`query::visit`].
+  pub fn visit(
+    &mut self,
+    selector: &NodeSelector,
+    callback: impl FnMut(&QueryMatch) -> Result<VisitAction, AstError>,
+  ) -> Result<usize> {
+    visit_nodes(&mut self.state, selector, callback)
+  }
+
+  /// Instruments every function in the target contract with coverage probes, built on the same
+  /// edge-injection machinery as [`Self::inject_shadow_at_edges`]. See [`coverage::instrument`]
+  /// for the sites covered.
+  pub fn instrument_coverage(
+    &mut self,
+    mode: CoverageProbeKind,
+    options: Option<AstConfigOptions>,
+  ) -> Result<CoverageMap> {
+    instrument_coverage(&mut self.state, mode, options.as_ref())
+  }
+
+  /// Replaces the whole source unit. See [`core::set_source_unit`].
+  pub fn set_source_unit(&mut self, unit: Value, options: Option<AstConfigOptions>) -> Result<()> {
+    core::set_source_unit(&mut self.state, unit, options.as_ref())
+  }
+
+  /// Replaces a single node keyed by [`NodeLocator`]. See [`core::set_node_at`].
+  pub fn set_node_at(&mut self, locator: &NodeLocator, node: Value) -> Result<()> {
+    core::set_node_at(&mut self.state, locator, node)
+  }
+
+  /// Removes a single node keyed by [`NodeLocator`]. See [`core::remove_node`].
+  pub fn remove_node(&mut self, locator: &NodeLocator) -> Result<()> {
+    core::remove_node(&mut self.state, locator)
+  }
+
+  /// Pretty-prints the current source unit back into Solidity source. See [`core::emit_source`]
+  /// for which node types are currently understood.
+  pub fn emit_source(&self, options: Option<AstConfigOptions>) -> Result<String> {
+    emit_source(&self.state, options.as_ref())
+  }
+
   pub fn config(&self) -> &AstConfig {
     &self.state.config
   }
@@ -115,6 +235,33 @@ impl Ast {
   pub fn into_state(self) -> State {
     self.state
   }
+
+  /// Per-node provenance for everything [`Self::inject_shadow`]/[`Self::inject_shadow_at_edges`]/
+  /// [`Self::inject_shadow_as_modifier`] have inserted so far. See [`core::provenance`].
+  pub fn provenance(&self) -> &[InjectedSpan] {
+    state_provenance(&self.state)
+  }
+
+  /// Name collisions resolved so far by `inject_shadow`/`inject_fragment*` under
+  /// [`crate::internal::config::ResolveConflictStrategy::Overwrite`]/`Rename`/`KeepBoth`. See
+  /// [`core::stitch_conflicts`].
+  pub fn stitch_conflicts(&self) -> &[StitchConflict] {
+    state_stitch_conflicts(&self.state)
+  }
+
+  /// Structured audit of everything `inject_shadow`/`inject_fragment*` have applied so far: which
+  /// members were appended, replaced, or skipped, and how many ids were reassigned along the way.
+  /// See [`core::stitch_report`].
+  pub fn stitch_report(&self) -> &StitchReport {
+    state_stitch_report(&self.state)
+  }
+
+  /// Maps every node of the current (instrumented) source unit back to its counterpart in the
+  /// source loaded via [`Self::from_source`], tagging synthesized regions with no original
+  /// counterpart as such. See [`core::source_map`].
+  pub fn source_map(&self) -> Result<Vec<SourceMapRegion>> {
+    state_source_map(&self.state)
+  }
 }
 
 /// High-level helper for manipulating Solidity ASTs prior to recompilation.
@@ -172,6 +319,40 @@ impl JsAst {
     Ok(self.clone())
   }
 
+  /// Async counterpart of [`Self::from_source`] that runs parsing on the libuv thread pool instead
+  /// of blocking the event loop, which matters for large sources. Because the work runs off-thread
+  /// against a snapshot of the current state, the promise resolves with a *new* `Ast` carrying the
+  /// parsed result rather than mutating `this` in place -- chain off the resolved value instead of
+  /// `this`. Pass `onProgress` to receive `"parse"`/`"parsed"` stage notifications.
+  #[napi(
+    ts_args_type = "target: string | object, options?: AstConfigOptions | undefined, onProgress?: (stage: string) => void",
+    ts_return_type = "Promise<Ast>"
+  )]
+  pub fn from_source_async(
+    &self,
+    env: Env,
+    target: Either<String, JsObject>,
+    options: Option<JsUnknown>,
+    on_progress: Option<JsFunction>,
+  ) -> napi::Result<AsyncTask<FromSourceTask>> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let target = parse_source_target(&env, target)?;
+    let progress = on_progress
+      .as_ref()
+      .map(|callback| create_stage_callback(&env, callback))
+      .transpose()?;
+    Ok(AsyncTask::new(FromSourceTask {
+      state: self.inner.state.clone(),
+      target,
+      overrides,
+      progress,
+    }))
+  }
+
   /// Parse an AST fragment from source text or inject a pre-parsed AST fragment into the targeted
   /// contract.
   #[napi(
@@ -220,6 +401,76 @@ impl JsAst {
     Ok(self.clone())
   }
 
+  /// Derive the canonical ABI signature and 4-byte calldata selector for a function, accepting the
+  /// same locator syntax as `injectShadowAtEdges` -- a bare name, a full `name(types)` signature, a
+  /// `0x`-prefixed selector, or `fallback`/`receive`/`constructor`. Useful to disambiguate
+  /// overloads by their exact on-chain selector instead of solc's internal type names.
+  #[napi(ts_args_type = "selector: string, options?: AstConfigOptions | undefined")]
+  pub fn function_selector(
+    &self,
+    env: Env,
+    selector: String,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsFunctionSelector> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let selector = to_napi_result(self.inner.function_selector(&selector, overrides))?;
+    Ok(to_js_function_selector(&selector))
+  }
+
+  /// Factor `before`/`after` statements into a single reusable `modifier` applied to the target
+  /// function, instead of duplicating them at every exit point like `injectShadowAtEdges` does.
+  /// An identical `before`/`after` pair targeting another function in the same contract reuses the
+  /// generated modifier. Note the semantic difference: code after `_;` runs on normal exits but is
+  /// skipped when the function reverts.
+  #[napi(
+    ts_args_type = "selector: string, options: { before?: string | string[], after?: string | string[] } & AstConfigOptions",
+    ts_return_type = "this"
+  )]
+  pub fn inject_shadow_as_modifier(
+    &mut self,
+    env: Env,
+    selector: String,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let (before, after, overrides) = parse_edges_options(&env, options)?;
+    if before.is_empty() && after.is_empty() {
+      return Err(napi_error(
+        "injectShadowAsModifier requires a `before` and/or `after` snippet.",
+      ));
+    }
+    to_napi_result(
+      self
+        .inner
+        .inject_shadow_as_modifier(&selector, &before, &after, overrides),
+    )?;
+    Ok(self.clone())
+  }
+
+  /// Instrument every function in the target contract with coverage probes, built on the same
+  /// edge injection `injectShadowAtEdges` uses. `mode` selects how probes report: `"counter"`
+  /// (default) appends a fixed-size `uint256[N]` storage array (one slot per probe) plus a
+  /// generated `coverageHits()` getter;
+  /// `"event"` appends an event and has probes `emit` it instead. Returns a map from probe id to
+  /// its originating contract/function/site so recorded hits can be resolved back to source; the
+  /// ids are baked into the AST as literals and stay stable across later recompiles of this unit.
+  #[napi(
+    ts_args_type = "options?: { mode?: 'counter' | 'event' } & AstConfigOptions",
+    ts_return_type = "AstCoverageMap"
+  )]
+  pub fn instrument_coverage(
+    &mut self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsCoverageMap> {
+    let (mode, overrides) = parse_coverage_options(&env, options)?;
+    let map = to_napi_result(self.inner.instrument_coverage(mode, overrides))?;
+    Ok(to_js_coverage_map(map))
+  }
+
   /// Promote private/internal state variables to public visibility. Omitting `instrumentedContract`
   /// applies the change to all contracts.
   #[napi(
@@ -268,6 +519,20 @@ impl JsAst {
     Ok(self.clone())
   }
 
+  /// Pretty-prints the current source unit back into Solidity source, so instrumentation applied
+  /// through `injectShadow`/`exposeInternalFunctions`/etc. can be inspected or exported without
+  /// recompiling. Errors if the AST contains a node type the emitter doesn't yet reconstruct
+  /// (inline assembly, try/catch, and other rarely-instrumented shapes).
+  #[napi(js_name = "emitSource", ts_args_type = "options?: AstConfigOptions | undefined")]
+  pub fn emit_source(&self, env: Env, options: Option<JsUnknown>) -> napi::Result<String> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    to_napi_result(self.inner.emit_source(overrides))
+  }
+
   /// Compile the current AST with the constructor options into a CompileOutput.
   #[napi(
     js_name = "compile",
@@ -278,8 +543,32 @@ impl JsAst {
     Ok(into_js_compile_output(output))
   }
 
+  /// Async counterpart of [`Self::compile`] that runs `solc` on the libuv thread pool instead of
+  /// blocking the event loop. Operates on a snapshot of the current state taken when the promise
+  /// is created, so it does not observe mutations made to `this` afterwards. Pass `onProgress` to
+  /// receive a `"compile"`/`"compiled"` stage notification.
+  #[napi(
+    js_name = "compileAsync",
+    ts_args_type = "onProgress?: (stage: string) => void",
+    ts_return_type = "Promise<CompileOutput<true, undefined> | CompileOutput<false, undefined>>"
+  )]
+  pub fn compile_async(
+    &self,
+    env: Env,
+    on_progress: Option<JsFunction>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    let progress = on_progress
+      .as_ref()
+      .map(|callback| create_stage_callback(&env, callback))
+      .transpose()?;
+    Ok(AsyncTask::new(CompileTask {
+      state: self.inner.state.clone(),
+      progress,
+    }))
+  }
+
   /// Get the current instrumented AST.
-  #[napi(ts_return_type = "import('./solc-ast').SourceUnit")]
+  #[napi(getter, ts_return_type = "import('./solc-ast').SourceUnit")]
   pub fn source_unit(&self, env: Env) -> napi::Result<JsUnknown> {
     let ast = self
       .inner
@@ -287,6 +576,468 @@ impl JsAst {
       .map_err(|err| napi_error(err.to_string()))?;
     to_js_value(&env, ast)
   }
+
+  /// Replace the whole source unit with `unit`, re-validating that the configured contract still
+  /// exists before committing it. Throws if `unit` isn't structurally sound (e.g. missing the
+  /// instrumented contract), leaving the previous AST untouched.
+  #[napi(setter, js_name = "sourceUnit", ts_args_type = "unit: import('./solc-ast').SourceUnit")]
+  pub fn set_source_unit(&mut self, env: Env, unit: JsUnknown) -> napi::Result<()> {
+    let unit = from_js_value(&env, unit)?;
+    to_napi_result(self.inner.set_source_unit(unit, None))
+  }
+
+  /// Replace a single node, located either by its stable solc `id` or by an RFC 6901 JSON pointer
+  /// path (as reported by [`Self::query`]/[`Self::visit`] matches). Throws if `node`'s `nodeType`
+  /// doesn't match the node currently at that location.
+  #[napi(
+    ts_args_type = "locator: number | string, node: import('./solc-ast').Node"
+  )]
+  pub fn set_node_at(
+    &mut self,
+    env: Env,
+    locator: Either<i64, String>,
+    node: JsUnknown,
+  ) -> napi::Result<()> {
+    let locator = match locator {
+      Either::A(id) => NodeLocator::Id(id),
+      Either::B(path) => NodeLocator::Path(path),
+    };
+    let node = from_js_value(&env, node)?;
+    to_napi_result(self.inner.set_node_at(&locator, node))
+  }
+
+  /// Remove a single node, located either by its stable solc `id` or by an RFC 6901 JSON pointer
+  /// path.
+  #[napi(ts_args_type = "locator: number | string")]
+  pub fn remove_node(&mut self, locator: Either<i64, String>) -> napi::Result<()> {
+    let locator = match locator {
+      Either::A(id) => NodeLocator::Id(id),
+      Either::B(path) => NodeLocator::Path(path),
+    };
+    to_napi_result(self.inner.remove_node(&locator))
+  }
+
+  /// Select nodes from the current source unit by `nodeType`, `name`, `visibility`, and/or
+  /// containing contract. Every filter is optional; omitting `selector` entirely returns every
+  /// node in the tree.
+  #[napi(
+    ts_args_type = "selector?: { nodeType?: string, name?: string, visibility?: string, contract?: string }",
+    ts_return_type = "AstQueryMatch[]"
+  )]
+  pub fn query(&self, env: Env, selector: Option<JsObject>) -> napi::Result<Vec<JsQueryMatch>> {
+    let selector = parse_node_selector(&env, selector)?;
+    let matches = to_napi_result(self.inner.query(&selector))?;
+    matches
+      .into_iter()
+      .map(|found| to_js_query_match(&env, found))
+      .collect()
+  }
+
+  /// Per-node provenance for everything `injectShadow`/`injectShadowAtEdges`/
+  /// `injectShadowAsModifier` have inserted so far, re-resolved against the current AST by the
+  /// last `validate()` call. See [`Ast::provenance`].
+  #[napi(getter, ts_return_type = "AstInjectedSpan[]")]
+  pub fn injected_spans(&self) -> Vec<JsInjectedSpan> {
+    self.inner.provenance().iter().map(to_js_injected_span).collect()
+  }
+
+  /// Name collisions resolved so far by `injectShadow`/`injectFragment*` under the configured
+  /// `resolveConflictStrategy`'s `"overwrite"`/`"rename"`/`"keepBoth"` modes. Empty under
+  /// `"safe"`/`"replace"`, since neither of those can produce a collision report. See
+  /// [`Ast::stitch_conflicts`].
+  #[napi(getter, ts_return_type = "AstStitchConflict[]")]
+  pub fn stitch_conflicts(&self) -> Vec<JsStitchConflict> {
+    self.inner.stitch_conflicts().iter().map(to_js_stitch_conflict).collect()
+  }
+
+  /// Structured audit of everything `injectShadow`/`injectFragment*` have applied so far: which
+  /// members were appended, replaced, or skipped, and how many ids were reassigned along the way.
+  /// Unlike `stitchConflicts`, this covers every `resolveConflictStrategy`. See
+  /// [`Ast::stitch_report`].
+  #[napi(getter, ts_return_type = "AstStitchReport")]
+  pub fn stitch_report(&self) -> JsStitchReport {
+    to_js_stitch_report(self.inner.stitch_report())
+  }
+
+  /// Maps every node of the current (instrumented) source unit back to its counterpart in the
+  /// source loaded via `fromSource`, tagging synthesized regions with no original counterpart as
+  /// such. See [`Ast::source_map`].
+  #[napi(ts_return_type = "AstSourceMapRegion[]")]
+  pub fn source_map(&self) -> napi::Result<Vec<JsSourceMapRegion>> {
+    let regions = to_napi_result(self.inner.source_map())?;
+    Ok(regions.iter().map(to_js_source_map_region).collect())
+  }
+
+  /// Invoke `callback` once per node matching `selector` (same filters as [`Self::query`]).
+  /// `callback` receives the match descriptor and may return a patched node to replace it with,
+  /// or `undefined`/`null` to leave it unchanged; replacements are re-numbered and stitched back
+  /// through the same node-id machinery the fragment stitcher uses. Runs synchronously on the
+  /// calling thread (unlike `compileAsync`/`fromSourceAsync`) since the callback's return value is
+  /// needed immediately to splice the tree, which a `ThreadsafeFunction`'s fire-and-forget queue
+  /// cannot provide. Returns the number of nodes replaced.
+  #[napi(
+    ts_args_type = "callback: (match: AstQueryMatch) => any, selector?: { nodeType?: string, name?: string, visibility?: string, contract?: string }"
+  )]
+  pub fn visit(
+    &mut self,
+    env: Env,
+    callback: JsFunction,
+    selector: Option<JsObject>,
+  ) -> napi::Result<u32> {
+    let selector = parse_node_selector(&env, selector)?;
+    let replaced = to_napi_result(self.inner.visit(&selector, |found| {
+      let descriptor = to_js_value(&env, found)
+        .map_err(|err| AstError::AnalysisFailed(err.to_string()))?;
+      let result: JsUnknown = callback
+        .call(None, &[descriptor])
+        .map_err(|err| AstError::AnalysisFailed(err.to_string()))?;
+      let value_type = result
+        .get_type()
+        .map_err(|err| AstError::AnalysisFailed(err.to_string()))?;
+      if matches!(value_type, ValueType::Undefined | ValueType::Null) {
+        return Ok(VisitAction::Keep);
+      }
+      let replacement: Value =
+        from_js_value(&env, result).map_err(|err| AstError::AnalysisFailed(err.to_string()))?;
+      Ok(VisitAction::Replace(replacement))
+    }))?;
+    Ok(replaced as u32)
+  }
+}
+
+/// One node matched by [`JsAst::query`]/[`JsAst::visit`].
+#[napi(object, js_name = "AstQueryMatch")]
+#[derive(Clone, Debug)]
+pub struct JsQueryMatch {
+  /// RFC 6901 JSON pointer to the node within the source unit, e.g. `/nodes/0/nodes/2`.
+  pub path: String,
+  /// Name of the contract containing this node, if any.
+  #[napi(ts_type = "string | undefined")]
+  pub contract: Option<String>,
+  /// The node's `nodeType`, e.g. `FunctionDefinition`.
+  #[napi(ts_type = "string | undefined")]
+  pub node_type: Option<String>,
+  /// The matched node itself.
+  #[napi(ts_type = "Record<string, unknown>")]
+  pub node: Value,
+}
+
+/// The probe map returned by [`JsAst::instrument_coverage`].
+#[napi(object, js_name = "AstCoverageMap")]
+#[derive(Clone, Debug)]
+pub struct JsCoverageMap {
+  #[napi(ts_type = "'counter' | 'event'")]
+  pub mode: String,
+  pub probes: Vec<JsCoverageProbe>,
+}
+
+/// One coverage probe injected by [`JsAst::instrument_coverage`].
+#[napi(object, js_name = "AstCoverageProbe")]
+#[derive(Clone, Debug)]
+pub struct JsCoverageProbe {
+  pub id: u32,
+  pub contract: String,
+  pub function: String,
+  #[napi(
+    ts_type = "'functionEntry' | 'branchTrue' | 'branchFalse' | 'loopBody' | 'require' | 'assert'"
+  )]
+  pub kind: String,
+  /// The raw solc `"start:length:fileIndex"` source span of the instrumented statement/block.
+  pub src: String,
+}
+
+/// One [`InjectedSpan`] reported by [`JsAst::injected_spans`].
+#[napi(object, js_name = "AstInjectedSpan")]
+#[derive(Clone, Debug)]
+pub struct JsInjectedSpan {
+  pub node_id: i64,
+  pub node_type: String,
+  #[napi(ts_type = "'original' | 'shadowFragment' | 'edgeBefore' | 'edgeAfter'")]
+  pub origin: String,
+  /// The selector `origin` was recorded against, for `'edgeBefore'`/`'edgeAfter'` only.
+  #[napi(ts_type = "string | undefined")]
+  pub selector: Option<String>,
+  /// The raw solc `"start:length:fileIndex"` source span of the inserted node.
+  pub src: String,
+}
+
+fn to_js_injected_span(span: &InjectedSpan) -> JsInjectedSpan {
+  let (origin, selector) = match &span.origin {
+    SpanOrigin::Original => ("original", None),
+    SpanOrigin::ShadowFragment => ("shadowFragment", None),
+    SpanOrigin::EdgeBefore(selector) => ("edgeBefore", Some(selector.clone())),
+    SpanOrigin::EdgeAfter(selector) => ("edgeAfter", Some(selector.clone())),
+  };
+  JsInjectedSpan {
+    node_id: span.node_id,
+    node_type: span.node_type.clone(),
+    origin: origin.to_string(),
+    selector,
+    src: span.src.clone(),
+  }
+}
+
+/// One [`StitchConflict`] reported by [`JsAst::stitch_conflicts`].
+#[napi(object, js_name = "AstStitchConflict")]
+#[derive(Clone, Debug)]
+pub struct JsStitchConflict {
+  pub member_name: String,
+  pub node_type: String,
+  #[napi(ts_type = "'overwritten' | 'renamed' | 'keptBoth'")]
+  pub action: String,
+  /// The name the fragment member was renamed to, for `'renamed'` only.
+  #[napi(ts_type = "string | undefined")]
+  pub new_name: Option<String>,
+}
+
+fn to_js_stitch_conflict(conflict: &StitchConflict) -> JsStitchConflict {
+  let (action, new_name) = match &conflict.action {
+    StitchAction::Overwritten => ("overwritten", None),
+    StitchAction::Renamed { new_name } => ("renamed", Some(new_name.clone())),
+    StitchAction::KeptBoth => ("keptBoth", None),
+  };
+  JsStitchConflict {
+    member_name: conflict.member_name.clone(),
+    node_type: conflict.node_type.clone(),
+    action: action.to_string(),
+    new_name,
+  }
+}
+
+/// One [`StitchedMember`] reported by [`JsAst::stitch_report`].
+#[napi(object, js_name = "AstStitchedMember")]
+#[derive(Clone, Debug)]
+pub struct JsStitchedMember {
+  pub name: String,
+  pub node_type: String,
+}
+
+fn to_js_stitched_member(member: &StitchedMember) -> JsStitchedMember {
+  JsStitchedMember {
+    name: member.name.clone(),
+    node_type: member.node_type.clone(),
+  }
+}
+
+/// [`StitchReport`] reported by [`JsAst::stitch_report`].
+#[napi(object, js_name = "AstStitchReport")]
+#[derive(Clone, Debug)]
+pub struct JsStitchReport {
+  pub appended: Vec<JsStitchedMember>,
+  pub replaced: Vec<JsStitchedMember>,
+  pub skipped: Vec<JsStitchedMember>,
+  pub reassigned_ids: u32,
+}
+
+fn to_js_stitch_report(report: &StitchReport) -> JsStitchReport {
+  JsStitchReport {
+    appended: report.appended.iter().map(to_js_stitched_member).collect(),
+    replaced: report.replaced.iter().map(to_js_stitched_member).collect(),
+    skipped: report.skipped.iter().map(to_js_stitched_member).collect(),
+    reassigned_ids: report.reassigned_ids as u32,
+  }
+}
+
+/// [`FunctionSelector`] returned by [`JsAst::function_selector`].
+#[napi(object, js_name = "AstFunctionSelector")]
+#[derive(Clone, Debug)]
+pub struct JsFunctionSelector {
+  pub signature: String,
+  /// The 4-byte calldata selector, `0x`-prefixed (e.g. `"0xa9059cbb"`).
+  pub selector: String,
+}
+
+fn to_js_function_selector(selector: &FunctionSelector) -> JsFunctionSelector {
+  JsFunctionSelector {
+    signature: selector.signature.clone(),
+    selector: selector.to_hex(),
+  }
+}
+
+/// One [`SourceMapRegion`] reported by [`JsAst::source_map`].
+#[napi(object, js_name = "AstSourceMapRegion")]
+#[derive(Clone, Debug)]
+pub struct JsSourceMapRegion {
+  pub node_id: i64,
+  /// The raw solc `"start:length:fileIndex"` span in the original source, or `undefined` for a
+  /// region with no original counterpart (`kind !== "original"`).
+  #[napi(ts_type = "string | undefined")]
+  pub original_span: Option<String>,
+  /// The raw solc `"start:length:fileIndex"` span in the current (instrumented) source unit.
+  pub instrumented_span: String,
+  #[napi(ts_type = "'original' | 'injectedBefore' | 'injectedAfter' | 'stitchedFragment'")]
+  pub kind: String,
+}
+
+fn to_js_source_map_region(region: &SourceMapRegion) -> JsSourceMapRegion {
+  let kind = match region.kind {
+    SourceMapRegionKind::Original => "original",
+    SourceMapRegionKind::InjectedBefore => "injectedBefore",
+    SourceMapRegionKind::InjectedAfter => "injectedAfter",
+    SourceMapRegionKind::StitchedFragment => "stitchedFragment",
+  };
+  JsSourceMapRegion {
+    node_id: region.node_id,
+    original_span: region.original_span.clone(),
+    instrumented_span: region.instrumented_span.clone(),
+    kind: kind.to_string(),
+  }
+}
+
+fn to_js_coverage_map(map: CoverageMap) -> JsCoverageMap {
+  JsCoverageMap {
+    mode: map.mode.to_string(),
+    probes: map
+      .probes
+      .into_iter()
+      .map(|probe| JsCoverageProbe {
+        id: probe.id,
+        contract: probe.contract,
+        function: probe.function,
+        kind: probe.kind.to_string(),
+        src: probe.src,
+      })
+      .collect(),
+  }
+}
+
+fn parse_coverage_options(
+  env: &Env,
+  options: Option<JsUnknown>,
+) -> napi::Result<(CoverageProbeKind, Option<AstConfigOptions>)> {
+  let Some(value) = options else {
+    return Ok((CoverageProbeKind::default(), None));
+  };
+
+  let object = value.coerce_to_object()?;
+
+  let mode = optional_string_property(&object, "mode")?
+    .map(|value| value.parse::<CoverageProbeKind>().map_err(|err| napi_error(err.to_string())))
+    .transpose()?
+    .unwrap_or_default();
+
+  let overrides = parse_js_ast_options(env, Some(object.into_unknown()))?
+    .as_ref()
+    .map(|opts| AstConfigOptions::try_from(opts))
+    .transpose()?;
+
+  Ok((mode, overrides))
+}
+
+fn to_js_query_match(env: &Env, found: QueryMatch) -> napi::Result<JsQueryMatch> {
+  let _ = env;
+  Ok(JsQueryMatch {
+    path: found.path,
+    contract: found.contract,
+    node_type: found.node_type,
+    node: found.node,
+  })
+}
+
+fn parse_node_selector(env: &Env, selector: Option<JsObject>) -> napi::Result<NodeSelector> {
+  let Some(object) = selector else {
+    return Ok(NodeSelector::default());
+  };
+
+  let kind = optional_string_property(&object, "nodeType")?
+    .map(|value| value.parse::<NodeKind>().map_err(|err| napi_error(err.to_string())))
+    .transpose()?;
+  let visibility = optional_string_property(&object, "visibility")?
+    .map(|value| {
+      value
+        .parse::<NodeVisibility>()
+        .map_err(|err| napi_error(err.to_string()))
+    })
+    .transpose()?;
+  let name = optional_string_property(&object, "name")?;
+  let contract = optional_string_property(&object, "contract")?;
+  let _ = env;
+
+  Ok(NodeSelector {
+    kind,
+    name,
+    visibility,
+    contract,
+  })
+}
+
+fn optional_string_property(object: &JsObject, property: &str) -> napi::Result<Option<String>> {
+  if !object.has_named_property(property)? {
+    return Ok(None);
+  }
+  let value = object.get_named_property::<JsUnknown>(property)?;
+  if matches!(value.get_type()?, ValueType::Undefined | ValueType::Null) {
+    return Ok(None);
+  }
+  let js_string = value.coerce_to_string()?;
+  Ok(Some(js_string.into_utf8()?.into_owned()?))
+}
+
+/// Off-thread counterpart of [`JsAst::from_source_async`]: owns a `State` snapshot so `compute`
+/// can run `from_source` on the libuv thread pool without touching the JS-owned `JsAst`.
+pub struct FromSourceTask {
+  state: State,
+  target: SourceTarget,
+  overrides: Option<AstConfigOptions>,
+  progress: Option<ThreadsafeFunction<String>>,
+}
+
+impl Task for FromSourceTask {
+  type Output = State;
+  type JsValue = JsAst;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    report_stage(&self.progress, "parse");
+    to_napi_result(from_source(
+      &mut self.state,
+      self.target.clone(),
+      self.overrides.as_ref(),
+    ))?;
+    report_stage(&self.progress, "parsed");
+    Ok(self.state.clone())
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(JsAst::from_ast(Ast { state: output }))
+  }
+}
+
+/// Off-thread counterpart of [`JsAst::compile_async`]: owns a `State` snapshot so `compute` can
+/// run `compile_output` (and thus `solc`) on the libuv thread pool.
+pub struct CompileTask {
+  state: State,
+  progress: Option<ThreadsafeFunction<String>>,
+}
+
+impl Task for CompileTask {
+  type Output = CompileOutput;
+  type JsValue = JsCompileOutput;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    report_stage(&self.progress, "compile");
+    let output = to_napi_result(compile_output(&mut self.state))?;
+    report_stage(&self.progress, "compiled");
+    Ok(output)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(into_js_compile_output(output))
+  }
+}
+
+fn report_stage(progress: &Option<ThreadsafeFunction<String>>, stage: &str) {
+  if let Some(progress) = progress {
+    let _ = progress.call(Ok(stage.to_string()), ThreadsafeFunctionCallMode::NonBlocking);
+  }
+}
+
+/// Wraps a JS callback as a [`ThreadsafeFunction`] so [`FromSourceTask`]/[`CompileTask`] can report
+/// stage transitions from the libuv thread pool back to JS.
+fn create_stage_callback(env: &Env, callback: &JsFunction) -> napi::Result<ThreadsafeFunction<String>> {
+  env.create_threadsafe_function::<String, JsUnknown, _>(callback, 0, |ctx| {
+    let stage = ctx.env.create_string(&ctx.value)?;
+    Ok(vec![stage.into_unknown()])
+  })
 }
 
 fn parse_source_target(env: &Env, target: Either<String, JsObject>) -> napi::Result<SourceTarget> {
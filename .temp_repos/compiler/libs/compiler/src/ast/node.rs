@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+/// A strongly-typed view over the handful of Solidity AST node kinds [`super::utils`] and
+/// [`super::stitcher`] care about, deserialized from the same raw `serde_json::Value` nodes solc
+/// emits (tagged on `nodeType`, matching solc's own JSON shape). This sits *alongside* the
+/// `Value`-based traversal those modules already do rather than replacing it -- converting a
+/// source unit into [`AstNode`] gives compile-time guarantees that `id`/`src` exist for the kinds
+/// modeled here, at the cost of any node kind not listed collapsing into [`AstNode::Other`] with
+/// its fields discarded. Until every node kind solc can emit is modeled, stitching/renumbering
+/// stay on the `Value` traversal; this type is for call sites that only need to work with the
+/// kinds below and want that guarantee.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "nodeType")]
+pub enum AstNode {
+  SourceUnit(SourceUnitNode),
+  ContractDefinition(ContractDefinitionNode),
+  FunctionDefinition(FunctionDefinitionNode),
+  VariableDeclaration(VariableDeclarationNode),
+  Block(BlockNode),
+  /// Any node kind not modeled above. Carries no fields, so a round trip through [`AstNode`]
+  /// loses this node's data -- callers that need every kind should stay on the `Value` traversal.
+  #[serde(other)]
+  Other,
+}
+
+impl AstNode {
+  /// This node's solc `id`, or `None` for [`AstNode::Other`].
+  pub fn id(&self) -> Option<i64> {
+    match self {
+      Self::SourceUnit(node) => Some(node.id),
+      Self::ContractDefinition(node) => Some(node.id),
+      Self::FunctionDefinition(node) => Some(node.id),
+      Self::VariableDeclaration(node) => Some(node.id),
+      Self::Block(node) => Some(node.id),
+      Self::Other => None,
+    }
+  }
+
+  /// This node's solc `src` span, or `None` for [`AstNode::Other`].
+  pub fn src(&self) -> Option<&str> {
+    match self {
+      Self::SourceUnit(node) => Some(&node.src),
+      Self::ContractDefinition(node) => Some(&node.src),
+      Self::FunctionDefinition(node) => Some(&node.src),
+      Self::VariableDeclaration(node) => Some(&node.src),
+      Self::Block(node) => Some(&node.src),
+      Self::Other => None,
+    }
+  }
+
+  /// This node's `nodeType` string, matching what solc would emit.
+  pub fn node_type(&self) -> &'static str {
+    match self {
+      Self::SourceUnit(_) => "SourceUnit",
+      Self::ContractDefinition(_) => "ContractDefinition",
+      Self::FunctionDefinition(_) => "FunctionDefinition",
+      Self::VariableDeclaration(_) => "VariableDeclaration",
+      Self::Block(_) => "Block",
+      Self::Other => "Other",
+    }
+  }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SourceUnitNode {
+  pub id: i64,
+  pub src: String,
+  #[serde(default)]
+  pub nodes: Vec<AstNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContractDefinitionNode {
+  pub id: i64,
+  pub src: String,
+  pub name: String,
+  #[serde(default)]
+  pub nodes: Vec<AstNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FunctionDefinitionNode {
+  pub id: i64,
+  pub src: String,
+  pub name: String,
+  pub scope: i64,
+  pub body: Option<Box<AstNode>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VariableDeclarationNode {
+  pub id: i64,
+  pub src: String,
+  pub name: String,
+  pub scope: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockNode {
+  pub id: i64,
+  pub src: String,
+  #[serde(default)]
+  pub statements: Vec<AstNode>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn deserializes_modeled_node_kinds() {
+    let value = json!({
+      "nodeType": "SourceUnit",
+      "id": 1,
+      "src": "0:40:0",
+      "nodes": [
+        {
+          "nodeType": "ContractDefinition",
+          "id": 2,
+          "src": "0:40:0",
+          "name": "Example",
+          "nodes": [
+            {
+              "nodeType": "FunctionDefinition",
+              "id": 3,
+              "src": "10:20:0",
+              "name": "demo",
+              "scope": 2,
+              "body": {
+                "nodeType": "Block",
+                "id": 4,
+                "src": "15:5:0",
+                "statements": []
+              }
+            }
+          ]
+        }
+      ]
+    });
+
+    let node: AstNode = serde_json::from_value(value).expect("deserializes");
+    assert_eq!(node.node_type(), "SourceUnit");
+    assert_eq!(node.id(), Some(1));
+
+    let AstNode::SourceUnit(source_unit) = &node else {
+      panic!("expected SourceUnit");
+    };
+    let AstNode::ContractDefinition(contract) = &source_unit.nodes[0] else {
+      panic!("expected ContractDefinition");
+    };
+    assert_eq!(contract.name, "Example");
+    let AstNode::FunctionDefinition(function) = &contract.nodes[0] else {
+      panic!("expected FunctionDefinition");
+    };
+    assert_eq!(function.scope, 2);
+    assert!(matches!(function.body.as_deref(), Some(AstNode::Block(_))));
+  }
+
+  #[test]
+  fn unmodeled_node_kinds_collapse_to_other() {
+    let value = json!({ "nodeType": "PragmaDirective", "literals": ["solidity", "^0.8.0"] });
+    let node: AstNode = serde_json::from_value(value).expect("deserializes");
+    assert!(matches!(node, AstNode::Other));
+    assert_eq!(node.id(), None);
+    assert_eq!(node.src(), None);
+  }
+
+  #[test]
+  fn round_trips_through_value() {
+    let original = json!({
+      "nodeType": "VariableDeclaration",
+      "id": 9,
+      "src": "1:2:0",
+      "name": "value",
+      "scope": 5
+    });
+
+    let node: AstNode = serde_json::from_value(original.clone()).expect("deserializes");
+    let serialized = serde_json::to_value(&node).expect("serializes");
+    assert_eq!(serialized, original);
+  }
+}
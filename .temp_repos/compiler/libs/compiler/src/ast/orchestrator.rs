@@ -1,17 +1,27 @@
 use foundry_compilers::artifacts::{output_selection::OutputSelection, Settings};
 use foundry_compilers::solc::Solc;
+use semver::Version;
 
 use super::{error::AstError, parser, stitcher, utils};
-use crate::internal::{config::ResolveConflictStrategy, settings};
+use super::stitcher::FragmentStitchResult;
+use crate::internal::{
+  ast_cache,
+  config::{MergePlacement, ResolveConflictStrategy},
+  project::default_cache_dir,
+  settings,
+};
 use serde_json::Value;
 
 pub(crate) struct AstOrchestrator;
 
 impl AstOrchestrator {
-  pub fn sanitize_settings(settings: Option<Settings>) -> Result<Settings, AstError> {
+  pub fn sanitize_settings(
+    settings: Option<Settings>,
+    solc_version: &Version,
+  ) -> Result<Settings, AstError> {
     let base = settings.unwrap_or_default();
-    let sanitized =
-      settings::sanitize_settings(&base).map_err(|err| AstError::ConfigError(err.to_string()))?;
+    let sanitized = settings::sanitize_settings(&base, solc_version)
+      .map_err(|err| AstError::ConfigError(err.to_string()))?;
     let mut sanitized = sanitized;
     sanitized.stop_after = Some("parsing".to_string());
     sanitized.output_selection = OutputSelection::ast_output_selection();
@@ -19,13 +29,37 @@ impl AstOrchestrator {
     Ok(sanitized)
   }
 
+  /// Parses `source` into its AST, reusing a previously cached result when `cache_enabled` and the
+  /// on-disk cache under `{cwd}/.tevm/ast-cache` already holds an entry for this exact source,
+  /// solc version, and settings. A cache miss (or `cache_enabled == false`) falls through to
+  /// [`parser::parse_source_ast`] and, on success, persists the result for next time. Failing to
+  /// read or write the cache never fails the parse itself -- it only means this call (or a future
+  /// one) re-invokes solc.
   pub fn parse_source_unit(
     source: &str,
     file_name: &str,
     solc: &Solc,
     settings: &Settings,
+    cache_enabled: bool,
   ) -> Result<Value, AstError> {
-    parser::parse_source_ast(source, file_name, solc, settings)
+    if !cache_enabled {
+      return parser::parse_source_ast(source, file_name, solc, settings);
+    }
+
+    let dir = ast_cache::cache_dir(&default_cache_dir());
+    let key = ast_cache::cache_key(source, &solc.version, settings).ok();
+
+    if let Some(key) = &key {
+      if let Some(cached) = ast_cache::read(&dir, key) {
+        return Ok(cached);
+      }
+    }
+
+    let ast = parser::parse_source_ast(source, file_name, solc, settings)?;
+    if let Some(key) = &key {
+      let _ = ast_cache::write(&dir, key, &ast);
+    }
+    Ok(ast)
   }
 
   pub fn parse_fragment_contract(
@@ -40,19 +74,62 @@ impl AstOrchestrator {
     parser::extract_fragment_contract(unit)
   }
 
+  /// Stitches `fragment_contract` into `target`'s contract at `contract_idx`, remapping the
+  /// fragment's `src` spans onto `target`'s own file index and shifting them past the end of
+  /// `target`'s source (see [`utils::source_extent`]) so they land in a reserved region instead of
+  /// overlapping the target's real spans.
   pub fn stitch_fragment_into_contract(
     target: &mut Value,
     contract_idx: usize,
     fragment_contract: &Value,
     strategy: ResolveConflictStrategy,
-  ) -> Result<(), AstError> {
+    merge_placement: MergePlacement,
+    source: Option<&str>,
+  ) -> Result<FragmentStitchResult, AstError> {
     let max_target_id = utils::max_id(target);
+    let target_file_index = utils::src_file_index(target);
+    let base_offset = utils::source_extent(target);
     stitcher::stitch_fragment_nodes_into_contract(
       target,
       contract_idx,
       fragment_contract,
       max_target_id,
       strategy,
+      merge_placement,
+      target_file_index,
+      base_offset,
+      source,
+    )
+  }
+
+  /// Like [`Self::stitch_fragment_into_contract`], but first resolves the fragment's dangling
+  /// library references against `library_units` -- full `SourceUnit` ASTs the fragment's libraries
+  /// were parsed from -- splicing each required library's `ContractDefinition` into the target
+  /// unit and rebinding the fragment's references onto it. See
+  /// [`stitcher::stitch_fragment_with_libraries`] for the resolution/dedup rules.
+  pub fn stitch_fragment_with_libraries(
+    target: &mut Value,
+    contract_idx: usize,
+    fragment_contract: &Value,
+    library_units: &[Value],
+    strategy: ResolveConflictStrategy,
+    merge_placement: MergePlacement,
+    source: Option<&str>,
+  ) -> Result<FragmentStitchResult, AstError> {
+    let max_target_id = utils::max_id(target);
+    let target_file_index = utils::src_file_index(target);
+    let base_offset = utils::source_extent(target);
+    stitcher::stitch_fragment_with_libraries(
+      target,
+      contract_idx,
+      fragment_contract,
+      library_units,
+      max_target_id,
+      strategy,
+      merge_placement,
+      target_file_index,
+      base_offset,
+      source,
     )
   }
 }
@@ -85,7 +162,9 @@ contract Target {
 
   #[test]
   fn sanitize_settings_applies_ast_defaults() {
-    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize settings");
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize settings");
     assert_eq!(settings.stop_after.as_deref(), Some("parsing"));
     assert!(
       !settings.output_selection.as_ref().is_empty(),
@@ -100,9 +179,11 @@ contract Target {
       return;
     };
 
-    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let default_version = solc::default_version().expect("default version");
+    let settings = AstOrchestrator::sanitize_settings(None, &default_version)
+      .expect("sanitize default settings");
     let mut unit =
-      AstOrchestrator::parse_source_unit(MULTI_CONTRACT, "Target.sol", &solc, &settings)
+      AstOrchestrator::parse_source_unit(MULTI_CONTRACT, "Target.sol", &solc, &settings, true)
         .expect("parse source unit");
     let fragment =
       AstOrchestrator::parse_fragment_contract(FRAGMENT, &solc, &settings).expect("parse fragment");
@@ -115,6 +196,8 @@ contract Target {
       idx,
       &fragment,
       ResolveConflictStrategy::Safe,
+      MergePlacement::default(),
+      Some(MULTI_CONTRACT),
     )
     .expect("stitch fragment");
 
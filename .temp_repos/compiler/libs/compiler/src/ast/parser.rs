@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
+use foundry_compilers::artifacts::error::{Error as FoundryCompilerError, Severity};
 use foundry_compilers::artifacts::{Settings, SolcInput, SolcLanguage, Source, Sources};
 use foundry_compilers::solc::Solc;
 
 use super::error::AstError;
+use crate::compiler::output::solc_error_to_core;
+use crate::contract::SourceMapEntry;
 use serde_json::Value;
 
 // TODO: remove in favor of compile_source with correct settings once we add ast to output
@@ -27,23 +30,14 @@ fn parse_source_ast_internal(
     .get("errors")
     .and_then(|value| value.as_array())
   {
-    let mut messages = Vec::new();
-    for error in errors {
-      let severity = error
-        .get("severity")
-        .and_then(|value| value.as_str())
-        .unwrap_or_default();
-      if severity.eq_ignore_ascii_case("error") {
-        let message = error
-          .get("formattedMessage")
-          .and_then(|value| value.as_str())
-          .or_else(|| error.get("message").and_then(|value| value.as_str()))
-          .unwrap_or("Compilation error");
-        messages.push(message.to_string());
-      }
-    }
-    if !messages.is_empty() {
-      return Err(AstError::CompilerError(messages.join("\n")));
+    let diagnostics: Vec<_> = errors
+      .iter()
+      .filter_map(|error| serde_json::from_value::<FoundryCompilerError>(error.clone()).ok())
+      .filter(|error| matches!(error.severity, Severity::Error))
+      .map(|error| solc_error_to_core(&error))
+      .collect();
+    if !diagnostics.is_empty() {
+      return Err(AstError::CompilationFailed(diagnostics));
     }
   }
 
@@ -66,17 +60,33 @@ pub fn parse_source_ast(
   parse_source_ast_internal(source, file_name, solc, settings)
 }
 
+/// Decodes a compact Solidity source map -- the `sourceMap`/`deployedSourceMap` string solc emits
+/// alongside bytecode, e.g. `"1:87:0:-:0;1:87:0:-:0;..."` -- into structured [`SourceMapEntry`]
+/// records. Lives next to [`parse_source_ast`] so callers who already resolved a unit's AST can
+/// decode the matching bytecode source map without reaching into `crate::contract` themselves; the
+/// decoding logic itself stays owned by [`crate::contract::decode_source_map`], which this forwards
+/// to.
+pub fn decode_source_map(raw: &str) -> Vec<SourceMapEntry> {
+  crate::contract::decode_source_map(raw)
+}
+
+const FRAGMENT_PREFIX: &str = "// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.0;\n\ncontract __AstFragment {\n    ";
+const FRAGMENT_SUFFIX: &str = "\n}\n";
+
 pub fn wrap_fragment_source(source: &str) -> String {
-  format!(
-    r#"// SPDX-License-Identifier: UNLICENSED
-pragma solidity ^0.8.0;
+  wrap_fragment_source_with_offset(source).0
+}
 
-contract __AstFragment {{
-    {}
-}}
-"#,
-    source
-  )
+/// Wraps `source` the same way as [`wrap_fragment_source`], additionally returning the byte
+/// offset at which `source` begins inside the wrapper. Callers that need to map solc diagnostics
+/// for `__AstFragment.sol` back onto the original snippet use this offset to translate byte spans.
+pub fn wrap_fragment_source_with_offset(source: &str) -> (String, usize) {
+  let mut wrapped = String::with_capacity(FRAGMENT_PREFIX.len() + source.len() + FRAGMENT_SUFFIX.len());
+  wrapped.push_str(FRAGMENT_PREFIX);
+  let offset = wrapped.len();
+  wrapped.push_str(source);
+  wrapped.push_str(FRAGMENT_SUFFIX);
+  (wrapped, offset)
 }
 
 pub fn parse_fragment_contract(
@@ -137,6 +147,16 @@ contract Example {
     Solc::find_svm_installed_version(&version).ok().flatten()
   }
 
+  #[test]
+  fn decode_source_map_forwards_to_contract_decoder() {
+    let entries = decode_source_map("1:87:0:-:0;2:3:1:i:1");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].start, 1);
+    assert_eq!(entries[0].length, 87);
+    assert_eq!(entries[1].start, 2);
+    assert_eq!(entries[1].file_index, 1);
+  }
+
   #[test]
   fn wraps_fragment_in_shadow_contract() {
     let wrapped = wrap_fragment_source(SAMPLE_FRAGMENT);
@@ -150,7 +170,9 @@ contract Example {
     let Some(solc) = find_default_solc() else {
       return;
     };
-    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
     let ast = parse_source_ast(SAMPLE_CONTRACT, "Example.sol", &solc, &settings)
       .expect("should parse contract");
     let nodes = ast
@@ -171,7 +193,9 @@ contract Example {
     let Some(solc) = find_default_solc() else {
       return;
     };
-    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let default_version = solc::default_version().expect("default version");
+    let settings =
+      AstOrchestrator::sanitize_settings(None, &default_version).expect("sanitize default settings");
     let contract =
       parse_fragment_contract(SAMPLE_FRAGMENT, &solc, &settings).expect("parse fragment");
     assert_eq!(
@@ -0,0 +1,83 @@
+use serde_json::Value;
+
+/// A single rewrite applied to every direct member of a contract (functions, state variables,
+/// events, modifiers, ...) visited by [`super::core::run_passes`]. Implementations mutate `node`
+/// in place; leaving it untouched is a no-op for that member. Stateful passes (e.g. one that
+/// renames every variable it sees to avoid collisions) can accumulate state across calls since
+/// `visit_contract_member` takes `&mut self`.
+pub trait AstPass {
+  fn visit_contract_member(&mut self, node: &mut Value);
+}
+
+/// Flips `node`'s `visibility` to `public` when its `nodeType` matches `node_type`, adding the
+/// field if it's missing entirely. Shared by [`ExposeVariablesPass`] and [`ExposeFunctionsPass`],
+/// the only difference between the two being which `nodeType` they target.
+fn force_public(node: &mut Value, node_type: &str) {
+  if node.get("nodeType").and_then(Value::as_str) != Some(node_type) {
+    return;
+  }
+  let Some(object) = node.as_object_mut() else {
+    return;
+  };
+  match object.get_mut("visibility") {
+    Some(value) => {
+      if !matches!(value.as_str(), Some("public")) {
+        *value = Value::String("public".to_string());
+      }
+    }
+    None => {
+      object.insert("visibility".to_string(), Value::String("public".to_string()));
+    }
+  }
+}
+
+/// Built-in pass backing [`super::core::expose_internal_variables`]: promotes every
+/// `VariableDeclaration` to `public` visibility.
+#[derive(Default)]
+pub struct ExposeVariablesPass;
+
+impl AstPass for ExposeVariablesPass {
+  fn visit_contract_member(&mut self, node: &mut Value) {
+    force_public(node, "VariableDeclaration");
+  }
+}
+
+/// Built-in pass backing [`super::core::expose_internal_functions`]: promotes every
+/// `FunctionDefinition` to `public` visibility.
+#[derive(Default)]
+pub struct ExposeFunctionsPass;
+
+impl AstPass for ExposeFunctionsPass {
+  fn visit_contract_member(&mut self, node: &mut Value) {
+    force_public(node, "FunctionDefinition");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn expose_variables_pass_only_touches_variable_declarations() {
+    let mut variable = json!({"nodeType": "VariableDeclaration", "visibility": "internal"});
+    let mut function = json!({"nodeType": "FunctionDefinition", "visibility": "internal"});
+
+    let mut pass = ExposeVariablesPass;
+    pass.visit_contract_member(&mut variable);
+    pass.visit_contract_member(&mut function);
+
+    assert_eq!(variable["visibility"], "public");
+    assert_eq!(function["visibility"], "internal");
+  }
+
+  #[test]
+  fn expose_functions_pass_inserts_visibility_when_missing() {
+    let mut function = json!({"nodeType": "FunctionDefinition"});
+
+    let mut pass = ExposeFunctionsPass;
+    pass.visit_contract_member(&mut function);
+
+    assert_eq!(function["visibility"], "public");
+  }
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::sources::Source as FoundrySource;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::internal::config::{MergePlacement, ResolveConflictStrategy};
+use crate::internal::errors::{Error, Result};
+use crate::internal::keccak::keccak256;
+
+/// On-disk cache of a whole `from_source` + `inject_shadow*` + `validate` pipeline, keyed by a
+/// content hash over everything that determines its outcome. Sits a layer above
+/// [`super::orchestrator::AstOrchestrator::parse_source_unit`]'s own cache (which only covers the
+/// initial parse): it is consulted by [`super::core::validate`] only, after parsing and stitching
+/// have already run, and a hit skips re-invoking solc to validate the result by replaying the
+/// instrumented source unit `validate` produced last time.
+pub(crate) fn cache_dir(base_dir: &Path) -> PathBuf {
+  base_dir.join(".tevm").join("ast-pipeline-cache")
+}
+
+/// Everything that changes what [`super::core::validate`] produces for a pipeline run: the
+/// original source text, every fragment applied (in order, via `inject_shadow`) since that source
+/// was loaded followed by the serialized AST as it stands when `validate` runs (which also captures
+/// edits that add no fragment), the conflict strategy fragments were stitched under (and, when that strategy is
+/// [`ResolveConflictStrategy::Merge`], where its statements land), and whether the
+/// expose-internal passes ran. Two runs that agree on all of these produce byte-identical
+/// instrumented output, so the hash over them doubles as the cache key.
+pub(crate) struct PipelineInputs<'a> {
+  pub source: &'a str,
+  pub fragments: &'a [String],
+  pub strategy: ResolveConflictStrategy,
+  pub merge_placement: MergePlacement,
+  pub exposed_variables: bool,
+  pub exposed_functions: bool,
+}
+
+/// Hashes a [`PipelineInputs`] into the hex key [`read`]/[`write`] store entries under. Each
+/// variable-length field (the source, and every fragment, which are raw user-supplied Solidity
+/// and routinely contain embedded newlines) is content-hashed on its own via
+/// [`FoundrySource::content_hash_of`] before being joined with `\n`, the same approach
+/// [`super::super::internal::ast_cache::cache_key`] uses -- joining the raw strings directly would
+/// let two differently-shaped fragment lists serialize to the same payload (e.g. one fragment
+/// containing a newline vs. two fragments split at it).
+pub(crate) fn fingerprint(inputs: &PipelineInputs<'_>) -> String {
+  let mut payload = String::new();
+  payload.push_str(&FoundrySource::content_hash_of(inputs.source));
+  payload.push('\n');
+  for fragment in inputs.fragments {
+    payload.push_str(&FoundrySource::content_hash_of(fragment));
+    payload.push('\n');
+  }
+  payload.push_str(&format!("{:?}\n{:?}\n", inputs.strategy, inputs.merge_placement));
+  payload.push_str(&format!(
+    "{}\n{}\n",
+    inputs.exposed_variables, inputs.exposed_functions
+  ));
+  hex::encode(keccak256(payload.as_bytes()))
+}
+
+/// The previously produced instrumented source unit and the validation outcome it came with,
+/// persisted verbatim so a cache hit can replay both without re-invoking solc.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct CachedPipelineResult {
+  pub source_unit: Value,
+  pub validation_errors: Vec<String>,
+}
+
+/// Loads the cached result for `key`, if present. A missing or corrupt entry (hand-deleted, or
+/// written by an incompatible version) is treated as a cache miss rather than an error.
+pub(crate) fn read(dir: &Path, key: &str) -> Option<CachedPipelineResult> {
+  let contents = fs::read_to_string(dir.join(format!("{key}.json"))).ok()?;
+  serde_json::from_str(&contents).ok()
+}
+
+/// Persists `result` under `key`, creating the cache directory if needed.
+pub(crate) fn write(dir: &Path, key: &str, result: &CachedPipelineResult) -> Result<()> {
+  fs::create_dir_all(dir).map_err(|err| {
+    Error::io(format!(
+      "Failed to prepare AST pipeline cache directory {}: {err}",
+      dir.display()
+    ))
+  })?;
+  let path = dir.join(format!("{key}.json"));
+  let serialized = serde_json::to_string(result).map_err(|err| {
+    Error::new(format!(
+      "Failed to serialise cached pipeline result {}: {err}",
+      path.display()
+    ))
+  })?;
+  fs::write(&path, serialized).map_err(|err| {
+    Error::io(format!(
+      "Failed to write cached pipeline result {}: {err}",
+      path.display()
+    ))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn inputs(source: &str, fragments: &[String]) -> PipelineInputs<'_> {
+    PipelineInputs {
+      source,
+      fragments,
+      strategy: ResolveConflictStrategy::Safe,
+      merge_placement: MergePlacement::Around,
+      exposed_variables: false,
+      exposed_functions: false,
+    }
+  }
+
+  #[test]
+  fn fingerprint_is_stable_for_identical_inputs() {
+    let fragments = vec!["function f() internal {}".to_string()];
+    let a = fingerprint(&inputs("contract A {}", &fragments));
+    let b = fingerprint(&inputs("contract A {}", &fragments));
+    assert_eq!(a, b);
+  }
+
+  #[test]
+  fn fingerprint_changes_with_fragments_or_strategy_or_exposure() {
+    let base = inputs("contract A {}", &[]);
+    let baseline = fingerprint(&base);
+
+    let with_fragment = vec!["function f() internal {}".to_string()];
+    assert_ne!(
+      baseline,
+      fingerprint(&inputs("contract A {}", &with_fragment))
+    );
+
+    let mut overwrite = inputs("contract A {}", &[]);
+    overwrite.strategy = ResolveConflictStrategy::Overwrite;
+    assert_ne!(baseline, fingerprint(&overwrite));
+
+    let mut exposed = inputs("contract A {}", &[]);
+    exposed.exposed_functions = true;
+    assert_ne!(baseline, fingerprint(&exposed));
+  }
+
+  #[test]
+  fn fingerprint_distinguishes_a_single_multiline_fragment_from_several_split_ones() {
+    let one_fragment = vec!["function a() {}\nfunction b() {}".to_string()];
+    let two_fragments = vec!["function a() {}".to_string(), "function b() {}".to_string()];
+
+    assert_ne!(
+      fingerprint(&inputs("contract A {}", &one_fragment)),
+      fingerprint(&inputs("contract A {}", &two_fragments))
+    );
+  }
+
+  #[test]
+  fn write_then_read_round_trips_the_cached_result() {
+    let dir = std::env::temp_dir().join(format!(
+      "tevm-ast-pipeline-cache-test-{}",
+      std::process::id()
+    ));
+    let result = CachedPipelineResult {
+      source_unit: serde_json::json!({"nodeType": "SourceUnit", "id": 1}),
+      validation_errors: Vec::new(),
+    };
+    write(&dir, "entry", &result).unwrap();
+    assert_eq!(read(&dir, "entry").unwrap().source_unit, result.source_unit);
+    assert!(read(&dir, "missing").is_none());
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// Where a node in the current AST came from, relative to the source the caller originally loaded
+/// via `from_source`. Recorded per top-level node inserted by
+/// [`super::core::inject_shadow`]/[`super::core::inject_shadow_at_edges`]/
+/// [`super::core::inject_shadow_as_modifier`], so downstream coverage or debugging tooling can
+/// attribute bytecode to shadow code vs. original code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpanOrigin {
+  /// Present in the source the caller loaded; none of the instrumentation helpers inserted it.
+  /// Reserved for future callers that want to record the full span table rather than only the
+  /// synthetic delta -- nothing in this crate currently tags a node `Original`.
+  Original,
+  /// Inserted by [`super::core::inject_shadow`]'s fragment stitching.
+  ShadowFragment,
+  /// Inserted by [`super::core::inject_shadow_at_edges`]/[`super::core::inject_shadow_as_modifier`]
+  /// before the targeted function's body, keyed by the selector that was instrumented.
+  EdgeBefore(String),
+  /// Inserted after the targeted function's body (including before every early `return`/exiting
+  /// `assembly` block), keyed by the selector that was instrumented.
+  EdgeAfter(String),
+}
+
+/// One inserted node's provenance: which [`SpanOrigin`] produced it, its solc node id, and the
+/// byte `src` range it currently occupies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InjectedSpan {
+  pub node_id: i64,
+  pub node_type: String,
+  pub origin: SpanOrigin,
+  pub src: String,
+}
+
+/// Appends one [`InjectedSpan`] per node in `nodes` tagged with `origin`. Nodes without an `id`
+/// (malformed or synthetic-without-id fragments) are skipped rather than recorded with a
+/// placeholder id that could collide with a real one.
+pub(crate) fn record(spans: &mut Vec<InjectedSpan>, origin: SpanOrigin, nodes: &[Value]) {
+  for node in nodes {
+    let Some(node_id) = node.get("id").and_then(Value::as_i64) else {
+      continue;
+    };
+    let node_type = node
+      .get("nodeType")
+      .and_then(Value::as_str)
+      .unwrap_or("<unknown>")
+      .to_string();
+    let src = node
+      .get("src")
+      .and_then(Value::as_str)
+      .unwrap_or("0:0:0")
+      .to_string();
+    spans.push(InjectedSpan {
+      node_id,
+      node_type,
+      origin: origin.clone(),
+      src,
+    });
+  }
+}
+
+/// Re-resolves every recorded span's `src` by matching `node_id` against `unit`, which
+/// [`super::core::validate`] calls after a recompile replaces the AST wholesale. A span whose node
+/// id no longer appears (the recompile dropped or merged it) keeps its last-known `src` rather
+/// than being silently removed, since the synthetic code it describes may still be present
+/// elsewhere in the compiled output under a different id.
+pub(crate) fn reresolve(spans: &mut [InjectedSpan], unit: &Value) {
+  for span in spans.iter_mut() {
+    if let Some(src) = find_src_by_id(unit, span.node_id) {
+      span.src = src;
+    }
+  }
+}
+
+/// Which of [`SpanOrigin`]'s variants a [`SourceMapRegion`] falls under, collapsed to the four
+/// kinds a consumer actually needs to branch on -- `EdgeBefore`/`EdgeAfter` carry a selector that
+/// matters for attribution inside `injected_spans` but not for "can I skip this region".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceMapRegionKind {
+  /// Present in the source the caller loaded via `from_source`; `original_span` and
+  /// `instrumented_span` are identical, since untouched nodes keep the byte offsets solc assigned
+  /// them at the first parse through every later injection/validate.
+  Original,
+  /// Inserted by `inject_shadow_at_edges`/`inject_shadow_as_modifier` before the targeted
+  /// function's body.
+  InjectedBefore,
+  /// Inserted by `inject_shadow_at_edges`/`inject_shadow_as_modifier` after the targeted
+  /// function's body.
+  InjectedAfter,
+  /// Inserted by `inject_shadow`'s fragment stitching.
+  StitchedFragment,
+}
+
+impl From<&SpanOrigin> for SourceMapRegionKind {
+  fn from(origin: &SpanOrigin) -> Self {
+    match origin {
+      SpanOrigin::Original => SourceMapRegionKind::Original,
+      SpanOrigin::EdgeBefore(_) => SourceMapRegionKind::InjectedBefore,
+      SpanOrigin::EdgeAfter(_) => SourceMapRegionKind::InjectedAfter,
+      SpanOrigin::ShadowFragment => SourceMapRegionKind::StitchedFragment,
+    }
+  }
+}
+
+/// One region of the current (instrumented) source unit, mapped back to its counterpart in the
+/// caller's original source where one exists. `original_span` is `None` for every region
+/// synthesized by an `inject_*` call -- there is nothing in the original source to point at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceMapRegion {
+  pub node_id: i64,
+  pub original_span: Option<String>,
+  pub instrumented_span: String,
+  pub kind: SourceMapRegionKind,
+}
+
+/// Builds a [`SourceMapRegion`] for every node in `unit` that carries both an `id` and a `src`,
+/// classified against `spans` (`injected_spans`, keyed by node id): a match reuses that span's
+/// origin and stops recursing into the node's children, since the whole synthesized subtree is
+/// already covered by the one region; everything else is tagged [`SourceMapRegionKind::Original`]
+/// and recursed into, so nested statements/expressions each get their own (overlapping, innermost
+/// wins) region too.
+pub(crate) fn source_map(spans: &[InjectedSpan], unit: &Value) -> Vec<SourceMapRegion> {
+  let injected: HashMap<i64, SourceMapRegionKind> = spans
+    .iter()
+    .map(|span| (span.node_id, SourceMapRegionKind::from(&span.origin)))
+    .collect();
+  let mut regions = Vec::new();
+  collect_source_map(unit, &injected, &mut regions);
+  regions
+}
+
+fn collect_source_map(
+  node: &Value,
+  injected: &HashMap<i64, SourceMapRegionKind>,
+  regions: &mut Vec<SourceMapRegion>,
+) {
+  match node {
+    Value::Object(map) => {
+      let id = map.get("id").and_then(Value::as_i64);
+      let src = map.get("src").and_then(Value::as_str);
+      if let (Some(node_id), Some(src)) = (id, src) {
+        if let Some(kind) = injected.get(&node_id) {
+          regions.push(SourceMapRegion {
+            node_id,
+            original_span: None,
+            instrumented_span: src.to_string(),
+            kind: kind.clone(),
+          });
+          return;
+        }
+        regions.push(SourceMapRegion {
+          node_id,
+          original_span: Some(src.to_string()),
+          instrumented_span: src.to_string(),
+          kind: SourceMapRegionKind::Original,
+        });
+      }
+      for value in map.values() {
+        collect_source_map(value, injected, regions);
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        collect_source_map(item, injected, regions);
+      }
+    }
+    _ => {}
+  }
+}
+
+fn find_src_by_id(node: &Value, target_id: i64) -> Option<String> {
+  match node {
+    Value::Object(map) => {
+      if map.get("id").and_then(Value::as_i64) == Some(target_id) {
+        return map.get("src").and_then(Value::as_str).map(str::to_string);
+      }
+      map.values().find_map(|child| find_src_by_id(child, target_id))
+    }
+    Value::Array(items) => items.iter().find_map(|item| find_src_by_id(item, target_id)),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn record_skips_nodes_without_id() {
+    let mut spans = Vec::new();
+    let nodes = vec![
+      json!({ "nodeType": "ExpressionStatement", "id": 7, "src": "1:2:0" }),
+      json!({ "nodeType": "ExpressionStatement", "src": "3:4:0" }),
+    ];
+    record(&mut spans, SpanOrigin::EdgeBefore("foo()".to_string()), &nodes);
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].node_id, 7);
+    assert_eq!(spans[0].src, "1:2:0");
+    assert_eq!(spans[0].origin, SpanOrigin::EdgeBefore("foo()".to_string()));
+  }
+
+  #[test]
+  fn reresolve_updates_src_and_preserves_stale_on_miss() {
+    let mut spans = vec![
+      InjectedSpan {
+        node_id: 7,
+        node_type: "ExpressionStatement".to_string(),
+        origin: SpanOrigin::EdgeAfter("foo()".to_string()),
+        src: "1:2:0".to_string(),
+      },
+      InjectedSpan {
+        node_id: 99,
+        node_type: "ExpressionStatement".to_string(),
+        origin: SpanOrigin::ShadowFragment,
+        src: "5:6:0".to_string(),
+      },
+    ];
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "nodes": [{ "nodeType": "ExpressionStatement", "id": 7, "src": "10:2:0" }]
+    });
+
+    reresolve(&mut spans, &unit);
+
+    assert_eq!(spans[0].src, "10:2:0");
+    assert_eq!(spans[1].src, "5:6:0", "missing id keeps its last-known src");
+  }
+
+  #[test]
+  fn source_map_tags_injected_nodes_and_stops_recursing_into_them() {
+    let spans = vec![InjectedSpan {
+      node_id: 7,
+      node_type: "ExpressionStatement".to_string(),
+      origin: SpanOrigin::EdgeAfter("foo()".to_string()),
+      src: "20:2:0".to_string(),
+    }];
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "id": 1,
+      "src": "0:30:0",
+      "nodes": [{
+        "nodeType": "ExpressionStatement",
+        "id": 7,
+        "src": "20:2:0",
+        "expression": { "nodeType": "Identifier", "id": 8, "src": "20:1:0" }
+      }]
+    });
+
+    let regions = source_map(&spans, &unit);
+
+    let injected = regions
+      .iter()
+      .find(|region| region.node_id == 7)
+      .expect("injected region present");
+    assert_eq!(injected.kind, SourceMapRegionKind::InjectedAfter);
+    assert_eq!(injected.original_span, None);
+    assert_eq!(injected.instrumented_span, "20:2:0");
+    assert!(
+      !regions.iter().any(|region| region.node_id == 8),
+      "children of an injected node are not walked separately"
+    );
+  }
+
+  #[test]
+  fn source_map_mirrors_original_span_for_untouched_nodes() {
+    let unit = json!({
+      "nodeType": "SourceUnit",
+      "id": 1,
+      "src": "0:10:0",
+      "nodes": [{ "nodeType": "ContractDefinition", "id": 2, "src": "0:10:0" }]
+    });
+
+    let regions = source_map(&[], &unit);
+
+    let root = regions.iter().find(|region| region.node_id == 1).unwrap();
+    assert_eq!(root.kind, SourceMapRegionKind::Original);
+    assert_eq!(root.original_span.as_deref(), Some("0:10:0"));
+    assert_eq!(root.instrumented_span, "0:10:0");
+  }
+}
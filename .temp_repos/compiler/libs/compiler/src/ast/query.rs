@@ -0,0 +1,475 @@
+use serde_json::Value;
+
+use super::{error::AstError, utils};
+
+/// Solidity declaration kinds a [`NodeSelector`] can filter on, modeled as a small closed set
+/// (in the spirit of e.g. rhai's `FnAccess`/`ScriptFnDef` split of public/private function
+/// definitions) rather than accepting an arbitrary `nodeType` string, so a typo in a selector
+/// fails fast instead of silently matching nothing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+  ContractDefinition,
+  FunctionDefinition,
+  ModifierDefinition,
+  VariableDeclaration,
+  EventDefinition,
+  ErrorDefinition,
+  StructDefinition,
+  EnumDefinition,
+}
+
+impl NodeKind {
+  const fn as_node_type(self) -> &'static str {
+    match self {
+      Self::ContractDefinition => "ContractDefinition",
+      Self::FunctionDefinition => "FunctionDefinition",
+      Self::ModifierDefinition => "ModifierDefinition",
+      Self::VariableDeclaration => "VariableDeclaration",
+      Self::EventDefinition => "EventDefinition",
+      Self::ErrorDefinition => "ErrorDefinition",
+      Self::StructDefinition => "StructDefinition",
+      Self::EnumDefinition => "EnumDefinition",
+    }
+  }
+
+  fn from_node_type(node_type: &str) -> Option<Self> {
+    match node_type {
+      "ContractDefinition" => Some(Self::ContractDefinition),
+      "FunctionDefinition" => Some(Self::FunctionDefinition),
+      "ModifierDefinition" => Some(Self::ModifierDefinition),
+      "VariableDeclaration" => Some(Self::VariableDeclaration),
+      "EventDefinition" => Some(Self::EventDefinition),
+      "ErrorDefinition" => Some(Self::ErrorDefinition),
+      "StructDefinition" => Some(Self::StructDefinition),
+      "EnumDefinition" => Some(Self::EnumDefinition),
+      _ => None,
+    }
+  }
+}
+
+impl std::str::FromStr for NodeKind {
+  type Err = AstError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    Self::from_node_type(value)
+      .ok_or_else(|| AstError::InvalidContractStructure(format!("Unknown node kind `{value}`")))
+  }
+}
+
+impl std::fmt::Display for NodeKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_node_type())
+  }
+}
+
+/// Visibility modifiers solc reports on `FunctionDefinition`/`VariableDeclaration` nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeVisibility {
+  Public,
+  Private,
+  Internal,
+  External,
+}
+
+impl std::str::FromStr for NodeVisibility {
+  type Err = AstError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "public" => Ok(Self::Public),
+      "private" => Ok(Self::Private),
+      "internal" => Ok(Self::Internal),
+      "external" => Ok(Self::External),
+      other => Err(AstError::InvalidContractStructure(format!(
+        "Unknown node visibility `{other}`"
+      ))),
+    }
+  }
+}
+
+impl std::fmt::Display for NodeVisibility {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let value = match self {
+      Self::Public => "public",
+      Self::Private => "private",
+      Self::Internal => "internal",
+      Self::External => "external",
+    };
+    f.write_str(value)
+  }
+}
+
+/// Filters applied when walking a source unit with [`query`]/[`visit`]. Every field is optional;
+/// omitted filters match anything.
+#[derive(Clone, Debug, Default)]
+pub struct NodeSelector {
+  pub kind: Option<NodeKind>,
+  pub name: Option<String>,
+  pub visibility: Option<NodeVisibility>,
+  pub contract: Option<String>,
+}
+
+/// One node matched by [`query`]/[`visit`]: its RFC 6901 JSON pointer within the source unit
+/// (e.g. `/nodes/0/nodes/2`), the enclosing contract (if any), its `nodeType`, and a clone of the
+/// node itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryMatch {
+  pub path: String,
+  pub contract: Option<String>,
+  pub node_type: Option<String>,
+  pub node: Value,
+}
+
+/// What a [`visit`] callback decided to do with a matched node.
+#[derive(Clone, Debug)]
+pub enum VisitAction {
+  Keep,
+  Replace(Value),
+}
+
+fn node_type(value: &Value) -> Option<&str> {
+  value.get("nodeType").and_then(Value::as_str)
+}
+
+fn node_name(value: &Value) -> Option<&str> {
+  value.get("name").and_then(Value::as_str)
+}
+
+fn node_visibility(value: &Value) -> Option<NodeVisibility> {
+  value
+    .get("visibility")
+    .and_then(Value::as_str)
+    .and_then(|visibility| visibility.parse().ok())
+}
+
+fn matches_selector(node: &Value, contract: Option<&str>, selector: &NodeSelector) -> bool {
+  if let Some(kind) = selector.kind {
+    if node_type(node) != Some(kind.as_node_type()) {
+      return false;
+    }
+  }
+  if let Some(name) = &selector.name {
+    if node_name(node) != Some(name.as_str()) {
+      return false;
+    }
+  }
+  if let Some(visibility) = selector.visibility {
+    if node_visibility(node) != Some(visibility) {
+      return false;
+    }
+  }
+  if let Some(want_contract) = &selector.contract {
+    if contract != Some(want_contract.as_str()) {
+      return false;
+    }
+  }
+  true
+}
+
+/// Walks `unit` like a generic tree (every object/array key, recursively) and collects every node
+/// carrying a `nodeType` that satisfies `selector`, in document order.
+pub fn query(unit: &Value, selector: &NodeSelector) -> Vec<QueryMatch> {
+  let mut matches = Vec::new();
+  walk(unit, String::new(), None, selector, &mut matches);
+  matches
+}
+
+/// Like [`query`], but runs `callback` against each match and splices any
+/// [`VisitAction::Replace`] back into `unit` in place, assigning fresh `id`s to the replacement
+/// subtree via the same renumbering [`utils::clone_with_new_ids`] the stitcher module uses when
+/// merging fragment nodes -- so a visited replacement behaves like any other stitched-in node to
+/// a later `compile()`. Returns the number of nodes replaced.
+pub fn visit(
+  unit: &mut Value,
+  selector: &NodeSelector,
+  mut callback: impl FnMut(&QueryMatch) -> Result<VisitAction, AstError>,
+) -> Result<usize, AstError> {
+  let matches = query(unit, selector);
+  let mut next_id = utils::max_id(unit);
+  let mut replaced = 0;
+  for found in &matches {
+    if let VisitAction::Replace(replacement) = callback(found)? {
+      let renumbered = utils::clone_with_new_ids(&replacement, &mut next_id);
+      let slot = unit.pointer_mut(&found.path).ok_or_else(|| {
+        AstError::InvalidContractStructure(format!("No node at path `{}`", found.path))
+      })?;
+      *slot = renumbered;
+      replaced += 1;
+    }
+  }
+  Ok(replaced)
+}
+
+fn walk(
+  node: &Value,
+  pointer: String,
+  contract: Option<&str>,
+  selector: &NodeSelector,
+  matches: &mut Vec<QueryMatch>,
+) {
+  match node {
+    Value::Object(map) => {
+      let current_contract = if node_type(node) == Some("ContractDefinition") {
+        node_name(node)
+      } else {
+        contract
+      };
+      if node_type(node).is_some() && matches_selector(node, current_contract, selector) {
+        matches.push(QueryMatch {
+          path: pointer.clone(),
+          contract: current_contract.map(str::to_owned),
+          node_type: node_type(node).map(str::to_owned),
+          node: node.clone(),
+        });
+      }
+      for (key, value) in map {
+        walk(
+          value,
+          format!("{pointer}/{}", escape_pointer_segment(key)),
+          current_contract,
+          selector,
+          matches,
+        );
+      }
+    }
+    Value::Array(items) => {
+      for (idx, item) in items.iter().enumerate() {
+        walk(item, format!("{pointer}/{idx}"), contract, selector, matches);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Escapes `~`/`/` per RFC 6901 so object keys round-trip through [`Value::pointer_mut`].
+fn escape_pointer_segment(segment: &str) -> String {
+  segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverses [`escape_pointer_segment`].
+fn unescape_pointer_segment(segment: &str) -> String {
+  segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// Finds the RFC 6901 pointer path of the first node in `unit` whose `id` field equals `id`, in
+/// document order. Used to let callers key edits off a stable solc node id instead of a path that
+/// shifts whenever a sibling is inserted or removed.
+pub fn find_path_by_id(unit: &Value, id: i64) -> Option<String> {
+  fn walk(node: &Value, pointer: &str, id: i64) -> Option<String> {
+    match node {
+      Value::Object(map) => {
+        if node.get("id").and_then(Value::as_i64) == Some(id) {
+          return Some(pointer.to_string());
+        }
+        for (key, value) in map {
+          if let Some(found) = walk(value, &format!("{pointer}/{}", escape_pointer_segment(key)), id) {
+            return Some(found);
+          }
+        }
+        None
+      }
+      Value::Array(items) => items
+        .iter()
+        .enumerate()
+        .find_map(|(idx, item)| walk(item, &format!("{pointer}/{idx}"), id)),
+      _ => None,
+    }
+  }
+  walk(unit, "", id)
+}
+
+/// Splits an RFC 6901 pointer into its parent pointer and final (unescaped) segment. Returns
+/// `None` for the root pointer (`""`), which has no parent.
+fn split_pointer(path: &str) -> Option<(String, String)> {
+  let idx = path.rfind('/')?;
+  Some((path[..idx].to_string(), unescape_pointer_segment(&path[idx + 1..])))
+}
+
+/// Removes the node at `path` (as produced by [`query`] or [`find_path_by_id`]) from `unit`,
+/// shifting down any later siblings if the parent is an array.
+pub fn remove_at_path(unit: &mut Value, path: &str) -> Result<(), AstError> {
+  let (parent_path, segment) = split_pointer(path).ok_or_else(|| {
+    AstError::InvalidContractStructure("Cannot remove the root source unit".to_string())
+  })?;
+  let parent = unit
+    .pointer_mut(&parent_path)
+    .ok_or_else(|| AstError::InvalidContractStructure(format!("No node at path `{path}`")))?;
+  match parent {
+    Value::Array(items) => {
+      let idx: usize = segment.parse().map_err(|_| {
+        AstError::InvalidContractStructure(format!("Invalid array index in path `{path}`"))
+      })?;
+      if idx >= items.len() {
+        return Err(AstError::InvalidContractStructure(format!(
+          "No node at path `{path}`"
+        )));
+      }
+      items.remove(idx);
+      Ok(())
+    }
+    Value::Object(map) => {
+      if map.remove(&segment).is_none() {
+        return Err(AstError::InvalidContractStructure(format!(
+          "No node at path `{path}`"
+        )));
+      }
+      Ok(())
+    }
+    _ => Err(AstError::InvalidContractStructure(format!(
+      "No node at path `{path}`"
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn sample_unit() -> Value {
+    json!({
+      "nodeType": "SourceUnit",
+      "nodes": [
+        {
+          "nodeType": "ContractDefinition",
+          "name": "Token",
+          "nodes": [
+            {
+              "nodeType": "FunctionDefinition",
+              "name": "transfer",
+              "visibility": "public",
+              "id": 1
+            },
+            {
+              "nodeType": "FunctionDefinition",
+              "name": "_burn",
+              "visibility": "internal",
+              "id": 2
+            },
+            {
+              "nodeType": "VariableDeclaration",
+              "name": "balance",
+              "visibility": "private",
+              "id": 3
+            }
+          ]
+        },
+        {
+          "nodeType": "ContractDefinition",
+          "name": "Vault",
+          "nodes": [
+            {
+              "nodeType": "FunctionDefinition",
+              "name": "transfer",
+              "visibility": "external",
+              "id": 4
+            }
+          ]
+        }
+      ]
+    })
+  }
+
+  #[test]
+  fn query_filters_by_kind_and_visibility() {
+    let unit = sample_unit();
+    let selector = NodeSelector {
+      kind: Some(NodeKind::FunctionDefinition),
+      visibility: Some(NodeVisibility::Internal),
+      ..NodeSelector::default()
+    };
+
+    let matches = query(&unit, &selector);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].node["name"], "_burn");
+    assert_eq!(matches[0].contract.as_deref(), Some("Token"));
+    assert_eq!(matches[0].path, "/nodes/0/nodes/1");
+  }
+
+  #[test]
+  fn query_filters_by_name_across_contracts() {
+    let unit = sample_unit();
+    let selector = NodeSelector {
+      name: Some("transfer".to_string()),
+      ..NodeSelector::default()
+    };
+
+    let matches = query(&unit, &selector);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].contract.as_deref(), Some("Token"));
+    assert_eq!(matches[1].contract.as_deref(), Some("Vault"));
+  }
+
+  #[test]
+  fn query_filters_by_containing_contract() {
+    let unit = sample_unit();
+    let selector = NodeSelector {
+      contract: Some("Vault".to_string()),
+      kind: Some(NodeKind::FunctionDefinition),
+      ..NodeSelector::default()
+    };
+
+    let matches = query(&unit, &selector);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].node["name"], "transfer");
+  }
+
+  #[test]
+  fn visit_replaces_matched_nodes_and_assigns_fresh_ids() {
+    let mut unit = sample_unit();
+    let selector = NodeSelector {
+      kind: Some(NodeKind::VariableDeclaration),
+      ..NodeSelector::default()
+    };
+
+    let replaced = visit(&mut unit, &selector, |found| {
+      let mut patched = found.node.clone();
+      patched["visibility"] = json!("public");
+      Ok(VisitAction::Replace(patched))
+    })
+    .expect("visit");
+
+    assert_eq!(replaced, 1);
+    let balance = &unit["nodes"][0]["nodes"][2];
+    assert_eq!(balance["visibility"], "public");
+    assert_ne!(balance["id"], json!(3), "replacement should get a fresh id");
+  }
+
+  #[test]
+  fn visit_keeps_nodes_the_callback_declines_to_replace() {
+    let mut unit = sample_unit();
+    let selector = NodeSelector {
+      kind: Some(NodeKind::FunctionDefinition),
+      ..NodeSelector::default()
+    };
+
+    let replaced = visit(&mut unit, &selector, |_found| Ok(VisitAction::Keep)).expect("visit");
+
+    assert_eq!(replaced, 0);
+    assert_eq!(unit, sample_unit());
+  }
+
+  #[test]
+  fn find_path_by_id_locates_matching_node() {
+    let unit = sample_unit();
+    assert_eq!(find_path_by_id(&unit, 2), Some("/nodes/0/nodes/1".to_string()));
+    assert_eq!(find_path_by_id(&unit, 999), None);
+  }
+
+  #[test]
+  fn remove_at_path_deletes_array_entry_and_shifts_siblings() {
+    let mut unit = sample_unit();
+    remove_at_path(&mut unit, "/nodes/0/nodes/1").expect("remove");
+
+    let members = unit["nodes"][0]["nodes"].as_array().expect("members");
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[1]["name"], "balance");
+  }
+
+  #[test]
+  fn remove_at_path_rejects_root_and_missing_paths() {
+    let mut unit = sample_unit();
+    assert!(remove_at_path(&mut unit, "").is_err());
+    assert!(remove_at_path(&mut unit, "/nodes/0/nodes/99").is_err());
+  }
+}
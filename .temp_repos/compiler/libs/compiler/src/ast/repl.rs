@@ -0,0 +1,292 @@
+use std::fmt;
+
+use napi::{Env, JsUnknown};
+use serde_json::Value;
+
+use super::utils::to_js_value;
+use super::{Ast, JsAst};
+use crate::internal::errors::{napi_error, Error};
+
+/// Which half of an edge-instrumentation pair a REPL snippet is being entered for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EdgeKind {
+  Before,
+  After,
+}
+
+impl fmt::Display for EdgeKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      EdgeKind::Before => write!(f, "before"),
+      EdgeKind::After => write!(f, "after"),
+    }
+  }
+}
+
+/// The result of feeding one line of input into a [`Repl`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReplOutcome {
+  /// The current snippet is unbalanced (open brace/paren/bracket or string literal); keep
+  /// prompting and feeding lines until it resolves.
+  Pending,
+  /// A message to print to the user: a confirmation, a `:show` render, or a diagnostic.
+  Output(String),
+}
+
+/// Interactive session for iterating on `injectShadowAtEdges` instrumentation one snippet at a
+/// time. Feed it lines of input (from stdin, a Node readline loop, anywhere); it accumulates
+/// multi-line snippets until they're balanced, applies them to the active target, and keeps a
+/// snapshot of the AST before every successful injection so `:undo` can restore it.
+///
+/// Recognised commands (anything not starting with `:` while no snippet is pending is rejected):
+/// - `:target <selector>` — switch the function resolved via `parse_selector` for later snippets.
+/// - `:before <code>` / `:after <code>` — begin a before/after snippet; keeps prompting across
+///   lines until braces/parens/brackets balance and no string literal is left open.
+/// - `:show` — render the current stitched AST.
+/// - `:undo` — restore the AST to its state before the last successful injection.
+pub struct Repl {
+  ast: Ast,
+  target: Option<String>,
+  history: Vec<Value>,
+  pending: Option<EdgeKind>,
+  buffer: String,
+}
+
+impl Repl {
+  pub fn new(ast: Ast) -> Self {
+    Self {
+      ast,
+      target: None,
+      history: Vec::new(),
+      pending: None,
+      buffer: String::new(),
+    }
+  }
+
+  pub fn ast(&self) -> &Ast {
+    &self.ast
+  }
+
+  pub fn into_ast(self) -> Ast {
+    self.ast
+  }
+
+  /// Feed one line of input. While a multi-line snippet is still unbalanced this returns
+  /// [`ReplOutcome::Pending`] and should be called again with the next line.
+  pub fn feed_line(&mut self, line: &str) -> ReplOutcome {
+    if self.pending.is_none() {
+      return self.handle_command(line);
+    }
+
+    if !self.buffer.is_empty() {
+      self.buffer.push('\n');
+    }
+    self.buffer.push_str(line);
+
+    if is_balanced(&self.buffer) {
+      self.dispatch_pending()
+    } else {
+      ReplOutcome::Pending
+    }
+  }
+
+  fn handle_command(&mut self, line: &str) -> ReplOutcome {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+      return ReplOutcome::Output(String::new());
+    }
+    if !trimmed.starts_with(':') {
+      return ReplOutcome::Output(format!(
+        "Unrecognized input `{trimmed}`. Commands start with `:` — try `:target`, `:before`, `:after`, `:show`, or `:undo`."
+      ));
+    }
+
+    let body = &trimmed[1..];
+    let (command, rest) = body
+      .split_once(char::is_whitespace)
+      .unwrap_or((body, ""));
+
+    match command {
+      "target" => {
+        let selector = rest.trim();
+        if selector.is_empty() {
+          return ReplOutcome::Output("Usage: `:target <selector>`".to_string());
+        }
+        self.target = Some(selector.to_string());
+        ReplOutcome::Output(format!("target set to `{selector}`"))
+      }
+      "show" => ReplOutcome::Output(self.render_current()),
+      "undo" => self.undo(),
+      "before" | "after" => {
+        let kind = if command == "before" {
+          EdgeKind::Before
+        } else {
+          EdgeKind::After
+        };
+        self.pending = Some(kind);
+        self.buffer.clear();
+        self.buffer.push_str(rest.trim_start());
+        if is_balanced(&self.buffer) {
+          self.dispatch_pending()
+        } else {
+          ReplOutcome::Pending
+        }
+      }
+      other => ReplOutcome::Output(format!(
+        "Unknown command `:{other}`. Try `:target`, `:before`, `:after`, `:show`, or `:undo`."
+      )),
+    }
+  }
+
+  fn dispatch_pending(&mut self) -> ReplOutcome {
+    let kind = self
+      .pending
+      .take()
+      .expect("dispatch_pending called without a pending snippet");
+    let snippet = std::mem::take(&mut self.buffer).trim().to_string();
+
+    let Some(target) = self.target.clone() else {
+      return ReplOutcome::Output("No target set. Use `:target <selector>` first.".to_string());
+    };
+    if snippet.is_empty() {
+      return ReplOutcome::Output("Empty snippet; nothing to inject.".to_string());
+    }
+
+    let snapshot = match self.ast.source_unit() {
+      Ok(unit) => unit.clone(),
+      Err(err) => return ReplOutcome::Output(format_diagnostic(&err)),
+    };
+
+    let (before, after): (&[String], &[String]) = match kind {
+      EdgeKind::Before => (std::slice::from_ref(&snippet), &[]),
+      EdgeKind::After => (&[], std::slice::from_ref(&snippet)),
+    };
+
+    match self.ast.inject_shadow_at_edges(&target, before, after, None) {
+      Ok(_) => {
+        self.history.push(snapshot);
+        ReplOutcome::Output(format!("{kind} snippet applied to `{target}`."))
+      }
+      Err(err) => ReplOutcome::Output(format_diagnostic(&err)),
+    }
+  }
+
+  fn undo(&mut self) -> ReplOutcome {
+    let Some(previous) = self.history.pop() else {
+      return ReplOutcome::Output("Nothing to undo.".to_string());
+    };
+    match self.ast.source_unit_mut() {
+      Ok(unit) => {
+        *unit = previous;
+        ReplOutcome::Output("Reverted to the previous snapshot.".to_string())
+      }
+      Err(err) => ReplOutcome::Output(format_diagnostic(&err)),
+    }
+  }
+
+  fn render_current(&self) -> String {
+    match self.ast.source_unit() {
+      Ok(unit) => serde_json::to_string_pretty(unit)
+        .unwrap_or_else(|_| "<failed to render AST>".to_string()),
+      Err(err) => format_diagnostic(&err),
+    }
+  }
+}
+
+fn format_diagnostic(err: &Error) -> String {
+  err
+    .rendered()
+    .map(|rendered| rendered.to_string())
+    .unwrap_or_else(|| err.to_string())
+}
+
+/// Tracks brace/paren/bracket depth and string-literal state across lines so the REPL only
+/// dispatches a snippet once it forms a complete statement. A trailing backslash inside a string
+/// literal escapes the following character (including the closing quote) rather than ending it.
+fn is_balanced(source: &str) -> bool {
+  let mut depth: i32 = 0;
+  let mut string_quote: Option<char> = None;
+  let mut chars = source.chars();
+
+  while let Some(ch) = chars.next() {
+    if let Some(quote) = string_quote {
+      if ch == '\\' {
+        chars.next();
+      } else if ch == quote {
+        string_quote = None;
+      }
+      continue;
+    }
+
+    match ch {
+      '"' | '\'' => string_quote = Some(ch),
+      '(' | '{' | '[' => depth += 1,
+      ')' | '}' | ']' => depth -= 1,
+      _ => {}
+    }
+  }
+
+  depth <= 0 && string_quote.is_none()
+}
+
+/// Interactive REPL for iterating on edge instrumentation, exposed to JS.
+#[napi(js_name = "AstRepl")]
+pub struct JsRepl {
+  inner: Repl,
+}
+
+#[napi]
+impl JsRepl {
+  /// Starts a REPL session over an existing [`JsAst`] instance.
+  #[napi(constructor)]
+  pub fn new(ast: &JsAst) -> Self {
+    Self {
+      inner: Repl::new(ast.inner.clone()),
+    }
+  }
+
+  /// Feed one line of REPL input. Returns `null` while a multi-line snippet is still being
+  /// accumulated, otherwise the message to display (a confirmation, a `:show` render, or a
+  /// span-aware diagnostic).
+  #[napi]
+  pub fn feed_line(&mut self, line: String) -> Option<String> {
+    match self.inner.feed_line(&line) {
+      ReplOutcome::Pending => None,
+      ReplOutcome::Output(message) => Some(message),
+    }
+  }
+
+  /// The stitched AST as it currently stands in the session.
+  #[napi(ts_return_type = "import('./solc-ast').SourceUnit")]
+  pub fn source_unit(&self, env: Env) -> napi::Result<JsUnknown> {
+    let unit = self
+      .inner
+      .ast()
+      .source_unit()
+      .map_err(|err| napi_error(err.to_string()))?;
+    to_js_value(&env, unit)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn is_balanced_tracks_nested_brackets_across_lines() {
+    assert!(!is_balanced("if (value > 0) {"));
+    assert!(is_balanced("if (value > 0) {\n  require(true);\n}"));
+  }
+
+  #[test]
+  fn is_balanced_ignores_brackets_inside_string_literals() {
+    assert!(is_balanced(r#"emit Log("{unbalanced");"#));
+    assert!(!is_balanced(r#"emit Log("unterminated);"#));
+  }
+
+  #[test]
+  fn is_balanced_honors_escaped_quotes() {
+    assert!(is_balanced(r#"emit Log("a \" b");"#));
+    assert!(!is_balanced(r#"emit Log("a \""#));
+  }
+}
@@ -1,9 +1,9 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::internal::config::ResolveConflictStrategy;
+use crate::internal::config::{MergePlacement, ResolveConflictStrategy};
 
-use super::{error::AstError, utils};
+use super::{error::AstError, instrumenter, utils};
 
 const CONTRACT_DEFINITION: &str = "ContractDefinition";
 
@@ -46,39 +46,501 @@ pub fn find_instrumented_contract_index(
     })
 }
 
+/// One name collision [`stitch_fragment_nodes_into_contract`] resolved under
+/// [`ResolveConflictStrategy::Overwrite`]/[`ResolveConflictStrategy::Rename`]/
+/// [`ResolveConflictStrategy::KeepBoth`], and what it did about it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StitchConflict {
+  pub member_name: String,
+  pub node_type: String,
+  pub action: StitchAction,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StitchAction {
+  /// The target's member was replaced with the fragment's.
+  Overwritten,
+  /// The fragment's member was inserted under a new name, suffixed to avoid the collision.
+  Renamed { new_name: String },
+  /// The fragment's member was inserted alongside the target's (a legitimate overload).
+  KeptBoth,
+}
+
+/// The result of splicing a fragment into a target contract: the final form of every node that was
+/// inserted or replaced -- callers use this to record
+/// [`super::provenance::SpanOrigin::ShadowFragment`] provenance for the synthetic members -- plus
+/// any name collisions [`ResolveConflictStrategy::Overwrite`]/[`ResolveConflictStrategy::Rename`]/
+/// [`ResolveConflictStrategy::KeepBoth`] had to resolve along the way, and the same outcome
+/// summarized as a [`StitchReport`] for callers that just want an audit trail.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FragmentStitchResult {
+  pub nodes: Vec<Value>,
+  pub conflicts: Vec<StitchConflict>,
+  pub report: StitchReport,
+}
+
+/// A function, variable, event, or other contract member identified the same way
+/// [`StitchConflict`] identifies one, but tracked by [`StitchReport`] regardless of whether it
+/// collided with anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StitchedMember {
+  pub name: String,
+  pub node_type: String,
+}
+
+/// Structured audit of how a fragment was applied, independent of `strategy`: which members were
+/// newly appended, which replaced an existing target member outright (an
+/// [`ResolveConflictStrategy::Overwrite`]/[`ResolveConflictStrategy::Replace`] collision, or a
+/// [`ResolveConflictStrategy::Merge`] splice -- the target's content changes either way), which
+/// were skipped entirely (currently only a [`stitch_fragment_with_libraries`] library already
+/// present in the target), and how many ids had to be reassigned along the way to keep the
+/// fragment's internal references intact after renumbering (see
+/// [`utils::clone_with_new_ids_mapped`]/[`apply_id_snapshot`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StitchReport {
+  pub appended: Vec<StitchedMember>,
+  pub replaced: Vec<StitchedMember>,
+  pub skipped: Vec<StitchedMember>,
+  pub reassigned_ids: usize,
+}
+
+impl StitchReport {
+  /// Folds `other` into `self`, for a caller (e.g. `inject_fragment*`) accumulating a report
+  /// across several `inject_shadow` calls.
+  pub fn merge(&mut self, other: StitchReport) {
+    self.appended.extend(other.appended);
+    self.replaced.extend(other.replaced);
+    self.skipped.extend(other.skipped);
+    self.reassigned_ids += other.reassigned_ids;
+  }
+}
+
+/// Stitches `fragment_contract`'s members into the target contract. See [`FragmentStitchResult`]
+/// for what's returned.
+///
+/// Before anything is spliced in, every fragment node's `src` span is remapped onto
+/// `target_file_index` with `start` shifted by `base_offset` (see [`utils::walk_remap_src`]), since
+/// the fragment was parsed in its own throwaway source file and its spans otherwise reference that
+/// coordinate space rather than the target unit's.
 pub fn stitch_fragment_nodes_into_contract(
   target: &mut Value,
   contract_idx: usize,
   fragment_contract: &Value,
   max_target_id: i64,
   strategy: ResolveConflictStrategy,
-) -> Result<(), AstError> {
-  let target_contract = contract_mut_at(target, contract_idx)?;
+  merge_placement: MergePlacement,
+  target_file_index: i64,
+  base_offset: i64,
+  source: Option<&str>,
+) -> Result<FragmentStitchResult, AstError> {
+  let mut fragment_contract = fragment_contract.clone();
+  utils::walk_remap_src(&mut fragment_contract, target_file_index, base_offset);
+
+  let target_contract = contract_mut_at(target, contract_idx, source)?;
   let fragment_nodes = fragment_contract
     .get("nodes")
     .and_then(|value| value.as_array())
     .ok_or_else(|| {
-      AstError::InvalidContractStructure("Fragment contract missing nodes array".to_string())
+      AstError::invalid_contract_structure_at(
+        "Fragment contract missing nodes array",
+        &fragment_contract,
+        source,
+      )
     })?;
 
+  let target_contract_snapshot = target_contract.clone();
   let target_nodes = target_contract
     .get_mut("nodes")
     .and_then(|value| value.as_array_mut())
     .ok_or_else(|| {
-      AstError::InvalidContractStructure("Target contract missing nodes array".to_string())
+      AstError::invalid_contract_structure_at(
+        "Target contract missing nodes array",
+        &target_contract_snapshot,
+        source,
+      )
     })?;
 
+  let original_ids: HashSet<i64> = target_nodes
+    .iter()
+    .filter_map(|node| node.get("id").and_then(Value::as_i64))
+    .collect();
+
   let mut next_id = max_target_id;
+  let mut reference_map: HashMap<i64, i64> = HashMap::new();
 
-  match strategy {
+  let (touched, conflicts) = match strategy {
     ResolveConflictStrategy::Safe => {
+      let mut inserted = Vec::with_capacity(fragment_nodes.len());
       for part in fragment_nodes {
-        let cloned = utils::clone_with_new_ids(part, &mut next_id);
-        target_nodes.push(cloned);
+        let (cloned, mapping) = utils::clone_with_new_ids_mapped(part, &mut next_id);
+        reference_map.extend(mapping);
+        target_nodes.push(cloned.clone());
+        inserted.push(cloned);
       }
-      Ok(())
+      (inserted, Vec::new())
+    }
+    ResolveConflictStrategy::Replace => (
+      stitch_replace(target_nodes, fragment_nodes, &mut next_id, &mut reference_map, source)?,
+      Vec::new(),
+    ),
+    ResolveConflictStrategy::Overwrite | ResolveConflictStrategy::Rename | ResolveConflictStrategy::KeepBoth => {
+      stitch_named(
+        target_nodes,
+        fragment_nodes,
+        &mut next_id,
+        &mut reference_map,
+        strategy,
+        source,
+      )?
     }
-    ResolveConflictStrategy::Replace => stitch_replace(target_nodes, fragment_nodes, &mut next_id),
+    ResolveConflictStrategy::Merge => (
+      stitch_merge(
+        target_nodes,
+        fragment_nodes,
+        &mut next_id,
+        &mut reference_map,
+        merge_placement,
+        source,
+      )?,
+      Vec::new(),
+    ),
+  };
+
+  // Fix up any reference elsewhere in the target contract (outside the nodes we just inserted or
+  // replaced) that pointed at a fragment-internal id which got renumbered above.
+  if !reference_map.is_empty() {
+    utils::rewrite_references(target_contract, &reference_map);
+  }
+
+  let report = build_stitch_report(&touched, &original_ids, reference_map.len());
+
+  Ok(FragmentStitchResult { nodes: touched, conflicts, report })
+}
+
+/// Resolves name collisions for [`ResolveConflictStrategy::Overwrite`]/
+/// [`ResolveConflictStrategy::Rename`]/[`ResolveConflictStrategy::KeepBoth`]: a fragment member
+/// with no name, or whose name doesn't collide with any existing target member, is simply
+/// inserted. A colliding member is resolved per `strategy` -- see [`StitchAction`].
+fn stitch_named(
+  target_nodes: &mut Vec<Value>,
+  fragment_nodes: &[Value],
+  next_id: &mut i64,
+  reference_map: &mut HashMap<i64, i64>,
+  strategy: ResolveConflictStrategy,
+  source: Option<&str>,
+) -> Result<(Vec<Value>, Vec<StitchConflict>), AstError> {
+  let mut target_index_by_name: HashMap<String, usize> = HashMap::new();
+  let mut target_names: HashSet<String> = HashSet::new();
+  for (idx, node) in target_nodes.iter().enumerate() {
+    if let Some(name) = node_name(node) {
+      target_index_by_name.insert(name.to_string(), idx);
+      target_names.insert(name.to_string());
+    }
+  }
+
+  let mut touched = Vec::with_capacity(fragment_nodes.len());
+  let mut conflicts = Vec::new();
+
+  for node in fragment_nodes {
+    let node_type_str = node_type(node).unwrap_or("<unknown>").to_string();
+
+    let Some(name) = node_name(node).map(str::to_string) else {
+      let (cloned, mapping) = utils::clone_with_new_ids_mapped(node, next_id);
+      reference_map.extend(mapping);
+      target_nodes.push(cloned.clone());
+      touched.push(cloned);
+      continue;
+    };
+
+    let Some(&target_idx) = target_index_by_name.get(&name) else {
+      let (cloned, mapping) = utils::clone_with_new_ids_mapped(node, next_id);
+      reference_map.extend(mapping);
+      target_names.insert(name);
+      target_nodes.push(cloned.clone());
+      touched.push(cloned);
+      continue;
+    };
+
+    match strategy {
+      ResolveConflictStrategy::Overwrite => {
+        let replacement = overwrite_target_member(target_nodes, target_idx, node, next_id, reference_map);
+        touched.push(replacement);
+        conflicts.push(StitchConflict {
+          member_name: name,
+          node_type: node_type_str,
+          action: StitchAction::Overwritten,
+        });
+      }
+      ResolveConflictStrategy::Rename => {
+        let (mut cloned, mapping) = utils::clone_with_new_ids_mapped(node, next_id);
+        reference_map.extend(mapping);
+        let new_name = unique_name(&name, &target_names);
+        rename_node_and_self_references(&mut cloned, &new_name);
+        target_names.insert(new_name.clone());
+        target_nodes.push(cloned.clone());
+        touched.push(cloned);
+        conflicts.push(StitchConflict {
+          member_name: name,
+          node_type: node_type_str,
+          action: StitchAction::Renamed { new_name },
+        });
+      }
+      ResolveConflictStrategy::KeepBoth => {
+        let same_signature = contract_part_key(node, source)?
+          == contract_part_key(&target_nodes[target_idx], source)?;
+        if same_signature {
+          let replacement = overwrite_target_member(target_nodes, target_idx, node, next_id, reference_map);
+          touched.push(replacement);
+          conflicts.push(StitchConflict {
+            member_name: name,
+            node_type: node_type_str,
+            action: StitchAction::Overwritten,
+          });
+        } else {
+          let (cloned, mapping) = utils::clone_with_new_ids_mapped(node, next_id);
+          reference_map.extend(mapping);
+          target_nodes.push(cloned.clone());
+          touched.push(cloned);
+          conflicts.push(StitchConflict {
+            member_name: name,
+            node_type: node_type_str,
+            action: StitchAction::KeptBoth,
+          });
+        }
+      }
+      ResolveConflictStrategy::Safe | ResolveConflictStrategy::Replace | ResolveConflictStrategy::Merge => {
+        unreachable!("stitch_named is only called for Overwrite/Rename/KeepBoth")
+      }
+    }
+  }
+
+  Ok((touched, conflicts))
+}
+
+/// Replaces `target_nodes[target_idx]` with `node`, reusing the replaced node's own id sequence
+/// (see [`apply_id_snapshot`]) so downstream solc-facing tooling sees stable ids across the
+/// overwrite, and returns the final replacement value.
+fn overwrite_target_member(
+  target_nodes: &mut [Value],
+  target_idx: usize,
+  node: &Value,
+  next_id: &mut i64,
+  reference_map: &mut HashMap<i64, i64>,
+) -> Value {
+  let mut ids = Vec::new();
+  collect_ids(&target_nodes[target_idx], &mut ids);
+  let mut replacement = node.clone();
+  let mapping = apply_id_snapshot(&mut replacement, &ids, next_id);
+  reference_map.extend(mapping);
+  target_nodes[target_idx] = replacement.clone();
+  replacement
+}
+
+/// The first of `{base}_1`, `{base}_2`, ... not already in `taken`.
+fn unique_name(base: &str, taken: &HashSet<String>) -> String {
+  let mut suffix = 1u32;
+  loop {
+    let candidate = format!("{base}_{suffix}");
+    if !taken.contains(&candidate) {
+      return candidate;
+    }
+    suffix += 1;
+  }
+}
+
+/// Sets `node`'s own `name` to `new_name`, and does the same for any node within `node`'s subtree
+/// whose `referencedDeclaration` points back at `node`'s own id (e.g. a recursive call to a
+/// just-renamed function), so the renamed member stays internally self-consistent for a future
+/// textual re-emission of the AST.
+fn rename_node_and_self_references(node: &mut Value, new_name: &str) {
+  let Some(id) = node.get("id").and_then(Value::as_i64) else {
+    return;
+  };
+  if let Value::Object(map) = node {
+    map.insert("name".to_string(), Value::String(new_name.to_string()));
+  }
+  rename_references_to(node, id, new_name);
+}
+
+fn rename_references_to(node: &mut Value, target_id: i64, new_name: &str) {
+  match node {
+    Value::Object(map) => {
+      if map.get("referencedDeclaration").and_then(Value::as_i64) == Some(target_id)
+        && map.contains_key("name")
+      {
+        map.insert("name".to_string(), Value::String(new_name.to_string()));
+      }
+      for child in map.values_mut() {
+        rename_references_to(child, target_id, new_name);
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        rename_references_to(item, target_id, new_name);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Like [`stitch_fragment_nodes_into_contract`], but first resolves the fragment's dangling
+/// library references against `library_units` -- full `SourceUnit` ASTs the fragment's libraries
+/// were parsed from. Mirroring how Foundry resolves and de-duplicates libraries before linking: a
+/// `referencedDeclaration` in the fragment that doesn't resolve to any id inside the fragment
+/// itself is assumed to point at a library declared in one of `library_units`; each such library is
+/// spliced into the target `SourceUnit`'s top-level `nodes` exactly once (skipped if a library of
+/// the same name is already present there), renumbered with the same reference-preserving map as
+/// everything else in this module, and the fragment's own references are rebound onto the newly
+/// inserted ids before the usual contract-member stitch runs.
+pub fn stitch_fragment_with_libraries(
+  target: &mut Value,
+  contract_idx: usize,
+  fragment_contract: &Value,
+  library_units: &[Value],
+  max_target_id: i64,
+  strategy: ResolveConflictStrategy,
+  merge_placement: MergePlacement,
+  target_file_index: i64,
+  base_offset: i64,
+  source: Option<&str>,
+) -> Result<FragmentStitchResult, AstError> {
+  let mut next_id = max_target_id;
+  let mut reference_map: HashMap<i64, i64> = HashMap::new();
+
+  let already_present: HashSet<String> = target
+    .get("nodes")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter(|node| is_library(node))
+    .filter_map(|node| node_name(node).map(str::to_string))
+    .collect();
+
+  let mut required_libraries: Vec<&Value> = Vec::new();
+  let mut requested_names: HashSet<String> = HashSet::new();
+  let mut skipped: Vec<StitchedMember> = Vec::new();
+  for id in collect_unresolved_references(fragment_contract) {
+    let Some(library) = library_contract_for_id(library_units, id) else {
+      continue;
+    };
+    let Some(name) = node_name(library) else {
+      continue;
+    };
+    if already_present.contains(name) {
+      skipped.push(StitchedMember {
+        name: name.to_string(),
+        node_type: CONTRACT_DEFINITION.to_string(),
+      });
+      continue;
+    }
+    if !requested_names.insert(name.to_string()) {
+      continue;
+    }
+    required_libraries.push(library);
+  }
+
+  let target_snapshot = target.clone();
+  let target_nodes = target.get_mut("nodes").and_then(Value::as_array_mut).ok_or_else(|| {
+    AstError::invalid_contract_structure_at(
+      "Source unit has no nodes array",
+      &target_snapshot,
+      source,
+    )
+  })?;
+
+  for library in required_libraries {
+    let (cloned, mapping) = utils::clone_with_new_ids_mapped(library, &mut next_id);
+    reference_map.extend(mapping);
+    target_nodes.push(cloned);
+  }
+
+  let mut rebound_fragment = fragment_contract.clone();
+  if !reference_map.is_empty() {
+    utils::rewrite_references(&mut rebound_fragment, &reference_map);
+  }
+
+  let mut result = stitch_fragment_nodes_into_contract(
+    target,
+    contract_idx,
+    &rebound_fragment,
+    next_id,
+    strategy,
+    merge_placement,
+    target_file_index,
+    base_offset,
+    source,
+  )?;
+  result.report.skipped.extend(skipped);
+  result.report.reassigned_ids += reference_map.len();
+  Ok(result)
+}
+
+fn is_library(node: &Value) -> bool {
+  node_type(node) == Some(CONTRACT_DEFINITION)
+    && node.get("contractKind").and_then(Value::as_str) == Some("library")
+}
+
+/// Every `referencedDeclaration` in `fragment` that doesn't resolve to any node id within the
+/// fragment itself -- candidates for a library (or other external declaration) the fragment expects
+/// to find elsewhere. The sentinel `-1` ("no declaration", e.g. a builtin) is excluded.
+fn collect_unresolved_references(fragment: &Value) -> Vec<i64> {
+  let mut own_ids = Vec::new();
+  collect_ids(fragment, &mut own_ids);
+  let own_ids: HashSet<i64> = own_ids.into_iter().collect();
+
+  let mut referenced = Vec::new();
+  collect_referenced_declarations(fragment, &mut referenced);
+
+  referenced.into_iter().filter(|id| *id >= 0 && !own_ids.contains(id)).collect()
+}
+
+fn collect_referenced_declarations(node: &Value, out: &mut Vec<i64>) {
+  match node {
+    Value::Object(map) => {
+      if let Some(id) = map.get("referencedDeclaration").and_then(Value::as_i64) {
+        out.push(id);
+      }
+      for child in map.values() {
+        collect_referenced_declarations(child, out);
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        collect_referenced_declarations(item, out);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Finds the top-level library `ContractDefinition` in `library_units` that either owns `id`
+/// directly (a reference to the library contract itself, e.g. `using L for uint256`) or contains a
+/// node with that id somewhere in its subtree (e.g. a reference to one of the library's functions).
+fn library_contract_for_id(library_units: &[Value], id: i64) -> Option<&Value> {
+  for unit in library_units {
+    let Some(nodes) = unit.get("nodes").and_then(Value::as_array) else {
+      continue;
+    };
+    for node in nodes {
+      if !is_library(node) {
+        continue;
+      }
+      if node.get("id").and_then(Value::as_i64) == Some(id) || contains_id(node, id) {
+        return Some(node);
+      }
+    }
+  }
+  None
+}
+
+fn contains_id(node: &Value, target: i64) -> bool {
+  match node {
+    Value::Object(map) => {
+      map.get("id").and_then(Value::as_i64) == Some(target)
+        || map.values().any(|child| contains_id(child, target))
+    }
+    Value::Array(items) => items.iter().any(|item| contains_id(item, target)),
+    _ => false,
   }
 }
 
@@ -86,11 +548,13 @@ fn stitch_replace(
   target_nodes: &mut Vec<Value>,
   fragment_nodes: &[Value],
   next_id: &mut i64,
-) -> Result<(), AstError> {
+  reference_map: &mut HashMap<i64, i64>,
+  source: Option<&str>,
+) -> Result<Vec<Value>, AstError> {
   let mut target_index_by_key: HashMap<ConflictKey, (usize, Vec<i64>)> = HashMap::new();
 
   for (idx, node) in target_nodes.iter().enumerate() {
-    if let Some(key) = contract_part_key(node)? {
+    if let Some(key) = contract_part_key(node, source)? {
       let mut ids = Vec::new();
       collect_ids(node, &mut ids);
       target_index_by_key.insert(key, (idx, ids));
@@ -101,7 +565,7 @@ fn stitch_replace(
   let mut append_nodes: Vec<Value> = Vec::new();
 
   for node in fragment_nodes {
-    let candidate = if let Some(key) = contract_part_key(node)? {
+    let candidate = if let Some(key) = contract_part_key(node, source)? {
       if let Some((idx, ids)) = target_index_by_key.remove(&key) {
         replacements.push((idx, ids, node.clone()));
         continue;
@@ -115,23 +579,175 @@ fn stitch_replace(
 
   replacements.sort_by_key(|(idx, _, _)| *idx);
 
+  let mut touched = Vec::with_capacity(replacements.len() + append_nodes.len());
+
   for (idx, snapshot, mut replacement) in replacements {
-    apply_id_snapshot(&mut replacement, &snapshot, next_id);
+    let mapping = apply_id_snapshot(&mut replacement, &snapshot, next_id);
+    reference_map.extend(mapping);
     if let Some(slot) = target_nodes.get_mut(idx) {
-      *slot = replacement;
+      *slot = replacement.clone();
+      touched.push(replacement);
     } else {
-      return Err(AstError::InvalidContractStructure(
-        "Replacement index out of bounds".to_string(),
+      return Err(AstError::invalid_contract_structure_at(
+        "Replacement index out of bounds",
+        &replacement,
+        source,
       ));
     }
   }
 
   for node in append_nodes {
-    let cloned = utils::clone_with_new_ids(&node, next_id);
-    target_nodes.push(cloned);
+    let (cloned, mapping) = utils::clone_with_new_ids_mapped(&node, next_id);
+    reference_map.extend(mapping);
+    target_nodes.push(cloned.clone());
+    touched.push(cloned);
+  }
+
+  Ok(touched)
+}
+
+/// Resolves [`ResolveConflictStrategy::Merge`]: a fragment `FunctionDefinition` whose
+/// [`ConflictKey`] matches an existing target function has its body spliced around the target's
+/// own statements (see [`merge_function_body`]) instead of being replaced outright; every other
+/// fragment node -- a non-colliding function, or any other member kind -- falls back to
+/// [`ResolveConflictStrategy::Safe`]'s plain append.
+fn stitch_merge(
+  target_nodes: &mut Vec<Value>,
+  fragment_nodes: &[Value],
+  next_id: &mut i64,
+  reference_map: &mut HashMap<i64, i64>,
+  placement: MergePlacement,
+  source: Option<&str>,
+) -> Result<Vec<Value>, AstError> {
+  let mut target_index_by_key: HashMap<ConflictKey, usize> = HashMap::new();
+  for (idx, node) in target_nodes.iter().enumerate() {
+    if let Some(key @ ConflictKey::Function { .. }) = contract_part_key(node, source)? {
+      target_index_by_key.insert(key, idx);
+    }
+  }
+
+  let mut touched = Vec::with_capacity(fragment_nodes.len());
+
+  for node in fragment_nodes {
+    let key = contract_part_key(node, source)?;
+    let target_idx = key.as_ref().and_then(|key| target_index_by_key.get(key).copied());
+
+    let Some(idx) = target_idx else {
+      let (cloned, mapping) = utils::clone_with_new_ids_mapped(node, next_id);
+      reference_map.extend(mapping);
+      target_nodes.push(cloned.clone());
+      touched.push(cloned);
+      continue;
+    };
+
+    let merged = merge_function_body(&mut target_nodes[idx], node, next_id, placement, source)?;
+    touched.push(merged);
   }
 
-  Ok(())
+  Ok(touched)
+}
+
+/// Splices `fragment_function`'s body statements around `target_function`'s own statements
+/// per `placement`, mirroring [`super::instrumenter::inject_edges`]'s before/after handling for a
+/// single function: `Before` inserts a fresh clone of the fragment's statements ahead of the
+/// target's own, `After` reuses [`instrumenter::inject_after`] to splice a clone before every
+/// `return`/exiting `assembly` block and appends one more clone at the very end (so a fallthrough
+/// exit still runs them), and `Around` does both. Each insertion gets its own freshly cloned,
+/// freshly numbered statements -- see [`utils::clone_with_new_ids`] -- the target function itself
+/// keeps its own id and position, so nothing outside its body needs a reference fix-up.
+fn merge_function_body(
+  target_function: &mut Value,
+  fragment_function: &Value,
+  next_id: &mut i64,
+  placement: MergePlacement,
+  source: Option<&str>,
+) -> Result<Value, AstError> {
+  let fragment_statements = fragment_function
+    .get("body")
+    .and_then(|body| body.get("statements"))
+    .and_then(Value::as_array)
+    .ok_or_else(|| {
+      AstError::invalid_contract_structure_at(
+        "Merge fragment function has no statements to splice",
+        fragment_function,
+        source,
+      )
+    })?
+    .clone();
+
+  let target_statements = target_function
+    .get_mut("body")
+    .and_then(|body| body.get_mut("statements"))
+    .and_then(Value::as_array_mut)
+    .ok_or_else(|| {
+      AstError::invalid_contract_structure_at(
+        "Cannot merge into a function without an implementation",
+        target_function,
+        source,
+      )
+    })?;
+
+  if matches!(placement, MergePlacement::After | MergePlacement::Around) {
+    instrumenter::inject_after(target_statements, &fragment_statements, next_id, &mut Vec::new())
+      .map_err(|err| AstError::InvalidContractStructure(err.to_string()))?;
+    let tail = clone_statement_list(&fragment_statements, next_id);
+    target_statements.extend(tail);
+  }
+
+  if matches!(placement, MergePlacement::Before | MergePlacement::Around) {
+    let clones = clone_statement_list(&fragment_statements, next_id);
+    for (offset, statement) in clones.into_iter().enumerate() {
+      target_statements.insert(offset, statement);
+    }
+  }
+
+  Ok(target_function.clone())
+}
+
+fn clone_statement_list(statements: &[Value], next_id: &mut i64) -> Vec<Value> {
+  statements
+    .iter()
+    .map(|statement| utils::clone_with_new_ids(statement, next_id))
+    .collect()
+}
+
+/// Classifies every node in `touched` as appended (a brand-new id, not present in
+/// `original_target_ids`) or replaced (its id matches one the target contract already had, which
+/// every id-preserving strategy -- [`overwrite_target_member`], [`stitch_replace`]'s matched
+/// branch, and [`merge_function_body`] -- deliberately keeps so downstream tooling sees a stable
+/// id across the change).
+fn build_stitch_report(
+  touched: &[Value],
+  original_target_ids: &HashSet<i64>,
+  reassigned_ids: usize,
+) -> StitchReport {
+  let mut report = StitchReport {
+    reassigned_ids,
+    ..Default::default()
+  };
+
+  for node in touched {
+    let Some(member) = node_type(node).map(|node_type| StitchedMember {
+      name: node_name(node).unwrap_or_default().to_string(),
+      node_type: node_type.to_string(),
+    }) else {
+      continue;
+    };
+
+    let replaced = node
+      .get("id")
+      .and_then(Value::as_i64)
+      .map(|id| original_target_ids.contains(&id))
+      .unwrap_or(false);
+
+    if replaced {
+      report.replaced.push(member);
+    } else {
+      report.appended.push(member);
+    }
+  }
+
+  report
 }
 
 fn node_type(value: &Value) -> Option<&str> {
@@ -142,21 +758,36 @@ fn node_name(value: &Value) -> Option<&str> {
   value.get("name").and_then(|value| value.as_str())
 }
 
-fn contract_mut_at<'a>(unit: &'a mut Value, idx: usize) -> Result<&'a mut Value, AstError> {
+fn contract_mut_at<'a>(
+  unit: &'a mut Value,
+  idx: usize,
+  source: Option<&str>,
+) -> Result<&'a mut Value, AstError> {
+  let unit_snapshot = unit.clone();
   let nodes = unit
     .get_mut("nodes")
     .and_then(|value| value.as_array_mut())
-    .ok_or_else(|| AstError::InvalidContractStructure("Source unit has no nodes array".into()))?;
+    .ok_or_else(|| {
+      AstError::invalid_contract_structure_at(
+        "Source unit has no nodes array",
+        &unit_snapshot,
+        source,
+      )
+    })?;
 
   let Some(node) = nodes.get_mut(idx) else {
-    return Err(AstError::InvalidContractStructure(
-      "Invalid contract index".to_string(),
+    return Err(AstError::invalid_contract_structure_at(
+      "Invalid contract index",
+      &unit_snapshot,
+      source,
     ));
   };
 
   if node_type(node) != Some(CONTRACT_DEFINITION) {
-    return Err(AstError::InvalidContractStructure(
-      "Target index is not a contract definition".to_string(),
+    return Err(AstError::invalid_contract_structure_at(
+      "Target index is not a contract definition",
+      node,
+      source,
     ));
   }
 
@@ -179,11 +810,11 @@ enum ConflictKey {
   UserDefinedValueType(String),
 }
 
-fn contract_part_key(node: &Value) -> Result<Option<ConflictKey>, AstError> {
+fn contract_part_key(node: &Value, source: Option<&str>) -> Result<Option<ConflictKey>, AstError> {
   match node_type(node) {
     Some("FunctionDefinition") => {
       let name = node_name(node).unwrap_or_default().to_string();
-      let signature = function_signature(node)?;
+      let signature = function_signature(node, source)?;
       let kind = function_kind_tag(node);
       Ok(Some(ConflictKey::Function {
         name,
@@ -211,14 +842,25 @@ fn contract_part_key(node: &Value) -> Result<Option<ConflictKey>, AstError> {
   }
 }
 
-pub(crate) fn function_signature(function: &Value) -> Result<Vec<String>, AstError> {
+/// Keys a function's parameters off solc's internal `typeIdentifier`/`typeString`, purely to tell
+/// same-shape overloads apart while stitching ([`ConflictKey::Function`]) -- two parameters that
+/// solc itself considers the same type always produce equal keys here, which is all a dedup check
+/// needs. For the canonical ABI signature/selector external tooling actually keys functions by
+/// (and that [`instrumenter::parse_selector`]/[`super::abi::function_selector`] use to resolve a
+/// `name(types)` or `0x`-prefixed locator), see [`super::abi::canonical_parameter_types`] instead.
+pub(crate) fn function_signature(
+  function: &Value,
+  source: Option<&str>,
+) -> Result<Vec<String>, AstError> {
   let parameters = function
     .get("parameters")
     .and_then(|value| value.get("parameters"))
     .and_then(|value| value.as_array())
     .ok_or_else(|| {
-      AstError::InvalidContractStructure(
-        "FunctionDefinition parameters list is missing".to_string(),
+      AstError::invalid_contract_structure_at(
+        "FunctionDefinition parameters list is missing",
+        function,
+        source,
       )
     })?;
 
@@ -258,7 +900,7 @@ fn parameter_type_key(param: &Value, idx: usize) -> Result<String, AstError> {
   Ok(format!("__anon_parameter_{}", idx))
 }
 
-fn serialise_without_ids(node: &Value) -> Result<String, AstError> {
+pub(crate) fn serialise_without_ids(node: &Value) -> Result<String, AstError> {
   let mut clone = node.clone();
   drop_ids(&mut clone);
   serde_json::to_string(&clone).map_err(|err| AstError::JsonError(err.to_string()))
@@ -302,9 +944,16 @@ fn collect_ids(node: &Value, ids: &mut Vec<i64>) {
   }
 }
 
-fn apply_id_snapshot(node: &mut Value, snapshot: &[i64], next_id: &mut i64) {
+/// Reassigns `node`'s ids from `snapshot` (reusing the replaced target node's own id sequence so
+/// downstream solc-facing tooling sees stable ids across a replace), falling back to `next_id` once
+/// `snapshot` runs out. Returns the `old_id -> new_id` map so the caller can fix up any reference
+/// (within this node or elsewhere in the contract) that pointed at the fragment node's original id.
+fn apply_id_snapshot(node: &mut Value, snapshot: &[i64], next_id: &mut i64) -> HashMap<i64, i64> {
   let mut cursor = 0usize;
-  assign_ids_with_snapshot(node, snapshot, &mut cursor, next_id);
+  let mut mapping = HashMap::new();
+  assign_ids_with_snapshot(node, snapshot, &mut cursor, next_id, &mut mapping);
+  utils::rewrite_references(node, &mapping);
+  mapping
 }
 
 fn assign_ids_with_snapshot(
@@ -312,10 +961,12 @@ fn assign_ids_with_snapshot(
   snapshot: &[i64],
   cursor: &mut usize,
   next_id: &mut i64,
+  mapping: &mut HashMap<i64, i64>,
 ) {
   match node {
     Value::Object(map) => {
       if map.get("nodeType").is_some() {
+        let old_id = map.get("id").and_then(Value::as_i64);
         let replacement = if *cursor < snapshot.len() {
           let id = snapshot[*cursor];
           *cursor += 1;
@@ -325,14 +976,17 @@ fn assign_ids_with_snapshot(
           *next_id
         };
         map.insert("id".to_string(), json!(replacement));
+        if let Some(old_id) = old_id {
+          mapping.insert(old_id, replacement);
+        }
       }
       for child in map.values_mut() {
-        assign_ids_with_snapshot(child, snapshot, cursor, next_id);
+        assign_ids_with_snapshot(child, snapshot, cursor, next_id, mapping);
       }
     }
     Value::Array(items) => {
       for item in items {
-        assign_ids_with_snapshot(item, snapshot, cursor, next_id);
+        assign_ids_with_snapshot(item, snapshot, cursor, next_id, mapping);
       }
     }
     _ => {}
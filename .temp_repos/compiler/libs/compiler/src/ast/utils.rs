@@ -1,8 +1,21 @@
+use std::collections::HashMap;
+
 use napi::{Env, JsUnknown};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::Value;
 
+use super::error::SourceSpan;
+
+/// Node fields whose integer value is itself a reference to another node's `id`, rather than an
+/// id of its own.
+const REFERENCE_SCALAR_FIELDS: [&str; 2] = ["referencedDeclaration", "scope"];
+
+/// Node fields whose array of integers are themselves references to other nodes' `id`s, rather
+/// than ids of their own -- e.g. `baseFunctions`, the override-declaration list a `FunctionDefinition`
+/// carries when it overrides a base contract's function.
+const REFERENCE_ARRAY_FIELDS: [&str; 1] = ["baseFunctions"];
+
 pub fn to_js_value<T>(env: &Env, value: &T) -> napi::Result<JsUnknown>
 where
   T: Serialize,
@@ -38,20 +51,67 @@ fn walk_max_id(node: &Value, max_id: &mut i64) {
   }
 }
 
-fn walk_renumber(node: &mut Value, next_id: &mut i64) {
+/// Assigns every `nodeType`-bearing node a fresh id, recording `old_id -> new_id` in `mapping` for
+/// every node that already had one. Nodes with no existing `id` (e.g. freshly synthesized members)
+/// simply get a new one with no mapping entry, since nothing in the tree could already reference
+/// them by their (nonexistent) old id.
+fn walk_renumber(node: &mut Value, next_id: &mut i64, mapping: &mut HashMap<i64, i64>) {
   match node {
     Value::Object(map) => {
       if map.get("nodeType").is_some() {
+        let old_id = map.get("id").and_then(Value::as_i64);
         *next_id += 1;
         map.insert("id".to_string(), Value::Number((*next_id).into()));
+        if let Some(old_id) = old_id {
+          mapping.insert(old_id, *next_id);
+        }
+      }
+      for child in map.values_mut() {
+        walk_renumber(child, next_id, mapping);
+      }
+    }
+    Value::Array(items) => {
+      for child in items {
+        walk_renumber(child, next_id, mapping);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Rewrites every reference to an old id recorded in `mapping` onto its new id: scalar
+/// [`REFERENCE_SCALAR_FIELDS`] (`referencedDeclaration`, `scope`, ...) and integer-array
+/// [`REFERENCE_ARRAY_FIELDS`] (`baseFunctions`, ...). References to an id absent from `mapping` --
+/// declarations outside the renumbered subtree, or the sentinel `-1` -- are left untouched so
+/// cross-contract references keep resolving.
+pub fn rewrite_references(node: &mut Value, mapping: &HashMap<i64, i64>) {
+  match node {
+    Value::Object(map) => {
+      for field in REFERENCE_SCALAR_FIELDS {
+        if let Some(old_id) = map.get(field).and_then(Value::as_i64) {
+          if let Some(&new_id) = mapping.get(&old_id) {
+            map.insert(field.to_string(), Value::Number(new_id.into()));
+          }
+        }
+      }
+      for field in REFERENCE_ARRAY_FIELDS {
+        if let Some(Value::Array(items)) = map.get_mut(field) {
+          for item in items.iter_mut() {
+            if let Some(old_id) = item.as_i64() {
+              if let Some(&new_id) = mapping.get(&old_id) {
+                *item = Value::Number(new_id.into());
+              }
+            }
+          }
+        }
       }
       for child in map.values_mut() {
-        walk_renumber(child, next_id);
+        rewrite_references(child, mapping);
       }
     }
     Value::Array(items) => {
       for child in items {
-        walk_renumber(child, next_id);
+        rewrite_references(child, mapping);
       }
     }
     _ => {}
@@ -64,10 +124,132 @@ pub fn max_id(value: &Value) -> i64 {
   max_id
 }
 
-pub fn clone_with_new_ids(value: &Value, next_id: &mut i64) -> Value {
+/// Parses a solc `"start:length:fileIndex"` span into its three integer components.
+fn parse_src(src: &str) -> Option<(i64, i64, i64)> {
+  let mut parts = src.splitn(3, ':');
+  let start = parts.next()?.parse().ok()?;
+  let length = parts.next()?.parse().ok()?;
+  let file_index = parts.next()?.parse().ok()?;
+  Some((start, length, file_index))
+}
+
+fn walk_source_extent(node: &Value, extent: &mut i64) {
+  match node {
+    Value::Object(map) => {
+      if let Some((start, length, _)) = map.get("src").and_then(Value::as_str).and_then(parse_src) {
+        *extent = (*extent).max(start + length);
+      }
+      for child in map.values() {
+        walk_source_extent(child, extent);
+      }
+    }
+    Value::Array(items) => {
+      for child in items {
+        walk_source_extent(child, extent);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// The highest `start + length` reached by any `src` span in `value`, i.e. how far into its source
+/// file the AST's spans extend. Used as the base offset a stitched-in fragment's spans are shifted
+/// by, so they land past the end of the target source rather than overlapping it.
+pub fn source_extent(value: &Value) -> i64 {
+  let mut extent = 0;
+  walk_source_extent(value, &mut extent);
+  extent
+}
+
+/// The `fileIndex` component of `value`'s own top-level `src` (e.g. a `SourceUnit`'s), or `0` if
+/// absent or unparseable.
+pub fn src_file_index(value: &Value) -> i64 {
+  value
+    .get("src")
+    .and_then(Value::as_str)
+    .and_then(parse_src)
+    .map(|(_, _, file_index)| file_index)
+    .unwrap_or(0)
+}
+
+/// Rewrites every `src` span in `node` onto `target_file_index`, offsetting `start` by
+/// `base_offset` so a fragment parsed in its own throwaway source file (see
+/// [`super::parser::parse_fragment_contract`]) gets spans that land in a reserved region of the
+/// target unit's coordinate space instead of colliding with it. `length` is left untouched --
+/// only the position and file identity of each span move.
+pub fn walk_remap_src(node: &mut Value, target_file_index: i64, base_offset: i64) {
+  match node {
+    Value::Object(map) => {
+      if let Some((start, length, _)) = map.get("src").and_then(Value::as_str).and_then(parse_src) {
+        map.insert(
+          "src".to_string(),
+          Value::String(format!("{}:{}:{}", start + base_offset, length, target_file_index)),
+        );
+      }
+      for child in map.values_mut() {
+        walk_remap_src(child, target_file_index, base_offset);
+      }
+    }
+    Value::Array(items) => {
+      for child in items {
+        walk_remap_src(child, target_file_index, base_offset);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Resolves `node`'s `src` field (`"start:length:fileIndex"`) against `source` into a
+/// [`SourceSpan`] carrying a 1-based line/column, by scanning `source` once for newline byte
+/// offsets and binary-searching `start` into them (the column is then `start - lineStart`).
+/// Returns `None` if `node` has no `src` field, or if `start` or `start + length` falls outside
+/// `source`'s bounds (e.g. `node` was parsed from a different file than `source`, or `source` is
+/// a stale/partial snapshot of the text the node's offsets were computed against).
+pub(crate) fn locate_span(node: &Value, source: &str) -> Option<SourceSpan> {
+  let (start, length, file) = node.get("src").and_then(Value::as_str).and_then(parse_src)?;
+  if start < 0 || (start as usize) > source.len() || !source.is_char_boundary(start as usize) {
+    return None;
+  }
+  let start = start as usize;
+  let end = start.checked_add(length.max(0) as usize)?;
+  if end > source.len() || !source.is_char_boundary(end) {
+    return None;
+  }
+
+  let newline_offsets: Vec<usize> = source
+    .bytes()
+    .enumerate()
+    .filter(|&(_, byte)| byte == b'\n')
+    .map(|(offset, _)| offset)
+    .collect();
+  let line = newline_offsets.partition_point(|&offset| offset < start);
+  let line_start = if line == 0 { 0 } else { newline_offsets[line - 1] + 1 };
+  let column = source[line_start..start].chars().count();
+
+  Some(SourceSpan {
+    start: start as u32,
+    length: length.max(0) as u32,
+    file: file.max(0) as u32,
+    line: (line + 1) as u32,
+    column: (column + 1) as u32,
+  })
+}
+
+/// Like [`clone_with_new_ids`], but also returns the `old_id -> new_id` map produced along the
+/// way, rewriting every in-tree reference to a renumbered id (see [`rewrite_references`]) so the
+/// clone stays internally consistent. Callers that stitch the clone into a larger tree (e.g.
+/// [`super::stitcher::stitch_fragment_nodes_into_contract`]) can reuse the returned map to fix up
+/// references in the surrounding tree too.
+pub fn clone_with_new_ids_mapped(value: &Value, next_id: &mut i64) -> (Value, HashMap<i64, i64>) {
   let mut clone = value.clone();
-  walk_renumber(&mut clone, next_id);
-  clone
+  let mut mapping = HashMap::new();
+  walk_renumber(&mut clone, next_id, &mut mapping);
+  rewrite_references(&mut clone, &mapping);
+  (clone, mapping)
+}
+
+pub fn clone_with_new_ids(value: &Value, next_id: &mut i64) -> Value {
+  clone_with_new_ids_mapped(value, next_id).0
 }
 
 #[cfg(test)]
@@ -133,4 +315,119 @@ mod tests {
     assert!(statements[0]["id"].as_i64().is_some());
     assert_eq!(next_id, 2);
   }
+
+  #[test]
+  fn clone_with_new_ids_mapped_rewrites_internal_references() {
+    let original = json!({
+      "nodeType": "FunctionDefinition",
+      "id": 10,
+      "scope": 1,
+      "body": {
+        "nodeType": "Block",
+        "id": 11,
+        "statements": [
+          {
+            "nodeType": "Identifier",
+            "id": 12,
+            "referencedDeclaration": 10
+          }
+        ]
+      }
+    });
+
+    let mut next_id = 100;
+    let (cloned, mapping) = clone_with_new_ids_mapped(&original, &mut next_id);
+
+    let new_function_id = cloned["id"].as_i64().unwrap();
+    assert_eq!(mapping.get(&10), Some(&new_function_id));
+    assert_eq!(
+      cloned["body"]["statements"][0]["referencedDeclaration"],
+      json!(new_function_id)
+    );
+    assert_eq!(cloned["scope"], json!(1), "scope outside the map is untouched");
+  }
+
+  #[test]
+  fn source_extent_finds_farthest_span_end() {
+    let value = json!({
+      "nodeType": "SourceUnit",
+      "src": "0:50:0",
+      "nodes": [
+        { "nodeType": "ContractDefinition", "src": "10:30:0" },
+        { "nodeType": "PragmaDirective", "src": "41:9:0" }
+      ]
+    });
+
+    assert_eq!(source_extent(&value), 50);
+  }
+
+  #[test]
+  fn src_file_index_reads_top_level_span() {
+    let value = json!({ "nodeType": "SourceUnit", "src": "0:50:3" });
+    assert_eq!(src_file_index(&value), 3);
+  }
+
+  #[test]
+  fn src_file_index_defaults_to_zero_when_absent() {
+    assert_eq!(src_file_index(&json!({ "nodeType": "SourceUnit" })), 0);
+  }
+
+  #[test]
+  fn walk_remap_src_offsets_start_and_rewrites_file_index() {
+    let mut value = json!({
+      "nodeType": "ContractDefinition",
+      "src": "0:10:0",
+      "nodes": [
+        { "nodeType": "FunctionDefinition", "src": "2:5:0" }
+      ]
+    });
+
+    walk_remap_src(&mut value, 2, 100);
+
+    assert_eq!(value["src"], json!("100:10:2"));
+    assert_eq!(value["nodes"][0]["src"], json!("102:5:2"));
+  }
+
+  #[test]
+  fn locate_span_resolves_line_and_column_from_src() {
+    let source = "pragma solidity ^0.8.0;\ncontract C {\n  function f() public {}\n}\n";
+    let node = json!({ "nodeType": "FunctionDefinition", "src": "39:22:0" });
+
+    let span = locate_span(&node, source).expect("span");
+
+    assert_eq!(span.start, 39);
+    assert_eq!(span.length, 22);
+    assert_eq!(span.file, 0);
+    assert_eq!(span.line, 3);
+    assert_eq!(span.column, 3);
+  }
+
+  #[test]
+  fn locate_span_returns_none_when_src_is_missing_or_out_of_bounds() {
+    let source = "contract C {}\n";
+    assert!(locate_span(&json!({ "nodeType": "ContractDefinition" }), source).is_none());
+    assert!(locate_span(&json!({ "src": "1000:1:0" }), source).is_none());
+  }
+
+  #[test]
+  fn locate_span_returns_none_when_only_the_end_is_out_of_bounds() {
+    let source = "contract C {}\n";
+    assert!(locate_span(&json!({ "src": "9:1000:0" }), source).is_none());
+  }
+
+  #[test]
+  fn rewrite_references_leaves_unmapped_and_sentinel_ids_untouched() {
+    let mut node = json!({
+      "nodeType": "Identifier",
+      "id": 1,
+      "referencedDeclaration": -1,
+      "baseFunctions": [5, 6]
+    });
+
+    let mapping = HashMap::from([(6, 60)]);
+    rewrite_references(&mut node, &mapping);
+
+    assert_eq!(node["referencedDeclaration"], json!(-1));
+    assert_eq!(node["baseFunctions"], json!([5, 60]));
+  }
 }
@@ -61,6 +61,40 @@ contract Sample {
     assert!(!output.artifacts.is_empty());
   }
 
+  #[test]
+  fn compile_files_version_grouped_reads_from_disk() {
+    use crate::compiler::compile_files_version_grouped;
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+    let output = compile_files_version_grouped(compiler.config(), vec![path], None)
+      .expect("compile file version grouped");
+    assert!(!output.artifacts.is_empty());
+  }
+
+  #[test]
+  fn compile_files_version_grouped_splits_and_merges_mismatched_pragmas() {
+    use crate::compiler::compile_files_version_grouped;
+
+    let temp_dir = tempdir().expect("tempdir");
+    let old_path = temp_dir.path().join("Old.sol");
+    let new_path = temp_dir.path().join("New.sol");
+    std::fs::write(&old_path, "pragma solidity ^0.8.13;\ncontract Old {}\n").expect("write old");
+    std::fs::write(&new_path, "pragma solidity ^0.8.20;\ncontract New {}\n").expect("write new");
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let output = compile_files_version_grouped(compiler.config(), vec![old_path, new_path], None)
+      .expect("compile files version grouped");
+    let has_contract = |name: &str| {
+      output
+        .artifacts
+        .values()
+        .any(|source| source.contracts.contains_key(name))
+    };
+    assert!(has_contract("Old"));
+    assert!(has_contract("New"));
+  }
+
   #[test]
   fn get_paths_returns_synthetic_layout() {
     let compiler = Compiler::new(None).expect("compiler");
@@ -96,6 +130,201 @@ contract Sample {
     let output = compiler.compile_project(None).expect("compile project");
     assert!(!output.artifacts.is_empty());
   }
+
+  #[test]
+  fn compile_files_rejects_path_that_fails_its_restriction() {
+    use crate::internal::config::{CompilerConfigOptions, CompilerRestriction};
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.restrictions = Some(vec![CompilerRestriction {
+      path_glob: "**/InlineExample.sol".to_string(),
+      version_req: Some(semver::VersionReq::parse(">=100.0.0").unwrap()),
+      min_optimizer_runs: None,
+      max_optimizer_runs: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      via_ir: None,
+    }]);
+
+    let err = compiler
+      .compile_files(vec![path], Some(options))
+      .expect_err("restriction should reject the resolved solc version");
+    assert!(err.to_string().contains("restricted to solc"));
+  }
+
+  #[test]
+  fn compile_files_allows_path_that_satisfies_its_restriction() {
+    use crate::internal::config::{CompilerConfigOptions, CompilerRestriction};
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.restrictions = Some(vec![CompilerRestriction {
+      path_glob: "**/InlineExample.sol".to_string(),
+      version_req: Some(semver::VersionReq::parse(">=0.8.0").unwrap()),
+      min_optimizer_runs: None,
+      max_optimizer_runs: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      via_ir: None,
+    }]);
+
+    let output = compiler
+      .compile_files(vec![path], Some(options))
+      .expect("restriction satisfied by default solc version");
+    assert!(!output.artifacts.is_empty());
+  }
+
+  #[test]
+  fn compile_files_clamps_optimizer_runs_to_restriction_minimum() {
+    use crate::internal::config::{CompilerConfigOptions, CompilerRestriction};
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.restrictions = Some(vec![CompilerRestriction {
+      path_glob: "**/InlineExample.sol".to_string(),
+      version_req: None,
+      min_optimizer_runs: Some(500),
+      max_optimizer_runs: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      via_ir: None,
+    }]);
+
+    let output = compiler
+      .compile_files(vec![path], Some(options))
+      .expect("restriction minimum should be clamped up to instead of rejected");
+    assert!(!output.artifacts.is_empty());
+  }
+
+  #[test]
+  fn compile_files_auto_detects_version_from_pragma() {
+    use crate::internal::config::CompilerConfigOptions;
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.auto_detect_version = Some(true);
+
+    let output = compiler
+      .compile_files(vec![path], Some(options))
+      .expect("pragma-satisfying solc version should be auto-detected");
+    assert!(!output.artifacts.is_empty());
+  }
+
+  #[test]
+  fn compile_files_auto_detect_errors_on_unsatisfiable_pragma() {
+    use crate::internal::config::CompilerConfigOptions;
+
+    let temp_dir = tempdir().expect("tempdir");
+    let path = temp_dir.path().join("Unsatisfiable.sol");
+    std::fs::write(&path, "pragma solidity >=100.0.0;\ncontract Unsatisfiable {}\n")
+      .expect("write fixture");
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let mut options = CompilerConfigOptions::default();
+    options.auto_detect_version = Some(true);
+
+    let err = compiler
+      .compile_files(vec![path], Some(options))
+      .expect_err("no solc release should satisfy an unreleased version requirement");
+    assert!(err.to_string().contains("pragma"));
+  }
+
+  #[test]
+  fn compile_source_auto_detects_version_from_pragma() {
+    use crate::internal::config::CompilerConfigOptions;
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let mut options = CompilerConfigOptions::default();
+    options.auto_detect_version = Some(true);
+
+    let output = compiler
+      .compile_source(SourceTarget::Text(SAMPLE_SOURCE.into()), Some(options))
+      .expect("inline pragma-satisfying solc version should be auto-detected");
+    assert!(!output.artifacts.is_empty());
+  }
+
+  #[test]
+  fn compile_files_writes_hardhat_artifacts_when_requested() {
+    use crate::internal::config::{ArtifactFormat, CompilerConfigOptions};
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.artifact_format = Some(ArtifactFormat::Hardhat);
+
+    compiler
+      .compile_files(vec![path], Some(options))
+      .expect("compile with hardhat artifact format");
+
+    let artifacts_dir = PathBuf::from(compiler.get_paths().expect("paths").artifacts);
+    let written = std::fs::read_dir(&artifacts_dir)
+      .expect("artifacts dir")
+      .filter_map(|entry| entry.ok())
+      .flat_map(|source_dir| std::fs::read_dir(source_dir.path()).into_iter().flatten())
+      .filter_map(|entry| entry.ok())
+      .find(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+      .expect("hardhat artifact file");
+
+    let contents = std::fs::read_to_string(written.path()).expect("read artifact");
+    let artifact: serde_json::Value = serde_json::from_str(&contents).expect("parse artifact");
+    assert_eq!(artifact["_format"], "hh-sol-artifact-1");
+    assert!(artifact["bytecode"].as_str().unwrap().starts_with("0x"));
+  }
+
+  #[test]
+  fn recompiling_unchanged_inline_source_reports_it_as_reused() {
+    let temp_dir = tempdir().expect("tempdir");
+    let compiler = Compiler::from_root(temp_dir.path(), None).expect("compiler");
+
+    let first = compiler
+      .compile_source(SourceTarget::Text(SAMPLE_SOURCE.into()), None)
+      .expect("first compile");
+    assert_eq!(first.dirty_paths.len(), 1);
+    assert!(first.reused_paths.is_empty());
+
+    let second = compiler
+      .compile_source(SourceTarget::Text(SAMPLE_SOURCE.into()), None)
+      .expect("second compile");
+    assert!(second.dirty_paths.is_empty());
+    assert_eq!(second.reused_paths.len(), 1);
+  }
+
+  #[test]
+  fn compile_files_decodes_source_maps_when_requested() {
+    use crate::internal::config::CompilerConfigOptions;
+
+    let compiler = Compiler::new(None).expect("compiler");
+    let path = fixture("contracts/InlineExample.sol");
+
+    let mut options = CompilerConfigOptions::default();
+    options.source_maps = Some(true);
+
+    let output = compiler
+      .compile_files(vec![path], Some(options))
+      .expect("compile with source maps enabled");
+
+    let contract = output
+      .artifact
+      .as_ref()
+      .and_then(|entry| entry.contracts.values().next())
+      .expect("compiled contract");
+    assert!(contract.state().creation_source_map.is_some());
+    assert!(contract
+      .state()
+      .creation_source_map_decoded
+      .as_ref()
+      .is_some_and(|entries| !entries.is_empty()));
+  }
 }
 
 #[test]
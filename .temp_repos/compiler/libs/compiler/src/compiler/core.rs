@@ -1,6 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use foundry_compilers::artifacts::{
   CompilerOutput, SolcInput, SolcLanguage as FoundrySolcLanguage, Source, Sources,
@@ -8,18 +9,34 @@ use foundry_compilers::artifacts::{
 use foundry_compilers::compilers::vyper::VyperInput;
 use foundry_compilers::compilers::CompilerOutput as FoundryCompilerOutput;
 use log::{error, info, warn};
-use serde_json::{json, Value};
+use rayon::prelude::*;
+use semver::Version;
+use serde_json::{json, Map, Value};
 
+use super::diagnostics::{
+  apply_compiler_severity_filter, apply_deny_warnings, apply_diagnostic_path_filters,
+  apply_ignored_error_codes, apply_severity_overrides,
+};
 use super::input::CompilationInput;
-use super::output::{build_compile_output, from_standard_json, vyper_error_to_core, CompileOutput};
+use super::output::{
+  build_compile_output, from_standard_json_with_selection, merge_compile_outputs,
+  merge_compile_outputs_by_version, version_resolution_error_output, vyper_error_to_core,
+  CompileOutput,
+};
 use super::project_runner::ProjectRunner;
 use crate::internal::config::{
   CompilerConfig, CompilerConfigOptions, CompilerLanguage, SolcConfig,
 };
+use crate::internal::config_discovery;
 use crate::internal::errors::{map_err_with_context, Error, Result};
+use crate::internal::incremental_cache::{self, CachedArtifact};
 use crate::internal::project::{
-  create_synthetic_context, FoundryAdapter, HardhatAdapter, ProjectContext,
+  create_synthetic_context, DapptoolsAdapter, FoundryAdapter, HardhatAdapter, ProjectContext,
+  ProjectLayout,
 };
+use crate::internal::graph::{self, VersionGraphReport};
+use crate::internal::report::{ProgressEvent, Reporter};
+use crate::internal::restrictions;
 use crate::internal::{solc, vyper};
 
 const LOG_TARGET: &str = "tevm::compiler.core";
@@ -108,16 +125,63 @@ pub fn init_from_hardhat_root(config: CompilerConfig, root: &Path) -> Result<Sta
   init_with_context(config, || HardhatAdapter::load(root))
 }
 
+pub fn init_from_dapptools_root(config: CompilerConfig, root: &Path) -> Result<State> {
+  init_with_context(config, || DapptoolsAdapter::load(root))
+}
+
 pub fn init_from_root(config: CompilerConfig, root: &Path) -> Result<State> {
   let context = create_synthetic_context(root)?;
   init(config, Some(context))
 }
 
+/// Probes `root` for the markers [`ProjectContext::detect`] recognises (`foundry.toml`/
+/// `foundry.json`, `hardhat.config.js`/`.ts`/`.cjs`) and binds to whichever adapter matches,
+/// falling back to a synthetic workspace when neither is present -- so a caller that doesn't
+/// already know `root`'s layout can use one entry point instead of trying each of
+/// `init_from_foundry_root`/`init_from_hardhat_root`/`init_from_root` in turn.
+pub fn init_from_detected_root(config: CompilerConfig, root: &Path) -> Result<State> {
+  init_with_context(config, || {
+    let (overrides, context) = ProjectContext::detect(root)?;
+    if matches!(
+      context.layout,
+      ProjectLayout::Foundry {
+        ambiguous_with_hardhat: true
+      }
+    ) {
+      warn!(
+        target: LOG_TARGET,
+        "{} has both a foundry.toml/foundry.json and a hardhat.config.*; defaulting to the \
+         Foundry adapter",
+        context.root.display()
+      );
+    }
+    Ok((overrides, context))
+  })
+}
+
+/// Resolves the effective configuration for one call: `state.config` (the instance defaults) with
+/// any config file discovered by walking up from the attached project's root layered on top, and
+/// `overrides` (the per-call options the caller passed explicitly) layered on top of that --
+/// so `overrides` always wins, a discovered config file fills in anything `overrides` left unset,
+/// and the instance defaults are the final fallback. Remappings are the one field that doesn't
+/// follow plain replace-on-override semantics between the instance defaults and the discovered
+/// file: they're concatenated, since a config file's remappings are additive to whatever the
+/// instance was already configured with rather than a full replacement of them.
 pub fn resolve_config(
   state: &State,
   overrides: Option<&CompilerConfigOptions>,
 ) -> Result<CompilerConfig> {
-  state.config.merge_options(overrides).map_err(Error::from)
+  let mut base = state.config.clone();
+  if let Some(project) = &state.project {
+    let mut discovered = config_discovery::discover_layered_options(&project.root);
+    if let Some(discovered_remappings) = discovered.remappings.take() {
+      let mut combined = base.remappings.clone();
+      combined.extend(discovered_remappings);
+      discovered.remappings = Some(combined);
+    }
+    base = base.merged(&discovered).map_err(Error::from)?;
+  }
+  base.merge_options(overrides).map_err(Error::from)
 }
 
 pub fn compile_source(
@@ -149,8 +213,176 @@ pub fn compile_files(
   config: &CompilerConfig,
   paths: Vec<PathBuf>,
   language_override: Option<CompilerLanguage>,
+  reporter: Option<&Reporter>,
 ) -> Result<CompileOutput> {
-  compile_file_paths(config, paths, language_override)
+  if let Some(reporter) = reporter {
+    reporter.report(ProgressEvent::SolcVersionSelected {
+      version: config.solc_version.to_string(),
+    });
+    reporter.report(ProgressEvent::GroupStarted {
+      file_count: paths.len(),
+    });
+  }
+  let output = compile_file_paths(config, paths, language_override, None)?;
+  let output = apply_diagnostic_path_filters(config, output)?;
+  let output = apply_ignored_error_codes(config, output);
+  let output = apply_compiler_severity_filter(config, output);
+  let output = apply_severity_overrides(config, output);
+  let output = apply_deny_warnings(config, output);
+  if let Some(reporter) = reporter {
+    for path in &output.reused_paths {
+      reporter.report(ProgressEvent::CacheHit { path: path.clone() });
+    }
+    for path in &output.dirty_paths {
+      reporter.report(ProgressEvent::CacheMiss { path: path.clone() });
+    }
+    reporter.report(ProgressEvent::GroupFinished);
+  }
+  Ok(output)
+}
+
+/// Like [`compile_files`], but classifies files into per-pragma solc-version buckets the same way
+/// [`project_runner`](super::project_runner)'s synthetic-project pipeline does for inline sources,
+/// then compiles each bucket's standard-JSON input on its own rayon thread and merges the results
+/// with [`merge_compile_outputs_by_version`]. Used by the `*Async` compile entry points so a
+/// multi-version project pays for genuinely parallel solc invocations instead of N sequential
+/// ones; falls back to a single [`compile_files`] call when every file resolves to the same
+/// bucket, so the common single-version case pays no extra overhead.
+pub fn compile_files_version_grouped(
+  config: &CompilerConfig,
+  paths: Vec<PathBuf>,
+  language_override: Option<CompilerLanguage>,
+) -> Result<CompileOutput> {
+  if paths.is_empty() {
+    error!(
+      target: LOG_TARGET,
+      "compile_files_version_grouped called without any paths"
+    );
+    return Err(Error::new("compileFilesAsync requires at least one path."));
+  }
+
+  let mut sources: BTreeMap<String, String> = BTreeMap::new();
+  let mut canonical_by_original: Vec<(PathBuf, String)> = Vec::with_capacity(paths.len());
+  for original in paths {
+    let content = fs::read_to_string(&original).map_err(|err| {
+      Error::with_context(format!("Failed to read source file {}", original.display()), err)
+    })?;
+    let canonical_path = original.canonicalize().unwrap_or_else(|_| original.clone());
+    let key = canonical_path.to_string_lossy().into_owned();
+    sources.insert(key.clone(), content);
+    canonical_by_original.push((canonical_path, key));
+  }
+
+  let buckets = graph::resolve_per_source_version_buckets(&sources, &config.solc_version, config.offline_mode)?;
+  let mut paths_by_version: BTreeMap<Version, Vec<PathBuf>> = BTreeMap::new();
+  for (canonical_path, key) in canonical_by_original {
+    let version = buckets[&key].clone();
+    paths_by_version.entry(version).or_default().push(canonical_path);
+  }
+
+  if paths_by_version.len() <= 1 {
+    let mut bucket_config = config.clone();
+    if let Some(version) = paths_by_version.keys().next() {
+      bucket_config.solc_version = version.clone();
+    }
+    let paths = paths_by_version.into_values().next().unwrap_or_default();
+    return compile_files(&bucket_config, paths, language_override, None);
+  }
+
+  info!(
+    target: LOG_TARGET,
+    "pragma version split produced {} bucket(s) for compile_files_version_grouped; compiling in parallel",
+    paths_by_version.len()
+  );
+
+  let per_version_outputs = paths_by_version
+    .into_par_iter()
+    .map(|(version, paths)| {
+      let mut bucket_config = config.clone();
+      bucket_config.solc_version = version.clone();
+      compile_files(&bucket_config, paths, language_override, None).map(|output| (version, output))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  Ok(merge_compile_outputs_by_version(per_version_outputs))
+}
+
+/// Builds the import dependency graph of `paths` and checks, per connected subtree, whether a
+/// single solc version can satisfy the union of every file's `pragma solidity` requirement. Unlike
+/// `compile_files`, this never invokes solc itself -- it only reads sources and reasons about their
+/// pragmas -- so it has no language-detection or AST-entry handling to share with
+/// `compile_file_paths`.
+pub fn resolve_version_graph(
+  config: &CompilerConfig,
+  paths: Vec<PathBuf>,
+) -> Result<VersionGraphReport> {
+  if paths.is_empty() {
+    warn!(
+      target: LOG_TARGET,
+      "resolve_version_graph invoked with empty input"
+    );
+    return Err(Error::new("resolveVersionGraph requires at least one path."));
+  }
+
+  let mut sources: BTreeMap<String, String> = BTreeMap::new();
+  for original in paths {
+    let content = match fs::read_to_string(&original) {
+      Ok(content) => content,
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to read source file {}: {}",
+          original.display(),
+          err
+        );
+        return Err(Error::with_context(
+          format!("Failed to read source file {}", original.display()),
+          err,
+        ));
+      }
+    };
+    let canonical_path = original.canonicalize().unwrap_or_else(|_| original.clone());
+    sources.insert(canonical_path.to_string_lossy().into_owned(), content);
+  }
+
+  graph::resolve_version_graph(&sources, &config.remappings, config.offline_mode)
+}
+
+/// Builds the import dependency graph of `paths`, resolving each file's `import`s against
+/// `config.remappings` and its own directory the same way `resolve_version_graph` resolves
+/// version requirements, but returning the resolved edges themselves rather than only a
+/// satisfiability verdict. Never invokes solc.
+pub fn resolve_graph(
+  config: &CompilerConfig,
+  paths: Vec<PathBuf>,
+) -> Result<graph::DependencyGraphReport> {
+  if paths.is_empty() {
+    warn!(target: LOG_TARGET, "resolve_graph invoked with empty input");
+    return Err(Error::new("resolveGraph requires at least one path."));
+  }
+
+  let mut sources: BTreeMap<String, String> = BTreeMap::new();
+  for original in paths {
+    let content = match fs::read_to_string(&original) {
+      Ok(content) => content,
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to read source file {}: {}",
+          original.display(),
+          err
+        );
+        return Err(Error::with_context(
+          format!("Failed to read source file {}", original.display()),
+          err,
+        ));
+      }
+    };
+    let canonical_path = original.canonicalize().unwrap_or_else(|_| original.clone());
+    sources.insert(canonical_path.to_string_lossy().into_owned(), content);
+  }
+
+  graph::resolve_dependency_graph(&sources, &config.remappings)
 }
 
 pub fn compile_as(
@@ -158,7 +390,7 @@ pub fn compile_as(
   config: &CompilerConfig,
   input: CompilationInput,
 ) -> Result<CompileOutput> {
-  if let Some(context) = &state.project {
+  let output = if let Some(context) = &state.project {
     info!(
       target: LOG_TARGET,
       "attempting to compile as project (layout={:?})",
@@ -168,13 +400,14 @@ pub fn compile_as(
     match runner.compile(config, &input)? {
       Some(result) => {
         info!(target: LOG_TARGET, "compilation succeeded");
-        return Ok(result);
+        result
       }
       None => {
         info!(
           target: LOG_TARGET,
           "unable to compile a project; falling back to standalone pipeline"
         );
+        compile_pure_or_version_diagnostic(state, config, input)?
       }
     }
   } else {
@@ -182,14 +415,109 @@ pub fn compile_as(
       target: LOG_TARGET,
       "no project context attached; using standalone compiler pipeline"
     );
+    compile_pure_or_version_diagnostic(state, config, input)?
+  };
+
+  let output = apply_diagnostic_path_filters(config, output)?;
+  let output = apply_ignored_error_codes(config, output);
+  let output = apply_compiler_severity_filter(config, output);
+  let output = apply_severity_overrides(config, output);
+  Ok(apply_deny_warnings(config, output))
+}
+
+/// Runs a batch of independent compilation jobs concurrently, each with its own `CompilerConfig`
+/// and `CompilationInput`, and returns their outputs in the same order the jobs were given. Jobs
+/// are grouped by solc version so workers pulling from the shared queue tend to compile the same
+/// version back-to-back, then handed out to a bounded pool of worker threads sized to the first
+/// job's `max_jobs` (clamped to the job count, since more workers than jobs can't help). This
+/// matters when a workspace mixes pragmas requiring several solc versions, or when compiling many
+/// independent inline snippets, since `compile_as` otherwise blocks on one invocation at a time.
+pub fn compile_many(
+  state: &State,
+  jobs: Vec<(CompilerConfig, CompilationInput)>,
+) -> Result<Vec<CompileOutput>> {
+  if jobs.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let max_jobs = jobs[0].0.max_jobs.max(1);
+  let worker_count = max_jobs.min(jobs.len());
+  info!(
+    target: LOG_TARGET,
+    "compiling {} job(s) across {} worker thread(s)",
+    jobs.len(),
+    worker_count
+  );
+
+  let mut grouped: BTreeMap<semver::Version, Vec<(usize, CompilerConfig, CompilationInput)>> =
+    BTreeMap::new();
+  for (index, (config, input)) in jobs.into_iter().enumerate() {
+    grouped
+      .entry(config.solc_version.clone())
+      .or_default()
+      .push((index, config, input));
   }
 
-  compile_pure(config, input)
+  let queue: Mutex<VecDeque<(usize, CompilerConfig, CompilationInput)>> =
+    Mutex::new(grouped.into_values().flatten().collect());
+  let results: Mutex<BTreeMap<usize, Result<CompileOutput>>> = Mutex::new(BTreeMap::new());
+
+  std::thread::scope(|scope| {
+    for _ in 0..worker_count {
+      scope.spawn(|| loop {
+        let next = queue.lock().expect("compile_many queue mutex poisoned").pop_front();
+        let Some((index, config, input)) = next else {
+          break;
+        };
+        let output = compile_as(state, &config, input);
+        results
+          .lock()
+          .expect("compile_many results mutex poisoned")
+          .insert(index, output);
+      });
+    }
+  });
+
+  results
+    .into_inner()
+    .expect("compile_many results mutex poisoned")
+    .into_values()
+    .collect()
 }
 
-pub fn compile_project(state: &State, config: &CompilerConfig) -> Result<CompileOutput> {
+pub fn compile_project(
+  state: &State,
+  config: &CompilerConfig,
+  reporter: Option<&Reporter>,
+) -> Result<CompileOutput> {
   let runner = project_runner(state)?;
-  runner.compile_project(config)
+  if let Some(reporter) = reporter {
+    reporter.report(ProgressEvent::SolcVersionSelected {
+      version: config.solc_version.to_string(),
+    });
+    let file_count = state
+      .project
+      .as_ref()
+      .map(|context| context.paths.input_files().len())
+      .unwrap_or(0);
+    reporter.report(ProgressEvent::GroupStarted { file_count });
+  }
+  let output = runner.compile_project(config)?;
+  let output = apply_diagnostic_path_filters(config, output)?;
+  let output = apply_ignored_error_codes(config, output);
+  let output = apply_compiler_severity_filter(config, output);
+  let output = apply_severity_overrides(config, output);
+  let output = apply_deny_warnings(config, output);
+  if let Some(reporter) = reporter {
+    for path in &output.reused_paths {
+      reporter.report(ProgressEvent::CacheHit { path: path.clone() });
+    }
+    for path in &output.dirty_paths {
+      reporter.report(ProgressEvent::CacheMiss { path: path.clone() });
+    }
+    reporter.report(ProgressEvent::GroupFinished);
+  }
+  Ok(output)
 }
 
 pub fn compile_contract(
@@ -198,10 +526,20 @@ pub fn compile_contract(
   contract_name: &str,
 ) -> Result<CompileOutput> {
   let runner = project_runner(state)?;
-  runner.compile_contract(config, contract_name)
+  let output = runner.compile_contract(config, contract_name)?;
+  let output = apply_diagnostic_path_filters(config, output)?;
+  let output = apply_ignored_error_codes(config, output);
+  let output = apply_compiler_severity_filter(config, output);
+  let output = apply_severity_overrides(config, output);
+  Ok(apply_deny_warnings(config, output))
 }
 
-fn compile_pure(config: &CompilerConfig, input: CompilationInput) -> Result<CompileOutput> {
+fn compile_pure(
+  state: &State,
+  config: &CompilerConfig,
+  input: CompilationInput,
+) -> Result<CompileOutput> {
+  let cache_root = cache_paths(state);
   match input {
     CompilationInput::InlineSource { source } => {
       info!(
@@ -210,7 +548,7 @@ fn compile_pure(config: &CompilerConfig, input: CompilationInput) -> Result<Comp
         source.len(),
         config.language
       );
-      compile_inline_source(config, source, config.language)
+      compile_inline_source(config, source, config.language, cache_root.as_ref())
     }
     CompilationInput::SourceMap {
       sources,
@@ -223,8 +561,26 @@ fn compile_pure(config: &CompilerConfig, input: CompilationInput) -> Result<Comp
         language_override
       );
       let resolved_language = language_override.unwrap_or(config.language);
-      let solc_sources = sources_from_map(sources);
-      compile_standard_sources(config, solc_sources, resolved_language)
+      let mut updated = config.clone();
+      updated.language = resolved_language;
+      let buckets = apply_auto_detected_version(&mut updated, &sources)?;
+      let solc_sources = sources_from_map(sources.clone());
+      compile_auto_detected_sources(
+        &updated,
+        &sources,
+        solc_sources,
+        resolved_language,
+        buckets,
+        cache_root.as_ref(),
+      )
+    }
+    CompilationInput::MixedSourceMap { by_language } => {
+      info!(
+        target: LOG_TARGET,
+        "compiling mixed-language source map (groups={})",
+        by_language.len()
+      );
+      compile_mixed_language_sources(config, by_language, cache_root.as_ref())
     }
     CompilationInput::AstUnits { units } => {
       info!(
@@ -244,51 +600,304 @@ fn compile_pure(config: &CompilerConfig, input: CompilationInput) -> Result<Comp
         paths.len(),
         language_override
       );
-      compile_file_paths(config, paths, language_override)
+      compile_file_paths(config, paths, language_override, cache_root.as_ref())
+    }
+  }
+}
+
+/// Runs [`compile_pure`], converting a [`Error::MissingSolcVersion`] failure -- a required solc
+/// release isn't already installed and `config.offline_mode` forbids downloading it -- into a
+/// [`CompileOutput`] carrying a single `version-resolution` diagnostic instead of propagating it
+/// as a thrown exception. Every other failure still propagates as-is.
+fn compile_pure_or_version_diagnostic(
+  state: &State,
+  config: &CompilerConfig,
+  input: CompilationInput,
+) -> Result<CompileOutput> {
+  match compile_pure(state, config, input) {
+    Ok(output) => Ok(output),
+    Err(Error::MissingSolcVersion { requirement, .. }) => {
+      warn!(
+        target: LOG_TARGET,
+        "solc requirement `{requirement}` isn't installed and offline_mode forbids downloading \
+         it; surfacing a version-resolution diagnostic instead of aborting the compile"
+      );
+      Ok(version_resolution_error_output(&requirement))
     }
+    Err(err) => Err(err),
   }
 }
 
+/// Derives the incremental-cache manifest and artifact-directory paths from the project context
+/// attached to `state`, mirroring `project_runner.rs`'s own cache-path derivation. Returns `None`
+/// when `state` has no project context (e.g. `cache_enabled` was `false` at `init` time), in which
+/// case the standalone pipeline compiles fresh every time -- there is no project directory to
+/// anchor a cache in.
+fn cache_paths(state: &State) -> Option<(PathBuf, PathBuf)> {
+  let context = state.project.as_ref()?;
+  let manifest_path = context.paths.cache.with_file_name("tevm-incremental-cache.json");
+  let artifacts_dir = context.paths.cache.with_file_name("tevm-incremental-artifacts");
+  Some((manifest_path, artifacts_dir))
+}
+
 fn compile_inline_source(
   config: &CompilerConfig,
   source: String,
   language: CompilerLanguage,
+  cache_root: Option<&(PathBuf, PathBuf)>,
 ) -> Result<CompileOutput> {
-  let mut sources = Sources::new();
   let virtual_name = match language {
     CompilerLanguage::Solidity => "__VIRTUAL__.sol",
     CompilerLanguage::Yul => "__VIRTUAL__.yul",
     CompilerLanguage::Vyper => "__VIRTUAL__.vy",
   };
+
+  let mut updated = config.clone();
+  updated.language = language;
+  let mut string_entries = BTreeMap::new();
+  string_entries.insert(virtual_name.to_string(), source.clone());
+  let buckets = apply_auto_detected_version(&mut updated, &string_entries)?;
+
+  let mut sources = Sources::new();
   sources.insert(PathBuf::from(virtual_name), Source::new(source));
-  compile_standard_sources(config, sources, language)
+  compile_auto_detected_sources(
+    &updated,
+    &string_entries,
+    sources,
+    language,
+    buckets,
+    cache_root,
+  )
+}
+
+/// Compiles a source map whose per-file language spans more than one solc-compatible language
+/// (Solidity and Yul) by running [`compile_auto_detected_sources`] once per language group --
+/// each group keeps its own auto-detected version buckets -- and merging the resulting
+/// `CompileOutput`s with [`merge_compile_outputs`]. Vyper never reaches here: it's rejected
+/// upstream in [`compilation_input_from_values`] and filesystem language grouping before a
+/// `MixedSourceMap` is ever constructed.
+fn compile_mixed_language_sources(
+  config: &CompilerConfig,
+  by_language: BTreeMap<CompilerLanguage, BTreeMap<String, String>>,
+  cache_root: Option<&(PathBuf, PathBuf)>,
+) -> Result<CompileOutput> {
+  let mut outputs = Vec::with_capacity(by_language.len());
+  for (language, texts) in by_language {
+    let mut updated = config.clone();
+    updated.language = language;
+    let buckets = apply_auto_detected_version(&mut updated, &texts)?;
+    let sources = sources_from_map(texts.clone());
+    outputs.push(compile_auto_detected_sources(
+      &updated, &texts, sources, language, buckets, cache_root,
+    )?);
+  }
+  Ok(merge_compile_outputs(outputs))
+}
+
+/// When `config.auto_detect_version` is set, builds the import dependency graph of `sources`
+/// (see [`graph::resolve_compilation_buckets`]) and overrides `config.solc_version` with the
+/// release used by the most files, so restriction checks and single-bucket callers see a
+/// sensible default without having to reason about the full per-file map themselves. Returns
+/// that full path -> version map so [`compile_auto_detected_sources`] can split a multi-version
+/// workspace into one `SolcInput` per bucket instead of compiling everything against the
+/// primary pick. Shared by the inline, source-map, and filesystem compile paths so auto-detection
+/// behaves identically regardless of how the caller supplied its sources.
+fn apply_auto_detected_version(
+  config: &mut CompilerConfig,
+  sources: &BTreeMap<String, String>,
+) -> Result<Option<BTreeMap<String, Version>>> {
+  if !config.auto_detect_version || !matches!(config.language, CompilerLanguage::Solidity) {
+    return Ok(None);
+  }
+
+  let buckets = graph::resolve_compilation_buckets(sources, &config.remappings, config.offline_mode)?;
+
+  let mut files_per_version: BTreeMap<Version, usize> = BTreeMap::new();
+  for version in buckets.values() {
+    *files_per_version.entry(version.clone()).or_default() += 1;
+  }
+
+  if let Some((version, count)) = files_per_version.iter().max_by_key(|(_, count)| **count) {
+    info!(
+      target: LOG_TARGET,
+      "auto-detected solc {version} from pragma solidity declarations and imports ({count} of {} \
+       file(s))",
+      sources.len()
+    );
+    config.solc_version = version.clone();
+  }
+
+  if files_per_version.len() > 1 {
+    info!(
+      target: LOG_TARGET,
+      "auto-detected imports split {} file(s) into {} solc version bucket(s); compiling each \
+       bucket separately",
+      sources.len(),
+      files_per_version.len()
+    );
+  }
+
+  Ok(Some(buckets))
+}
+
+/// Compiles `sources` against `config`, consulting `buckets` (as produced by
+/// [`apply_auto_detected_version`]) to decide whether a genuine multi-version compile is needed.
+/// `None` (auto-detection disabled, or the language isn't Solidity) and a single-version map both
+/// fall back to one `compile_standard_sources` pass; a map spanning more than one version splits
+/// `sources` by resolved version and compiles each split concurrently, bounded by
+/// `config.max_jobs` the same way [`compile_many`] bounds its worker pool, since a workspace
+/// mixing several pragma requirements otherwise pays for each solc invocation one at a time. The
+/// per-version outputs are merged with [`merge_compile_outputs_by_version`], which tags every
+/// diagnostic with the solc version that produced it and keeps both artifacts (keyed by
+/// `"{path}@{version}"`) when a shared source is legitimately compiled under more than one
+/// version.
+fn compile_auto_detected_sources(
+  config: &CompilerConfig,
+  texts: &BTreeMap<String, String>,
+  sources: Sources,
+  language: CompilerLanguage,
+  buckets: Option<BTreeMap<String, Version>>,
+  cache_root: Option<&(PathBuf, PathBuf)>,
+) -> Result<CompileOutput> {
+  let Some(buckets) = buckets else {
+    return compile_standard_sources(config, texts, sources, language, cache_root);
+  };
+
+  let mut paths_by_version: BTreeMap<Version, Vec<String>> = BTreeMap::new();
+  for (path, version) in buckets {
+    paths_by_version.entry(version).or_default().push(path);
+  }
+
+  if paths_by_version.len() <= 1 {
+    return compile_standard_sources(config, texts, sources, language, cache_root);
+  }
+
+  let mut remaining: BTreeMap<String, Source> = sources
+    .into_iter()
+    .map(|(path, source)| (path.to_string_lossy().into_owned(), source))
+    .collect();
+
+  let mut jobs = Vec::with_capacity(paths_by_version.len());
+  for (version, paths) in paths_by_version {
+    let mut bucket_sources = Sources::new();
+    for path in paths {
+      if let Some(source) = remaining.remove(&path) {
+        bucket_sources.insert(PathBuf::from(path), source);
+      }
+    }
+    let mut bucket_config = config.clone();
+    bucket_config.solc_version = version.clone();
+    jobs.push((version, bucket_config, bucket_sources));
+  }
+
+  let worker_count = config.max_jobs.max(1).min(jobs.len());
+  info!(
+    target: LOG_TARGET,
+    "auto-detected version split produced {} bucket(s); compiling across {} worker thread(s)",
+    jobs.len(),
+    worker_count
+  );
+
+  let queue: Mutex<VecDeque<(usize, Version, CompilerConfig, Sources)>> = Mutex::new(
+    jobs
+      .into_iter()
+      .enumerate()
+      .map(|(index, (version, bucket_config, bucket_sources))| {
+        (index, version, bucket_config, bucket_sources)
+      })
+      .collect(),
+  );
+  let results: Mutex<BTreeMap<usize, Result<(Version, CompileOutput)>>> =
+    Mutex::new(BTreeMap::new());
+
+  std::thread::scope(|scope| {
+    for _ in 0..worker_count {
+      scope.spawn(|| loop {
+        let next = queue
+          .lock()
+          .expect("compile_auto_detected_sources queue mutex poisoned")
+          .pop_front();
+        let Some((index, version, bucket_config, bucket_sources)) = next else {
+          break;
+        };
+        let output = compile_standard_sources(
+          &bucket_config,
+          texts,
+          bucket_sources,
+          language,
+          cache_root,
+        )
+        .map(|output| (version, output));
+        results
+          .lock()
+          .expect("compile_auto_detected_sources results mutex poisoned")
+          .insert(index, output);
+      });
+    }
+  });
+
+  let outputs = results
+    .into_inner()
+    .expect("compile_auto_detected_sources results mutex poisoned")
+    .into_values()
+    .collect::<Result<Vec<(Version, CompileOutput)>>>()?;
+
+  Ok(merge_compile_outputs_by_version(outputs))
 }
 
+/// Compiles `sources` and stamps [`CompileOutput::version_resolution`] with `config.solc_version`
+/// for every Solidity/Yul path compiled, so callers can audit pragma-driven version selection
+/// regardless of which sub-pipeline (cached, fresh, or per-bucket) actually ran. Vyper sources
+/// aren't stamped: `version_resolution` tracks solc version selection, which pragma directives
+/// don't apply to.
 fn compile_standard_sources(
   config: &CompilerConfig,
+  texts: &BTreeMap<String, String>,
   sources: Sources,
   language: CompilerLanguage,
+  cache_root: Option<&(PathBuf, PathBuf)>,
+) -> Result<CompileOutput> {
+  let solc_paths: Vec<String> = if matches!(language, CompilerLanguage::Solidity | CompilerLanguage::Yul)
+  {
+    sources
+      .keys()
+      .map(|path| path.to_string_lossy().into_owned())
+      .collect()
+  } else {
+    Vec::new()
+  };
+
+  let mut output = compile_standard_sources_inner(config, texts, sources, language, cache_root)?;
+  for path in solc_paths {
+    output
+      .version_resolution
+      .entry(path)
+      .or_insert_with(|| config.solc_version.clone());
+  }
+  Ok(output)
+}
+
+fn compile_standard_sources_inner(
+  config: &CompilerConfig,
+  texts: &BTreeMap<String, String>,
+  sources: Sources,
+  language: CompilerLanguage,
+  cache_root: Option<&(PathBuf, PathBuf)>,
 ) -> Result<CompileOutput> {
   match language {
     CompilerLanguage::Solidity | CompilerLanguage::Yul => {
-      info!(
-        target: LOG_TARGET,
-        "running solc compilation (language={:?}, sources={})",
-        language,
-        sources.len()
-      );
-      let solc_language = to_solc_language(language)?;
-      let solc_config = SolcConfig {
-        version: config.solc_version.clone(),
-        settings: config.solc_settings.clone(),
-        language: solc_language,
-      };
-      let solc = solc::ensure_installed(&solc_config.version)?;
-      let mut input = SolcInput::new(solc_language, sources, solc_config.settings.clone());
-      input.sanitize(&solc.version);
-      let output: CompilerOutput =
-        map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
-      Ok(from_standard_json(output))
+      if language == CompilerLanguage::Solidity && config.cache_enabled {
+        if let Some((manifest_path, artifacts_dir)) = cache_root {
+          return compile_solc_sources_incremental(
+            config,
+            texts,
+            sources,
+            manifest_path,
+            artifacts_dir,
+          );
+        }
+      }
+      compile_solc_sources_fresh(config, sources, language)
     }
     CompilerLanguage::Vyper => {
       info!(
@@ -296,7 +905,7 @@ fn compile_standard_sources(
         "running vyper compilation (sources={})",
         sources.len()
       );
-      let vyper_compiler = vyper::ensure_installed(config.vyper_settings.path.clone())?;
+      let vyper_compiler = vyper::ensure_installed(config.vyper_settings.path.clone(), None)?;
       let search_paths = combined_vyper_search_paths(config);
       let mut settings = config
         .vyper_settings
@@ -324,11 +933,191 @@ fn compile_standard_sources(
         &compiler_output.sources,
         raw_artifacts,
         errors,
+        config.artifact_field_selection,
       ))
     }
   }
 }
 
+fn compile_solc_sources_fresh(
+  config: &CompilerConfig,
+  sources: Sources,
+  language: CompilerLanguage,
+) -> Result<CompileOutput> {
+  info!(
+    target: LOG_TARGET,
+    "running solc compilation (language={:?}, sources={})",
+    language,
+    sources.len()
+  );
+  let solc_language = to_solc_language(language)?;
+  let solc_config = SolcConfig {
+    version: config.solc_version.clone(),
+    settings: config.solc_settings.clone(),
+    language: solc_language,
+    path: None,
+  };
+  let solc = solc::ensure_installed(&solc_config.version)?;
+  let mut input = SolcInput::new(solc_language, sources, solc_config.settings.clone());
+  input.sanitize(&solc.version);
+  let output: CompilerOutput =
+    map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
+  Ok(from_standard_json_with_selection(
+    output,
+    config.artifact_field_selection,
+  ))
+}
+
+/// Content-hash-based incremental wrapper around [`compile_solc_sources_fresh`]: every source is
+/// hashed and checked against the manifest at `manifest_path`, a change to any file is expanded
+/// across its import-connected component (see [`graph::import_connected_components`]) since an
+/// importer can depend on symbols defined in what changed, and solc is skipped entirely only when
+/// *nothing* in the batch is dirty after that expansion -- the cached artifact fragments are
+/// replayed back through the same `from_standard_json_with_selection` path a fresh compile would
+/// use. Any dirty file forces a full recompile of the whole batch (solc needs the complete source
+/// set to resolve imports correctly; splitting out only the dirty files is not attempted here),
+/// and the fresh result is used to refresh the cache for next time. `config.force_rebuild` skips
+/// the manifest lookup outright (as if nothing were cached), so every source recompiles; the fresh
+/// result is still written back, so a later call with `force_rebuild` back to `false` benefits.
+fn compile_solc_sources_incremental(
+  config: &CompilerConfig,
+  texts: &BTreeMap<String, String>,
+  sources: Sources,
+  manifest_path: &Path,
+  artifacts_dir: &Path,
+) -> Result<CompileOutput> {
+  let paths: Vec<String> = sources
+    .keys()
+    .map(|path| path.to_string_lossy().into_owned())
+    .collect();
+  let relevant_texts: BTreeMap<String, String> = paths
+    .iter()
+    .filter_map(|path| texts.get(path).map(|content| (path.clone(), content.clone())))
+    .collect();
+
+  let fingerprint = incremental_cache::config_fingerprint(config);
+  let report = if config.force_rebuild {
+    incremental_cache::ArtifactCacheReport::default()
+  } else {
+    incremental_cache::evaluate_with_artifacts(manifest_path, &fingerprint, &relevant_texts)?
+  };
+
+  let dirty: BTreeSet<String> =
+    incremental_cache::expand_dirty_across_imports(&report.dirty, &relevant_texts, &config.remappings);
+
+  if dirty.is_empty() && !report.fresh.is_empty() {
+    info!(
+      target: LOG_TARGET,
+      "incremental cache hit: reusing {} cached artifact(s), skipping solc",
+      report.fresh.len()
+    );
+    let mut output = reconstruct_cached_output(config, &report.fresh)?;
+    output.dirty_paths = Vec::new();
+    output.reused_paths = paths;
+    return Ok(output);
+  }
+
+  info!(
+    target: LOG_TARGET,
+    "incremental cache miss ({} of {} file(s) dirty after import-graph expansion); recompiling \
+     the whole batch",
+    dirty.len(),
+    paths.len()
+  );
+  let mut output = compile_solc_sources_fresh(config, sources, CompilerLanguage::Solidity)?;
+
+  let all_errors = output
+    .raw_artifacts
+    .get("errors")
+    .and_then(|errors| errors.as_array())
+    .cloned()
+    .unwrap_or_default();
+
+  let fresh_entries: Vec<(String, String, CachedArtifact)> = paths
+    .iter()
+    .filter_map(|path| {
+      let content = relevant_texts.get(path)?;
+      let source = output
+        .raw_artifacts
+        .get("sources")
+        .and_then(|sources| sources.get(path))
+        .cloned()
+        .unwrap_or(Value::Null);
+      let contracts = output
+        .raw_artifacts
+        .get("contracts")
+        .and_then(|contracts| contracts.get(path))
+        .cloned()
+        .unwrap_or(Value::Null);
+      let errors = errors_for_path(&all_errors, path);
+      Some((
+        path.clone(),
+        content.clone(),
+        CachedArtifact {
+          source,
+          contracts,
+          errors,
+        },
+      ))
+    })
+    .collect();
+  incremental_cache::store_artifacts(manifest_path, artifacts_dir, &fingerprint, &fresh_entries)?;
+
+  output.dirty_paths = paths;
+  output.reused_paths = Vec::new();
+  Ok(output)
+}
+
+/// Filters a full compile's `errors` array down to the diagnostics whose `sourceLocation.file`
+/// matches `path`, so each cached artifact only replays the warnings that belong to it.
+pub(crate) fn errors_for_path(errors: &[Value], path: &str) -> Vec<Value> {
+  errors
+    .iter()
+    .filter(|error| {
+      error
+        .get("sourceLocation")
+        .and_then(|location| location.get("file"))
+        .and_then(|file| file.as_str())
+        == Some(path)
+    })
+    .cloned()
+    .collect()
+}
+
+/// Replays cached `sources`/`contracts` JSON fragments (as stored by
+/// [`compile_solc_sources_incremental`], or by [`super::project_runner::ProjectRunner`]'s own
+/// artifact cache) through a synthetic `CompilerOutput`, so a full cache hit can reuse
+/// `from_standard_json_with_selection` instead of duplicating its artifact-assembly logic. Each
+/// entry's cached `errors` are merged back in, so warnings recorded on a previous compile are
+/// still surfaced even when solc itself is skipped this time.
+pub(crate) fn reconstruct_cached_output(
+  config: &CompilerConfig,
+  fresh: &BTreeMap<String, CachedArtifact>,
+) -> Result<CompileOutput> {
+  let mut sources_value = Map::new();
+  let mut contracts_value = Map::new();
+  let mut errors_value = Vec::new();
+  for (path, artifact) in fresh {
+    sources_value.insert(path.clone(), artifact.source.clone());
+    contracts_value.insert(path.clone(), artifact.contracts.clone());
+    errors_value.extend(artifact.errors.iter().cloned());
+  }
+
+  let synthetic = json!({
+    "sources": Value::Object(sources_value),
+    "contracts": Value::Object(contracts_value),
+    "errors": Value::Array(errors_value),
+  });
+  let output: CompilerOutput = map_err_with_context(
+    serde_json::from_value(synthetic),
+    "Failed to reconstruct cached compiler output",
+  )?;
+  Ok(from_standard_json_with_selection(
+    output,
+    config.artifact_field_selection,
+  ))
+}
+
 fn compile_ast_sources(
   config: &CompilerConfig,
   ast_sources: BTreeMap<String, Value>,
@@ -343,6 +1132,7 @@ fn compile_ast_sources(
     version: config.solc_version.clone(),
     settings: config.solc_settings.clone(),
     language: FoundrySolcLanguage::Solidity,
+    path: None,
   };
   let solc = solc::ensure_installed(&solc_config.version)?;
   let settings_value = map_err_with_context(
@@ -354,6 +1144,7 @@ fn compile_ast_sources(
   for (file_name, ast_value) in ast_sources {
     sources_value.insert(file_name, json!({ "ast": ast_value }));
   }
+  let paths: Vec<String> = sources_value.keys().cloned().collect();
 
   let input = json!({
     "language": "SolidityAST",
@@ -363,13 +1154,21 @@ fn compile_ast_sources(
 
   let output: CompilerOutput =
     map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
-  Ok(from_standard_json(output))
+  let mut output = from_standard_json_with_selection(output, config.artifact_field_selection);
+  for path in paths {
+    output
+      .version_resolution
+      .entry(path)
+      .or_insert_with(|| solc_config.version.clone());
+  }
+  Ok(output)
 }
 
 fn compile_file_paths(
   config: &CompilerConfig,
   paths: Vec<PathBuf>,
   language_override: Option<CompilerLanguage>,
+  cache_root: Option<&(PathBuf, PathBuf)>,
 ) -> Result<CompileOutput> {
   if paths.is_empty() {
     warn!(
@@ -389,7 +1188,7 @@ fn compile_file_paths(
 
   let mut string_entries: BTreeMap<String, String> = BTreeMap::new();
   let mut ast_entries: BTreeMap<String, Value> = BTreeMap::new();
-  let mut detected_language: Option<CompilerLanguage> = None;
+  let mut path_languages: BTreeMap<String, CompilerLanguage> = BTreeMap::new();
 
   for original in paths {
     let content = match fs::read_to_string(&original) {
@@ -415,22 +1214,7 @@ fn compile_file_paths(
     }
 
     let inferred = infer_compiler_language(&canonical_path, &content, language_override)?;
-    if language_override.is_none() {
-      if let Some(existing) = detected_language {
-        if existing != inferred {
-          warn!(
-            target: LOG_TARGET,
-            "detected mixed source languages ({existing:?} vs {inferred:?})"
-          );
-          return Err(Error::new(
-            "compileFiles requires all non-AST sources to share the same language. Provide language explicitly to disambiguate.",
-          ));
-        }
-      } else {
-        detected_language = Some(inferred);
-      }
-    }
-
+    path_languages.insert(canonical_string.clone(), inferred);
     string_entries.insert(canonical_string, content);
   }
 
@@ -455,9 +1239,62 @@ fn compile_file_paths(
     return compile_ast_sources(&updated, ast_entries);
   }
 
+  if language_override.is_none() {
+    let mut by_language: BTreeMap<CompilerLanguage, BTreeMap<String, String>> = BTreeMap::new();
+    for (path, content) in &string_entries {
+      by_language
+        .entry(path_languages[path])
+        .or_default()
+        .insert(path.clone(), content.clone());
+    }
+
+    if by_language.len() > 1 {
+      if by_language.contains_key(&CompilerLanguage::Vyper) {
+        warn!(
+          target: LOG_TARGET,
+          "detected Vyper mixed with a solc language across {} file(s)",
+          path_count
+        );
+        return Err(Error::new(
+          "compileFiles requires all non-AST sources to share the same language. Provide language explicitly to disambiguate.",
+        ));
+      }
+      info!(
+        target: LOG_TARGET,
+        "compiling {} solc-compatible language group(s) ({:?}) in one call",
+        by_language.len(),
+        by_language.keys().collect::<Vec<_>>()
+      );
+      let mut outputs = Vec::with_capacity(by_language.len());
+      for (language, entries) in by_language {
+        outputs.push(compile_standard_sources_for_files(
+          config,
+          entries,
+          language,
+          path_count,
+          cache_root,
+        )?);
+      }
+      return Ok(merge_compile_outputs(outputs));
+    }
+  }
+
   let final_language = language_override
-    .or(detected_language)
+    .or_else(|| path_languages.values().next().copied())
     .unwrap_or(config.language);
+  compile_standard_sources_for_files(config, string_entries, final_language, path_count, cache_root)
+}
+
+/// Shared tail of `compile_file_paths`: applies auto-detection and restriction checks for a single
+/// language group of filesystem sources, then compiles it. Factored out so a heterogeneous
+/// Solidity+Yul batch can run this once per language group and merge the results.
+fn compile_standard_sources_for_files(
+  config: &CompilerConfig,
+  string_entries: BTreeMap<String, String>,
+  final_language: CompilerLanguage,
+  path_count: usize,
+  cache_root: Option<&(PathBuf, PathBuf)>,
+) -> Result<CompileOutput> {
   info!(
     target: LOG_TARGET,
     "using final language {:?} for filesystem compilation",
@@ -465,8 +1302,41 @@ fn compile_file_paths(
   );
   let mut updated = config.clone();
   updated.language = final_language;
-  let sources = sources_from_map(string_entries);
-  compile_standard_sources(&updated, sources, final_language)
+
+  let buckets = apply_auto_detected_version(&mut updated, &string_entries)?;
+
+  if !updated.restrictions.is_empty() {
+    let canonical_paths: Vec<String> = string_entries.keys().cloned().collect();
+    let groups = restrictions::group_paths(&canonical_paths, &updated.restrictions)?;
+    if let [group] = groups.as_slice() {
+      // Every file shares the same applicable restrictions, so their exact-valued bounds
+      // (viaIr, optimizer runs) can be applied directly instead of only being validated.
+      updated.solc_settings = restrictions::clamp_settings(&updated.solc_settings, group)?;
+    }
+    for group in &groups {
+      restrictions::ensure_group_satisfied(&updated.solc_version, &updated.solc_settings, group)?;
+    }
+    if groups.len() > 1 {
+      warn!(
+        target: LOG_TARGET,
+        "compiler restrictions split {} file(s) into {} incompatible group(s); compiling as a \
+         single pass against the shared configuration since per-group solc re-selection is not \
+         yet implemented",
+        path_count,
+        groups.len()
+      );
+    }
+  }
+
+  let sources = sources_from_map(string_entries.clone());
+  compile_auto_detected_sources(
+    &updated,
+    &string_entries,
+    sources,
+    final_language,
+    buckets,
+    cache_root,
+  )
 }
 
 fn try_parse_ast_from_file(
@@ -559,25 +1429,31 @@ fn compilation_input_from_values(
     return Ok(CompilationInput::AstUnits { units: ast_entries });
   }
 
-  let mut inferred_language: Option<CompilerLanguage> = None;
-  for path in string_entries.keys() {
-    let path_buf = Path::new(path);
-    let candidate = infer_compiler_language(path_buf, "", None)?;
-    if let Some(existing) = inferred_language {
-      if existing != candidate {
-        return Err(Error::new(
-          "compileSources requires all entries to share the same language. Provide language explicitly to disambiguate.",
-        ));
-      }
-    } else {
-      inferred_language = Some(candidate);
+  let mut by_language: BTreeMap<CompilerLanguage, BTreeMap<String, String>> = BTreeMap::new();
+  for (path, source) in string_entries {
+    let candidate = infer_compiler_language(Path::new(&path), "", None)?;
+    by_language.entry(candidate).or_default().insert(path, source);
+  }
+
+  if by_language.len() > 1 {
+    if by_language.contains_key(&CompilerLanguage::Vyper) {
+      return Err(Error::new(
+        "compileSources requires all entries to share the same language. Provide language explicitly to disambiguate.",
+      ));
     }
+    return Ok(CompilationInput::MixedSourceMap { by_language });
   }
 
-  Ok(CompilationInput::SourceMap {
-    sources: string_entries,
-    language_override: inferred_language,
-  })
+  match by_language.into_iter().next() {
+    Some((language, sources)) => Ok(CompilationInput::SourceMap {
+      sources,
+      language_override: Some(language),
+    }),
+    None => Ok(CompilationInput::SourceMap {
+      sources: BTreeMap::new(),
+      language_override: None,
+    }),
+  }
 }
 
 fn sources_from_map(entries: BTreeMap<String, String>) -> Sources {
@@ -592,7 +1468,7 @@ fn project_runner(state: &State) -> Result<ProjectRunner<'_>> {
   let context = state
     .project
     .as_ref()
-    .ok_or_else(|| Error::new("This compiler instance is not bound to a project root."))?;
+    .ok_or_else(|| Error::project_config("This compiler instance is not bound to a project root."))?;
   Ok(ProjectRunner::new(context))
 }
 
@@ -664,6 +1540,19 @@ mod tests {
     assert!(entries.is_empty());
   }
 
+  #[test]
+  fn errors_for_path_filters_by_source_location_file() {
+    let errors = vec![
+      json!({ "message": "in A", "sourceLocation": { "file": "A.sol", "start": 0, "end": 1 } }),
+      json!({ "message": "in B", "sourceLocation": { "file": "B.sol", "start": 0, "end": 1 } }),
+      json!({ "message": "no location" }),
+    ];
+
+    let matched = errors_for_path(&errors, "A.sol");
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0]["message"], "in A");
+  }
+
   #[test]
   fn compilation_input_from_values_rejects_mixed_languages() {
     let mut sources = BTreeMap::new();
@@ -682,6 +1571,27 @@ mod tests {
       .contains("compileSources requires all entries to share the same language"));
   }
 
+  #[test]
+  fn compilation_input_from_values_splits_solidity_and_yul_instead_of_rejecting() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      SourceValue::Text("contract A {}".into()),
+    );
+    sources.insert(
+      "B.yul".to_string(),
+      SourceValue::Text("object \"B\" { code {} }".into()),
+    );
+
+    let input = compilation_input_from_values(sources).expect("mixed solc languages are allowed");
+    let CompilationInput::MixedSourceMap { by_language } = input else {
+      panic!("expected a MixedSourceMap, got a single-language input");
+    };
+    assert_eq!(by_language.len(), 2);
+    assert!(by_language.contains_key(&CompilerLanguage::Solidity));
+    assert!(by_language.contains_key(&CompilerLanguage::Yul));
+  }
+
   #[test]
   fn compilation_input_from_values_rejects_mixed_ast_and_sources() {
     let mut sources = BTreeMap::new();
@@ -0,0 +1,748 @@
+use std::collections::HashMap;
+
+use foundry_compilers::artifacts::error::Severity;
+
+use crate::internal::config::{CompilerConfig, SeverityOverride, SeverityOverrideLevel};
+use crate::internal::errors::{Error, Result};
+
+use super::output::{CompileOutput, CompilerError, SecondarySourceLocation, SeverityLevel, SourceLocation};
+
+/// Common ranking for [`SeverityLevel`] (a diagnostic's reported severity) and [`Severity`] (the
+/// threshold configured via `compiler_severity_filter`), so the two can be compared directly in
+/// [`apply_compiler_severity_filter`].
+fn severity_rank(severity: SeverityLevel) -> u8 {
+  match severity {
+    SeverityLevel::Error => 2,
+    SeverityLevel::Warning => 1,
+    SeverityLevel::Info => 0,
+  }
+}
+
+fn severity_filter_rank(severity: Severity) -> u8 {
+  match severity {
+    Severity::Error => 2,
+    Severity::Warning => 1,
+    _ => 0,
+  }
+}
+
+/// Drops diagnostics whose `error_code` is listed in `config.ignored_error_codes`, mirroring
+/// foundry-compilers' `ignored_error_codes` project option. A no-op when the list is empty, which
+/// keeps the common path free of the `Vec` rebuild below. Diagnostics without a code (general
+/// compiler errors) are never suppressed by this filter.
+pub(crate) fn apply_ignored_error_codes(
+  config: &CompilerConfig,
+  mut output: CompileOutput,
+) -> CompileOutput {
+  if config.ignored_error_codes.is_empty() {
+    return output;
+  }
+
+  output.errors.retain(|error| {
+    !error
+      .error_code
+      .is_some_and(|code| config.ignored_error_codes.iter().any(|ignored| *ignored as i64 == code))
+  });
+
+  output
+}
+
+/// Drops diagnostics reported below `config.compiler_severity_filter`, the lowest severity
+/// surfaced to consumers. Defaults to `Severity::Error`, so warnings and info-level diagnostics
+/// are dropped unless a caller lowers the threshold. A diagnostic whose code is targeted by a
+/// `config.severity_overrides` entry is kept regardless, so [`apply_severity_overrides`] decides it.
+pub(crate) fn apply_compiler_severity_filter(
+  config: &CompilerConfig,
+  mut output: CompileOutput,
+) -> CompileOutput {
+  let threshold = severity_filter_rank(config.compiler_severity_filter);
+  output.errors.retain(|error| {
+    severity_rank(error.severity) >= threshold
+      || error.error_code.is_some_and(|code| {
+        config
+          .severity_overrides
+          .iter()
+          .any(|override_| override_.code as i64 == code)
+      })
+  });
+  output
+}
+
+/// Resolves the effective severity for a diagnostic `code` against `overrides`, falling back to
+/// `intrinsic` (the severity the compiler itself reported) when no override targets this code or
+/// `code` is absent. Returns `None` when the diagnostic is fully suppressed (`allow`). When more
+/// than one override targets the same code, the one with the highest `ordinal` -- the last one
+/// declared in the configured list -- wins, mirroring how a later `#[warn]`/`#[allow]` attribute
+/// overrides an earlier one in rustc.
+pub(crate) fn resolve_severity(
+  overrides: &[SeverityOverride],
+  code: Option<i64>,
+  intrinsic: SeverityLevel,
+) -> Option<SeverityLevel> {
+  let Some(code) = code else {
+    return Some(intrinsic);
+  };
+
+  match overrides
+    .iter()
+    .filter(|override_| override_.code as i64 == code)
+    .max_by_key(|override_| override_.ordinal)
+  {
+    None => Some(intrinsic),
+    Some(SeverityOverride {
+      level: SeverityOverrideLevel::Allow,
+      ..
+    }) => None,
+    Some(SeverityOverride {
+      level: SeverityOverrideLevel::Warn,
+      ..
+    }) => Some(SeverityLevel::Warning),
+    Some(SeverityOverride {
+      level: SeverityOverrideLevel::Error,
+      ..
+    }) => Some(SeverityLevel::Error),
+  }
+}
+
+/// Applies `config.severity_overrides` to every diagnostic in `output.errors`, dropping those
+/// resolved to `None` (suppressed) and rewriting the rest to their overridden severity. A no-op
+/// when `config.severity_overrides` is empty, which keeps the common path free of the `Vec`
+/// rebuild below.
+pub(crate) fn apply_severity_overrides(
+  config: &CompilerConfig,
+  mut output: CompileOutput,
+) -> CompileOutput {
+  if config.severity_overrides.is_empty() {
+    return output;
+  }
+
+  output.errors = output
+    .errors
+    .into_iter()
+    .filter_map(|error| {
+      let severity =
+        resolve_severity(&config.severity_overrides, error.error_code, error.severity)?;
+      Some(CompilerError { severity, ..error })
+    })
+    .collect();
+
+  output
+}
+
+/// Path a diagnostic is attributed to, checking the solc-style `source_location` first and
+/// falling back to Vyper's own `vyper_source_location`. `None` for diagnostics that aren't tied to
+/// a specific source file (e.g. general compiler errors), which are never filtered by path.
+fn diagnostic_path(error: &CompilerError) -> Option<&str> {
+  error
+    .source_location
+    .as_ref()
+    .map(|location| location.file.as_str())
+    .or_else(|| {
+      error
+        .vyper_source_location
+        .as_ref()
+        .map(|location| location.file.as_str())
+    })
+}
+
+fn compile_path_patterns(globs: &[String]) -> Result<Vec<glob::Pattern>> {
+  globs
+    .iter()
+    .map(|pattern| {
+      glob::Pattern::new(pattern)
+        .map_err(|err| Error::new(format!("Invalid diagnostic path glob `{pattern}`: {err}")))
+    })
+    .collect()
+}
+
+/// Applies `config.diagnostic_path_denylist`/`diagnostic_path_allowlist` to `output.errors`. A
+/// diagnostic is dropped when its path matches the denylist, or -- when the allowlist is
+/// non-empty -- when its path matches none of the allowlist patterns; both lists apply together,
+/// so a denylist entry still silences a path that also happens to satisfy the allowlist. A
+/// diagnostic with no resolvable source path is never filtered by either list. A no-op when both
+/// lists are empty, which keeps the common path free of the `Vec` rebuild below.
+pub(crate) fn apply_diagnostic_path_filters(
+  config: &CompilerConfig,
+  mut output: CompileOutput,
+) -> Result<CompileOutput> {
+  if config.diagnostic_path_denylist.is_empty() && config.diagnostic_path_allowlist.is_empty() {
+    return Ok(output);
+  }
+
+  let denylist = compile_path_patterns(&config.diagnostic_path_denylist)?;
+  let allowlist = compile_path_patterns(&config.diagnostic_path_allowlist)?;
+
+  output.errors.retain(|error| {
+    let Some(path) = diagnostic_path(error) else {
+      return true;
+    };
+    if !allowlist.is_empty() && !allowlist.iter().any(|pattern| pattern.matches(path)) {
+      return false;
+    }
+    !denylist.iter().any(|pattern| pattern.matches(path))
+  });
+
+  Ok(output)
+}
+
+/// Records `config.deny_warnings` onto `output` so [`CompileOutput::has_compiler_errors`] also
+/// counts warning-level diagnostics for exit-code purposes, without rewriting the severity each
+/// diagnostic is reported at. Run after [`apply_severity_overrides`] so it sees post-override
+/// severities.
+pub(crate) fn apply_deny_warnings(
+  config: &CompilerConfig,
+  mut output: CompileOutput,
+) -> CompileOutput {
+  output.deny_warnings = config.deny_warnings;
+  output
+}
+
+/// Number of source lines to show above and below a diagnostic's primary span when rendering a
+/// code frame via [`format_diagnostics`]. `None` fields fall back to their documented default.
+#[napi(object)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FormatDiagnosticsOptions {
+  /// Number of lines of surrounding source to include above and below the diagnostic's first
+  /// line. Defaults to `1`.
+  pub context_lines: Option<u32>,
+}
+
+/// Diagnostic counts grouped by [`SeverityLevel`], as returned by [`diagnostic_summary`].
+#[napi(object)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticSummary {
+  pub errors: u32,
+  pub warnings: u32,
+  pub infos: u32,
+}
+
+const DEFAULT_CONTEXT_LINES: u32 = 1;
+
+fn severity_label(severity: SeverityLevel) -> &'static str {
+  match severity {
+    SeverityLevel::Error => "error",
+    SeverityLevel::Warning => "warning",
+    SeverityLevel::Info => "info",
+  }
+}
+
+/// 1-based line and column reached by scanning `source`'s UTF-8 bytes up to `offset`. `offset` is
+/// clamped to `[0, source.len()]` and snapped back to the nearest char boundary, so offsets past
+/// EOF (or solc's occasional `-1` "unknown" sentinel) degrade to the start/end of the file instead
+/// of panicking.
+pub(crate) struct LineCol {
+  pub(crate) line: usize,
+  pub(crate) column: usize,
+}
+
+pub(crate) fn locate_byte_offset(source: &str, offset: i32) -> LineCol {
+  let mut offset = (offset.max(0) as usize).min(source.len());
+  while offset > 0 && !source.is_char_boundary(offset) {
+    offset -= 1;
+  }
+
+  let mut line = 1;
+  let mut line_start = 0;
+  for (index, ch) in source.char_indices() {
+    if index >= offset {
+      break;
+    }
+    if ch == '\n' {
+      line += 1;
+      line_start = index + 1;
+    }
+  }
+
+  let column = source[line_start..offset].chars().count() + 1;
+  LineCol { line, column }
+}
+
+/// Renders the source lines spanning `[start, end)` (byte offsets, clamped the same way as
+/// [`locate_byte_offset`]) with a line-numbered gutter and a `^~~~` underline beneath the first
+/// line. A span that crosses multiple lines underlines only to the end of its first line, since
+/// the remaining lines are shown for context but aren't part of the caret.
+fn render_code_frame(source: &str, start: i32, end: i32, context_lines: u32) -> String {
+  let lines: Vec<&str> = source.split('\n').collect();
+  let start_loc = locate_byte_offset(source, start);
+  let mut end_loc = locate_byte_offset(source, end.max(start));
+  if end_loc.line == start_loc.line && end_loc.column <= start_loc.column {
+    end_loc.column = start_loc.column + 1;
+  }
+
+  let first_line = start_loc.line;
+  let last_line = end_loc.line.min(lines.len().max(1));
+  let range_start = first_line.saturating_sub(context_lines as usize).max(1);
+  let range_end = (last_line + context_lines as usize).min(lines.len());
+  let gutter_width = range_end.to_string().len();
+
+  let mut out = String::new();
+  for line_no in range_start..=range_end {
+    let text = lines.get(line_no - 1).copied().unwrap_or("");
+    out.push_str(&format!("{line_no:>gutter_width$} | {text}\n"));
+    if line_no != first_line {
+      continue;
+    }
+    let underline_start = start_loc.column.saturating_sub(1);
+    let underline_end = if last_line == first_line {
+      end_loc.column.saturating_sub(1).max(underline_start + 1)
+    } else {
+      text.chars().count().max(underline_start + 1)
+    };
+    let width = underline_end.saturating_sub(underline_start).max(1);
+    let marker = if width == 1 {
+      "^".to_string()
+    } else {
+      format!("^{}", "~".repeat(width - 1))
+    };
+    out.push_str(&format!(
+      "{} | {}{marker}\n",
+      " ".repeat(gutter_width),
+      " ".repeat(underline_start)
+    ));
+  }
+  out.trim_end_matches('\n').to_string()
+}
+
+fn render_primary_frame(
+  location: &SourceLocation,
+  sources: &HashMap<String, String>,
+  context_lines: u32,
+) -> Option<String> {
+  let source = sources.get(&location.file)?;
+  let loc = locate_byte_offset(source, location.start);
+  let frame = render_code_frame(source, location.start, location.end, context_lines);
+  Some(format!(" --> {}:{}:{}\n{frame}", location.file, loc.line, loc.column))
+}
+
+fn render_secondary_frame(
+  secondary: &SecondarySourceLocation,
+  sources: &HashMap<String, String>,
+  context_lines: u32,
+) -> String {
+  let message = secondary.message.as_deref().unwrap_or("related location");
+  let mut out = format!("  note: {message}\n");
+
+  let Some(file) = &secondary.file else {
+    return out;
+  };
+  let Some(start) = secondary.start else {
+    out.push_str(&format!("   --> {file}\n"));
+    return out;
+  };
+  let end = secondary.end.unwrap_or(start + 1);
+
+  match sources.get(file) {
+    Some(source) => {
+      let loc = locate_byte_offset(source, start);
+      out.push_str(&format!("   --> {file}:{}:{}\n", loc.line, loc.column));
+      for line in render_code_frame(source, start, end, context_lines).lines() {
+        out.push_str("  ");
+        out.push_str(line);
+        out.push('\n');
+      }
+    }
+    None => out.push_str(&format!("   --> {file}\n")),
+  }
+
+  out
+}
+
+/// Renders one [`CompilerError`] into a Rust-style diagnostic: a header line carrying the
+/// severity, `error_type`, and `error_code`, followed by a source code frame (when the primary
+/// `source_location`'s file content is present in `sources`) or the compiler's own
+/// `formatted_message` as a fallback, followed by an indented frame per secondary location.
+pub(crate) fn render_diagnostic(
+  error: &CompilerError,
+  sources: &HashMap<String, String>,
+  context_lines: u32,
+) -> String {
+  let code_suffix = error
+    .error_code
+    .map(|code| format!("[{code}]"))
+    .unwrap_or_default();
+  let mut out = format!(
+    "{}{code_suffix} ({}): {}\n",
+    severity_label(error.severity),
+    error.error_type,
+    error.message
+  );
+
+  match error
+    .source_location
+    .as_ref()
+    .and_then(|location| render_primary_frame(location, sources, context_lines))
+  {
+    Some(frame) => out.push_str(&frame),
+    None => {
+      if let Some(formatted) = &error.formatted_message {
+        out.push_str(formatted.trim_end());
+      } else {
+        out.push_str(error.message.as_str());
+      }
+    }
+  }
+
+  for secondary in error.secondary_source_locations.iter().flatten() {
+    out.push('\n');
+    out.push_str(render_secondary_frame(secondary, sources, context_lines).trim_end());
+  }
+
+  out
+}
+
+/// Renders every diagnostic in `errors` via [`render_diagnostic`], preserving order so entry `i`
+/// of the result corresponds to `errors[i]`.
+pub(crate) fn format_diagnostics(
+  errors: &[CompilerError],
+  sources: &HashMap<String, String>,
+  options: Option<FormatDiagnosticsOptions>,
+) -> Vec<String> {
+  let context_lines = options
+    .and_then(|options| options.context_lines)
+    .unwrap_or(DEFAULT_CONTEXT_LINES);
+  errors
+    .iter()
+    .map(|error| render_diagnostic(error, sources, context_lines))
+    .collect()
+}
+
+/// Tallies `errors` by severity for a quick `{ errors, warnings, infos }` overview, e.g. for a
+/// status line in an editor integration that doesn't want to walk the full diagnostic list.
+pub(crate) fn diagnostic_summary(errors: &[CompilerError]) -> DiagnosticSummary {
+  let mut summary = DiagnosticSummary::default();
+  for error in errors {
+    match error.severity {
+      SeverityLevel::Error => summary.errors += 1,
+      SeverityLevel::Warning => summary.warnings += 1,
+      SeverityLevel::Info => summary.infos += 1,
+    }
+  }
+  summary
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn override_(code: u64, level: SeverityOverrideLevel, ordinal: usize) -> SeverityOverride {
+    SeverityOverride { code, level, ordinal }
+  }
+
+  #[test]
+  fn resolve_severity_falls_back_to_intrinsic_when_no_override_matches() {
+    let overrides = vec![override_(1234, SeverityOverrideLevel::Allow, 0)];
+    assert_eq!(
+      resolve_severity(&overrides, Some(5678), SeverityLevel::Warning),
+      Some(SeverityLevel::Warning)
+    );
+  }
+
+  #[test]
+  fn resolve_severity_suppresses_allowed_codes() {
+    let overrides = vec![override_(1234, SeverityOverrideLevel::Allow, 0)];
+    assert_eq!(
+      resolve_severity(&overrides, Some(1234), SeverityLevel::Error),
+      None
+    );
+  }
+
+  #[test]
+  fn resolve_severity_escalates_warning_to_error() {
+    let overrides = vec![override_(1234, SeverityOverrideLevel::Error, 0)];
+    assert_eq!(
+      resolve_severity(&overrides, Some(1234), SeverityLevel::Warning),
+      Some(SeverityLevel::Error)
+    );
+  }
+
+  #[test]
+  fn resolve_severity_last_override_wins_on_conflict() {
+    let overrides = vec![
+      override_(1234, SeverityOverrideLevel::Allow, 0),
+      override_(1234, SeverityOverrideLevel::Error, 1),
+    ];
+    assert_eq!(
+      resolve_severity(&overrides, Some(1234), SeverityLevel::Warning),
+      Some(SeverityLevel::Error)
+    );
+  }
+
+  #[test]
+  fn resolve_severity_ignores_diagnostics_without_a_code() {
+    let overrides = vec![override_(1234, SeverityOverrideLevel::Allow, 0)];
+    assert_eq!(
+      resolve_severity(&overrides, None, SeverityLevel::Info),
+      Some(SeverityLevel::Info)
+    );
+  }
+
+  fn error_for_path(path: &str) -> CompilerError {
+    CompilerError {
+      message: "detail".into(),
+      formatted_message: None,
+      component: "general".into(),
+      severity: SeverityLevel::Warning,
+      error_type: "Warning".into(),
+      error_code: None,
+      source_location: Some(SourceLocation {
+        file: path.to_string(),
+        start: 0,
+        end: 1,
+      }),
+      secondary_source_locations: None,
+      vyper_source_location: None,
+      solc_version: None,
+    }
+  }
+
+  fn output_with_paths(paths: &[&str]) -> CompileOutput {
+    let errors: Vec<CompilerError> = paths.iter().map(|path| error_for_path(path)).collect();
+    CompileOutput {
+      raw_artifacts: serde_json::Value::Null,
+      artifacts: Default::default(),
+      artifact: None,
+      all_errors: errors.clone(),
+      errors,
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: Default::default(),
+    }
+  }
+
+  fn config_with_filters(denylist: &[&str], allowlist: &[&str]) -> CompilerConfig {
+    let mut config = CompilerConfig::default();
+    config.diagnostic_path_denylist = denylist.iter().map(|s| s.to_string()).collect();
+    config.diagnostic_path_allowlist = allowlist.iter().map(|s| s.to_string()).collect();
+    config
+  }
+
+  #[test]
+  fn apply_diagnostic_path_filters_is_a_noop_without_any_lists() {
+    let config = CompilerConfig::default();
+    let output = output_with_paths(&["contracts/Token.sol"]);
+    let filtered = apply_diagnostic_path_filters(&config, output).expect("filter");
+    assert_eq!(filtered.errors.len(), 1);
+  }
+
+  #[test]
+  fn apply_diagnostic_path_filters_drops_denylisted_paths() {
+    let config = config_with_filters(&["node_modules/**"], &[]);
+    let output = output_with_paths(&["node_modules/dep/Dep.sol", "contracts/Token.sol"]);
+    let filtered = apply_diagnostic_path_filters(&config, output).expect("filter");
+    assert_eq!(filtered.errors.len(), 1);
+    assert_eq!(
+      diagnostic_path(&filtered.errors[0]),
+      Some("contracts/Token.sol")
+    );
+  }
+
+  #[test]
+  fn apply_diagnostic_path_filters_keeps_only_allowlisted_paths() {
+    let config = config_with_filters(&[], &["contracts/**"]);
+    let output = output_with_paths(&["node_modules/dep/Dep.sol", "contracts/Token.sol"]);
+    let filtered = apply_diagnostic_path_filters(&config, output).expect("filter");
+    assert_eq!(filtered.errors.len(), 1);
+    assert_eq!(
+      diagnostic_path(&filtered.errors[0]),
+      Some("contracts/Token.sol")
+    );
+  }
+
+  #[test]
+  fn apply_diagnostic_path_filters_denylist_wins_over_an_overlapping_allowlist() {
+    let config = config_with_filters(&["contracts/Vendored.sol"], &["contracts/**"]);
+    let output = output_with_paths(&["contracts/Vendored.sol", "contracts/Token.sol"]);
+    let filtered = apply_diagnostic_path_filters(&config, output).expect("filter");
+    assert_eq!(filtered.errors.len(), 1);
+    assert_eq!(
+      diagnostic_path(&filtered.errors[0]),
+      Some("contracts/Token.sol")
+    );
+  }
+
+  #[test]
+  fn apply_diagnostic_path_filters_rejects_invalid_globs() {
+    let config = config_with_filters(&["["], &[]);
+    let output = output_with_paths(&["contracts/Token.sol"]);
+    assert!(apply_diagnostic_path_filters(&config, output).is_err());
+  }
+
+  fn output_with_codes(codes: &[Option<i64>]) -> CompileOutput {
+    let errors: Vec<CompilerError> = codes
+      .iter()
+      .map(|code| CompilerError {
+        error_code: *code,
+        ..error_for_path("contracts/Token.sol")
+      })
+      .collect();
+    CompileOutput {
+      raw_artifacts: serde_json::Value::Null,
+      artifacts: Default::default(),
+      artifact: None,
+      all_errors: errors.clone(),
+      errors,
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: Default::default(),
+    }
+  }
+
+  #[test]
+  fn apply_ignored_error_codes_is_a_noop_without_any_codes() {
+    let config = CompilerConfig::default();
+    let output = output_with_codes(&[Some(2072)]);
+    let filtered = apply_ignored_error_codes(&config, output);
+    assert_eq!(filtered.errors.len(), 1);
+  }
+
+  #[test]
+  fn apply_ignored_error_codes_drops_matching_codes() {
+    let mut config = CompilerConfig::default();
+    config.ignored_error_codes = vec![2072];
+    let output = output_with_codes(&[Some(2072), Some(3420), None]);
+    let filtered = apply_ignored_error_codes(&config, output);
+    assert_eq!(
+      filtered.errors.iter().map(|error| error.error_code).collect::<Vec<_>>(),
+      vec![Some(3420), None]
+    );
+  }
+
+  #[test]
+  fn apply_ignored_error_codes_preserves_all_errors() {
+    let mut config = CompilerConfig::default();
+    config.ignored_error_codes = vec![2072];
+    let output = output_with_codes(&[Some(2072)]);
+    let filtered = apply_ignored_error_codes(&config, output);
+    assert!(filtered.errors.is_empty());
+    assert_eq!(filtered.all_errors.len(), 1);
+  }
+
+  #[test]
+  fn apply_compiler_severity_filter_drops_warnings_below_the_default_error_threshold() {
+    let config = CompilerConfig::default();
+    let output = output_with_paths(&["contracts/Token.sol"]);
+    let filtered = apply_compiler_severity_filter(&config, output);
+    assert!(filtered.errors.is_empty());
+  }
+
+  #[test]
+  fn apply_compiler_severity_filter_keeps_warnings_once_lowered() {
+    let mut config = CompilerConfig::default();
+    config.compiler_severity_filter = Severity::Warning;
+    let output = output_with_paths(&["contracts/Token.sol"]);
+    let filtered = apply_compiler_severity_filter(&config, output);
+    assert_eq!(filtered.errors.len(), 1);
+  }
+
+  fn error_with_location(message: &str, file: &str, start: i32, end: i32) -> CompilerError {
+    CompilerError {
+      message: message.into(),
+      formatted_message: None,
+      component: "general".into(),
+      severity: SeverityLevel::Error,
+      error_type: "TypeError".into(),
+      error_code: Some(1234),
+      source_location: Some(SourceLocation {
+        file: file.to_string(),
+        start,
+        end,
+      }),
+      secondary_source_locations: None,
+      vyper_source_location: None,
+      solc_version: None,
+    }
+  }
+
+  #[test]
+  fn render_diagnostic_underlines_the_offending_span() {
+    let source = "contract C {\n  uint256 x = ;\n}\n";
+    let error = error_with_location("Expected expression.", "C.sol", 27, 28);
+    let sources = HashMap::from([("C.sol".to_string(), source.to_string())]);
+
+    let rendered = render_diagnostic(&error, &sources, 0);
+    assert!(rendered.starts_with("error[1234] (TypeError): Expected expression."));
+    assert!(rendered.contains("--> C.sol:2:15"));
+    assert!(rendered.contains("uint256 x = ;"));
+    assert!(rendered.contains('^'));
+  }
+
+  #[test]
+  fn render_diagnostic_includes_requested_context_lines() {
+    let source = "line one\nline two\nline three\n";
+    let error = error_with_location("bad token", "C.sol", 9, 17);
+    let sources = HashMap::from([("C.sol".to_string(), source.to_string())]);
+
+    let rendered = render_diagnostic(&error, &sources, 1);
+    assert!(rendered.contains("line one"));
+    assert!(rendered.contains("line two"));
+    assert!(rendered.contains("line three"));
+  }
+
+  #[test]
+  fn render_diagnostic_falls_back_to_formatted_message_without_source() {
+    let mut error = error_with_location("bad token", "Missing.sol", 0, 1);
+    error.formatted_message = Some("ParserError: bad token\n--> Missing.sol".to_string());
+    let sources = HashMap::new();
+
+    let rendered = render_diagnostic(&error, &sources, 0);
+    assert!(rendered.contains("ParserError: bad token"));
+  }
+
+  #[test]
+  fn render_diagnostic_renders_secondary_locations_as_related_notes() {
+    let mut error = error_with_location("shadowed declaration", "C.sol", 0, 1);
+    error.source_location = None;
+    error.secondary_source_locations = Some(vec![SecondarySourceLocation {
+      file: Some("C.sol".to_string()),
+      start: Some(13),
+      end: Some(14),
+      message: Some("original declaration here".to_string()),
+    }]);
+    let sources = HashMap::from(["C.sol".to_string()].map(|file| (file, "contract C {\n  uint256 x;\n}\n".to_string())));
+
+    let rendered = render_diagnostic(&error, &sources, 0);
+    assert!(rendered.contains("note: original declaration here"));
+    assert!(rendered.contains("--> C.sol:2:1"));
+  }
+
+  #[test]
+  fn format_diagnostics_preserves_order() {
+    let errors = vec![
+      error_with_location("first", "C.sol", 0, 1),
+      error_with_location("second", "C.sol", 0, 1),
+    ];
+    let sources = HashMap::new();
+
+    let rendered = format_diagnostics(&errors, &sources, None);
+    assert_eq!(rendered.len(), 2);
+    assert!(rendered[0].contains("first"));
+    assert!(rendered[1].contains("second"));
+  }
+
+  #[test]
+  fn diagnostic_summary_counts_by_severity() {
+    let errors = vec![
+      CompilerError {
+        severity: SeverityLevel::Error,
+        ..error_with_location("e", "C.sol", 0, 1)
+      },
+      CompilerError {
+        severity: SeverityLevel::Warning,
+        ..error_with_location("w", "C.sol", 0, 1)
+      },
+      CompilerError {
+        severity: SeverityLevel::Warning,
+        ..error_with_location("w2", "C.sol", 0, 1)
+      },
+      CompilerError {
+        severity: SeverityLevel::Info,
+        ..error_with_location("i", "C.sol", 0, 1)
+      },
+    ];
+
+    let summary = diagnostic_summary(&errors);
+    assert_eq!(summary, DiagnosticSummary { errors: 1, warnings: 2, infos: 1 });
+  }
+}
@@ -15,6 +15,12 @@ pub enum CompilationInput {
     sources: BTreeMap<String, String>,
     language_override: Option<CompilerLanguage>,
   },
+  /// A map of virtual file paths to source text whose per-file inferred language spans more than
+  /// one solc-compatible language (Solidity and Yul can share a compile, Vyper never can). Each
+  /// group compiles as its own `SolcInput` and the results are merged.
+  MixedSourceMap {
+    by_language: BTreeMap<CompilerLanguage, BTreeMap<String, String>>,
+  },
   /// Pre-parsed Solidity AST units keyed by their path.
   AstUnits { units: BTreeMap<String, Value> },
   /// Concrete filesystem paths that must be read from disk.
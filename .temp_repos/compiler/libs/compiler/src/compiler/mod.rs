@@ -1,13 +1,15 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Instant;
 
 use log::{error, info};
 use napi::bindgen_prelude::*;
-use napi::{Env, JsObject, JsUnknown};
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction, JsObject, JsUnknown, Task};
 use serde_json::Value;
 
-use crate::ast::utils::from_js_value;
+use crate::ast::utils::{from_js_value, to_js_value};
 use crate::internal::config::{
   parse_js_compiler_config, CompilerConfig, CompilerConfigOptions, CompilerLanguage,
 };
@@ -15,16 +17,24 @@ use crate::internal::errors::{napi_error, to_napi_result, Error, Result};
 use crate::internal::logging::{ensure_napi_logger, ensure_rust_logger, update_level};
 use crate::internal::path::ProjectPaths;
 use crate::internal::project::{default_cache_dir, synthetic_project_paths, ProjectContext};
+use crate::internal::report::{ProgressEvent, ProgressEventJson, Reporter};
 use crate::internal::solc;
+use crate::internal::vyper;
+use crate::internal::watch;
 pub use core::{
-  compile_contract, compile_files, compile_project, compile_source, compile_sources, init,
-  init_from_foundry_root, init_from_hardhat_root, init_from_root, resolve_config, SourceTarget,
-  SourceValue, State,
+  compile_contract, compile_files, compile_files_version_grouped, compile_many, compile_project,
+  compile_source, compile_sources, init, init_from_dapptools_root, init_from_detected_root,
+  init_from_foundry_root, init_from_hardhat_root, init_from_root, resolve_config, resolve_graph,
+  resolve_version_graph, SourceTarget, SourceValue, State,
 };
 pub use input::CompilationInput;
-use output::{into_js_compile_output, CompileOutput, JsCompileOutput};
+use output::{
+  into_js_compile_output, CompileOutput, DependencyGraphReportJson, JsCompileOutput,
+  VersionGraphReportJson,
+};
 
 pub mod core;
+mod diagnostics;
 mod input;
 pub mod output;
 mod project_runner;
@@ -155,6 +165,47 @@ impl Compiler {
     }
   }
 
+  /// Instantiate a compiler scoped to a dapptools/forge-std style project root (`src`/`lib`/`out`,
+  /// no `foundry.toml`). A `.dapprc` in `root`, or the matching `DAPP_SOLC_VERSION`/
+  /// `DAPP_BUILD_OPTIMIZE` environment variables, seed the solc version and optimizer settings
+  /// when present; otherwise they're left for the version graph resolver to decide.
+  pub fn from_dapptools_root<P: AsRef<Path>>(
+    root: P,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<Self> {
+    let config = CompilerConfig::from_options(options).map_err(Error::from)?;
+    ensure_rust_logger(config.logging_level)?;
+    let root_path = root.as_ref();
+    let root_display = format!("{}", root_path.display());
+    info!(
+      target: LOG_TARGET,
+      "initialising compiler from dapptools root {}",
+      root_display
+    );
+    let started = Instant::now();
+    match init_from_dapptools_root(config, root_path) {
+      Ok(state) => {
+        info!(
+          target: LOG_TARGET,
+          "compiler bound to dapptools project {} in {:?}",
+          root_display,
+          started.elapsed()
+        );
+        Ok(Self { state })
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to initialise dapptools compiler for {} after {:?}: {}",
+          root_display,
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
   /// Instantiate a compiler using an arbitrary filesystem root. Best suited for ad-hoc projects that
   /// still expect Foundry's output directory layout (e.g. temporary repositories).
   pub fn from_root<P: AsRef<Path>>(
@@ -194,6 +245,46 @@ impl Compiler {
     }
   }
 
+  /// Instantiate a compiler rooted at an arbitrary directory, auto-detecting whether it's a
+  /// Foundry project, a Hardhat project, or neither, instead of requiring the caller to already
+  /// know which of `from_foundry_root`/`from_hardhat_root`/`from_root` applies.
+  pub fn from_detected_root<P: AsRef<Path>>(
+    root: P,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<Self> {
+    let config = CompilerConfig::from_options(options).map_err(Error::from)?;
+    ensure_rust_logger(config.logging_level)?;
+    let root_path = root.as_ref();
+    let root_display = format!("{}", root_path.display());
+    info!(
+      target: LOG_TARGET,
+      "auto-detecting project layout at {}",
+      root_display
+    );
+    let started = Instant::now();
+    match init_from_detected_root(config, root_path) {
+      Ok(state) => {
+        info!(
+          target: LOG_TARGET,
+          "compiler bound to detected project {} in {:?}",
+          root_display,
+          started.elapsed()
+        );
+        Ok(Self { state })
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to initialise compiler for detected root {} after {:?}: {}",
+          root_display,
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
   /// Parse the supplied semantic version and ensure the matching `solc` binary is present on disk.
   /// The download is skipped when the version already exists.
   pub fn install_solc_version(version: &str) -> Result<()> {
@@ -277,6 +368,85 @@ impl Compiler {
     }
   }
 
+  /// Parse the supplied semantic version and ensure the matching `vyper` binary is present on
+  /// disk. The download is skipped when the version already exists.
+  pub fn install_vyper_version(version: &str) -> Result<()> {
+    info!(target: LOG_TARGET, "installing vyper version {}", version);
+    let started = Instant::now();
+    let parsed = match vyper::parse_version(version) {
+      Ok(parsed) => parsed,
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to parse vyper version \"{}\": {}",
+          version,
+          err
+        );
+        return Err(err);
+      }
+    };
+    match vyper::install_version(&parsed) {
+      Ok(()) => {
+        info!(
+          target: LOG_TARGET,
+          "vyper {} installed in {:?}",
+          parsed,
+          started.elapsed()
+        );
+        Ok(())
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to install vyper {} after {:?}: {}",
+          parsed,
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
+  /// Return whether the requested `vyper` version is already available locally.
+  pub fn is_vyper_version_installed(version: &str) -> Result<bool> {
+    let started = Instant::now();
+    let parsed = match vyper::parse_version(version) {
+      Ok(parsed) => parsed,
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to parse vyper version \"{}\": {}",
+          version,
+          err
+        );
+        return Err(err);
+      }
+    };
+    match vyper::is_version_installed(&parsed) {
+      Ok(installed) => {
+        info!(
+          target: LOG_TARGET,
+          "vyper {} installation status checked in {:?}: installed={}",
+          parsed,
+          started.elapsed(),
+          installed
+        );
+        Ok(installed)
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "failed to query vyper {} installation after {:?}: {}",
+          parsed,
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
   /// Compile a single inline source string or Solidity AST using the compiler's current
   /// configuration merged with any per-call overrides. Returns a `CompileOutput` that mirrors the
   /// TypeScript `CompileOutput<THasErrors, undefined>` shape. Passing an empty string results in a
@@ -384,6 +554,28 @@ impl Compiler {
     &self,
     paths: Vec<PathBuf>,
     options: Option<CompilerConfigOptions>,
+  ) -> Result<CompileOutput> {
+    self.compile_files_impl(paths, options, None)
+  }
+
+  /// Same as [`Self::compile_files`], but streams [`ProgressEvent`]s to `reporter` as the compile
+  /// progresses (solc version selection, the file group starting/finishing, and per-file cache
+  /// hits/misses), so a CLI or editor extension can render a live progress bar instead of only
+  /// seeing the final `CompileOutput` once everything has finished.
+  pub fn compile_files_with_progress(
+    &self,
+    paths: Vec<PathBuf>,
+    options: Option<CompilerConfigOptions>,
+    reporter: Reporter,
+  ) -> Result<CompileOutput> {
+    self.compile_files_impl(paths, options, Some(&reporter))
+  }
+
+  fn compile_files_impl(
+    &self,
+    paths: Vec<PathBuf>,
+    options: Option<CompilerConfigOptions>,
+    reporter: Option<&Reporter>,
   ) -> Result<CompileOutput> {
     if paths.is_empty() {
       error!(
@@ -412,7 +604,7 @@ impl Compiler {
       config.language,
       config.solc_version
     );
-    match compile_files(&config, paths, language_override) {
+    match compile_files(&config, paths, language_override, reporter) {
       Ok(output) => {
         info!(
           target: LOG_TARGET,
@@ -434,9 +626,162 @@ impl Compiler {
     }
   }
 
+  /// Run a batch of independent compilation jobs concurrently, each resolved against the
+  /// compiler's base configuration with its own overrides, then merged back into outputs ordered
+  /// to match `jobs`. Useful when a workspace mixes pragmas requiring several solc versions, or
+  /// when compiling many independent inline snippets, since a plain loop over `compile_sources`
+  /// would otherwise block on one invocation at a time. See
+  /// [`crate::internal::config::CompilerConfig::max_jobs`] to bound worker thread count.
+  pub fn compile_many(
+    &self,
+    jobs: Vec<(Option<CompilerConfigOptions>, CompilationInput)>,
+  ) -> Result<Vec<CompileOutput>> {
+    let started = Instant::now();
+    let job_count = jobs.len();
+    let mut resolved = Vec::with_capacity(job_count);
+    for (options, input) in jobs {
+      let config = match self.resolve_call_config(options.as_ref()) {
+        Ok(config) => config,
+        Err(err) => {
+          error!(
+            target: LOG_TARGET,
+            "compile_many failed to resolve config for a job: {}",
+            err
+          );
+          return Err(err);
+        }
+      };
+      resolved.push((config, input));
+    }
+
+    info!(
+      target: LOG_TARGET,
+      "compile_many start jobs={}",
+      job_count
+    );
+    match compile_many(&self.state, resolved) {
+      Ok(outputs) => {
+        info!(
+          target: LOG_TARGET,
+          "compile_many success jobs={} duration={:?}",
+          job_count,
+          started.elapsed()
+        );
+        Ok(outputs)
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "compile_many error after {:?}: {}",
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
+  /// Build the import dependency graph of `paths` and, per connected subtree, check whether a
+  /// single solc version can satisfy the union of every file's `pragma solidity` requirement.
+  /// Surfaces "no single version fits" problems up front, before a `compile_files` call would fail
+  /// partway through resolving a version for the mismatched group.
+  pub fn resolve_version_graph(
+    &self,
+    paths: Vec<PathBuf>,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<crate::internal::graph::VersionGraphReport> {
+    if paths.is_empty() {
+      error!(
+        target: LOG_TARGET,
+        "resolve_version_graph called without any paths"
+      );
+      return Err(Error::new("resolveVersionGraph requires at least one path."));
+    }
+    let started = Instant::now();
+    let config = self.resolve_call_config(options.as_ref())?;
+    match resolve_version_graph(&config, paths) {
+      Ok(report) => {
+        info!(
+          target: LOG_TARGET,
+          "resolve_version_graph success nodes={} duration={:?}",
+          report.nodes.len(),
+          started.elapsed()
+        );
+        Ok(report)
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "resolve_version_graph error after {:?}: {}",
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
+  /// Build the fully resolved import/dependency graph of `paths`: for each file, its combined
+  /// `pragma solidity` requirement and the canonical paths its `import`s resolve to once
+  /// remappings and relative-path resolution are applied. Unlike `resolve_version_graph`, which
+  /// only reports whether a subtree's requirements agree, this surfaces the edges themselves so
+  /// callers can do impact analysis or visualize project structure without invoking solc.
+  pub fn resolve_graph(
+    &self,
+    paths: Vec<PathBuf>,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<crate::internal::graph::DependencyGraphReport> {
+    if paths.is_empty() {
+      error!(target: LOG_TARGET, "resolve_graph called without any paths");
+      return Err(Error::new("resolveGraph requires at least one path."));
+    }
+    let started = Instant::now();
+    let config = self.resolve_call_config(options.as_ref())?;
+    match resolve_graph(&config, paths) {
+      Ok(report) => {
+        info!(
+          target: LOG_TARGET,
+          "resolve_graph success nodes={} duration={:?}",
+          report.nodes.len(),
+          started.elapsed()
+        );
+        Ok(report)
+      }
+      Err(err) => {
+        error!(
+          target: LOG_TARGET,
+          "resolve_graph error after {:?}: {}",
+          started.elapsed(),
+          err
+        );
+        Err(err)
+      }
+    }
+  }
+
   /// Compile every contract discovered in the attached project or synthetic workspace. Equivalent to
   /// running `forge build`/`hardhat compile` with the resolved configuration.
   pub fn compile_project(&self, options: Option<CompilerConfigOptions>) -> Result<CompileOutput> {
+    self.compile_project_impl(options, None)
+  }
+
+  /// Same as [`Self::compile_project`], but streams [`ProgressEvent`]s to `reporter` as the compile
+  /// progresses (solc version selection, the file group starting/finishing, and per-file cache
+  /// hits/misses), so a CLI or editor extension can render a live progress bar instead of only
+  /// seeing the final `CompileOutput` once everything has finished.
+  pub fn compile_project_with_progress(
+    &self,
+    options: Option<CompilerConfigOptions>,
+    reporter: Reporter,
+  ) -> Result<CompileOutput> {
+    self.compile_project_impl(options, Some(&reporter))
+  }
+
+  fn compile_project_impl(
+    &self,
+    options: Option<CompilerConfigOptions>,
+    reporter: Option<&Reporter>,
+  ) -> Result<CompileOutput> {
     let started = Instant::now();
     let config = match self.resolve_call_config(options.as_ref()) {
       Ok(config) => config,
@@ -456,7 +801,7 @@ impl Compiler {
       config.language,
       config.solc_version
     );
-    match compile_project(&self.state, &config) {
+    match compile_project(&self.state, &config, reporter) {
       Ok(output) => {
         info!(
           target: LOG_TARGET,
@@ -631,6 +976,34 @@ impl JsCompiler {
     to_napi_result(solc::is_version_installed(&parsed))
   }
 
+  /// Resolve and, if necessary, download the newest `solc` release satisfying a version
+  /// requirement (e.g. `"^0.8.20"`) without the caller needing to know the exact patch
+  /// version. The promise resolves with the concrete version string that was selected.
+  #[napi]
+  pub fn install_solc_version_req(
+    requirement: String,
+  ) -> napi::Result<AsyncTask<solc::InstallReqTask>> {
+    let parsed = semver::VersionReq::parse(&requirement).map_err(|err| {
+      napi_error(format!("Invalid solc version requirement `{requirement}`: {err}"))
+    })?;
+    Ok(solc::install_req_async(parsed))
+  }
+
+  /// Download and install a `vyper` binary that matches the requested semantic
+  /// version. The promise resolves once the binary has been persisted locally.
+  #[napi]
+  pub fn install_vyper_version(version: String) -> napi::Result<AsyncTask<vyper::InstallVyperTask>> {
+    let parsed = to_napi_result(vyper::parse_version(&version))?;
+    Ok(vyper::install_async(parsed))
+  }
+
+  /// Check whether a `vyper` binary for the provided version is already available.
+  #[napi]
+  pub fn is_vyper_version_installed(version: String) -> napi::Result<bool> {
+    let parsed = to_napi_result(vyper::parse_version(&version))?;
+    to_napi_result(vyper::is_version_installed(&parsed))
+  }
+
   /// Create a compiler that automatically discovers nearby project configuration.
   /// Pass `CompilerConfigOptions` to override defaults such as the solc version or
   /// remappings used for inline compilation.
@@ -709,6 +1082,36 @@ impl JsCompiler {
     Ok(Self::from_compiler(compiler))
   }
 
+  /// Construct a compiler that understands a dapptools/forge-std project layout rooted at
+  /// `root` (`src`/`lib`/`out`, no `foundry.toml`). A `.dapprc` or `DAPP_SOLC_VERSION`/
+  /// `DAPP_BUILD_OPTIMIZE` environment variables seed the solc version and optimizer settings
+  /// when present.
+  #[napi(
+    factory,
+    ts_args_type = "root: string, options?: CompilerConfigOptions | undefined"
+  )]
+  pub fn from_dapptools_root(
+    env: Env,
+    root: String,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<Self> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let config_options = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let level = config_options
+      .as_ref()
+      .and_then(|opts| opts.logging_level)
+      .unwrap_or_default();
+    ensure_napi_logger(&env, level)?;
+    let compiler = to_napi_result(Compiler::from_dapptools_root(
+      Path::new(&root),
+      config_options,
+    ))?;
+    Ok(Self::from_compiler(compiler))
+  }
+
   /// Construct a compiler bound to an arbitrary project root that follows the Foundry
   /// directory layout. Useful when working with generated or temporary repositories.
   #[napi(
@@ -730,6 +1133,34 @@ impl JsCompiler {
     Ok(Self::from_compiler(compiler))
   }
 
+  /// Construct a compiler rooted at `root`, auto-detecting whether it's a Foundry project, a
+  /// Hardhat project, or neither, instead of requiring the caller to pick the right factory.
+  #[napi(
+    factory,
+    ts_args_type = "root: string, options?: CompilerConfigOptions | undefined"
+  )]
+  pub fn from_detected_root(
+    env: Env,
+    root: String,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<Self> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let config_options = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let level = config_options
+      .as_ref()
+      .and_then(|opts| opts.logging_level)
+      .unwrap_or_default();
+    ensure_napi_logger(&env, level)?;
+    let compiler = to_napi_result(Compiler::from_detected_root(
+      Path::new(&root),
+      config_options,
+    ))?;
+    Ok(Self::from_compiler(compiler))
+  }
+
   /// Compile inline Solidity, Yul, or Vyper source text or an in-memory Solidity AST.
   /// Returns a rich `CompileOutput` snapshot describing contracts, sources, and errors.
   #[napi(
@@ -777,11 +1208,41 @@ impl JsCompiler {
     Ok(into_js_compile_output(output))
   }
 
+  /// Same as [`Self::compile_sources`], but runs the compile on napi's async task pool and
+  /// resolves a Promise instead of blocking the JS thread, so a bundler or LSP event loop stays
+  /// responsive while a large batch of inline sources compiles.
+  #[napi(
+    ts_generic_types = "TSources extends Record<string, string | object> = Record<string, string | object>",
+    ts_args_type = "sources: TSources, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, Extract<keyof TSources, string>[]> | CompileOutput<false, Extract<keyof TSources, string>[]>>"
+  )]
+  pub fn compile_sources_async(
+    &self,
+    env: Env,
+    sources: JsObject,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileSourcesTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let map = Self::parse_sources_object(&env, sources)?;
+    Ok(AsyncTask::new(CompileSourcesTask {
+      state: self.inner.state.clone(),
+      config,
+      sources: map,
+    }))
+  }
+
   /// Compile concrete files on disk. Language is inferred from extensions unless the
-  /// overrides provide an explicit compiler language.
+  /// overrides provide an explicit compiler language. Pass `onProgress` to receive structured
+  /// [`ProgressEvent`] notifications (solc version selection, the file group starting/finishing,
+  /// and per-file cache hits/misses) as the compile runs, instead of only seeing the final result.
   #[napi(
     ts_generic_types = "TFilePaths extends readonly string[] = readonly string[]",
-    ts_args_type = "paths: TFilePaths, options?: CompilerConfigOptions | undefined",
+    ts_args_type = "paths: TFilePaths, options?: CompilerConfigOptions | undefined, onProgress?: (event: ProgressEvent) => void",
     ts_return_type = "CompileOutput<true, TFilePaths> | CompileOutput<false, TFilePaths>"
   )]
   pub fn compile_files(
@@ -789,6 +1250,7 @@ impl JsCompiler {
     env: Env,
     paths: Vec<String>,
     options: Option<JsUnknown>,
+    on_progress: Option<JsFunction>,
   ) -> napi::Result<JsCompileOutput> {
     if paths.is_empty() {
       return Err(napi_error("compileFiles requires at least one path."));
@@ -801,20 +1263,117 @@ impl JsCompiler {
     let config = self.resolve_call_config(overrides.as_ref())?;
     let language_override = language_override(overrides.as_ref());
     let path_bufs = paths.into_iter().map(PathBuf::from).collect();
-    let output = to_napi_result(compile_files(&config, path_bufs, language_override))?;
+    let reporter = on_progress
+      .as_ref()
+      .map(|callback| create_progress_reporter(&env, callback))
+      .transpose()?;
+    let output = to_napi_result(compile_files(
+      &config,
+      path_bufs,
+      language_override,
+      reporter.as_ref(),
+    ))?;
     Ok(into_js_compile_output(output))
   }
 
+  /// Same as [`Self::compile_files`], but runs on napi's async task pool and resolves a Promise
+  /// instead of blocking the JS thread. Unlike `compile_files`, sources are first grouped by the
+  /// solc version their pragma requires and each group's standard-JSON invocation runs on its own
+  /// rayon thread (see [`compile_files_version_grouped`]), so a project spanning several solc
+  /// versions pays for genuinely parallel compilation instead of one invocation per group in
+  /// sequence. Does not accept an `onProgress` callback: progress events are delivered on the
+  /// calling thread, which the async task pool does not run on.
+  #[napi(
+    ts_generic_types = "TFilePaths extends readonly string[] = readonly string[]",
+    ts_args_type = "paths: TFilePaths, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, TFilePaths> | CompileOutput<false, TFilePaths>>"
+  )]
+  pub fn compile_files_async(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileFilesTask>> {
+    if paths.is_empty() {
+      return Err(napi_error("compileFilesAsync requires at least one path."));
+    }
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let language_override = language_override(overrides.as_ref());
+    let path_bufs = paths.into_iter().map(PathBuf::from).collect();
+    Ok(AsyncTask::new(CompileFilesTask {
+      config,
+      paths: path_bufs,
+      language_override,
+    }))
+  }
+
+  /// Build the import dependency graph of `paths` and, per connected subtree, check whether a
+  /// single solc version can satisfy the union of every file's `pragma solidity` requirement.
+  /// Returns one node per path with its combined requirement and an `incompatible` flag so JS
+  /// callers can render a colored tree before invoking the compiler.
+  #[napi(ts_args_type = "paths: readonly string[], options?: CompilerConfigOptions | undefined")]
+  pub fn resolve_version_graph(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<VersionGraphReportJson> {
+    if paths.is_empty() {
+      return Err(napi_error("resolveVersionGraph requires at least one path."));
+    }
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let path_bufs = paths.into_iter().map(PathBuf::from).collect();
+    let report = to_napi_result(self.inner.resolve_version_graph(path_bufs, overrides))?;
+    Ok(VersionGraphReportJson::from_report(&report))
+  }
+
+  /// Build the fully resolved import/dependency graph of `paths`. Returns one node per path with
+  /// its combined pragma requirement and the canonical paths its `import`s resolve to after
+  /// remappings and relative-path resolution, so JS callers can do impact analysis ("which
+  /// contracts must recompile if X changes?") or visualize project structure without running solc.
+  #[napi(ts_args_type = "paths: readonly string[], options?: CompilerConfigOptions | undefined")]
+  pub fn resolve_graph(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<DependencyGraphReportJson> {
+    if paths.is_empty() {
+      return Err(napi_error("resolveGraph requires at least one path."));
+    }
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let path_bufs = paths.into_iter().map(PathBuf::from).collect();
+    let report = to_napi_result(self.inner.resolve_graph(path_bufs, overrides))?;
+    Ok(DependencyGraphReportJson::from_report(&report))
+  }
+
   /// Compile the project associated with this compiler instance, returning a snapshot
-  /// covering every source file that emitted artifacts.
+  /// covering every source file that emitted artifacts. Pass `onProgress` to receive structured
+  /// [`ProgressEvent`] notifications (solc version selection, the file group starting/finishing,
+  /// and per-file cache hits/misses) as the compile runs, so a CLI or editor extension can render a
+  /// live progress bar instead of only seeing the final result.
   #[napi(
-    ts_args_type = "options?: CompilerConfigOptions | undefined",
+    ts_args_type = "options?: CompilerConfigOptions | undefined, onProgress?: (event: ProgressEvent) => void",
     ts_return_type = "CompileOutput<true, string[]> | CompileOutput<false, string[]>"
   )]
   pub fn compile_project(
     &self,
     env: Env,
     options: Option<JsUnknown>,
+    on_progress: Option<JsFunction>,
   ) -> napi::Result<JsCompileOutput> {
     let parsed = parse_js_compiler_config(&env, options)?;
     let overrides = parsed
@@ -822,10 +1381,44 @@ impl JsCompiler {
       .map(|opts| CompilerConfigOptions::try_from(opts))
       .transpose()?;
     let config = self.resolve_call_config(overrides.as_ref())?;
-    let output = to_napi_result(compile_project(&self.inner.state, &config))?;
+    let reporter = on_progress
+      .as_ref()
+      .map(|callback| create_progress_reporter(&env, callback))
+      .transpose()?;
+    let output = to_napi_result(compile_project(
+      &self.inner.state,
+      &config,
+      reporter.as_ref(),
+    ))?;
     Ok(into_js_compile_output(output))
   }
 
+  /// Same as [`Self::compile_project`], but runs on napi's async task pool and resolves a
+  /// Promise instead of blocking the JS thread, so a bundler or LSP event loop stays responsive
+  /// during a large `forge build`/`hardhat compile`-equivalent run. Does not accept an
+  /// `onProgress` callback: progress events are delivered on the calling thread, which the async
+  /// task pool does not run on.
+  #[napi(
+    ts_args_type = "options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, string[]> | CompileOutput<false, string[]>>"
+  )]
+  pub fn compile_project_async(
+    &self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileProjectTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    Ok(AsyncTask::new(CompileProjectTask {
+      state: self.inner.state.clone(),
+      config,
+    }))
+  }
+
   /// Compile a single contract from the attached project by its canonical name.
   #[napi(
     ts_args_type = "contractName: string, options?: CompilerConfigOptions | undefined",
@@ -853,6 +1446,53 @@ impl JsCompiler {
   pub fn get_paths(&self) -> napi::Result<ProjectPaths> {
     to_napi_result(self.inner.get_paths())
   }
+
+  /// Watch `paths` and everything they transitively import for changes, recompiling through the
+  /// instance's incremental cache (the one `compileSources` uses; `compileFiles` bypasses it) and invoking `callback` with
+  /// each fresh result. `callback` follows the Node error-first convention: `(error, output)`,
+  /// with exactly one of the two set. Returns a [`WatchHandle`]; call `.dispose()` on it to stop
+  /// watching -- the watcher also stops if the handle is garbage collected without being disposed.
+  #[napi(
+    ts_generic_types = "TFilePaths extends readonly string[] = readonly string[]",
+    ts_args_type = "paths: TFilePaths, options: CompilerConfigOptions | undefined, callback: (error: Error | null, output: CompileOutput<true, TFilePaths> | CompileOutput<false, TFilePaths> | null) => void"
+  )]
+  pub fn watch(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+    callback: JsFunction,
+  ) -> napi::Result<WatchHandle> {
+    if paths.is_empty() {
+      return Err(napi_error("watch requires at least one path."));
+    }
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let language_override = language_override(overrides.as_ref());
+    let path_bufs: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+
+    let tsfn: ThreadsafeFunction<JsCompileOutput> = env
+      .create_threadsafe_function::<JsCompileOutput, JsUnknown, _>(&callback, 0, |ctx| {
+        Ok(vec![to_js_value(&ctx.env, &ctx.value)?])
+      })?;
+
+    let session = watch::start(
+      self.inner.state.clone(),
+      config,
+      path_bufs,
+      language_override,
+      move |result: Result<CompileOutput>| {
+        let call_result = to_napi_result(result).map(into_js_compile_output);
+        let _ = tsfn.call(call_result, ThreadsafeFunctionCallMode::NonBlocking);
+      },
+    );
+    let session = to_napi_result(session)?;
+    Ok(WatchHandle::new(session))
+  }
 }
 
 impl JsCompiler {
@@ -899,6 +1539,104 @@ impl JsCompiler {
   }
 }
 
+/// Backs [`JsCompiler::compile_sources_async`]: runs [`compile_sources`] on napi's async task
+/// pool instead of the calling thread.
+pub struct CompileSourcesTask {
+  state: State,
+  config: CompilerConfig,
+  sources: BTreeMap<String, SourceValue>,
+}
+
+impl Task for CompileSourcesTask {
+  type Output = CompileOutput;
+  type JsValue = JsCompileOutput;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    to_napi_result(compile_sources(
+      &self.state,
+      &self.config,
+      std::mem::take(&mut self.sources),
+    ))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(into_js_compile_output(output))
+  }
+}
+
+/// Backs [`JsCompiler::compile_files_async`]: runs [`compile_files_version_grouped`] on napi's
+/// async task pool instead of the calling thread.
+pub struct CompileFilesTask {
+  config: CompilerConfig,
+  paths: Vec<PathBuf>,
+  language_override: Option<CompilerLanguage>,
+}
+
+impl Task for CompileFilesTask {
+  type Output = CompileOutput;
+  type JsValue = JsCompileOutput;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    to_napi_result(compile_files_version_grouped(
+      &self.config,
+      std::mem::take(&mut self.paths),
+      self.language_override,
+    ))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(into_js_compile_output(output))
+  }
+}
+
+/// Backs [`JsCompiler::compile_project_async`]: runs [`compile_project`] on napi's async task
+/// pool instead of the calling thread.
+pub struct CompileProjectTask {
+  state: State,
+  config: CompilerConfig,
+}
+
+impl Task for CompileProjectTask {
+  type Output = CompileOutput;
+  type JsValue = JsCompileOutput;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    to_napi_result(compile_project(&self.state, &self.config, None))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(into_js_compile_output(output))
+  }
+}
+
+/// Disposer handle returned by [`JsCompiler::watch`]. Holds the running [`watch::WatchSession`]
+/// behind a mutex so `.dispose()` can take it from JS (which only ever calls through `&self`);
+/// dropping the handle without disposing stops the watcher the same way, since `WatchSession`
+/// stops itself on drop.
+#[napi]
+pub struct WatchHandle {
+  session: Mutex<Option<watch::WatchSession>>,
+}
+
+impl WatchHandle {
+  fn new(session: watch::WatchSession) -> Self {
+    Self {
+      session: Mutex::new(Some(session)),
+    }
+  }
+}
+
+#[napi]
+impl WatchHandle {
+  /// Stop watching. Safe to call more than once; later calls are no-ops.
+  #[napi]
+  pub fn dispose(&self) {
+    if let Some(mut session) = self.session.lock().unwrap().take() {
+      session.stop();
+    }
+  }
+}
+
 fn parse_source_target(env: &Env, target: Either<String, JsObject>) -> napi::Result<SourceTarget> {
   match target {
     Either::A(source) => Ok(SourceTarget::Text(source)),
@@ -916,3 +1654,20 @@ fn language_override(overrides: Option<&CompilerConfigOptions>) -> Option<Compil
       .or_else(|| opts.solc.language.map(CompilerLanguage::from))
   })
 }
+
+/// Wraps a JS `onProgress` callback as a [`Reporter`], so [`JsCompiler::compile_files`]/
+/// [`JsCompiler::compile_project`] can stream [`ProgressEvent`]s back to JS through a
+/// `ThreadsafeFunction` -- the same mechanism the AST module's `onProgress` stage strings use,
+/// except each event is forwarded as a plain [`ProgressEventJson`] object instead of a string.
+fn create_progress_reporter(env: &Env, callback: &JsFunction) -> napi::Result<Reporter> {
+  let tsfn: ThreadsafeFunction<ProgressEventJson> =
+    env.create_threadsafe_function::<ProgressEventJson, JsUnknown, _>(callback, 0, |ctx| {
+      Ok(vec![to_js_value(&ctx.env, &ctx.value)?])
+    })?;
+  Ok(Reporter::new(move |event: ProgressEvent| {
+    let _ = tsfn.call(
+      Ok(ProgressEventJson::from(event)),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
+  }))
+}
@@ -21,11 +21,18 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::str::FromStr;
 
+use super::diagnostics;
 use crate::ast::{Ast, JsAst, SourceTarget};
 use crate::contract;
-use crate::contract::{Contract, JsContract, JsContractState};
+use crate::contract::{
+  ArtifactFieldSelection, ArtifactId, Contract, JsContract, JsContractState, JumpType,
+  SourceMapEntry,
+};
 use crate::internal::config::AstConfigOptions;
 use crate::internal::errors::napi_error;
+use crate::internal::graph::{DependencyGraphReport, VersionGraphReport};
+use crate::internal::hardhat_artifacts;
+use crate::internal::truffle_artifacts;
 
 // -----------------------------------------------------------------------------
 // Shared error and location types
@@ -173,6 +180,11 @@ pub struct CompilerError {
   pub secondary_source_locations: Option<Vec<SecondarySourceLocation>>,
   /// Vyper-specific source metadata when the diagnostic originated from Vyper.
   pub vyper_source_location: Option<VyperSourceLocation>,
+  /// Solc version that produced this diagnostic, when known. Populated when merging the outputs
+  /// of multiple per-version compiles (see [`merge_compile_outputs_by_version`]) so callers can
+  /// tell apart duplicate diagnostics raised by different versions of the same source; `None` for
+  /// diagnostics from an ordinary single-version compile.
+  pub solc_version: Option<String>,
 }
 
 // -----------------------------------------------------------------------------
@@ -195,6 +207,11 @@ pub struct SourceArtifacts {
   /// Contracts emitted for this source keyed by contract name. Each entry is the rich wrapper used
   /// elsewhere in the bindings (ABI, bytecode, metadata, etc.).
   pub contracts: BTreeMap<String, Contract>,
+  /// Every solc version a contract was compiled under for this source, keyed by contract name.
+  /// Populated alongside [`Self::contracts`] (which holds just one version per name) so a project
+  /// compiling the same contract name under multiple solc versions -- e.g. mixed pragmas across
+  /// files -- doesn't silently lose every build but the last one processed.
+  pub versioned_contracts: BTreeMap<String, Vec<(Version, Contract)>>,
 }
 
 impl SourceArtifacts {
@@ -280,23 +297,181 @@ pub struct CompileOutput {
   pub artifacts: BTreeMap<String, SourceArtifacts>,
   /// Convenience handle to the sole artifact when only one source produced output.
   pub artifact: Option<SourceArtifacts>,
-  /// All diagnostics produced during compilation across every severity level.
+  /// All diagnostics produced during compilation across every severity level, after the facade
+  /// filtering pipeline (`ignored_error_codes`, `compiler_severity_filter`,
+  /// `diagnostic_path_denylist`/`diagnostic_path_allowlist`, `severity_overrides`) has dropped or
+  /// rewritten entries. See `all_errors` for the unfiltered list.
   pub errors: Vec<CompilerError>,
+  /// Every diagnostic the compiler reported, before any of the facade filtering in
+  /// [`crate::compiler::diagnostics`] ran. Equal to `errors` until that pipeline runs, so callers
+  /// that want to know what was suppressed (e.g. to show a "3 warnings hidden" notice) can diff
+  /// this against `errors` instead of losing the difference entirely. For project-rooted compiles,
+  /// diagnostics already dropped by foundry's own `ignore_error_codes`/
+  /// `set_compiler_severity_filter` project options (see
+  /// [`crate::internal::project::ProjectContext`]'s builder wiring) never reach either list.
+  pub all_errors: Vec<CompilerError>,
+  /// Keys recompiled because their content hash or the resolved `CompilerConfig` fingerprint
+  /// changed since the last compile. Populated when `cache_enabled` routes inline/virtual sources
+  /// or a real project tree (`ProjectRunner::compile_project`) through the incremental cache
+  /// manifest; empty otherwise.
+  pub dirty_paths: Vec<String>,
+  /// Counterpart to `dirty_paths`: keys whose content hash and config fingerprint matched the
+  /// persisted manifest and were therefore reused instead of recompiled.
+  pub reused_paths: Vec<String>,
+  /// Mirrors `CompilerConfig::deny_warnings` for the compile that produced this output. When
+  /// `true`, [`CompileOutput::has_compiler_errors`] also counts warning-level diagnostics, without
+  /// changing the severity they're reported at in `errors`. Set by
+  /// [`crate::compiler::diagnostics::apply_deny_warnings`]; `false` otherwise.
+  pub deny_warnings: bool,
+  /// The solc version resolved for each compiled source path. Lets callers audit pragma-driven
+  /// version selection, e.g. confirming a multi-version workspace split the way they expected.
+  /// Empty when nothing was compiled (e.g. the version-resolution diagnostic below fired before
+  /// any source reached solc).
+  pub version_resolution: BTreeMap<String, Version>,
 }
 
 impl CompileOutput {
-  /// Returns `true` when any diagnostic is reported at error severity.
+  /// Returns `true` when any diagnostic is reported at error severity, or -- when `deny_warnings`
+  /// is set -- at warning severity.
   pub fn has_compiler_errors(&self) -> bool {
-    self
-      .errors
-      .iter()
-      .any(|error| error.severity == SeverityLevel::Error)
+    self.errors.iter().any(|error| {
+      error.severity == SeverityLevel::Error
+        || (self.deny_warnings && error.severity == SeverityLevel::Warning)
+    })
   }
 
   /// Convert the output into the struct consumed by the JavaScript bindings.
   pub fn to_json(&self) -> CompileOutputJson {
     CompileOutputJson::from_compile_output(self)
   }
+
+  /// Re-projects every contract already held in [`Self::artifacts`] into Hardhat's flat artifact
+  /// schema (`contractName`, `sourceName`, `abi`, `bytecode`, `deployedBytecode`,
+  /// `linkReferences`, `deployedLinkReferences`), keyed by `"<SourceName>:<ContractName>"` to stay
+  /// unique across files. Pure re-shaping of data already in memory -- no re-compilation involved
+  /// -- for toolchains that expect `npx hardhat compile`'s on-disk shape instead of Foundry's
+  /// aggregated `{contracts, sources, errors}` tree.
+  pub fn to_hardhat_artifacts(&self) -> BTreeMap<String, Value> {
+    let mut artifacts = BTreeMap::new();
+    for source in self.artifacts.values() {
+      let source_name = source.source_path.clone().unwrap_or_default();
+      for (name, contract) in &source.contracts {
+        let key = format!("{source_name}:{name}");
+        artifacts.insert(key, hardhat_artifacts::hardhat_artifact_json(name, contract.state()));
+      }
+    }
+    artifacts
+  }
+
+  /// Re-projects every contract already held in [`Self::artifacts`] into a minimal `{ abi,
+  /// bytecode }` shape, keyed the same way as [`Self::to_hardhat_artifacts`], for lightweight
+  /// clients that only need enough to deploy a contract and don't care about the rest of the
+  /// Hardhat artifact envelope.
+  pub fn to_minimal_artifacts(&self) -> BTreeMap<String, Value> {
+    let mut artifacts = BTreeMap::new();
+    for source in self.artifacts.values() {
+      let source_name = source.source_path.clone().unwrap_or_default();
+      for (name, contract) in &source.contracts {
+        let key = format!("{source_name}:{name}");
+        let state = contract.state();
+        artifacts.insert(
+          key,
+          json!({
+            "abi": state.abi.clone().unwrap_or_else(|| Value::Array(Vec::new())),
+            "bytecode": state
+              .creation_bytecode
+              .as_ref()
+              .map(|bytecode| bytecode.to_hex())
+              .unwrap_or_else(|| "0x".to_string()),
+          }),
+        );
+      }
+    }
+    artifacts
+  }
+
+  /// Re-projects every contract already held in [`Self::artifacts`] into Truffle's flat
+  /// contract-object schema (`contractName`, `abi`, `bytecode`, `deployedBytecode`, `sourceMap`,
+  /// `deployedSourceMap`, the defining source's `ast`, and a `compiler`/`networks` envelope),
+  /// keyed the same way as [`Self::to_hardhat_artifacts`], for toolchains built around `truffle
+  /// compile`'s `build/contracts/` output instead of Foundry's aggregated `{contracts, sources,
+  /// errors}` tree.
+  pub fn to_truffle_artifacts(&self) -> BTreeMap<String, Value> {
+    let mut artifacts = BTreeMap::new();
+    for source in self.artifacts.values() {
+      let source_name = source.source_path.clone().unwrap_or_default();
+      for (name, contract) in &source.contracts {
+        let key = format!("{source_name}:{name}");
+        artifacts.insert(
+          key,
+          truffle_artifacts::truffle_artifact_json(
+            name,
+            contract.state(),
+            source.solc_version.as_ref(),
+            source.ast.as_ref(),
+          ),
+        );
+      }
+    }
+    artifacts
+  }
+
+  /// Every [`ArtifactId`] resolvable from [`Self::artifacts`], one per compiled contract.
+  /// Contracts whose [`ContractState::artifact_id`] isn't resolvable yet (missing source path or
+  /// compiler version) are skipped.
+  pub fn ids(&self) -> impl Iterator<Item = ArtifactId> + '_ {
+    self
+      .artifacts
+      .values()
+      .flat_map(|source| source.contracts.values())
+      .filter_map(|contract| contract.state().artifact_id())
+  }
+
+  /// Looks up a contract by its precise [`ArtifactId`] (source path, name, and compiler version),
+  /// disambiguating contracts that share a name -- across files, or across solc versions -- which
+  /// a path string alone can't. `None` if no contract in [`Self::artifacts`] resolves to a
+  /// matching id.
+  pub fn artifact_by_id(&self, id: &ArtifactId) -> Option<&Contract> {
+    self
+      .artifacts
+      .values()
+      .flat_map(|source| source.contracts.values())
+      .find(|contract| contract.state().artifact_id().as_ref() == Some(id))
+  }
+}
+
+/// N-API mirror of [`ArtifactId`] so JS callers can round-trip a precise artifact reference
+/// (source path, contract name, and compiler version) -- for deployment manifests and similar --
+/// instead of guessing from a path string.
+#[napi(object, js_name = "ArtifactId")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsArtifactId {
+  pub source_path: String,
+  pub name: String,
+  pub version: String,
+}
+
+impl From<ArtifactId> for JsArtifactId {
+  fn from(id: ArtifactId) -> Self {
+    Self {
+      source_path: id.source_path,
+      name: id.name,
+      version: id.version.to_string(),
+    }
+  }
+}
+
+impl TryFrom<JsArtifactId> for ArtifactId {
+  type Error = napi::Error;
+
+  fn try_from(id: JsArtifactId) -> napi::Result<Self> {
+    Ok(ArtifactId {
+      source_path: id.source_path,
+      name: id.name,
+      version: Version::parse(&id.version).map_err(|err| napi_error(err.to_string()))?,
+    })
+  }
 }
 
 /// Serializable projection of `CompileOutput` exposed to JS callers.
@@ -310,12 +485,22 @@ pub struct CompileOutputJson {
   /// Map of every source artifact keyed by canonical path.
   #[napi(ts_type = "Record<string, SourceArtifactsJson> | undefined")]
   pub artifacts: Option<BTreeMap<String, SourceArtifactsJson>>,
-  /// Compiler diagnostics across all severity levels.
+  /// Compiler diagnostics across all severity levels, after facade filtering.
   #[napi(ts_type = "ReadonlyArray<CompilerError> | undefined")]
   pub errors: Option<Vec<CompilerError>>,
+  /// Every diagnostic the compiler reported, before facade filtering dropped or rewrote entries.
+  #[napi(ts_type = "ReadonlyArray<CompilerError> | undefined")]
+  pub all_errors: Option<Vec<CompilerError>>,
   /// Raw artifact payload mirroring the underlying compiler output.
   #[napi(ts_type = "Record<string, unknown> | undefined")]
   pub raw_artifacts: Option<Value>,
+  /// Keys recompiled since the last incremental-cache-backed compile.
+  #[napi(ts_type = "ReadonlyArray<string> | undefined")]
+  pub dirty_paths: Option<Vec<String>>,
+  /// Keys reused from the incremental cache because neither their content nor the resolved
+  /// config changed.
+  #[napi(ts_type = "ReadonlyArray<string> | undefined")]
+  pub reused_paths: Option<Vec<String>>,
 }
 
 impl CompileOutputJson {
@@ -340,47 +525,366 @@ impl CompileOutputJson {
       Some(output.errors.clone())
     };
 
+    let all_errors = if output.all_errors.is_empty() {
+      None
+    } else {
+      Some(output.all_errors.clone())
+    };
+
     Self {
       artifact,
       artifacts,
       errors,
+      all_errors,
       raw_artifacts: if output.raw_artifacts.is_null() {
         None
       } else {
         Some(output.raw_artifacts.clone())
       },
+      dirty_paths: if output.dirty_paths.is_empty() {
+        None
+      } else {
+        Some(output.dirty_paths.clone())
+      },
+      reused_paths: if output.reused_paths.is_empty() {
+        None
+      } else {
+        Some(output.reused_paths.clone())
+      },
+    }
+  }
+}
+
+/// Single entry in a [`VersionGraphReportJson`]: a source file's combined pragma requirement and
+/// whether it sits in an import subtree with no solc version satisfying every file in it.
+#[napi(object, js_name = "VersionGraphNode")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionGraphNodeJson {
+  /// Canonical path of the source file.
+  pub source: String,
+  /// Combined `pragma solidity` requirement for this file, rendered as written to `VersionReq`.
+  pub requirement: String,
+  /// `true` when this file's import subtree has no solc version satisfying every requirement in it.
+  pub incompatible: bool,
+}
+
+/// Import-graph version-compatibility report returned by `Compiler::resolveVersionGraph`, one node
+/// per source file passed in.
+#[napi(object, js_name = "VersionGraphReportJson")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionGraphReportJson {
+  #[napi(ts_type = "ReadonlyArray<VersionGraphNode>")]
+  pub nodes: Vec<VersionGraphNodeJson>,
+}
+
+impl VersionGraphReportJson {
+  pub(crate) fn from_report(report: &VersionGraphReport) -> Self {
+    Self {
+      nodes: report
+        .nodes
+        .iter()
+        .map(|node| VersionGraphNodeJson {
+          source: node.source.clone(),
+          requirement: node.requirement.to_string(),
+          incompatible: node.incompatible,
+        })
+        .collect(),
+    }
+  }
+}
+
+/// Single entry in a [`DependencyGraphReportJson`]: a source file's canonical path, combined
+/// pragma requirement, and the canonical paths its `import`s resolve to.
+#[napi(object, js_name = "DependencyGraphNode")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphNodeJson {
+  /// Canonical path of the source file.
+  pub source: String,
+  /// Combined `pragma solidity` requirement for this file, rendered as written to `VersionReq`.
+  pub requirement: String,
+  /// Canonical paths this file's `import`s resolve to after remappings and relative-path
+  /// resolution, in the order the imports appear in the source.
+  pub imports: Vec<String>,
+}
+
+/// Fully resolved import/dependency graph returned by `Compiler::resolveGraph`, one node per
+/// source file passed in.
+#[napi(object, js_name = "DependencyGraphReport")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphReportJson {
+  #[napi(ts_type = "ReadonlyArray<DependencyGraphNode>")]
+  pub nodes: Vec<DependencyGraphNodeJson>,
+}
+
+impl DependencyGraphReportJson {
+  pub(crate) fn from_report(report: &DependencyGraphReport) -> Self {
+    Self {
+      nodes: report
+        .nodes
+        .iter()
+        .map(|node| DependencyGraphNodeJson {
+          source: node.source.clone(),
+          requirement: node.requirement.to_string(),
+          imports: node.imports.clone(),
+        })
+        .collect(),
     }
   }
 }
 
 pub fn into_core_compile_output(output: ProjectCompileOutput<MultiCompiler>) -> CompileOutput {
-  let artifacts = collate_project_artifacts(&output);
+  into_core_compile_output_with_selection(output, ArtifactFieldSelection::ALL)
+}
+
+pub fn into_core_compile_output_with_selection(
+  output: ProjectCompileOutput<MultiCompiler>,
+  selection: ArtifactFieldSelection,
+) -> CompileOutput {
+  let artifacts = collate_project_artifacts(&output, selection);
+  let version_resolution: BTreeMap<String, Version> = artifacts
+    .iter()
+    .filter_map(|(path, entry)| entry.solc_version.clone().map(|version| (path.clone(), version)))
+    .collect();
   let artifact = artifacts
     .values()
     .next()
     .cloned()
     .filter(|_| artifacts.len() == 1);
+  let errors: Vec<CompilerError> = output
+    .output()
+    .errors
+    .iter()
+    .map(|error: &MultiCompilerError| multi_error_to_core(error))
+    .collect();
   CompileOutput {
     raw_artifacts: aggregated_to_value(output.output()),
-    errors: output
-      .output()
-      .errors
-      .iter()
-      .map(|error: &MultiCompilerError| multi_error_to_core(error))
-      .collect(),
+    all_errors: errors.clone(),
+    errors,
     artifact,
     artifacts,
+    dirty_paths: Vec::new(),
+    reused_paths: Vec::new(),
+    deny_warnings: false,
+    version_resolution,
   }
 }
 
 pub fn from_standard_json(output: CompilerOutput) -> CompileOutput {
+  from_standard_json_with_selection(output, ArtifactFieldSelection::ALL)
+}
+
+pub fn from_standard_json_with_selection(
+  output: CompilerOutput,
+  selection: ArtifactFieldSelection,
+) -> CompileOutput {
   let raw_artifacts = serde_json::to_value(&output).unwrap_or(Value::Null);
   let errors = output
     .errors
     .iter()
     .map(|error: &FoundryCompilerError| solc_error_to_core(error))
     .collect();
-  build_compile_output(&output.contracts, &output.sources, raw_artifacts, errors)
+  build_compile_output(
+    &output.contracts,
+    &output.sources,
+    raw_artifacts,
+    errors,
+    selection,
+  )
+}
+
+/// Combines the `CompileOutput`s produced by compiling disjoint buckets of a single logical
+/// source set -- e.g. the per-solc-version split performed by `CompilerConfig::auto_detect_version`
+/// when a workspace's import graph spans more than one pragma requirement -- back into the single
+/// aggregate result callers expect from one `compile_*` call. Assumes the buckets partition
+/// disjoint path sets, so artifact maps never collide. `raw_artifacts` keeps each bucket's own
+/// Foundry-shaped payload as an array entry rather than attempting to merge them into one tree,
+/// since buckets compiled against different solc releases have no single shared schema.
+pub(crate) fn merge_compile_outputs(outputs: Vec<CompileOutput>) -> CompileOutput {
+  let mut artifacts = BTreeMap::new();
+  let mut errors = Vec::new();
+  let mut all_errors = Vec::new();
+  let mut dirty_paths = Vec::new();
+  let mut reused_paths = Vec::new();
+  let mut raw_buckets = Vec::with_capacity(outputs.len());
+  let mut deny_warnings = false;
+  let mut version_resolution = BTreeMap::new();
+
+  for output in outputs {
+    artifacts.extend(output.artifacts);
+    errors.extend(output.errors);
+    all_errors.extend(output.all_errors);
+    dirty_paths.extend(output.dirty_paths);
+    reused_paths.extend(output.reused_paths);
+    deny_warnings |= output.deny_warnings;
+    version_resolution.extend(output.version_resolution);
+    raw_buckets.push(output.raw_artifacts);
+  }
+
+  let artifact = artifacts
+    .values()
+    .next()
+    .cloned()
+    .filter(|_| artifacts.len() == 1);
+
+  CompileOutput {
+    raw_artifacts: Value::Array(raw_buckets),
+    artifacts,
+    artifact,
+    errors,
+    all_errors,
+    dirty_paths,
+    reused_paths,
+    deny_warnings,
+    version_resolution,
+  }
+}
+
+/// Combines the `CompileOutput`s produced by compiling the *same* source set under several
+/// distinct solc versions -- e.g. [`crate::compiler::core::compile_many_versions`] deliberately
+/// recompiling shared sources against every version a workspace's contracts require -- back into
+/// a single aggregate result. Unlike [`merge_compile_outputs`], the buckets here are expected to
+/// overlap: a path compiled under more than one version keeps its first artifact entry under its
+/// plain path and every subsequent version's entry under `"{path}@{version}"`, so later versions
+/// never silently overwrite earlier ones. Every error is stamped with the version that produced it
+/// before merging, so callers can tell apart duplicate diagnostics raised by different versions of
+/// the same source.
+pub(crate) fn merge_compile_outputs_by_version(outputs: Vec<(Version, CompileOutput)>) -> CompileOutput {
+  let mut artifacts = BTreeMap::new();
+  let mut errors = Vec::new();
+  let mut all_errors = Vec::new();
+  let mut dirty_paths = Vec::new();
+  let mut reused_paths = Vec::new();
+  let mut raw_buckets = Vec::with_capacity(outputs.len());
+  let mut deny_warnings = false;
+  let mut version_resolution = BTreeMap::new();
+
+  for (version, output) in outputs {
+    for (path, source_artifacts) in output.artifacts {
+      let key = if artifacts.contains_key(&path) {
+        format!("{path}@{version}")
+      } else {
+        path
+      };
+      artifacts.insert(key, source_artifacts);
+    }
+    errors.extend(output.errors.into_iter().map(|error| CompilerError {
+      solc_version: Some(version.to_string()),
+      ..error
+    }));
+    all_errors.extend(output.all_errors.into_iter().map(|error| CompilerError {
+      solc_version: Some(version.to_string()),
+      ..error
+    }));
+    dirty_paths.extend(output.dirty_paths);
+    reused_paths.extend(output.reused_paths);
+    deny_warnings |= output.deny_warnings;
+    version_resolution.extend(output.version_resolution);
+    raw_buckets.push(output.raw_artifacts);
+  }
+
+  let artifact = artifacts
+    .values()
+    .next()
+    .cloned()
+    .filter(|_| artifacts.len() == 1);
+
+  CompileOutput {
+    raw_artifacts: Value::Array(raw_buckets),
+    artifacts,
+    artifact,
+    errors,
+    all_errors,
+    dirty_paths,
+    reused_paths,
+    deny_warnings,
+    version_resolution,
+  }
+}
+
+/// Builds a [`CompileOutput`] carrying a single structured diagnostic instead of any compiled
+/// artifacts, for when a required solc release isn't already installed and `offline_mode`
+/// forbids downloading it. Lets callers inspect `errors` the same way they would any other
+/// compile failure instead of catching a thrown exception.
+pub(crate) fn version_resolution_error_output(requirement: &str) -> CompileOutput {
+  let errors = vec![CompilerError {
+    message: format!(
+      "No installed solc version satisfies pragma requirement `{requirement}` and \
+       offline_mode is enabled; install a matching release first."
+    ),
+    formatted_message: None,
+    component: "version-resolution".to_string(),
+    severity: SeverityLevel::Error,
+    error_type: "VersionResolutionError".to_string(),
+    error_code: None,
+    source_location: None,
+    secondary_source_locations: None,
+    vyper_source_location: None,
+    solc_version: None,
+  }];
+  CompileOutput {
+    raw_artifacts: Value::Null,
+    artifacts: BTreeMap::new(),
+    artifact: None,
+    all_errors: errors.clone(),
+    errors,
+    dirty_paths: Vec::new(),
+    reused_paths: Vec::new(),
+    deny_warnings: false,
+    version_resolution: BTreeMap::new(),
+  }
+}
+
+/// Pluggable artifact-emission shape, following the ethers-solc `ArtifactOutput` abstraction: each
+/// implementor re-projects a [`CompileOutput`] into its own JSON schema without `CompileOutput`
+/// itself knowing about any particular downstream toolchain. Used by [`JsCompileOutput::to_json`]
+/// to let callers request a format by name instead of re-deriving artifacts on the JS side.
+pub(crate) trait ArtifactOutput {
+  /// Re-projects `output` into this format's JSON shape.
+  fn format(output: &CompileOutput) -> Value;
+}
+
+/// The native artifact shape emitted by [`CompileOutput::to_json`] -- what every caller got before
+/// format selection existed, and still the default when no format is requested.
+pub(crate) struct NativeArtifactOutput;
+
+impl ArtifactOutput for NativeArtifactOutput {
+  fn format(output: &CompileOutput) -> Value {
+    serde_json::to_value(output.to_json()).unwrap_or(Value::Null)
+  }
+}
+
+/// Hardhat's flat `{ contractName, abi, bytecode, deployedBytecode, linkReferences }` shape (see
+/// [`CompileOutput::to_hardhat_artifacts`]).
+pub(crate) struct HardhatArtifactOutput;
+
+impl ArtifactOutput for HardhatArtifactOutput {
+  fn format(output: &CompileOutput) -> Value {
+    serde_json::to_value(output.to_hardhat_artifacts()).unwrap_or(Value::Null)
+  }
+}
+
+/// Minimal `{ abi, bytecode }` shape (see [`CompileOutput::to_minimal_artifacts`]) for lightweight
+/// clients that only need enough to deploy a contract.
+pub(crate) struct MinimalArtifactOutput;
+
+impl ArtifactOutput for MinimalArtifactOutput {
+  fn format(output: &CompileOutput) -> Value {
+    serde_json::to_value(output.to_minimal_artifacts()).unwrap_or(Value::Null)
+  }
+}
+
+/// Truffle's `{ contractName, abi, bytecode, deployedBytecode, sourceMap, ast, compiler }` shape
+/// (see [`CompileOutput::to_truffle_artifacts`]).
+pub(crate) struct TruffleArtifactOutput;
+
+impl ArtifactOutput for TruffleArtifactOutput {
+  fn format(output: &CompileOutput) -> Value {
+    serde_json::to_value(output.to_truffle_artifacts()).unwrap_or(Value::Null)
+  }
 }
 
 fn convert_source_ast(source: &SourceFile) -> Option<Value> {
@@ -388,7 +892,10 @@ fn convert_source_ast(source: &SourceFile) -> Option<Value> {
   serde_json::to_value(ast).ok()
 }
 
-fn solc_error_to_core(error: &FoundryCompilerError) -> CompilerError {
+/// Converts a single raw solc diagnostic into the normalised [`CompilerError`] shape. Exposed to
+/// [`crate::ast::parser`] so its fragment-compile path can reuse the same conversion instead of
+/// hand-rolling a second one for raw solc JSON it deserialises itself.
+pub(crate) fn solc_error_to_core(error: &FoundryCompilerError) -> CompilerError {
   let severity = match error.severity {
     Severity::Error => SeverityLevel::Error,
     Severity::Warning => SeverityLevel::Warning,
@@ -420,6 +927,7 @@ fn solc_error_to_core(error: &FoundryCompilerError) -> CompilerError {
     }),
     secondary_source_locations: secondary,
     vyper_source_location: None,
+    solc_version: None,
   }
 }
 
@@ -446,6 +954,7 @@ pub(crate) fn vyper_error_to_core(error: &VyperCompilationError) -> CompilerErro
     source_location: None,
     secondary_source_locations: None,
     vyper_source_location,
+    solc_version: None,
   }
 }
 
@@ -461,17 +970,24 @@ pub(crate) fn build_compile_output(
   sources: &BTreeMap<PathBuf, SourceFile>,
   raw_artifacts: Value,
   errors: Vec<CompilerError>,
+  selection: ArtifactFieldSelection,
 ) -> CompileOutput {
   let mut artifacts: BTreeMap<String, SourceArtifacts> = BTreeMap::new();
 
   for (path, contract_map) in contracts {
     let key = path.to_string_lossy().to_string();
+    let source_ast = sources.get(path).and_then(convert_source_ast);
     let entry = artifacts
       .entry(key.clone())
       .or_insert_with(|| SourceArtifacts::new(Some(key.clone())));
 
     for (name, foundry_contract) in contract_map {
-      let mut core = Contract::from_foundry_standard_json(name.clone(), foundry_contract);
+      let mut core = Contract::from_foundry_standard_json_with_ast(
+        name.clone(),
+        foundry_contract,
+        source_ast.as_ref(),
+        selection,
+      );
       core.state_mut().source_path = Some(key.clone());
       entry.contracts.insert(name.clone(), core);
     }
@@ -496,7 +1012,12 @@ pub(crate) fn build_compile_output(
     raw_artifacts,
     artifacts,
     artifact,
+    all_errors: errors.clone(),
     errors,
+    dirty_paths: Vec::new(),
+    reused_paths: Vec::new(),
+    deny_warnings: false,
+    version_resolution: BTreeMap::new(),
   }
 }
 
@@ -529,15 +1050,32 @@ fn clamp_u64_to_i32(value: u64) -> i32 {
   i32::try_from(value).unwrap_or(i32::MAX)
 }
 
-fn collate_project_artifacts(
+pub(crate) fn collate_project_artifacts(
   output: &ProjectCompileOutput<MultiCompiler>,
+  selection: ArtifactFieldSelection,
 ) -> BTreeMap<String, SourceArtifacts> {
   let mut artifacts: BTreeMap<String, SourceArtifacts> = BTreeMap::new();
 
-  let mut version_lookup: BTreeMap<(String, String), Version> = BTreeMap::new();
-  for (path, name, _, version) in output.output().contracts.contracts_with_files_and_version() {
+  for (path, name, contract, version) in
+    output.output().contracts.contracts_with_files_and_version()
+  {
     let key = path.to_string_lossy().to_string();
-    version_lookup.insert((key, name.clone()), version.clone());
+    let entry = artifacts
+      .entry(key.clone())
+      .or_insert_with(|| SourceArtifacts::new(Some(key.clone())));
+    if entry.solc_version.is_none() {
+      entry.solc_version = Some(version.clone());
+    }
+
+    let mut versioned =
+      Contract::from_foundry_standard_json_with_ast(name.clone(), contract, None, selection);
+    versioned.state_mut().source_path = Some(key.clone());
+    versioned.state_mut().version = Some(version.clone());
+    entry
+      .versioned_contracts
+      .entry(name.clone())
+      .or_insert_with(Vec::new)
+      .push((version.clone(), versioned));
   }
 
   for (path, name, artifact) in output.artifacts_with_files() {
@@ -546,16 +1084,15 @@ fn collate_project_artifacts(
       .entry(key.clone())
       .or_insert_with(|| SourceArtifacts::new(Some(key.clone())));
 
-    let version = version_lookup.get(&(key.clone(), name.clone())).cloned();
-    if entry.solc_version.is_none() {
-      entry.solc_version = version.clone();
-    }
-
-    let mut contract = Contract::from_configurable_artifact(name.clone(), artifact);
+    let mut contract =
+      Contract::from_configurable_artifact_with_selection(name.clone(), artifact, selection);
     contract.state_mut().source_path = Some(key.clone());
     if entry.source_id.is_none() {
       entry.source_id = contract.state().source_id;
     }
+    if entry.solc_version.is_none() {
+      entry.solc_version = contract.state().version.clone();
+    }
     entry.contracts.insert(name.clone(), contract);
   }
 
@@ -588,9 +1125,20 @@ where
   for (path, entries) in aggregated.contracts.0.iter() {
     let mut contract_map = Map::new();
     for (name, versions) in entries.iter() {
-      if let Some(latest) = versions.last() {
-        if let Ok(value) = serde_json::to_value(&latest.contract) {
-          contract_map.insert(name.clone(), value);
+      let mut values: Vec<Value> = versions
+        .iter()
+        .filter_map(|versioned| serde_json::to_value(&versioned.contract).ok())
+        .collect();
+
+      // Keep the single-version shape Solc's own standard JSON uses; only fall back to an array
+      // once a contract was genuinely compiled under more than one version.
+      match values.len() {
+        0 => {}
+        1 => {
+          contract_map.insert(name.clone(), values.remove(0));
+        }
+        _ => {
+          contract_map.insert(name.clone(), Value::Array(values));
         }
       }
     }
@@ -639,10 +1187,16 @@ pub struct JsSourceArtifacts {
   json: SourceArtifactsJson,
   /// Contracts emitted for the source keyed by name (rich `Contract` wrappers, not plain JSON).
   contracts: HashMap<String, Contract>,
+  /// Every solc version of each contract compiled for this source, keyed by contract name.
+  versioned_contracts: HashMap<String, Vec<(Version, Contract)>>,
+  /// Whether this source's artifacts were replayed from the incremental cache rather than freshly
+  /// compiled this run. Mirrors membership in `CompileOutput::reused_paths`, surfaced per-artifact
+  /// so editors can show exactly which files were skipped.
+  from_cache: bool,
 }
 
 impl JsSourceArtifacts {
-  fn from_core(artifacts: SourceArtifacts) -> Self {
+  fn from_core(artifacts: SourceArtifacts, from_cache: bool) -> Self {
     let json = artifacts.to_json();
 
     let SourceArtifacts {
@@ -651,6 +1205,7 @@ impl JsSourceArtifacts {
       solc_version,
       ast,
       contracts,
+      versioned_contracts,
     } = artifacts;
 
     Self {
@@ -660,6 +1215,8 @@ impl JsSourceArtifacts {
       ast_unit: ast,
       json,
       contracts: contracts.into_iter().collect(),
+      versioned_contracts: versioned_contracts.into_iter().collect(),
+      from_cache,
     }
   }
 
@@ -692,6 +1249,8 @@ impl JsSourceArtifacts {
       ast_unit: None,
       json: SourceArtifactsJson::default(),
       contracts: HashMap::new(),
+      versioned_contracts: HashMap::new(),
+      from_cache: false,
     }
   }
 
@@ -748,6 +1307,146 @@ impl JsSourceArtifacts {
   pub fn to_json(&self) -> SourceArtifactsJson {
     self.json.clone()
   }
+
+  /// Whether these artifacts were replayed from the incremental cache instead of being freshly
+  /// compiled this run.
+  #[napi(getter, js_name = "fromCache")]
+  pub fn from_cache(&self) -> bool {
+    self.from_cache
+  }
+
+  /// Every compiled version of each contract keyed by contract name, so callers can disambiguate
+  /// a contract compiled under more than one solc version instead of only ever seeing whichever
+  /// version ended up in `contracts`.
+  #[napi(getter, ts_return_type = "Record<string, Contract[]>")]
+  pub fn contracts_by_version(&self) -> HashMap<String, Vec<JsContract>> {
+    self
+      .versioned_contracts
+      .iter()
+      .map(|(name, versions)| {
+        let wrapped = versions
+          .iter()
+          .map(|(_, contract)| contract::contract_class(contract))
+          .collect();
+        (name.clone(), wrapped)
+      })
+      .collect()
+  }
+
+  /// Looks up a single contract by name and solc version string (e.g. `"0.8.21"`). `undefined`
+  /// when that contract wasn't compiled under the given version.
+  #[napi(ts_return_type = "Contract | undefined")]
+  pub fn contract_for_version(
+    &self,
+    name: String,
+    version: String,
+  ) -> napi::Result<Option<JsContract>> {
+    let target = Version::parse(&version).map_err(|err| napi_error(err.to_string()))?;
+    Ok(
+      self
+        .versioned_contracts
+        .get(&name)
+        .and_then(|versions| versions.iter().find(|(v, _)| *v == target))
+        .map(|(_, contract)| contract::contract_class(contract)),
+    )
+  }
+
+  /// Re-projects this source's contracts into Hardhat's flat artifact schema, keyed by contract
+  /// name, so consumers can drop the output straight into Hardhat-style deployment pipelines
+  /// without writing their own converter.
+  #[napi(getter, js_name = "hardhatArtifacts", ts_return_type = "Record<string, unknown>")]
+  pub fn hardhat_artifacts(&self) -> HashMap<String, Value> {
+    self
+      .contracts
+      .iter()
+      .map(|(name, contract)| {
+        (
+          name.clone(),
+          hardhat_artifacts::hardhat_artifact_json(name, contract.state()),
+        )
+      })
+      .collect()
+  }
+
+  /// Parsed ABI for every contract in this source, keyed by contract name. A contract the
+  /// compiler didn't emit an ABI for falls back to an empty array, matching
+  /// [`ContractState::abi`]'s own JSON representation.
+  #[napi(getter, ts_return_type = "Record<string, unknown>")]
+  pub fn abi(&self) -> HashMap<String, Value> {
+    self
+      .contracts
+      .iter()
+      .map(|(name, contract)| {
+        (
+          name.clone(),
+          contract
+            .state()
+            .abi
+            .clone()
+            .unwrap_or_else(|| Value::Array(Vec::new())),
+        )
+      })
+      .collect()
+  }
+
+  /// Creation bytecode for every contract in this source, keyed by contract name, as `0x`-prefixed
+  /// hex. A contract with one or more unresolved library placeholders keeps the placeholder string
+  /// verbatim instead of producing malformed hex (see [`ContractBytecode::to_hex`]). Contracts
+  /// without creation bytecode (e.g. interfaces) are omitted.
+  #[napi(getter, js_name = "creationBytecode")]
+  pub fn creation_bytecode(&self) -> HashMap<String, String> {
+    self
+      .contracts
+      .iter()
+      .filter_map(|(name, contract)| {
+        contract
+          .state()
+          .creation_bytecode
+          .as_ref()
+          .map(|bytecode| (name.clone(), bytecode.to_hex()))
+      })
+      .collect()
+  }
+
+  /// Deployed bytecode counterpart to [`Self::creation_bytecode`].
+  #[napi(getter, js_name = "deployedBytecode")]
+  pub fn deployed_bytecode(&self) -> HashMap<String, String> {
+    self
+      .contracts
+      .iter()
+      .filter_map(|(name, contract)| {
+        contract
+          .state()
+          .deployed_bytecode
+          .as_ref()
+          .map(|bytecode| (name.clone(), bytecode.to_hex()))
+      })
+      .collect()
+  }
+
+  /// Resolves every library placeholder across this source's contracts against `libraries`
+  /// (keyed by fully-qualified `file:library` or bare `library` name, each mapped to a 20-byte hex
+  /// address) and returns the linked contracts keyed by name. Fails with the underlying
+  /// [`LinkError`]'s message -- listing every placeholder `libraries` didn't cover, or the
+  /// offending address, if one was invalid -- rather than returning malformed hex for an
+  /// unresolved contract.
+  #[napi(ts_return_type = "Record<string, ContractState>")]
+  pub fn link(
+    &self,
+    libraries: HashMap<String, String>,
+  ) -> napi::Result<HashMap<String, JsContractState>> {
+    self
+      .contracts
+      .iter()
+      .map(|(name, contract)| {
+        let linked = contract
+          .state()
+          .link(&libraries)
+          .map_err(|err| napi_error(err.to_string()))?;
+        Ok((name.clone(), contract::contract_state_to_js(&linked)))
+      })
+      .collect()
+  }
 }
 
 /// JavaScript-facing mirror of `CompileOutput` with ergonomic getters for downstream tooling. This
@@ -757,6 +1456,13 @@ impl JsSourceArtifacts {
 pub struct JsCompileOutput {
   /// Eagerly prepared JSON snapshot for this compile output.
   json: CompileOutputJson,
+  /// Eagerly prepared Hardhat-shaped projection, selected by `toJson({ format: "hardhat" })`.
+  hardhat_json: Value,
+  /// Eagerly prepared minimal `{ abi, bytecode }` projection, selected by
+  /// `toJson({ format: "minimal" })`.
+  minimal_json: Value,
+  /// Eagerly prepared Truffle-shaped projection, selected by `toJson({ format: "truffle" })`.
+  truffle_json: Value,
   /// Raw artifact tree mirroring the underlying compiler response (equivalent to
   /// `compileOutput.rawArtifacts`).
   raw_artifacts: Value,
@@ -766,34 +1472,90 @@ pub struct JsCompileOutput {
   artifact: Option<JsSourceArtifacts>,
   /// Diagnostics produced during compilation.
   errors: Vec<CompilerError>,
+  /// Every diagnostic the compiler reported, before facade filtering dropped or rewrote entries.
+  all_errors: Vec<CompilerError>,
   /// Cached flag indicating whether any diagnostic has error severity.
   has_compiler_errors: bool,
+  /// Keys recompiled since the last incremental-cache-backed compile.
+  dirty_paths: Vec<String>,
+  /// Keys reused from the incremental cache.
+  reused_paths: Vec<String>,
+  /// Every compiled contract's [`ArtifactId`], precomputed at construction time.
+  ids: Vec<JsArtifactId>,
+  /// Lookup from a stringified [`ArtifactId`] to its contract, backing [`Self::artifact_by_id`].
+  contracts_by_id: HashMap<String, Contract>,
+  /// The solc version resolved for each compiled source path.
+  version_resolution: HashMap<String, Version>,
+}
+
+/// Canonical lookup key for an [`ArtifactId`], combining all three fields so contracts sharing a
+/// name -- across files or solc versions -- never collide.
+fn artifact_id_key(id: &ArtifactId) -> String {
+  format!("{}::{}::{}", id.source_path, id.name, id.version)
 }
 
 impl JsCompileOutput {
   fn from_core(core: CompileOutput) -> Self {
     let has_compiler_errors = core.has_compiler_errors();
     let json = core.to_json();
+    let hardhat_json = HardhatArtifactOutput::format(&core);
+    let minimal_json = MinimalArtifactOutput::format(&core);
+    let truffle_json = TruffleArtifactOutput::format(&core);
+    let ids = core.ids().map(JsArtifactId::from).collect();
+    let contracts_by_id = core
+      .artifacts
+      .values()
+      .flat_map(|source| source.contracts.values())
+      .filter_map(|contract| {
+        let id = contract.state().artifact_id()?;
+        Some((artifact_id_key(&id), contract.clone()))
+      })
+      .collect();
     let CompileOutput {
       raw_artifacts,
       artifacts,
       artifact,
       errors,
+      all_errors,
+      dirty_paths,
+      reused_paths,
+      deny_warnings: _,
+      version_resolution,
     } = core;
 
+    let reused: std::collections::HashSet<&str> =
+      reused_paths.iter().map(String::as_str).collect();
     let artifacts = artifacts
       .into_iter()
-      .map(|(path, artifacts)| (path, JsSourceArtifacts::from_core(artifacts)))
+      .map(|(path, artifacts)| {
+        let from_cache = reused.contains(path.as_str());
+        (path, JsSourceArtifacts::from_core(artifacts, from_cache))
+      })
       .collect::<HashMap<_, _>>();
-    let artifact = artifact.map(JsSourceArtifacts::from_core);
+    let artifact = artifact.map(|artifacts| {
+      let from_cache = artifacts
+        .source_path
+        .as_deref()
+        .is_some_and(|path| reused.contains(path));
+      JsSourceArtifacts::from_core(artifacts, from_cache)
+    });
 
     Self {
       json,
+      hardhat_json,
+      minimal_json,
+      truffle_json,
       raw_artifacts,
       artifacts,
       artifact,
+      dirty_paths,
+      reused_paths,
       errors,
+      all_errors,
       has_compiler_errors,
+      ids,
+      contracts_by_id,
+      version_resolution: version_resolution.into_iter().collect(),
     }
   }
 }
@@ -805,11 +1567,20 @@ impl JsCompileOutput {
   pub fn new() -> Self {
     Self {
       json: CompileOutputJson::default(),
+      hardhat_json: Value::Object(Map::new()),
+      minimal_json: Value::Object(Map::new()),
+      truffle_json: Value::Object(Map::new()),
       raw_artifacts: Value::Null,
       artifacts: HashMap::new(),
       artifact: None,
       errors: Vec::new(),
+      all_errors: Vec::new(),
       has_compiler_errors: false,
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      ids: Vec::new(),
+      contracts_by_id: HashMap::new(),
+      version_resolution: HashMap::new(),
     }
   }
 
@@ -848,45 +1619,326 @@ impl JsCompileOutput {
     }
   }
 
+  /// Keys recompiled since the last incremental-cache-backed compile, or `undefined` when the
+  /// incremental cache was not used.
+  #[napi(getter, ts_return_type = "ReadonlyArray<string> | undefined")]
+  pub fn dirty_paths(&self) -> Option<Vec<String>> {
+    if self.dirty_paths.is_empty() {
+      None
+    } else {
+      Some(self.dirty_paths.clone())
+    }
+  }
+
+  /// Keys reused from the incremental cache because neither their content nor the resolved config
+  /// changed, or `undefined` when the incremental cache was not used.
+  #[napi(getter, ts_return_type = "ReadonlyArray<string> | undefined")]
+  pub fn reused_paths(&self) -> Option<Vec<String>> {
+    if self.reused_paths.is_empty() {
+      None
+    } else {
+      Some(self.reused_paths.clone())
+    }
+  }
+
   /// Full diagnostic list regardless of severity. Useful for editor integrations.
   #[napi(getter)]
   pub fn diagnostics(&self) -> Vec<CompilerError> {
     self.errors.clone()
   }
 
+  /// Every diagnostic the compiler reported, unaffected by `ignoredErrorCodes`,
+  /// `compilerSeverityFilter`, `diagnosticPathDenylist`/`diagnosticPathAllowlist`, or
+  /// `severityOverrides`. Compare against [`Self::diagnostics`] to see what those options
+  /// suppressed or rewrote.
+  #[napi(getter)]
+  pub fn all_errors(&self) -> Vec<CompilerError> {
+    self.all_errors.clone()
+  }
+
+  /// Whether any source in this compile output was served from the incremental cache rather than
+  /// freshly compiled. Equivalent to checking whether `reusedPaths` is non-empty, but reads
+  /// better at a call site that only cares about a yes/no answer.
+  #[napi(getter, js_name = "fromCache")]
+  pub fn from_cache(&self) -> bool {
+    !self.reused_paths.is_empty()
+  }
+
   /// Return whether the compile output contains any errors.
   #[napi]
   pub fn has_compiler_errors(&self) -> bool {
     self.has_compiler_errors
   }
 
-  /// Serialise the compile output as JSON for transport or persistence.
-  #[napi(js_name = "toJson", ts_return_type = "CompileOutputJson")]
-  pub fn to_json(&self) -> CompileOutputJson {
-    self.json.clone()
+  /// Serialise the compile output as JSON for transport or persistence. `format` selects the
+  /// artifact shape: omitted or `"native"` returns the usual [`CompileOutputJson`] snapshot;
+  /// `"hardhat"` re-projects every contract into Hardhat's flat artifact schema (see
+  /// [`CompileOutput::to_hardhat_artifacts`]); `"minimal"` returns just `{ abi, bytecode }` per
+  /// contract (see [`CompileOutput::to_minimal_artifacts`]); `"truffle"` re-projects into
+  /// Truffle's flat contract-object schema (see [`CompileOutput::to_truffle_artifacts`]). All
+  /// non-native formats are precomputed at construction time, so requesting them never re-derives
+  /// artifacts.
+  #[napi(
+    js_name = "toJson",
+    ts_return_type = "CompileOutputJson | Record<string, unknown>"
+  )]
+  pub fn to_json(&self, env: Env, format: Option<String>) -> napi::Result<JsUnknown> {
+    let value = match format.as_deref() {
+      None | Some("native") => env.to_js_value(&self.json),
+      Some("hardhat") => env.to_js_value(&self.hardhat_json),
+      Some("minimal") => env.to_js_value(&self.minimal_json),
+      Some("truffle") => env.to_js_value(&self.truffle_json),
+      Some(other) => {
+        return Err(napi_error(format!(
+          "Unknown artifact format `{other}`; expected \"native\", \"hardhat\", \"minimal\", or \"truffle\"."
+        )))
+      }
+    };
+    value.map_err(|err| napi_error(err.to_string()))
   }
-}
 
-pub fn into_js_compile_output(core: CompileOutput) -> JsCompileOutput {
-  JsCompileOutput::from_core(core)
-}
+  /// Every compiled contract's [`ArtifactId`](JsArtifactId), for round-tripping a precise
+  /// artifact reference (source path, name, and compiler version) -- e.g. in a deployment
+  /// manifest -- instead of guessing from a path string.
+  #[napi(getter, ts_return_type = "ArtifactId[]")]
+  pub fn ids(&self) -> Vec<JsArtifactId> {
+    self.ids.clone()
+  }
 
-// -----------------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------------
+  /// Looks up a contract by its precise [`ArtifactId`](JsArtifactId). Returns `undefined` if no
+  /// compiled contract resolves to a matching id.
+  #[napi(js_name = "artifactById", ts_return_type = "Contract | undefined")]
+  pub fn artifact_by_id(&self, id: JsArtifactId) -> napi::Result<Option<JsContract>> {
+    let id = ArtifactId::try_from(id)?;
+    Ok(
+      self
+        .contracts_by_id
+        .get(&artifact_id_key(&id))
+        .map(contract::contract_class),
+    )
+  }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use foundry_compilers::artifacts::CompilerOutput as StandardCompilerOutput;
-  use foundry_compilers::artifacts::SourceFile;
-  use serde_json::json;
-  use std::path::PathBuf;
+  /// The solc version resolved for each compiled source path, keyed by path. Lets callers audit
+  /// pragma-driven version selection, e.g. confirming a multi-version workspace split the way
+  /// they expected.
+  #[napi(getter, js_name = "versionResolution", ts_return_type = "Record<string, string>")]
+  pub fn version_resolution(&self) -> HashMap<String, String> {
+    self
+      .version_resolution
+      .iter()
+      .map(|(path, version)| (path.clone(), version.to_string()))
+      .collect()
+  }
 
-  #[test]
-  fn from_standard_json_populates_contracts_map() {
-    let json = r#"{
-      "contracts": {
+  /// Renders every diagnostic in [`Self::diagnostics`] into a Rust-style code frame: a header
+  /// line naming the severity/type/code, the offending source line(s) underlined with `^~~~`
+  /// (plus `options.contextLines` lines of surrounding context, default `1`), and an indented
+  /// frame per secondary location. `sources` should map each diagnostic's `source_location.file`
+  /// to its full text; a diagnostic whose file is missing from `sources` falls back to its
+  /// `formatted_message`. Entry `i` of the result corresponds to `this.diagnostics[i]`.
+  #[napi(js_name = "formatDiagnostics")]
+  pub fn format_diagnostics(
+    &self,
+    sources: HashMap<String, String>,
+    options: Option<diagnostics::FormatDiagnosticsOptions>,
+  ) -> Vec<String> {
+    diagnostics::format_diagnostics(&self.errors, &sources, options)
+  }
+
+  /// Diagnostic counts grouped by severity, e.g. for a status-bar summary that doesn't want to
+  /// walk the full [`Self::diagnostics`] list itself.
+  #[napi(getter, js_name = "diagnosticSummary")]
+  pub fn diagnostic_summary(&self) -> diagnostics::DiagnosticSummary {
+    diagnostics::diagnostic_summary(&self.errors)
+  }
+
+  /// Resolves every contract's decoded `creationSourceMap`/`deployedSourceMap` (see
+  /// `CompilerConfigOptions.sourceMap`) from an opaque byte-offset/file-index pair into a file
+  /// path and 1-based line/column, the way `CompilerConfigOptions.inlineSourceMap` upgrades a
+  /// plain emitted map into something a debugger can render directly. `sources` should map each
+  /// compiled source path to its full text, the same convention [`Self::format_diagnostics`]
+  /// uses, since this crate doesn't retain source text of its own once a compile has run. Pass
+  /// `{ inlineSources: true }` (mirroring `CompilerConfigOptions.inlineSources`) to additionally
+  /// bundle the matching entries from `sources` onto each contract's result, so downstream
+  /// tooling doesn't need to keep the originals around separately. Contracts with no decoded
+  /// source map of either kind are omitted.
+  #[napi(js_name = "sourceMaps", ts_return_type = "Record<string, ContractSourceMaps>")]
+  pub fn source_maps(
+    &self,
+    sources: HashMap<String, String>,
+    options: Option<SourceMapOptions>,
+  ) -> HashMap<String, ContractSourceMaps> {
+    let inline_sources = options.and_then(|options| options.inline_sources).unwrap_or(false);
+    let file_paths = source_file_indices(&self.raw_artifacts);
+
+    self
+      .contracts_by_id
+      .iter()
+      .filter_map(|(id, contract)| {
+        let state = contract.state();
+        let creation = state
+          .creation_source_map_decoded
+          .as_ref()
+          .map(|entries| resolve_source_map_entries(entries, &file_paths, &sources));
+        let deployed = state
+          .deployed_source_map_decoded
+          .as_ref()
+          .map(|entries| resolve_source_map_entries(entries, &file_paths, &sources));
+        if creation.is_none() && deployed.is_none() {
+          return None;
+        }
+
+        let inline_sources = inline_sources.then(|| {
+          referenced_files(creation.as_deref(), deployed.as_deref())
+            .into_iter()
+            .filter_map(|file| sources.get(&file).map(|text| (file, text.clone())))
+            .collect()
+        });
+
+        Some((
+          id.clone(),
+          ContractSourceMaps {
+            creation,
+            deployed,
+            inline_sources,
+          },
+        ))
+      })
+      .collect()
+  }
+}
+
+/// Options for [`JsCompileOutput::source_maps`].
+#[napi(object)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SourceMapOptions {
+  /// When `true`, bundles the original source text of every file a contract's source map
+  /// references onto [`ContractSourceMaps::inline_sources`]. Mirrors
+  /// `CompilerConfigOptions.inlineSources`. Defaults to `false`.
+  pub inline_sources: Option<bool>,
+}
+
+/// A [`SourceMapEntry`] enriched with the file path and 1-based line/column its byte offset
+/// resolves to, turning the raw offset/file-index pair Solc emits into something a debugger or
+/// coverage tool can render directly. `line`/`column` are `None` when `file` is `None` (no source,
+/// i.e. compiler-generated code) or the caller's `sources` didn't include that file's text.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecodedSourceMapEntry {
+  /// Source file this entry's span resolves to, or `None` for compiler-generated code.
+  pub file: Option<String>,
+  /// Byte offset into `file` where the instruction's source range begins.
+  pub start: u32,
+  /// Byte length of the instruction's source range.
+  pub length: u32,
+  /// 1-based line number within `file`.
+  pub line: Option<u32>,
+  /// 1-based column within `line`.
+  pub column: Option<u32>,
+  /// Jump kind for this instruction.
+  pub jump: JumpType,
+  /// Depth of the modifier stack active at this instruction.
+  pub modifier_depth: u32,
+}
+
+/// Per-contract decoded source maps, as returned by [`JsCompileOutput::source_maps`].
+#[napi(object)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ContractSourceMaps {
+  /// Decoded, file/line/column-resolved counterpart to `ContractState.creationSourceMap`.
+  pub creation: Option<Vec<DecodedSourceMapEntry>>,
+  /// Decoded, file/line/column-resolved counterpart to `ContractState.deployedSourceMap`.
+  pub deployed: Option<Vec<DecodedSourceMapEntry>>,
+  /// Original source text for every file referenced by `creation`/`deployed`, present only when
+  /// `SourceMapOptions.inlineSources` was requested and the caller's `sources` covered that file.
+  pub inline_sources: Option<HashMap<String, String>>,
+}
+
+/// Maps each source's compiler-assigned index (the `f`/file-index field of a compact source map)
+/// to its canonical path, read from the `sources` section of the raw standard-JSON artifact tree.
+fn source_file_indices(raw_artifacts: &Value) -> BTreeMap<i32, String> {
+  raw_artifacts
+    .get("sources")
+    .and_then(Value::as_object)
+    .map(|sources| {
+      sources
+        .iter()
+        .filter_map(|(path, entry)| {
+          let id = entry.get("id")?.as_i64()?;
+          Some((id as i32, path.clone()))
+        })
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Resolves every decoded [`SourceMapEntry`] in `entries` to a [`DecodedSourceMapEntry`], using
+/// `file_paths` to turn a file index into a path and `sources` to turn a byte offset within that
+/// file into a 1-based line/column.
+fn resolve_source_map_entries(
+  entries: &[SourceMapEntry],
+  file_paths: &BTreeMap<i32, String>,
+  sources: &HashMap<String, String>,
+) -> Vec<DecodedSourceMapEntry> {
+  entries
+    .iter()
+    .map(|entry| {
+      let file = file_paths.get(&entry.file_index).cloned();
+      let (line, column) = file
+        .as_ref()
+        .and_then(|file| sources.get(file))
+        .map(|text| {
+          let location = diagnostics::locate_byte_offset(text, entry.start as i32);
+          (Some(location.line as u32), Some(location.column as u32))
+        })
+        .unwrap_or((None, None));
+
+      DecodedSourceMapEntry {
+        file,
+        start: entry.start,
+        length: entry.length,
+        line,
+        column,
+        jump: entry.jump,
+        modifier_depth: entry.modifier_depth,
+      }
+    })
+    .collect()
+}
+
+/// Every distinct file referenced across `creation` and `deployed`.
+fn referenced_files(
+  creation: Option<&[DecodedSourceMapEntry]>,
+  deployed: Option<&[DecodedSourceMapEntry]>,
+) -> std::collections::BTreeSet<String> {
+  creation
+    .into_iter()
+    .chain(deployed)
+    .flatten()
+    .filter_map(|entry| entry.file.clone())
+    .collect()
+}
+
+pub fn into_js_compile_output(core: CompileOutput) -> JsCompileOutput {
+  JsCompileOutput::from_core(core)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use foundry_compilers::artifacts::CompilerOutput as StandardCompilerOutput;
+  use foundry_compilers::artifacts::SourceFile;
+  use serde_json::json;
+  use std::path::PathBuf;
+
+  #[test]
+  fn from_standard_json_populates_contracts_map() {
+    let json = r#"{
+      "contracts": {
         "Test.sol": {
           "Test": {
             "abi": [],
@@ -939,6 +1991,271 @@ mod tests {
     assert_eq!(error.error_code, Some(42));
   }
 
+  #[test]
+  fn to_hardhat_artifacts_reprojects_contracts_by_source_and_name() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": { "object": "0x6000" },
+              "deployedBytecode": { "bytecode": { "object": "0x6001" }, "immutableReferences": {} }
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {},
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output);
+
+    let hardhat = core.to_hardhat_artifacts();
+    let artifact = hardhat.get("Test.sol:Test").expect("hardhat artifact");
+    assert_eq!(artifact["contractName"], "Test");
+    assert_eq!(artifact["sourceName"], "Test.sol");
+    assert_eq!(artifact["bytecode"], "0x6000");
+    assert_eq!(artifact["deployedBytecode"], "0x6001");
+  }
+
+  #[test]
+  fn to_minimal_artifacts_keeps_only_abi_and_bytecode() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": { "object": "0x6000" },
+              "deployedBytecode": { "bytecode": { "object": "0x6001" }, "immutableReferences": {} }
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {},
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output);
+
+    let minimal = core.to_minimal_artifacts();
+    let artifact = minimal.get("Test.sol:Test").expect("minimal artifact");
+    assert_eq!(artifact["bytecode"], "0x6000");
+    assert!(artifact.get("deployedBytecode").is_none());
+    assert!(artifact.get("contractName").is_none());
+  }
+
+  #[test]
+  fn to_truffle_artifacts_reprojects_contracts_by_source_and_name() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": { "object": "0x6000" },
+              "deployedBytecode": { "bytecode": { "object": "0x6001" }, "immutableReferences": {} }
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {},
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output);
+
+    let truffle = core.to_truffle_artifacts();
+    let artifact = truffle.get("Test.sol:Test").expect("truffle artifact");
+    assert_eq!(artifact["contractName"], "Test");
+    assert_eq!(artifact["bytecode"], "0x6000");
+    assert_eq!(artifact["deployedBytecode"], "0x6001");
+    assert_eq!(artifact["networks"], json!({}));
+  }
+
+  fn contract_with_unresolved_library(name: &str) -> Contract {
+    let json = r#"{
+      "abi": [{"type": "function", "name": "greet"}],
+      "evm": {
+        "bytecode": {
+          "object": "6000__$1234567890abcdef1234567890abcdef12$__00",
+          "linkReferences": {
+            "src/Lib.sol": { "Lib": [{ "start": 2, "length": 20 }] }
+          }
+        },
+        "deployedBytecode": {
+          "bytecode": { "object": "0x6001" }
+        }
+      }
+    }"#;
+    let foundry_contract: FoundryContract = serde_json::from_str(json).expect("contract");
+    Contract::from_foundry_standard_json_with_ast(
+      name.to_string(),
+      &foundry_contract,
+      None,
+      ArtifactFieldSelection::ALL,
+    )
+  }
+
+  fn source_artifacts_with(name: &str, contract: Contract) -> JsSourceArtifacts {
+    JsSourceArtifacts::from_core(
+      SourceArtifacts {
+        source_path: Some("src/Consumer.sol".to_string()),
+        source_id: None,
+        solc_version: None,
+        ast: None,
+        contracts: BTreeMap::from([(name.to_string(), contract)]),
+        versioned_contracts: BTreeMap::new(),
+      },
+      false,
+    )
+  }
+
+  #[test]
+  fn source_artifacts_abi_and_bytecode_getters_read_through_to_contract_state() {
+    let artifacts = source_artifacts_with("Consumer", contract_with_unresolved_library("Consumer"));
+
+    let abi = artifacts.abi();
+    assert_eq!(abi.get("Consumer").expect("abi")[0]["name"], "greet");
+
+    let creation = artifacts.creation_bytecode();
+    assert_eq!(
+      creation.get("Consumer").expect("creation bytecode"),
+      "6000__$1234567890abcdef1234567890abcdef12$__00"
+    );
+
+    let deployed = artifacts.deployed_bytecode();
+    assert_eq!(deployed.get("Consumer").expect("deployed bytecode"), "0x6001");
+  }
+
+  #[test]
+  fn source_artifacts_link_resolves_every_contract_against_supplied_addresses() {
+    let artifacts = source_artifacts_with("Consumer", contract_with_unresolved_library("Consumer"));
+
+    let libraries = HashMap::from([(
+      "Lib".to_string(),
+      "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+    )]);
+    let linked = artifacts.link(libraries).expect("link");
+    assert!(linked.contains_key("Consumer"));
+  }
+
+  #[test]
+  fn source_artifacts_link_reports_missing_libraries_instead_of_malformed_hex() {
+    let artifacts = source_artifacts_with("Consumer", contract_with_unresolved_library("Consumer"));
+
+    let err = artifacts.link(HashMap::new()).unwrap_err();
+    assert!(err.to_string().contains("Lib"));
+    assert!(err.to_string().contains("src/Lib.sol"));
+  }
+
+  #[test]
+  fn artifact_output_implementors_agree_with_their_compile_output_methods() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": { "object": "0x6000" },
+              "deployedBytecode": {}
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {},
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output);
+
+    assert_eq!(
+      NativeArtifactOutput::format(&core),
+      serde_json::to_value(core.to_json()).unwrap()
+    );
+    assert_eq!(
+      HardhatArtifactOutput::format(&core),
+      serde_json::to_value(core.to_hardhat_artifacts()).unwrap()
+    );
+    assert_eq!(
+      MinimalArtifactOutput::format(&core),
+      serde_json::to_value(core.to_minimal_artifacts()).unwrap()
+    );
+    assert_eq!(
+      TruffleArtifactOutput::format(&core),
+      serde_json::to_value(core.to_truffle_artifacts()).unwrap()
+    );
+  }
+
+  fn contract_with_id(name: &str, source_path: &str, version: &str) -> Contract {
+    let json = r#"{
+      "abi": [],
+      "evm": {
+        "bytecode": { "object": "0x" },
+        "deployedBytecode": {}
+      }
+    }"#;
+    let foundry_contract: FoundryContract = serde_json::from_str(json).expect("contract");
+    let mut contract = Contract::from_foundry_standard_json_with_ast(
+      name.to_string(),
+      &foundry_contract,
+      None,
+      ArtifactFieldSelection::ALL,
+    );
+    contract.state_mut().source_path = Some(source_path.to_string());
+    contract.state_mut().version = Some(Version::parse(version).expect("version"));
+    contract
+  }
+
+  #[test]
+  fn ids_and_artifact_by_id_disambiguate_same_name_across_versions() {
+    let mut v1 = SourceArtifacts::new(Some("src/Token.sol".to_string()));
+    v1.contracts.insert(
+      "Token".to_string(),
+      contract_with_id("Token", "src/Token.sol", "0.8.19"),
+    );
+    let mut v2 = SourceArtifacts::new(Some("src/TokenV2.sol".to_string()));
+    v2.contracts.insert(
+      "Token".to_string(),
+      contract_with_id("Token", "src/TokenV2.sol", "0.8.20"),
+    );
+
+    let core = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::from([
+        ("src/Token.sol".to_string(), v1),
+        ("src/TokenV2.sol".to_string(), v2),
+      ]),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    let ids: Vec<ArtifactId> = core.ids().collect();
+    assert_eq!(ids.len(), 2);
+
+    let target = ArtifactId {
+      source_path: "src/TokenV2.sol".to_string(),
+      name: "Token".to_string(),
+      version: Version::parse("0.8.20").unwrap(),
+    };
+    let found = core.artifact_by_id(&target).expect("contract by id");
+    assert_eq!(found.state().source_path.as_deref(), Some("src/TokenV2.sol"));
+  }
+
   #[test]
   fn from_standard_json_captures_ast_when_present() {
     use foundry_compilers::artifacts::ast::Ast;
@@ -1022,6 +2339,62 @@ mod tests {
     assert_eq!(error.error_code, Some(256));
   }
 
+  #[test]
+  fn version_resolution_error_output_carries_a_single_version_resolution_diagnostic() {
+    let output = version_resolution_error_output(">=99.0.0");
+    assert!(output.artifacts.is_empty());
+    assert!(output.artifact.is_none());
+    assert!(output.version_resolution.is_empty());
+    assert_eq!(output.errors.len(), 1);
+    let error = &output.errors[0];
+    assert_eq!(error.component, "version-resolution");
+    assert_eq!(error.severity, SeverityLevel::Error);
+    assert!(error.message.contains(">=99.0.0"));
+  }
+
+  #[test]
+  fn merge_compile_outputs_by_version_tags_errors_and_rekeys_colliding_paths() {
+    let v1 = Version::new(0, 8, 19);
+    let v2 = Version::new(0, 8, 24);
+
+    let mut first = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::new(),
+      artifact: None,
+      errors: vec![CompilerError {
+        message: "detail".into(),
+        formatted_message: None,
+        component: "general".into(),
+        severity: SeverityLevel::Warning,
+        error_type: "Warning".into(),
+        error_code: None,
+        source_location: None,
+        secondary_source_locations: None,
+        vyper_source_location: None,
+        solc_version: None,
+      }],
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+    first
+      .artifacts
+      .insert("Shared.sol".to_string(), SourceArtifacts::default());
+
+    let mut second = first.clone();
+    second.errors[0].message = "other detail".into();
+
+    let merged = merge_compile_outputs_by_version(vec![(v1.clone(), first), (v2.clone(), second)]);
+
+    assert_eq!(merged.errors.len(), 2);
+    assert_eq!(merged.errors[0].solc_version.as_deref(), Some("0.8.19"));
+    assert_eq!(merged.errors[1].solc_version.as_deref(), Some("0.8.24"));
+    assert!(merged.artifacts.contains_key("Shared.sol"));
+    assert!(merged.artifacts.contains_key("Shared.sol@0.8.24"));
+  }
+
   #[test]
   fn into_js_compile_output_preserves_contracts_and_errors() {
     let mut core = CompileOutput {
@@ -1047,7 +2420,13 @@ mod tests {
           message: Some("secondary".into()),
         }]),
         vyper_source_location: None,
+        solc_version: None,
       }],
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
     };
 
     let mut artifacts = SourceArtifacts::default();
@@ -1062,7 +2441,7 @@ mod tests {
       .get("Widget.sol")
       .and_then(|entry| entry.contracts.get("Widget"))
       .is_some());
-    let snapshot = js_output.to_json();
+    let snapshot = &js_output.json;
     assert!(snapshot
       .artifacts
       .as_ref()
@@ -1082,4 +2461,223 @@ mod tests {
       Some("Test.sol")
     );
   }
+
+  #[test]
+  fn into_js_compile_output_flags_reused_artifacts_as_from_cache() {
+    let mut core = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::new(),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: vec!["Dirty.sol".into()],
+      reused_paths: vec!["Cached.sol".into()],
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+    core
+      .artifacts
+      .insert("Cached.sol".into(), SourceArtifacts::default());
+    core
+      .artifacts
+      .insert("Dirty.sol".into(), SourceArtifacts::default());
+
+    let js_output = into_js_compile_output(core);
+    assert!(js_output.from_cache());
+    assert!(js_output.artifacts.get("Cached.sol").unwrap().from_cache());
+    assert!(!js_output.artifacts.get("Dirty.sol").unwrap().from_cache());
+  }
+
+  #[test]
+  fn into_js_compile_output_precomputes_hardhat_minimal_and_truffle_projections() {
+    let mut core = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::new(),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+    let mut artifacts = SourceArtifacts::new(Some("Widget.sol".into()));
+    artifacts
+      .contracts
+      .insert("Widget".into(), Contract::new("Widget"));
+    core.artifacts.insert("Widget.sol".into(), artifacts);
+
+    let expected_hardhat = serde_json::to_value(core.to_hardhat_artifacts()).unwrap();
+    let expected_minimal = serde_json::to_value(core.to_minimal_artifacts()).unwrap();
+    let expected_truffle = serde_json::to_value(core.to_truffle_artifacts()).unwrap();
+    let js_output = into_js_compile_output(core);
+
+    assert_eq!(js_output.hardhat_json, expected_hardhat);
+    assert_eq!(js_output.minimal_json, expected_minimal);
+    assert_eq!(js_output.truffle_json, expected_truffle);
+  }
+
+  #[test]
+  fn has_compiler_errors_ignores_warnings_by_default() {
+    let output = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::new(),
+      artifact: None,
+      errors: vec![CompilerError {
+        message: "detail".into(),
+        formatted_message: None,
+        component: "general".into(),
+        severity: SeverityLevel::Warning,
+        error_type: "Warning".into(),
+        error_code: Some(1),
+        source_location: None,
+        secondary_source_locations: None,
+        vyper_source_location: None,
+        solc_version: None,
+      }],
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    assert!(!output.has_compiler_errors());
+  }
+
+  #[test]
+  fn has_compiler_errors_counts_warnings_when_deny_warnings_is_set() {
+    let mut output = CompileOutput {
+      raw_artifacts: Value::Null,
+      artifacts: BTreeMap::new(),
+      artifact: None,
+      errors: vec![CompilerError {
+        message: "detail".into(),
+        formatted_message: None,
+        component: "general".into(),
+        severity: SeverityLevel::Warning,
+        error_type: "Warning".into(),
+        error_code: Some(1),
+        source_location: None,
+        secondary_source_locations: None,
+        vyper_source_location: None,
+        solc_version: None,
+      }],
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    output.deny_warnings = true;
+    assert!(output.has_compiler_errors());
+    assert_eq!(output.errors[0].severity, SeverityLevel::Warning);
+  }
+
+  fn contract_with_source_map(
+    name: &str,
+    source_path: &str,
+    creation_map: Option<&str>,
+    deployed_map: Option<&str>,
+  ) -> Contract {
+    let mut contract = contract_with_id(name, source_path, "0.8.20");
+    contract.state_mut().creation_source_map_decoded =
+      creation_map.map(crate::contract::decode_source_map);
+    contract.state_mut().deployed_source_map_decoded =
+      deployed_map.map(crate::contract::decode_source_map);
+    contract
+  }
+
+  #[test]
+  fn source_maps_resolves_file_and_line_column_from_decoded_entries() {
+    let path = "src/Token.sol";
+    let text = "contract Token {\n  function f() public {}\n}\n";
+    let contract = contract_with_source_map("Token", path, Some("0:8:0:-:0"), None);
+
+    let mut artifacts = SourceArtifacts::new(Some(path.to_string()));
+    artifacts.contracts.insert("Token".to_string(), contract);
+    let core = CompileOutput {
+      raw_artifacts: json!({ "sources": { path: { "id": 0 } } }),
+      artifacts: BTreeMap::from([(path.to_string(), artifacts)]),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    let js_output = into_js_compile_output(core);
+    let sources = HashMap::from([(path.to_string(), text.to_string())]);
+    let maps = js_output.source_maps(sources, None);
+    let (_, contract_maps) = maps.into_iter().next().expect("one contract");
+
+    let entries = contract_maps.creation.expect("creation source map");
+    assert_eq!(entries[0].file.as_deref(), Some(path));
+    assert_eq!(entries[0].line, Some(1));
+    assert_eq!(entries[0].column, Some(1));
+    assert!(contract_maps.deployed.is_none());
+    assert!(contract_maps.inline_sources.is_none());
+  }
+
+  #[test]
+  fn source_maps_omits_contracts_without_a_decoded_source_map() {
+    let path = "src/Plain.sol";
+    let contract = contract_with_source_map("Plain", path, None, None);
+
+    let mut artifacts = SourceArtifacts::new(Some(path.to_string()));
+    artifacts.contracts.insert("Plain".to_string(), contract);
+    let core = CompileOutput {
+      raw_artifacts: json!({ "sources": { path: { "id": 0 } } }),
+      artifacts: BTreeMap::from([(path.to_string(), artifacts)]),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    let js_output = into_js_compile_output(core);
+    let maps = js_output.source_maps(HashMap::new(), None);
+    assert!(maps.is_empty());
+  }
+
+  #[test]
+  fn source_maps_bundles_inline_sources_when_requested() {
+    let path = "src/Token.sol";
+    let text = "contract Token {}\n";
+    let contract = contract_with_source_map("Token", path, Some("0:8:0:-:0"), None);
+
+    let mut artifacts = SourceArtifacts::new(Some(path.to_string()));
+    artifacts.contracts.insert("Token".to_string(), contract);
+    let core = CompileOutput {
+      raw_artifacts: json!({ "sources": { path: { "id": 0 } } }),
+      artifacts: BTreeMap::from([(path.to_string(), artifacts)]),
+      artifact: None,
+      errors: Vec::new(),
+      all_errors: Vec::new(),
+      dirty_paths: Vec::new(),
+      reused_paths: Vec::new(),
+      deny_warnings: false,
+      version_resolution: BTreeMap::new(),
+    };
+
+    let js_output = into_js_compile_output(core);
+    let sources = HashMap::from([(path.to_string(), text.to_string())]);
+    let maps = js_output.source_maps(
+      sources,
+      Some(SourceMapOptions {
+        inline_sources: Some(true),
+      }),
+    );
+    let (_, contract_maps) = maps.into_iter().next().expect("one contract");
+    assert_eq!(
+      contract_maps.inline_sources.unwrap().get(path).map(String::as_str),
+      Some(text)
+    );
+  }
 }
@@ -1,10 +1,22 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Instant;
 
+use semver::Version;
+
+use super::core;
 use super::input::CompilationInput;
-use super::output::{into_core_compile_output, CompileOutput};
-use crate::internal::config::CompilerLanguage;
+use super::output::{
+  collate_project_artifacts, into_core_compile_output_with_selection, merge_compile_outputs,
+  merge_compile_outputs_by_version, CompileOutput, SourceArtifacts,
+};
+use crate::internal::artifact_output;
+use crate::internal::config::{ArtifactOutputFormat, CompilerLanguage};
+use crate::internal::flatten;
+use crate::internal::graph;
+use crate::internal::incremental_cache::{self, CachedArtifact, DirtyReport};
 use crate::internal::path::canonicalize_path;
 use crate::internal::vyper;
 use crate::internal::{
@@ -15,10 +27,12 @@ use crate::internal::{
   },
   solc,
 };
+use foundry_compilers::artifacts::remappings::Remapping;
 use foundry_compilers::artifacts::sources::Source as FoundrySource;
 use foundry_compilers::compilers::multi::MultiCompiler;
 use foundry_compilers::{Project, ProjectCompileOutput};
 use log::{error, info};
+use serde_json::Value;
 
 struct VirtualSourceEntry<'a> {
   original_path: Option<&'a str>,
@@ -51,7 +65,7 @@ impl<'a> ProjectRunner<'a> {
             target: LOG_TARGET,
             "materialising inline source for synthetic project cache"
           );
-          let mut paths = self.write_virtual_sources(
+          let (mut paths, dirty_report) = self.write_virtual_sources(
             config,
             [VirtualSourceEntry {
               original_path: None,
@@ -62,10 +76,23 @@ impl<'a> ProjectRunner<'a> {
           let path = paths
             .pop()
             .ok_or_else(|| Error::new("Failed to prepare virtual source for inline compilation"))?;
+          let source_hash = FoundrySource::content_hash_of(source);
+          let path_for_cache = path.clone();
           let output = self.compile_with_project(config, "Compilation failed", |project| {
             project.compile_file(path)
           });
-          output.map(|out| Some(into_core_compile_output(out)))
+          output.map(|out| {
+            let core_output = apply_dirty_report(
+              into_core_compile_output_with_selection(out, config.artifact_field_selection),
+              dirty_report,
+            );
+            self.store_fresh_artifacts(
+              config,
+              &[(source_hash, source.clone(), path_for_cache)],
+              &core_output,
+            );
+            Some(core_output)
+          })
         } else {
           info!(
             target: LOG_TARGET,
@@ -93,7 +120,12 @@ impl<'a> ProjectRunner<'a> {
         let output = self.compile_with_project(config, "Compilation failed", |project| {
           project.compile_files(normalized)
         });
-        output.map(|out| Some(into_core_compile_output(out)))
+        output.map(|out| {
+          Some(into_core_compile_output_with_selection(
+            out,
+            config.artifact_field_selection,
+          ))
+        })
       }
       CompilationInput::SourceMap {
         sources,
@@ -106,7 +138,14 @@ impl<'a> ProjectRunner<'a> {
             sources.len(),
             language_override
           );
-          let files = self.write_virtual_sources(
+          let effective_language = language_override.unwrap_or(config.language);
+          if config.auto_detect_version && matches!(effective_language, CompilerLanguage::Solidity) {
+            return self
+              .compile_source_map_with_version_buckets(config, sources, *language_override)
+              .map(Some);
+          }
+
+          let (files, dirty_report) = self.write_virtual_sources(
             config,
             sources.iter().map(|(path, contents)| VirtualSourceEntry {
               original_path: Some(path.as_str()),
@@ -114,10 +153,22 @@ impl<'a> ProjectRunner<'a> {
             }),
             *language_override,
           )?;
+          let cache_entries: Vec<(String, String, PathBuf)> = sources
+            .iter()
+            .zip(files.iter())
+            .map(|((path, contents), file)| (path.clone(), contents.clone(), file.clone()))
+            .collect();
           let output = self.compile_with_project(config, "Compilation failed", move |project| {
             project.compile_files(files.clone())
           });
-          output.map(|out| Some(into_core_compile_output(out)))
+          output.map(|out| {
+            let core_output = apply_dirty_report(
+              into_core_compile_output_with_selection(out, config.artifact_field_selection),
+              dirty_report,
+            );
+            self.store_fresh_artifacts(config, &cache_entries, &core_output);
+            Some(core_output)
+          })
         } else {
           info!(
             target: LOG_TARGET,
@@ -135,19 +186,283 @@ impl<'a> ProjectRunner<'a> {
         );
         Ok(None)
       }
+      CompilationInput::MixedSourceMap { .. } => {
+        info!(
+          target: LOG_TARGET,
+          "project runner skipping mixed-language source maps; falling back to the standalone \
+           pipeline to compile each language group separately"
+        );
+        Ok(None)
+      }
     }
   }
 
+  /// Answers `input` directly from the on-disk artifact cache [`Self::compile`] populates, when
+  /// every one of its sources is unchanged since the last compile through this same input shape:
+  /// skips `build_project` and the solc/vyper `ensure_installed` checks entirely, replaying the
+  /// cached `{source, contracts}` JSON fragments back through [`core::reconstruct_cached_output`]
+  /// instead of invoking solc again. A source whose content and fingerprint are both unchanged is
+  /// still treated as dirty if anything in its import-connected component changed (see
+  /// [`incremental_cache::expand_dirty_across_imports`]), matching the expansion
+  /// `compile_solc_sources_incremental` already does for the standalone pipeline. Returns
+  /// `Ok(None)` -- never an error -- when any source is dirty or missing from the cache, so the
+  /// caller falls back to [`Self::compile`]. Also always `Ok(None)` when `config.force_rebuild` is
+  /// `true`, without even consulting the manifest. Only
+  /// `InlineSource` and `SourceMap` inputs populate this cache today (see
+  /// [`Self::store_fresh_artifacts`]); `FilePaths` always misses until that's wired up too, and
+  /// `AstUnits`/`MixedSourceMap` are out of scope the same way they already are in
+  /// [`Self::compile`].
+  pub fn read_cached_output(
+    &self,
+    config: &CompilerConfig,
+    input: &CompilationInput,
+  ) -> Result<Option<CompileOutput>> {
+    if !config.cache_enabled || config.force_rebuild {
+      return Ok(None);
+    }
+
+    let texts: BTreeMap<String, String> = match input {
+      CompilationInput::InlineSource { source } => {
+        BTreeMap::from([(FoundrySource::content_hash_of(source), source.clone())])
+      }
+      CompilationInput::SourceMap { sources, .. } => sources.clone(),
+      CompilationInput::FilePaths { paths, .. } => {
+        let normalized = self.context.normalise_paths(paths.as_slice())?;
+        normalized
+          .into_iter()
+          .filter_map(|path| {
+            let contents = fs::read_to_string(&path).ok()?;
+            Some((path.to_string_lossy().into_owned(), contents))
+          })
+          .collect()
+      }
+      CompilationInput::AstUnits { .. } | CompilationInput::MixedSourceMap { .. } => {
+        return Ok(None)
+      }
+    };
+
+    if texts.is_empty() {
+      return Ok(None);
+    }
+
+    let manifest_path = self.context.incremental_cache_manifest_path();
+    let fingerprint = incremental_cache::config_fingerprint(config);
+    let report = incremental_cache::evaluate_with_artifacts(&manifest_path, &fingerprint, &texts)?;
+    let dirty = incremental_cache::expand_dirty_across_imports(&report.dirty, &texts, &config.remappings);
+
+    if !dirty.is_empty() || report.fresh.len() != texts.len() {
+      return Ok(None);
+    }
+
+    info!(
+      target: LOG_TARGET,
+      "project artifact cache hit: reusing {} cached artifact(s), skipping build_project",
+      report.fresh.len()
+    );
+    let mut output = core::reconstruct_cached_output(config, &report.fresh)?;
+    output.dirty_paths = Vec::new();
+    output.reused_paths = texts.into_keys().collect();
+    Ok(Some(output))
+  }
+
+  /// Persists each `(logical_key, content, virtual_path)` entry's `{source, contracts}` JSON
+  /// fragments (and any diagnostics targeting it) into the same content-hash artifact store
+  /// [`core::reconstruct_cached_output`]'s standalone-pipeline counterpart
+  /// (`compile_solc_sources_incremental`) already uses, keyed by the entry's logical path (a
+  /// source map's own path, or an inline source's content hash) rather than the hashed virtual
+  /// path solc actually saw -- that's what [`Self::read_cached_output`] looks entries up by.
+  /// [`Self::compile_project`] reuses this too, passing the project's own canonical path as both
+  /// the logical key and the virtual path, since a real project compile never rewrites paths
+  /// through a virtual file the way source maps and inline sources do. Best-effort: a failure to
+  /// persist never fails the compile that produced `output`.
+  fn store_fresh_artifacts(
+    &self,
+    config: &CompilerConfig,
+    entries: &[(String, String, PathBuf)],
+    output: &CompileOutput,
+  ) {
+    if !config.cache_enabled || entries.is_empty() {
+      return;
+    }
+
+    let all_errors = output
+      .raw_artifacts
+      .get("errors")
+      .and_then(|errors| errors.as_array())
+      .cloned()
+      .unwrap_or_default();
+
+    let fresh_entries: Vec<(String, String, CachedArtifact)> = entries
+      .iter()
+      .map(|(logical_key, content, virtual_path)| {
+        let virtual_key = virtual_path.to_string_lossy().into_owned();
+        let source = output
+          .raw_artifacts
+          .get("sources")
+          .and_then(|sources| sources.get(&virtual_key))
+          .cloned()
+          .unwrap_or(Value::Null);
+        let contracts = output
+          .raw_artifacts
+          .get("contracts")
+          .and_then(|contracts| contracts.get(&virtual_key))
+          .cloned()
+          .unwrap_or(Value::Null);
+        let errors = core::errors_for_path(&all_errors, &virtual_key);
+        (
+          logical_key.clone(),
+          content.clone(),
+          CachedArtifact {
+            source,
+            contracts,
+            errors,
+          },
+        )
+      })
+      .collect();
+
+    let manifest_path = self.context.incremental_cache_manifest_path();
+    let artifacts_dir = self.context.incremental_cache_artifacts_dir();
+    let fingerprint = incremental_cache::config_fingerprint(config);
+    if let Err(err) =
+      incremental_cache::store_artifacts(&manifest_path, &artifacts_dir, &fingerprint, &fresh_entries)
+    {
+      error!(
+        target: LOG_TARGET,
+        "failed to persist project artifact cache: {err}"
+      );
+    }
+  }
+
+  /// Reads every one of the project's own input files (per [`ProjectContext::paths`]'s
+  /// `input_files`) and checks them against the incremental cache manifest, the same way
+  /// [`Self::read_cached_output`] does for a synthetic source map, but scoped to the real project
+  /// tree rather than a virtual one. Returns the file contents keyed by canonical path alongside
+  /// the raw [`ArtifactCacheReport`] and the dirty set expanded across import-connected
+  /// components (see [`incremental_cache::expand_dirty_across_imports`]), so [`Self::compile_project`]
+  /// can decide between a full cache hit, a partial recompile of just the dirty closure, or a full
+  /// rebuild without re-reading every file twice.
+  fn project_cache_report(
+    &self,
+    config: &CompilerConfig,
+  ) -> Result<(BTreeMap<String, String>, incremental_cache::ArtifactCacheReport, BTreeSet<String>)> {
+    let mut texts: BTreeMap<String, String> = BTreeMap::new();
+    for path in self.context.paths.input_files() {
+      let contents = fs::read_to_string(&path).map_err(|err| {
+        Error::io(format!(
+          "Failed to read {} for the project incremental cache: {err}",
+          path.display()
+        ))
+      })?;
+      texts.insert(path.to_string_lossy().into_owned(), contents);
+    }
+
+    let manifest_path = self.context.incremental_cache_manifest_path();
+    let fingerprint = incremental_cache::config_fingerprint(config);
+    let report = incremental_cache::evaluate_with_artifacts(&manifest_path, &fingerprint, &texts)?;
+    let dirty = incremental_cache::expand_dirty_across_imports(&report.dirty, &texts, &config.remappings);
+    Ok((texts, report, dirty))
+  }
+
+  /// Compiles the full project tree via `project.compile()`, with none of the incremental-cache
+  /// bookkeeping [`Self::compile_project`] layers on top -- used both for its uncached fallback and
+  /// whenever every source in the project is dirty, where a partial recompile wouldn't save
+  /// anything anyway.
+  fn compile_project_fresh(&self, config: &CompilerConfig) -> Result<CompileOutput> {
+    let output = self.compile_with_project(config, "Project compilation failed", |project| {
+      project.compile()
+    })?;
+    Ok(into_core_compile_output_with_selection(
+      output,
+      config.artifact_field_selection,
+    ))
+  }
+
+  /// Compiles the project associated with this context, consulting the incremental cache when
+  /// `config.cache_enabled` is set (and `config.force_rebuild` isn't): a source is dirty if its
+  /// content hash or `config`'s fingerprint (solc version, settings, remappings) changed since the
+  /// last compile, and dirtiness is expanded across each source's import-connected component so an
+  /// importer of a changed file is never treated as unaffected. When nothing is dirty, the cached
+  /// artifacts are replayed back without invoking solc at all; when only some sources are dirty,
+  /// only that closure is recompiled (via `project.compile_files`) and merged with the rest of the
+  /// project's cached artifacts; otherwise the whole project is compiled and every entry refreshed.
   pub fn compile_project(&self, config: &CompilerConfig) -> Result<CompileOutput> {
     info!(
       target: LOG_TARGET,
       "compiling full project (layout={:?})",
       self.context.layout
     );
-    let output = self.compile_with_project(config, "Project compilation failed", |project| {
-      project.compile()
-    });
-    output.map(into_core_compile_output)
+
+    if !config.cache_enabled {
+      return self.compile_project_fresh(config);
+    }
+
+    let (texts, report, dirty) = self.project_cache_report(config)?;
+
+    if !config.force_rebuild && !texts.is_empty() && dirty.is_empty() && report.fresh.len() == texts.len() {
+      info!(
+        target: LOG_TARGET,
+        "project incremental cache hit: reusing {} cached artifact(s), skipping project.compile()",
+        report.fresh.len()
+      );
+      let mut output = core::reconstruct_cached_output(config, &report.fresh)?;
+      output.dirty_paths = Vec::new();
+      output.reused_paths = texts.into_keys().collect();
+      return Ok(output);
+    }
+
+    if !config.force_rebuild && !dirty.is_empty() && dirty.len() < texts.len() {
+      info!(
+        target: LOG_TARGET,
+        "project incremental cache: recompiling {} of {} dirty file(s), reusing the rest",
+        dirty.len(),
+        texts.len()
+      );
+      let dirty_paths: Vec<PathBuf> = dirty.iter().map(PathBuf::from).collect();
+      let dirty_out = self.compile_with_project(config, "Project compilation failed", move |project| {
+        project.compile_files(dirty_paths)
+      })?;
+      let dirty_output = into_core_compile_output_with_selection(dirty_out, config.artifact_field_selection);
+
+      let dirty_entries: Vec<(String, String, PathBuf)> = dirty
+        .iter()
+        .filter_map(|path| {
+          texts
+            .get(path)
+            .map(|content| (path.clone(), content.clone(), PathBuf::from(path.clone())))
+        })
+        .collect();
+      self.store_fresh_artifacts(config, &dirty_entries, &dirty_output);
+
+      let fresh: BTreeMap<String, CachedArtifact> = report
+        .fresh
+        .into_iter()
+        .filter(|(path, _)| !dirty.contains(path))
+        .collect();
+      let mut merged = if fresh.is_empty() {
+        dirty_output
+      } else {
+        let reused_output = core::reconstruct_cached_output(config, &fresh)?;
+        merge_compile_outputs(vec![dirty_output, reused_output])
+      };
+      merged.reused_paths = texts.keys().filter(|path| !dirty.contains(*path)).cloned().collect();
+      merged.dirty_paths = dirty.into_iter().collect();
+      return Ok(merged);
+    }
+
+    info!(
+      target: LOG_TARGET,
+      "project incremental cache: every source is dirty or uncached, recompiling the whole project"
+    );
+    let mut output = self.compile_project_fresh(config)?;
+    let entries: Vec<(String, String, PathBuf)> = texts
+      .iter()
+      .map(|(path, content)| (path.clone(), content.clone(), PathBuf::from(path.clone())))
+      .collect();
+    self.store_fresh_artifacts(config, &entries, &output);
+    output.dirty_paths = texts.into_keys().collect();
+    output.reused_paths = Vec::new();
+    Ok(output)
   }
 
   pub fn compile_contract(
@@ -166,9 +481,148 @@ impl<'a> ProjectRunner<'a> {
       let path = project.find_contract_path(&name)?;
       project.compile_file(path)
     });
-    output.map(into_core_compile_output)
+    output.map(|out| into_core_compile_output_with_selection(out, config.artifact_field_selection))
+  }
+
+  /// Compiles only the subset of the project's source files for which `filter` returns `true`,
+  /// letting callers exclude test, script, or mock files from a full [`compile_project`] run
+  /// without reconfiguring the project's `sources`/`tests`/`script` layout. Mirrors ethers-solc's
+  /// `FileFilter` concept; see [`compile_filtered_by_glob`](Self::compile_filtered_by_glob) for a
+  /// glob-pattern convenience and [`exclude_tests_scripts_and_mocks`] for a ready-made predicate.
+  pub fn compile_filtered<F>(&self, config: &CompilerConfig, filter: F) -> Result<CompileOutput>
+  where
+    F: Fn(&Path) -> bool,
+  {
+    let files: Vec<PathBuf> = self
+      .context
+      .paths
+      .input_files()
+      .into_iter()
+      .filter(|path| filter(path))
+      .collect();
+    info!(
+      target: LOG_TARGET,
+      "compiling filtered project subset ({} of the project's source files, layout={:?})",
+      files.len(),
+      self.context.layout
+    );
+    let output = self.compile_with_project(config, "Filtered compilation failed", move |project| {
+      project.compile_files(files)
+    });
+    output.map(|out| into_core_compile_output_with_selection(out, config.artifact_field_selection))
   }
 
+  /// [`compile_filtered`](Self::compile_filtered) restricted by glob patterns matched against
+  /// each source file's canonicalised path, the same `glob::Pattern` matching
+  /// [`restrictions::matching_restrictions`](crate::internal::restrictions) already uses for
+  /// compiler restrictions. A file is excluded from compilation if any pattern in `exclude_globs`
+  /// matches it.
+  pub fn compile_filtered_by_glob(
+    &self,
+    config: &CompilerConfig,
+    exclude_globs: &[String],
+  ) -> Result<CompileOutput> {
+    let patterns = exclude_globs
+      .iter()
+      .map(|raw| {
+        glob::Pattern::new(raw)
+          .map_err(|err| Error::new(format!("Invalid compile_filtered glob `{raw}`: {err}")))
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    self.compile_filtered(config, move |path| {
+      let path = path.to_string_lossy();
+      !patterns.iter().any(|pattern| pattern.matches(&path))
+    })
+  }
+
+  /// Resolves `entry` (a contract name or a file path) against the project and returns a single
+  /// self-contained Solidity string with every transitively-relative-imported file inlined ahead
+  /// of it, in [`flatten::flatten_sources`]'s dependency order. Doesn't touch solc or write any
+  /// artifacts -- this only reads source files already on disk, so it's safe to call without
+  /// `config.offline_mode` restricting it or a compiler being installed at all.
+  pub fn flatten(&self, config: &CompilerConfig, entry: &str) -> Result<String> {
+    info!(
+      target: LOG_TARGET,
+      "flattening {entry} (layout={:?})",
+      self.context.layout
+    );
+
+    let project = map_err_with_context(
+      build_project(config, self.context),
+      "Failed to configure Solidity project",
+    )?;
+    let entry_path = if Path::new(entry).is_file() {
+      canonicalize_path(Path::new(entry))
+    } else {
+      map_err_with_context(
+        project.find_contract_path(entry),
+        format!("Failed to locate flatten entry `{entry}`"),
+      )?
+    };
+
+    let sources = self.collect_source_closure(&entry_path, &config.remappings)?;
+    let entry_key = entry_path.to_string_lossy().into_owned();
+    flatten::flatten_sources(&sources, &entry_key, &config.remappings)
+  }
+
+  /// Reads `entry_path` and every file it transitively reaches via relative (`./`, `../`) or
+  /// `remappings`-resolved imports from disk into an in-memory source map keyed by canonicalised
+  /// path, the shape [`flatten::flatten_sources`] and the rest of `internal::graph` operate on. A
+  /// bare import that isn't covered by `remappings`, or whose remapped target isn't an existing
+  /// file, is left unresolved (out of scope -- `include_paths`/`library_paths` aren't searched
+  /// here, same as [`graph::resolve_import`]); a relative import that doesn't resolve to an
+  /// existing file is a broken reference and fails loudly.
+  fn collect_source_closure(
+    &self,
+    entry_path: &Path,
+    remappings: &[Remapping],
+  ) -> Result<BTreeMap<String, String>> {
+    let mut sources = BTreeMap::new();
+    let mut queue = VecDeque::from([entry_path.to_path_buf()]);
+
+    while let Some(path) = queue.pop_front() {
+      let key = path.to_string_lossy().into_owned();
+      if sources.contains_key(&key) {
+        continue;
+      }
+
+      let content = map_err_with_context(
+        fs::read_to_string(&path),
+        format!("Failed to read `{}` while flattening", path.display()),
+      )?;
+
+      for import_literal in graph::extract_imports(&content) {
+        if import_literal.starts_with('.') {
+          let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+          let resolved = canonicalize_path(&base_dir.join(&import_literal));
+          if !resolved.is_file() {
+            return Err(Error::new(format!(
+              "`{}` imports `{import_literal}`, which does not resolve to an existing file",
+              path.display()
+            )));
+          }
+          queue.push_back(resolved);
+        } else if let Some(remapped) = graph::resolve_against_remappings(&import_literal, remappings) {
+          let resolved = canonicalize_path(&self.context.root.join(remapped));
+          if resolved.is_file() {
+            queue.push_back(resolved);
+          }
+        }
+      }
+
+      sources.insert(key, content);
+    }
+
+    Ok(sources)
+  }
+
+  /// Drives `compile_fn` against a freshly built [`Project`]. Compiler resolution here never
+  /// touches the network regardless of `config.offline_mode`: `solc::ensure_installed` and
+  /// `vyper::ensure_installed` only ever look up an already-installed binary or an explicitly
+  /// configured path, returning a clear "is not installed" [`Error`] otherwise (see their doc
+  /// comments). `config.offline_mode` itself is threaded into the `Project` builder in
+  /// `build_project`, which is what restricts remapping auto-detection from reaching the network.
   fn compile_with_project<F>(
     &self,
     config: &CompilerConfig,
@@ -194,7 +648,7 @@ impl<'a> ProjectRunner<'a> {
       solc::ensure_installed(&config.solc_version)?;
     } else if config.language == CompilerLanguage::Vyper {
       info!(target: LOG_TARGET, "ensuring vyper compiler for project compilation");
-      vyper::ensure_installed(config.vyper_settings.path.clone())?;
+      vyper::ensure_installed(config.vyper_settings.path.clone(), None)?;
     }
     let project = match map_err_with_context(
       build_project(config, self.context),
@@ -225,6 +679,12 @@ impl<'a> ProjectRunner<'a> {
           "project compilation step succeeded ({label}) in {:?}",
           started.elapsed()
         );
+        if !config.no_artifacts {
+          let format =
+            artifact_output::resolve_format(config.artifact_output, config.artifact_format, &self.context.layout);
+          let artifacts = collate_project_artifacts(&output, config.artifact_field_selection);
+          artifact_output::writer(format).write(&self.context.paths.artifacts, &self.context.paths.build_infos, &artifacts)?;
+        }
         Ok(output)
       }
       Err(err) => {
@@ -238,6 +698,154 @@ impl<'a> ProjectRunner<'a> {
     }
   }
 
+  /// Splits a `SourceMap` input into per-pragma solc version buckets (see
+  /// [`graph::resolve_per_source_version_buckets`]) and compiles each bucket with its own `Solc`
+  /// instance concurrently, bounded by `config.max_jobs` the same way
+  /// `compile_auto_detected_sources` bounds its worker pool for the standalone pipeline. Falls
+  /// back to the single-version path when every source resolves to the same bucket, so the common
+  /// case pays no extra overhead. Virtual sources are materialised once up front (not per worker)
+  /// so concurrent buckets never race on the shared incremental-cache manifest; artifact export is
+  /// likewise deferred until every bucket's output has been collated, so it reflects the full
+  /// merged set rather than whichever bucket finished last.
+  fn compile_source_map_with_version_buckets(
+    &self,
+    config: &CompilerConfig,
+    sources: &BTreeMap<String, String>,
+    language_override: Option<CompilerLanguage>,
+  ) -> Result<CompileOutput> {
+    let buckets =
+      graph::resolve_per_source_version_buckets(sources, &config.solc_version, config.offline_mode)?;
+
+    let mut paths_by_version: BTreeMap<Version, Vec<&str>> = BTreeMap::new();
+    for (path, version) in &buckets {
+      paths_by_version.entry(version.clone()).or_default().push(path.as_str());
+    }
+
+    let (files, dirty_report) = self.write_virtual_sources(
+      config,
+      sources.iter().map(|(path, contents)| VirtualSourceEntry {
+        original_path: Some(path.as_str()),
+        contents: contents.as_str(),
+      }),
+      language_override,
+    )?;
+    let virtual_path_by_original: BTreeMap<&str, PathBuf> = sources
+      .keys()
+      .map(String::as_str)
+      .zip(files)
+      .collect();
+
+    if paths_by_version.len() <= 1 {
+      let mut bucket_config = config.clone();
+      if let Some(version) = paths_by_version.into_keys().next() {
+        bucket_config.solc_version = version;
+      }
+      let output = self.compile_with_project(&bucket_config, "Compilation failed", move |project| {
+        project.compile_files(virtual_path_by_original.into_values().collect())
+      })?;
+      return Ok(apply_dirty_report(
+        into_core_compile_output_with_selection(output, config.artifact_field_selection),
+        dirty_report,
+      ));
+    }
+
+    info!(
+      target: LOG_TARGET,
+      "pragma version split produced {} bucket(s) for source map compilation; compiling across \
+       up to {} worker thread(s)",
+      paths_by_version.len(),
+      config.max_jobs.max(1)
+    );
+
+    let worker_count = config.max_jobs.max(1).min(paths_by_version.len());
+    let queue: Mutex<VecDeque<(usize, Version, Vec<PathBuf>)>> = Mutex::new(
+      paths_by_version
+        .into_iter()
+        .map(|(version, paths)| {
+          (
+            version,
+            paths
+              .into_iter()
+              .map(|path| virtual_path_by_original[path].clone())
+              .collect(),
+          )
+        })
+        .enumerate()
+        .map(|(index, (version, paths))| (index, version, paths))
+        .collect(),
+    );
+    let want_artifact_collation = !config.no_artifacts;
+    type BucketResult = (Version, CompileOutput, Option<BTreeMap<String, SourceArtifacts>>);
+    // Keyed by the bucket's original queue index (not a plain `Vec`) so merge order below stays
+    // stable regardless of which worker thread finishes first -- the same pattern
+    // `compile_auto_detected_sources` (core.rs) uses, for the same reason:
+    // `merge_compile_outputs_by_version`'s "first entry keeps the plain path key" rule depends on
+    // input order, and two version buckets can share byte-identical source content.
+    let results: Mutex<BTreeMap<usize, Result<BucketResult>>> = Mutex::new(BTreeMap::new());
+
+    std::thread::scope(|scope| {
+      for _ in 0..worker_count {
+        scope.spawn(|| loop {
+          let next = queue
+            .lock()
+            .expect("source map version bucket queue mutex poisoned")
+            .pop_front();
+          let Some((index, version, paths)) = next else {
+            break;
+          };
+
+          let mut bucket_config = config.clone();
+          bucket_config.solc_version = version.clone();
+          // The per-bucket artifact export is suppressed here; we collate and write it once below
+          // from every bucket's raw output so it reflects the full merged artifact set.
+          bucket_config.artifact_output = Some(ArtifactOutputFormat::Foundry);
+
+          // Converted to the crate's own `CompileOutput`/`SourceArtifacts` before crossing back out
+          // of this worker, the same way `compile_auto_detected_sources` keeps only crate-owned
+          // types in its shared results `Mutex` rather than foundry-compilers' raw project output.
+          let result = self
+            .compile_with_project(&bucket_config, "Compilation failed", move |project| {
+              project.compile_files(paths)
+            })
+            .map(|output| {
+              let artifacts = want_artifact_collation
+                .then(|| collate_project_artifacts(&output, config.artifact_field_selection));
+              (
+                version,
+                into_core_compile_output_with_selection(output, config.artifact_field_selection),
+                artifacts,
+              )
+            });
+          results
+            .lock()
+            .expect("source map version bucket results mutex poisoned")
+            .insert(index, result);
+        });
+      }
+    });
+
+    let mut artifact_collation = BTreeMap::new();
+    let mut per_version_outputs = Vec::new();
+    for (_, result) in results.into_inner().expect("results mutex poisoned") {
+      let (version, output, artifacts) = result?;
+      if let Some(artifacts) = artifacts {
+        artifact_collation.extend(artifacts);
+      }
+      per_version_outputs.push((version, output));
+    }
+
+    if want_artifact_collation {
+      let format =
+        artifact_output::resolve_format(config.artifact_output, config.artifact_format, &self.context.layout);
+      artifact_output::writer(format).write(&self.context.paths.artifacts, &self.context.paths.build_infos, &artifact_collation)?;
+    }
+
+    Ok(apply_dirty_report(
+      merge_compile_outputs_by_version(per_version_outputs),
+      dirty_report,
+    ))
+  }
+
   pub fn prepare_synthetic_context(config: &CompilerConfig) -> Result<Option<ProjectContext>> {
     if !config.cache_enabled {
       info!(
@@ -269,11 +877,12 @@ impl<'a> ProjectRunner<'a> {
     config: &CompilerConfig,
     entries: I,
     language_override: Option<CompilerLanguage>,
-  ) -> Result<Vec<PathBuf>>
+  ) -> Result<(Vec<PathBuf>, DirtyReport)>
   where
     I: IntoIterator<Item = VirtualSourceEntry<'entries>>,
   {
     let mut paths = Vec::new();
+    let mut hash_entries = Vec::new();
     let mut processed = 0usize;
 
     for entry in entries {
@@ -310,6 +919,8 @@ impl<'a> ProjectRunner<'a> {
         language,
         path.display()
       );
+      let cache_key = entry.original_path.unwrap_or(&source_hash).to_string();
+      hash_entries.push((cache_key, source_hash));
       paths.push(canonicalize_path(&path));
     }
 
@@ -317,10 +928,43 @@ impl<'a> ProjectRunner<'a> {
       target: LOG_TARGET,
       "materialised {processed} virtual source(s)"
     );
-    Ok(paths)
+
+    let dirty_report = if config.cache_enabled && !config.force_rebuild {
+      let manifest_path = self.context.incremental_cache_manifest_path();
+      let fingerprint = incremental_cache::config_fingerprint(config);
+      incremental_cache::evaluate(&manifest_path, &fingerprint, &hash_entries)?
+    } else {
+      DirtyReport::default()
+    };
+
+    Ok((paths, dirty_report))
   }
 }
 
+fn apply_dirty_report(mut output: CompileOutput, report: DirtyReport) -> CompileOutput {
+  output.dirty_paths = report.dirty;
+  output.reused_paths = report.reused;
+  output
+}
+
+/// A ready-made [`ProjectRunner::compile_filtered`] predicate excluding Forge-style test, script,
+/// and mock sources: anything with a `test`/`tests`/`script`/`scripts`/`mock`/`mocks` path
+/// component, or a `*.t.sol` file name. Mirrors ethers-solc's `TestFileFilter` default.
+pub fn exclude_tests_scripts_and_mocks(path: &Path) -> bool {
+  let under_excluded_dir = path.components().any(|component| {
+    matches!(
+      component.as_os_str().to_str(),
+      Some("test") | Some("tests") | Some("script") | Some("scripts") | Some("mock") | Some("mocks")
+    )
+  });
+  let is_test_file = path
+    .file_name()
+    .and_then(|name| name.to_str())
+    .is_some_and(|name| name.ends_with(".t.sol"));
+
+  !under_excluded_dir && !is_test_file
+}
+
 fn determine_extension(original_path: Option<&str>, language: CompilerLanguage) -> String {
   if let Some(path) = original_path {
     if let Some(ext) = Path::new(path)
@@ -354,7 +998,7 @@ mod tests {
 
     let mut config = CompilerConfig::default();
     config.language = CompilerLanguage::Solidity;
-    let sol_path = runner
+    let (sol_path, _report) = runner
       .write_virtual_sources(
         &config,
         [VirtualSourceEntry {
@@ -378,7 +1022,7 @@ mod tests {
     );
 
     config.language = CompilerLanguage::Yul;
-    let yul_path = runner
+    let (yul_path, _report) = runner
       .write_virtual_sources(
         &config,
         [VirtualSourceEntry {
@@ -398,6 +1042,86 @@ mod tests {
       .ends_with("yul"));
   }
 
+  #[test]
+  fn write_virtual_sources_reports_dirty_then_reused_entries() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    let mut config = CompilerConfig::default();
+    config.language = CompilerLanguage::Solidity;
+    config.cache_enabled = true;
+
+    let (_, first_report) = runner
+      .write_virtual_sources(
+        &config,
+        [VirtualSourceEntry {
+          original_path: Some("Sample.sol"),
+          contents: "contract Sample {}",
+        }],
+        None,
+      )
+      .expect("first write");
+    assert_eq!(first_report.dirty, vec!["Sample.sol".to_string()]);
+    assert!(first_report.reused.is_empty());
+
+    let (_, second_report) = runner
+      .write_virtual_sources(
+        &config,
+        [VirtualSourceEntry {
+          original_path: Some("Sample.sol"),
+          contents: "contract Sample {}",
+        }],
+        None,
+      )
+      .expect("second write");
+    assert_eq!(second_report.reused, vec!["Sample.sol".to_string()]);
+    assert!(second_report.dirty.is_empty());
+  }
+
+  #[test]
+  fn project_cache_report_flags_a_fresh_source_dirty_then_reuses_it_once_seeded() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let source_path = context.root.join("A.sol");
+    fs::write(&source_path, "contract A {}").expect("write source");
+    let runner = ProjectRunner::new(&context);
+
+    let mut config = CompilerConfig::default();
+    config.cache_enabled = true;
+
+    let (texts, first_report, first_dirty) = runner
+      .project_cache_report(&config)
+      .expect("first project_cache_report");
+    let key = source_path.to_string_lossy().into_owned();
+    assert_eq!(texts.get(&key), Some(&"contract A {}".to_string()));
+    assert!(first_report.fresh.is_empty());
+    assert_eq!(first_dirty, BTreeSet::from([key.clone()]));
+
+    let fingerprint = incremental_cache::config_fingerprint(&config);
+    incremental_cache::store_artifacts(
+      &context.incremental_cache_manifest_path(),
+      &context.incremental_cache_artifacts_dir(),
+      &fingerprint,
+      &[(
+        key.clone(),
+        "contract A {}".to_string(),
+        CachedArtifact {
+          source: serde_json::json!({"ast": {}}),
+          contracts: serde_json::json!({}),
+          errors: Vec::new(),
+        },
+      )],
+    )
+    .expect("seed project artifact cache");
+
+    let (_, second_report, second_dirty) = runner
+      .project_cache_report(&config)
+      .expect("second project_cache_report");
+    assert!(second_dirty.is_empty());
+    assert!(second_report.fresh.contains_key(&key));
+  }
+
   #[test]
   fn prepare_synthetic_context_respects_cache_flag() {
     let mut config = CompilerConfig::default();
@@ -412,4 +1136,133 @@ mod tests {
       .expect("some context");
     assert!(matches!(context.layout, ProjectLayout::Synthetic));
   }
+
+  #[test]
+  fn compile_with_project_reports_a_missing_solc_version_instead_of_downloading_it() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    let mut config = CompilerConfig::default();
+    config.offline_mode = true;
+    config.solc_version = semver::Version::new(0, 0, 0);
+
+    let err = runner
+      .compile_with_project(&config, "test compilation", |project| project.compile())
+      .unwrap_err();
+    assert!(
+      err.to_string().contains("is not installed"),
+      "unexpected message: {}",
+      err
+    );
+    assert!(
+      err.to_string().contains("Call installSolcVersion first"),
+      "unexpected message: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn compile_source_map_with_version_buckets_reports_an_unsatisfiable_pragma_before_compiling() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    let mut config = CompilerConfig::default();
+    config.offline_mode = true;
+    config.auto_detect_version = true;
+
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "pragma solidity =0.8.19;\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "B.sol".to_string(),
+      "pragma solidity >=99.0.0;\ncontract B {}".to_string(),
+    );
+
+    let err = runner
+      .compile_source_map_with_version_buckets(&config, &sources, None)
+      .unwrap_err();
+    assert!(
+      err.to_string().contains("B.sol"),
+      "expected error to name the offending source: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn exclude_tests_scripts_and_mocks_drops_test_directories_and_t_sol_files() {
+    assert!(!exclude_tests_scripts_and_mocks(Path::new("test/Token.t.sol")));
+    assert!(!exclude_tests_scripts_and_mocks(Path::new("src/Token.t.sol")));
+    assert!(!exclude_tests_scripts_and_mocks(Path::new("script/Deploy.s.sol")));
+    assert!(!exclude_tests_scripts_and_mocks(Path::new("test/mocks/MockERC20.sol")));
+    assert!(exclude_tests_scripts_and_mocks(Path::new("src/Token.sol")));
+  }
+
+  #[test]
+  fn compile_filtered_by_glob_rejects_an_invalid_pattern() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    let config = CompilerConfig::default();
+    let err = runner
+      .compile_filtered_by_glob(&config, &["[".to_string()])
+      .unwrap_err();
+    assert!(
+      err.to_string().contains("Invalid compile_filtered glob"),
+      "unexpected message: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn flatten_inlines_a_relative_import_in_dependency_order() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    fs::write(
+      temp_dir.path().join("B.sol"),
+      "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\ncontract B {}",
+    )
+    .expect("write B.sol");
+    fs::write(
+      temp_dir.path().join("A.sol"),
+      "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\nimport \"./B.sol\";\ncontract A {}",
+    )
+    .expect("write A.sol");
+
+    let config = CompilerConfig::default();
+    let entry = temp_dir.path().join("A.sol");
+    let flattened = runner
+      .flatten(&config, entry.to_str().expect("utf8 path"))
+      .expect("flatten");
+
+    assert!(flattened.find("contract B").unwrap() < flattened.find("contract A").unwrap());
+    assert!(!flattened.contains("import"));
+    assert_eq!(flattened.matches("SPDX-License-Identifier").count(), 1);
+  }
+
+  #[test]
+  fn flatten_reports_a_broken_relative_import() {
+    let temp_dir = tempdir().expect("temp dir");
+    let context = create_synthetic_context(temp_dir.path()).expect("context");
+    let runner = ProjectRunner::new(&context);
+
+    fs::write(
+      temp_dir.path().join("A.sol"),
+      "import \"./Missing.sol\";\ncontract A {}",
+    )
+    .expect("write A.sol");
+
+    let config = CompilerConfig::default();
+    let entry = temp_dir.path().join("A.sol");
+    let err = runner
+      .flatten(&config, entry.to_str().expect("utf8 path"))
+      .unwrap_err();
+    assert!(err.to_string().contains("Missing.sol"));
+  }
 }
@@ -1,30 +1,58 @@
 use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use foundry_compilers::artifacts::{
   bytecode::{
     Bytecode, BytecodeObject, CompactBytecode, CompactDeployedBytecode, DeployedBytecode,
   },
   contract::Contract as FoundryContract,
-  ConfigurableContractArtifact, Creation, Ewasm, FunctionDebugData, GasEstimates,
+  CompilerOutput, ConfigurableContractArtifact, Creation, Ewasm, FunctionDebugData, GasEstimates,
 };
 use foundry_compilers::Artifact;
 use hex;
+use semver::Version;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ContractBytecode {
-  bytes: Vec<u8>,
+use crate::compiler::output::SourceLocation;
+use crate::internal::errors::{map_err_with_context, Error, Result};
+use crate::internal::keccak::keccak256;
+
+/// Bytecode as carried through the compile pipeline. Solc emits a literal placeholder string
+/// (`__$<hash>$__`) in place of concrete bytes wherever a contract references an unlinked
+/// library, so unlike a plain `Vec<u8>` this has to represent "not yet resolvable" as well as
+/// "resolved".
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ContractBytecode {
+  /// Concrete bytes: either the contract never referenced an unlinked library, or linking has
+  /// already happened upstream of this crate.
+  Bytes(Vec<u8>),
+  /// Raw bytecode object string still containing one or more unresolved library placeholders.
+  Unlinked(String),
+  /// Bytes produced by [`ContractState::link`], alongside the placeholder string they were
+  /// resolved from (kept around so callers can tell which libraries this artifact was linked
+  /// against).
+  LinkedBytecode { bytes: Vec<u8>, placeholder: String },
+}
+
+impl Default for ContractBytecode {
+  fn default() -> Self {
+    Self::Bytes(Vec::new())
+  }
 }
 
 impl ContractBytecode {
   pub fn from_bytes<T: Into<Vec<u8>>>(bytes: T) -> Self {
-    Self {
-      bytes: bytes.into(),
-    }
+    Self::Bytes(bytes.into())
+  }
+
+  pub fn unlinked(placeholder: impl Into<String>) -> Self {
+    Self::Unlinked(placeholder.into())
   }
 
-  pub fn from_hex_string(hex_string: &str) -> Result<Self, hex::FromHexError> {
+  pub fn from_hex_string(hex_string: &str) -> std::result::Result<Self, hex::FromHexError> {
     let trimmed = hex_string.strip_prefix("0x").unwrap_or(hex_string);
     let bytes = hex::decode(trimmed)?;
     Ok(Self::from_bytes(bytes))
@@ -49,30 +77,62 @@ impl ContractBytecode {
       .and_then(Self::from_compact_bytecode)
   }
 
+  /// Unlike a plain `object.as_bytes()`, this never silently drops unlinked bytecode: a
+  /// placeholder string comes back as `Some(Self::Unlinked(..))` rather than `None`, so library
+  /// references survive into [`ContractState`] instead of disappearing before `link` ever sees
+  /// them.
   pub fn from_bytecode_object(object: &BytecodeObject) -> Option<Self> {
-    object
-      .as_bytes()
-      .map(|bytes| Self::from_bytes(bytes.as_ref()))
+    if let Some(bytes) = object.as_bytes() {
+      return Some(Self::from_bytes(bytes.as_ref()));
+    }
+    object.as_str().map(Self::unlinked)
   }
 
   pub fn bytes(&self) -> &[u8] {
-    &self.bytes
+    match self {
+      Self::Bytes(bytes) => bytes,
+      Self::Unlinked(_) => &[],
+      Self::LinkedBytecode { bytes, .. } => bytes,
+    }
   }
 
   pub fn into_bytes(self) -> Vec<u8> {
-    self.bytes
+    match self {
+      Self::Bytes(bytes) => bytes,
+      Self::Unlinked(_) => Vec::new(),
+      Self::LinkedBytecode { bytes, .. } => bytes,
+    }
   }
 
   pub fn is_empty(&self) -> bool {
-    self.bytes.is_empty()
+    self.bytes().is_empty()
   }
 
   pub fn len(&self) -> usize {
-    self.bytes.len()
+    self.bytes().len()
   }
 
+  /// Unlinked bytecode has no well-defined byte length, so this returns the placeholder string
+  /// verbatim; every other variant returns `0x`-prefixed hex of its resolved bytes.
   pub fn to_hex(&self) -> String {
-    format!("0x{}", hex::encode(&self.bytes))
+    match self {
+      Self::Unlinked(placeholder) => placeholder.clone(),
+      other => format!("0x{}", hex::encode(other.bytes())),
+    }
+  }
+
+  pub fn is_unlinked(&self) -> bool {
+    matches!(self, Self::Unlinked(_))
+  }
+
+  /// The unlinked placeholder string this bytecode carries, whether it's still unresolved or was
+  /// already linked via [`ContractState::link`].
+  pub fn placeholder(&self) -> Option<&str> {
+    match self {
+      Self::Unlinked(placeholder) => Some(placeholder),
+      Self::LinkedBytecode { placeholder, .. } => Some(placeholder),
+      Self::Bytes(_) => None,
+    }
   }
 }
 
@@ -84,7 +144,7 @@ impl AsRef<[u8]> for ContractBytecode {
 
 impl From<Vec<u8>> for ContractBytecode {
   fn from(value: Vec<u8>) -> Self {
-    Self { bytes: value }
+    Self::Bytes(value)
   }
 }
 
@@ -98,6 +158,204 @@ pub struct ImmutableSlot {
   pub length: u32,
 }
 
+/// Jump kind recorded in a decoded [`SourceMapEntry`], the `j` field of Solc's compact source-map
+/// format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JumpType {
+  /// `i`: jump into a function.
+  Into,
+  /// `o`: jump out of a function.
+  Out,
+  /// `-`: a regular jump, not associated with entering or leaving a function.
+  Regular,
+}
+
+impl JumpType {
+  const fn as_str(self) -> &'static str {
+    match self {
+      Self::Into => "i",
+      Self::Out => "o",
+      Self::Regular => "-",
+    }
+  }
+}
+
+impl std::str::FromStr for JumpType {
+  type Err = String;
+
+  fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+    match value {
+      "i" => Ok(Self::Into),
+      "o" => Ok(Self::Out),
+      "-" => Ok(Self::Regular),
+      other => Err(format!("Invalid source-map jump type `{other}`")),
+    }
+  }
+}
+
+impl std::fmt::Display for JumpType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl ::napi::bindgen_prelude::ToNapiValue for JumpType {
+  unsafe fn to_napi_value(
+    env: ::napi::sys::napi_env,
+    value: Self,
+  ) -> ::napi::Result<::napi::sys::napi_value> {
+    <&str as ::napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, value.as_str())
+  }
+}
+
+impl ::napi::bindgen_prelude::FromNapiValue for JumpType {
+  unsafe fn from_napi_value(
+    env: ::napi::sys::napi_env,
+    napi_val: ::napi::sys::napi_value,
+  ) -> ::napi::Result<Self> {
+    let value = <String as ::napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+    value.parse().map_err(|err| ::napi::Error::new(::napi::Status::InvalidArg, err))
+  }
+}
+
+/// A single decoded entry of Solc's compact `s:l:f:j:m` source-map format.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMapEntry {
+  /// Byte offset into the source file where the instruction's source range begins.
+  pub start: u32,
+  /// Byte length of the instruction's source range.
+  pub length: u32,
+  /// Index into the compilation's source list; `-1` means "no source" (compiler-generated code).
+  pub file_index: i32,
+  /// Jump kind for this instruction.
+  pub jump: JumpType,
+  /// Depth of the modifier stack active at this instruction.
+  pub modifier_depth: u32,
+}
+
+/// Decodes a compact `s:l:f:j:m` source-map string into structured entries. Each `;`-separated
+/// entry inherits any field left blank from the previous entry, matching Solc's own encoding.
+pub fn decode_source_map(raw: &str) -> Vec<SourceMapEntry> {
+  let mut entries = Vec::new();
+  let mut start = 0u32;
+  let mut length = 0u32;
+  let mut file_index = -1i32;
+  let mut jump = JumpType::Regular;
+  let mut modifier_depth = 0u32;
+
+  for chunk in raw.split(';') {
+    let fields: Vec<&str> = chunk.split(':').collect();
+
+    if let Some(value) = fields.first().filter(|field| !field.is_empty()) {
+      if let Ok(parsed) = value.parse() {
+        start = parsed;
+      }
+    }
+    if let Some(value) = fields.get(1).filter(|field| !field.is_empty()) {
+      if let Ok(parsed) = value.parse() {
+        length = parsed;
+      }
+    }
+    if let Some(value) = fields.get(2).filter(|field| !field.is_empty()) {
+      if let Ok(parsed) = value.parse() {
+        file_index = parsed;
+      }
+    }
+    if let Some(value) = fields.get(3).filter(|field| !field.is_empty()) {
+      if let Ok(parsed) = value.parse() {
+        jump = parsed;
+      }
+    }
+    if let Some(value) = fields.get(4).filter(|field| !field.is_empty()) {
+      if let Ok(parsed) = value.parse() {
+        modifier_depth = parsed;
+      }
+    }
+
+    entries.push(SourceMapEntry {
+      start,
+      length,
+      file_index,
+      jump,
+      modifier_depth,
+    });
+  }
+
+  entries
+}
+
+impl SourceMapEntry {
+  /// Resolves [`Self::file_index`] to a [`SourceLocation`] using `sources` to map indices to file
+  /// paths. `None` when [`Self::file_index`] is `-1` (compiler-generated code with no associated
+  /// source) or isn't present in `sources`.
+  pub fn source_location(&self, sources: &BTreeMap<i32, String>) -> Option<SourceLocation> {
+    let file = sources.get(&self.file_index)?.clone();
+    Some(SourceLocation {
+      file,
+      start: self.start as i32,
+      end: self.start as i32 + self.length as i32,
+    })
+  }
+}
+
+/// Walks raw EVM bytecode and records the starting program counter of each instruction, skipping
+/// over `PUSH1`..`PUSH32` operand bytes (`0x60`..`0x7f`), which don't carry their own source-map
+/// entry. The Nth entry of the returned table corresponds to the Nth entry decoded by
+/// [`decode_source_map`].
+pub fn build_instruction_pcs(bytecode: &[u8]) -> Vec<u32> {
+  const PUSH1: u8 = 0x60;
+  const PUSH32: u8 = 0x7f;
+
+  let mut pcs = Vec::new();
+  let mut pc = 0usize;
+  while pc < bytecode.len() {
+    pcs.push(pc as u32);
+    let operand_len = match bytecode[pc] {
+      opcode @ PUSH1..=PUSH32 => (opcode - PUSH1 + 1) as usize,
+      _ => 0,
+    };
+    pc += 1 + operand_len;
+  }
+  pcs
+}
+
+/// A decoded source map linked to the bytecode it describes, so a program counter reached during
+/// execution can be resolved back to the [`SourceMapEntry`] that produced it. Built via
+/// [`ContractState::creation_source_map`] or [`ContractState::deployed_source_map`].
+#[derive(Clone, Debug)]
+pub struct SourceMap {
+  entries: Vec<SourceMapEntry>,
+  instruction_pcs: Vec<u32>,
+}
+
+impl SourceMap {
+  fn new(entries: Vec<SourceMapEntry>, bytecode: &[u8]) -> Self {
+    Self {
+      instruction_pcs: build_instruction_pcs(bytecode),
+      entries,
+    }
+  }
+
+  /// Decoded source-map entries, one per instruction, in bytecode order.
+  pub fn entries(&self) -> &[SourceMapEntry] {
+    &self.entries
+  }
+
+  /// Resolves `pc` to the [`SourceMapEntry`] for the instruction it falls within, by
+  /// binary-searching the instruction-to-PC table built from the bytecode. `None` if `pc` falls
+  /// before the first known instruction, or past the last instruction with a decoded entry (a
+  /// truncated or mismatched source map).
+  pub fn map_pc(&self, pc: u32) -> Option<&SourceMapEntry> {
+    let index = match self.instruction_pcs.binary_search(&pc) {
+      Ok(index) => index,
+      Err(0) => return None,
+      Err(index) => index - 1,
+    };
+    self.entries.get(index)
+  }
+}
+
 #[napi(object, js_name = "FunctionDebugDataEntry")]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -208,6 +466,16 @@ pub struct ContractState {
   pub source_path: Option<String>,
   /// Numeric source identifier assigned by solc.
   pub source_id: Option<u32>,
+  /// Version of the compiler (solc or vyper) that produced this artifact. Paired with
+  /// [`Self::name`] and [`Self::source_path`] via [`Self::artifact_id`] to form a key that stays
+  /// unique when the same contract is compiled under more than one compiler version.
+  pub version: Option<Version>,
+  /// Parsed AST of the source file that defines this contract, when the compiler emitted one.
+  /// Foundry's on-disk artifacts embed their defining source's AST directly on the contract
+  /// (`ConfigurableContractArtifact::ast`); for standard-JSON input, where solc only emits one AST
+  /// per source file rather than per contract, this is populated from the matching
+  /// `sources[path].ast` entry instead.
+  pub ast: Option<Value>,
   /// Compiler metadata payload (string or JSON value depending on version).
   pub metadata: Option<Value>,
   /// User documentation section (`userdoc`).
@@ -218,6 +486,13 @@ pub struct ContractState {
   pub storage_layout: Option<Value>,
   /// Offsets for immutable variables keyed by label (`immutableReferences`).
   pub immutable_references: Option<BTreeMap<String, Vec<ImmutableSlot>>>,
+  /// Unresolved library placeholders in the creation bytecode, keyed by the source file that
+  /// defines the library and then by library name (`evm.bytecode.linkReferences`). Consumed by
+  /// [`ContractState::link`].
+  pub creation_link_references: Option<BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>>,
+  /// Unresolved library placeholders in the deployed bytecode, keyed the same way as
+  /// [`Self::creation_link_references`] (`evm.deployedBytecode.linkReferences`).
+  pub deployed_link_references: Option<BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>>,
   /// Map of function signatures to selectors (`methodIdentifiers`).
   pub method_identifiers: Option<BTreeMap<String, String>>,
   /// Function debug metadata keyed by signature.
@@ -237,6 +512,14 @@ pub struct ContractState {
   /// Ewasm output payload when generated.
   pub ewasm: Option<Ewasm>,
   pub creation_source_map: Option<String>,
+  /// Raw deployed-bytecode source map (`evm.deployedBytecode.sourceMap`), when requested.
+  pub deployed_source_map: Option<String>,
+  /// `creation_source_map`, decoded into structured entries. `None` unless a creation source map
+  /// was emitted.
+  pub creation_source_map_decoded: Option<Vec<SourceMapEntry>>,
+  /// `deployed_source_map`, decoded into structured entries. `None` unless a deployed source map
+  /// was emitted.
+  pub deployed_source_map_decoded: Option<Vec<SourceMapEntry>>,
 }
 
 impl ContractState {
@@ -246,6 +529,377 @@ impl ContractState {
       ..Default::default()
     }
   }
+
+  /// Builds this contract's [`ArtifactId`] from [`Self::source_path`], [`Self::name`], and
+  /// [`Self::version`]. `None` unless both the source path and compiler version are known.
+  pub fn artifact_id(&self) -> Option<ArtifactId> {
+    Some(ArtifactId {
+      source_path: self.source_path.clone()?,
+      name: self.name.clone(),
+      version: self.version.clone()?,
+    })
+  }
+
+  /// `"<file_stem>.json:<Name>"`, via [`Self::artifact_id`]. Falls back to [`Self::name`] as the
+  /// stem when the source path or compiler version isn't known yet.
+  pub fn slug(&self) -> String {
+    match self.artifact_id() {
+      Some(id) => id.slug(),
+      None => format!("{}.json:{}", self.name, self.name),
+    }
+  }
+
+  /// [`Self::slug`] with the compiler version spliced in, via [`Self::artifact_id`], so the same
+  /// contract compiled under multiple solc versions gets distinct keys. Falls back to
+  /// [`Self::slug`] when the source path or compiler version isn't known yet.
+  pub fn slug_versioned(&self) -> String {
+    match self.artifact_id() {
+      Some(id) => id.slug_versioned(),
+      None => self.slug(),
+    }
+  }
+
+  /// Resolves every library placeholder recorded in [`Self::creation_link_references`] and
+  /// [`Self::deployed_link_references`] against `libraries` (20-byte hex address, keyed by either
+  /// the fully-qualified `file:library` or, when that's ambiguous for the caller, the bare
+  /// `library` name), overwriting the corresponding bytes of the creation/deployed bytecode and
+  /// returning a new, linked `ContractState`. Contracts compiled without structured offsets (pre-
+  /// `linkReferences` solc) fall back to scanning for the legacy `__LibName_____` /
+  /// `__$<hash>$__` placeholder forms instead. Errors with [`LinkError::MissingAddresses`] listing
+  /// every placeholder `libraries` didn't cover, or [`LinkError::InvalidAddress`] if a supplied
+  /// address isn't valid 20-byte hex.
+  pub fn link(&self, libraries: &HashMap<String, String>) -> std::result::Result<Self, LinkError> {
+    let mut linked = self.clone();
+    let mut missing = Vec::new();
+    linked.creation_bytecode = link_bytecode(
+      self.creation_bytecode.as_ref(),
+      self.creation_link_references.as_ref(),
+      libraries,
+      &mut missing,
+    )?;
+    linked.deployed_bytecode = link_bytecode(
+      self.deployed_bytecode.as_ref(),
+      self.deployed_link_references.as_ref(),
+      libraries,
+      &mut missing,
+    )?;
+    if !missing.is_empty() {
+      return Err(LinkError::MissingAddresses(missing));
+    }
+    Ok(linked)
+  }
+
+  /// Links [`Self::creation_source_map_decoded`] to [`Self::creation_bytecode`] so its PCs can be
+  /// resolved via [`SourceMap::map_pc`]. `None` unless both a decoded creation source map and
+  /// creation bytecode are present.
+  pub fn creation_source_map(&self) -> Option<SourceMap> {
+    Some(SourceMap::new(
+      self.creation_source_map_decoded.clone()?,
+      self.creation_bytecode.as_ref()?.bytes(),
+    ))
+  }
+
+  /// Links [`Self::deployed_source_map_decoded`] to [`Self::deployed_bytecode`] so its PCs can be
+  /// resolved via [`SourceMap::map_pc`]. `None` unless both a decoded deployed source map and
+  /// deployed bytecode are present.
+  pub fn deployed_source_map(&self) -> Option<SourceMap> {
+    Some(SourceMap::new(
+      self.deployed_source_map_decoded.clone()?,
+      self.deployed_bytecode.as_ref()?.bytes(),
+    ))
+  }
+}
+
+/// Stable identity for a compiled artifact, mirroring foundry-compilers' `ArtifactId` (source
+/// path + contract name + compiler version). Lets callers dedupe/cache artifacts that share a
+/// name but were compiled from different sources, or from the same source under more than one
+/// solc version -- something [`ContractState::name`] and [`ContractState::source_path`] alone
+/// can't disambiguate. Obtained via [`ContractState::artifact_id`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArtifactId {
+  /// Source file path that defines the contract.
+  pub source_path: String,
+  /// Name of the contract as reported by the compiler.
+  pub name: String,
+  /// Version of the compiler that produced the artifact.
+  pub version: Version,
+}
+
+impl ArtifactId {
+  fn file_stem(&self) -> &str {
+    Path::new(&self.source_path)
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .unwrap_or(&self.source_path)
+  }
+
+  /// `"<file_stem>.json:<Name>"`, ignoring the compiler version.
+  pub fn slug(&self) -> String {
+    format!("{}.json:{}", self.file_stem(), self.name)
+  }
+
+  /// `"<file_stem>.<major>.<minor>.<patch>.json:<Name>"`, disambiguating artifacts of the same
+  /// contract compiled under different solc versions.
+  pub fn slug_versioned(&self) -> String {
+    format!(
+      "{}.{}.{}.{}.json:{}",
+      self.file_stem(),
+      self.version.major,
+      self.version.minor,
+      self.version.patch,
+      self.name
+    )
+  }
+}
+
+/// A library placeholder [`ContractState::link`] found no address for. `file` is empty when the
+/// placeholder was recovered from a legacy hash-form placeholder with no way back to the source
+/// file that declared it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingLibrary {
+  pub file: String,
+  pub library: String,
+}
+
+/// Error returned by [`ContractState::link`] when one or more library placeholders can't be
+/// resolved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkError {
+  /// `libraries` had no address for every library placeholder listed.
+  MissingAddresses(Vec<MissingLibrary>),
+  /// The address supplied for `library` (referenced from `file`) isn't valid 20-byte hex.
+  InvalidAddress {
+    file: String,
+    library: String,
+    reason: String,
+  },
+}
+
+impl std::fmt::Display for LinkError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::MissingAddresses(missing) => {
+        write!(f, "no address supplied for library placeholder(s): ")?;
+        let descriptions: Vec<String> = missing
+          .iter()
+          .map(|entry| {
+            if entry.file.is_empty() {
+              format!("`{}`", entry.library)
+            } else {
+              format!("`{}` (referenced from `{}`)", entry.library, entry.file)
+            }
+          })
+          .collect();
+        write!(f, "{}", descriptions.join(", "))
+      }
+      Self::InvalidAddress {
+        file,
+        library,
+        reason,
+      } => write!(
+        f,
+        "invalid address for library `{library}` (referenced from `{file}`): {reason}"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for LinkError {}
+
+/// Substitutes every recorded library placeholder into `bytecode`'s raw object string (operating
+/// on hex characters, the same domain Solc's placeholder lives in -- the unlinked object isn't
+/// valid hex as a whole, so this can't go through [`ContractBytecode::bytes`]) and hex-decodes the
+/// result. Bytecode that's already fully resolved (no placeholder) is returned unchanged. Falls
+/// back to [`apply_legacy_placeholders`] when `link_references` carries no structured offsets.
+/// Unresolved libraries are appended to `missing` rather than failing immediately, so
+/// [`ContractState::link`] can report every placeholder it couldn't resolve in one error.
+fn link_bytecode(
+  bytecode: Option<&ContractBytecode>,
+  link_references: Option<&BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>>,
+  libraries: &HashMap<String, String>,
+  missing: &mut Vec<MissingLibrary>,
+) -> std::result::Result<Option<ContractBytecode>, LinkError> {
+  let Some(bytecode) = bytecode else {
+    return Ok(None);
+  };
+  let Some(placeholder) = bytecode.placeholder() else {
+    return Ok(Some(bytecode.clone()));
+  };
+
+  let placeholder = placeholder.to_string();
+  let mut hex_chars = placeholder
+    .strip_prefix("0x")
+    .unwrap_or(&placeholder)
+    .as_bytes()
+    .to_vec();
+
+  let has_structured_refs = link_references.is_some_and(|refs| !refs.is_empty());
+  if has_structured_refs {
+    for (file, libs) in link_references.expect("checked above") {
+      for (library, offsets) in libs {
+        let Some(address) = resolve_library_address(libraries, file, library) else {
+          missing.push(MissingLibrary {
+            file: file.clone(),
+            library: library.clone(),
+          });
+          continue;
+        };
+        let address_hex =
+          normalize_address_hex(address).map_err(|reason| LinkError::InvalidAddress {
+            file: file.clone(),
+            library: library.clone(),
+            reason,
+          })?;
+
+        for offset in offsets {
+          let char_start = offset.start as usize * 2;
+          let char_len = offset.length as usize * 2;
+          if char_start + char_len > hex_chars.len() {
+            return Err(LinkError::InvalidAddress {
+              file: file.clone(),
+              library: library.clone(),
+              reason: format!(
+                "offset {char_start}..{} is out of bounds for a {}-character bytecode object",
+                char_start + char_len,
+                hex_chars.len()
+              ),
+            });
+          }
+          hex_chars[char_start..char_start + char_len].copy_from_slice(address_hex.as_bytes());
+        }
+      }
+    }
+  } else {
+    apply_legacy_placeholders(&mut hex_chars, libraries, missing)?;
+  }
+
+  let linked_hex =
+    String::from_utf8(hex_chars).expect("hex digits and ASCII addresses are always valid UTF-8");
+  if !linked_hex.contains("__") {
+    let bytes = hex::decode(&linked_hex).map_err(|err| LinkError::InvalidAddress {
+      file: String::new(),
+      library: String::new(),
+      reason: format!("bytecode object is not valid hex after linking: {err}"),
+    })?;
+    return Ok(Some(ContractBytecode::LinkedBytecode { bytes, placeholder }));
+  }
+
+  Ok(Some(ContractBytecode::Unlinked(linked_hex)))
+}
+
+/// Resolves a library's address from `libraries`, preferring the fully-qualified `file:library`
+/// key and falling back to the bare `library` name when the caller didn't qualify it.
+fn resolve_library_address<'a>(
+  libraries: &'a HashMap<String, String>,
+  file: &str,
+  library: &str,
+) -> Option<&'a String> {
+  libraries
+    .get(&format!("{file}:{library}"))
+    .or_else(|| libraries.get(library))
+}
+
+/// Validates `address` as 20-byte hex (with or without a `0x` prefix) and returns it as 40
+/// lowercase hex characters, ready to splice into a bytecode object string.
+fn normalize_address_hex(address: &str) -> std::result::Result<String, String> {
+  let trimmed = address.strip_prefix("0x").unwrap_or(address);
+  let bytes = hex::decode(trimmed).map_err(|err| err.to_string())?;
+  if bytes.len() != 20 {
+    return Err(format!("expected a 20-byte address, got {} bytes", bytes.len()));
+  }
+  Ok(hex::encode(bytes))
+}
+
+/// Builds the legacy, pre-`linkReferences` human-readable placeholder solc emits in place of an
+/// unlinked library reference: `__` followed by the library name, truncated or right-padded with
+/// underscores to fill 40 hex characters (20 bytes).
+fn legacy_name_placeholder(library: &str) -> String {
+  let mut placeholder = format!("__{library}");
+  placeholder.truncate(40);
+  while placeholder.len() < 40 {
+    placeholder.push('_');
+  }
+  placeholder
+}
+
+/// Builds the legacy hash-form placeholder solc falls back to when a library's fully-qualified
+/// name is too long for [`legacy_name_placeholder`] to stay unambiguous: `__$<hash>$__`, where
+/// `<hash>` is the first 17 bytes of `keccak256("file:library")`, hex-encoded (34 hex characters,
+/// for 40 total).
+fn legacy_hash_placeholder(file: &str, library: &str) -> String {
+  let digest = keccak256(format!("{file}:{library}").as_bytes());
+  format!("__${}$__", hex::encode(&digest[..17]))
+}
+
+/// Resolves legacy placeholders (see [`legacy_name_placeholder`]/[`legacy_hash_placeholder`]) by
+/// substituting each library's address wherever either form appears in `hex`. `libraries` entries
+/// may be keyed `file:library` (needed to resolve the hash form) or the bare `library` name
+/// (sufficient for the name form). Placeholder runs left over after substitution are appended to
+/// `missing`.
+fn apply_legacy_placeholders(
+  hex_chars: &mut [u8],
+  libraries: &HashMap<String, String>,
+  missing: &mut Vec<MissingLibrary>,
+) -> std::result::Result<(), LinkError> {
+  let mut hex = String::from_utf8(hex_chars.to_vec()).expect("hex digits are valid UTF-8");
+  if !hex.contains("__") {
+    return Ok(());
+  }
+
+  for (key, address) in libraries {
+    let (file, library) = match key.split_once(':') {
+      Some((file, library)) => (file, library),
+      None => ("", key.as_str()),
+    };
+    let name_placeholder = legacy_name_placeholder(library);
+    let hash_placeholder = (!file.is_empty()).then(|| legacy_hash_placeholder(file, library));
+    let hash_matches = hash_placeholder.as_deref().is_some_and(|p| hex.contains(p));
+    if !hex.contains(&name_placeholder) && !hash_matches {
+      continue;
+    }
+    let address_hex = normalize_address_hex(address).map_err(|reason| LinkError::InvalidAddress {
+      file: file.to_string(),
+      library: library.to_string(),
+      reason,
+    })?;
+    hex = hex.replace(&name_placeholder, &address_hex);
+    if let Some(hash_placeholder) = hash_placeholder {
+      hex = hex.replace(&hash_placeholder, &address_hex);
+    }
+  }
+
+  for leftover in find_legacy_placeholder_runs(&hex) {
+    missing.push(MissingLibrary {
+      file: String::new(),
+      library: leftover,
+    });
+  }
+
+  hex_chars.copy_from_slice(hex.as_bytes());
+  Ok(())
+}
+
+/// Scans `hex` for 40-character windows that still look like an unresolved legacy placeholder
+/// (start with `__`), reporting the human-readable library name when the run matches the name
+/// form, or the raw placeholder text when it's the hash form (which can't be reversed to a name).
+fn find_legacy_placeholder_runs(hex: &str) -> Vec<String> {
+  let chars: Vec<char> = hex.chars().collect();
+  let mut runs = Vec::new();
+  let mut offset = 0;
+  while offset + 40 <= chars.len() {
+    let window: String = chars[offset..offset + 40].iter().collect();
+    if window.starts_with("__") {
+      if window.starts_with("__$") && window.ends_with("$__") {
+        runs.push(window);
+      } else {
+        runs.push(window.trim_start_matches('_').trim_end_matches('_').to_string());
+      }
+      offset += 40;
+    } else {
+      offset += 2;
+    }
+  }
+  runs
 }
 
 /// Internal builder used to assemble [`ContractState`] values.
@@ -261,7 +915,16 @@ impl ContractBuilder {
   }
 
   fn finish(self) -> ContractState {
-    self.state
+    let mut state = self.state;
+    state.creation_source_map_decoded = state
+      .creation_source_map
+      .as_deref()
+      .map(decode_source_map);
+    state.deployed_source_map_decoded = state
+      .deployed_source_map
+      .as_deref()
+      .map(decode_source_map);
+    state
   }
 
   fn set_abi(mut self, abi: Option<Value>) -> Self {
@@ -284,6 +947,11 @@ impl ContractBuilder {
     self
   }
 
+  fn set_deployed_source_map(mut self, map: Option<String>) -> Self {
+    self.state.deployed_source_map = map;
+    self
+  }
+
   fn set_metadata(mut self, metadata: Option<Value>) -> Self {
     self.state.metadata = metadata;
     self
@@ -317,6 +985,22 @@ impl ContractBuilder {
     self
   }
 
+  fn set_creation_link_references(
+    mut self,
+    value: Option<BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>>,
+  ) -> Self {
+    self.state.creation_link_references = value;
+    self
+  }
+
+  fn set_deployed_link_references(
+    mut self,
+    value: Option<BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>>,
+  ) -> Self {
+    self.state.deployed_link_references = value;
+    self
+  }
+
   fn set_function_debug_data(mut self, value: Option<BTreeMap<String, FunctionDebugData>>) -> Self {
     self.state.function_debug_data = value;
     self
@@ -360,6 +1044,10 @@ impl ContractBuilder {
     self.state.source_id = value;
     self
   }
+  fn set_ast(mut self, value: Option<Value>) -> Self {
+    self.state.ast = value;
+    self
+  }
 }
 
 pub fn new_state(name: impl Into<String>) -> ContractState {
@@ -405,142 +1093,864 @@ pub fn ewasm_to_js(state: &ContractState) -> Option<JsEwasm> {
   state.ewasm.as_ref().map(JsEwasm::from)
 }
 
+/// A single gas-estimate value as reported by solc: either a bounded cost or `"infinite"` for an
+/// estimate solc could not bound (e.g. a function containing a loop with no static bound).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GasEstimateValue {
+  Finite(u128),
+  Infinite,
+}
+
+impl GasEstimateValue {
+  /// Parses one of solc's stringified gas-estimate fields. Returns `None` for anything that is
+  /// neither a decimal integer nor the literal `"infinite"`, since malformed artifacts should not
+  /// crash a diff -- they should simply be reported as unparseable.
+  fn parse(raw: &str) -> Option<Self> {
+    if raw.eq_ignore_ascii_case("infinite") {
+      Some(Self::Infinite)
+    } else {
+      raw.parse::<u128>().ok().map(Self::Finite)
+    }
+  }
+}
+
+/// The change in a single gas-estimate value between two builds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GasEstimateChange {
+  /// Both sides parsed to the same finite value.
+  Unchanged,
+  /// Both sides were finite and differ; `absolute` is `new - old` and `percent` is the change
+  /// relative to `old`, scaled by 1e6 and rounded to preserve precision without floats leaking
+  /// into a hashable/comparable type.
+  Changed { absolute: i128, percent_micros: i64 },
+  /// The estimate went from bounded to `"infinite"`.
+  BecameUnbounded,
+  /// The estimate went from `"infinite"` to bounded.
+  BecameBounded,
+  /// Both sides were `"infinite"`.
+  StillUnbounded,
+  /// The method/bucket exists in the old snapshot only.
+  Removed,
+  /// The method/bucket exists in the new snapshot only.
+  Added,
+  /// One or both sides failed to parse as a gas estimate.
+  Unparseable,
+}
+
+/// One row of a [`diff_gas_estimates`] report: a single method signature (or a creation-bucket
+/// field name) joined across two snapshots, plus the resulting change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasEstimateDelta {
+  pub key: String,
+  pub old_raw: Option<String>,
+  pub new_raw: Option<String>,
+  pub change: GasEstimateChange,
+}
+
+fn diff_gas_estimate_value(key: &str, old_raw: Option<&String>, new_raw: Option<&String>) -> GasEstimateDelta {
+  let old_value = old_raw.and_then(|raw| GasEstimateValue::parse(raw));
+  let new_value = new_raw.and_then(|raw| GasEstimateValue::parse(raw));
+  let change = match (old_raw, new_raw, old_value, new_value) {
+    (None, Some(_), _, _) => GasEstimateChange::Added,
+    (Some(_), None, _, _) => GasEstimateChange::Removed,
+    (Some(_), Some(_), Some(GasEstimateValue::Infinite), Some(GasEstimateValue::Infinite)) => {
+      GasEstimateChange::StillUnbounded
+    }
+    (Some(_), Some(_), Some(GasEstimateValue::Infinite), Some(GasEstimateValue::Finite(_))) => {
+      GasEstimateChange::BecameBounded
+    }
+    (Some(_), Some(_), Some(GasEstimateValue::Finite(_)), Some(GasEstimateValue::Infinite)) => {
+      GasEstimateChange::BecameUnbounded
+    }
+    (Some(_), Some(_), Some(GasEstimateValue::Finite(old)), Some(GasEstimateValue::Finite(new))) => {
+      if old == new {
+        GasEstimateChange::Unchanged
+      } else {
+        let absolute = new as i128 - old as i128;
+        let percent_micros = if old == 0 {
+          0
+        } else {
+          ((absolute * 1_000_000) / old as i128) as i64
+        };
+        GasEstimateChange::Changed { absolute, percent_micros }
+      }
+    }
+    _ => GasEstimateChange::Unparseable,
+  };
+  GasEstimateDelta {
+    key: key.to_owned(),
+    old_raw: old_raw.cloned(),
+    new_raw: new_raw.cloned(),
+    change,
+  }
+}
+
+fn diff_gas_estimate_maps(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<GasEstimateDelta> {
+  let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+  keys.sort();
+  keys.dedup();
+  keys
+    .into_iter()
+    .map(|key| diff_gas_estimate_value(key, old.get(key), new.get(key)))
+    .collect()
+}
+
+/// Joins the `gas_estimates` of two contract snapshots by method signature (for `external`/
+/// `internal` functions) and by field name (for the `creation` bucket: `codeDepositCost`,
+/// `executionCost`, `totalCost`), reporting a per-method delta for CI gas-regression gating.
+/// Methods present in only one snapshot are reported with `change` set to [`GasEstimateChange::Added`]
+/// or [`GasEstimateChange::Removed`]. A side missing `gas_estimates` entirely is treated as an
+/// empty set of methods, so e.g. diffing against a build compiled without gas estimates reports
+/// every method in the other build as added/removed rather than failing.
+pub fn diff_gas_estimates(old: &ContractState, new: &ContractState) -> Vec<GasEstimateDelta> {
+  fn creation_fields(creation: Option<&Creation>) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    if let Some(creation) = creation {
+      fields.insert("creation.codeDepositCost".to_owned(), creation.code_deposit_cost.clone());
+      fields.insert("creation.executionCost".to_owned(), creation.execution_cost.clone());
+      fields.insert("creation.totalCost".to_owned(), creation.total_cost.clone());
+    }
+    fields
+  }
+
+  let old_creation = creation_fields(old.gas_estimates.as_ref().map(|g| &g.creation));
+  let new_creation = creation_fields(new.gas_estimates.as_ref().map(|g| &g.creation));
+  let old_external = old.gas_estimates.as_ref().map(|g| &g.external).cloned().unwrap_or_default();
+  let new_external = new.gas_estimates.as_ref().map(|g| &g.external).cloned().unwrap_or_default();
+  let old_internal = old.gas_estimates.as_ref().map(|g| &g.internal).cloned().unwrap_or_default();
+  let new_internal = new.gas_estimates.as_ref().map(|g| &g.internal).cloned().unwrap_or_default();
+
+  let mut deltas = diff_gas_estimate_maps(&old_creation, &new_creation);
+  deltas.extend(diff_gas_estimate_maps(&old_external, &new_external));
+  deltas.extend(diff_gas_estimate_maps(&old_internal, &new_internal));
+  deltas
+}
+
+/// Controls which [`ContractState`] sections `from_foundry_standard_json`/
+/// `from_configurable_artifact`/`from_foundry_project_artifact` populate from a compiled artifact.
+/// Every flag defaults to `true` ([`Self::ALL`]), matching the crate's historical behaviour of
+/// extracting everything the compiler returned; set individual flags to `false` to skip cloning
+/// sections (IR, assembly, gas estimates, ...) a caller has no use for. This is independent of
+/// [`crate::internal::config::OutputMode`], which controls what solc itself is asked to compute --
+/// this struct only governs what we do with a section once solc has already produced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArtifactFieldSelection {
+  pub abi: bool,
+  pub creation_bytecode: bool,
+  pub deployed_bytecode: bool,
+  pub metadata: bool,
+  pub userdoc: bool,
+  pub devdoc: bool,
+  pub storage_layout: bool,
+  pub method_identifiers: bool,
+  pub function_debug_data: bool,
+  pub gas_estimates: bool,
+  pub assembly: bool,
+  pub legacy_assembly: bool,
+  pub opcodes: bool,
+  pub ir: bool,
+  pub ir_optimized: bool,
+  pub ewasm: bool,
+}
+
+impl ArtifactFieldSelection {
+  pub const ALL: Self = Self {
+    abi: true,
+    creation_bytecode: true,
+    deployed_bytecode: true,
+    metadata: true,
+    userdoc: true,
+    devdoc: true,
+    storage_layout: true,
+    method_identifiers: true,
+    function_debug_data: true,
+    gas_estimates: true,
+    assembly: true,
+    legacy_assembly: true,
+    opcodes: true,
+    ir: true,
+    ir_optimized: true,
+    ewasm: true,
+  };
+
+  /// Named alias for [`Self::ALL`]: every section the compiler produced is kept. Pairs with
+  /// [`Self::MINIMAL`] as the two built-in presets a caller can pick by name instead of spelling
+  /// out every flag, e.g. `CompilerConfigOptions { artifact_field_selection:
+  /// Some(ArtifactFieldSelection::FULL.into()), .. }`.
+  pub const FULL: Self = Self::ALL;
+
+  /// Keeps only what's needed to deploy and call a contract -- ABI plus creation/deployed
+  /// bytecode -- and skips every heavier section (metadata, doc comments, debug data, gas
+  /// estimates, assembly, IR, ewasm). Trims serialized output size dramatically for callers that
+  /// don't need them, without recompiling: this only governs what
+  /// [`crate::compiler::output::build_compile_output`] keeps from an already-produced compiler
+  /// output.
+  pub const MINIMAL: Self = Self {
+    abi: true,
+    creation_bytecode: true,
+    deployed_bytecode: true,
+    metadata: false,
+    userdoc: false,
+    devdoc: false,
+    storage_layout: false,
+    method_identifiers: false,
+    function_debug_data: false,
+    gas_estimates: false,
+    assembly: false,
+    legacy_assembly: false,
+    opcodes: false,
+    ir: false,
+    ir_optimized: false,
+    ewasm: false,
+  };
+}
+
+impl Default for ArtifactFieldSelection {
+  fn default() -> Self {
+    Self::ALL
+  }
+}
+
+/// JS-facing [`ArtifactFieldSelection`] override: every field is optional and falls back to
+/// [`ArtifactFieldSelection::ALL`] when omitted, so `{ abi: true, deployedBytecode: true }` skips
+/// everything else without callers having to spell out `false` for every other section.
+#[napi(object, js_name = "ArtifactFieldSelection")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsArtifactFieldSelection {
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub abi: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub creation_bytecode: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub deployed_bytecode: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub metadata: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub userdoc: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub devdoc: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub storage_layout: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub method_identifiers: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub function_debug_data: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub gas_estimates: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub assembly: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub legacy_assembly: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub opcodes: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub ir: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub ir_optimized: Option<bool>,
+  #[napi(ts_type = "boolean | null | undefined")]
+  pub ewasm: Option<bool>,
+}
+
+impl From<JsArtifactFieldSelection> for ArtifactFieldSelection {
+  fn from(options: JsArtifactFieldSelection) -> Self {
+    let all = ArtifactFieldSelection::ALL;
+    Self {
+      abi: options.abi.unwrap_or(all.abi),
+      creation_bytecode: options.creation_bytecode.unwrap_or(all.creation_bytecode),
+      deployed_bytecode: options.deployed_bytecode.unwrap_or(all.deployed_bytecode),
+      metadata: options.metadata.unwrap_or(all.metadata),
+      userdoc: options.userdoc.unwrap_or(all.userdoc),
+      devdoc: options.devdoc.unwrap_or(all.devdoc),
+      storage_layout: options.storage_layout.unwrap_or(all.storage_layout),
+      method_identifiers: options.method_identifiers.unwrap_or(all.method_identifiers),
+      function_debug_data: options
+        .function_debug_data
+        .unwrap_or(all.function_debug_data),
+      gas_estimates: options.gas_estimates.unwrap_or(all.gas_estimates),
+      assembly: options.assembly.unwrap_or(all.assembly),
+      legacy_assembly: options.legacy_assembly.unwrap_or(all.legacy_assembly),
+      opcodes: options.opcodes.unwrap_or(all.opcodes),
+      ir: options.ir.unwrap_or(all.ir),
+      ir_optimized: options.ir_optimized.unwrap_or(all.ir_optimized),
+      ewasm: options.ewasm.unwrap_or(all.ewasm),
+    }
+  }
+}
+
 pub fn from_foundry_standard_json(
   name: impl Into<String>,
   contract: &FoundryContract,
 ) -> ContractState {
-  build_from_standard_json(&name.into(), contract)
+  from_foundry_standard_json_with_selection(name, contract, ArtifactFieldSelection::ALL)
+}
+
+pub fn from_foundry_standard_json_with_selection(
+  name: impl Into<String>,
+  contract: &FoundryContract,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  build_from_standard_json(&name.into(), contract, None, selection)
+}
+
+/// Like [`from_foundry_standard_json_with_selection`], but also populates
+/// [`ContractState::ast`] from `source_ast` -- standard-JSON solc output has no per-contract AST
+/// (only one per source file), so callers that already have the matching `sources[path].ast`
+/// entry in hand (e.g. [`crate::compiler::output::build_compile_output`]) pass it through here.
+pub fn from_foundry_standard_json_with_ast(
+  name: impl Into<String>,
+  contract: &FoundryContract,
+  source_ast: Option<&Value>,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  build_from_standard_json(&name.into(), contract, source_ast, selection)
 }
 
 pub fn from_configurable_artifact(
   name: impl Into<String>,
   artifact: &ConfigurableContractArtifact,
 ) -> ContractState {
-  build_from_configurable_artifact(&name.into(), artifact)
+  from_configurable_artifact_with_selection(name, artifact, ArtifactFieldSelection::ALL)
+}
+
+pub fn from_configurable_artifact_with_selection(
+  name: impl Into<String>,
+  artifact: &ConfigurableContractArtifact,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  build_from_configurable_artifact(&name.into(), artifact, selection)
 }
 
 pub fn from_foundry_project_artifact(
   name: impl Into<String>,
   artifact: &impl Artifact,
 ) -> ContractState {
-  build_from_project_artifact(&name.into(), artifact)
+  from_foundry_project_artifact_with_selection(name, artifact, ArtifactFieldSelection::ALL)
 }
 
-fn build_from_project_artifact(name: &str, artifact: &impl Artifact) -> ContractState {
-  let mut builder = ContractBuilder::new(name.to_string());
-  let bytecode_cow = artifact.get_contract_bytecode();
+pub fn from_foundry_project_artifact_with_selection(
+  name: impl Into<String>,
+  artifact: &impl Artifact,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  build_from_project_artifact(&name.into(), artifact, selection)
+}
 
-  if let Some(abi) = serialize_optional(&bytecode_cow.abi) {
-    builder = builder.set_abi(Some(abi));
+/// Walks `root` for files matching any of `patterns` and folds every contract they contain into a
+/// single map, so callers can ingest an entire compiled Foundry `out/` tree in one call instead of
+/// parsing each artifact file individually. Supports the glob subset Foundry output actually
+/// needs: a `**/` prefix recurses into every subdirectory, and the remainder matches the file name
+/// by exact match or `*`-suffix (e.g. `**/*.json`, `*.json`).
+///
+/// Each matched file is classified by its top-level shape -- a `contracts` key means a full solc
+/// standard-JSON compiler output (walked via [`from_foundry_standard_json`] for every contract it
+/// contains), anything else is assumed to be a single Foundry [`ConfigurableContractArtifact`]
+/// (parsed via [`from_configurable_artifact`], named after the file). Files that don't parse as
+/// either are skipped.
+///
+/// Keyed by contract name; when the same name shows up from more than one source file, both the
+/// existing and the new entry are re-keyed `source_path:name` so neither is silently overwritten.
+pub fn load_artifacts_glob(root: &Path, patterns: &[&str]) -> BTreeMap<String, ContractState> {
+  let mut states = BTreeMap::new();
+  let mut source_paths: BTreeMap<String, String> = BTreeMap::new();
+
+  for path in find_glob_matches(root, patterns) {
+    let Ok(raw) = fs::read_to_string(&path) else {
+      continue;
+    };
+    let Ok(value) = serde_json::from_str::<Value>(&raw) else {
+      continue;
+    };
+    let source_path = path.to_string_lossy().into_owned();
+
+    if value.get("contracts").is_some() {
+      let Ok(output) = serde_json::from_value::<CompilerOutput>(value) else {
+        continue;
+      };
+      for (file, contracts) in &output.contracts {
+        for (name, contract) in contracts {
+          let mut state = from_foundry_standard_json(name.clone(), contract);
+          state.source_path = Some(file.clone());
+          insert_artifact(&mut states, &mut source_paths, name.clone(), file.clone(), state);
+        }
+      }
+    } else if let Ok(artifact) = serde_json::from_value::<ConfigurableContractArtifact>(value) {
+      let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+      let mut state = from_configurable_artifact(name.clone(), &artifact);
+      state.source_path = Some(source_path.clone());
+      insert_artifact(&mut states, &mut source_paths, name, source_path, state);
+    }
   }
 
-  if let Some(source) = bytecode_cow.bytecode.as_ref() {
-    if let Some(bytecode) = ContractBytecode::from_compact_bytecode(source.as_ref()) {
-      builder = builder.set_creation_bytecode(Some(bytecode));
+  states
+}
+
+/// Inserts `state` under `name`, re-keying both the incoming and any previously-stored entry as
+/// `source_path:name` the moment two different source files produce the same contract name.
+fn insert_artifact(
+  states: &mut BTreeMap<String, ContractState>,
+  source_paths: &mut BTreeMap<String, String>,
+  name: String,
+  source_path: String,
+  state: ContractState,
+) {
+  match source_paths.get(&name) {
+    None => {
+      source_paths.insert(name.clone(), source_path);
+      states.insert(name, state);
     }
-    if let Some(map) = source.as_ref().source_map.clone() {
-      builder = builder.set_creation_source_map(Some(map));
+    Some(existing) if existing == &source_path => {
+      states.insert(name, state);
+    }
+    Some(existing) => {
+      if let Some(existing_state) = states.remove(&name) {
+        states.insert(format!("{existing}:{name}"), existing_state);
+      }
+      states.insert(format!("{source_path}:{name}"), state);
     }
   }
+}
 
-  if let Some(deployed) = bytecode_cow.deployed_bytecode.as_ref() {
-    let immutable_refs = deserialize_immutable_refs(&deployed.as_ref().immutable_references);
-    let bytecode = ContractBytecode::from_compact_deployed_bytecode(deployed.as_ref());
-    builder = builder
-      .set_deployed_bytecode(bytecode)
-      .set_immutable_references(optional_map(immutable_refs));
-  }
-
-  builder.finish()
+/// Resolves `patterns` against `root`; see [`load_artifacts_glob`] for the supported subset.
+fn find_glob_matches(root: &Path, patterns: &[&str]) -> Vec<PathBuf> {
+  let mut matches = Vec::new();
+  for pattern in patterns {
+    let (recursive, file_pattern) = match pattern.strip_prefix("**/") {
+      Some(rest) => (true, rest),
+      None => (false, *pattern),
+    };
+    collect_glob_matches(root, file_pattern, recursive, &mut matches);
+  }
+  matches
 }
 
-fn build_from_standard_json(name: &str, contract: &FoundryContract) -> ContractState {
-  let mut builder = ContractBuilder::new(name.to_string());
+fn collect_glob_matches(dir: &Path, file_pattern: &str, recursive: bool, matches: &mut Vec<PathBuf>) {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return;
+  };
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if path.is_dir() {
+      if recursive {
+        collect_glob_matches(&path, file_pattern, recursive, matches);
+      }
+      continue;
+    }
+    if matches_glob_file_pattern(&path, file_pattern) {
+      matches.push(path);
+    }
+  }
+}
 
-  if let Some(abi) = serialize_optional(&contract.abi) {
-    builder = builder.set_abi(Some(abi));
+fn matches_glob_file_pattern(path: &Path, pattern: &str) -> bool {
+  let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+    return false;
+  };
+  match pattern.strip_prefix('*') {
+    Some(suffix) => file_name.ends_with(suffix),
+    None => file_name == pattern,
   }
+}
 
-  if let Some(evm) = &contract.evm {
-    builder = apply_standard_json_evm(
-      builder,
-      evm.bytecode.as_ref(),
-      evm.deployed_bytecode.as_ref(),
-    );
+/// Where [`write_artifact_with_extras`] places one of [`ContractState`]'s heavier sections.
+/// Mirrors [`ArtifactFieldSelection`]'s per-section shape but governs output placement instead of
+/// what got read from the compiler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtraOutputMode {
+  /// Leave the section out of the written output entirely.
+  Omit,
+  /// Write the section inline, within the core artifact JSON.
+  Inline,
+  /// Write the section to its own `<Name>.<section>.json` file next to the core artifact.
+  Sidecar,
+}
 
-    if !evm.method_identifiers.is_empty() {
-      builder = builder.set_method_identifiers(Some(evm.method_identifiers.clone()));
-    }
+/// Per-section [`ExtraOutputMode`] selection for [`write_artifact_with_extras`]. `abi` and the
+/// creation/deployed bytecodes are always written inline in the core artifact and aren't
+/// represented here. Defaults to [`Self::NONE`], leaving just the slim core artifact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtraOutputValues {
+  pub metadata: ExtraOutputMode,
+  pub userdoc: ExtraOutputMode,
+  pub devdoc: ExtraOutputMode,
+  pub storage_layout: ExtraOutputMode,
+  pub gas_estimates: ExtraOutputMode,
+  pub function_debug_data: ExtraOutputMode,
+  pub ewasm: ExtraOutputMode,
+}
 
-    builder = builder
-      .set_assembly(evm.assembly.clone())
-      .set_legacy_assembly(evm.legacy_assembly.clone())
-      .set_gas_estimates(evm.gas_estimates.clone());
-  }
+impl ExtraOutputValues {
+  pub const NONE: Self = Self {
+    metadata: ExtraOutputMode::Omit,
+    userdoc: ExtraOutputMode::Omit,
+    devdoc: ExtraOutputMode::Omit,
+    storage_layout: ExtraOutputMode::Omit,
+    gas_estimates: ExtraOutputMode::Omit,
+    function_debug_data: ExtraOutputMode::Omit,
+    ewasm: ExtraOutputMode::Omit,
+  };
+
+  pub const ALL_INLINE: Self = Self {
+    metadata: ExtraOutputMode::Inline,
+    userdoc: ExtraOutputMode::Inline,
+    devdoc: ExtraOutputMode::Inline,
+    storage_layout: ExtraOutputMode::Inline,
+    gas_estimates: ExtraOutputMode::Inline,
+    function_debug_data: ExtraOutputMode::Inline,
+    ewasm: ExtraOutputMode::Inline,
+  };
+
+  pub const ALL_SIDECAR: Self = Self {
+    metadata: ExtraOutputMode::Sidecar,
+    userdoc: ExtraOutputMode::Sidecar,
+    devdoc: ExtraOutputMode::Sidecar,
+    storage_layout: ExtraOutputMode::Sidecar,
+    gas_estimates: ExtraOutputMode::Sidecar,
+    function_debug_data: ExtraOutputMode::Sidecar,
+    ewasm: ExtraOutputMode::Sidecar,
+  };
+}
 
-  if let Some(metadata) = contract.metadata.as_ref() {
-    builder = builder.set_metadata(serialize(metadata));
+impl Default for ExtraOutputValues {
+  fn default() -> Self {
+    Self::NONE
   }
+}
 
-  builder
-    .set_userdoc(serialize(&contract.userdoc))
-    .set_devdoc(serialize(&contract.devdoc))
-    .set_storage_layout(serialize(&contract.storage_layout))
-    .set_ir(contract.ir.clone())
-    .set_ir_optimized(contract.ir_optimized.clone())
-    .set_ewasm(contract.ewasm.clone())
-    .finish()
+/// Result of [`write_artifact_with_extras`]: the core artifact's path, plus any sidecar files
+/// written alongside it (empty unless `selection` promoted at least one section to
+/// [`ExtraOutputMode::Sidecar`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrittenArtifact {
+  pub core_path: PathBuf,
+  pub sidecar_paths: Vec<PathBuf>,
+}
+
+/// Serializes `state` to `<dir>/<state.name>.json`, keeping `abi` and the creation/deployed
+/// bytecodes inline regardless of `selection`, and routing every other heavy section
+/// ([`ContractState::metadata`], [`ContractState::userdoc`], [`ContractState::devdoc`],
+/// [`ContractState::storage_layout`], [`ContractState::gas_estimates`],
+/// [`ContractState::function_debug_data`], [`ContractState::ewasm`]) per `selection`: omitted,
+/// inlined in the core artifact, or promoted to its own `<Name>.<section>.json` sidecar next to
+/// it. This is the inverse of [`load_artifacts_glob`]/[`from_configurable_artifact`], trading a
+/// slim core artifact for richer data kept available on demand -- the same tradeoff Foundry's
+/// `ConfigurableArtifacts` handler makes by only populating essential entries by default.
+pub fn write_artifact_with_extras(
+  state: &ContractState,
+  dir: &Path,
+  selection: ExtraOutputValues,
+) -> Result<WrittenArtifact> {
+  map_err_with_context(fs::create_dir_all(dir), "creating artifact output directory")?;
+
+  let mut core = serde_json::json!({
+    "abi": state.abi,
+    "bytecode": state.creation_bytecode,
+    "deployedBytecode": state.deployed_bytecode,
+  });
+  let mut sidecar_paths = Vec::new();
+
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "metadata",
+    selection.metadata,
+    serialize_optional(&state.metadata),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "userdoc",
+    selection.userdoc,
+    serialize_optional(&state.userdoc),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "devdoc",
+    selection.devdoc,
+    serialize_optional(&state.devdoc),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "storageLayout",
+    selection.storage_layout,
+    serialize_optional(&state.storage_layout),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "gasEstimates",
+    selection.gas_estimates,
+    serialize_optional(&state.gas_estimates),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "functionDebugData",
+    selection.function_debug_data,
+    serialize_optional(&state.function_debug_data),
+  )?;
+  place_extra_section(
+    &mut core,
+    &mut sidecar_paths,
+    dir,
+    &state.name,
+    "ewasm",
+    selection.ewasm,
+    serialize_optional(&state.ewasm),
+  )?;
+
+  let core_path = dir.join(format!("{}.json", state.name));
+  write_json_file(&core_path, &core)?;
+
+  Ok(WrittenArtifact {
+    core_path,
+    sidecar_paths,
+  })
+}
+
+/// Applies one [`ExtraOutputValues`] field's [`ExtraOutputMode`] to `value`: left out of `core`
+/// entirely, merged into `core` under `key`, or written to `<dir>/<contract_name>.<key>.json` and
+/// recorded in `sidecar_paths`. A `None` value (the section wasn't populated on the state) is
+/// always a no-op, regardless of `mode`.
+fn place_extra_section(
+  core: &mut Value,
+  sidecar_paths: &mut Vec<PathBuf>,
+  dir: &Path,
+  contract_name: &str,
+  key: &str,
+  mode: ExtraOutputMode,
+  value: Option<Value>,
+) -> Result<()> {
+  let Some(value) = value else {
+    return Ok(());
+  };
+  match mode {
+    ExtraOutputMode::Omit => {}
+    ExtraOutputMode::Inline => {
+      core[key] = value;
+    }
+    ExtraOutputMode::Sidecar => {
+      let path = dir.join(format!("{contract_name}.{key}.json"));
+      write_json_file(&path, &value)?;
+      sidecar_paths.push(path);
+    }
+  }
+  Ok(())
+}
+
+fn write_json_file(path: &Path, value: &Value) -> Result<()> {
+  let rendered = serde_json::to_string_pretty(value)
+    .map_err(|err| Error::with_context("serializing artifact JSON", err))?;
+  map_err_with_context(fs::write(path, rendered), format!("writing {}", path.display()))
+}
+
+fn build_from_project_artifact(
+  name: &str,
+  artifact: &impl Artifact,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  let mut builder = ContractBuilder::new(name.to_string());
+  let bytecode_cow = artifact.get_contract_bytecode();
+
+  if selection.abi {
+    if let Some(abi) = serialize_optional(&bytecode_cow.abi) {
+      builder = builder.set_abi(Some(abi));
+    }
+  }
+
+  if selection.creation_bytecode {
+    if let Some(source) = bytecode_cow.bytecode.as_ref() {
+      if let Some(bytecode) = ContractBytecode::from_compact_bytecode(source.as_ref()) {
+        builder = builder.set_creation_bytecode(Some(bytecode));
+      }
+      if let Some(map) = source.as_ref().source_map.clone() {
+        builder = builder.set_creation_source_map(Some(map));
+      }
+    }
+  }
+
+  if selection.deployed_bytecode {
+    if let Some(deployed) = bytecode_cow.deployed_bytecode.as_ref() {
+      let immutable_refs = deserialize_immutable_refs(&deployed.as_ref().immutable_references);
+      let bytecode = ContractBytecode::from_compact_deployed_bytecode(deployed.as_ref());
+      let deployed_source_map = deployed
+        .as_ref()
+        .bytecode
+        .as_ref()
+        .and_then(|bytecode| bytecode.source_map.clone());
+      builder = builder
+        .set_deployed_bytecode(bytecode)
+        .set_immutable_references(optional_map(immutable_refs))
+        .set_deployed_source_map(deployed_source_map);
+    }
+  }
+
+  builder.finish()
+}
+
+fn build_from_standard_json(
+  name: &str,
+  contract: &FoundryContract,
+  source_ast: Option<&Value>,
+  selection: ArtifactFieldSelection,
+) -> ContractState {
+  let mut builder = ContractBuilder::new(name.to_string()).set_ast(source_ast.cloned());
+
+  if selection.abi {
+    if let Some(abi) = serialize_optional(&contract.abi) {
+      builder = builder.set_abi(Some(abi));
+    }
+  }
+
+  if let Some(evm) = &contract.evm {
+    builder = apply_standard_json_evm(
+      builder,
+      evm.bytecode.as_ref(),
+      evm.deployed_bytecode.as_ref(),
+      selection,
+    );
+
+    if selection.method_identifiers && !evm.method_identifiers.is_empty() {
+      builder = builder.set_method_identifiers(Some(evm.method_identifiers.clone()));
+    }
+
+    if selection.assembly {
+      builder = builder.set_assembly(evm.assembly.clone());
+    }
+    if selection.legacy_assembly {
+      builder = builder.set_legacy_assembly(evm.legacy_assembly.clone());
+    }
+    if selection.gas_estimates {
+      builder = builder.set_gas_estimates(evm.gas_estimates.clone());
+    }
+  }
+
+  if selection.metadata {
+    if let Some(metadata) = contract.metadata.as_ref() {
+      builder = builder.set_metadata(serialize(metadata));
+    }
+  }
+
+  if selection.userdoc {
+    builder = builder.set_userdoc(serialize(&contract.userdoc));
+  }
+  if selection.devdoc {
+    builder = builder.set_devdoc(serialize(&contract.devdoc));
+  }
+  if selection.storage_layout {
+    builder = builder.set_storage_layout(serialize(&contract.storage_layout));
+  }
+  if selection.ir {
+    builder = builder.set_ir(contract.ir.clone());
+  }
+  if selection.ir_optimized {
+    builder = builder.set_ir_optimized(contract.ir_optimized.clone());
+  }
+  if selection.ewasm {
+    builder = builder.set_ewasm(contract.ewasm.clone());
+  }
+
+  builder.finish()
 }
 
 fn build_from_configurable_artifact(
   name: &str,
   artifact: &ConfigurableContractArtifact,
+  selection: ArtifactFieldSelection,
 ) -> ContractState {
   let mut builder = ContractBuilder::new(name.to_string());
 
-  if let Some(abi) = serialize_optional(&artifact.abi) {
-    builder = builder.set_abi(Some(abi));
+  if selection.abi {
+    if let Some(abi) = serialize_optional(&artifact.abi) {
+      builder = builder.set_abi(Some(abi));
+    }
   }
 
   builder = apply_compact_evm_artifacts(
     builder,
     artifact.bytecode.as_ref(),
     artifact.deployed_bytecode.as_ref(),
+    selection,
   );
 
-  if let Some(storage_layout) = artifact.storage_layout.as_ref() {
-    builder = builder.set_storage_layout(serialize(storage_layout));
+  if selection.storage_layout {
+    if let Some(storage_layout) = artifact.storage_layout.as_ref() {
+      builder = builder.set_storage_layout(serialize(storage_layout));
+    }
   }
 
-  if let Some(userdoc) = artifact.userdoc.as_ref() {
-    builder = builder.set_userdoc(serialize(userdoc));
+  if selection.userdoc {
+    if let Some(userdoc) = artifact.userdoc.as_ref() {
+      builder = builder.set_userdoc(serialize(userdoc));
+    }
   }
 
-  if let Some(devdoc) = artifact.devdoc.as_ref() {
-    builder = builder.set_devdoc(serialize(devdoc));
+  if selection.devdoc {
+    if let Some(devdoc) = artifact.devdoc.as_ref() {
+      builder = builder.set_devdoc(serialize(devdoc));
+    }
   }
 
-  if let Some(metadata) = artifact.metadata.as_ref() {
-    builder = builder.set_metadata(serialize(metadata));
-  } else if let Some(raw) = artifact.raw_metadata.as_ref() {
-    builder = builder.set_metadata(Some(Value::String(raw.clone())));
+  if selection.metadata {
+    if let Some(metadata) = artifact.metadata.as_ref() {
+      builder = builder.set_metadata(serialize(metadata));
+    } else if let Some(raw) = artifact.raw_metadata.as_ref() {
+      builder = builder.set_metadata(Some(Value::String(raw.clone())));
+    }
   }
 
-  builder = builder
-    .set_function_debug_data(artifact.function_debug_data.clone())
-    .set_gas_estimates(artifact.gas_estimates.clone())
-    .set_assembly(artifact.assembly.clone())
-    .set_legacy_assembly(artifact.legacy_assembly.clone())
-    .set_opcodes(artifact.opcodes.clone())
-    .set_method_identifiers(artifact.method_identifiers.clone())
-    .set_ir(artifact.ir.clone())
-    .set_ir_optimized(artifact.ir_optimized.clone())
-    .set_ewasm(artifact.ewasm.clone());
+  if selection.function_debug_data {
+    builder = builder.set_function_debug_data(artifact.function_debug_data.clone());
+  }
+  if selection.gas_estimates {
+    builder = builder.set_gas_estimates(artifact.gas_estimates.clone());
+  }
+  if selection.assembly {
+    builder = builder.set_assembly(artifact.assembly.clone());
+  }
+  if selection.legacy_assembly {
+    builder = builder.set_legacy_assembly(artifact.legacy_assembly.clone());
+  }
+  if selection.opcodes {
+    builder = builder.set_opcodes(artifact.opcodes.clone());
+  }
+  if selection.method_identifiers {
+    builder = builder.set_method_identifiers(artifact.method_identifiers.clone());
+  }
+  if selection.ir {
+    builder = builder.set_ir(artifact.ir.clone());
+  }
+  if selection.ir_optimized {
+    builder = builder.set_ir_optimized(artifact.ir_optimized.clone());
+  }
+  if selection.ewasm {
+    builder = builder.set_ewasm(artifact.ewasm.clone());
+  }
 
   if let Some(id) = artifact.id {
     builder = builder.set_source_id(Some(id));
   }
 
+  builder = builder.set_ast(serialize_optional(&artifact.ast));
+
   builder.finish()
 }
 
@@ -548,20 +1958,40 @@ fn apply_standard_json_evm(
   mut builder: ContractBuilder,
   bytecode: Option<&Bytecode>,
   deployed: Option<&DeployedBytecode>,
+  selection: ArtifactFieldSelection,
 ) -> ContractBuilder {
-  if let Some(bytecode) = bytecode {
-    builder = builder.set_creation_bytecode(ContractBytecode::from_bytecode(bytecode));
-    if let Some(map) = &bytecode.source_map {
-      builder = builder.set_creation_source_map(Some(map.clone()));
+  if selection.creation_bytecode {
+    if let Some(bytecode) = bytecode {
+      builder = builder
+        .set_creation_bytecode(ContractBytecode::from_bytecode(bytecode))
+        .set_creation_link_references(optional_map(deserialize_link_references(
+          &bytecode.link_references,
+        )));
+      if let Some(map) = &bytecode.source_map {
+        builder = builder.set_creation_source_map(Some(map.clone()));
+      }
     }
   }
 
-  if let Some(deployed) = deployed {
-    let bytecode = ContractBytecode::from_deployed_bytecode(deployed);
-    let immutable_refs = deserialize_immutable_refs(&deployed.immutable_references);
-    builder = builder
-      .set_deployed_bytecode(bytecode)
-      .set_immutable_references(optional_map(immutable_refs));
+  if selection.deployed_bytecode {
+    if let Some(deployed) = deployed {
+      let bytecode = ContractBytecode::from_deployed_bytecode(deployed);
+      let immutable_refs = deserialize_immutable_refs(&deployed.immutable_references);
+      let deployed_source_map = deployed
+        .bytecode
+        .as_ref()
+        .and_then(|bytecode| bytecode.source_map.clone());
+      let deployed_link_refs = deployed
+        .bytecode
+        .as_ref()
+        .map(|bytecode| deserialize_link_references(&bytecode.link_references))
+        .unwrap_or_default();
+      builder = builder
+        .set_deployed_bytecode(bytecode)
+        .set_immutable_references(optional_map(immutable_refs))
+        .set_deployed_source_map(deployed_source_map)
+        .set_deployed_link_references(optional_map(deployed_link_refs));
+    }
   }
 
   builder
@@ -571,20 +2001,40 @@ fn apply_compact_evm_artifacts(
   mut builder: ContractBuilder,
   bytecode: Option<&CompactBytecode>,
   deployed: Option<&CompactDeployedBytecode>,
+  selection: ArtifactFieldSelection,
 ) -> ContractBuilder {
-  if let Some(bytecode) = bytecode {
-    builder = builder.set_creation_bytecode(ContractBytecode::from_compact_bytecode(bytecode));
-    if let Some(map) = &bytecode.source_map {
-      builder = builder.set_creation_source_map(Some(map.clone()));
+  if selection.creation_bytecode {
+    if let Some(bytecode) = bytecode {
+      builder = builder
+        .set_creation_bytecode(ContractBytecode::from_compact_bytecode(bytecode))
+        .set_creation_link_references(optional_map(deserialize_link_references(
+          &bytecode.link_references,
+        )));
+      if let Some(map) = &bytecode.source_map {
+        builder = builder.set_creation_source_map(Some(map.clone()));
+      }
     }
   }
 
-  if let Some(deployed) = deployed {
-    let bytecode = ContractBytecode::from_compact_deployed_bytecode(deployed);
-    let immutable_refs = deserialize_immutable_refs(&deployed.immutable_references);
-    builder = builder
-      .set_deployed_bytecode(bytecode)
-      .set_immutable_references(optional_map(immutable_refs));
+  if selection.deployed_bytecode {
+    if let Some(deployed) = deployed {
+      let bytecode = ContractBytecode::from_compact_deployed_bytecode(deployed);
+      let immutable_refs = deserialize_immutable_refs(&deployed.immutable_references);
+      let deployed_source_map = deployed
+        .bytecode
+        .as_ref()
+        .and_then(|bytecode| bytecode.source_map.clone());
+      let deployed_link_refs = deployed
+        .bytecode
+        .as_ref()
+        .map(|bytecode| deserialize_link_references(&bytecode.link_references))
+        .unwrap_or_default();
+      builder = builder
+        .set_deployed_bytecode(bytecode)
+        .set_immutable_references(optional_map(immutable_refs))
+        .set_deployed_source_map(deployed_source_map)
+        .set_deployed_link_references(optional_map(deployed_link_refs));
+    }
   }
 
   builder
@@ -618,6 +2068,21 @@ where
     .unwrap_or_default()
 }
 
+/// Same shape conversion as [`deserialize_immutable_refs`], but for `linkReferences`, which nests
+/// an extra level (source file -> library name -> offsets) versus `immutableReferences` (label ->
+/// offsets).
+pub fn deserialize_link_references<T>(
+  source: &BTreeMap<String, BTreeMap<String, Vec<T>>>,
+) -> BTreeMap<String, BTreeMap<String, Vec<ImmutableSlot>>>
+where
+  T: Serialize,
+{
+  serde_json::to_value(source)
+    .ok()
+    .and_then(|value| serde_json::from_value(value).ok())
+    .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -829,6 +2294,46 @@ mod tests {
     assert_eq!(slots[0].length, 32);
   }
 
+  #[test]
+  fn from_configurable_artifact_carries_ast() {
+    let json = r#"{
+      "abi": [],
+      "ast": { "nodeType": "SourceUnit", "nodes": [] },
+      "id": 3
+    }"#;
+
+    let artifact: ConfigurableContractArtifact = serde_json::from_str(json).expect("artifact");
+    let state = from_configurable_artifact("Library", &artifact);
+    assert_eq!(
+      state.ast.as_ref().and_then(|ast| ast.get("nodeType")),
+      Some(&Value::String("SourceUnit".to_string()))
+    );
+  }
+
+  #[test]
+  fn from_foundry_standard_json_with_ast_carries_caller_supplied_ast() {
+    let json = r#"{
+      "abi": [],
+      "evm": {
+        "bytecode": { "object": "0x" },
+        "deployedBytecode": {}
+      }
+    }"#;
+
+    let contract: FoundryContract = serde_json::from_str(json).expect("contract");
+    let source_ast = serde_json::json!({ "nodeType": "SourceUnit", "nodes": [] });
+    let state = from_foundry_standard_json_with_ast(
+      "Sample",
+      &contract,
+      Some(&source_ast),
+      ArtifactFieldSelection::ALL,
+    );
+    assert_eq!(state.ast.as_ref(), Some(&source_ast));
+
+    let without_ast = from_foundry_standard_json("Sample", &contract);
+    assert!(without_ast.ast.is_none());
+  }
+
   #[test]
   fn from_standard_json_without_optional_fields_leaves_defaults() {
     let json = r#"{
@@ -849,6 +2354,355 @@ mod tests {
     assert!(state.gas_estimates.is_none());
   }
 
+  #[test]
+  fn slug_versioned_disambiguates_same_contract_by_solc_version() {
+    let mut state = ContractState::new("Fixture");
+    state.source_path = Some("src/Fixture.sol".into());
+    state.version = Some(Version::new(0, 8, 19));
+
+    assert_eq!(state.slug(), "Fixture.json:Fixture");
+    assert_eq!(state.slug_versioned(), "Fixture.0.8.19.json:Fixture");
+
+    state.version = Some(Version::new(0, 8, 20));
+    assert_eq!(state.slug_versioned(), "Fixture.0.8.20.json:Fixture");
+  }
+
+  #[test]
+  fn slug_falls_back_to_name_without_source_path_or_version() {
+    let state = ContractState::new("Fixture");
+    assert_eq!(state.slug(), "Fixture.json:Fixture");
+    assert_eq!(state.slug_versioned(), "Fixture.json:Fixture");
+    assert!(state.artifact_id().is_none());
+  }
+
+  #[test]
+  fn decode_source_map_inherits_unspecified_fields() {
+    let entries = decode_source_map("1:2:0:-:0;3::1:i;::::2");
+    assert_eq!(entries.len(), 3);
+
+    assert_eq!(entries[0].start, 1);
+    assert_eq!(entries[0].length, 2);
+    assert_eq!(entries[0].file_index, 0);
+    assert_eq!(entries[0].jump, JumpType::Regular);
+    assert_eq!(entries[0].modifier_depth, 0);
+
+    // Inherits length/modifier_depth from the previous entry, overrides start/file_index/jump.
+    assert_eq!(entries[1].start, 3);
+    assert_eq!(entries[1].length, 2);
+    assert_eq!(entries[1].file_index, 1);
+    assert_eq!(entries[1].jump, JumpType::Into);
+    assert_eq!(entries[1].modifier_depth, 0);
+
+    // Inherits everything but modifier_depth from the previous entry.
+    assert_eq!(entries[2].start, 3);
+    assert_eq!(entries[2].length, 2);
+    assert_eq!(entries[2].file_index, 1);
+    assert_eq!(entries[2].jump, JumpType::Into);
+    assert_eq!(entries[2].modifier_depth, 2);
+  }
+
+  #[test]
+  fn decode_source_map_handles_no_source_file_index() {
+    let entries = decode_source_map("0:0:-1:-:0");
+    assert_eq!(entries[0].file_index, -1);
+  }
+
+  #[test]
+  fn source_map_entry_resolves_to_source_location() {
+    let entries = decode_source_map("1:2:0:-:0");
+    let mut sources = BTreeMap::new();
+    sources.insert(0, "src/Fixture.sol".to_string());
+
+    let location = entries[0].source_location(&sources).expect("resolved location");
+    assert_eq!(location.file, "src/Fixture.sol");
+    assert_eq!(location.start, 1);
+    assert_eq!(location.end, 3);
+  }
+
+  #[test]
+  fn source_map_entry_with_no_source_does_not_resolve() {
+    let entries = decode_source_map("0:0:-1:-:0");
+    assert!(entries[0].source_location(&BTreeMap::new()).is_none());
+  }
+
+  #[test]
+  fn build_instruction_pcs_skips_push_operands() {
+    // PUSH1 0x01, PUSH2 0x00 0x02, STOP
+    let bytecode = [0x60, 0x01, 0x61, 0x00, 0x02, 0x00];
+    assert_eq!(build_instruction_pcs(&bytecode), vec![0, 2, 5]);
+  }
+
+  #[test]
+  fn source_map_maps_pc_to_decoded_entry() {
+    // PUSH1 0x01 (pc 0-1), PUSH1 0x02 (pc 2-3), STOP (pc 4)
+    let bytecode = [0x60, 0x01, 0x60, 0x02, 0x00];
+    let entries = decode_source_map("1:1:0:-:0;2:1:0:-:0;3:1:0:-:0");
+    let map = SourceMap::new(entries, &bytecode);
+
+    assert_eq!(map.map_pc(0).unwrap().start, 1);
+    // Falls back to the instruction covering the PC, even mid-operand.
+    assert_eq!(map.map_pc(1).unwrap().start, 1);
+    assert_eq!(map.map_pc(2).unwrap().start, 2);
+    assert_eq!(map.map_pc(4).unwrap().start, 3);
+  }
+
+  #[test]
+  fn source_map_map_pc_at_first_instruction_is_some() {
+    let bytecode = [0x00];
+    let entries = decode_source_map("0:1:0:-:0");
+    let map = SourceMap::new(entries, &bytecode);
+    assert!(map.map_pc(0).is_some());
+  }
+
+  #[test]
+  fn contract_state_creation_source_map_requires_bytecode_and_decoded_map() {
+    let mut state = ContractState::new("Fixture");
+    assert!(state.creation_source_map().is_none());
+
+    state.creation_bytecode = Some(ContractBytecode::from_bytes(vec![0x60, 0x01, 0x00]));
+    assert!(state.creation_source_map().is_none());
+
+    state.creation_source_map_decoded = Some(decode_source_map("1:1:0:-:0;2:1:0:-:0"));
+    let map = state.creation_source_map().expect("source map");
+    assert_eq!(map.map_pc(0).unwrap().start, 1);
+    assert_eq!(map.map_pc(2).unwrap().start, 2);
+  }
+
+  #[test]
+  fn from_standard_json_decodes_source_maps_when_present() {
+    let json = r#"{
+      "abi": [],
+      "evm": {
+        "bytecode": { "object": "0x6000", "sourceMap": "0:1:0:-:0" },
+        "deployedBytecode": {
+          "bytecode": { "object": "0x6001", "sourceMap": "0:1:0:-:0;1:2:0:i" }
+        }
+      }
+    }"#;
+
+    let contract: FoundryContract = serde_json::from_str(json).expect("contract");
+    let state = from_foundry_standard_json("Sample", &contract);
+
+    assert_eq!(state.creation_source_map.as_deref(), Some("0:1:0:-:0"));
+    assert_eq!(
+      state.deployed_source_map.as_deref(),
+      Some("0:1:0:-:0;1:2:0:i")
+    );
+    assert_eq!(state.creation_source_map_decoded.as_ref().unwrap().len(), 1);
+    assert_eq!(state.deployed_source_map_decoded.as_ref().unwrap().len(), 2);
+  }
+
+  #[test]
+  fn link_substitutes_the_library_address_into_the_placeholder() {
+    let mut state = ContractState::new("Consumer");
+    // 2-byte prefix (4 hex chars) + 20-byte placeholder (40 hex chars) + 1-byte suffix.
+    state.creation_bytecode = Some(ContractBytecode::unlinked(
+      "6000__$1234567890abcdef1234567890abcdef12$__00",
+    ));
+    state.creation_link_references = Some(BTreeMap::from([(
+      "src/Lib.sol".to_string(),
+      BTreeMap::from([(
+        "Lib".to_string(),
+        vec![ImmutableSlot {
+          start: 2,
+          length: 20,
+        }],
+      )]),
+    )]));
+
+    let libraries = HashMap::from([(
+      "Lib".to_string(),
+      "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+    )]);
+    let linked = state.link(&libraries).expect("link");
+
+    let bytecode = linked.creation_bytecode.expect("creation bytecode");
+    assert!(!bytecode.is_unlinked());
+    assert_eq!(
+      bytecode.to_hex(),
+      "0x6000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa00"
+    );
+    assert_eq!(
+      bytecode.placeholder(),
+      Some("6000__$1234567890abcdef1234567890abcdef12$__00")
+    );
+  }
+
+  #[test]
+  fn link_errors_when_a_library_address_is_missing() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_bytecode = Some(ContractBytecode::unlinked(
+      "6000__$1234567890abcdef1234567890abcdef12$__00",
+    ));
+    state.creation_link_references = Some(BTreeMap::from([(
+      "src/Lib.sol".to_string(),
+      BTreeMap::from([(
+        "Lib".to_string(),
+        vec![ImmutableSlot {
+          start: 2,
+          length: 20,
+        }],
+      )]),
+    )]));
+
+    let err = state.link(&HashMap::new()).unwrap_err();
+    assert_eq!(
+      err,
+      LinkError::MissingAddresses(vec![MissingLibrary {
+        file: "src/Lib.sol".to_string(),
+        library: "Lib".to_string(),
+      }])
+    );
+  }
+
+  #[test]
+  fn link_resolves_fully_qualified_library_keys() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_bytecode = Some(ContractBytecode::unlinked(
+      "6000__$1234567890abcdef1234567890abcdef12$__00",
+    ));
+    state.creation_link_references = Some(BTreeMap::from([(
+      "src/Lib.sol".to_string(),
+      BTreeMap::from([(
+        "Lib".to_string(),
+        vec![ImmutableSlot {
+          start: 2,
+          length: 20,
+        }],
+      )]),
+    )]));
+
+    let libraries = HashMap::from([(
+      "src/Lib.sol:Lib".to_string(),
+      "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+    )]);
+    let linked = state.link(&libraries).expect("link");
+    assert_eq!(
+      linked
+        .creation_bytecode
+        .expect("creation bytecode")
+        .to_hex(),
+      "0x6000bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb00"
+    );
+  }
+
+  #[test]
+  fn link_reports_every_missing_library_across_creation_and_deployed_bytecode() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_bytecode = Some(ContractBytecode::unlinked(
+      "__$1234567890abcdef1234567890abcdef12$__",
+    ));
+    state.creation_link_references = Some(BTreeMap::from([(
+      "src/A.sol".to_string(),
+      BTreeMap::from([(
+        "A".to_string(),
+        vec![ImmutableSlot {
+          start: 0,
+          length: 20,
+        }],
+      )]),
+    )]));
+    state.deployed_bytecode = Some(ContractBytecode::unlinked(
+      "__$abcdef1234567890abcdef1234567890ab$__",
+    ));
+    state.deployed_link_references = Some(BTreeMap::from([(
+      "src/B.sol".to_string(),
+      BTreeMap::from([(
+        "B".to_string(),
+        vec![ImmutableSlot {
+          start: 0,
+          length: 20,
+        }],
+      )]),
+    )]));
+
+    let err = state.link(&HashMap::new()).unwrap_err();
+    assert_eq!(
+      err,
+      LinkError::MissingAddresses(vec![
+        MissingLibrary {
+          file: "src/A.sol".to_string(),
+          library: "A".to_string(),
+        },
+        MissingLibrary {
+          file: "src/B.sol".to_string(),
+          library: "B".to_string(),
+        },
+      ])
+    );
+  }
+
+  #[test]
+  fn link_resolves_legacy_human_readable_placeholder_without_structured_offsets() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_bytecode = Some(ContractBytecode::unlinked(format!(
+      "6000{}00",
+      legacy_name_placeholder("Lib")
+    )));
+
+    let libraries = HashMap::from([(
+      "Lib".to_string(),
+      "0xcccccccccccccccccccccccccccccccccccccccc".to_string(),
+    )]);
+    let linked = state.link(&libraries).expect("link");
+    assert_eq!(
+      linked
+        .creation_bytecode
+        .expect("creation bytecode")
+        .to_hex(),
+      "0x6000cccccccccccccccccccccccccccccccccccccccc00"
+    );
+  }
+
+  #[test]
+  fn link_resolves_legacy_hash_placeholder_without_structured_offsets() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_bytecode = Some(ContractBytecode::unlinked(format!(
+      "6000{}00",
+      legacy_hash_placeholder("src/Lib.sol", "Lib")
+    )));
+
+    let libraries = HashMap::from([(
+      "src/Lib.sol:Lib".to_string(),
+      "0xdddddddddddddddddddddddddddddddddddddddd".to_string(),
+    )]);
+    let linked = state.link(&libraries).expect("link");
+    assert_eq!(
+      linked
+        .creation_bytecode
+        .expect("creation bytecode")
+        .to_hex(),
+      "0x6000dddddddddddddddddddddddddddddddddddddddd00"
+    );
+  }
+
+  #[test]
+  fn link_is_a_no_op_without_link_references() {
+    let mut state = ContractState::new("Standalone");
+    state.creation_bytecode = Some(ContractBytecode::from_hex_string("0x6000").unwrap());
+
+    let linked = state.link(&HashMap::new()).expect("link");
+    assert_eq!(linked.creation_bytecode, state.creation_bytecode);
+  }
+
+  #[test]
+  fn from_bytecode_object_retains_unlinked_placeholder_instead_of_dropping_it() {
+    let bytecode: Bytecode = serde_json::from_value(json!({
+      "object": "__$1234567890abcdef1234567890abcdef12$__",
+      "linkReferences": {
+        "src/Lib.sol": { "Lib": [ { "start": 0, "length": 20 } ] }
+      }
+    }))
+    .expect("bytecode");
+
+    let contract = ContractBytecode::from_bytecode(&bytecode).expect("unlinked bytecode");
+    assert!(contract.is_unlinked());
+    assert_eq!(
+      contract.placeholder(),
+      Some("__$1234567890abcdef1234567890abcdef12$__")
+    );
+  }
+
   #[test]
   fn deserialize_immutable_refs_returns_structured_slots() {
     let source = BTreeMap::from([(
@@ -861,4 +2715,225 @@ mod tests {
     assert_eq!(slots[0].start, 4);
     assert_eq!(slots[0].length, 32);
   }
+
+  #[test]
+  fn load_artifacts_glob_ingests_configurable_and_standard_json_artifacts() {
+    use tempfile::tempdir;
+
+    let root = tempdir().expect("tempdir");
+    let out_dir = root.path().join("out").join("Counter.sol");
+    fs::create_dir_all(&out_dir).expect("mkdir");
+    fs::write(
+      out_dir.join("Counter.json"),
+      json!({ "abi": [], "id": 1 }).to_string(),
+    )
+    .expect("write configurable artifact");
+
+    fs::write(
+      root.path().join("standard-json-output.json"),
+      json!({
+        "contracts": {
+          "src/Token.sol": {
+            "Token": { "abi": [] }
+          }
+        },
+        "sources": { "src/Token.sol": { "id": 1 } },
+        "errors": [],
+        "version": "0.8.21"
+      })
+      .to_string(),
+    )
+    .expect("write standard-json output");
+
+    let states = load_artifacts_glob(root.path(), &["**/*.json"]);
+    assert_eq!(states.get("Counter").map(|s| s.name.as_str()), Some("Counter"));
+    assert_eq!(states.get("Token").map(|s| s.name.as_str()), Some("Token"));
+  }
+
+  #[test]
+  fn load_artifacts_glob_requalifies_name_collisions_by_source_path() {
+    use tempfile::tempdir;
+
+    let root = tempdir().expect("tempdir");
+    fs::create_dir_all(root.path().join("a")).expect("mkdir a");
+    fs::create_dir_all(root.path().join("b")).expect("mkdir b");
+    fs::write(
+      root.path().join("a").join("Lib.json"),
+      json!({ "abi": [], "id": 1 }).to_string(),
+    )
+    .expect("write a/Lib.json");
+    fs::write(
+      root.path().join("b").join("Lib.json"),
+      json!({ "abi": [], "id": 2 }).to_string(),
+    )
+    .expect("write b/Lib.json");
+
+    let states = load_artifacts_glob(root.path(), &["**/*.json"]);
+    assert!(!states.contains_key("Lib"));
+    assert_eq!(states.len(), 2);
+    assert!(states.keys().all(|key| key.ends_with(":Lib")));
+  }
+
+  fn sample_state_with_extras() -> ContractState {
+    let mut state = ContractState::new("Fixture");
+    state.abi = Some(json!([]));
+    state.creation_bytecode = Some(ContractBytecode::from_hex_string("0x6000").unwrap());
+    state.metadata = Some(json!({ "language": "Solidity" }));
+    state.storage_layout = Some(json!({ "storage": [] }));
+    state
+  }
+
+  #[test]
+  fn write_artifact_with_extras_inlines_sections_in_all_inline_mode() {
+    use tempfile::tempdir;
+
+    let dir = tempdir().expect("tempdir");
+    let state = sample_state_with_extras();
+
+    let written = write_artifact_with_extras(&state, dir.path(), ExtraOutputValues::ALL_INLINE)
+      .expect("write artifact");
+    assert!(written.sidecar_paths.is_empty());
+
+    let core: Value =
+      serde_json::from_str(&fs::read_to_string(&written.core_path).expect("read core")).expect("parse core");
+    assert!(core.get("metadata").is_some());
+    assert!(core.get("storageLayout").is_some());
+  }
+
+  #[test]
+  fn write_artifact_with_extras_promotes_selected_sections_to_sidecar_files() {
+    use tempfile::tempdir;
+
+    let dir = tempdir().expect("tempdir");
+    let state = sample_state_with_extras();
+    let selection = ExtraOutputValues {
+      metadata: ExtraOutputMode::Sidecar,
+      ..ExtraOutputValues::NONE
+    };
+
+    let written =
+      write_artifact_with_extras(&state, dir.path(), selection).expect("write artifact");
+    assert_eq!(written.sidecar_paths.len(), 1);
+    assert_eq!(
+      written.sidecar_paths[0],
+      dir.path().join("Fixture.metadata.json")
+    );
+
+    let core: Value =
+      serde_json::from_str(&fs::read_to_string(&written.core_path).expect("read core")).expect("parse core");
+    assert!(core.get("metadata").is_none());
+    assert!(core.get("storageLayout").is_none());
+
+    let sidecar: Value = serde_json::from_str(
+      &fs::read_to_string(&written.sidecar_paths[0]).expect("read sidecar"),
+    )
+    .expect("parse sidecar");
+    assert_eq!(sidecar, json!({ "language": "Solidity" }));
+  }
+
+  fn state_with_gas_estimates(creation_total: &str, external: Value) -> ContractState {
+    let mut state = ContractState::new("Fixture");
+    state.gas_estimates = Some(
+      serde_json::from_value(json!({
+        "creation": {
+          "codeDepositCost": "100",
+          "executionCost": "200",
+          "totalCost": creation_total
+        },
+        "external": external,
+        "internal": {}
+      }))
+      .expect("gas"),
+    );
+    state
+  }
+
+  #[test]
+  fn diff_gas_estimates_reports_unchanged_and_changed_methods() {
+    let old = state_with_gas_estimates("300", json!({ "transfer(address,uint256)": "1000" }));
+    let new = state_with_gas_estimates("300", json!({ "transfer(address,uint256)": "1200" }));
+
+    let deltas = diff_gas_estimates(&old, &new);
+    let transfer = deltas
+      .iter()
+      .find(|d| d.key == "transfer(address,uint256)")
+      .expect("transfer delta");
+    assert_eq!(
+      transfer.change,
+      GasEstimateChange::Changed {
+        absolute: 200,
+        percent_micros: 200_000
+      }
+    );
+
+    let total_cost = deltas
+      .iter()
+      .find(|d| d.key == "creation.totalCost")
+      .expect("creation.totalCost delta");
+    assert_eq!(total_cost.change, GasEstimateChange::Unchanged);
+  }
+
+  #[test]
+  fn diff_gas_estimates_reports_added_removed_and_unbounded_methods() {
+    let old = state_with_gas_estimates(
+      "300",
+      json!({ "removedMethod()": "500", "boundedThenNot()": "700" }),
+    );
+    let new = state_with_gas_estimates(
+      "300",
+      json!({ "addedMethod()": "900", "boundedThenNot()": "infinite" }),
+    );
+
+    let deltas = diff_gas_estimates(&old, &new);
+    assert_eq!(
+      deltas.iter().find(|d| d.key == "removedMethod()").map(|d| d.change),
+      Some(GasEstimateChange::Removed)
+    );
+    assert_eq!(
+      deltas.iter().find(|d| d.key == "addedMethod()").map(|d| d.change),
+      Some(GasEstimateChange::Added)
+    );
+    assert_eq!(
+      deltas.iter().find(|d| d.key == "boundedThenNot()").map(|d| d.change),
+      Some(GasEstimateChange::BecameUnbounded)
+    );
+  }
+
+  #[test]
+  fn diff_gas_estimates_treats_missing_gas_estimates_as_empty() {
+    let old = ContractState::new("Fixture");
+    let new = state_with_gas_estimates("300", json!({ "transfer(address,uint256)": "1000" }));
+
+    let deltas = diff_gas_estimates(&old, &new);
+    assert_eq!(
+      deltas.iter().find(|d| d.key == "transfer(address,uint256)").map(|d| d.change),
+      Some(GasEstimateChange::Added)
+    );
+  }
+
+  #[test]
+  fn artifact_field_selection_minimal_keeps_only_abi_and_bytecode() {
+    let minimal = ArtifactFieldSelection::MINIMAL;
+    assert!(minimal.abi);
+    assert!(minimal.creation_bytecode);
+    assert!(minimal.deployed_bytecode);
+    assert!(!minimal.metadata);
+    assert!(!minimal.userdoc);
+    assert!(!minimal.devdoc);
+    assert!(!minimal.storage_layout);
+    assert!(!minimal.method_identifiers);
+    assert!(!minimal.function_debug_data);
+    assert!(!minimal.gas_estimates);
+    assert!(!minimal.assembly);
+    assert!(!minimal.legacy_assembly);
+    assert!(!minimal.opcodes);
+    assert!(!minimal.ir);
+    assert!(!minimal.ir_optimized);
+    assert!(!minimal.ewasm);
+  }
+
+  #[test]
+  fn artifact_field_selection_full_is_all() {
+    assert_eq!(ArtifactFieldSelection::FULL, ArtifactFieldSelection::ALL);
+  }
 }
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::compiler::output::SourceArtifacts;
+use crate::internal::config::{ArtifactFormat, ArtifactOutputFormat};
+use crate::internal::errors::{Error, Result};
+use crate::internal::hardhat_artifacts;
+use crate::internal::project::ProjectLayout;
+use crate::internal::truffle_artifacts;
+
+/// Controls the on-disk shape a compiled project's artifacts are written in: file naming, whether
+/// ABI/bytecode/metadata are split across files or combined into one JSON document per contract,
+/// and the directory nesting. Selected per build via [`resolve_format`]/[`writer`] from
+/// [`crate::internal::config::CompilerConfig::artifact_output`], which in turn defaults to the
+/// detected [`ProjectLayout`] when left unset.
+pub(crate) trait ArtifactOutput {
+  /// `build_infos_dir` is only consulted by [`HardhatArtifacts`], which points each artifact's
+  /// `.dbg.json` sidecar at the matching build-info document there; other implementors ignore it.
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    build_infos_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+  ) -> Result<()>;
+}
+
+/// Hardhat's `<source-file-name>/<ContractName>.json` layout, wrapped in the `hh-sol-artifact-1`
+/// envelope, plus a `<ContractName>.dbg.json` sidecar per contract pointing at its build-info
+/// document. See [`hardhat_artifacts::write_artifacts`].
+pub(crate) struct HardhatArtifacts;
+
+impl ArtifactOutput for HardhatArtifacts {
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    build_infos_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+  ) -> Result<()> {
+    hardhat_artifacts::write_artifacts(artifacts_dir, build_infos_dir, artifacts)
+  }
+}
+
+/// Foundry's native `out/<File.sol>/<ContractName>.json` layout. `foundry-compilers`' own
+/// `ConfigurableArtifacts` writer already produces this shape as part of `Project::compile()`, so
+/// this implementor has nothing left to do.
+pub(crate) struct ForgeArtifacts;
+
+impl ArtifactOutput for ForgeArtifacts {
+  fn write(
+    &self,
+    _artifacts_dir: &Path,
+    _build_infos_dir: &Path,
+    _artifacts: &BTreeMap<String, SourceArtifacts>,
+  ) -> Result<()> {
+    Ok(())
+  }
+}
+
+/// Truffle's flat `build/contracts/<ContractName>.json` layout, keyed only by contract name. See
+/// [`truffle_artifacts::write_artifacts`].
+pub(crate) struct TruffleArtifacts;
+
+impl ArtifactOutput for TruffleArtifacts {
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    _build_infos_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+  ) -> Result<()> {
+    truffle_artifacts::write_artifacts(artifacts_dir, artifacts)
+  }
+}
+
+/// Flat `<ContractName>.json` per contract directly under `artifacts_dir`, with ABI, bytecode, and
+/// deployed bytecode combined into one document instead of split or nested by source file. The
+/// default for [`ProjectLayout::Synthetic`], where there is no external toolchain layout to match.
+pub(crate) struct SyntheticArtifacts;
+
+impl ArtifactOutput for SyntheticArtifacts {
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    _build_infos_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+  ) -> Result<()> {
+    fs::create_dir_all(artifacts_dir).map_err(|err| {
+      Error::new(format!(
+        "Failed to prepare synthetic artifacts directory {}: {err}",
+        artifacts_dir.display()
+      ))
+    })?;
+
+    for source in artifacts.values() {
+      for (name, contract) in &source.contracts {
+        let state = contract.state();
+        let payload = json!({
+          "contractName": name,
+          "sourceName": state.source_path.clone().unwrap_or_default(),
+          "abi": state.abi.clone().unwrap_or_else(|| serde_json::Value::Array(Vec::new())),
+          "bytecode": state
+            .creation_bytecode
+            .as_ref()
+            .map(|bytecode| bytecode.to_hex())
+            .unwrap_or_else(|| "0x".to_string()),
+          "deployedBytecode": state
+            .deployed_bytecode
+            .as_ref()
+            .map(|bytecode| bytecode.to_hex())
+            .unwrap_or_else(|| "0x".to_string()),
+          "metadata": state.metadata.clone(),
+        });
+
+        let path = artifacts_dir.join(format!("{name}.json"));
+        let contents = serde_json::to_string_pretty(&payload)
+          .map_err(|err| Error::new(format!("Failed to serialise synthetic artifact {name}: {err}")))?;
+        fs::write(&path, contents)
+          .map_err(|err| Error::new(format!("Failed to write synthetic artifact {}: {err}", path.display())))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// The [`ArtifactOutputFormat`] a given [`ProjectLayout`] produces when
+/// `CompilerConfig::artifact_output` is left unset. `Truffle` is never a default here -- no
+/// [`ProjectLayout`] detects a Truffle project, so it's only reachable via an explicit override.
+pub(crate) fn default_format_for_layout(layout: &ProjectLayout) -> ArtifactOutputFormat {
+  match layout {
+    ProjectLayout::Hardhat => ArtifactOutputFormat::Hardhat,
+    ProjectLayout::Foundry { .. } | ProjectLayout::Dapptools => ArtifactOutputFormat::Foundry,
+    ProjectLayout::Synthetic => ArtifactOutputFormat::Synthetic,
+  }
+}
+
+/// Resolves the [`ArtifactOutputFormat`] a build should write in: an explicit
+/// `CompilerConfig::artifact_output` wins outright; otherwise the legacy
+/// `CompilerConfig::artifact_format` flag is honoured for backward compatibility (`Hardhat` there
+/// still means "write the Hardhat envelope regardless of layout"); otherwise the detected
+/// [`ProjectLayout`]'s own default applies, via [`default_format_for_layout`].
+pub(crate) fn resolve_format(
+  explicit: Option<ArtifactOutputFormat>,
+  legacy_format: ArtifactFormat,
+  layout: &ProjectLayout,
+) -> ArtifactOutputFormat {
+  if let Some(explicit) = explicit {
+    return explicit;
+  }
+  if legacy_format == ArtifactFormat::Hardhat {
+    return ArtifactOutputFormat::Hardhat;
+  }
+  default_format_for_layout(layout)
+}
+
+/// Returns the [`ArtifactOutput`] implementor for a resolved [`ArtifactOutputFormat`].
+pub(crate) fn writer(format: ArtifactOutputFormat) -> Box<dyn ArtifactOutput> {
+  match format {
+    ArtifactOutputFormat::Hardhat => Box::new(HardhatArtifacts),
+    ArtifactOutputFormat::Foundry => Box::new(ForgeArtifacts),
+    ArtifactOutputFormat::Synthetic => Box::new(SyntheticArtifacts),
+    ArtifactOutputFormat::Truffle => Box::new(TruffleArtifacts),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn default_format_matches_each_project_layout() {
+    assert_eq!(
+      default_format_for_layout(&ProjectLayout::Hardhat),
+      ArtifactOutputFormat::Hardhat
+    );
+    assert_eq!(
+      default_format_for_layout(&ProjectLayout::Dapptools),
+      ArtifactOutputFormat::Foundry
+    );
+    assert_eq!(
+      default_format_for_layout(&ProjectLayout::Synthetic),
+      ArtifactOutputFormat::Synthetic
+    );
+    assert_eq!(
+      default_format_for_layout(&ProjectLayout::Foundry {
+        ambiguous_with_hardhat: false
+      }),
+      ArtifactOutputFormat::Foundry
+    );
+  }
+
+  #[test]
+  fn explicit_override_wins_over_layout_default() {
+    let resolved = resolve_format(
+      Some(ArtifactOutputFormat::Hardhat),
+      ArtifactFormat::Foundry,
+      &ProjectLayout::Synthetic,
+    );
+    assert_eq!(resolved, ArtifactOutputFormat::Hardhat);
+  }
+
+  #[test]
+  fn legacy_hardhat_flag_is_honoured_without_an_explicit_override() {
+    let resolved = resolve_format(None, ArtifactFormat::Hardhat, &ProjectLayout::Dapptools);
+    assert_eq!(resolved, ArtifactOutputFormat::Hardhat);
+  }
+
+  #[test]
+  fn truffle_is_only_reachable_via_an_explicit_override() {
+    let resolved = resolve_format(
+      Some(ArtifactOutputFormat::Truffle),
+      ArtifactFormat::Foundry,
+      &ProjectLayout::Synthetic,
+    );
+    assert_eq!(resolved, ArtifactOutputFormat::Truffle);
+  }
+
+  #[test]
+  fn forge_artifacts_write_is_a_no_op() {
+    let temp = std::env::temp_dir().join("tevm-forge-artifacts-no-op-test");
+    let build_infos = std::env::temp_dir().join("tevm-forge-artifacts-no-op-test-build-info");
+    let artifacts = BTreeMap::new();
+    ForgeArtifacts
+      .write(&temp, &build_infos, &artifacts)
+      .expect("no-op write succeeds");
+    assert!(!temp.exists(), "ForgeArtifacts should not create a directory");
+  }
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::{sources::Source as FoundrySource, Settings};
+use semver::Version;
+use serde_json::Value;
+
+use crate::internal::errors::{Error, Result};
+use crate::internal::keccak::keccak256;
+
+/// On-disk cache of parsed ASTs, keyed by the content hash of the source plus the resolved solc
+/// version and sanitized settings. Mirrors [`super::incremental_cache`]'s content-hash/artifact
+/// approach, but sits in front of `parse_source_ast` rather than a full project compile -- so
+/// repeated fragment/AST parsing (the common case in editor tooling, where the same snippet gets
+/// re-parsed on every keystroke) can skip solc entirely once it has run once.
+pub(crate) fn cache_dir(base_dir: &Path) -> PathBuf {
+  base_dir.join(".tevm").join("ast-cache")
+}
+
+/// Fingerprints everything that changes what solc would produce for this parse: the source text,
+/// the resolved solc version, and the sanitized settings. Used as the cached entry's file name, so
+/// two sources with identical content and configuration share one cache entry.
+pub(crate) fn cache_key(source: &str, solc_version: &Version, settings: &Settings) -> Result<String> {
+  let mut payload = FoundrySource::content_hash_of(source);
+  payload.push('\n');
+  payload.push_str(&solc_version.to_string());
+  payload.push('\n');
+  payload.push_str(&serde_json::to_string(settings).map_err(|err| {
+    Error::new(format!("Failed to serialise AST cache settings: {err}"))
+  })?);
+  Ok(hex::encode(keccak256(payload.as_bytes())))
+}
+
+/// Loads the cached AST for `key` from `dir`, if present. A missing or corrupt entry (e.g. hand
+/// deleted, or written by an incompatible version) is treated as a cache miss rather than an
+/// error.
+pub(crate) fn read(dir: &Path, key: &str) -> Option<Value> {
+  let contents = fs::read_to_string(dir.join(format!("{key}.json"))).ok()?;
+  serde_json::from_str(&contents).ok()
+}
+
+/// Persists `ast` under `key` in `dir`, creating the directory if needed.
+pub(crate) fn write(dir: &Path, key: &str, ast: &Value) -> Result<()> {
+  fs::create_dir_all(dir).map_err(|err| {
+    Error::io(format!(
+      "Failed to prepare AST cache directory {}: {err}",
+      dir.display()
+    ))
+  })?;
+  let path = dir.join(format!("{key}.json"));
+  let serialized = serde_json::to_string(ast)
+    .map_err(|err| Error::new(format!("Failed to serialise cached AST {}: {err}", path.display())))?;
+  fs::write(&path, serialized)
+    .map_err(|err| Error::io(format!("Failed to write cached AST {}: {err}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn cache_key_is_stable_for_identical_inputs() {
+    let version = Version::new(0, 8, 20);
+    let settings = Settings::default();
+    let a = cache_key("contract A {}", &version, &settings).unwrap();
+    let b = cache_key("contract A {}", &version, &settings).unwrap();
+    assert_eq!(a, b);
+  }
+
+  #[test]
+  fn cache_key_changes_with_source_or_version() {
+    let version = Version::new(0, 8, 20);
+    let other_version = Version::new(0, 8, 21);
+    let settings = Settings::default();
+    let base = cache_key("contract A {}", &version, &settings).unwrap();
+    let other_source = cache_key("contract B {}", &version, &settings).unwrap();
+    let other_version_key = cache_key("contract A {}", &other_version, &settings).unwrap();
+    assert_ne!(base, other_source);
+    assert_ne!(base, other_version_key);
+  }
+
+  #[test]
+  fn write_then_read_round_trips_the_cached_ast() {
+    let dir = std::env::temp_dir().join(format!(
+      "tevm-ast-cache-test-{}",
+      std::process::id()
+    ));
+    let ast = json!({"nodeType": "SourceUnit", "id": 1});
+    write(&dir, "entry", &ast).unwrap();
+    assert_eq!(read(&dir, "entry"), Some(ast));
+    assert!(read(&dir, "missing").is_none());
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
@@ -1,5 +1,5 @@
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use foundry_compilers::artifacts::vyper::{VyperOptimizationMode, VyperSettings};
@@ -7,19 +7,116 @@ use foundry_compilers::artifacts::{
   error::Severity, output_selection::OutputSelection, remappings::Remapping, Settings,
 };
 use foundry_compilers::solc::SolcLanguage as FoundrySolcLanguage;
+use log::warn;
 use napi::bindgen_prelude::*;
 use napi::{Env, JsObject, JsUnknown, NapiRaw, ValueType};
-use semver::Version;
+use once_cell::sync::Lazy;
+use semver::{Version, VersionReq};
 
+use crate::contract::{ArtifactFieldSelection, JsArtifactFieldSelection};
 use crate::internal::errors::{map_napi_error, napi_error};
 use crate::internal::logging::LoggingLevel;
 use crate::internal::path::{to_path_set, to_path_vec};
+use crate::internal::pragma;
 use crate::internal::settings::{
-  default_output_selection, merge_settings, sanitize_settings, CompilerSettingsOptions,
-  JsCompilerSettingsOptions, VyperSettingsOptions,
+  add_source_map_outputs, default_output_selection, merge_settings, narrow_output_selection,
+  requests_ast_output, sanitize_settings, strip_unrequested_ast_output, CompilerSettingsOptions,
+  EvmVersion, JsCompilerSettingsOptions, VyperSettingsOptions,
 };
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+const LOG_TARGET: &str = "tevm::compiler.config";
+
+/// Minimum solc release exposing the `--include-path` CLI flag. Older compilers don't understand
+/// it, so `include_paths` configured against them are folded into `--allow-paths` instead
+/// (`CompilerConfigBuilder::build`).
+static INCLUDE_PATH_VERSION_REQ: Lazy<VersionReq> =
+  Lazy::new(|| VersionReq::parse(">=0.8.8").expect("valid version requirement"));
+
+/// Minimum solc release exposing the `--base-path` CLI flag. Unlike `--include-path`, there is no
+/// safe fallback for an unsupported `--base-path`, so targeting an older release with a
+/// base-path-dependent project layout is a hard configuration error
+/// (see `crate::internal::project::build_project`).
+static BASE_PATH_VERSION_REQ: Lazy<VersionReq> =
+  Lazy::new(|| VersionReq::parse(">=0.6.9").expect("valid version requirement"));
+
+/// Highest `EvmVersion` each solc release line accepts, oldest first. The last entry whose
+/// version is `<= solc_version` gives the ceiling a requested `evmVersion` must respect; releases
+/// older than the first entry fall back to `EvmVersion::Byzantium`, solc's original target.
+const EVM_VERSION_CEILINGS: &[(Version, EvmVersion)] = &[
+  (Version::new(0, 4, 21), EvmVersion::Constantinople),
+  (Version::new(0, 5, 5), EvmVersion::Petersburg),
+  (Version::new(0, 5, 14), EvmVersion::Istanbul),
+  (Version::new(0, 8, 5), EvmVersion::Berlin),
+  (Version::new(0, 8, 7), EvmVersion::London),
+  (Version::new(0, 8, 18), EvmVersion::Paris),
+  (Version::new(0, 8, 20), EvmVersion::Shanghai),
+  (Version::new(0, 8, 24), EvmVersion::Cancun),
+  (Version::new(0, 8, 29), EvmVersion::Prague),
+];
+
+/// Highest `EvmVersion` that `solc_version` can target, per [`EVM_VERSION_CEILINGS`]. Exposed to
+/// [`crate::internal::solc`] so a caller that only has a target `EvmVersion` (rather than a full
+/// settings override to normalize) can clamp it with the same thresholds via
+/// [`crate::internal::solc::normalize_evm_version`].
+pub(crate) fn max_evm_version_for_solc(solc_version: &Version) -> EvmVersion {
+  EVM_VERSION_CEILINGS
+    .iter()
+    .rev()
+    .find(|(threshold, _)| solc_version >= threshold)
+    .map(|(_, evm_version)| *evm_version)
+    .unwrap_or(EvmVersion::Byzantium)
+}
+
+/// Clamps (or, when `strict` is `true`, rejects) an `evmVersion` override that exceeds what
+/// `solc_version` can produce. Returns the target to store on the resolved settings; `None` if
+/// the caller never requested one.
+fn normalize_evm_version(
+  solc_version: &Version,
+  requested: Option<EvmVersion>,
+  strict: bool,
+) -> Result<Option<EvmVersion>> {
+  let Some(requested) = requested else {
+    return Ok(None);
+  };
+
+  let max_supported = max_evm_version_for_solc(solc_version);
+  if requested <= max_supported {
+    return Ok(Some(requested));
+  }
+
+  if strict {
+    return Err(napi_error(format!(
+      "evmVersion {requested:?} is not supported by solc {solc_version} (highest supported is \
+       {max_supported:?}); lower evmVersion or leave strictEvmVersion unset to clamp automatically"
+    )));
+  }
+
+  warn!(
+    target: LOG_TARGET,
+    "evmVersion {:?} exceeds what solc {} supports; clamping to {:?}",
+    requested,
+    solc_version,
+    max_supported
+  );
+  Ok(Some(max_supported))
+}
+
+/// Runs [`normalize_evm_version`] over a settings override's `evm_version`, returning an owned
+/// copy with the (possibly clamped) target when an override was supplied.
+fn normalize_settings_evm_version(
+  settings: Option<&CompilerSettingsOptions>,
+  solc_version: &Version,
+  strict: bool,
+) -> Result<Option<CompilerSettingsOptions>> {
+  let Some(settings) = settings else {
+    return Ok(None);
+  };
+  let mut normalized = settings.clone();
+  normalized.evm_version = normalize_evm_version(solc_version, normalized.evm_version, strict)?;
+  Ok(Some(normalized))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum CompilerLanguage {
   Solidity,
   Yul,
@@ -36,6 +133,75 @@ impl From<FoundrySolcLanguage> for CompilerLanguage {
   }
 }
 
+/// Controls how much of Foundry's output bundle is requested from solc. `Full` keeps the crate's
+/// historical default (ABI, bytecode, metadata, AST, ...); `Minimal`/`AbiOnly` trade that
+/// completeness for faster, smaller compiles when a caller only needs ABI and/or bytecode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputMode {
+  /// Selects only `abi`.
+  AbiOnly,
+  /// Selects `abi` plus `evm.bytecode.object`/`evm.deployedBytecode.object`.
+  Minimal,
+  /// Keeps [`default_output_selection()`], Foundry's full default bundle.
+  #[default]
+  Full,
+}
+
+impl OutputMode {
+  /// Rewrites `selection` to match this mode. `include_ast` is forwarded so `ast` is only added to
+  /// the narrowed selections when a caller actually needs it (e.g. `build_info_enabled`).
+  fn output_selection(self, include_ast: bool) -> OutputSelection {
+    match self {
+      OutputMode::Full => default_output_selection(),
+      OutputMode::AbiOnly => narrow_output_selection(&["abi"], include_ast),
+      OutputMode::Minimal => narrow_output_selection(
+        &["abi", "evm.bytecode.object", "evm.deployedBytecode.object"],
+        include_ast,
+      ),
+    }
+  }
+}
+
+/// Selects the on-disk artifact schema written after a successful compile. `Foundry` keeps the
+/// crate's historical layout; `Hardhat` additionally writes a `hh-sol-artifact-1` envelope per
+/// contract so existing Hardhat-based deploy/test tooling can consume this crate's output
+/// directly.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ArtifactFormat {
+  /// Foundry's native artifact layout. The crate's historical default.
+  #[default]
+  Foundry,
+  /// Additionally emit Hardhat-compatible `<ContractName>.json` artifacts.
+  Hardhat,
+}
+
+/// Selects the on-disk artifact *layout* a compiled project is written in -- file naming,
+/// directory nesting, and whether ABI/bytecode/metadata are split across files or combined into
+/// one JSON document per contract -- as opposed to [`ArtifactFormat`], which only toggles whether
+/// an additional Hardhat envelope is written. See
+/// [`crate::internal::artifact_output`] for the implementors this selects between and
+/// [`crate::internal::artifact_output::resolve_format`] for how the default is derived from a
+/// project's detected [`crate::internal::project::ProjectLayout`] when this is left unset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArtifactOutputFormat {
+  /// Flat `<ContractName>.json` per contract with no per-source nesting -- the default for
+  /// [`crate::internal::project::ProjectLayout::Synthetic`].
+  Synthetic,
+  /// Hardhat's `<source-file-name>/<ContractName>.json` layout -- the default for
+  /// [`crate::internal::project::ProjectLayout::Hardhat`].
+  Hardhat,
+  /// Foundry's native `out/<File.sol>/<ContractName>.json` layout, already produced by
+  /// `foundry-compilers`' own artifact writer -- the default for
+  /// [`crate::internal::project::ProjectLayout::Foundry`] and
+  /// [`crate::internal::project::ProjectLayout::Dapptools`].
+  Foundry,
+  /// Truffle's flat `build/contracts/<ContractName>.json` layout: one document per contract name
+  /// (not nested by source file), each carrying `sourceMap`/`deployedSourceMap`, the defining
+  /// source's AST, and a `compiler`/`networks` envelope. No [`crate::internal::project::ProjectLayout`]
+  /// defaults to this -- it's only selected via an explicit override.
+  Truffle,
+}
+
 impl From<CompilerLanguage> for FoundrySolcLanguage {
   fn from(language: CompilerLanguage) -> Self {
     match language {
@@ -144,6 +310,7 @@ pub struct CompilerConfig {
   /// options or project metadata.
   pub language: CompilerLanguage,
   /// Semver-qualified solc release resolved after applying user overrides. Defaults to `0.8.30`.
+  /// Ignored in favour of a per-import-subtree detection when `auto_detect_version` is `true`.
   pub solc_version: Version,
   /// Sanitised solc `Settings` struct emitted to the underlying compiler.
   pub solc_settings: Settings,
@@ -151,21 +318,42 @@ pub struct CompilerConfig {
   pub vyper_settings: VyperCompilerSettings,
   /// Controls whether synthetic projects cache inline sources on disk (`~/.tevm/virtual-sources`).
   pub cache_enabled: bool,
-  /// Forces offline compilation when `true`. When `false`, Foundry may fetch remappings on demand.
+  /// When `true`, ignores the incremental content-hash cache and `foundry-compilers`' own
+  /// timestamp cache for this call, recompiling every source regardless of whether it or its
+  /// configuration changed. Does not clear the persisted cache -- a subsequent call with this back
+  /// to `false` reuses whatever the forced rebuild just wrote. See
+  /// [`crate::internal::incremental_cache::clear`] to actually wipe it.
+  pub force_rebuild: bool,
+  /// Forces offline compilation when `true`. When `false`, Foundry may fetch remappings on demand
+  /// and auto-detected solc versions may be downloaded (see
+  /// [`super::graph::resolve_compilation_buckets`]). The plain `ensure_installed` lookups
+  /// (`solc::ensure_installed`, `vyper::ensure_installed`) never touch the network regardless of
+  /// this flag -- they only resolve against already-installed binaries or an explicit configured
+  /// path, so sandboxed callers that pin an exact version are already offline-safe without it.
   pub offline_mode: bool,
   /// Skips emitting artifact files entirely when `true`.
   pub no_artifacts: bool,
-  /// Emits Foundry build-info JSON files alongside the compiled artifacts when `true`.
+  /// Emits Foundry build-info JSON files alongside the compiled artifacts when `true`. Only
+  /// populates the build-info directory for project-rooted compiles (`compile_project`,
+  /// `compile_contract`, `compile_filtered`); list and read them back with
+  /// [`ProjectContext::build_info_files`](crate::internal::project::ProjectContext::build_info_files)
+  /// and [`ProjectContext::read_build_info`](crate::internal::project::ProjectContext::read_build_info).
   pub build_info_enabled: bool,
   /// Normalises emitted paths to forward slashes so results remain cross-platform stable.
   pub slash_paths: bool,
   /// Explicit solc job count override. `None` signals that Foundry should choose automatically.
   pub solc_jobs: Option<usize>,
+  /// Upper bound on worker threads [`crate::compiler::core::compile_many`] spins up to run
+  /// independent jobs concurrently. Unrelated to `solc_jobs`, which tunes a single solc
+  /// invocation's own internal parallelism. Defaults to the host's available parallelism.
+  pub max_jobs: usize,
   /// Emits a reduced artifact payload when `true`, mirroring Foundry's `sparse` output mode.
   pub sparse_output: bool,
   /// Canonicalised paths forwarded to solc's `--allow-paths` flag.
   pub allow_paths: BTreeSet<PathBuf>,
-  /// Canonicalised directories appended to the compiler's include path.
+  /// Canonicalised directories forwarded to solc's `--include-path` flag when `solc_version`
+  /// supports it (see [`CompilerConfig::supports_include_path`]); otherwise merged into
+  /// `allow_paths` by the builder.
   pub include_paths: BTreeSet<PathBuf>,
   /// Additional library directories searched when resolving imports.
   pub library_paths: Vec<PathBuf>,
@@ -179,6 +367,70 @@ pub struct CompilerConfig {
   pub compiler_severity_filter: Severity,
   /// Global logging level applied to compiler operations.
   pub logging_level: LoggingLevel,
+  /// Path-scoped compiler restrictions enforced against the filesystem sources passed to
+  /// `compileFiles`/`compileProject`. Empty by default.
+  pub restrictions: Vec<CompilerRestriction>,
+  /// Per-diagnostic-code severity overrides, checked after `compiler_severity_filter`/
+  /// `ignored_error_codes`. A code they target is kept past `compiler_severity_filter` so the
+  /// override decides its severity; a code listed in `ignored_error_codes` is dropped before the
+  /// overrides run. Empty by default.
+  pub severity_overrides: Vec<SeverityOverride>,
+  /// When `true`, compilation ignores `solc_version` and instead builds the `import` dependency
+  /// graph of the input sources, intersects the `pragma solidity` requirements within each
+  /// connected subtree, and resolves a compatible release per subtree -- so files that need
+  /// different compilers are compiled as separate `SolcInput`s instead of all sharing one version.
+  /// Defaults to `false`.
+  pub auto_detect_version: bool,
+  /// Controls how much of solc's output bundle `solc_settings.output_selection` requests. Defaults
+  /// to `OutputMode::Full`, matching the crate's historical behaviour.
+  pub output_mode: OutputMode,
+  /// Selects the on-disk artifact schema written after a successful compile. Defaults to
+  /// `ArtifactFormat::Foundry`.
+  pub artifact_format: ArtifactFormat,
+  /// Overrides the on-disk artifact layout [`crate::internal::artifact_output::resolve_format`]
+  /// would otherwise derive from the project's detected `ProjectLayout`. `None` (the default)
+  /// leaves that per-layout default in place.
+  pub artifact_output: Option<ArtifactOutputFormat>,
+  /// Controls which [`crate::contract::ContractState`] sections are extracted from a compiled
+  /// artifact. Unlike `output_mode`, which trims what solc itself computes, this only governs
+  /// what we do with a section once solc has already produced it. Defaults to
+  /// `ArtifactFieldSelection::ALL`, matching the crate's historical behaviour of extracting
+  /// everything the compiler returned. Both the standalone and project pipelines honor this
+  /// without recompiling; `ArtifactFieldSelection::MINIMAL` (ABI plus bytecode only) and
+  /// `ArtifactFieldSelection::FULL` are the built-in named presets, or set individual flags for a
+  /// custom shape.
+  pub artifact_field_selection: ArtifactFieldSelection,
+  /// When `true`, requests `evm.bytecode.sourceMap`/`evm.deployedBytecode.sourceMap` from solc and
+  /// decodes them into [`crate::contract::SourceMapEntry`] records on each artifact. Defaults to
+  /// `false`.
+  pub source_maps_enabled: bool,
+  /// When `true`, additionally resolves each decoded source map entry's file index and byte
+  /// offset into a file path and 1-based line/column, the way swc's `sourceMap`/`inlineSourceMap`
+  /// pair distinguishes "emit a map" from "resolve it to something a debugger can render".
+  /// Implies [`Self::source_maps_enabled`]. Defaults to `false`.
+  pub inline_source_map_enabled: bool,
+  /// When `true`, bundles the original source text of every file a contract's source map
+  /// references alongside the resolved entries, mirroring `inlineSources` in swc/Deno's transpile
+  /// options. Implies [`Self::inline_source_map_enabled`]. Defaults to `false`.
+  pub inline_sources_enabled: bool,
+  /// When `true`, keeps the file-level `ast` output that `build()` would otherwise strip from the
+  /// merged `output_selection`. Set whenever a caller explicitly asked for it (`includeAst`, an
+  /// `outputSelection` override that already lists `ast`, or a fully pre-resolved `Settings`);
+  /// left `false` to skip the (expensive) AST output for plain compiles. Defaults to `false`.
+  pub include_ast_output: bool,
+  /// When `true`, any diagnostic that survives [`CompilerConfig::compiler_severity_filter`] at
+  /// [`crate::compiler::output::SeverityLevel::Warning`] counts toward
+  /// [`crate::compiler::output::CompileOutput::has_compiler_errors`], without changing the
+  /// severity it's reported at in the diagnostics array. Defaults to `false`.
+  pub deny_warnings: bool,
+  /// Glob patterns matched against a diagnostic's source path; a match drops the diagnostic
+  /// entirely (e.g. `node_modules/**`, `lib/**`). Checked after `diagnostic_path_allowlist`.
+  /// Empty by default.
+  pub diagnostic_path_denylist: Vec<String>,
+  /// Glob patterns matched against a diagnostic's source path; when non-empty, only diagnostics
+  /// matching at least one pattern are kept. Empty by default, which keeps every diagnostic
+  /// regardless of its path.
+  pub diagnostic_path_allowlist: Vec<String>,
 }
 
 impl Default for CompilerConfig {
@@ -193,11 +445,15 @@ impl Default for CompilerConfig {
       solc_settings,
       vyper_settings: VyperCompilerSettings::default(),
       cache_enabled: true,
+      force_rebuild: false,
       offline_mode: false,
       no_artifacts: false,
       build_info_enabled: false,
       slash_paths: true,
       solc_jobs: None,
+      max_jobs: std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1),
       sparse_output: false,
       allow_paths: BTreeSet::new(),
       include_paths: BTreeSet::new(),
@@ -207,11 +463,35 @@ impl Default for CompilerConfig {
       ignored_error_codes: Vec::new(),
       compiler_severity_filter: Severity::Error,
       logging_level: LoggingLevel::default(),
+      restrictions: Vec::new(),
+      severity_overrides: Vec::new(),
+      auto_detect_version: false,
+      output_mode: OutputMode::default(),
+      artifact_format: ArtifactFormat::default(),
+      artifact_output: None,
+      artifact_field_selection: ArtifactFieldSelection::ALL,
+      source_maps_enabled: false,
+      inline_source_map_enabled: false,
+      inline_sources_enabled: false,
+      include_ast_output: false,
+      deny_warnings: false,
+      diagnostic_path_denylist: Vec::new(),
+      diagnostic_path_allowlist: Vec::new(),
     }
   }
 }
 
 impl CompilerConfig {
+  /// Whether `solc_version` is new enough to accept the `--include-path` CLI flag.
+  pub fn supports_include_path(&self) -> bool {
+    INCLUDE_PATH_VERSION_REQ.matches(&self.solc_version)
+  }
+
+  /// Whether `solc_version` is new enough to accept the `--base-path` CLI flag.
+  pub fn supports_base_path(&self) -> bool {
+    BASE_PATH_VERSION_REQ.matches(&self.solc_version)
+  }
+
   pub fn merged(&self, overrides: &CompilerConfigOptions) -> Result<Self> {
     CompilerConfigBuilder::with_base(self.clone())
       .apply_compiler_options(overrides.clone())?
@@ -246,6 +526,15 @@ pub struct SolcConfigOptions {
   pub settings: Option<CompilerSettingsOptions>,
   /// Pre-resolved solc settings that replace the defaults entirely when provided.
   pub resolved_settings: Option<Settings>,
+  /// When `true`, an `evmVersion` that exceeds what `version` supports is a hard error instead of
+  /// being silently clamped down to the highest version that compiler can target. Defaults to
+  /// `false` (clamp with a warning).
+  pub strict_evm_version: Option<bool>,
+  /// Explicit path to a solc binary to use directly, bypassing the installed-version lookup
+  /// `solc::ensure_installed` would otherwise do against `version`. Useful for pointing at a
+  /// binary the svm install directory doesn't know about (a custom build, a container-wrapped
+  /// compiler, etc).
+  pub path: Option<PathBuf>,
 }
 
 /// Vyper-specific overrides captured from user input.
@@ -267,6 +556,53 @@ pub struct VyperConfigOptions {
   pub experimental_codegen: Option<bool>,
 }
 
+/// A single path-scoped compiler constraint, analogous to Foundry's per-profile
+/// `compilation_restrictions`. Every bound is optional; an absent bound leaves that axis
+/// unconstrained. See [`crate::internal::restrictions`] for how these are matched, validated, and
+/// grouped.
+#[derive(Clone, Debug)]
+pub struct CompilerRestriction {
+  /// Glob matched against each source file's canonicalised path, e.g. `"**/src/core/*.sol"`.
+  pub path_glob: String,
+  /// Allowed solc version range. Unconstrained when `None`.
+  pub version_req: Option<VersionReq>,
+  /// Minimum optimizer `runs` the resolved settings must use.
+  pub min_optimizer_runs: Option<u64>,
+  /// Maximum optimizer `runs` the resolved settings must use.
+  pub max_optimizer_runs: Option<u64>,
+  /// Lowest EVM version the resolved settings may target.
+  pub min_evm_version: Option<crate::internal::settings::EvmVersion>,
+  /// Highest EVM version the resolved settings may target.
+  pub max_evm_version: Option<crate::internal::settings::EvmVersion>,
+  /// Requires (`Some(true)`) or forbids (`Some(false)`) `viaIR` compilation.
+  pub via_ir: Option<bool>,
+}
+
+/// Effective level a [`SeverityOverride`] resolves a diagnostic code to. `Allow` suppresses the
+/// diagnostic outright; `Warn`/`Error` force it to that severity regardless of what the compiler
+/// itself reported. See [`crate::compiler::diagnostics`] for how these are resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeverityOverrideLevel {
+  Allow,
+  Warn,
+  Error,
+}
+
+/// A per-diagnostic-code severity override, analogous to rustc's `#[allow]`/`#[warn]`/`#[deny]`
+/// lint attributes. Wins over `compiler_severity_filter` for diagnostics matching `code`; a code
+/// listed in `ignored_error_codes` is dropped before overrides apply. `ordinal` records this entry's position within the configured list so that,
+/// when two overrides target the same code, the later one wins (see
+/// [`crate::compiler::diagnostics::resolve_severity`]).
+#[derive(Clone, Copy, Debug)]
+pub struct SeverityOverride {
+  /// Numeric compiler diagnostic code this override targets, e.g. `2072`.
+  pub code: u64,
+  /// Severity to resolve matching diagnostics to.
+  pub level: SeverityOverrideLevel,
+  /// Position of this override within the configured list; later entries win ties.
+  pub ordinal: usize,
+}
+
 /// Strongly-typed Rust overrides that can be merged into a [`CompilerConfig`].
 #[derive(Clone, Debug, Default)]
 pub struct CompilerConfigOptions {
@@ -275,11 +611,21 @@ pub struct CompilerConfigOptions {
   pub compiler: Option<CompilerLanguage>,
   /// Solc-specific overrides such as version, optimizer configuration, and output selection.
   pub solc: SolcConfigOptions,
+  /// Requirement-style solc version selection, parsed by
+  /// [`parse_compiler_version_requirement`]: a plain `0.8` matches any `0.8.x` release, a plain
+  /// `0.8.19` pins that exact release, and a caret-prefixed `^0.8.19` matches any release
+  /// compatible with it. Resolved against installed (or, when `offline_mode` is `false`,
+  /// installable) solc releases and written to [`CompilerConfig::solc_version`], taking
+  /// precedence over a plain `solc.version` override set by the same options.
+  pub compiler_version: Option<VersionReq>,
   /// Vyper-specific overrides applied whenever the active language front-end is `Vyper`.
   pub vyper: VyperConfigOptions,
   /// Overrides the cache flag. Set to `false` to avoid writing virtual sources to
   /// `~/.tevm/virtual-sources` during inline compilations.
   pub cache_enabled: Option<bool>,
+  /// Overrides [`CompilerConfig::force_rebuild`]. Set to `true` to bypass the incremental cache
+  /// for this call without clearing it.
+  pub force_rebuild: Option<bool>,
   /// Forces offline compilation. Useful when you need deterministic builds without network access.
   pub offline_mode: Option<bool>,
   /// Disables artifact emission when `Some(true)` to keep compilation side-effect free.
@@ -292,6 +638,9 @@ pub struct CompilerConfigOptions {
   /// Explicit solc job count override. Use `Some(Some(n))` to pin the concurrency level or
   /// `Some(None)` to reset back to auto-detection.
   pub solc_jobs: Option<Option<usize>>,
+  /// Overrides [`CompilerConfig::max_jobs`], the worker thread bound for
+  /// [`crate::compiler::core::compile_many`].
+  pub max_jobs: Option<usize>,
   /// Requests sparse artifact output (lighter JSON artifacts) when set to `true`.
   pub sparse_output: Option<bool>,
   /// Additional paths forwarded to solc's `--allow-paths`. Entries are canonicalised before use.
@@ -311,6 +660,45 @@ pub struct CompilerConfigOptions {
   pub compiler_severity_filter: Option<Severity>,
   /// Overrides the compiler logging level. Defaults to [`LoggingLevel::Silent`].
   pub logging_level: Option<LoggingLevel>,
+  /// Path-scoped compiler restrictions (version/optimizer runs/EVM version/`viaIR` bounds)
+  /// validated before compilation. `Some(restrictions)` replaces the existing list entirely.
+  pub restrictions: Option<Vec<CompilerRestriction>>,
+  /// Per-diagnostic-code severity overrides. `Some(overrides)` replaces the existing list
+  /// entirely.
+  pub severity_overrides: Option<Vec<SeverityOverride>>,
+  /// Overrides [`CompilerConfig::auto_detect_version`].
+  pub auto_detect_version: Option<bool>,
+  /// Overrides [`CompilerConfig::output_mode`].
+  pub output_mode: Option<OutputMode>,
+  /// Overrides [`CompilerConfig::artifact_format`].
+  pub artifact_format: Option<ArtifactFormat>,
+  /// Overrides [`CompilerConfig::artifact_output`], pinning a specific on-disk layout instead of
+  /// the per-layout default.
+  pub artifact_output: Option<ArtifactOutputFormat>,
+  /// Overrides [`CompilerConfig::artifact_field_selection`]. Replaces the existing selection
+  /// entirely; a caller that only wants to narrow a couple of fields should start from
+  /// `ArtifactFieldSelection::ALL` and flip the ones it doesn't need.
+  pub artifact_field_selection: Option<ArtifactFieldSelection>,
+  /// Overrides [`CompilerConfig::source_maps_enabled`].
+  pub source_maps: Option<bool>,
+  /// Overrides [`CompilerConfig::inline_source_map_enabled`]. `Some(true)` also enables
+  /// [`CompilerConfig::source_maps_enabled`].
+  pub inline_source_map: Option<bool>,
+  /// Overrides [`CompilerConfig::inline_sources_enabled`]. `Some(true)` also enables
+  /// [`CompilerConfig::inline_source_map_enabled`] and [`CompilerConfig::source_maps_enabled`].
+  pub inline_sources: Option<bool>,
+  /// Overrides [`CompilerConfig::deny_warnings`]. `None` inherits whatever the base config already
+  /// carries (itself either the crate default of `false` or a value set by an earlier, broader
+  /// merge) rather than resetting to a hardcoded default, the same three-valued inheritance
+  /// `evm_version` already gets from [`crate::internal::settings::CompilerSettingsOptions`]'s
+  /// JSON overlay onto the base `Settings`.
+  pub deny_warnings: Option<bool>,
+  /// Overrides [`CompilerConfig::diagnostic_path_denylist`]. `Some(patterns)` replaces the
+  /// existing list entirely.
+  pub diagnostic_path_denylist: Option<Vec<String>>,
+  /// Overrides [`CompilerConfig::diagnostic_path_allowlist`]. `Some(patterns)` replaces the
+  /// existing list entirely.
+  pub diagnostic_path_allowlist: Option<Vec<String>>,
 }
 
 /// Overrides for the AST helper configuration.
@@ -324,6 +712,26 @@ pub struct AstConfigOptions {
   pub logging_level: Option<LoggingLevel>,
   /// Controls how AST stitching resolves contract member conflicts.
   pub resolve_conflict_strategy: Option<ResolveConflictStrategy>,
+  /// Overrides [`AstConfig::merge_placement`], used only when `resolve_conflict_strategy` is
+  /// [`ResolveConflictStrategy::Merge`].
+  pub merge_placement: Option<MergePlacement>,
+  /// When `true`, edge instrumentation (`injectShadowAtEdges`/`injectShadowAsModifier`) refuses
+  /// any function containing inline assembly outright. Defaults to `false`, in which case
+  /// instrumentation walks the Yul AST and only lifts the `after` snippet ahead of an assembly
+  /// block that can actually exit the function (`return`/`revert`/`stop`/`leave`).
+  pub reject_inline_assembly: Option<bool>,
+  /// Forces offline AST operations when `true`, mirroring `CompilerConfig::offline_mode`.
+  /// Defaults to `false`. Every AST entry point already resolves solc through
+  /// `solc::ensure_installed`/`solc::ensure_installed_for`, which only ever look up an
+  /// already-installed binary (or `solc.path`, when set) and never download -- so this flag is
+  /// mostly documentation of that guarantee today, kept for API symmetry with
+  /// `CompilerConfigOptions::offline_mode` and so a future auto-detecting AST entry point has
+  /// somewhere to read the caller's intent from.
+  pub offline: Option<bool>,
+  /// Overrides [`AstConfig::cache_enabled`]. Mirrors [`CompilerConfig::cache_enabled`]'s
+  /// semantics, but for the on-disk AST cache `parse_source_ast` consults instead of the
+  /// synthetic-project virtual-sources cache.
+  pub cache_enabled: Option<bool>,
 }
 
 impl AstConfigOptions {
@@ -343,6 +751,19 @@ pub struct AstConfig {
   pub logging_level: LoggingLevel,
   /// Conflict resolution strategy applied when stitching fragments.
   pub resolve_conflict_strategy: ResolveConflictStrategy,
+  /// Where a colliding fragment function's body lands under
+  /// [`ResolveConflictStrategy::Merge`]. Ignored by every other strategy.
+  pub merge_placement: MergePlacement,
+  /// Whether edge instrumentation should refuse functions containing inline assembly outright
+  /// instead of instrumenting around Yul exit points.
+  pub reject_inline_assembly: bool,
+  /// Whether AST operations are restricted to offline solc resolution. See
+  /// [`AstConfigOptions::offline`].
+  pub offline: bool,
+  /// Whether `parse_source_ast` may read from and write to the on-disk AST cache under
+  /// `{cache_dir}/.tevm/ast-cache`. Defaults to `true`, matching
+  /// [`CompilerConfig::cache_enabled`]'s default.
+  pub cache_enabled: bool,
 }
 
 impl AstConfig {
@@ -362,11 +783,21 @@ impl AstConfig {
     let resolve_conflict_strategy = options
       .and_then(|opts| opts.resolve_conflict_strategy)
       .unwrap_or_default();
+    let merge_placement = options.and_then(|opts| opts.merge_placement).unwrap_or_default();
+    let reject_inline_assembly = options
+      .and_then(|opts| opts.reject_inline_assembly)
+      .unwrap_or(false);
+    let offline = options.and_then(|opts| opts.offline).unwrap_or(false);
+    let cache_enabled = options.and_then(|opts| opts.cache_enabled).unwrap_or(true);
     Ok(AstConfig {
       solc,
       instrumented_contract: options.and_then(|opts| opts.instrumented_contract.clone()),
       logging_level,
       resolve_conflict_strategy,
+      merge_placement,
+      reject_inline_assembly,
+      offline,
+      cache_enabled,
     })
   }
 
@@ -380,11 +811,21 @@ impl AstConfig {
     let resolve_conflict_strategy = overrides
       .resolve_conflict_strategy
       .unwrap_or(self.resolve_conflict_strategy);
+    let merge_placement = overrides.merge_placement.unwrap_or(self.merge_placement);
+    let reject_inline_assembly = overrides
+      .reject_inline_assembly
+      .unwrap_or(self.reject_inline_assembly);
+    let offline = overrides.offline.unwrap_or(self.offline);
+    let cache_enabled = overrides.cache_enabled.unwrap_or(self.cache_enabled);
     Ok(AstConfig {
       solc,
       instrumented_contract,
       logging_level,
       resolve_conflict_strategy,
+      merge_placement,
+      reject_inline_assembly,
+      offline,
+      cache_enabled,
     })
   }
 
@@ -418,6 +859,10 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
       overrides.solc.version = Some(parse_version(version)?);
     }
 
+    if let Some(requirement) = options.compiler_version.as_ref() {
+      overrides.compiler_version = Some(parse_compiler_version_requirement(requirement)?);
+    }
+
     if let Some(language) = options.language {
       overrides.compiler = Some(language.into());
     }
@@ -427,6 +872,7 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
     }
 
     overrides.cache_enabled = options.cache_enabled;
+    overrides.force_rebuild = options.force_rebuild;
     overrides.offline_mode = options.offline_mode;
     overrides.no_artifacts = options.no_artifacts;
     overrides.build_info_enabled = options.build_info_enabled;
@@ -434,6 +880,7 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
     overrides.solc_jobs = options
       .solc_jobs
       .map(|jobs| if jobs == 0 { None } else { Some(jobs as usize) });
+    overrides.max_jobs = options.max_jobs.map(|jobs| jobs.max(1) as usize);
     overrides.sparse_output = options.sparse_output;
     overrides.allow_paths = options
       .allow_paths
@@ -469,6 +916,47 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
       overrides.logging_level = Some(level.into());
     }
 
+    if let Some(restrictions) = options.restrictions.as_ref() {
+      overrides.restrictions = Some(
+        restrictions
+          .iter()
+          .map(CompilerRestriction::try_from)
+          .collect::<Result<Vec<_>>>()?,
+      );
+    }
+
+    if let Some(severity_overrides) = options.severity_overrides.as_ref() {
+      overrides.severity_overrides = Some(
+        severity_overrides
+          .iter()
+          .enumerate()
+          .map(|(ordinal, entry)| {
+            Ok(SeverityOverride {
+              code: entry.code as u64,
+              level: parse_severity_override_level(&entry.level)?,
+              ordinal,
+            })
+          })
+          .collect::<Result<Vec<_>>>()?,
+      );
+    }
+
+    overrides.auto_detect_version = options.auto_detect_version;
+    overrides.output_mode = options.output_mode.map(OutputMode::from);
+    overrides.artifact_format = options.artifact_format.map(ArtifactFormat::from);
+    overrides.artifact_output = options.artifact_output.map(ArtifactOutputFormat::from);
+    overrides.artifact_field_selection = options
+      .artifact_field_selection
+      .clone()
+      .map(ArtifactFieldSelection::from);
+    overrides.source_maps = options.source_maps;
+    overrides.inline_source_map = options.inline_source_map;
+    overrides.inline_sources = options.inline_sources;
+    overrides.deny_warnings = options.deny_warnings;
+    overrides.diagnostic_path_denylist = options.diagnostic_path_denylist.clone();
+    overrides.diagnostic_path_allowlist = options.diagnostic_path_allowlist.clone();
+    overrides.solc.strict_evm_version = options.strict_evm_version;
+
     Ok(overrides)
   }
 }
@@ -549,6 +1037,12 @@ impl TryFrom<&JsAstConfigOptions> for AstConfigOptions {
     typed.resolve_conflict_strategy = options
       .resolve_conflict_strategy
       .map(ResolveConflictStrategy::from);
+    typed.merge_placement = options.merge_placement.map(MergePlacement::from);
+    typed.reject_inline_assembly = options.reject_inline_assembly;
+    typed.solc.strict_evm_version = options.strict_evm_version;
+    typed.solc.path = options.solc_path.as_ref().map(PathBuf::from);
+    typed.offline = options.offline;
+    typed.cache_enabled = options.cache_enabled;
 
     Ok(typed)
   }
@@ -570,6 +1064,15 @@ pub struct JsCompilerConfigOptions {
   /// release when omitted.
   #[napi(ts_type = "string | undefined")]
   pub solc_version: Option<String>,
+  /// Requirement-style solc version selection: a plain `"0.8"` matches any `0.8.x` release, a
+  /// plain `"0.8.19"` pins that exact release, and a caret-prefixed `"^0.8.19"` matches any
+  /// release compatible with it (same semantics as Cargo's `rust-version`/partial-version
+  /// syntax). Resolved against installed (or, when `offlineMode` is `false`, installable) solc
+  /// releases. Takes precedence over `solcVersion` when both are set. A bare non-numeric value,
+  /// a build-metadata suffix (`"0.8.19+commit"`), or a multi-comparator range
+  /// (`">=0.8, <0.9"`) is rejected with an error rather than silently ignored.
+  #[napi(ts_type = "string | undefined")]
+  pub compiler_version: Option<String>,
   /// Override the compiler front-end (`Solidity`, `Yul`, or `Vyper`). Falls back to
   /// `CompilerLanguage::Solidity` unless project metadata specifies otherwise.
   #[napi(ts_type = "CompilerLanguage | undefined")]
@@ -585,6 +1088,10 @@ pub struct JsCompilerConfigOptions {
   /// sources under `~/.tevm/virtual-sources`; `false` keeps everything in-memory for ephemeral runs.
   #[napi(ts_type = "boolean | undefined")]
   pub cache_enabled: Option<bool>,
+  /// Bypasses the incremental cache for this call, recompiling every source regardless of content
+  /// or configuration changes, without clearing what's already persisted. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub force_rebuild: Option<bool>,
   /// Prevents network access during compilation. Defaults to `false` so Foundry can download
   /// missing remappings when necessary.
   #[napi(ts_type = "boolean | undefined")]
@@ -603,6 +1110,10 @@ pub struct JsCompilerConfigOptions {
   /// deterministic concurrency level inside CI.
   #[napi(ts_type = "number | undefined")]
   pub solc_jobs: Option<u32>,
+  /// Upper bound on worker threads used to run independent compilation jobs concurrently.
+  /// Defaults to the host's available parallelism. Unrelated to `solcJobs`.
+  #[napi(ts_type = "number | undefined")]
+  pub max_jobs: Option<u32>,
   /// Emits minimal artifact output when `true` (Foundry's sparse output mode). Defaults to `false`.
   #[napi(ts_type = "boolean | undefined")]
   pub sparse_output: Option<bool>,
@@ -636,6 +1147,145 @@ pub struct JsCompilerConfigOptions {
   /// `"info"`.
   #[napi(ts_type = "LoggingLevel | undefined")]
   pub logging_level: Option<JsLoggingLevel>,
+  /// Path-scoped compiler restrictions (version/optimizer runs/EVM version/`viaIR` bounds),
+  /// similar to Foundry's per-profile `compilation_restrictions`. Files matched by more than one
+  /// restriction must satisfy the intersection of all of them. Defaults to an empty list.
+  #[napi(ts_type = "CompilerRestriction[] | undefined")]
+  pub restrictions: Option<Vec<JsCompilerRestriction>>,
+  /// Per-diagnostic-code severity overrides, checked after `compilerSeverity`/
+  /// `ignoredErrorCodes` and winning over both for any code they target. When two overrides in
+  /// the list target the same code, the later one wins. Defaults to an empty list.
+  #[napi(ts_type = "SeverityOverride[] | undefined")]
+  pub severity_overrides: Option<Vec<JsSeverityOverride>>,
+  /// Auto-detects the solc version per source group from each file's `pragma solidity`
+  /// declarations instead of using `solcVersion`. Resolves the highest installed (or, when
+  /// `offlineMode` is not set, installable) release satisfying all of a file's pragmas. Defaults
+  /// to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub auto_detect_version: Option<bool>,
+  /// Trims the requested solc output bundle (`"full"` (default), `"minimal"`, or `"abiOnly"`) to
+  /// speed up compiles that only need ABI and/or bytecode.
+  #[napi(ts_type = "OutputMode | undefined")]
+  pub output_mode: Option<JsOutputMode>,
+  /// Selects the on-disk artifact schema written after a successful compile (`"foundry"`
+  /// (default) or `"hardhat"`). The Hardhat format additionally writes a `hh-sol-artifact-1`
+  /// envelope per contract for existing Hardhat-based deploy/test tooling.
+  #[napi(ts_type = "ArtifactFormat | undefined")]
+  pub artifact_format: Option<JsArtifactFormat>,
+  /// Overrides the on-disk artifact *layout* (file naming, directory nesting, split vs. combined
+  /// ABI/bytecode/metadata) that would otherwise be derived from the detected project layout:
+  /// `"synthetic"` (flat, no nesting), `"hardhat"`, or `"foundry"`. Defaults to the layout's own
+  /// convention -- see [`crate::internal::artifact_output`].
+  #[napi(ts_type = "ArtifactOutputFormat | undefined")]
+  pub artifact_output: Option<JsArtifactOutputFormat>,
+  /// Narrows which [`crate::contract::ContractState`] sections get populated from a compiled
+  /// artifact, e.g. `{ abi: true, deployedBytecode: true, storageLayout: true }` to skip the
+  /// (expensive) IR/assembly serialization when a caller only needs those three. Every field
+  /// defaults to `true` when this object -- or any field on it -- is omitted.
+  #[napi(ts_type = "ArtifactFieldSelection | undefined")]
+  pub artifact_field_selection: Option<JsArtifactFieldSelection>,
+  /// Requests `evm.bytecode.sourceMap`/`evm.deployedBytecode.sourceMap` from solc and decodes them
+  /// into structured entries on each artifact, for coverage and stack-trace tooling. Defaults to
+  /// `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub source_maps: Option<bool>,
+  /// Resolves each decoded source map entry to a file path and 1-based line/column instead of a
+  /// raw byte offset, the way swc's `inlineSourceMap` augments its plain `sourceMap` option.
+  /// Implies `sourceMap`. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub inline_source_map: Option<bool>,
+  /// Bundles the original source text of every file referenced by a contract's source map
+  /// alongside the resolved entries, mirroring `inlineSources` in swc/Deno's transpile options.
+  /// Implies `inlineSourceMap`. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub inline_sources: Option<bool>,
+  /// Promotes every diagnostic that survives `compilerSeverity`/`ignoredErrorCodes` at `"warning"`
+  /// level to count as a compiler error for `hasCompilerErrors`/exit-code purposes, without
+  /// changing the severity it's reported at in `errors`/`diagnostics`. Leaving this `undefined`
+  /// inherits whichever value the base configuration already carries (the crate default of
+  /// `false`, or a value set by a broader `CompilerConfigOptions` applied earlier) instead of
+  /// resetting it. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub deny_warnings: Option<bool>,
+  /// Glob patterns matched against a diagnostic's source path; a match drops the diagnostic
+  /// entirely (e.g. `"node_modules/**"`, `"lib/**"`). Checked after `diagnosticPathAllowlist`.
+  /// `Some(patterns)` replaces the existing list entirely. Defaults to an empty list.
+  #[napi(ts_type = "string[] | undefined")]
+  pub diagnostic_path_denylist: Option<Vec<String>>,
+  /// Glob patterns matched against a diagnostic's source path; when non-empty, only diagnostics
+  /// matching at least one pattern are kept. `Some(patterns)` replaces the existing list
+  /// entirely. Defaults to an empty list, which keeps every diagnostic regardless of its path.
+  #[napi(ts_type = "string[] | undefined")]
+  pub diagnostic_path_allowlist: Option<Vec<String>>,
+  /// When `true`, an `evmVersion` (top-level `solcSettings.evmVersion` or Vyper's
+  /// `vyperSettings.evmVersion`) that exceeds what the resolved compiler version supports is a
+  /// hard error instead of being clamped down with a warning. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub strict_evm_version: Option<bool>,
+}
+
+/// A single path-scoped compiler restriction, mirroring [`CompilerRestriction`].
+#[napi(object, js_name = "CompilerRestriction")]
+#[derive(Clone, Default)]
+pub struct JsCompilerRestriction {
+  /// Glob matched against each source file's canonicalised path, e.g. `"**/src/core/*.sol"`.
+  pub path_glob: String,
+  /// Allowed solc version range expressed as a semver requirement, e.g. `">=0.8.20"`.
+  #[napi(ts_type = "string | undefined")]
+  pub solc_version_req: Option<String>,
+  /// Minimum optimizer `runs` permitted for matching files.
+  #[napi(ts_type = "number | undefined")]
+  pub min_optimizer_runs: Option<u32>,
+  /// Maximum optimizer `runs` permitted for matching files.
+  #[napi(ts_type = "number | undefined")]
+  pub max_optimizer_runs: Option<u32>,
+  /// Lowest EVM version permitted for matching files.
+  #[napi(ts_type = "import('./solc-settings').EvmVersion | undefined")]
+  pub min_evm_version: Option<crate::internal::settings::EvmVersion>,
+  /// Highest EVM version permitted for matching files.
+  #[napi(ts_type = "import('./solc-settings').EvmVersion | undefined")]
+  pub max_evm_version: Option<crate::internal::settings::EvmVersion>,
+  /// Requires (`true`) or forbids (`false`) `viaIR` compilation for matching files.
+  #[napi(ts_type = "boolean | undefined")]
+  pub via_ir: Option<bool>,
+}
+
+/// A single per-diagnostic-code severity override, mirroring [`SeverityOverride`].
+#[napi(object, js_name = "SeverityOverride")]
+#[derive(Clone, Default)]
+pub struct JsSeverityOverride {
+  /// Numeric compiler diagnostic code this override targets, e.g. `2072`.
+  pub code: u32,
+  /// Severity to resolve matching diagnostics to (`"allow"`, `"warning"`, or `"error"`).
+  pub level: String,
+}
+
+impl TryFrom<&JsCompilerRestriction> for CompilerRestriction {
+  type Error = napi::Error;
+
+  fn try_from(options: &JsCompilerRestriction) -> Result<Self> {
+    let version_req = options
+      .solc_version_req
+      .as_ref()
+      .map(|req| {
+        VersionReq::parse(req).map_err(|err| {
+          napi_error(format!(
+            "Invalid compiler restriction version requirement `{req}`: {err}"
+          ))
+        })
+      })
+      .transpose()?;
+
+    Ok(CompilerRestriction {
+      path_glob: options.path_glob.clone(),
+      version_req,
+      min_optimizer_runs: options.min_optimizer_runs.map(|runs| runs as u64),
+      max_optimizer_runs: options.max_optimizer_runs.map(|runs| runs as u64),
+      min_evm_version: options.min_evm_version,
+      max_evm_version: options.max_evm_version,
+      via_ir: options.via_ir,
+    })
+  }
 }
 
 macro_rules! impl_js_enum_string_traits {
@@ -706,6 +1356,78 @@ impl From<JsCompilerLanguage> for CompilerLanguage {
   }
 }
 
+/// Output bundle sizes surfaced to JavaScript callers. See [`OutputMode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsOutputMode {
+  AbiOnly,
+  Minimal,
+  Full,
+}
+
+impl_js_enum_string_traits!(JsOutputMode {
+  AbiOnly => "abiOnly",
+  Minimal => "minimal",
+  Full => "full"
+});
+
+impl From<JsOutputMode> for OutputMode {
+  fn from(mode: JsOutputMode) -> Self {
+    match mode {
+      JsOutputMode::AbiOnly => OutputMode::AbiOnly,
+      JsOutputMode::Minimal => OutputMode::Minimal,
+      JsOutputMode::Full => OutputMode::Full,
+    }
+  }
+}
+
+/// Artifact output schemas surfaced to JavaScript callers. See [`ArtifactFormat`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsArtifactFormat {
+  Foundry,
+  Hardhat,
+}
+
+impl_js_enum_string_traits!(JsArtifactFormat {
+  Foundry => "foundry",
+  Hardhat => "hardhat"
+});
+
+impl From<JsArtifactFormat> for ArtifactFormat {
+  fn from(format: JsArtifactFormat) -> Self {
+    match format {
+      JsArtifactFormat::Foundry => ArtifactFormat::Foundry,
+      JsArtifactFormat::Hardhat => ArtifactFormat::Hardhat,
+    }
+  }
+}
+
+/// On-disk artifact layouts surfaced to JavaScript callers. See [`ArtifactOutputFormat`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsArtifactOutputFormat {
+  Synthetic,
+  Hardhat,
+  Foundry,
+  Truffle,
+}
+
+impl_js_enum_string_traits!(JsArtifactOutputFormat {
+  Synthetic => "synthetic",
+  Hardhat => "hardhat",
+  Foundry => "foundry",
+  Truffle => "truffle"
+});
+
+impl From<JsArtifactOutputFormat> for ArtifactOutputFormat {
+  fn from(format: JsArtifactOutputFormat) -> Self {
+    match format {
+      JsArtifactOutputFormat::Synthetic => ArtifactOutputFormat::Synthetic,
+      JsArtifactOutputFormat::Hardhat => ArtifactOutputFormat::Hardhat,
+      JsArtifactOutputFormat::Foundry => ArtifactOutputFormat::Foundry,
+      JsArtifactOutputFormat::Truffle => ArtifactOutputFormat::Truffle,
+    }
+  }
+}
+
 /// Logging levels surfaced to JavaScript callers.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum JsLoggingLevel {
@@ -810,6 +1532,32 @@ pub struct JsAstConfigOptions {
   /// will overwrite the existing members when conflicting.
   #[napi(ts_type = "ResolveConflictStrategy | undefined")]
   pub resolve_conflict_strategy: Option<JsResolveConflictStrategy>,
+  /// Where a colliding fragment function's body lands relative to the target's own statements
+  /// when `resolveConflictStrategy` is `"merge"`. Defaults to `"around"` (runs both before and
+  /// after). Ignored by every other strategy.
+  #[napi(ts_type = "MergePlacement | undefined")]
+  pub merge_placement: Option<JsMergePlacement>,
+  /// When `true`, edge instrumentation (`injectShadowAtEdges`/`injectShadowAsModifier`) refuses
+  /// any function containing inline assembly outright instead of instrumenting around Yul exit
+  /// points. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub reject_inline_assembly: Option<bool>,
+  /// When `true`, an `evmVersion` that exceeds what the resolved `solcVersion` supports is a hard
+  /// error instead of being clamped down with a warning. Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub strict_evm_version: Option<bool>,
+  /// Absolute path to a `solc` binary to use directly, bypassing the installed-version lookup.
+  /// Useful for pointing at a binary the svm install directory doesn't know about.
+  #[napi(ts_type = "string | undefined")]
+  pub solc_path: Option<String>,
+  /// Forces offline AST operations when `true`, mirroring `CompilerConfigOptions::offlineMode`.
+  /// Defaults to `false`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub offline: Option<bool>,
+  /// Controls whether `parseSourceAst` reuses the on-disk AST cache, mirroring
+  /// `CompilerConfigOptions::cacheEnabled`. Defaults to `true`.
+  #[napi(ts_type = "boolean | undefined")]
+  pub cache_enabled: Option<bool>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -837,17 +1585,42 @@ pub enum ResolveConflictStrategy {
   #[default]
   Safe,
   Replace,
+  /// Replaces a target member matching the fragment member's name, regardless of overload
+  /// signature -- blunter than [`Self::Replace`], which only replaces a member whose full
+  /// signature matches.
+  Overwrite,
+  /// Suffixes a colliding fragment member's name (and every in-fragment reference to it) to a
+  /// unique name instead of replacing or erroring, then inserts it alongside the target member.
+  Rename,
+  /// Inserts a colliding fragment member alongside the target member when their signatures
+  /// differ (a legitimate overload); falls back to [`Self::Overwrite`]'s behavior when the
+  /// signatures are identical, since two members with the same name and signature can't coexist.
+  KeepBoth,
+  /// Splices a colliding fragment function's body around the target function's own statements
+  /// instead of replacing it, matched by the same name+signature+kind key [`Self::Replace`] uses
+  /// so overloads are merged independently. Where the fragment's statements land is controlled by
+  /// [`AstConfig::merge_placement`]. Non-colliding members and non-function conflicts fall back to
+  /// [`Self::Safe`]'s append behavior.
+  Merge,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum JsResolveConflictStrategy {
   Safe,
   Replace,
+  Overwrite,
+  Rename,
+  KeepBoth,
+  Merge,
 }
 
 impl_js_enum_string_traits!(JsResolveConflictStrategy {
   Safe => "safe",
-  Replace => "replace"
+  Replace => "replace",
+  Overwrite => "overwrite",
+  Rename => "rename",
+  KeepBoth => "keepBoth",
+  Merge => "merge"
 });
 
 impl From<JsResolveConflictStrategy> for ResolveConflictStrategy {
@@ -855,6 +1628,10 @@ impl From<JsResolveConflictStrategy> for ResolveConflictStrategy {
     match strategy {
       JsResolveConflictStrategy::Safe => ResolveConflictStrategy::Safe,
       JsResolveConflictStrategy::Replace => ResolveConflictStrategy::Replace,
+      JsResolveConflictStrategy::Overwrite => ResolveConflictStrategy::Overwrite,
+      JsResolveConflictStrategy::Rename => ResolveConflictStrategy::Rename,
+      JsResolveConflictStrategy::KeepBoth => ResolveConflictStrategy::KeepBoth,
+      JsResolveConflictStrategy::Merge => ResolveConflictStrategy::Merge,
     }
   }
 }
@@ -864,6 +1641,59 @@ impl From<ResolveConflictStrategy> for JsResolveConflictStrategy {
     match strategy {
       ResolveConflictStrategy::Safe => JsResolveConflictStrategy::Safe,
       ResolveConflictStrategy::Replace => JsResolveConflictStrategy::Replace,
+      ResolveConflictStrategy::Overwrite => JsResolveConflictStrategy::Overwrite,
+      ResolveConflictStrategy::Rename => JsResolveConflictStrategy::Rename,
+      ResolveConflictStrategy::KeepBoth => JsResolveConflictStrategy::KeepBoth,
+      ResolveConflictStrategy::Merge => JsResolveConflictStrategy::Merge,
+    }
+  }
+}
+
+/// Where a colliding fragment function's body lands relative to the target function's own
+/// statements under [`ResolveConflictStrategy::Merge`]. Mirrors the before/after vocabulary
+/// [`super::super::ast::instrumenter::inject_edges`] already uses for wrapping a single function.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergePlacement {
+  /// Fragment statements run once, before the target's own statements.
+  Before,
+  /// Fragment statements run before every `return` in the target function (and once more,
+  /// appended at the end, so a fallthrough exit still runs them too).
+  After,
+  /// Fragment statements run both before and after -- the target's body is wrapped on both
+  /// sides. The default, since this is what "wraps existing function bodies" means literally.
+  #[default]
+  Around,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsMergePlacement {
+  Before,
+  After,
+  Around,
+}
+
+impl_js_enum_string_traits!(JsMergePlacement {
+  Before => "before",
+  After => "after",
+  Around => "around"
+});
+
+impl From<JsMergePlacement> for MergePlacement {
+  fn from(placement: JsMergePlacement) -> Self {
+    match placement {
+      JsMergePlacement::Before => MergePlacement::Before,
+      JsMergePlacement::After => MergePlacement::After,
+      JsMergePlacement::Around => MergePlacement::Around,
+    }
+  }
+}
+
+impl From<MergePlacement> for JsMergePlacement {
+  fn from(placement: MergePlacement) -> Self {
+    match placement {
+      MergePlacement::Before => JsMergePlacement::Before,
+      MergePlacement::After => JsMergePlacement::After,
+      MergePlacement::Around => JsMergePlacement::Around,
     }
   }
 }
@@ -873,6 +1703,8 @@ pub(crate) trait SolcUserOptions {
   fn compiler_language(&self) -> Option<CompilerLanguage>;
   fn compiler_settings(&self) -> Option<&CompilerSettingsOptions>;
   fn resolved_settings(&self) -> Option<&Settings>;
+  fn strict_evm_version(&self) -> bool;
+  fn solc_path(&self) -> Option<&Path>;
 }
 
 impl SolcUserOptions for SolcConfigOptions {
@@ -891,6 +1723,14 @@ impl SolcUserOptions for SolcConfigOptions {
   fn resolved_settings(&self) -> Option<&Settings> {
     self.resolved_settings.as_ref()
   }
+
+  fn strict_evm_version(&self) -> bool {
+    self.strict_evm_version.unwrap_or(false)
+  }
+
+  fn solc_path(&self) -> Option<&Path> {
+    self.path.as_deref()
+  }
 }
 
 impl SolcUserOptions for CompilerConfigOptions {
@@ -909,6 +1749,14 @@ impl SolcUserOptions for CompilerConfigOptions {
   fn resolved_settings(&self) -> Option<&Settings> {
     self.solc.resolved_settings.as_ref()
   }
+
+  fn strict_evm_version(&self) -> bool {
+    self.solc.strict_evm_version.unwrap_or(false)
+  }
+
+  fn solc_path(&self) -> Option<&Path> {
+    self.solc.path.as_deref()
+  }
 }
 
 impl SolcUserOptions for AstConfigOptions {
@@ -924,9 +1772,17 @@ impl SolcUserOptions for AstConfigOptions {
     self.solc.settings.as_ref()
   }
 
+  fn solc_path(&self) -> Option<&Path> {
+    self.solc.path.as_deref()
+  }
+
   fn resolved_settings(&self) -> Option<&Settings> {
     self.solc.resolved_settings.as_ref()
   }
+
+  fn strict_evm_version(&self) -> bool {
+    self.solc.strict_evm_version.unwrap_or(false)
+  }
 }
 
 #[derive(Clone, Debug)]
@@ -934,6 +1790,9 @@ pub struct SolcConfig {
   pub version: Version,
   pub settings: Settings,
   pub language: FoundrySolcLanguage,
+  /// Explicit solc binary to use instead of resolving `version` against the svm install
+  /// directory. See [`SolcConfigOptions::path`].
+  pub path: Option<PathBuf>,
 }
 
 impl SolcConfig {
@@ -967,19 +1826,28 @@ impl SolcConfig {
       .unwrap_or(default_language);
     let solc_language = solc_language_from(language)?;
 
+    let strict_evm_version = overrides.map(|opts| opts.strict_evm_version()).unwrap_or(false);
+    let normalized_settings = normalize_settings_evm_version(
+      overrides.and_then(|opts| opts.compiler_settings()),
+      &version,
+      strict_evm_version,
+    )?;
+
     let settings = if let Some(resolved) = overrides.and_then(|opts| opts.resolved_settings()) {
-      sanitize_settings(resolved)?
+      sanitize_settings(resolved, &version)?
     } else {
-      merge_settings(
-        default_settings,
-        overrides.and_then(|opts| opts.compiler_settings()),
-      )?
+      merge_settings(default_settings, normalized_settings.as_ref(), &version)?
     };
 
+    let path = overrides
+      .and_then(|opts| opts.solc_path())
+      .map(Path::to_path_buf);
+
     Ok(SolcConfig {
       version,
       settings,
       language: solc_language,
+      path,
     })
   }
 
@@ -994,19 +1862,29 @@ impl SolcConfig {
       .unwrap_or_else(|| CompilerLanguage::from(self.language));
     let solc_language = solc_language_from(language)?;
 
+    let strict_evm_version = overrides.map(|opts| opts.strict_evm_version()).unwrap_or(false);
+    let normalized_settings = normalize_settings_evm_version(
+      overrides.and_then(|opts| opts.compiler_settings()),
+      &version,
+      strict_evm_version,
+    )?;
+
     let settings = if let Some(resolved) = overrides.and_then(|opts| opts.resolved_settings()) {
-      sanitize_settings(resolved)?
+      sanitize_settings(resolved, &version)?
     } else {
-      merge_settings(
-        &self.settings,
-        overrides.and_then(|opts| opts.compiler_settings()),
-      )?
+      merge_settings(&self.settings, normalized_settings.as_ref(), &version)?
     };
 
+    let path = overrides
+      .and_then(|opts| opts.solc_path())
+      .map(Path::to_path_buf)
+      .or_else(|| self.path.clone());
+
     Ok(SolcConfig {
       version,
       settings,
       language: solc_language,
+      path,
     })
   }
 }
@@ -1088,6 +1966,51 @@ fn parse_version(value: &str) -> Result<Version> {
   )
 }
 
+/// Parses a requirement-style solc version selection, mirroring Cargo's `rust-version`/
+/// `PartialVersion` syntax: a plain `major.minor` (or bare `major`) matches any release sharing
+/// that prefix, a plain full `major.minor.patch` pins that exact release, and a caret-prefixed
+/// `^major[.minor[.patch]]` matches any release compatible with it per ordinary semver caret
+/// rules. Rejects anything else (a bare non-numeric component, a pre-release/build-metadata
+/// suffix, or a multi-comparator range) with a structured error instead of panicking.
+fn parse_compiler_version_requirement(value: &str) -> Result<VersionReq> {
+  let invalid = || {
+    napi_error(format!(
+      "Unsupported compiler version requirement \"{value}\"; expected a plain version like \
+       \"0.8\" or \"0.8.19\", or a caret range like \"^0.8.19\""
+    ))
+  };
+
+  let trimmed = value.trim();
+  let (is_caret, rest) = match trimmed.strip_prefix('^') {
+    Some(rest) => (true, rest.trim()),
+    None => (false, trimmed),
+  };
+
+  let parts: Vec<&str> = rest.split('.').collect();
+  if parts.is_empty()
+    || parts.len() > 3
+    || parts
+      .iter()
+      .any(|part| part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()))
+  {
+    return Err(invalid());
+  }
+
+  // `VersionReq::parse` already treats a bare `major[.minor]` as "compatible with that prefix"
+  // (caret semantics), so only the remaining two cases need an explicit operator: a caret prefix
+  // is forwarded as-is, and a full `major.minor.patch` with no caret is pinned exactly via `=`
+  // (the operator `VersionReq` would otherwise default to caret for).
+  let normalized = if is_caret {
+    format!("^{rest}")
+  } else if parts.len() == 3 {
+    format!("={rest}")
+  } else {
+    rest.to_string()
+  };
+
+  VersionReq::parse(&normalized).map_err(|_| invalid())
+}
+
 fn parse_severity(value: &str) -> Result<Severity> {
   match value.to_ascii_lowercase().as_str() {
     "error" => Ok(Severity::Error),
@@ -1099,6 +2022,17 @@ fn parse_severity(value: &str) -> Result<Severity> {
   }
 }
 
+fn parse_severity_override_level(value: &str) -> Result<SeverityOverrideLevel> {
+  match value.to_ascii_lowercase().as_str() {
+    "allow" => Ok(SeverityOverrideLevel::Allow),
+    "warning" | "warn" => Ok(SeverityOverrideLevel::Warn),
+    "error" => Ok(SeverityOverrideLevel::Error),
+    other => Err(napi_error(format!(
+      "Unsupported severity override level \"{other}\""
+    ))),
+  }
+}
+
 #[derive(Default)]
 pub(crate) struct CompilerConfigBuilder {
   config: CompilerConfig,
@@ -1119,13 +2053,16 @@ impl CompilerConfigBuilder {
     let CompilerConfigOptions {
       compiler,
       mut solc,
+      compiler_version,
       mut vyper,
       cache_enabled,
+      force_rebuild,
       offline_mode,
       no_artifacts,
       build_info_enabled,
       slash_paths,
       solc_jobs,
+      max_jobs,
       sparse_output,
       allow_paths,
       include_paths,
@@ -1135,6 +2072,19 @@ impl CompilerConfigBuilder {
       ignored_error_codes,
       compiler_severity_filter,
       logging_level,
+      restrictions,
+      severity_overrides,
+      auto_detect_version,
+      output_mode,
+      artifact_format,
+      artifact_output,
+      artifact_field_selection,
+      source_maps,
+      inline_source_map,
+      inline_sources,
+      deny_warnings,
+      diagnostic_path_denylist,
+      diagnostic_path_allowlist,
     } = overrides;
 
     if let Some(language) = compiler {
@@ -1146,9 +2096,24 @@ impl CompilerConfigBuilder {
       self.config.solc_version = version;
     }
     if let Some(settings) = solc.resolved_settings.take() {
-      self.config.solc_settings = sanitize_settings(&settings)?;
+      // A pre-resolved `Settings` bundle is taken verbatim, AST output included, so there's
+      // nothing to infer here; the caller chose the whole output selection themselves.
+      self.config.include_ast_output = true;
+      self.config.solc_settings = sanitize_settings(&settings, &self.config.solc_version)?;
     } else if let Some(settings) = solc.settings.take() {
-      self.config.solc_settings = merge_settings(&self.config.solc_settings, Some(&settings))?;
+      if settings.include_ast == Some(true)
+        || settings
+          .output_selection
+          .as_ref()
+          .is_some_and(requests_ast_output)
+      {
+        self.config.include_ast_output = true;
+      }
+      self.config.solc_settings = merge_settings(
+        &self.config.solc_settings,
+        Some(&settings),
+        &self.config.solc_version,
+      )?;
     }
     if let Some(path) = vyper.path.take() {
       self.config.vyper_settings.path = Some(path);
@@ -1157,6 +2122,9 @@ impl CompilerConfigBuilder {
       self.config.vyper_settings.optimize = Some(optimize);
     }
     if let Some(evm_version) = vyper.evm_version.take() {
+      // Unlike solc, the Vyper binary's version isn't resolved until the compiler actually runs
+      // (`vyper_settings.path` is only a path, not a parsed `Version`), so there's no version
+      // table to clamp/validate this against here; Vyper itself rejects an unsupported target.
       self.config.vyper_settings.evm_version = Some(evm_version);
     }
     if let Some(bytecode_metadata) = vyper.bytecode_metadata.take() {
@@ -1174,9 +2142,15 @@ impl CompilerConfigBuilder {
     if let Some(cache) = cache_enabled {
       self.config.cache_enabled = cache;
     }
+    if let Some(force) = force_rebuild {
+      self.config.force_rebuild = force;
+    }
     if let Some(offline) = offline_mode {
       self.config.offline_mode = offline;
     }
+    if let Some(requirement) = compiler_version {
+      self.config.solc_version = pragma::resolve_version(&requirement, self.config.offline_mode)?;
+    }
     if let Some(no_artifacts) = no_artifacts {
       self.config.no_artifacts = no_artifacts;
     }
@@ -1189,6 +2163,9 @@ impl CompilerConfigBuilder {
     if let Some(solc_jobs) = solc_jobs {
       self.config.solc_jobs = solc_jobs;
     }
+    if let Some(max_jobs) = max_jobs {
+      self.config.max_jobs = max_jobs.max(1);
+    }
     if let Some(sparse_output) = sparse_output {
       self.config.sparse_output = sparse_output;
     }
@@ -1216,14 +2193,91 @@ impl CompilerConfigBuilder {
     if let Some(level) = logging_level {
       self.config.logging_level = level;
     }
+    if let Some(restrictions) = restrictions {
+      self.config.restrictions = restrictions;
+    }
+    if let Some(severity_overrides) = severity_overrides {
+      self.config.severity_overrides = severity_overrides;
+    }
+    if let Some(auto_detect_version) = auto_detect_version {
+      self.config.auto_detect_version = auto_detect_version;
+    }
+    if let Some(output_mode) = output_mode {
+      self.config.output_mode = output_mode;
+      self.config.solc_settings.output_selection =
+        output_mode.output_selection(self.config.build_info_enabled);
+    }
+    if let Some(artifact_format) = artifact_format {
+      self.config.artifact_format = artifact_format;
+    }
+    if let Some(artifact_output) = artifact_output {
+      self.config.artifact_output = Some(artifact_output);
+    }
+    if let Some(artifact_field_selection) = artifact_field_selection {
+      self.config.artifact_field_selection = artifact_field_selection;
+    }
+    if let Some(source_maps) = source_maps {
+      self.config.source_maps_enabled = source_maps;
+    }
+    if let Some(inline_source_map) = inline_source_map {
+      self.config.inline_source_map_enabled = inline_source_map;
+      if inline_source_map {
+        self.config.source_maps_enabled = true;
+      }
+    }
+    if let Some(inline_sources) = inline_sources {
+      self.config.inline_sources_enabled = inline_sources;
+      if inline_sources {
+        self.config.inline_source_map_enabled = true;
+        self.config.source_maps_enabled = true;
+      }
+    }
+    if self.config.source_maps_enabled {
+      self.config.solc_settings.output_selection =
+        add_source_map_outputs(&self.config.solc_settings.output_selection);
+    }
+    if let Some(deny_warnings) = deny_warnings {
+      self.config.deny_warnings = deny_warnings;
+    }
+    if let Some(denylist) = diagnostic_path_denylist {
+      self.config.diagnostic_path_denylist = denylist;
+    }
+    if let Some(allowlist) = diagnostic_path_allowlist {
+      self.config.diagnostic_path_allowlist = allowlist;
+    }
 
     Ok(self)
   }
 
   pub fn build(mut self) -> Result<CompilerConfig> {
-    self.config.solc_settings = sanitize_settings(&self.config.solc_settings)?;
+    self.config.solc_settings =
+      sanitize_settings(&self.config.solc_settings, &self.config.solc_version)?;
+    if !self.config.include_ast_output {
+      self.config.solc_settings.output_selection =
+        strip_unrequested_ast_output(&self.config.solc_settings.output_selection);
+    }
+    self.fold_unsupported_include_paths();
     Ok(self.config)
   }
+
+  /// Solc only understands `--include-path` from 0.8.8 onward. When the resolved version
+  /// predates that, fold `include_paths` into `allow_paths` (which every supported solc
+  /// understands) rather than forwarding a flag the compiler will reject, and warn so callers
+  /// targeting a mixed-version project notice the downgrade.
+  fn fold_unsupported_include_paths(&mut self) {
+    if self.config.include_paths.is_empty() || self.config.supports_include_path() {
+      return;
+    }
+    warn!(
+      target: LOG_TARGET,
+      "solc {} predates --include-path (requires {}); folding {} include path(s) into --allow-paths",
+      self.config.solc_version,
+      *INCLUDE_PATH_VERSION_REQ,
+      self.config.include_paths.len()
+    );
+    let include_paths = std::mem::take(&mut self.config.include_paths);
+    self.config.allow_paths.extend(include_paths);
+  }
 }
 
 #[cfg(test)]
@@ -1330,7 +2384,8 @@ mod tests {
       BTreeMap::from([("*".to_string(), Vec::new()), (String::new(), Vec::new())]),
     )]));
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("settings");
+    let merged =
+      merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("settings");
     assert!(
       !crate::internal::settings::output_selection_is_effectively_empty(&merged.output_selection),
       "merged selection should fallback to defaults"
@@ -1356,4 +2411,429 @@ mod tests {
       .to_string()
       .contains("Unsupported compiler severity filter"));
   }
+
+  #[test]
+  fn abi_only_output_mode_drops_ast_and_bytecode() {
+    let mut options = CompilerConfigOptions::default();
+    options.output_mode = Some(OutputMode::AbiOnly);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let per_contract = built
+      .solc_settings
+      .output_selection
+      .as_ref()
+      .get("*")
+      .expect("wildcard file entry");
+    assert_eq!(per_contract.get("*"), Some(&vec!["abi".to_string()]));
+    assert!(!per_contract.contains_key(""));
+  }
+
+  #[test]
+  fn minimal_output_mode_includes_ast_when_build_info_enabled() {
+    let mut options = CompilerConfigOptions::default();
+    options.build_info_enabled = Some(true);
+    options.output_mode = Some(OutputMode::Minimal);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let per_contract = built
+      .solc_settings
+      .output_selection
+      .as_ref()
+      .get("*")
+      .expect("wildcard file entry");
+    assert_eq!(
+      per_contract.get("*"),
+      Some(&vec![
+        "abi".to_string(),
+        "evm.bytecode.object".to_string(),
+        "evm.deployedBytecode.object".to_string()
+      ])
+    );
+    assert_eq!(per_contract.get(""), Some(&vec!["ast".to_string()]));
+  }
+
+  #[test]
+  fn full_output_mode_is_the_default() {
+    let baseline = CompilerConfig::default();
+    assert_eq!(baseline.output_mode, OutputMode::Full);
+  }
+
+  #[test]
+  fn foundry_artifact_format_is_the_default() {
+    let baseline = CompilerConfig::default();
+    assert_eq!(baseline.artifact_format, ArtifactFormat::Foundry);
+  }
+
+  #[test]
+  fn hardhat_artifact_format_override_is_applied() {
+    let mut options = CompilerConfigOptions::default();
+    options.artifact_format = Some(ArtifactFormat::Hardhat);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert_eq!(built.artifact_format, ArtifactFormat::Hardhat);
+  }
+
+  #[test]
+  fn js_artifact_format_maps_to_core_enum() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.artifact_format = Some(JsArtifactFormat::Hardhat);
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("convert options");
+    assert_eq!(overrides.artifact_format, Some(ArtifactFormat::Hardhat));
+  }
+
+  #[test]
+  fn artifact_output_defaults_to_per_layout_selection() {
+    let baseline = CompilerConfig::default();
+    assert_eq!(baseline.artifact_output, None);
+  }
+
+  #[test]
+  fn artifact_output_override_is_applied() {
+    let mut options = CompilerConfigOptions::default();
+    options.artifact_output = Some(ArtifactOutputFormat::Synthetic);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert_eq!(built.artifact_output, Some(ArtifactOutputFormat::Synthetic));
+  }
+
+  #[test]
+  fn js_artifact_output_maps_to_core_enum() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.artifact_output = Some(JsArtifactOutputFormat::Foundry);
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("convert options");
+    assert_eq!(overrides.artifact_output, Some(ArtifactOutputFormat::Foundry));
+  }
+
+  #[test]
+  fn js_artifact_output_maps_truffle_to_core_enum() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.artifact_output = Some(JsArtifactOutputFormat::Truffle);
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("convert options");
+    assert_eq!(overrides.artifact_output, Some(ArtifactOutputFormat::Truffle));
+  }
+
+  #[test]
+  fn artifact_field_selection_defaults_to_all() {
+    let baseline = CompilerConfig::default();
+    assert_eq!(baseline.artifact_field_selection, ArtifactFieldSelection::ALL);
+  }
+
+  #[test]
+  fn artifact_field_selection_override_is_applied() {
+    let mut options = CompilerConfigOptions::default();
+    options.artifact_field_selection = Some(ArtifactFieldSelection {
+      ir: false,
+      ir_optimized: false,
+      assembly: false,
+      ..ArtifactFieldSelection::ALL
+    });
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(!built.artifact_field_selection.ir);
+    assert!(!built.artifact_field_selection.ir_optimized);
+    assert!(!built.artifact_field_selection.assembly);
+    assert!(built.artifact_field_selection.abi);
+  }
+
+  #[test]
+  fn js_artifact_field_selection_falls_back_to_all_for_omitted_fields() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.artifact_field_selection = Some(JsArtifactFieldSelection {
+      abi: Some(true),
+      deployed_bytecode: Some(true),
+      ir: Some(false),
+      ..Default::default()
+    });
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("convert options");
+    let selection = overrides
+      .artifact_field_selection
+      .expect("selection override present");
+    assert!(selection.abi);
+    assert!(selection.deployed_bytecode);
+    assert!(!selection.ir);
+    assert!(selection.metadata);
+    assert!(selection.gas_estimates);
+  }
+
+  #[test]
+  fn source_maps_disabled_by_default() {
+    let baseline = CompilerConfig::default();
+    assert!(!baseline.source_maps_enabled);
+  }
+
+  #[test]
+  fn enabling_source_maps_augments_output_selection() {
+    let mut options = CompilerConfigOptions::default();
+    options.source_maps = Some(true);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.source_maps_enabled);
+    let per_contract = built
+      .solc_settings
+      .output_selection
+      .as_ref()
+      .get("*")
+      .expect("wildcard file entry")
+      .get("*")
+      .expect("wildcard contract entry");
+    assert!(per_contract.contains(&"evm.bytecode.sourceMap".to_string()));
+    assert!(per_contract.contains(&"evm.deployedBytecode.sourceMap".to_string()));
+  }
+
+  #[test]
+  fn enabling_inline_source_map_implies_source_maps() {
+    let mut options = CompilerConfigOptions::default();
+    options.inline_source_map = Some(true);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.inline_source_map_enabled);
+    assert!(built.source_maps_enabled);
+  }
+
+  #[test]
+  fn enabling_inline_sources_implies_inline_source_map_and_source_maps() {
+    let mut options = CompilerConfigOptions::default();
+    options.inline_sources = Some(true);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.inline_sources_enabled);
+    assert!(built.inline_source_map_enabled);
+    assert!(built.source_maps_enabled);
+  }
+
+  #[test]
+  fn supports_include_path_requires_0_8_8() {
+    let mut config = CompilerConfig::default();
+    config.solc_version = Version::new(0, 8, 7);
+    assert!(!config.supports_include_path());
+    config.solc_version = Version::new(0, 8, 8);
+    assert!(config.supports_include_path());
+  }
+
+  #[test]
+  fn supports_base_path_requires_0_6_9() {
+    let mut config = CompilerConfig::default();
+    config.solc_version = Version::new(0, 6, 8);
+    assert!(!config.supports_base_path());
+    config.solc_version = Version::new(0, 6, 9);
+    assert!(config.supports_base_path());
+  }
+
+  #[test]
+  fn builder_folds_include_paths_into_allow_paths_for_old_solc() {
+    let mut options = CompilerConfigOptions::default();
+    options.solc.version = Some(Version::new(0, 7, 6));
+    options.include_paths = Some(BTreeSet::from([PathBuf::from("/tmp/includes")]));
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.include_paths.is_empty());
+    assert!(built.allow_paths.contains(&PathBuf::from("/tmp/includes")));
+  }
+
+  #[test]
+  fn builder_leaves_include_paths_untouched_when_supported() {
+    let mut options = CompilerConfigOptions::default();
+    options.solc.version = Some(Version::new(0, 8, 30));
+    options.include_paths = Some(BTreeSet::from([PathBuf::from("/tmp/includes")]));
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.allow_paths.is_empty());
+    assert!(built.include_paths.contains(&PathBuf::from("/tmp/includes")));
+  }
+
+  #[test]
+  fn max_evm_version_for_solc_matches_ceiling_table() {
+    assert_eq!(
+      max_evm_version_for_solc(&Version::new(0, 4, 20)),
+      EvmVersion::Byzantium
+    );
+    assert_eq!(
+      max_evm_version_for_solc(&Version::new(0, 5, 5)),
+      EvmVersion::Petersburg
+    );
+    assert_eq!(
+      max_evm_version_for_solc(&Version::new(0, 8, 25)),
+      EvmVersion::Cancun
+    );
+    assert_eq!(
+      max_evm_version_for_solc(&Version::new(0, 8, 29)),
+      EvmVersion::Prague
+    );
+  }
+
+  #[test]
+  fn normalize_evm_version_clamps_unsupported_target_by_default() {
+    let normalized = normalize_evm_version(&Version::new(0, 8, 20), Some(EvmVersion::Cancun), false)
+      .expect("clamp instead of error");
+    assert_eq!(normalized, Some(EvmVersion::Shanghai));
+  }
+
+  #[test]
+  fn normalize_evm_version_errors_when_strict() {
+    let err = normalize_evm_version(&Version::new(0, 8, 20), Some(EvmVersion::Cancun), true)
+      .expect_err("strict mode should reject an unsupported evmVersion");
+    assert!(err.to_string().contains("is not supported by solc"));
+  }
+
+  #[test]
+  fn normalize_evm_version_passes_through_supported_target() {
+    let normalized =
+      normalize_evm_version(&Version::new(0, 8, 24), Some(EvmVersion::Cancun), true)
+        .expect("supported evmVersion is never rejected");
+    assert_eq!(normalized, Some(EvmVersion::Cancun));
+  }
+
+  #[test]
+  fn solc_config_clamps_evm_version_to_what_solc_supports() {
+    let mut options = AstConfigOptions::default();
+    options.solc.version = Some(Version::new(0, 8, 20));
+    let mut settings_overrides = CompilerSettingsOptions::default();
+    settings_overrides.evm_version = Some(EvmVersion::Cancun);
+    options.solc.settings = Some(settings_overrides);
+
+    let ast_config = AstConfig::from_options(
+      &FoundrySolcLanguage::Solidity,
+      &Settings::default(),
+      Some(&options),
+    )
+    .expect("build ast config");
+
+    let settings_json =
+      serde_json::to_value(&ast_config.solc.settings).expect("settings serialize");
+    assert_eq!(
+      settings_json.get("evmVersion").and_then(|v| v.as_str()),
+      Some("shanghai")
+    );
+  }
+
+  #[test]
+  fn solc_config_rejects_evm_version_when_strict_evm_version_set() {
+    let mut options = AstConfigOptions::default();
+    options.solc.version = Some(Version::new(0, 8, 20));
+    options.solc.strict_evm_version = Some(true);
+    let mut settings_overrides = CompilerSettingsOptions::default();
+    settings_overrides.evm_version = Some(EvmVersion::Cancun);
+    options.solc.settings = Some(settings_overrides);
+
+    let err = AstConfig::from_options(
+      &FoundrySolcLanguage::Solidity,
+      &Settings::default(),
+      Some(&options),
+    )
+    .expect_err("strict evmVersion should surface as a build error");
+    assert!(err.to_string().contains("is not supported by solc"));
+  }
+
+  #[test]
+  fn deny_warnings_disabled_by_default() {
+    let baseline = CompilerConfig::default();
+    assert!(!baseline.deny_warnings);
+  }
+
+  #[test]
+  fn deny_warnings_none_inherits_the_base_config_value() {
+    let mut base = CompilerConfig::default();
+    base.deny_warnings = true;
+
+    let built = CompilerConfigBuilder::with_base(base)
+      .apply_compiler_options(CompilerConfigOptions::default())
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(built.deny_warnings);
+  }
+
+  #[test]
+  fn deny_warnings_explicit_override_wins() {
+    let mut base = CompilerConfig::default();
+    base.deny_warnings = true;
+    let mut options = CompilerConfigOptions::default();
+    options.deny_warnings = Some(false);
+
+    let built = CompilerConfigBuilder::with_base(base)
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(!built.deny_warnings);
+  }
+
+  #[test]
+  fn diagnostic_path_filters_are_empty_by_default() {
+    let baseline = CompilerConfig::default();
+    assert!(baseline.diagnostic_path_denylist.is_empty());
+    assert!(baseline.diagnostic_path_allowlist.is_empty());
+  }
+
+  #[test]
+  fn diagnostic_path_filters_replace_the_existing_lists() {
+    let mut options = CompilerConfigOptions::default();
+    options.diagnostic_path_denylist = Some(vec!["node_modules/**".to_string()]);
+    options.diagnostic_path_allowlist = Some(vec!["contracts/**".to_string()]);
+
+    let built = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert_eq!(built.diagnostic_path_denylist, vec!["node_modules/**"]);
+    assert_eq!(built.diagnostic_path_allowlist, vec!["contracts/**"]);
+  }
 }
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use foundry_compilers::artifacts::remappings::Remapping;
+use foundry_config::{Config as FoundryConfig, SolcReq};
+use log::warn;
+use semver::Version;
+use serde::Deserialize;
+
+use super::config::{CompilerConfigOptions, CompilerLanguage};
+use super::project::read_package_remappings;
+use super::settings::OptimizerSettingsOptions;
+
+const LOG_TARGET: &str = "tevm::compiler.config_discovery";
+
+/// Name of the JSON config file [`discover_layered_options`] looks for at each directory it walks
+/// through on its way up from the project root.
+const CONFIG_FILE_NAME: &str = "tevm.config.json";
+
+/// Minimal JSON config shape read from [`CONFIG_FILE_NAME`]. Only the fields significant to this
+/// crate are deserialized -- the same way Deno's `EmitConfigOptions` pulls a handful of fields out
+/// of a full `tsconfig.json` -- so a config file shared with other tooling doesn't need to be
+/// pruned down to exactly what we understand first; unknown keys are ignored rather than rejected.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct DiscoveredConfigFile {
+  compiler: Option<String>,
+  solc: Option<Version>,
+  optimizer: Option<OptimizerSettingsOptions>,
+  #[serde(rename = "outputSelection")]
+  output_selection: Option<BTreeMap<String, BTreeMap<String, Vec<String>>>>,
+  remappings: Option<Vec<String>>,
+}
+
+/// Walks upward from `start` (inclusive) to the filesystem root, merging in the first
+/// `tevm.config.json`, `foundry.toml`/`foundry.json` `[profile]` section, and `remappings.txt`
+/// found along the way. Each of the three sources is independent -- a directory may have a
+/// `remappings.txt` without a `tevm.config.json`, or vice versa -- so all three are searched for
+/// at every directory visited, and the first hit for each wins. Remappings found this way are
+/// returned separately from the rest of the options so a caller can concatenate them with any
+/// remappings already configured, rather than having them silently replace one another the way
+/// every other [`CompilerConfigOptions`] field does when merged.
+///
+/// Returns `CompilerConfigOptions::default()` (with empty remappings) when nothing is found,
+/// which is a no-op when merged on top of an existing configuration.
+pub(crate) fn discover_layered_options(start: &Path) -> CompilerConfigOptions {
+  let mut options = CompilerConfigOptions::default();
+  let mut remappings: Vec<Remapping> = Vec::new();
+  let mut found_config_file = false;
+  let mut found_foundry_profile = false;
+  let mut found_remappings_file = false;
+
+  for dir in start.ancestors() {
+    if !found_remappings_file {
+      if let Some(found) = read_package_remappings(dir) {
+        remappings.extend(found);
+        found_remappings_file = true;
+      }
+    }
+
+    if !found_foundry_profile && has_foundry_config(dir) {
+      apply_foundry_profile(dir, &mut options, &mut remappings);
+      found_foundry_profile = true;
+    }
+
+    if !found_config_file {
+      let config_path = dir.join(CONFIG_FILE_NAME);
+      if let Ok(contents) = fs::read_to_string(&config_path) {
+        match serde_json::from_str::<DiscoveredConfigFile>(&contents) {
+          Ok(discovered) => {
+            apply_discovered_config_file(discovered, &mut options);
+            found_config_file = true;
+          }
+          Err(err) => {
+            warn!(
+              target: LOG_TARGET,
+              "ignoring unparsable {}: {}",
+              config_path.display(),
+              err
+            );
+          }
+        }
+      }
+    }
+
+    if found_config_file && found_foundry_profile && found_remappings_file {
+      break;
+    }
+  }
+
+  if !remappings.is_empty() {
+    options.remappings = Some(remappings);
+  }
+  options
+}
+
+fn has_foundry_config(dir: &Path) -> bool {
+  ["foundry.toml", "foundry.json"]
+    .iter()
+    .any(|name| dir.join(name).is_file())
+}
+
+/// Pulls just the solc version, optimizer settings, and remappings out of `dir`'s `foundry.toml`
+/// `[profile]` section. Unlike [`super::project::FoundryAdapter::load`], which binds a whole
+/// [`super::project::ProjectContext`] to a Foundry layout, this only needs the handful of fields
+/// that feed into [`CompilerConfigOptions`], so failures here are logged and swallowed rather than
+/// propagated -- a malformed `foundry.toml` encountered while merely discovering defaults
+/// shouldn't break a call that didn't ask to compile as a Foundry project.
+fn apply_foundry_profile(
+  dir: &Path,
+  options: &mut CompilerConfigOptions,
+  remappings: &mut Vec<Remapping>,
+) {
+  let figment = FoundryConfig::figment_with_root(dir);
+  let config = match FoundryConfig::try_from(figment) {
+    Ok(config) => config,
+    Err(err) => {
+      warn!(
+        target: LOG_TARGET,
+        "ignoring unparsable foundry.toml/foundry.json in {}: {}",
+        dir.display(),
+        err
+      );
+      return;
+    }
+  };
+
+  if let Some(SolcReq::Version(version)) = &config.solc {
+    options.solc.version = Some(version.clone());
+  }
+  // Mirrors `FoundryAdapter::load`: `solc_settings()` already produces a fully resolved `Settings`
+  // bundle, so it's taken verbatim via `resolved_settings` rather than the partial `settings`
+  // overlay used for the `tevm.config.json` layer.
+  if let Ok(ethers_settings) = config.solc_settings() {
+    if let Ok(settings_value) = serde_json::to_value(&ethers_settings) {
+      if let Ok(settings) = serde_json::from_value(settings_value) {
+        options.solc.resolved_settings = Some(settings);
+      }
+    }
+  }
+  remappings.extend(
+    config
+      .remappings
+      .iter()
+      .filter_map(|remapping| Remapping::from_str(&remapping.to_string()).ok()),
+  );
+}
+
+fn apply_discovered_config_file(discovered: DiscoveredConfigFile, options: &mut CompilerConfigOptions) {
+  if let Some(compiler) = discovered.compiler.as_deref() {
+    options.compiler = parse_compiler_language(compiler);
+  }
+  if let Some(version) = discovered.solc {
+    options.solc.version = Some(version);
+  }
+  if discovered.optimizer.is_some() || discovered.output_selection.is_some() {
+    let mut settings = options.solc.settings.clone().unwrap_or_default();
+    if let Some(optimizer) = discovered.optimizer {
+      settings.optimizer = Some(optimizer);
+    }
+    if let Some(output_selection) = discovered.output_selection {
+      settings.output_selection = Some(output_selection);
+    }
+    options.solc.settings = Some(settings);
+  }
+}
+
+fn parse_compiler_language(value: &str) -> Option<CompilerLanguage> {
+  match value.to_ascii_lowercase().as_str() {
+    "solidity" | "sol" => Some(CompilerLanguage::Solidity),
+    "yul" => Some(CompilerLanguage::Yul),
+    "vyper" | "vy" => Some(CompilerLanguage::Vyper),
+    other => {
+      warn!(
+        target: LOG_TARGET,
+        "ignoring unrecognised `compiler` value `{other}` in {CONFIG_FILE_NAME}"
+      );
+      None
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn discovers_and_merges_a_json_config_file() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(
+      temp.path().join(CONFIG_FILE_NAME),
+      r#"{"compiler":"solidity","solc":"0.8.24","optimizer":{"enabled":true,"runs":200},"outputSelection":{"*":{"*":["abi"]}}}"#,
+    )
+    .unwrap();
+
+    let options = discover_layered_options(temp.path());
+    assert_eq!(options.solc.version, Some(Version::new(0, 8, 24)));
+    assert!(matches!(options.compiler, Some(CompilerLanguage::Solidity)));
+    let settings = options.solc.settings.expect("settings discovered");
+    assert_eq!(settings.optimizer.unwrap().runs, Some(200));
+    assert!(settings.output_selection.is_some());
+  }
+
+  #[test]
+  fn walks_up_to_find_a_remappings_txt() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(temp.path().join("remappings.txt"), "mypkg/=lib/mypkg/src/\n").unwrap();
+    let nested = temp.path().join("src").join("nested");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let options = discover_layered_options(&nested);
+    let remappings = options.remappings.expect("remappings discovered");
+    assert_eq!(remappings.len(), 1);
+    assert!(remappings[0].to_string().starts_with("mypkg/="));
+  }
+
+  #[test]
+  fn returns_defaults_when_nothing_is_found() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = discover_layered_options(temp.path());
+    assert!(options.remappings.is_none());
+    assert!(options.solc.version.is_none());
+  }
+}
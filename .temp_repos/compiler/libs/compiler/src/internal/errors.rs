@@ -1,19 +1,51 @@
 use std::fmt::{self, Display};
+use std::ops::Range;
 
 use napi::bindgen_prelude::Result as NapiResult;
 use napi::{Error as NapiError, Status};
 
+use crate::compiler::output::CompilerError;
+
 /// Canonical error type used by the Rust-facing API surface.
 #[derive(Debug, Clone)]
-pub struct Error {
-  message: String,
+pub enum Error {
+  /// A plain, single-line failure message.
+  Message(String),
+  /// A failure tied to a byte span within some source text the caller can annotate, e.g. a bad
+  /// `before`/`after` instrumentation snippet. `rendered` is a caret-annotated excerpt ready to
+  /// print as-is; `span` is the byte range it highlights within the original source.
+  Diagnostic {
+    message: String,
+    span: Range<usize>,
+    rendered: String,
+  },
+  /// A required solc release isn't already installed, and `offline_mode` forbids downloading it.
+  /// Carries the unmet version requirement (for structured diagnostics) alongside the full
+  /// human-readable message the resolver produced, so a compile-time caller can surface it as a
+  /// structured `version-resolution` diagnostic instead of aborting with a thrown exception.
+  MissingSolcVersion { requirement: String, message: String },
+  /// No installed solc release satisfies what was asked for, and nothing can be installed to fix
+  /// it (e.g. `offline_mode`, or an explicit path that doesn't exist).
+  CompilerNotInstalled(String),
+  /// The compiler instance or project configuration itself is invalid or incomplete, e.g. an
+  /// operation that requires a project root was called on a rootless instance.
+  ProjectConfig(String),
+  /// Solc (or Vyper) ran and reported error-severity diagnostics instead of producing output.
+  /// Carries the structured diagnostics alongside a human-readable summary, so callers can
+  /// inspect `severity`/`source_location`/etc. instead of parsing `message`.
+  CompilationFailed {
+    message: String,
+    diagnostics: Vec<CompilerError>,
+  },
+  /// A filesystem operation (reading or writing a cache, manifest, or config file) failed.
+  Io(String),
+  /// An AST-specific failure (parsing, analysis, or stitching) that doesn't fit another category.
+  Ast(String),
 }
 
 impl Error {
   pub fn new(message: impl Into<String>) -> Self {
-    Self {
-      message: message.into(),
-    }
+    Self::Message(message.into())
   }
 
   pub fn with_context(context: impl AsRef<str>, cause: impl Display) -> Self {
@@ -23,17 +55,132 @@ impl Error {
     }
     message.push(' ');
     message.push_str(&cause.to_string());
-    Self { message }
+    Self::Message(message)
+  }
+
+  /// Builds a span-aware diagnostic. `rendered` should already contain the annotated source
+  /// excerpt (caret underline and all) so callers can print it verbatim.
+  pub fn diagnostic(message: impl Into<String>, span: Range<usize>, rendered: impl Into<String>) -> Self {
+    Self::Diagnostic {
+      message: message.into(),
+      span,
+      rendered: rendered.into(),
+    }
+  }
+
+  /// Builds a [`Self::MissingSolcVersion`] naming the pragma requirement that no installed solc
+  /// release satisfies while offline, with the full human-readable explanation as `message`.
+  pub fn missing_solc_version(requirement: impl Into<String>, message: impl Into<String>) -> Self {
+    Self::MissingSolcVersion {
+      requirement: requirement.into(),
+      message: message.into(),
+    }
+  }
+
+  /// Builds a [`Self::CompilerNotInstalled`] error.
+  pub fn compiler_not_installed(message: impl Into<String>) -> Self {
+    Self::CompilerNotInstalled(message.into())
+  }
+
+  /// Builds a [`Self::ProjectConfig`] error.
+  pub fn project_config(message: impl Into<String>) -> Self {
+    Self::ProjectConfig(message.into())
+  }
+
+  /// Builds a [`Self::CompilationFailed`] error carrying the structured solc/vyper `diagnostics`
+  /// behind `message`, so callers can inspect them instead of re-parsing the joined text.
+  pub fn compilation_failed(message: impl Into<String>, diagnostics: Vec<CompilerError>) -> Self {
+    Self::CompilationFailed {
+      message: message.into(),
+      diagnostics,
+    }
+  }
+
+  /// Builds a [`Self::Io`] error.
+  pub fn io(message: impl Into<String>) -> Self {
+    Self::Io(message.into())
+  }
+
+  /// Builds a [`Self::Ast`] error.
+  pub fn ast(message: impl Into<String>) -> Self {
+    Self::Ast(message.into())
   }
 
   pub fn message(&self) -> &str {
-    &self.message
+    match self {
+      Self::Message(message) => message,
+      Self::Diagnostic { message, .. } => message,
+      Self::MissingSolcVersion { message, .. } => message,
+      Self::CompilerNotInstalled(message) => message,
+      Self::ProjectConfig(message) => message,
+      Self::CompilationFailed { message, .. } => message,
+      Self::Io(message) => message,
+      Self::Ast(message) => message,
+    }
+  }
+
+  /// Stable, machine-readable category name for this error -- distinct from [`Self::message`]'s
+  /// human-readable text -- so callers (including JS, once surfaced through the napi boundary)
+  /// can branch on failure type without parsing `message()`.
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Self::Message(_) => "message",
+      Self::Diagnostic { .. } => "diagnostic",
+      Self::MissingSolcVersion { .. } => "missingSolcVersion",
+      Self::CompilerNotInstalled(_) => "compilerNotInstalled",
+      Self::ProjectConfig(_) => "projectConfig",
+      Self::CompilationFailed { .. } => "compilationFailed",
+      Self::Io(_) => "io",
+      Self::Ast(_) => "ast",
+    }
+  }
+
+  /// The byte span this error highlights, if it carries one.
+  pub fn span(&self) -> Option<Range<usize>> {
+    match self {
+      Self::Diagnostic { span, .. } => Some(span.clone()),
+      _ => None,
+    }
+  }
+
+  /// The caret-annotated source excerpt, if this error carries one.
+  pub fn rendered(&self) -> Option<&str> {
+    match self {
+      Self::Diagnostic { rendered, .. } => Some(rendered),
+      _ => None,
+    }
+  }
+
+  /// The unmet pragma requirement, if this is a [`Self::MissingSolcVersion`].
+  pub fn missing_solc_requirement(&self) -> Option<&str> {
+    match self {
+      Self::MissingSolcVersion { requirement, .. } => Some(requirement),
+      _ => None,
+    }
+  }
+
+  /// The structured solc/vyper diagnostics behind this error, if it's a
+  /// [`Self::CompilationFailed`].
+  pub fn diagnostics(&self) -> Option<&[CompilerError]> {
+    match self {
+      Self::CompilationFailed { diagnostics, .. } => Some(diagnostics),
+      _ => None,
+    }
   }
 }
 
 impl Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    f.write_str(&self.message)
+    match self {
+      Self::Message(message) => f.write_str(message),
+      Self::Diagnostic { rendered, .. } => f.write_str(rendered),
+      Self::MissingSolcVersion { message, .. } => f.write_str(message),
+      Self::CompilerNotInstalled(message) => f.write_str(message),
+      Self::ProjectConfig(message) => f.write_str(message),
+      Self::CompilationFailed { message, .. } => f.write_str(message),
+      Self::Io(message) => f.write_str(message),
+      Self::Ast(message) => f.write_str(message),
+    }
   }
 }
 
@@ -41,7 +188,7 @@ impl std::error::Error for Error {}
 
 impl From<Error> for NapiError {
   fn from(err: Error) -> Self {
-    NapiError::new(Status::GenericFailure, err.message)
+    NapiError::new(Status::GenericFailure, err.to_string())
   }
 }
 
@@ -131,4 +278,84 @@ mod tests {
     assert_eq!(err.status, Status::GenericFailure);
     assert_eq!(err.reason, "during call: boom");
   }
+
+  #[test]
+  fn diagnostic_carries_span_and_rendered_excerpt() {
+    let err = Error::diagnostic("bad token", 4..8, "error: bad token\n  |\n1 | abcd>>>>\n  |     ^^^^");
+    assert_eq!(err.message(), "bad token");
+    assert_eq!(err.span(), Some(4..8));
+    assert!(err.rendered().unwrap().contains('^'));
+    assert_eq!(err.to_string(), err.rendered().unwrap());
+  }
+
+  #[test]
+  fn missing_solc_version_carries_requirement_and_message() {
+    let err = Error::missing_solc_version(
+      "^0.8.20",
+      "No installed solc version satisfies pragma requirement `^0.8.20`",
+    );
+    assert_eq!(err.missing_solc_requirement(), Some("^0.8.20"));
+    assert_eq!(
+      err.message(),
+      "No installed solc version satisfies pragma requirement `^0.8.20`"
+    );
+    assert_eq!(err.to_string(), err.message());
+    assert!(err.span().is_none());
+    assert!(err.rendered().is_none());
+  }
+
+  #[test]
+  fn missing_solc_requirement_is_none_for_other_variants() {
+    assert_eq!(Error::new("oops").missing_solc_requirement(), None);
+    assert_eq!(
+      Error::diagnostic("bad", 0..1, "bad").missing_solc_requirement(),
+      None
+    );
+  }
+
+  #[test]
+  fn kind_distinguishes_every_variant() {
+    assert_eq!(Error::new("oops").kind(), "message");
+    assert_eq!(Error::diagnostic("bad", 0..1, "bad").kind(), "diagnostic");
+    assert_eq!(
+      Error::missing_solc_version("^0.8.20", "oops").kind(),
+      "missingSolcVersion"
+    );
+    assert_eq!(
+      Error::compiler_not_installed("not installed").kind(),
+      "compilerNotInstalled"
+    );
+    assert_eq!(Error::project_config("bad config").kind(), "projectConfig");
+    assert_eq!(
+      Error::compilation_failed("failed", Vec::new()).kind(),
+      "compilationFailed"
+    );
+    assert_eq!(Error::io("disk full").kind(), "io");
+    assert_eq!(Error::ast("bad ast").kind(), "ast");
+  }
+
+  #[test]
+  fn compilation_failed_carries_its_diagnostics() {
+    let diagnostics = vec![CompilerError {
+      message: "Undeclared identifier".to_string(),
+      formatted_message: None,
+      component: "general".to_string(),
+      severity: crate::compiler::output::SeverityLevel::Error,
+      error_type: "DeclarationError".to_string(),
+      error_code: None,
+      source_location: None,
+      secondary_source_locations: None,
+      vyper_source_location: None,
+      solc_version: None,
+    }];
+    let err = Error::compilation_failed("Solc reported 1 error", diagnostics.clone());
+    assert_eq!(err.message(), "Solc reported 1 error");
+    assert_eq!(err.diagnostics().map(<[_]>::len), Some(1));
+    assert_eq!(err.diagnostics().unwrap()[0].message, diagnostics[0].message);
+  }
+
+  #[test]
+  fn diagnostics_is_none_for_other_variants() {
+    assert_eq!(Error::new("oops").diagnostics(), None);
+  }
 }
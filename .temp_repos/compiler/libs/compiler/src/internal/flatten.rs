@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use foundry_compilers::artifacts::remappings::Remapping;
+use semver::VersionReq;
+
+use super::errors::Result;
+use super::graph;
+use super::pragma;
+
+/// Concatenates `entry` and every source it transitively imports into one self-contained Solidity
+/// string, in the dependency order [`graph::topological_import_order`] produces (so a file's
+/// imports always appear above it, and -- since that traversal visits each path once -- a file
+/// reachable via more than one import path is still only emitted a single time). Mirrors Foundry's
+/// `forge flatten`: every `import` statement is stripped as each body is inlined, only the first
+/// `// SPDX-License-Identifier` line survives, and every `pragma solidity` line is replaced by one
+/// combined requirement (the intersection of every file's own pragma) at the top of the output.
+/// `remappings` is consulted the same way [`graph::topological_import_order`] consults it; a bare
+/// import that neither a remapping nor a relative path resolves is left exactly as that traversal
+/// leaves it -- out of scope, since `include_paths`/`library_paths` name filesystem search
+/// directories this in-memory pipeline has no access to.
+pub(crate) fn flatten_sources(
+  sources: &BTreeMap<String, String>,
+  entry: &str,
+  remappings: &[Remapping],
+) -> Result<String> {
+  let order = graph::topological_import_order(sources, entry, remappings)?;
+
+  let mut spdx_identifier: Option<String> = None;
+  let mut requirements = Vec::new();
+  let mut bodies = Vec::with_capacity(order.len());
+
+  for path in &order {
+    let content = &sources[path];
+    requirements.extend(pragma::extract_requirements(content)?);
+
+    let stripped = strip_import_statements(content);
+    let mut body = String::new();
+    for line in stripped.lines() {
+      let trimmed = line.trim_start();
+      if trimmed.starts_with("pragma solidity") {
+        continue;
+      }
+      if let Some(identifier) = trimmed.strip_prefix("// SPDX-License-Identifier:") {
+        spdx_identifier.get_or_insert_with(|| identifier.trim().to_string());
+        continue;
+      }
+      body.push_str(line);
+      body.push('\n');
+    }
+    bodies.push(format!("// {path}\n{}", body.trim_end_matches('\n')));
+  }
+
+  let combined_requirement = pragma::merge_requirements(&requirements)?;
+
+  let mut output = String::new();
+  if let Some(identifier) = spdx_identifier {
+    output.push_str(&format!("// SPDX-License-Identifier: {identifier}\n"));
+  }
+  if combined_requirement != VersionReq::STAR {
+    output.push_str(&format!("pragma solidity {combined_requirement};\n"));
+  }
+  output.push('\n');
+  output.push_str(&bodies.join("\n\n"));
+  output.push('\n');
+
+  Ok(output)
+}
+
+/// Removes every `import ...;` statement from `source`, regardless of whether it's written on one
+/// line or wraps across several (e.g. a braced `import {\n  A,\n  B\n} from "./X.sol";`). Uses the
+/// same word-boundary check [`graph::extract_imports`] uses to tell a real `import` keyword apart
+/// from an identifier merely containing it, so the two stay in agreement about what counts as an
+/// import statement.
+fn strip_import_statements(source: &str) -> String {
+  let bytes = source.as_bytes();
+  let mut result = String::with_capacity(source.len());
+  let mut cursor = 0;
+
+  while let Some(relative) = source[cursor..].find("import") {
+    let start = cursor + relative;
+    let keyword_end = start + "import".len();
+    let preceded_by_boundary =
+      start == 0 || !matches!(bytes[start - 1], b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_');
+    let followed_by_boundary = bytes
+      .get(keyword_end)
+      .is_some_and(|b| b.is_ascii_whitespace() || matches!(b, b'{' | b'*' | b'"' | b'\''));
+
+    if !preceded_by_boundary || !followed_by_boundary {
+      result.push_str(&source[cursor..keyword_end]);
+      cursor = keyword_end;
+      continue;
+    }
+
+    result.push_str(&source[cursor..start]);
+    cursor = match source[keyword_end..].find(';') {
+      Some(offset) => {
+        let mut end = keyword_end + offset + 1;
+        if source[end..].starts_with('\n') {
+          end += 1;
+        }
+        end
+      }
+      None => source.len(),
+    };
+  }
+
+  result.push_str(&source[cursor..]);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn flatten_sources_orders_dependencies_before_dependents() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\nimport \"./B.sol\";\ncontract A {}"
+        .to_string(),
+    );
+    sources.insert(
+      "B.sol".to_string(),
+      "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.10;\ncontract B {}".to_string(),
+    );
+
+    let flattened = flatten_sources(&sources, "A.sol", &[]).unwrap();
+    assert!(flattened.find("contract B").unwrap() < flattened.find("contract A").unwrap());
+    assert_eq!(flattened.matches("SPDX-License-Identifier").count(), 1);
+    assert_eq!(flattened.matches("pragma solidity").count(), 1);
+    assert!(!flattened.contains("import"));
+  }
+
+  #[test]
+  fn flatten_sources_combines_pragmas_into_their_intersection() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "pragma solidity >=0.8.0;\nimport \"./B.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "B.sol".to_string(),
+      "pragma solidity <0.9.0;\ncontract B {}".to_string(),
+    );
+
+    let flattened = flatten_sources(&sources, "A.sol", &[]).unwrap();
+    assert!(flattened.contains(">=0.8.0"));
+    assert!(flattened.contains("<0.9.0"));
+  }
+
+  #[test]
+  fn strip_import_statements_handles_multiline_braced_imports() {
+    let source = "import {\n  A,\n  B\n} from \"./X.sol\";\ncontract C {}";
+    let stripped = strip_import_statements(source);
+    assert_eq!(stripped, "contract C {}");
+  }
+}
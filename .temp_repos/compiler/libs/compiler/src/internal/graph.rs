@@ -0,0 +1,881 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Component, Path, PathBuf};
+#[cfg(test)]
+use std::str::FromStr;
+
+use foundry_compilers::artifacts::remappings::Remapping;
+use semver::{Version, VersionReq};
+
+use super::errors::{Error, Result};
+use super::pragma;
+use super::solc;
+
+/// One source file in a [`VersionGraphReport`]: its combined pragma requirement, and whether it
+/// sits in an import subtree whose per-file requirements have no solc version in common.
+#[derive(Clone, Debug)]
+pub(crate) struct VersionGraphNode {
+  pub source: String,
+  pub requirement: VersionReq,
+  pub incompatible: bool,
+}
+
+/// Per-file version compatibility report produced by [`resolve_version_graph`], one node per
+/// source passed in.
+#[derive(Clone, Debug)]
+pub(crate) struct VersionGraphReport {
+  pub nodes: Vec<VersionGraphNode>,
+}
+
+/// Extracts the literal path of every `import` statement in `source`, in the order they appear.
+/// Handles every form Solidity supports (`import "X";`, `import {A, B} from "X";`,
+/// `import * as Foo from "X";`, `import "X" as Foo;`) by scanning for the `import` keyword and
+/// taking the next quoted string rather than parsing each form separately.
+pub(crate) fn extract_imports(source: &str) -> Vec<String> {
+  let bytes = source.as_bytes();
+  let mut imports = Vec::new();
+  let mut cursor = 0;
+
+  while let Some(relative) = source[cursor..].find("import") {
+    let start = cursor + relative;
+    let keyword_end = start + "import".len();
+    let preceded_by_boundary =
+      start == 0 || !matches!(bytes[start - 1], b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_');
+    let followed_by_boundary = bytes
+      .get(keyword_end)
+      .is_some_and(|b| b.is_ascii_whitespace() || matches!(b, b'{' | b'*' | b'"' | b'\''));
+    cursor = keyword_end;
+    if !preceded_by_boundary || !followed_by_boundary {
+      continue;
+    }
+
+    let Some(quote_offset) = source[keyword_end..].find(['"', '\'']) else {
+      continue;
+    };
+    let quote = bytes[keyword_end + quote_offset];
+    let path_start = keyword_end + quote_offset + 1;
+    let Some(path_len) = source[path_start..].find(quote as char) else {
+      continue;
+    };
+
+    imports.push(source[path_start..path_start + path_len].to_string());
+    cursor = path_start + path_len + 1;
+  }
+
+  imports
+}
+
+/// Lexically resolves `.`/`..` path components without touching the filesystem (sources here may
+/// be virtual/in-memory, so `Path::canonicalize` isn't an option).
+fn normalize_path(path: &Path) -> PathBuf {
+  let mut stack: Vec<Component> = Vec::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir if matches!(stack.last(), Some(Component::Normal(_))) => {
+        stack.pop();
+      }
+      other => stack.push(other),
+    }
+  }
+  stack.into_iter().collect()
+}
+
+/// Rewrites `import_literal` using whichever of `remappings` has the longest matching prefix --
+/// the same tie-break solc itself applies when more than one remapping could apply. Remapping
+/// entries are read through their canonical `[context:]prefix=path` `Display` form rather than
+/// their fields directly, since that's the only shape this crate otherwise depends on (see
+/// `map_remappings` in `internal::config`). Returns `None` when no remapping's prefix matches,
+/// leaving the import exactly as unresolved as a bare/package reference.
+pub(crate) fn resolve_against_remappings(import_literal: &str, remappings: &[Remapping]) -> Option<String> {
+  remappings
+    .iter()
+    .filter_map(|remapping| {
+      let remapping = remapping.to_string();
+      let rule = remapping.split_once(':').map_or(remapping.as_str(), |(_, rule)| rule);
+      let (prefix, target) = rule.split_once('=')?;
+      import_literal
+        .strip_prefix(prefix)
+        .map(|suffix| (prefix.len(), format!("{target}{suffix}")))
+    })
+    .max_by_key(|(prefix_len, _)| *prefix_len)
+    .map(|(_, resolved)| resolved)
+}
+
+/// Resolves `import_literal` (as written in `from_path`) against `known_paths` the same way solc
+/// would: first against `remappings`' longest matching prefix, then as a relative import against
+/// the importing file's directory. Returns `None` for bare imports (npm-style package imports, or
+/// a remapping whose target isn't one of the given sources) that don't match any given source --
+/// those are out-of-scope dependencies, not edges in the graph we can reason about. `include_paths`
+/// and `library_paths` aren't consulted here since they name filesystem search directories rather
+/// than a path rewrite, and this function only ever reasons about the in-memory `known_paths` it's
+/// given, never touching disk.
+fn resolve_import(
+  from_path: &str,
+  import_literal: &str,
+  known_paths: &BTreeSet<String>,
+  remappings: &[Remapping],
+) -> Option<String> {
+  if let Some(remapped) = resolve_against_remappings(import_literal, remappings) {
+    let normalized = normalize_path(Path::new(&remapped)).to_string_lossy().replace('\\', "/");
+    if known_paths.contains(&normalized) {
+      return Some(normalized);
+    }
+  }
+
+  if !import_literal.starts_with('.') {
+    return known_paths
+      .contains(import_literal)
+      .then_some(import_literal.to_string());
+  }
+
+  let base_dir = Path::new(from_path).parent().unwrap_or_else(|| Path::new(""));
+  let normalized = normalize_path(&base_dir.join(import_literal))
+    .to_string_lossy()
+    .replace('\\', "/");
+  known_paths.contains(&normalized).then_some(normalized)
+}
+
+/// Groups `paths` into connected components of the undirected graph described by `adjacency`.
+fn connected_components(
+  paths: &[String],
+  adjacency: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<Vec<String>> {
+  let mut visited = BTreeSet::new();
+  let mut components = Vec::new();
+
+  for path in paths {
+    if visited.contains(path) {
+      continue;
+    }
+    let mut component = Vec::new();
+    let mut queue = VecDeque::from([path.clone()]);
+    visited.insert(path.clone());
+    while let Some(current) = queue.pop_front() {
+      component.push(current.clone());
+      if let Some(neighbors) = adjacency.get(&current) {
+        for neighbor in neighbors {
+          if visited.insert(neighbor.clone()) {
+            queue.push_back(neighbor.clone());
+          }
+        }
+      }
+    }
+    component.sort();
+    components.push(component);
+  }
+
+  components
+}
+
+/// When a subtree's combined requirement is unsatisfiable, narrows down which files are actually
+/// responsible by checking every pair: a file that can't agree with at least one other file in the
+/// subtree is flagged incompatible. This is a pairwise approximation -- a 3-or-more-way conflict
+/// where every pair agrees but no version satisfies all of them at once is vanishingly rare for
+/// real pragma ranges, and not worth the combinatorial cost of checking every subset.
+fn offending_nodes(
+  component: &[String],
+  requirements: &BTreeMap<String, VersionReq>,
+  offline_mode: bool,
+) -> Result<BTreeSet<String>> {
+  let mut incompatible = BTreeSet::new();
+  for (index, left) in component.iter().enumerate() {
+    for right in &component[index + 1..] {
+      let pair =
+        pragma::merge_requirements(&[requirements[left].clone(), requirements[right].clone()])?;
+      if pragma::resolve_version(&pair, offline_mode).is_err() {
+        incompatible.insert(left.clone());
+        incompatible.insert(right.clone());
+      }
+    }
+  }
+  Ok(incompatible)
+}
+
+/// Builds the per-file pragma requirement map and undirected import adjacency for `sources`,
+/// shared by [`resolve_version_graph`] (which only reports whether a subtree is satisfiable) and
+/// [`resolve_compilation_buckets`] (which picks and installs a version for it). `remappings` is
+/// consulted the same way solc would before falling back to relative-import resolution.
+fn build_import_graph(
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Result<(BTreeMap<String, VersionReq>, BTreeMap<String, BTreeSet<String>>)> {
+  let known_paths: BTreeSet<String> = sources.keys().cloned().collect();
+  let mut requirements: BTreeMap<String, VersionReq> = BTreeMap::new();
+  let mut adjacency: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+  for (path, content) in sources {
+    requirements.insert(
+      path.clone(),
+      pragma::merge_requirements(&pragma::extract_requirements(content)?)?,
+    );
+
+    for import_literal in extract_imports(content) {
+      match resolve_import(path, &import_literal, &known_paths, remappings) {
+        Some(target) => {
+          adjacency.entry(path.clone()).or_default().insert(target.clone());
+          adjacency.entry(target).or_default().insert(path.clone());
+        }
+        // A relative import (`./`, `../`) names a specific file next to the importer, so if it
+        // doesn't resolve against the sources we were given, that's a broken reference rather
+        // than an out-of-scope dependency -- surface it instead of silently dropping the edge.
+        None if import_literal.starts_with('.') => {
+          return Err(Error::new(format!(
+            "`{path}` imports `{import_literal}`, which does not resolve to any provided source \
+             file"
+          )));
+        }
+        None => {}
+      }
+    }
+  }
+
+  Ok((requirements, adjacency))
+}
+
+/// One source file in a [`DependencyGraphReport`]: its canonical path, combined pragma
+/// requirement, and the resolved import edges leaving it.
+#[derive(Clone, Debug)]
+pub(crate) struct DependencyGraphNode {
+  pub source: String,
+  pub requirement: VersionReq,
+  pub imports: Vec<String>,
+}
+
+/// Fully resolved import/dependency graph produced by [`resolve_dependency_graph`], one node per
+/// source passed in.
+#[derive(Clone, Debug)]
+pub(crate) struct DependencyGraphReport {
+  pub nodes: Vec<DependencyGraphNode>,
+}
+
+/// Resolves the full import/dependency graph of `sources`: for each file, its combined pragma
+/// requirement and the canonical paths its `import`s resolve to once `remappings` and relative-path
+/// resolution are applied -- the same resolution [`build_import_graph`] performs to reason about
+/// version compatibility, surfaced here as queryable data instead of only feeding a compile. Tools
+/// can use this for impact analysis ("which files does X transitively import, or get imported by")
+/// or to visualize project structure without ever invoking solc.
+pub(crate) fn resolve_dependency_graph(
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Result<DependencyGraphReport> {
+  let known_paths: BTreeSet<String> = sources.keys().cloned().collect();
+  let mut nodes = Vec::with_capacity(sources.len());
+
+  for (path, content) in sources {
+    let requirement = pragma::merge_requirements(&pragma::extract_requirements(content)?)?;
+    let mut imports = Vec::new();
+    for import_literal in extract_imports(content) {
+      match resolve_import(path, &import_literal, &known_paths, remappings) {
+        Some(target) => imports.push(target),
+        // Same as `build_import_graph`: a relative import that doesn't resolve against the given
+        // sources names a specific missing file rather than an out-of-scope dependency.
+        None if import_literal.starts_with('.') => {
+          return Err(Error::new(format!(
+            "`{path}` imports `{import_literal}`, which does not resolve to any provided source \
+             file"
+          )));
+        }
+        None => {}
+      }
+    }
+    nodes.push(DependencyGraphNode {
+      source: path.clone(),
+      requirement,
+      imports,
+    });
+  }
+
+  Ok(DependencyGraphReport { nodes })
+}
+
+/// Builds the import dependency graph of `sources` and groups its paths into connected
+/// components, so that a change to one file can be expanded to every other file reachable from
+/// it via `import` (in either direction) without having to re-derive the pragma/version graph.
+pub(crate) fn import_connected_components(
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Result<Vec<Vec<String>>> {
+  let (_, adjacency) = build_import_graph(sources, remappings)?;
+  let paths: Vec<String> = sources.keys().cloned().collect();
+  Ok(connected_components(&paths, &adjacency))
+}
+
+/// Reads `entry_paths` from disk and follows every resolvable `import` transitively (relative
+/// imports against the importing file's directory, then `remappings`) to discover the full set of
+/// files a compile of `entry_paths` would touch. Unlike [`resolve_dependency_graph`] and
+/// [`import_connected_components`], which only resolve imports against an already-known set of
+/// in-memory sources, this walks the filesystem itself -- used by [`crate::internal::watch`] to
+/// build the set of files a watcher needs to track so editing an imported library file, not just
+/// the entry point, triggers a recompile. Bare/package imports that don't resolve to a file on
+/// disk are silently skipped, the same as an out-of-scope dependency [`resolve_dependency_graph`]
+/// doesn't put an edge on.
+pub(crate) fn discover_transitive_sources(
+  entry_paths: &[PathBuf],
+  remappings: &[Remapping],
+) -> BTreeSet<PathBuf> {
+  let mut discovered: BTreeSet<PathBuf> = BTreeSet::new();
+  let mut queue: VecDeque<PathBuf> = VecDeque::new();
+
+  for path in entry_paths {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+    if discovered.insert(canonical.clone()) {
+      queue.push_back(canonical);
+    }
+  }
+
+  while let Some(current) = queue.pop_front() {
+    let Ok(content) = std::fs::read_to_string(&current) else {
+      continue;
+    };
+    let base_dir = current.parent().unwrap_or_else(|| Path::new(""));
+
+    for import_literal in extract_imports(&content) {
+      let candidate = resolve_against_remappings(&import_literal, remappings)
+        .map(PathBuf::from)
+        .or_else(|| import_literal.starts_with('.').then(|| base_dir.join(&import_literal)));
+
+      let Some(candidate) = candidate else { continue };
+      let Ok(canonical) = candidate.canonicalize() else {
+        continue;
+      };
+      if discovered.insert(canonical.clone()) {
+        queue.push_back(canonical);
+      }
+    }
+  }
+
+  discovered
+}
+
+/// Builds the import dependency graph of `sources` (canonical path -> content) and, for each
+/// connected subtree, checks whether a single solc version can satisfy the union of every file's
+/// `pragma solidity` requirement. Complements [`resolve_compilation_buckets`]: that function picks
+/// a version to compile each subtree with, this one reports *whether* one exists for a given
+/// import subtree, and which files are mutually incompatible when it doesn't.
+pub(crate) fn resolve_version_graph(
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+  offline_mode: bool,
+) -> Result<VersionGraphReport> {
+  let (requirements, adjacency) = build_import_graph(sources, remappings)?;
+  let paths: Vec<String> = sources.keys().cloned().collect();
+  let mut nodes = Vec::with_capacity(paths.len());
+
+  for component in connected_components(&paths, &adjacency) {
+    let component_requirements: Vec<VersionReq> = component
+      .iter()
+      .map(|path| requirements[path].clone())
+      .collect();
+    let combined = pragma::merge_requirements(&component_requirements)?;
+    let satisfiable = pragma::resolve_version(&combined, offline_mode).is_ok();
+
+    let incompatible = if satisfiable || component.len() < 2 {
+      BTreeSet::new()
+    } else {
+      offending_nodes(&component, &requirements, offline_mode)?
+    };
+
+    for path in component {
+      nodes.push(VersionGraphNode {
+        requirement: requirements[&path].clone(),
+        incompatible: incompatible.contains(&path),
+        source: path,
+      });
+    }
+  }
+
+  Ok(VersionGraphReport { nodes })
+}
+
+/// Builds the import dependency graph of `sources` and resolves (installing if necessary, unless
+/// `offline_mode` restricts the search to what's already on disk) a concrete solc [`Version`] per
+/// connected subtree, mapping every input path to the version its subtree should compile with.
+/// Unlike [`resolve_version_graph`], which only reports whether a subtree's requirements agree,
+/// this is the one that actually drives a multi-version compile: a project mixing `0.4.x` and
+/// `0.8.x` contracts gets back a map pointing each file at the right compiler, so the caller can
+/// group by value and invoke solc once per distinct version.
+pub(crate) fn resolve_compilation_buckets(
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+  offline_mode: bool,
+) -> Result<BTreeMap<String, Version>> {
+  let (requirements, adjacency) = build_import_graph(sources, remappings)?;
+  let paths: Vec<String> = sources.keys().cloned().collect();
+  let mut resolved = BTreeMap::new();
+
+  for component in connected_components(&paths, &adjacency) {
+    let component_requirements: Vec<VersionReq> = component
+      .iter()
+      .map(|path| requirements[path].clone())
+      .collect();
+    let combined = pragma::merge_requirements(&component_requirements)?;
+
+    let version = if offline_mode {
+      solc::ensure_compatible(&combined)
+    } else {
+      solc::ensure_installed_req(&combined)
+    }
+    .map(|solc| solc.version)
+    .map_err(|err| {
+      if let Some(requirement) = err.missing_solc_requirement() {
+        return Error::missing_solc_version(requirement.to_string(), err.to_string());
+      }
+      let constraints = component
+        .iter()
+        .map(|path| format!("{path} requires `{}`", requirements[path]))
+        .collect::<Vec<_>>()
+        .join(", ");
+      Error::new(format!(
+        "No solc version satisfies the combined pragma requirement `{combined}` of the import \
+         subtree {component:?} ({constraints}): {err}"
+      ))
+    })?;
+
+    for path in component {
+      resolved.insert(path, version.clone());
+    }
+  }
+
+  Ok(resolved)
+}
+
+/// Resolves each source's own `pragma solidity` requirement (ignoring import relationships,
+/// unlike [`resolve_compilation_buckets`]'s whole-subtree union) to a compatible version, falling
+/// back to `default_version` verbatim for a source that carries no pragma at all rather than
+/// resolving an unconstrained requirement to whatever happens to be the newest installed release.
+/// Used by the project-based pipeline's sparse virtual source maps, where sources are typically
+/// unrelated snippets rather than a cohesive import graph, so grouping per-file is the more
+/// predictable default.
+pub(crate) fn resolve_per_source_version_buckets(
+  sources: &BTreeMap<String, String>,
+  default_version: &Version,
+  offline_mode: bool,
+) -> Result<BTreeMap<String, Version>> {
+  let mut resolved = BTreeMap::new();
+
+  for (path, content) in sources {
+    let requirements = pragma::extract_requirements(content)?;
+    let version = if requirements.is_empty() {
+      default_version.clone()
+    } else {
+      let combined = pragma::merge_requirements(&requirements)?;
+      pragma::resolve_version(&combined, offline_mode).map_err(|err| {
+        Error::new(format!(
+          "No solc version satisfies `{path}`'s pragma requirement `{combined}`: {err}"
+        ))
+      })?
+    };
+    resolved.insert(path.clone(), version);
+  }
+
+  Ok(resolved)
+}
+
+/// Depth-first post-order traversal of `sources`' directed import graph starting from `entry`:
+/// every file `entry` transitively imports appears before it, and before any file that in turn
+/// imports it -- the order [`flatten`](super::flatten) needs to concatenate file bodies into one
+/// self-contained unit. Relative (`./`, `../`) imports and anything `remappings` rewrites to a
+/// known source are followed, the same scope [`build_import_graph`] resolves; a bare import that
+/// isn't covered by either is left for the caller to decide what to do with. Each path is visited
+/// at most once, so a cyclic import still produces a complete ordering rather than looping forever.
+pub(crate) fn topological_import_order(
+  sources: &BTreeMap<String, String>,
+  entry: &str,
+  remappings: &[Remapping],
+) -> Result<Vec<String>> {
+  if !sources.contains_key(entry) {
+    return Err(Error::new(format!("`{entry}` is not a known source file")));
+  }
+
+  let known_paths: BTreeSet<String> = sources.keys().cloned().collect();
+  let mut visited = BTreeSet::new();
+  let mut order = Vec::new();
+  visit_imports_post_order(entry, sources, &known_paths, remappings, &mut visited, &mut order)?;
+  Ok(order)
+}
+
+fn visit_imports_post_order(
+  path: &str,
+  sources: &BTreeMap<String, String>,
+  known_paths: &BTreeSet<String>,
+  remappings: &[Remapping],
+  visited: &mut BTreeSet<String>,
+  order: &mut Vec<String>,
+) -> Result<()> {
+  if !visited.insert(path.to_string()) {
+    return Ok(());
+  }
+
+  for import_literal in extract_imports(&sources[path]) {
+    match resolve_import(path, &import_literal, known_paths, remappings) {
+      Some(target) => {
+        visit_imports_post_order(&target, sources, known_paths, remappings, visited, order)?
+      }
+      None if import_literal.starts_with('.') => {
+        return Err(Error::new(format!(
+          "`{path}` imports `{import_literal}`, which does not resolve to any provided source \
+           file"
+        )));
+      }
+      None => {}
+    }
+  }
+
+  order.push(path.to_string());
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extract_imports_handles_every_form() {
+    let source = r#"
+      import "./Foo.sol";
+      import {A, B} from "./Bar.sol";
+      import * as C from "./Baz.sol";
+      import "./Qux.sol" as Qux;
+    "#;
+    assert_eq!(
+      extract_imports(source),
+      vec!["./Foo.sol", "./Bar.sol", "./Baz.sol", "./Qux.sol"]
+    );
+  }
+
+  #[test]
+  fn extract_imports_ignores_identifiers_containing_import() {
+    let source = "uint256 importantValue = 1;";
+    assert!(extract_imports(source).is_empty());
+  }
+
+  #[test]
+  fn resolve_version_graph_flags_incompatible_subtree() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"./B.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/B.sol".to_string(),
+      "pragma solidity ^0.4.24;\ncontract B {}".to_string(),
+    );
+
+    let report = resolve_version_graph(&sources, &[], true).unwrap();
+    assert!(report.nodes.iter().all(|node| node.incompatible));
+  }
+
+  #[test]
+  fn resolve_version_graph_leaves_unrelated_files_untouched() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/B.sol".to_string(),
+      "pragma solidity ^0.4.24;\ncontract B {}".to_string(),
+    );
+
+    let report = resolve_version_graph(&sources, &[], true).unwrap();
+    assert!(report.nodes.iter().all(|node| !node.incompatible));
+  }
+
+  #[test]
+  fn resolve_compilation_buckets_errors_for_an_unsatisfiable_subtree() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.4.14;\nimport \"./B.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/B.sol".to_string(),
+      "pragma solidity >=0.8.0;\ncontract B {}".to_string(),
+    );
+
+    let err = resolve_compilation_buckets(&sources, &[], true).unwrap_err();
+    assert!(
+      err.to_string().contains("No solc version satisfies"),
+      "unexpected message: {}",
+      err
+    );
+    assert!(
+      err.to_string().contains("requires `^0.4.14`") && err.to_string().contains("requires `>=0.8.0`"),
+      "expected both conflicting constraints to be named: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn resolve_compilation_buckets_errors_for_an_unresolvable_relative_import() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"./Missing.sol\";\ncontract A {}".to_string(),
+    );
+
+    let err = resolve_compilation_buckets(&sources, &[], true).unwrap_err();
+    assert!(
+      err.to_string().contains("/project/A.sol") && err.to_string().contains("./Missing.sol"),
+      "expected error to name the importing file and the unresolved import: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn resolve_compilation_buckets_reports_missing_solc_requirement_when_offline() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity >=99.0.0;\ncontract A {}".to_string(),
+    );
+
+    let err = resolve_compilation_buckets(&sources, &[], true).unwrap_err();
+    assert_eq!(err.missing_solc_requirement(), Some(">=99.0.0"));
+  }
+
+  #[test]
+  fn resolve_import_leaves_bare_package_imports_unresolved_without_erroring() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"@openzeppelin/contracts/token/ERC20.sol\";\ncontract A {}"
+        .to_string(),
+    );
+
+    let (_, adjacency) = build_import_graph(&sources, &[]).unwrap();
+    assert!(adjacency.get("/project/A.sol").is_none());
+  }
+
+  #[test]
+  fn resolve_dependency_graph_reports_resolved_imports_and_requirement_per_file() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"./B.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/B.sol".to_string(),
+      "pragma solidity ^0.8.24;\ncontract B {}".to_string(),
+    );
+
+    let report = resolve_dependency_graph(&sources, &[]).unwrap();
+    let a = report.nodes.iter().find(|node| node.source == "/project/A.sol").unwrap();
+    assert_eq!(a.imports, vec!["/project/B.sol".to_string()]);
+    assert!(a.requirement.matches(&Version::new(0, 8, 20)));
+
+    let b = report.nodes.iter().find(|node| node.source == "/project/B.sol").unwrap();
+    assert!(b.imports.is_empty());
+  }
+
+  #[test]
+  fn resolve_dependency_graph_leaves_bare_package_imports_out_of_the_edge_list() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"@openzeppelin/contracts/token/ERC20.sol\";\ncontract A {}"
+        .to_string(),
+    );
+
+    let report = resolve_dependency_graph(&sources, &[]).unwrap();
+    assert!(report.nodes[0].imports.is_empty());
+  }
+
+  #[test]
+  fn resolve_dependency_graph_errors_for_an_unresolvable_relative_import() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"./Missing.sol\";\ncontract A {}".to_string(),
+    );
+
+    let err = resolve_dependency_graph(&sources, &[]).unwrap_err();
+    assert!(
+      err.to_string().contains("/project/A.sol") && err.to_string().contains("./Missing.sol"),
+      "expected error to name the importing file and the unresolved import: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn import_connected_components_groups_files_that_import_each_other() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"./B.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/B.sol".to_string(),
+      "pragma solidity ^0.8.20;\ncontract B {}".to_string(),
+    );
+    sources.insert(
+      "/project/C.sol".to_string(),
+      "pragma solidity ^0.8.20;\ncontract C {}".to_string(),
+    );
+
+    let components = import_connected_components(&sources, &[]).unwrap();
+    assert_eq!(components.len(), 2);
+    assert!(components
+      .iter()
+      .any(|component| component == &["/project/A.sol".to_string(), "/project/B.sol".to_string()]));
+    assert!(components
+      .iter()
+      .any(|component| component == &["/project/C.sol".to_string()]));
+  }
+
+  #[test]
+  fn resolve_per_source_version_buckets_groups_by_each_sources_own_pragma() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "pragma solidity =0.8.19;\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "B.sol".to_string(),
+      "pragma solidity =0.8.24;\ncontract B {}".to_string(),
+    );
+    sources.insert("C.sol".to_string(), "contract C {}".to_string());
+
+    let default_version = Version::new(0, 8, 30);
+    let buckets = resolve_per_source_version_buckets(&sources, &default_version, true).unwrap();
+    assert_eq!(buckets["A.sol"], Version::new(0, 8, 19));
+    assert_eq!(buckets["B.sol"], Version::new(0, 8, 24));
+    assert_eq!(buckets["C.sol"], default_version);
+  }
+
+  #[test]
+  fn resolve_per_source_version_buckets_errors_for_an_unsatisfiable_pragma() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "pragma solidity >=99.0.0;\ncontract A {}".to_string(),
+    );
+
+    let default_version = Version::new(0, 8, 30);
+    let err = resolve_per_source_version_buckets(&sources, &default_version, true).unwrap_err();
+    assert!(
+      err.to_string().contains("A.sol"),
+      "expected error to name the offending source: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn topological_import_order_puts_dependencies_before_dependents() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "A.sol".to_string(),
+      "import \"./B.sol\";\nimport \"./C.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert("B.sol".to_string(), "import \"./C.sol\";\ncontract B {}".to_string());
+    sources.insert("C.sol".to_string(), "contract C {}".to_string());
+
+    let order = topological_import_order(&sources, "A.sol", &[]).unwrap();
+    assert_eq!(order, vec!["C.sol".to_string(), "B.sol".to_string(), "A.sol".to_string()]);
+  }
+
+  #[test]
+  fn topological_import_order_visits_each_file_once_despite_a_cycle() {
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "import \"./B.sol\";\ncontract A {}".to_string());
+    sources.insert("B.sol".to_string(), "import \"./A.sol\";\ncontract B {}".to_string());
+
+    let order = topological_import_order(&sources, "A.sol", &[]).unwrap();
+    assert_eq!(order.len(), 2);
+    assert!(order.contains(&"A.sol".to_string()));
+    assert!(order.contains(&"B.sol".to_string()));
+  }
+
+  #[test]
+  fn topological_import_order_errors_for_an_unknown_entry() {
+    let sources = BTreeMap::new();
+    let err = topological_import_order(&sources, "Missing.sol", &[]).unwrap_err();
+    assert!(err.to_string().contains("Missing.sol"));
+  }
+
+  #[test]
+  fn resolve_import_follows_a_remapped_bare_import() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/src/A.sol".to_string(),
+      "pragma solidity ^0.8.20;\nimport \"@oz/token/ERC20.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/lib/openzeppelin/token/ERC20.sol".to_string(),
+      "pragma solidity ^0.4.24;\ncontract ERC20 {}".to_string(),
+    );
+    let remappings = vec![Remapping::from_str("@oz/=/project/lib/openzeppelin/").unwrap()];
+
+    let (_, adjacency) = build_import_graph(&sources, &remappings).unwrap();
+    assert!(adjacency["/project/src/A.sol"]
+      .contains("/project/lib/openzeppelin/token/ERC20.sol"));
+  }
+
+  #[test]
+  fn resolve_import_prefers_the_longest_matching_remapping() {
+    let remappings = vec![
+      Remapping::from_str("@oz/=lib/openzeppelin/").unwrap(),
+      Remapping::from_str("@oz/token/=lib/openzeppelin-token/").unwrap(),
+    ];
+    let known_paths = BTreeSet::from(["lib/openzeppelin-token/ERC20.sol".to_string()]);
+
+    let resolved = resolve_import("src/A.sol", "@oz/token/ERC20.sol", &known_paths, &remappings);
+    assert_eq!(resolved, Some("lib/openzeppelin-token/ERC20.sol".to_string()));
+  }
+
+  #[test]
+  fn resolve_compilation_buckets_groups_a_remapped_import_into_one_subtree() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "/project/src/A.sol".to_string(),
+      "pragma solidity ^0.4.14;\nimport \"@oz/ERC20.sol\";\ncontract A {}".to_string(),
+    );
+    sources.insert(
+      "/project/lib/ERC20.sol".to_string(),
+      "pragma solidity >=0.8.0;\ncontract ERC20 {}".to_string(),
+    );
+    let remappings = vec![Remapping::from_str("@oz/=/project/lib/").unwrap()];
+
+    let err = resolve_compilation_buckets(&sources, &remappings, true).unwrap_err();
+    assert!(
+      err.to_string().contains("requires `^0.4.14`") && err.to_string().contains("requires `>=0.8.0`"),
+      "expected the remapped import to pull both files into the same subtree: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn resolve_import_leaves_an_unmatched_remapping_target_unresolved() {
+    let sources = BTreeMap::new();
+    let remappings = vec![Remapping::from_str("@oz/=lib/openzeppelin/").unwrap()];
+    let known_paths: BTreeSet<String> = sources.keys().cloned().collect();
+
+    let resolved = resolve_import("src/A.sol", "@oz/ERC20.sol", &known_paths, &remappings);
+    assert_eq!(resolved, None);
+  }
+
+  #[test]
+  fn discover_transitive_sources_follows_relative_imports_from_disk() {
+    let temp = tempfile::tempdir().unwrap();
+    let entry = temp.path().join("A.sol");
+    let imported = temp.path().join("B.sol");
+    std::fs::write(&entry, "pragma solidity ^0.8.20;\nimport \"./B.sol\";\ncontract A {}").unwrap();
+    std::fs::write(&imported, "pragma solidity ^0.8.20;\ncontract B {}").unwrap();
+
+    let discovered = discover_transitive_sources(&[entry.clone()], &[]);
+    assert_eq!(discovered.len(), 2);
+    assert!(discovered.contains(&entry.canonicalize().unwrap()));
+    assert!(discovered.contains(&imported.canonicalize().unwrap()));
+  }
+
+  #[test]
+  fn discover_transitive_sources_skips_unresolvable_bare_imports() {
+    let temp = tempfile::tempdir().unwrap();
+    let entry = temp.path().join("A.sol");
+    std::fs::write(&entry, "import \"@openzeppelin/contracts/Foo.sol\";\ncontract A {}").unwrap();
+
+    let discovered = discover_transitive_sources(&[entry.clone()], &[]);
+    assert_eq!(discovered.len(), 1);
+    assert!(discovered.contains(&entry.canonicalize().unwrap()));
+  }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::sources::Source as FoundrySource;
+use log::info;
+use serde_json::{json, Value};
+
+use crate::compiler::output::SourceArtifacts;
+use crate::contract::{Contract, ContractState};
+use crate::internal::errors::{Error, Result};
+
+const LOG_TARGET: &str = "tevm::hardhat_artifacts";
+
+/// Envelope version Hardhat itself emits for every compiled contract. See
+/// <https://hardhat.org/hardhat-runner/docs/advanced/artifacts> for the schema this mirrors.
+const HARDHAT_ARTIFACT_FORMAT: &str = "hh-sol-artifact-1";
+
+/// Envelope version Hardhat stamps on each artifact's `.dbg.json` debug sidecar. See
+/// <https://hardhat.org/hardhat-runner/docs/advanced/artifacts#debug-files> for the schema this
+/// mirrors.
+const HARDHAT_DEBUG_FORMAT: &str = "hh-sol-dbg-1";
+
+/// Builds the Hardhat-shaped artifact JSON for a single contract.
+pub(crate) fn hardhat_artifact_json(name: &str, state: &ContractState) -> Value {
+  json!({
+    "_format": HARDHAT_ARTIFACT_FORMAT,
+    "contractName": name,
+    "sourceName": state.source_path.clone().unwrap_or_default(),
+    "abi": state.abi.clone().unwrap_or_else(|| Value::Array(Vec::new())),
+    "bytecode": state
+      .creation_bytecode
+      .as_ref()
+      .map(|bytecode| bytecode.to_hex())
+      .unwrap_or_else(|| "0x".to_string()),
+    "deployedBytecode": state
+      .deployed_bytecode
+      .as_ref()
+      .map(|bytecode| bytecode.to_hex())
+      .unwrap_or_else(|| "0x".to_string()),
+    "linkReferences": link_references_to_json(state.creation_link_references.as_ref()),
+    "deployedLinkReferences": link_references_to_json(state.deployed_link_references.as_ref()),
+  })
+}
+
+/// Builds the `.dbg.json` debug sidecar Hardhat writes alongside each artifact, pointing at the
+/// build-info document holding the full standard-JSON input/output for that contract's compile.
+/// The build-info id mirrors Hardhat's own convention of hashing the compiler metadata, so
+/// repeated compiles of unchanged contracts resolve to the same file.
+fn hardhat_debug_json(build_info_path: &Path) -> Value {
+  json!({
+    "_format": HARDHAT_DEBUG_FORMAT,
+    "buildInfo": build_info_path.to_string_lossy(),
+  })
+}
+
+/// Path, relative to `from_dir`, of the build-info document for `state`. Falls back to a hash of
+/// the bare contract name when no compiler metadata was emitted, so the sidecar still resolves to
+/// a deterministic (if less precise) filename.
+fn build_info_relative_path(from_dir: &Path, build_infos_dir: &Path, name: &str, state: &ContractState) -> PathBuf {
+  let hash_input = state
+    .metadata
+    .as_ref()
+    .and_then(|metadata| serde_json::to_string(metadata).ok())
+    .unwrap_or_else(|| name.to_string());
+  let build_info_id = FoundrySource::content_hash_of(&hash_input);
+  relative_path(from_dir, &build_infos_dir.join(format!("{build_info_id}.json")))
+}
+
+/// Naive relative-path computation between two directories/files that share a common ancestor:
+/// strips the longest shared prefix off both, then prepends one `..` per remaining component of
+/// `from_dir`. Good enough for artifact/build-info directories, which always live under the same
+/// project root.
+fn relative_path(from_dir: &Path, to_file: &Path) -> PathBuf {
+  let from_components: Vec<_> = from_dir.components().collect();
+  let to_components: Vec<_> = to_file.components().collect();
+  let shared = from_components
+    .iter()
+    .zip(to_components.iter())
+    .take_while(|(a, b)| a == b)
+    .count();
+
+  let mut result = PathBuf::new();
+  for _ in shared..from_components.len() {
+    result.push("..");
+  }
+  for component in &to_components[shared..] {
+    result.push(component);
+  }
+  result
+}
+
+fn link_references_to_json(
+  link_references: Option<&BTreeMap<String, BTreeMap<String, Vec<crate::contract::ImmutableSlot>>>>,
+) -> Value {
+  serde_json::to_value(link_references.cloned().unwrap_or_default()).unwrap_or_else(|_| json!({}))
+}
+
+/// Writes one Hardhat-shaped `<ContractName>.json` file, plus its `<ContractName>.dbg.json` debug
+/// sidecar, per compiled contract under `artifacts_dir/<source-file-name>/`, mirroring `npx
+/// hardhat compile`'s on-disk layout so existing Hardhat-based deploy/test pipelines can consume
+/// this crate's output without a post-processing step.
+pub(crate) fn write_artifacts(
+  artifacts_dir: &Path,
+  build_infos_dir: &Path,
+  artifacts: &BTreeMap<String, SourceArtifacts>,
+) -> Result<()> {
+  for source in artifacts.values() {
+    let source_name = source
+      .source_path
+      .as_deref()
+      .map(|path| {
+        Path::new(path)
+          .file_name()
+          .map(|name| name.to_string_lossy().into_owned())
+          .unwrap_or_else(|| path.to_string())
+      })
+      .unwrap_or_else(|| "Unknown.sol".to_string());
+    let source_dir = artifacts_dir.join(&source_name);
+
+    for (name, contract) in &source.contracts {
+      fs::create_dir_all(&source_dir).map_err(|err| {
+        Error::new(format!(
+          "Failed to prepare Hardhat artifacts directory {}: {err}",
+          source_dir.display()
+        ))
+      })?;
+
+      let state = contract.state();
+      let path = source_dir.join(format!("{name}.json"));
+      let payload = hardhat_artifact_json(name, state);
+      let contents = serde_json::to_string_pretty(&payload)
+        .map_err(|err| Error::new(format!("Failed to serialise Hardhat artifact {name}: {err}")))?;
+      fs::write(&path, contents)
+        .map_err(|err| Error::new(format!("Failed to write Hardhat artifact {}: {err}", path.display())))?;
+
+      let build_info_path = build_info_relative_path(&source_dir, build_infos_dir, name, state);
+      let debug_path = source_dir.join(format!("{name}.dbg.json"));
+      let debug_payload = hardhat_debug_json(&build_info_path);
+      let debug_contents = serde_json::to_string_pretty(&debug_payload)
+        .map_err(|err| Error::new(format!("Failed to serialise Hardhat debug sidecar {name}: {err}")))?;
+      fs::write(&debug_path, debug_contents).map_err(|err| {
+        Error::new(format!(
+          "Failed to write Hardhat debug sidecar {}: {err}",
+          debug_path.display()
+        ))
+      })?;
+    }
+  }
+
+  info!(
+    target: LOG_TARGET,
+    "wrote {} Hardhat-format artifact bundle(s) to {}",
+    artifacts.len(),
+    artifacts_dir.display()
+  );
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hardhat_artifact_json_uses_hh_sol_artifact_envelope() {
+    let mut state = ContractState::new("Sample");
+    state.abi = Some(json!([{"type": "function", "name": "greet"}]));
+    state.creation_bytecode = crate::contract::ContractBytecode::from_hex_string("0x6000").ok();
+    state.deployed_bytecode = crate::contract::ContractBytecode::from_hex_string("0x6001").ok();
+
+    let payload = hardhat_artifact_json("Sample", &state);
+    assert_eq!(payload["_format"], HARDHAT_ARTIFACT_FORMAT);
+    assert_eq!(payload["contractName"], "Sample");
+    assert_eq!(payload["bytecode"], "0x6000");
+    assert_eq!(payload["deployedBytecode"], "0x6001");
+    assert_eq!(payload["linkReferences"], json!({}));
+    assert_eq!(payload["deployedLinkReferences"], json!({}));
+  }
+
+  #[test]
+  fn hardhat_artifact_json_includes_populated_link_references() {
+    let mut state = ContractState::new("Consumer");
+    state.creation_link_references = Some(BTreeMap::from([(
+      "src/Lib.sol".to_string(),
+      BTreeMap::from([(
+        "Lib".to_string(),
+        vec![crate::contract::ImmutableSlot {
+          start: 2,
+          length: 20,
+        }],
+      )]),
+    )]));
+
+    let payload = hardhat_artifact_json("Consumer", &state);
+    assert_eq!(payload["linkReferences"]["src/Lib.sol"]["Lib"][0]["start"], 2);
+    assert_eq!(
+      payload["linkReferences"]["src/Lib.sol"]["Lib"][0]["length"],
+      20
+    );
+    assert_eq!(payload["deployedLinkReferences"], json!({}));
+  }
+
+  #[test]
+  fn hardhat_artifact_json_defaults_missing_bytecode_to_0x() {
+    let state = ContractState::new("Interface");
+    let payload = hardhat_artifact_json("Interface", &state);
+    assert_eq!(payload["bytecode"], "0x");
+    assert_eq!(payload["deployedBytecode"], "0x");
+  }
+
+  #[test]
+  fn hardhat_debug_json_uses_dbg_envelope() {
+    let payload = hardhat_debug_json(Path::new("../../build-info/abc123.json"));
+    assert_eq!(payload["_format"], HARDHAT_DEBUG_FORMAT);
+    assert_eq!(payload["buildInfo"], "../../build-info/abc123.json");
+  }
+
+  #[test]
+  fn relative_path_walks_up_to_the_shared_ancestor() {
+    let from = Path::new("/root/out/contracts/Sample.sol");
+    let to = Path::new("/root/out/build-info/abc123.json");
+    assert_eq!(relative_path(from, to), Path::new("../../build-info/abc123.json"));
+  }
+
+  #[test]
+  fn build_info_relative_path_is_deterministic_for_identical_metadata() {
+    let mut state = ContractState::new("Sample");
+    state.metadata = Some(json!({"compiler": {"version": "0.8.19"}}));
+
+    let from_dir = Path::new("/root/out/contracts/Sample.sol");
+    let build_infos_dir = Path::new("/root/out/build-info");
+
+    let first = build_info_relative_path(from_dir, build_infos_dir, "Sample", &state);
+    let second = build_info_relative_path(from_dir, build_infos_dir, "Sample", &state);
+    assert_eq!(first, second);
+    assert!(first.starts_with(".."));
+  }
+}
@@ -0,0 +1,497 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use foundry_compilers::artifacts::{remappings::Remapping, sources::Source as FoundrySource};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::internal::config::CompilerConfig;
+use crate::internal::errors::{Error, Result};
+use crate::internal::graph;
+use crate::internal::keccak::keccak256;
+
+const LOG_TARGET: &str = "tevm::compiler.incremental_cache";
+
+/// Hashes every knob that changes what solc produces for a given source: the resolved version,
+/// the serialised settings (optimizer runs, output selection, EVM version, ...), and remappings.
+/// Bumping any of these must invalidate every manifest entry recorded against the old fingerprint.
+pub(crate) fn config_fingerprint(config: &CompilerConfig) -> String {
+  let mut payload = config.solc_version.to_string();
+  payload.push('\n');
+  payload.push_str(&serde_json::to_string(&config.solc_settings).unwrap_or_default());
+  payload.push('\n');
+  for remapping in &config.remappings {
+    payload.push_str(&remapping.to_string());
+    payload.push('\n');
+  }
+  hex::encode(keccak256(payload.as_bytes()))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+  content_hash: String,
+  config_fingerprint: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  artifact_path: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Manifest(BTreeMap<String, ManifestEntry>);
+
+impl Manifest {
+  fn load(path: &Path) -> Self {
+    fs::read_to_string(path)
+      .ok()
+      .and_then(|contents| serde_json::from_str(&contents).ok())
+      .unwrap_or_default()
+  }
+
+  fn save(&self, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).map_err(|err| {
+        Error::io(format!(
+          "Failed to prepare incremental cache directory {}: {err}",
+          parent.display()
+        ))
+      })?;
+    }
+    let serialized = serde_json::to_string_pretty(self).map_err(|err| {
+      Error::new(format!(
+        "Failed to serialise incremental cache manifest: {err}"
+      ))
+    })?;
+    fs::write(path, serialized).map_err(|err| {
+      Error::io(format!(
+        "Failed to write incremental cache manifest {}: {err}",
+        path.display()
+      ))
+    })
+  }
+}
+
+/// Keys classified against the persisted manifest for a single batch of sources: `dirty` entries
+/// had a new or changed content hash (or the config fingerprint moved), `reused` entries matched
+/// exactly and were not recompiled.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DirtyReport {
+  pub dirty: Vec<String>,
+  pub reused: Vec<String>,
+}
+
+/// Compares each `(key, content_hash)` pair against the manifest at `manifest_path`, classifies it
+/// as dirty or reused, then persists the updated manifest with the current hashes and fingerprint.
+/// Only the source's own content is hashed, not its transitive import closure.
+pub(crate) fn evaluate(
+  manifest_path: &Path,
+  fingerprint: &str,
+  entries: &[(String, String)],
+) -> Result<DirtyReport> {
+  let mut manifest = Manifest::load(manifest_path);
+  let mut report = DirtyReport::default();
+
+  for (key, content_hash) in entries {
+    let reused = manifest.0.get(key).is_some_and(|entry| {
+      entry.content_hash == *content_hash && entry.config_fingerprint == fingerprint
+    });
+
+    if reused {
+      report.reused.push(key.clone());
+    } else {
+      report.dirty.push(key.clone());
+    }
+
+    manifest.0.insert(
+      key.clone(),
+      ManifestEntry {
+        content_hash: content_hash.clone(),
+        config_fingerprint: fingerprint.to_string(),
+        artifact_path: None,
+      },
+    );
+  }
+
+  manifest.save(manifest_path)?;
+  Ok(report)
+}
+
+/// Like [`evaluate`], but purely a read: classifies each `(key, content_hash)` pair against the
+/// persisted manifest without writing anything back. Used by [`super::project::ProjectContext::dirty_sources`]
+/// so a caller can ask what would rebuild without mutating cache state as a side effect of asking.
+pub(crate) fn peek(manifest_path: &Path, fingerprint: &str, entries: &[(String, String)]) -> DirtyReport {
+  let manifest = Manifest::load(manifest_path);
+  let mut report = DirtyReport::default();
+
+  for (key, content_hash) in entries {
+    let reused = manifest.0.get(key).is_some_and(|entry| {
+      entry.content_hash == *content_hash && entry.config_fingerprint == fingerprint
+    });
+    if reused {
+      report.reused.push(key.clone());
+    } else {
+      report.dirty.push(key.clone());
+    }
+  }
+
+  report
+}
+
+/// Expands `dirty` -- paths already known dirty from a direct content-hash or fingerprint
+/// mismatch -- across each source's import-connected component (see
+/// [`graph::import_connected_components`]): an importer can depend on symbols defined in whatever
+/// changed, so it's not safe to treat it as fresh just because its own content is unchanged. Falls
+/// back to the unexpanded set (logging a warning) if the import graph itself fails to resolve,
+/// the same fallback `compile_solc_sources_incremental` already uses for the standalone pipeline.
+pub(crate) fn expand_dirty_across_imports(
+  dirty: &[String],
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> BTreeSet<String> {
+  let mut expanded: BTreeSet<String> = dirty.iter().cloned().collect();
+
+  match graph::import_connected_components(sources, remappings) {
+    Ok(components) => {
+      for component in &components {
+        if component.iter().any(|path| expanded.contains(path)) {
+          expanded.extend(component.iter().cloned());
+        }
+      }
+    }
+    Err(err) => {
+      warn!(
+        target: LOG_TARGET,
+        "failed to expand the incremental cache dirty set across the import graph ({err}); \
+         falling back to per-file dirty tracking"
+      );
+    }
+  }
+
+  expanded
+}
+
+/// A single source's compiled output, cached verbatim as the raw solc-output JSON fragments
+/// needed to reconstruct a [`super::super::compiler::output::SourceArtifacts`] without re-running
+/// solc: the `sources[path]` AST fragment and the `contracts[path]` contract map.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct CachedArtifact {
+  pub source: Value,
+  pub contracts: Value,
+  /// Diagnostics (errors and warnings) whose `sourceLocation.file` points at this entry's path,
+  /// so a fully cached compile replays solc's warnings instead of silently dropping them. Empty
+  /// for artifacts cached before this field existed.
+  #[serde(default)]
+  pub errors: Vec<Value>,
+}
+
+/// Result of checking a batch of sources against the manifest with artifact reuse in mind:
+/// `dirty` paths changed (or the config fingerprint moved) and must be recompiled, while `fresh`
+/// carries the cached artifact for every path whose content hash and fingerprint still match.
+#[derive(Debug, Default)]
+pub(crate) struct ArtifactCacheReport {
+  pub dirty: Vec<String>,
+  pub fresh: BTreeMap<String, CachedArtifact>,
+}
+
+/// Like [`evaluate`], but hashes full source text (via [`FoundrySource::content_hash_of`]) and,
+/// for every entry that's still fresh, loads its cached artifact from disk instead of merely
+/// reporting that it was reused. A path missing its cached artifact file (e.g. deleted by hand)
+/// is treated as dirty rather than failing the whole batch.
+pub(crate) fn evaluate_with_artifacts(
+  manifest_path: &Path,
+  fingerprint: &str,
+  sources: &BTreeMap<String, String>,
+) -> Result<ArtifactCacheReport> {
+  let manifest = Manifest::load(manifest_path);
+  let mut report = ArtifactCacheReport::default();
+
+  for (path, content) in sources {
+    let content_hash = FoundrySource::content_hash_of(content);
+    let fresh_entry = manifest.0.get(path).filter(|entry| {
+      entry.content_hash == content_hash && entry.config_fingerprint == fingerprint
+    });
+
+    let cached = fresh_entry
+      .and_then(|entry| entry.artifact_path.as_deref())
+      .and_then(load_cached_artifact);
+
+    match cached {
+      Some(artifact) => {
+        report.fresh.insert(path.clone(), artifact);
+      }
+      None => report.dirty.push(path.clone()),
+    }
+  }
+
+  Ok(report)
+}
+
+fn load_cached_artifact(path: &str) -> Option<CachedArtifact> {
+  let contents = fs::read_to_string(path).ok()?;
+  serde_json::from_str(&contents).ok()
+}
+
+/// Writes each `(path, content, artifact)` triple to `{artifacts_dir}/{content_hash}.json` and
+/// records it in the manifest under the current fingerprint, so the next [`evaluate_with_artifacts`]
+/// call can skip recompiling it. Reusing the content hash as the file name means unrelated sources
+/// that happen to share identical content also share one cached artifact file.
+pub(crate) fn store_artifacts(
+  manifest_path: &Path,
+  artifacts_dir: &Path,
+  fingerprint: &str,
+  entries: &[(String, String, CachedArtifact)],
+) -> Result<()> {
+  fs::create_dir_all(artifacts_dir).map_err(|err| {
+    Error::io(format!(
+      "Failed to prepare incremental artifact cache directory {}: {err}",
+      artifacts_dir.display()
+    ))
+  })?;
+
+  let mut manifest = Manifest::load(manifest_path);
+
+  for (path, content, artifact) in entries {
+    let content_hash = FoundrySource::content_hash_of(content);
+    let artifact_path = artifacts_dir.join(format!("{content_hash}.json"));
+    let serialized = serde_json::to_string(artifact).map_err(|err| {
+      Error::new(format!("Failed to serialise cached artifact for {path}: {err}"))
+    })?;
+    fs::write(&artifact_path, serialized).map_err(|err| {
+      Error::io(format!(
+        "Failed to write cached artifact {}: {err}",
+        artifact_path.display()
+      ))
+    })?;
+
+    manifest.0.insert(
+      path.clone(),
+      ManifestEntry {
+        content_hash,
+        config_fingerprint: fingerprint.to_string(),
+        artifact_path: Some(artifact_path.to_string_lossy().into_owned()),
+      },
+    );
+  }
+
+  manifest.save(manifest_path)
+}
+
+/// Deletes the manifest sidecar and its artifact store, so the next build treats every source as
+/// dirty regardless of content hash. Missing paths are not an error -- clearing an already-empty
+/// cache is a no-op.
+pub(crate) fn clear(manifest_path: &Path, artifacts_dir: &Path) -> Result<()> {
+  match fs::remove_file(manifest_path) {
+    Ok(()) => {}
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+    Err(err) => {
+      return Err(Error::io(format!(
+        "Failed to remove incremental cache manifest {}: {err}",
+        manifest_path.display()
+      )))
+    }
+  }
+
+  match fs::remove_dir_all(artifacts_dir) {
+    Ok(()) => Ok(()),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+    Err(err) => Err(Error::io(format!(
+      "Failed to remove incremental artifact cache directory {}: {err}",
+      artifacts_dir.display()
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::tempdir;
+
+  #[test]
+  fn config_fingerprint_changes_when_optimizer_runs_change() {
+    let mut config = CompilerConfig::default();
+    let baseline = config_fingerprint(&config);
+
+    config.solc_settings.optimizer.runs = Some(200);
+    let changed = config_fingerprint(&config);
+
+    assert_ne!(baseline, changed);
+  }
+
+  #[test]
+  fn evaluate_reuses_unchanged_entries_and_flags_new_ones() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+
+    let first = evaluate(
+      &manifest_path,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "hash-a".to_string())],
+    )
+    .expect("first evaluate");
+    assert_eq!(first.dirty, vec!["A.sol".to_string()]);
+    assert!(first.reused.is_empty());
+
+    let second = evaluate(
+      &manifest_path,
+      "fingerprint-a",
+      &[
+        ("A.sol".to_string(), "hash-a".to_string()),
+        ("B.sol".to_string(), "hash-b".to_string()),
+      ],
+    )
+    .expect("second evaluate");
+    assert_eq!(second.reused, vec!["A.sol".to_string()]);
+    assert_eq!(second.dirty, vec!["B.sol".to_string()]);
+  }
+
+  #[test]
+  fn evaluate_invalidates_on_fingerprint_change() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+
+    evaluate(
+      &manifest_path,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "hash-a".to_string())],
+    )
+    .expect("first evaluate");
+
+    let rerun = evaluate(
+      &manifest_path,
+      "fingerprint-b",
+      &[("A.sol".to_string(), "hash-a".to_string())],
+    )
+    .expect("second evaluate");
+    assert_eq!(rerun.dirty, vec!["A.sol".to_string()]);
+    assert!(rerun.reused.is_empty());
+  }
+
+  fn sample_artifact() -> CachedArtifact {
+    CachedArtifact {
+      source: serde_json::json!({"ast": {}}),
+      contracts: serde_json::json!({"A": {}}),
+      errors: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn evaluate_with_artifacts_is_all_dirty_before_anything_is_cached() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "contract A {}".to_string());
+
+    let report = evaluate_with_artifacts(&manifest_path, "fingerprint-a", &sources).unwrap();
+    assert_eq!(report.dirty, vec!["A.sol".to_string()]);
+    assert!(report.fresh.is_empty());
+  }
+
+  #[test]
+  fn store_artifacts_then_evaluate_with_artifacts_reuses_unchanged_entries() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+    let artifacts_dir = temp.path().join("artifacts");
+
+    store_artifacts(
+      &manifest_path,
+      &artifacts_dir,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "contract A {}".to_string(), sample_artifact())],
+    )
+    .expect("store_artifacts");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "contract A {}".to_string());
+    sources.insert("B.sol".to_string(), "contract B {}".to_string());
+
+    let report = evaluate_with_artifacts(&manifest_path, "fingerprint-a", &sources).unwrap();
+    assert_eq!(report.dirty, vec!["B.sol".to_string()]);
+    assert!(report.fresh.contains_key("A.sol"));
+  }
+
+  #[test]
+  fn evaluate_with_artifacts_invalidates_on_content_change() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+    let artifacts_dir = temp.path().join("artifacts");
+
+    store_artifacts(
+      &manifest_path,
+      &artifacts_dir,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "contract A {}".to_string(), sample_artifact())],
+    )
+    .expect("store_artifacts");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "contract A { function f() public {} }".to_string());
+
+    let report = evaluate_with_artifacts(&manifest_path, "fingerprint-a", &sources).unwrap();
+    assert_eq!(report.dirty, vec!["A.sol".to_string()]);
+    assert!(report.fresh.is_empty());
+  }
+
+  #[test]
+  fn store_artifacts_round_trips_cached_errors() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+    let artifacts_dir = temp.path().join("artifacts");
+
+    let mut artifact = sample_artifact();
+    artifact.errors = vec![serde_json::json!({
+      "severity": "warning",
+      "message": "unused variable",
+      "sourceLocation": { "file": "A.sol", "start": 10, "end": 20 },
+    })];
+
+    store_artifacts(
+      &manifest_path,
+      &artifacts_dir,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "contract A {}".to_string(), artifact)],
+    )
+    .expect("store_artifacts");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "contract A {}".to_string());
+
+    let report = evaluate_with_artifacts(&manifest_path, "fingerprint-a", &sources).unwrap();
+    let cached = report.fresh.get("A.sol").expect("cached artifact");
+    assert_eq!(cached.errors.len(), 1);
+    assert_eq!(cached.errors[0]["message"], "unused variable");
+  }
+
+  #[test]
+  fn clear_removes_manifest_and_artifacts_so_everything_is_dirty_again() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+    let artifacts_dir = temp.path().join("artifacts");
+
+    store_artifacts(
+      &manifest_path,
+      &artifacts_dir,
+      "fingerprint-a",
+      &[("A.sol".to_string(), "contract A {}".to_string(), sample_artifact())],
+    )
+    .expect("store_artifacts");
+
+    clear(&manifest_path, &artifacts_dir).expect("clear");
+    assert!(!manifest_path.exists());
+    assert!(!artifacts_dir.exists());
+
+    let mut sources = BTreeMap::new();
+    sources.insert("A.sol".to_string(), "contract A {}".to_string());
+    let report = evaluate_with_artifacts(&manifest_path, "fingerprint-a", &sources).unwrap();
+    assert_eq!(report.dirty, vec!["A.sol".to_string()]);
+  }
+
+  #[test]
+  fn clear_on_an_already_empty_cache_is_a_no_op() {
+    let temp = tempdir().expect("tempdir");
+    let manifest_path = temp.path().join("tevm-incremental-cache.json");
+    let artifacts_dir = temp.path().join("artifacts");
+
+    clear(&manifest_path, &artifacts_dir).expect("clear on empty cache");
+  }
+}
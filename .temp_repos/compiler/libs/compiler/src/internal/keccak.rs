@@ -0,0 +1,31 @@
+use sha3::{Digest, Keccak256};
+
+/// Computes the `Keccak256` digest (the legacy Keccak padding Ethereum uses, not NIST SHA3) of
+/// `data`. Used to derive 4-byte EVM calldata selectors from a function's canonical signature.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+  let mut hasher = Keccak256::new();
+  hasher.update(data);
+  let mut output = [0u8; 32];
+  output.copy_from_slice(&hasher.finalize());
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn keccak256_matches_known_vector_for_empty_input() {
+    let digest = keccak256(b"");
+    assert_eq!(
+      hex::encode(digest),
+      "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+  }
+
+  #[test]
+  fn keccak256_matches_known_vector_for_transfer_selector() {
+    let digest = keccak256(b"transfer(address,uint256)");
+    assert_eq!(&digest[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+  }
+}
@@ -1,6 +1,13 @@
+use std::collections::VecDeque;
 use std::fmt::Write as _;
-use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::{Arc, RwLock};
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use log::{Level, LevelFilter, Log, Metadata, Record};
 use napi::bindgen_prelude::*;
@@ -9,8 +16,19 @@ use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 #[cfg(not(test))]
 use napi::{JsFunction, JsObject, JsUnknown};
 use once_cell::sync::OnceCell;
+use regex::Regex;
+use serde_json::{json, Map, Value};
 
-use crate::internal::errors::Result;
+use crate::internal::errors::{map_err_with_context, Result};
+
+/// Default cap on [`LogRing`]'s in-memory record count when no explicit capacity has been set via
+/// [`configure_log_ring`].
+const DEFAULT_RING_CAPACITY: usize = 1_000;
+
+/// Bounded capacity of the channel `log()` pushes onto. Sized generously so a burst of diagnostic
+/// output from several compile workers doesn't immediately spill into dropped records; once full,
+/// `log()` drops the record and counts it rather than blocking the caller.
+const LOG_CHANNEL_CAPACITY: usize = 4_096;
 
 /// Shared logging level exposed to both Rust and JavaScript callers.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
@@ -20,6 +38,8 @@ pub enum LoggingLevel {
   Error,
   Warn,
   Info,
+  Debug,
+  Trace,
 }
 
 impl LoggingLevel {
@@ -29,6 +49,8 @@ impl LoggingLevel {
       LoggingLevel::Error => 1,
       LoggingLevel::Warn => 2,
       LoggingLevel::Info => 3,
+      LoggingLevel::Debug => 4,
+      LoggingLevel::Trace => 5,
     }
   }
 
@@ -37,7 +59,9 @@ impl LoggingLevel {
       0 => LoggingLevel::Silent,
       1 => LoggingLevel::Error,
       2 => LoggingLevel::Warn,
-      _ => LoggingLevel::Info,
+      3 => LoggingLevel::Info,
+      4 => LoggingLevel::Debug,
+      _ => LoggingLevel::Trace,
     }
   }
 
@@ -47,6 +71,8 @@ impl LoggingLevel {
       LoggingLevel::Error => "error",
       LoggingLevel::Warn => "warn",
       LoggingLevel::Info => "info",
+      LoggingLevel::Debug => "debug",
+      LoggingLevel::Trace => "trace",
     }
   }
 }
@@ -58,6 +84,8 @@ impl From<LoggingLevel> for LevelFilter {
       LoggingLevel::Error => LevelFilter::Error,
       LoggingLevel::Warn => LevelFilter::Warn,
       LoggingLevel::Info => LevelFilter::Info,
+      LoggingLevel::Debug => LevelFilter::Debug,
+      LoggingLevel::Trace => LevelFilter::Trace,
     }
   }
 }
@@ -67,11 +95,52 @@ impl From<Level> for LoggingLevel {
     match level {
       Level::Error => LoggingLevel::Error,
       Level::Warn => LoggingLevel::Warn,
-      Level::Info | Level::Debug | Level::Trace => LoggingLevel::Info,
+      Level::Info => LoggingLevel::Info,
+      Level::Debug => LoggingLevel::Debug,
+      Level::Trace => LoggingLevel::Trace,
+    }
+  }
+}
+
+/// Line format emitted by [`ConsoleLogger`]. `Pretty` is the existing
+/// `[LEVEL] target (file:line) - msg` text line; `Json` emits one Bunyan-style JSON object per
+/// record so tooling that scrapes compiler output can `JSON.parse` each line instead of
+/// pattern-matching text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum LogFormat {
+  #[default]
+  Pretty,
+  Json,
+}
+
+impl LogFormat {
+  const fn to_index(self) -> u8 {
+    match self {
+      LogFormat::Pretty => 0,
+      LogFormat::Json => 1,
+    }
+  }
+
+  const fn from_index(value: u8) -> Self {
+    match value {
+      1 => LogFormat::Json,
+      _ => LogFormat::Pretty,
     }
   }
 }
 
+/// Numeric severity following Bunyan's conventions (`trace`: 10 ... `fatal`: 60). `log` has no
+/// `Fatal` level, so the highest value ever emitted here is `error`'s 50.
+const fn bunyan_level(level: Level) -> u8 {
+  match level {
+    Level::Trace => 10,
+    Level::Debug => 20,
+    Level::Info => 30,
+    Level::Warn => 40,
+    Level::Error => 50,
+  }
+}
+
 /// Install the shared logger when invoked from Rust-only entry points.
 pub fn ensure_rust_logger(level: LoggingLevel) -> Result<()> {
   let state = install_logger()?;
@@ -95,6 +164,23 @@ pub fn ensure_napi_logger(env: &Env, level: LoggingLevel) -> napi::Result<()> {
   Ok(())
 }
 
+/// Install the shared logger backed by a rolling log file instead of stdout or Node's console.
+/// `rollover_bytes` is the size threshold past which the active file is rolled; `max_archives` is
+/// how many rolled-over files (`compiler.1.log`, `compiler.2.log`, ...) are kept before the oldest
+/// is deleted. Long-running builds can point this at a fixed path and retain bounded history
+/// instead of growing `compiler.log` forever.
+pub fn ensure_file_logger(
+  path: impl AsRef<Path>,
+  level: LoggingLevel,
+  rollover_bytes: u64,
+  max_archives: u32,
+) -> Result<()> {
+  let state = install_logger()?;
+  state.set_file_backend(path.as_ref(), rollover_bytes, max_archives)?;
+  state.update_level(level);
+  Ok(())
+}
+
 /// Update the active logging level in place.
 pub fn update_level(level: LoggingLevel) {
   if let Some(state) = LOGGER.get() {
@@ -104,32 +190,312 @@ pub fn update_level(level: LoggingLevel) {
   }
 }
 
-#[cfg(not(test))]
-#[derive(Clone)]
+/// Update the active line format (`Pretty` or `Json`) in place. A no-op if the logger hasn't been
+/// installed yet via `ensure_rust_logger`/`ensure_napi_logger`/`ensure_file_logger`, same as
+/// [`update_level`] would be if `log::set_max_level` weren't also available as a fallback.
+pub fn update_format(format: LogFormat) {
+  if let Some(state) = LOGGER.get() {
+    state.update_format(format);
+  }
+}
+
+/// Overrides the logging level for every target whose name starts with `prefix`, independent of
+/// the global level set via [`update_level`]. Consulted by longest-prefix match, so
+/// `update_target_level("compiler::solc", LoggingLevel::Error)` quiets a chatty subsystem down to
+/// errors-only while everything else keeps logging at the global level.
+pub fn update_target_level(prefix: impl Into<String>, level: LoggingLevel) {
+  let state = LOGGER.get_or_init(|| Arc::new(LoggerState::new()));
+  state.update_target_level(prefix.into(), level);
+}
+
+/// Reconfigures the in-memory log ring's capacity and retention window. `retention` drops records
+/// older than its `Duration` on the next insert rather than eagerly on a timer, so it only ever
+/// costs work proportional to what's actually evicted.
+pub fn configure_log_ring(capacity: usize, retention: Option<Duration>) {
+  let state = LOGGER.get_or_init(|| Arc::new(LoggerState::new()));
+  state.configure_ring(capacity, retention);
+}
+
+/// Scans the in-memory log ring newest-first, applying every predicate set on `filter`, and
+/// returns up to `filter.limit` matches (unlimited when `limit` is `0`). Lets N-API callers pull
+/// recent compiler diagnostics after a build completes instead of only receiving them live
+/// through the console callback.
+pub fn query_logs(filter: &RecordFilter) -> Vec<StoredRecord> {
+  match LOGGER.get() {
+    Some(state) => state.query_ring(filter),
+    None => Vec::new(),
+  }
+}
+
+/// A single captured log record, as retained by the in-memory ring.
+#[derive(Clone, Debug)]
+pub struct StoredRecord {
+  pub level: LoggingLevel,
+  pub target: String,
+  pub message: String,
+  pub time: SystemTime,
+}
+
+/// Predicate set for [`query_logs`]. Every field is optional (a `None`/empty value doesn't
+/// filter); `limit` of `0` means unlimited.
+#[derive(Default)]
+pub struct RecordFilter {
+  pub level: Option<LoggingLevel>,
+  pub module: Option<String>,
+  pub regex: Option<Regex>,
+  pub not_before: Option<SystemTime>,
+  pub limit: u32,
+}
+
+/// Bounded newest-first ring of [`StoredRecord`]s, capped at `capacity` entries and optionally at
+/// `retention` age. Eviction happens lazily on insert rather than on a background timer.
+struct LogRing {
+  entries: VecDeque<StoredRecord>,
+  capacity: usize,
+  retention: Option<Duration>,
+}
+
+impl LogRing {
+  fn new() -> Self {
+    Self {
+      entries: VecDeque::new(),
+      capacity: DEFAULT_RING_CAPACITY,
+      retention: None,
+    }
+  }
+
+  fn configure(&mut self, capacity: usize, retention: Option<Duration>) {
+    self.capacity = capacity.max(1);
+    self.retention = retention;
+    self.truncate_to_capacity();
+  }
+
+  fn push(&mut self, record: StoredRecord) {
+    self.entries.push_front(record);
+    self.evict_expired();
+    self.truncate_to_capacity();
+  }
+
+  fn evict_expired(&mut self) {
+    let Some(retention) = self.retention else {
+      return;
+    };
+    let Some(newest) = self.entries.front().map(|record| record.time) else {
+      return;
+    };
+    while let Some(oldest) = self.entries.back() {
+      if newest.duration_since(oldest.time).unwrap_or_default() > retention {
+        self.entries.pop_back();
+      } else {
+        break;
+      }
+    }
+  }
+
+  fn truncate_to_capacity(&mut self) {
+    while self.entries.len() > self.capacity {
+      self.entries.pop_back();
+    }
+  }
+}
+
+/// Lightweight payload `log()` pushes onto the writer channel. Formatting happens on the calling
+/// thread (cheap); the background writer thread owns everything that can block (stdout, file I/O,
+/// the Node threadsafe call).
 struct LogInvocation {
   level: LoggingLevel,
   line: String,
+  target: String,
+  time: SystemTime,
+}
+
+/// Command sent over the writer channel. `Flush` carries a rendezvous sender so [`LoggerState::flush`]
+/// can block until every `Write` queued ahead of it has been processed.
+enum LogCommand {
+  Write(LogInvocation),
+  Flush(SyncSender<()>),
 }
 
 enum LoggerBackend {
   Stdout,
+  File(RollingFileAppender),
   #[cfg(not(test))]
   Node(ThreadsafeFunction<LogInvocation>),
 }
 
+/// A fixed-window rolling file appender, modelled on log4rs's compound policy: writes formatted
+/// lines to `path`, and once the active file reaches `rollover_bytes` renames it through a window
+/// of `max_archives` numbered archives (`compiler.log` -> `compiler.1.log` -> `compiler.2.log`
+/// ...), deleting whichever archive falls off the end of the window. A `rollover_bytes` of `0`
+/// disables rolling; a `max_archives` of `0` discards the active file on rollover instead of
+/// archiving it.
+struct RollingFileAppender {
+  path: PathBuf,
+  rollover_bytes: u64,
+  max_archives: u32,
+  inner: Mutex<RollingFileInner>,
+}
+
+struct RollingFileInner {
+  file: File,
+  written_bytes: u64,
+}
+
+impl RollingFileAppender {
+  fn open(path: PathBuf, rollover_bytes: u64, max_archives: u32) -> Result<Self> {
+    let file = map_err_with_context(open_append(&path), "failed to open log file")?;
+    let written_bytes = map_err_with_context(file.metadata(), "failed to stat log file")?.len();
+    Ok(Self {
+      path,
+      rollover_bytes,
+      max_archives,
+      inner: Mutex::new(RollingFileInner { file, written_bytes }),
+    })
+  }
+
+  fn archive_path(&self, index: u32) -> PathBuf {
+    let stem = self
+      .path
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .unwrap_or("compiler");
+    let name = match self.path.extension().and_then(|ext| ext.to_str()) {
+      Some(ext) => format!("{stem}.{index}.{ext}"),
+      None => format!("{stem}.{index}"),
+    };
+    self.path.with_file_name(name)
+  }
+
+  fn write_line(&self, line: &str) {
+    let mut inner = self.inner.lock().expect("log file lock poisoned");
+    if self.rollover_bytes > 0 && inner.written_bytes >= self.rollover_bytes {
+      if let Err(err) = self.roll(&mut inner) {
+        eprintln!("failed to roll log file {}: {err}", self.path.display());
+      }
+    }
+
+    let mut bytes = line.as_bytes().to_vec();
+    bytes.push(b'\n');
+    match inner.file.write_all(&bytes) {
+      Ok(()) => inner.written_bytes += bytes.len() as u64,
+      Err(err) => eprintln!("failed to write log line to {}: {err}", self.path.display()),
+    }
+  }
+
+  fn roll(&self, inner: &mut RollingFileInner) -> std::io::Result<()> {
+    if self.max_archives == 0 {
+      std::fs::remove_file(&self.path).ok();
+    } else {
+      let oldest = self.archive_path(self.max_archives);
+      if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+      }
+      for index in (1..self.max_archives).rev() {
+        let from = self.archive_path(index);
+        if from.exists() {
+          std::fs::rename(&from, self.archive_path(index + 1))?;
+        }
+      }
+      if self.path.exists() {
+        std::fs::rename(&self.path, self.archive_path(1))?;
+      }
+    }
+
+    inner.file = open_append(&self.path)?;
+    inner.written_bytes = 0;
+    Ok(())
+  }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+  OpenOptions::new().create(true).append(true).open(path)
+}
+
 struct LoggerState {
   backend: RwLock<Option<LoggerBackend>>,
   level: AtomicU8,
+  format: AtomicU8,
+  ring: Mutex<LogRing>,
+  /// Target-prefix -> level overrides, consulted by longest-prefix match in [`Self::effective_level`]
+  /// before falling back to the global [`Self::active_level`]. Mirrors the `log` crate's own
+  /// target-based filtering convention.
+  directives: RwLock<Vec<(String, LoggingLevel)>>,
+  /// Writer channel `log()` pushes onto; the background writer thread owns the matching
+  /// [`Receiver`] (via [`Self::writer_receiver`]) and performs the actual backend I/O.
+  sender: SyncSender<LogCommand>,
+  /// Taken exactly once by [`Self::ensure_writer_thread`], whichever call path reaches it first.
+  writer_receiver: Mutex<Option<Receiver<LogCommand>>>,
+  writer_started: AtomicBool,
+  /// Count of records dropped because the writer channel was full (backpressure) or the writer
+  /// thread was gone. Surfaced via [`dropped_log_count`].
+  dropped: AtomicU64,
 }
 
 impl LoggerState {
   fn new() -> Self {
+    let (sender, receiver) = mpsc::sync_channel(LOG_CHANNEL_CAPACITY);
     Self {
       backend: RwLock::new(None),
       level: AtomicU8::new(LoggingLevel::Info.to_index()),
+      format: AtomicU8::new(LogFormat::Pretty.to_index()),
+      ring: Mutex::new(LogRing::new()),
+      directives: RwLock::new(Vec::new()),
+      sender,
+      writer_receiver: Mutex::new(Some(receiver)),
+      writer_started: AtomicBool::new(false),
+      dropped: AtomicU64::new(0),
     }
   }
 
+  /// Spawns the single background thread that owns backend I/O, the first time it's called for
+  /// this state. Later calls are no-ops, so every path that might install the logger can call this
+  /// unconditionally instead of coordinating who's responsible for starting it.
+  fn ensure_writer_thread(self: &Arc<Self>) {
+    if self.writer_started.swap(true, Ordering::AcqRel) {
+      return;
+    }
+
+    let receiver = self
+      .writer_receiver
+      .lock()
+      .expect("log writer receiver lock poisoned")
+      .take();
+    let Some(receiver) = receiver else {
+      return;
+    };
+
+    let state = self.clone();
+    let spawned = thread::Builder::new()
+      .name("compiler-log-writer".to_string())
+      .spawn(move || run_log_writer(&state, &receiver));
+
+    if spawned.is_err() {
+      // Couldn't spawn the writer thread (e.g. out of OS threads); let a later call retry. Log
+      // records will simply queue in the channel until one succeeds.
+      self.writer_started.store(false, Ordering::Release);
+    }
+  }
+
+  fn enqueue(&self, invocation: LogInvocation) {
+    if self.sender.try_send(LogCommand::Write(invocation)).is_err() {
+      self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  fn dropped_count(&self) -> u64 {
+    self.dropped.load(Ordering::Relaxed)
+  }
+
+  /// Blocks until every record enqueued before this call has been written. Returns `false` if the
+  /// writer thread is gone (e.g. never started, or panicked) and the flush couldn't be delivered.
+  fn flush(&self) -> bool {
+    let (ack_sender, ack_receiver) = mpsc::sync_channel(0);
+    if self.sender.send(LogCommand::Flush(ack_sender)).is_err() {
+      return false;
+    }
+    ack_receiver.recv().is_ok()
+  }
+
   fn ensure_stdout_backend(&self) {
     let mut backend = self.backend.write().expect("logger backend lock poisoned");
     if backend.is_none() {
@@ -143,6 +509,13 @@ impl LoggerState {
     *backend = Some(LoggerBackend::Node(tsfn));
   }
 
+  fn set_file_backend(&self, path: &Path, rollover_bytes: u64, max_archives: u32) -> Result<()> {
+    let appender = RollingFileAppender::open(path.to_path_buf(), rollover_bytes, max_archives)?;
+    let mut backend = self.backend.write().expect("logger backend lock poisoned");
+    *backend = Some(LoggerBackend::File(appender));
+    Ok(())
+  }
+
   fn update_level(&self, level: LoggingLevel) {
     self.level.store(level.to_index(), Ordering::Release);
     log::set_max_level(LevelFilter::from(level));
@@ -152,11 +525,138 @@ impl LoggerState {
     LoggingLevel::from_index(self.level.load(Ordering::Acquire))
   }
 
+  fn update_target_level(&self, prefix: String, level: LoggingLevel) {
+    let mut directives = self.directives.write().expect("logger directives lock poisoned");
+    match directives.iter_mut().find(|(existing, _)| *existing == prefix) {
+      Some(existing) => existing.1 = level,
+      None => directives.push((prefix, level)),
+    }
+    drop(directives);
+
+    // `log`'s own max-level filter gates whether a record reaches this logger at all, so a
+    // directive more verbose than the current global level would otherwise never arrive here.
+    log::set_max_level(LevelFilter::from(self.most_verbose_level()));
+  }
+
+  fn most_verbose_level(&self) -> LoggingLevel {
+    self
+      .directives
+      .read()
+      .expect("logger directives lock poisoned")
+      .iter()
+      .map(|(_, level)| *level)
+      .fold(self.active_level(), |most_verbose, level| {
+        if level.to_index() > most_verbose.to_index() {
+          level
+        } else {
+          most_verbose
+        }
+      })
+  }
+
+  /// The level that applies to `target`: the longest matching directive prefix, or the global
+  /// level when no directive matches.
+  fn effective_level(&self, target: &str) -> LoggingLevel {
+    self
+      .directives
+      .read()
+      .expect("logger directives lock poisoned")
+      .iter()
+      .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+      .max_by_key(|(prefix, _)| prefix.len())
+      .map(|(_, level)| *level)
+      .unwrap_or_else(|| self.active_level())
+  }
+
+  fn update_format(&self, format: LogFormat) {
+    self.format.store(format.to_index(), Ordering::Release);
+  }
+
+  fn active_format(&self) -> LogFormat {
+    LogFormat::from_index(self.format.load(Ordering::Acquire))
+  }
+
+  fn configure_ring(&self, capacity: usize, retention: Option<Duration>) {
+    self.ring.lock().expect("log ring lock poisoned").configure(capacity, retention);
+  }
+
+  fn record(&self, level: LoggingLevel, target: &str, message: String) {
+    self.ring.lock().expect("log ring lock poisoned").push(StoredRecord {
+      level,
+      target: target.to_string(),
+      message,
+      time: SystemTime::now(),
+    });
+  }
+
+  fn query_ring(&self, filter: &RecordFilter) -> Vec<StoredRecord> {
+    let limit = if filter.limit == 0 {
+      usize::MAX
+    } else {
+      filter.limit as usize
+    };
+
+    self
+      .ring
+      .lock()
+      .expect("log ring lock poisoned")
+      .entries
+      .iter()
+      .filter(|record| filter.level.map_or(true, |level| record.level == level))
+      .filter(|record| {
+        filter
+          .module
+          .as_deref()
+          .map_or(true, |module| record.target.contains(module))
+      })
+      .filter(|record| {
+        filter
+          .regex
+          .as_ref()
+          .map_or(true, |regex| regex.is_match(&record.message))
+      })
+      .filter(|record| {
+        filter
+          .not_before
+          .map_or(true, |not_before| record.time >= not_before)
+      })
+      .take(limit)
+      .cloned()
+      .collect()
+  }
+
   fn backend_guard(&self) -> std::sync::RwLockReadGuard<'_, Option<LoggerBackend>> {
     self.backend.read().expect("logger backend lock poisoned")
   }
 }
 
+/// Runs on the dedicated `compiler-log-writer` thread for the lifetime of the process: owns all
+/// backend I/O so formatting on the calling thread (in [`ConsoleLogger::log`]) never blocks on
+/// stdout/file/Node writes.
+fn run_log_writer(state: &Arc<LoggerState>, receiver: &Receiver<LogCommand>) {
+  for command in receiver {
+    match command {
+      LogCommand::Write(invocation) => dispatch_invocation(state, invocation),
+      LogCommand::Flush(ack) => {
+        let _ = ack.send(());
+      }
+    }
+  }
+}
+
+fn dispatch_invocation(state: &LoggerState, invocation: LogInvocation) {
+  let backend_guard = state.backend_guard();
+  match backend_guard.as_ref() {
+    Some(LoggerBackend::Stdout) => dispatch_stdout(invocation.level, &invocation.line),
+    Some(LoggerBackend::File(appender)) => appender.write_line(&invocation.line),
+    #[cfg(not(test))]
+    Some(LoggerBackend::Node(tsfn)) => {
+      let _ = tsfn.call(Ok(invocation), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+    None => dispatch_stdout(invocation.level, &invocation.line),
+  }
+}
+
 struct ConsoleLogger {
   state: Arc<LoggerState>,
 }
@@ -169,7 +669,7 @@ impl ConsoleLogger {
 
 impl Log for ConsoleLogger {
   fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-    metadata.level() <= LevelFilter::from(self.state.active_level())
+    metadata.level() <= LevelFilter::from(self.state.effective_level(metadata.target()))
   }
 
   fn log(&self, record: &Record<'_>) {
@@ -178,50 +678,110 @@ impl Log for ConsoleLogger {
     }
 
     let level = LoggingLevel::from(record.level());
-    let mut line = String::new();
-    let _ = write!(&mut line, "[{}]", level.as_str().to_uppercase());
-
-    let target = record.target();
-    if !target.is_empty() {
-      let _ = write!(&mut line, " {target}");
-    }
+    let target = record.target().to_string();
+    let line = match self.state.active_format() {
+      LogFormat::Pretty => format_pretty_line(record, level),
+      LogFormat::Json => format_json_line(record),
+    };
 
-    if let (Some(file), Some(line_no)) = (record.file(), record.line()) {
-      let _ = write!(&mut line, " ({file}:{line_no})");
-    }
+    self.state.record(level, &target, record.args().to_string());
 
-    let _ = write!(&mut line, " - {}", record.args());
-
-    let backend_guard = self.state.backend_guard();
-    match backend_guard.as_ref() {
-      Some(LoggerBackend::Stdout) => dispatch_stdout(level, &line),
-      #[cfg(not(test))]
-      Some(LoggerBackend::Node(tsfn)) => {
-        let _ = tsfn.call(
-          Ok(LogInvocation {
-            level,
-            line: line.clone(),
-          }),
-          ThreadsafeFunctionCallMode::NonBlocking,
-        );
-      }
-      None => dispatch_stdout(level, &line),
-    }
+    self.state.enqueue(LogInvocation {
+      level,
+      line,
+      target,
+      time: SystemTime::now(),
+    });
   }
 
   fn flush(&self) {}
 }
 
+fn format_pretty_line(record: &Record<'_>, level: LoggingLevel) -> String {
+  let mut line = String::new();
+  let _ = write!(&mut line, "[{}]", level.as_str().to_uppercase());
+
+  let target = record.target();
+  if !target.is_empty() {
+    let _ = write!(&mut line, " {target}");
+  }
+
+  if let (Some(file), Some(line_no)) = (record.file(), record.line()) {
+    let _ = write!(&mut line, " ({file}:{line_no})");
+  }
+
+  let _ = write!(&mut line, " - {}", record.args());
+  line
+}
+
+/// One Bunyan-style JSON object per record: `{ "level", "msg", "target", "file", "line", "time" }`,
+/// with `level` numeric per [`bunyan_level`] and `time` an RFC3339 timestamp. `target`/`file`/`line`
+/// are omitted when the record doesn't carry them, same as [`format_pretty_line`] skips them.
+fn format_json_line(record: &Record<'_>) -> String {
+  let mut object = Map::new();
+  object.insert("level".to_string(), json!(bunyan_level(record.level())));
+  object.insert("msg".to_string(), json!(record.args().to_string()));
+  object.insert("time".to_string(), json!(rfc3339_timestamp()));
+
+  let target = record.target();
+  if !target.is_empty() {
+    object.insert("target".to_string(), json!(target));
+  }
+  if let Some(file) = record.file() {
+    object.insert("file".to_string(), json!(file));
+  }
+  if let Some(line_no) = record.line() {
+    object.insert("line".to_string(), json!(line_no));
+  }
+
+  Value::Object(object).to_string()
+}
+
+/// Current UTC time as an RFC3339 timestamp (millisecond precision), e.g.
+/// `2026-07-30T12:34:56.789Z`. Hand-rolled via Howard Hinnant's `civil_from_days` algorithm instead
+/// of pulling in a dedicated date/time crate, since this is the only place that needs calendar math.
+fn rfc3339_timestamp() -> String {
+  let since_epoch = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .unwrap_or_default();
+  let total_secs = since_epoch.as_secs();
+  let millis = since_epoch.subsec_millis();
+  let days = (total_secs / 86_400) as i64;
+  let secs_of_day = total_secs % 86_400;
+  let (year, month, day) = civil_from_days(days);
+  let hour = secs_of_day / 3_600;
+  let minute = (secs_of_day % 3_600) / 60;
+  let second = secs_of_day % 60;
+  format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millis:03}Z")
+}
+
+/// Days-since-1970-01-01 to proleptic Gregorian (year, month, day). See Howard Hinnant's
+/// "chrono-Compatible Low-Level Date Algorithms" for the derivation of this formula.
+fn civil_from_days(days_since_epoch: i64) -> (i64, u32, u32) {
+  let z = days_since_epoch + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097) as u64;
+  let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+  let year_of_era = yoe as i64;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+  let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+  (year, month, day)
+}
+
 fn dispatch_stdout(level: LoggingLevel, line: &str) {
   match level {
     LoggingLevel::Error | LoggingLevel::Warn => eprintln!("{line}"),
     LoggingLevel::Silent => {}
-    LoggingLevel::Info => println!("{line}"),
+    LoggingLevel::Info | LoggingLevel::Debug | LoggingLevel::Trace => println!("{line}"),
   }
 }
 
 fn install_logger() -> Result<Arc<LoggerState>> {
   let state = LOGGER.get_or_init(|| Arc::new(LoggerState::new())).clone();
+  state.ensure_writer_thread();
 
   if log::set_boxed_logger(Box::new(ConsoleLogger::new(state.clone()))).is_ok() {
     log::set_max_level(LevelFilter::from(LoggingLevel::Info));
@@ -230,6 +790,22 @@ fn install_logger() -> Result<Arc<LoggerState>> {
   Ok(state)
 }
 
+/// Blocks until every log record enqueued before this call has been written by the background
+/// writer thread. No-op (returns `false`) if the logger was never installed. Call this before
+/// process exit so buffered diagnostics aren't lost.
+pub fn flush() -> bool {
+  match LOGGER.get() {
+    Some(state) => state.flush(),
+    None => false,
+  }
+}
+
+/// Number of log records dropped because the writer channel was full or disconnected. Useful for
+/// diagnosing whether `compile_project`'s diagnostic output is outrunning the writer thread.
+pub fn dropped_log_count() -> u64 {
+  LOGGER.get().map_or(0, |state| state.dropped_count())
+}
+
 #[cfg(not(test))]
 fn create_threadsafe_logger(env: &Env) -> napi::Result<ThreadsafeFunction<LogInvocation>> {
   let console: JsObject = env.get_global()?.get_named_property("console")?;
@@ -239,7 +815,7 @@ fn create_threadsafe_logger(env: &Env) -> napi::Result<ThreadsafeFunction<LogInv
     let method = match ctx.value.level {
       LoggingLevel::Error => "error",
       LoggingLevel::Warn => "warn",
-      LoggingLevel::Silent | LoggingLevel::Info => "log",
+      LoggingLevel::Silent | LoggingLevel::Info | LoggingLevel::Debug | LoggingLevel::Trace => "log",
     };
     let js_fn: JsFunction = console
       .get_named_property(method)
@@ -264,11 +840,194 @@ mod tests {
       LoggingLevel::Error,
       LoggingLevel::Warn,
       LoggingLevel::Info,
+      LoggingLevel::Debug,
+      LoggingLevel::Trace,
     ] {
       assert_eq!(LoggingLevel::from_index(level.to_index()), level);
     }
   }
 
+  #[test]
+  fn logging_level_level_filter_conversion_covers_every_variant() {
+    assert_eq!(LevelFilter::from(LoggingLevel::Silent), LevelFilter::Off);
+    assert_eq!(LevelFilter::from(LoggingLevel::Error), LevelFilter::Error);
+    assert_eq!(LevelFilter::from(LoggingLevel::Warn), LevelFilter::Warn);
+    assert_eq!(LevelFilter::from(LoggingLevel::Info), LevelFilter::Info);
+    assert_eq!(LevelFilter::from(LoggingLevel::Debug), LevelFilter::Debug);
+    assert_eq!(LevelFilter::from(LoggingLevel::Trace), LevelFilter::Trace);
+  }
+
+  #[test]
+  fn logging_level_from_log_level_preserves_debug_and_trace() {
+    assert_eq!(LoggingLevel::from(Level::Debug), LoggingLevel::Debug);
+    assert_eq!(LoggingLevel::from(Level::Trace), LoggingLevel::Trace);
+  }
+
+  #[test]
+  fn civil_from_days_matches_known_dates() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(18_993), (2022, 1, 1));
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+  }
+
+  #[test]
+  fn bunyan_level_follows_bunyan_conventions() {
+    assert_eq!(bunyan_level(Level::Trace), 10);
+    assert_eq!(bunyan_level(Level::Debug), 20);
+    assert_eq!(bunyan_level(Level::Info), 30);
+    assert_eq!(bunyan_level(Level::Warn), 40);
+    assert_eq!(bunyan_level(Level::Error), 50);
+  }
+
+  #[test]
+  fn format_json_line_emits_bunyan_shaped_object() {
+    let record = Record::builder()
+      .level(Level::Warn)
+      .target("compiler::core")
+      .file(Some("core.rs"))
+      .line(Some(42))
+      .args(format_args!("solc returned a warning"))
+      .build();
+
+    let line = format_json_line(&record);
+    let parsed: Value = serde_json::from_str(&line).expect("valid json");
+    assert_eq!(parsed["level"], 40);
+    assert_eq!(parsed["msg"], "solc returned a warning");
+    assert_eq!(parsed["target"], "compiler::core");
+    assert_eq!(parsed["file"], "core.rs");
+    assert_eq!(parsed["line"], 42);
+    assert!(parsed["time"].as_str().is_some_and(|time| time.ends_with('Z')));
+  }
+
+  #[test]
+  fn log_format_index_roundtrip() {
+    for format in [LogFormat::Pretty, LogFormat::Json] {
+      assert_eq!(LogFormat::from_index(format.to_index()), format);
+    }
+  }
+
+  fn record_at(level: LoggingLevel, target: &str, message: &str, time: SystemTime) -> StoredRecord {
+    StoredRecord {
+      level,
+      target: target.to_string(),
+      message: message.to_string(),
+      time,
+    }
+  }
+
+  #[test]
+  fn log_ring_evicts_oldest_past_capacity() {
+    let mut ring = LogRing::new();
+    ring.configure(2, None);
+    let now = SystemTime::now();
+
+    ring.push(record_at(LoggingLevel::Info, "a", "first", now));
+    ring.push(record_at(LoggingLevel::Info, "a", "second", now));
+    ring.push(record_at(LoggingLevel::Info, "a", "third", now));
+
+    let messages: Vec<_> = ring.entries.iter().map(|record| record.message.as_str()).collect();
+    assert_eq!(messages, vec!["third", "second"]);
+  }
+
+  #[test]
+  fn log_ring_drops_records_older_than_retention() {
+    let mut ring = LogRing::new();
+    ring.configure(10, Some(Duration::from_secs(5)));
+    let now = SystemTime::now();
+    let old = now - Duration::from_secs(10);
+
+    ring.push(record_at(LoggingLevel::Info, "a", "stale", old));
+    ring.push(record_at(LoggingLevel::Info, "a", "fresh", now));
+
+    let messages: Vec<_> = ring.entries.iter().map(|record| record.message.as_str()).collect();
+    assert_eq!(messages, vec!["fresh"]);
+  }
+
+  #[test]
+  fn query_logs_applies_every_predicate() {
+    let mut ring = LogRing::new();
+    ring.configure(10, None);
+    let now = SystemTime::now();
+
+    ring.push(record_at(LoggingLevel::Warn, "compiler::core", "solc warning about shadowing", now));
+    ring.push(record_at(LoggingLevel::Error, "compiler::core", "solc error: syntax", now));
+    ring.push(record_at(LoggingLevel::Warn, "compiler::ast", "unrelated warning", now));
+
+    let state = LoggerState::new();
+    *state.ring.lock().expect("ring lock") = ring;
+
+    let filter = RecordFilter {
+      level: Some(LoggingLevel::Warn),
+      module: Some("core".to_string()),
+      regex: Some(Regex::new("shadowing").expect("regex")),
+      not_before: None,
+      limit: 0,
+    };
+
+    let matches = state.query_ring(&filter);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].message, "solc warning about shadowing");
+  }
+
+  #[test]
+  fn query_logs_respects_limit_and_newest_first_order() {
+    let mut ring = LogRing::new();
+    ring.configure(10, None);
+    let now = SystemTime::now();
+
+    ring.push(record_at(LoggingLevel::Info, "a", "one", now));
+    ring.push(record_at(LoggingLevel::Info, "a", "two", now));
+    ring.push(record_at(LoggingLevel::Info, "a", "three", now));
+
+    let state = LoggerState::new();
+    *state.ring.lock().expect("ring lock") = ring;
+
+    let matches = state.query_ring(&RecordFilter {
+      limit: 2,
+      ..Default::default()
+    });
+    let messages: Vec<_> = matches.iter().map(|record| record.message.as_str()).collect();
+    assert_eq!(messages, vec!["three", "two"]);
+  }
+
+  #[test]
+  fn effective_level_falls_back_to_global_level_without_a_matching_directive() {
+    let state = LoggerState::new();
+    state.update_level(LoggingLevel::Warn);
+    assert_eq!(state.effective_level("compiler::core"), LoggingLevel::Warn);
+  }
+
+  #[test]
+  fn effective_level_prefers_the_longest_matching_directive() {
+    let state = LoggerState::new();
+    state.update_level(LoggingLevel::Info);
+    state.update_target_level("compiler".to_string(), LoggingLevel::Warn);
+    state.update_target_level("compiler::solc".to_string(), LoggingLevel::Error);
+
+    assert_eq!(state.effective_level("compiler::solc::resolver"), LoggingLevel::Error);
+    assert_eq!(state.effective_level("compiler::artifact"), LoggingLevel::Warn);
+    assert_eq!(state.effective_level("ast::cache"), LoggingLevel::Info);
+  }
+
+  #[test]
+  fn update_target_level_overwrites_an_existing_directive_for_the_same_prefix() {
+    let state = LoggerState::new();
+    state.update_target_level("compiler::solc".to_string(), LoggingLevel::Warn);
+    state.update_target_level("compiler::solc".to_string(), LoggingLevel::Error);
+
+    assert_eq!(state.effective_level("compiler::solc"), LoggingLevel::Error);
+    assert_eq!(
+      state
+        .directives
+        .read()
+        .expect("directives lock")
+        .iter()
+        .filter(|(prefix, _)| prefix == "compiler::solc")
+        .count(),
+      1
+    );
+  }
+
   #[test]
   fn ensure_logger_controls_max_level() {
     update_level(LoggingLevel::Silent);
@@ -283,4 +1042,121 @@ mod tests {
     ensure_rust_logger(LoggingLevel::Warn).expect("update logger level");
     assert_eq!(log::max_level(), LevelFilter::Warn);
   }
+
+  #[test]
+  fn rolling_file_appender_writes_lines_without_rolling_under_threshold() {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let path = temp_dir.path().join("compiler.log");
+    let appender = RollingFileAppender::open(path.clone(), 1024, 3).expect("appender");
+
+    appender.write_line("[INFO] first line");
+    appender.write_line("[INFO] second line");
+
+    let contents = std::fs::read_to_string(&path).expect("log contents");
+    assert_eq!(contents, "[INFO] first line\n[INFO] second line\n");
+    assert!(!path.with_file_name("compiler.1.log").exists());
+  }
+
+  #[test]
+  fn rolling_file_appender_rolls_into_fixed_window_and_drops_oldest_archive() {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let path = temp_dir.path().join("compiler.log");
+    let appender = RollingFileAppender::open(path.clone(), 10, 2).expect("appender");
+
+    for line in ["first line is long enough", "second line is long enough too", "third line rolls again"] {
+      appender.write_line(line);
+    }
+
+    let archive_one = path.with_file_name("compiler.1.log");
+    let archive_two = path.with_file_name("compiler.2.log");
+    let archive_three = path.with_file_name("compiler.3.log");
+    assert!(archive_one.exists());
+    assert!(archive_two.exists());
+    assert!(!archive_three.exists());
+    assert_eq!(
+      std::fs::read_to_string(&archive_two).expect("oldest archive"),
+      "first line is long enough\n"
+    );
+    assert_eq!(
+      std::fs::read_to_string(&archive_one).expect("latest archive"),
+      "second line is long enough too\n"
+    );
+    assert_eq!(
+      std::fs::read_to_string(&path).expect("active file"),
+      "third line rolls again\n"
+    );
+  }
+
+  #[test]
+  fn flush_drains_records_enqueued_before_it() {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let path = temp_dir.path().join("writer.log");
+
+    let state = Arc::new(LoggerState::new());
+    state
+      .set_file_backend(&path, 0, 0)
+      .expect("install file backend");
+    state.ensure_writer_thread();
+
+    for message in ["first", "second", "third"] {
+      state.enqueue(LogInvocation {
+        level: LoggingLevel::Info,
+        line: format!("[INFO] {message}"),
+        target: "compiler::core".to_string(),
+        time: SystemTime::now(),
+      });
+    }
+
+    assert!(state.flush());
+    let contents = std::fs::read_to_string(&path).expect("log contents");
+    assert_eq!(contents, "[INFO] first\n[INFO] second\n[INFO] third\n");
+  }
+
+  #[test]
+  fn ensure_writer_thread_is_idempotent_across_repeated_calls() {
+    let state = Arc::new(LoggerState::new());
+    state.ensure_writer_thread();
+    state.ensure_writer_thread();
+
+    state.enqueue(LogInvocation {
+      level: LoggingLevel::Info,
+      line: "[INFO] hello".to_string(),
+      target: "compiler::core".to_string(),
+      time: SystemTime::now(),
+    });
+    assert!(state.flush());
+  }
+
+  #[test]
+  fn enqueue_drops_and_counts_once_the_channel_is_saturated() {
+    let state = LoggerState::new();
+    // No writer thread running, so nothing ever drains the channel: everything past its
+    // capacity is dropped and counted rather than blocking the caller.
+    for i in 0..(LOG_CHANNEL_CAPACITY + 5) {
+      state.enqueue(LogInvocation {
+        level: LoggingLevel::Info,
+        line: format!("[INFO] {i}"),
+        target: "compiler::core".to_string(),
+        time: SystemTime::now(),
+      });
+    }
+
+    assert_eq!(state.dropped_count(), 5);
+  }
+
+  #[test]
+  fn flush_returns_false_when_the_writer_thread_was_never_started() {
+    let state = LoggerState::new();
+    // Fill and drop the only receiver so the channel is disconnected, mirroring what happens
+    // once `ensure_writer_thread` has taken and then lost its receiver.
+    drop(
+      state
+        .writer_receiver
+        .lock()
+        .expect("receiver lock")
+        .take()
+        .expect("receiver present"),
+    );
+    assert!(!state.flush());
+  }
 }
@@ -0,0 +1,129 @@
+use foundry_compilers::solc::Solc;
+use semver::{Version, VersionReq};
+
+use super::errors::{map_err_with_context, Error, Result};
+
+/// Scans `source` for `pragma solidity <req>;` declarations and returns one [`VersionReq`] per
+/// line found. Whitespace-separated comparators (e.g. `>=0.8.0 <0.9.0`) are normalised into the
+/// comma-joined form `semver::VersionReq` expects. Malformed expressions are surfaced as parse
+/// errors rather than silently ignored.
+pub(crate) fn extract_requirements(source: &str) -> Result<Vec<VersionReq>> {
+  let mut requirements = Vec::new();
+  for line in source.lines() {
+    let Some(rest) = line.trim().strip_prefix("pragma solidity") else {
+      continue;
+    };
+    let expr = rest.trim().trim_end_matches(';').trim();
+    if expr.is_empty() {
+      continue;
+    }
+    let normalized = expr.split_whitespace().collect::<Vec<_>>().join(", ");
+    let req = map_err_with_context(
+      VersionReq::parse(&normalized),
+      format!("Failed to parse pragma solidity requirement `{expr}`"),
+    )?;
+    requirements.push(req);
+  }
+  Ok(requirements)
+}
+
+/// Intersects every requirement extracted from a single source into one combined [`VersionReq`]
+/// by pooling their comparators. An empty `requirements` list imposes no constraint (`*`).
+pub(crate) fn merge_requirements(requirements: &[VersionReq]) -> Result<VersionReq> {
+  let joined = requirements
+    .iter()
+    .flat_map(|req| req.comparators.iter().map(ToString::to_string))
+    .collect::<Vec<_>>()
+    .join(", ");
+  if joined.is_empty() {
+    return Ok(VersionReq::STAR);
+  }
+  map_err_with_context(
+    VersionReq::parse(&joined),
+    format!("Failed to combine pragma solidity requirements `{joined}`"),
+  )
+}
+
+/// Resolves the highest installed (or, when `offline_mode` is `false`, installable) solc release
+/// satisfying `requirement`.
+pub(crate) fn resolve_version(requirement: &VersionReq, offline_mode: bool) -> Result<Version> {
+  if offline_mode {
+    return Solc::installed_versions()
+      .into_iter()
+      .filter(|version| requirement.matches(version))
+      .max()
+      .ok_or_else(|| {
+        Error::missing_solc_version(
+          requirement.to_string(),
+          format!(
+            "No installed solc version satisfies pragma requirement `{requirement}` and \
+             offline_mode is enabled; install a matching release first."
+          ),
+        )
+      });
+  }
+
+  map_err_with_context(
+    Solc::find_or_install(requirement).map(|solc| solc.version.clone()),
+    format!("No installed or installable solc version satisfies pragma requirement `{requirement}`"),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extract_requirements_parses_single_line() {
+    let reqs = extract_requirements("pragma solidity ^0.8.20;\ncontract C {}").unwrap();
+    assert_eq!(reqs.len(), 1);
+    assert!(reqs[0].matches(&Version::new(0, 8, 25)));
+    assert!(!reqs[0].matches(&Version::new(0, 9, 0)));
+  }
+
+  #[test]
+  fn extract_requirements_normalizes_space_separated_comparators() {
+    let reqs = extract_requirements("pragma solidity >=0.8.0 <0.9.0;").unwrap();
+    assert_eq!(reqs.len(), 1);
+    assert!(reqs[0].matches(&Version::new(0, 8, 5)));
+    assert!(!reqs[0].matches(&Version::new(0, 9, 0)));
+  }
+
+  #[test]
+  fn extract_requirements_rejects_malformed_expression() {
+    let err = extract_requirements("pragma solidity not-a-version;").unwrap_err();
+    assert!(err.to_string().contains("Failed to parse pragma"));
+  }
+
+  #[test]
+  fn extract_requirements_ignores_sources_without_pragma() {
+    let reqs = extract_requirements("contract C {}").unwrap();
+    assert!(reqs.is_empty());
+  }
+
+  #[test]
+  fn merge_requirements_intersects_multiple_lines() {
+    let reqs = extract_requirements("pragma solidity >=0.8.0;\npragma solidity <0.9.0;").unwrap();
+    let merged = merge_requirements(&reqs).unwrap();
+    assert!(merged.matches(&Version::new(0, 8, 5)));
+    assert!(!merged.matches(&Version::new(0, 9, 0)));
+  }
+
+  #[test]
+  fn merge_requirements_is_unconstrained_when_empty() {
+    let merged = merge_requirements(&[]).unwrap();
+    assert!(merged.matches(&Version::new(0, 8, 30)));
+  }
+
+  #[test]
+  fn resolve_version_offline_reports_missing_solc_requirement() {
+    let requirement = VersionReq::parse(">=99.0.0").unwrap();
+    let err = resolve_version(&requirement, true).unwrap_err();
+    assert_eq!(err.missing_solc_requirement(), Some(">=99.0.0"));
+    assert!(
+      err.to_string().contains("offline_mode is enabled"),
+      "unexpected message: {}",
+      err
+    );
+  }
+}
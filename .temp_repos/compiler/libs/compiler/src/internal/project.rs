@@ -1,11 +1,11 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::SystemTime;
 
 use foundry_compilers::artifacts::{
-  error::Severity, remappings::Remapping, CompilerOutput, Settings,
+  error::Severity, remappings::Remapping, sources::Source as FoundrySource, CompilerOutput, Settings,
 };
 use foundry_compilers::buildinfo::BuildInfo;
 use foundry_compilers::compilers::multi::{
@@ -18,17 +18,28 @@ use foundry_compilers::{
   Project, ProjectBuilder, ProjectPathsConfig,
 };
 use foundry_config::{Config as FoundryConfig, SolcReq};
+use semver::Version;
 
 use crate::internal::config::{CompilerConfig, CompilerConfigOptions, SolcConfig};
 use crate::internal::errors::{map_err_with_context, Error, Result};
+use crate::internal::incremental_cache;
 use crate::internal::path::{canonicalize_path, canonicalize_with_base, ProjectPaths};
-use crate::internal::settings::CompilerSettingsOptions;
+use crate::internal::settings::{CompilerSettingsOptions, OptimizerSettingsOptions};
 use crate::internal::vyper;
 
 #[derive(Clone, Debug)]
 pub enum ProjectLayout {
   Hardhat,
-  Foundry,
+  Foundry {
+    /// Set by [`ProjectContext::detect`] when `root` also has a `hardhat.config.*`, so callers
+    /// that default to Foundry on a tie can still warn that the choice was ambiguous. Always
+    /// `false` when loaded directly through [`FoundryAdapter::load`], which only ever looks for
+    /// Foundry's own markers.
+    ambiguous_with_hardhat: bool,
+  },
+  /// Classic dapptools/forge-std layout: `src`/`lib`/`out` with no `foundry.toml`. See
+  /// [`DapptoolsAdapter::load`].
+  Dapptools,
   Synthetic,
 }
 
@@ -86,18 +97,166 @@ impl ProjectContext {
   pub fn project_paths(&self) -> ProjectPaths {
     ProjectPaths::from_config(&self.paths).with_virtual_sources(self.virtual_sources_dir.as_deref())
   }
+
+  /// Where [`crate::compiler::project_runner::ProjectRunner`] and [`Self::dirty_sources`] persist
+  /// the content-hash/fingerprint sidecar driving incremental rebuilds; derived from the project's
+  /// own cache path so it lives alongside `foundry-compilers`' own timestamp cache without
+  /// colliding with it.
+  pub(crate) fn incremental_cache_manifest_path(&self) -> PathBuf {
+    self.paths.cache.with_file_name("tevm-incremental-cache.json")
+  }
+
+  /// Where the content-addressed `{source, contracts}` JSON fragments [`incremental_cache::store_artifacts`]
+  /// writes live, alongside [`Self::incremental_cache_manifest_path`]'s sidecar manifest.
+  pub(crate) fn incremental_cache_artifacts_dir(&self) -> PathBuf {
+    self.paths.cache.with_file_name("tevm-incremental-artifacts")
+  }
+
+  /// Deletes the incremental cache manifest and its artifact store, so the next compile through
+  /// this context treats every source as dirty and recompiles from scratch. Unlike
+  /// [`CompilerConfig::force_rebuild`], this mutates the cache on disk -- a subsequent build with
+  /// `force_rebuild` left at `false` still recompiles everything, since there's nothing left to
+  /// reuse.
+  pub fn clear_incremental_cache(&self) -> Result<()> {
+    incremental_cache::clear(
+      &self.incremental_cache_manifest_path(),
+      &self.incremental_cache_artifacts_dir(),
+    )
+  }
+
+  /// Lists every build-info JSON file under this project's build-info directory (populated by
+  /// [`ProjectRunner`](crate::compiler::project_runner::ProjectRunner) compiles made with
+  /// `config.build_info_enabled`), sorted by filename -- each is named after its content-derived
+  /// id, so the sort is also oldest/insertion-order-independent and deterministic across runs.
+  /// Returns an empty list rather than an error when the directory doesn't exist yet, e.g. before
+  /// the first such compile.
+  pub fn build_info_files(&self) -> Result<Vec<PathBuf>> {
+    let dir = &self.paths.build_infos;
+    if !dir.exists() {
+      return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir).map_err(|err| {
+      Error::io(format!(
+        "Failed to read build-info directory {}: {err}",
+        dir.display()
+      ))
+    })?;
+
+    let mut files: Vec<PathBuf> = entries
+      .filter_map(|entry| entry.ok())
+      .map(|entry| entry.path())
+      .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+      .collect();
+    files.sort();
+    Ok(files)
+  }
+
+  /// Parses a single build-info file (as listed by [`Self::build_info_files`]) back into the
+  /// exact `CompilerInput`/`CompilerOutput` pair and solc version that produced it, the same
+  /// typed shape [`infer_hardhat_build_info`] already reads to recover a Hardhat project's solc
+  /// settings from its most recent build.
+  pub fn read_build_info(&self, path: &Path) -> Result<BuildInfo<SolcVersionedInput, CompilerOutput>> {
+    map_err_with_context(
+      BuildInfo::read(path),
+      format!("Failed to read build-info file {}", path.display()),
+    )
+  }
+
+  /// Reports which of the project's source files would be recompiled by the next
+  /// [`ProjectRunner`](crate::compiler::project_runner::ProjectRunner) build, without compiling
+  /// anything or mutating the incremental cache: hashes every input file under `self.paths`,
+  /// classifies each against the persisted manifest (a pure read -- see [`incremental_cache::peek`]),
+  /// then expands the dirty set across each source's import-connected component the same way a
+  /// real compile does (see [`incremental_cache::expand_dirty_across_imports`]), since an importer
+  /// can depend on symbols defined in whatever changed even though its own content didn't. A
+  /// solc-version or settings change moves `config`'s fingerprint, which invalidates every manifest
+  /// entry at once -- every input file comes back dirty. Returns every input path unconditionally
+  /// when `config.cache_enabled` is `false` or `config.force_rebuild` is `true`, since neither
+  /// leaves anything to compare against.
+  pub fn dirty_sources(&self, config: &CompilerConfig) -> Result<BTreeSet<PathBuf>> {
+    let input_files = self.paths.input_files();
+    if !config.cache_enabled || config.force_rebuild {
+      return Ok(input_files.into_iter().collect());
+    }
+
+    let mut texts: BTreeMap<String, String> = BTreeMap::new();
+    for path in &input_files {
+      let contents = fs::read_to_string(path).map_err(|err| {
+        Error::io(format!(
+          "Failed to read {} while computing dirty sources: {err}",
+          path.display()
+        ))
+      })?;
+      texts.insert(path.to_string_lossy().into_owned(), contents);
+    }
+
+    let manifest_path = self.incremental_cache_manifest_path();
+    let fingerprint = incremental_cache::config_fingerprint(config);
+    let hash_entries: Vec<(String, String)> = texts
+      .iter()
+      .map(|(path, content)| (path.clone(), FoundrySource::content_hash_of(content)))
+      .collect();
+    let report = incremental_cache::peek(&manifest_path, &fingerprint, &hash_entries);
+    let dirty = incremental_cache::expand_dirty_across_imports(&report.dirty, &texts, &config.remappings);
+
+    Ok(dirty.into_iter().map(PathBuf::from).collect())
+  }
+
+  /// Probes `root` for the marker file each adapter expects and delegates to the matching one,
+  /// falling back to [`create_synthetic_context`] when neither is present -- so a caller that
+  /// doesn't already know whether `root` is a Foundry or Hardhat project (or an ad-hoc directory)
+  /// can still get a working [`ProjectContext`] without hand-wiring the choice itself. When both
+  /// `foundry.toml`/`foundry.json` and a `hardhat.config.js`/`.ts`/`.cjs` exist, Foundry wins (it
+  /// can itself load a bare `src`/`remappings.txt` layout, and a stale Hardhat config left over
+  /// from a migration is more common than the reverse), but the returned layout is marked
+  /// [`ProjectLayout::Foundry`]'s `ambiguous_with_hardhat` so the caller can warn instead of
+  /// silently picking one.
+  pub fn detect(root: &Path) -> Result<(CompilerConfigOptions, ProjectContext)> {
+    let has_foundry = ["foundry.toml", "foundry.json"]
+      .iter()
+      .any(|name| root.join(name).is_file());
+    let has_hardhat = ["hardhat.config.js", "hardhat.config.ts", "hardhat.config.cjs"]
+      .iter()
+      .any(|name| root.join(name).is_file());
+
+    if has_foundry {
+      let (overrides, mut context) = FoundryAdapter::load(root)?;
+      if has_hardhat {
+        context.layout = ProjectLayout::Foundry {
+          ambiguous_with_hardhat: true,
+        };
+      }
+      return Ok((overrides, context));
+    }
+
+    if has_hardhat {
+      return HardhatAdapter::load(root);
+    }
+
+    let context = create_synthetic_context(root)?;
+    Ok((CompilerConfigOptions::default(), context))
+  }
 }
 
 pub fn build_project(
   config: &CompilerConfig,
   context: &ProjectContext,
 ) -> Result<Project<MultiCompiler>> {
+  if !config.supports_base_path() {
+    return Err(Error::new(format!(
+      "solc {} does not support --base-path (requires >=0.6.9); use a newer solcVersion to \
+       compile against a project root",
+      config.solc_version
+    )));
+  }
+
   let mut paths = context.paths.clone();
-  extend_paths_with_config(&mut paths, config);
+  extend_paths_with_config(&mut paths, config, &context.layout);
 
   let mut builder = ProjectBuilder::default().paths(paths);
 
-  builder = builder.set_cached(config.cache_enabled);
+  builder = builder.set_cached(config.cache_enabled && !config.force_rebuild);
   builder = builder.set_offline(config.offline_mode);
   builder = builder.set_no_artifacts(config.no_artifacts);
   builder = builder.set_build_info(config.build_info_enabled);
@@ -225,6 +384,7 @@ pub fn default_cache_dir() -> PathBuf {
 fn extend_paths_with_config(
   paths: &mut ProjectPathsConfig<MultiCompilerLanguage>,
   config: &CompilerConfig,
+  layout: &ProjectLayout,
 ) {
   if !config.library_paths.is_empty() {
     let mut libraries: BTreeSet<PathBuf> = paths.libraries.iter().cloned().collect::<BTreeSet<_>>();
@@ -241,6 +401,157 @@ fn extend_paths_with_config(
   for path in &config.allow_paths {
     paths.allowed_paths.insert(path.clone());
   }
+
+  // Synthetic contexts are built with `.no_libs()` before `config.library_paths` is known, so
+  // this is the first point remappings can be auto-discovered for them; Hardhat discovers its own
+  // at `HardhatAdapter::load` time and Foundry/Dapptools already have an explicit remappings
+  // source, so neither should have auto-discovery clobber it here.
+  if matches!(layout, ProjectLayout::Synthetic) && paths.remappings.is_empty() {
+    let mut library_dirs = vec![paths.root.join("node_modules"), paths.root.join("lib")];
+    library_dirs.extend(config.library_paths.iter().cloned());
+    let discovered = discover_remappings(&paths.root, &library_dirs);
+    if !discovered.is_empty() {
+      paths.remappings = discovered;
+    }
+  }
+}
+
+/// Walks each of `library_dirs` (resolved against `root` when relative) one or two levels deep --
+/// one for a plain `lib/my-package` layout, two for a scoped `node_modules/@scope/my-package` one
+/// -- and emits a `<pkg-name>/=<path>/` [`Remapping`] for every package directory that contains
+/// Solidity sources, so bare imports like `@openzeppelin/contracts/...` resolve the same way
+/// Foundry's `remappings.txt`/`foundry.toml` would. A package's own `remappings.txt`, if present,
+/// is honoured verbatim (with relative targets resolved against that package's directory) instead
+/// of the default guess. Entries are deduplicated by their rendered `prefix=path` text, first
+/// write wins, so an earlier `library_dirs` entry takes precedence over a later one.
+pub fn discover_remappings(root: &Path, library_dirs: &[PathBuf]) -> Vec<Remapping> {
+  let mut discovered: BTreeMap<String, Remapping> = BTreeMap::new();
+
+  for library_dir in library_dirs {
+    let library_dir = canonicalize_with_base(root, library_dir);
+    let Ok(entries) = fs::read_dir(&library_dir) else {
+      continue;
+    };
+
+    for entry in entries.flatten() {
+      if !entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false) {
+        continue;
+      }
+
+      let path = entry.path();
+      let name = entry.file_name().to_string_lossy().into_owned();
+
+      if name.starts_with('@') {
+        let Ok(scoped_entries) = fs::read_dir(&path) else {
+          continue;
+        };
+        for scoped_entry in scoped_entries.flatten() {
+          if !scoped_entry
+            .file_type()
+            .map(|file_type| file_type.is_dir())
+            .unwrap_or(false)
+          {
+            continue;
+          }
+          let package_name = format!("{name}/{}", scoped_entry.file_name().to_string_lossy());
+          collect_package_remapping(&scoped_entry.path(), &package_name, &mut discovered);
+        }
+        continue;
+      }
+
+      collect_package_remapping(&path, &name, &mut discovered);
+    }
+  }
+
+  discovered.into_values().collect()
+}
+
+fn collect_package_remapping(
+  package_dir: &Path,
+  package_name: &str,
+  discovered: &mut BTreeMap<String, Remapping>,
+) {
+  if let Some(own_remappings) = read_package_remappings(package_dir) {
+    for remapping in own_remappings {
+      discovered.entry(remapping.to_string()).or_insert(remapping);
+    }
+    return;
+  }
+
+  let Some(source_root) = package_source_root(package_dir) else {
+    return;
+  };
+  if !contains_solidity_source(&source_root) {
+    return;
+  }
+
+  let target = format!(
+    "{}/",
+    source_root.to_string_lossy().replace('\\', "/").trim_end_matches('/')
+  );
+  if let Ok(remapping) = Remapping::from_str(&format!("{package_name}/={target}")) {
+    discovered.entry(remapping.to_string()).or_insert(remapping);
+  }
+}
+
+/// Prefers a package's `src`/`contracts` subdir over its own root, matching how Foundry and
+/// Hardhat packages are usually laid out (e.g. `@openzeppelin/contracts/contracts`).
+fn package_source_root(package_dir: &Path) -> Option<PathBuf> {
+  for candidate in ["src", "contracts"] {
+    let nested = package_dir.join(candidate);
+    if nested.is_dir() {
+      return Some(nested);
+    }
+  }
+  package_dir.is_dir().then(|| package_dir.to_path_buf())
+}
+
+fn contains_solidity_source(dir: &Path) -> bool {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return false;
+  };
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if path.extension().and_then(|ext| ext.to_str()) == Some("sol") {
+      return true;
+    }
+    if path.is_dir() {
+      if let Ok(nested) = fs::read_dir(&path) {
+        let has_source = nested.flatten().any(|nested_entry| {
+          nested_entry.path().extension().and_then(|ext| ext.to_str()) == Some("sol")
+        });
+        if has_source {
+          return true;
+        }
+      }
+    }
+  }
+  false
+}
+
+/// Reads a package's own `remappings.txt`, if present, resolving relative targets against
+/// `package_dir` so the caller doesn't need to know the package's internal layout.
+pub(crate) fn read_package_remappings(package_dir: &Path) -> Option<Vec<Remapping>> {
+  let contents = fs::read_to_string(package_dir.join("remappings.txt")).ok()?;
+  let mut remappings = Vec::new();
+  for line in contents.lines() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let Some((prefix, target)) = line.split_once('=') else {
+      continue;
+    };
+    let resolved_target = canonicalize_with_base(package_dir, Path::new(target));
+    let target_str = format!(
+      "{}/",
+      resolved_target.to_string_lossy().replace('\\', "/").trim_end_matches('/')
+    );
+    if let Ok(remapping) = Remapping::from_str(&format!("{prefix}={target_str}")) {
+      remappings.push(remapping);
+    }
+  }
+  Some(remappings)
 }
 
 fn collect_vyper_search_paths(
@@ -376,7 +687,9 @@ impl FoundryAdapter {
       .build_with_root::<MultiCompilerLanguage>(&config_paths.root);
     paths.slash_paths();
     let context = ProjectContext {
-      layout: ProjectLayout::Foundry,
+      layout: ProjectLayout::Foundry {
+        ambiguous_with_hardhat: false,
+      },
       root: base_dir,
       paths,
       virtual_sources_dir: None,
@@ -439,6 +752,15 @@ impl HardhatAdapter {
         .collect::<Vec<_>>(),
     );
 
+    // Hardhat never populates remappings itself, so bare imports like
+    // `@openzeppelin/contracts/...` would otherwise only resolve via node_modules lookup at solc
+    // level; discover them from the same library directories `overrides.library_paths` just
+    // reported so resolution matches Foundry's behaviour.
+    let discovered_remappings = discover_remappings(&paths.root, &paths.libraries);
+    if !discovered_remappings.is_empty() {
+      overrides.remappings = Some(discovered_remappings);
+    }
+
     let context = ProjectContext {
       layout: ProjectLayout::Hardhat,
       root: paths.root.clone(),
@@ -450,6 +772,113 @@ impl HardhatAdapter {
   }
 }
 
+pub struct DapptoolsAdapter;
+
+impl DapptoolsAdapter {
+  /// Loads a classic dapptools/forge-std layout: sources under `src`, libraries under `lib`,
+  /// artifacts under `out`, and tests colocated with sources (dapptools has no separate `test`
+  /// directory -- `*.t.sol` files live alongside the contracts they exercise). Unlike
+  /// [`FoundryAdapter::load`], there is no persisted build-info to read a solc version back out
+  /// of, so [`read_dapprc`] is consulted for `DAPP_SOLC_VERSION`/`DAPP_BUILD_OPTIMIZE` (env vars
+  /// win over a `.dapprc` in `root`); when neither is set, `overrides.solc.version` is left unset
+  /// so the caller's configured default -- or the import-graph version resolver -- decides.
+  pub fn load(root: &Path) -> Result<(CompilerConfigOptions, ProjectContext)> {
+    let root = canonicalize_path(root);
+    let cache_file = root.join("cache").join(SOLIDITY_FILES_CACHE_FILENAME);
+    let artifacts_dir = root.join("out");
+    let build_info_dir = artifacts_dir.join("build-info");
+    let sources_dir = root.join("src");
+    let library_dir = root.join("lib");
+    let scripts_dir = root.join("script");
+
+    let mut paths = ProjectPathsConfig::builder()
+      .root(&root)
+      .cache(&cache_file)
+      .artifacts(&artifacts_dir)
+      .build_infos(&build_info_dir)
+      .sources(&sources_dir)
+      .tests(&sources_dir)
+      .scripts(&scripts_dir)
+      .libs(vec![library_dir])
+      .build_with_root::<MultiCompilerLanguage>(&root);
+    paths.slash_paths();
+
+    let mut overrides = CompilerConfigOptions::default();
+    overrides.cache_enabled = Some(true);
+    overrides.build_info_enabled = Some(false);
+    overrides.no_artifacts = Some(false);
+
+    let dapprc = read_dapprc(&root);
+    if let Some(version) = dapprc.solc_version {
+      overrides.solc.version = Some(version);
+    }
+    if let Some(enabled) = dapprc.build_optimize {
+      overrides.solc.settings = Some(CompilerSettingsOptions {
+        optimizer: Some(OptimizerSettingsOptions {
+          enabled: Some(enabled),
+          ..Default::default()
+        }),
+        ..Default::default()
+      });
+    }
+
+    overrides.library_paths = Some(
+      paths
+        .libraries
+        .iter()
+        .map(|p| canonicalize_with_base(&paths.root, p))
+        .collect::<Vec<_>>(),
+    );
+
+    let context = ProjectContext {
+      layout: ProjectLayout::Dapptools,
+      root,
+      paths,
+      virtual_sources_dir: None,
+    };
+
+    Ok((overrides, context))
+  }
+}
+
+/// `.dapprc`-or-environment derived overrides. dapptools' own `dapp` CLI sources `.dapprc` into
+/// the shell before reading these, so a real `export DAPP_SOLC_VERSION=...` already in the
+/// environment takes precedence over the file, matching that behaviour.
+#[derive(Default)]
+struct DapprcSettings {
+  solc_version: Option<Version>,
+  build_optimize: Option<bool>,
+}
+
+fn read_dapprc(root: &Path) -> DapprcSettings {
+  let mut file_values = std::collections::BTreeMap::new();
+  if let Ok(contents) = fs::read_to_string(root.join(".dapprc")) {
+    for line in contents.lines() {
+      let line = line.trim();
+      let line = line.strip_prefix("export ").unwrap_or(line);
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      if let Some((key, value)) = line.split_once('=') {
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        file_values.insert(key.trim().to_string(), value.to_string());
+      }
+    }
+  }
+
+  let lookup = |name: &str| std::env::var(name).ok().or_else(|| file_values.get(name).cloned());
+
+  let solc_version = lookup("DAPP_SOLC_VERSION")
+    .and_then(|value| Version::parse(value.trim_start_matches('v')).ok());
+  let build_optimize = lookup("DAPP_BUILD_OPTIMIZE")
+    .map(|value| matches!(value.as_str(), "1" | "true" | "yes"));
+
+  DapprcSettings {
+    solc_version,
+    build_optimize,
+  }
+}
+
 fn infer_hardhat_build_info(
   paths: &ProjectPathsConfig<MultiCompilerLanguage>,
 ) -> Option<(SolcConfig, CliSettingsData)> {
@@ -495,6 +924,7 @@ fn infer_hardhat_build_info(
     version: build_info.solc_version.clone(),
     settings: build_info.input.input.settings.clone(),
     language: build_info.input.input.language,
+    path: None,
   };
 
   let cli_settings = CliSettingsData {
@@ -538,6 +968,21 @@ mod tests {
     );
   }
 
+  #[test]
+  fn build_project_rejects_solc_predating_base_path() {
+    let temp = tempdir().expect("tempdir");
+    let context = create_synthetic_context(temp.path()).expect("context");
+    let mut config = CompilerConfig::default();
+    config.solc_version = semver::Version::new(0, 6, 8);
+
+    let err = build_project(&config, &context).unwrap_err();
+    assert!(
+      err.to_string().contains("does not support --base-path"),
+      "unexpected error: {}",
+      err
+    );
+  }
+
   fn assert_contains_path(values: &[String], expected: &Path) {
     let expected = canonicalize_path(expected);
     assert!(
@@ -564,6 +1009,36 @@ mod tests {
     assert_eq!(resolved, vec![target.canonicalize().unwrap()]);
   }
 
+  #[test]
+  fn dirty_sources_reports_new_files_then_reuses_seeded_ones() {
+    let temp = tempdir().expect("tempdir");
+    let context = create_synthetic_context(temp.path()).expect("context");
+    let source_path = context.root.join("A.sol");
+    fs::write(&source_path, "contract A {}").expect("write source");
+
+    let config = CompilerConfig::default();
+    let first = context.dirty_sources(&config).expect("first dirty_sources");
+    assert_eq!(first, BTreeSet::from([source_path.clone()]));
+
+    let content = fs::read_to_string(&source_path).expect("read source");
+    incremental_cache::evaluate(
+      &context.incremental_cache_manifest_path(),
+      &incremental_cache::config_fingerprint(&config),
+      &[(
+        source_path.to_string_lossy().into_owned(),
+        FoundrySource::content_hash_of(&content),
+      )],
+    )
+    .expect("seed manifest");
+
+    let second = context.dirty_sources(&config).expect("second dirty_sources");
+    assert!(
+      second.is_empty(),
+      "expected no dirty sources once the manifest is seeded, got {:?}",
+      second
+    );
+  }
+
   #[test]
   fn virtual_source_path_prepares_directory() {
     let temp = tempdir().expect("tempdir");
@@ -655,6 +1130,72 @@ mod tests {
     );
   }
 
+  #[test]
+  fn hardhat_adapter_discovers_remappings_from_node_modules() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in [
+      "artifacts/build-info",
+      "cache",
+      "contracts",
+      "script",
+      "scripts",
+      "test",
+    ] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    let package = root.join("node_modules/@openzeppelin/contracts/contracts");
+    std::fs::create_dir_all(&package).expect("create dir");
+    std::fs::write(package.join("ERC20.sol"), "contract ERC20 {}").expect("write source");
+
+    let (overrides, _) = HardhatAdapter::load(root).expect("hardhat context");
+    let remappings = overrides.remappings.expect("discovered remappings");
+    assert!(remappings.iter().any(|r| r.to_string().starts_with("@openzeppelin/contracts/=")));
+  }
+
+  #[test]
+  fn discover_remappings_honours_a_packages_own_remappings_txt() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let package = root.join("lib/mypkg");
+    std::fs::create_dir_all(package.join("source")).expect("create dir");
+    std::fs::write(package.join("source").join("A.sol"), "contract A {}").expect("write source");
+    std::fs::write(package.join("remappings.txt"), "mypkg/=source/\n").expect("remappings.txt");
+
+    let remappings = discover_remappings(root, &[root.join("lib")]);
+    assert_eq!(remappings.len(), 1);
+    assert_eq!(remappings[0].to_string(), format!("mypkg/={}", package.join("source").to_string_lossy() + "/"));
+  }
+
+  #[test]
+  fn discover_remappings_skips_packages_without_solidity_sources() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("lib/not-a-contract-pkg")).expect("create dir");
+
+    let remappings = discover_remappings(root, &[root.join("lib")]);
+    assert!(remappings.is_empty());
+  }
+
+  #[test]
+  fn extend_paths_with_config_discovers_remappings_for_synthetic_layout() {
+    let temp = tempdir().expect("tempdir");
+    let context = create_synthetic_context(temp.path()).expect("context");
+    let package = context.root.join("node_modules/forge-std/src");
+    std::fs::create_dir_all(&package).expect("create dir");
+    std::fs::write(package.join("Test.sol"), "contract Test {}").expect("write source");
+
+    let config = CompilerConfig::default();
+    let mut paths = context.paths.clone();
+    extend_paths_with_config(&mut paths, &config, &context.layout);
+
+    assert!(
+      paths.remappings.iter().any(|r| r.to_string().starts_with("forge-std/=")),
+      "expected a forge-std remapping, got {:?}",
+      paths.remappings
+    );
+  }
+
   #[test]
   fn foundry_project_context_reports_expected_paths() {
     let temp = tempdir().expect("tempdir");
@@ -691,4 +1232,125 @@ mod tests {
       "foundry projects should not expose virtual sources"
     );
   }
+
+  #[test]
+  fn dapptools_project_context_reports_expected_paths() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in ["src", "lib"] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+
+    let (overrides, context) = DapptoolsAdapter::load(root).expect("dapptools context");
+    assert!(matches!(context.layout, ProjectLayout::Dapptools));
+
+    let project_paths = context.project_paths();
+    let canonical_root = canonicalize_path(root);
+    let expected_cache = canonical_root
+      .join("cache")
+      .join(SOLIDITY_FILES_CACHE_FILENAME);
+    let expected_artifacts = canonical_root.join("out");
+    let expected_build_infos = canonical_root.join("out/build-info");
+    let expected_sources = canonical_root.join("src");
+    let expected_library = canonical_root.join("lib");
+
+    assert_path_eq(&project_paths.root, canonical_root.as_path());
+    assert_path_eq(&project_paths.cache, expected_cache.as_path());
+    assert_path_eq(&project_paths.artifacts, expected_artifacts.as_path());
+    assert_path_eq(&project_paths.build_infos, expected_build_infos.as_path());
+    assert_path_eq(&project_paths.sources, expected_sources.as_path());
+    assert_path_eq(&project_paths.tests, expected_sources.as_path());
+
+    assert_contains_path(&project_paths.libraries, expected_library.as_path());
+    assert!(
+      project_paths.virtual_sources.is_none(),
+      "dapptools projects should not expose virtual sources"
+    );
+    assert!(overrides.solc.version.is_none());
+  }
+
+  #[test]
+  fn dapptools_project_context_reads_dapprc_solc_version_and_optimizer() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in ["src", "lib"] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    std::fs::write(
+      root.join(".dapprc"),
+      "export DAPP_SOLC_VERSION=0.8.19\nDAPP_BUILD_OPTIMIZE=1\n",
+    )
+    .expect(".dapprc");
+
+    let (overrides, _) = DapptoolsAdapter::load(root).expect("dapptools context");
+    assert_eq!(overrides.solc.version, Some(Version::new(0, 8, 19)));
+    assert_eq!(
+      overrides
+        .solc
+        .settings
+        .as_ref()
+        .and_then(|settings| settings.optimizer.as_ref())
+        .and_then(|optimizer| optimizer.enabled),
+      Some(true)
+    );
+  }
+
+  #[test]
+  fn detect_picks_foundry_when_only_foundry_toml_is_present() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in ["src", "test", "script", "lib"] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    std::fs::write(root.join("foundry.toml"), "[profile.default]\n").expect("foundry.toml");
+
+    let (_, context) = ProjectContext::detect(root).expect("detected context");
+    assert!(matches!(
+      context.layout,
+      ProjectLayout::Foundry {
+        ambiguous_with_hardhat: false
+      }
+    ));
+  }
+
+  #[test]
+  fn detect_picks_hardhat_when_only_a_hardhat_config_is_present() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in ["artifacts/build-info", "cache", "contracts"] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    std::fs::write(root.join("hardhat.config.ts"), "export default {};\n")
+      .expect("hardhat.config.ts");
+
+    let (_, context) = ProjectContext::detect(root).expect("detected context");
+    assert!(matches!(context.layout, ProjectLayout::Hardhat));
+  }
+
+  #[test]
+  fn detect_falls_back_to_synthetic_when_neither_marker_is_present() {
+    let temp = tempdir().expect("tempdir");
+    let (_, context) = ProjectContext::detect(temp.path()).expect("detected context");
+    assert!(matches!(context.layout, ProjectLayout::Synthetic));
+  }
+
+  #[test]
+  fn detect_prefers_foundry_but_flags_the_ambiguity_when_both_markers_exist() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    for dir in ["src", "test", "script", "lib"] {
+      std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    std::fs::write(root.join("foundry.toml"), "[profile.default]\n").expect("foundry.toml");
+    std::fs::write(root.join("hardhat.config.js"), "module.exports = {};\n")
+      .expect("hardhat.config.js");
+
+    let (_, context) = ProjectContext::detect(root).expect("detected context");
+    assert!(matches!(
+      context.layout,
+      ProjectLayout::Foundry {
+        ambiguous_with_hardhat: true
+      }
+    ));
+  }
 }
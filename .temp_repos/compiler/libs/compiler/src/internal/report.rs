@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// Structured progress events emitted during compilation, mirroring foundry-compilers' `report`
+/// module. [`crate::compiler::core::compile_files`] and [`crate::compiler::core::compile_project`]
+/// emit these through an optional [`Reporter`] so a CLI or editor extension can render a live
+/// progress bar instead of only seeing the final `CompileOutput` once everything has finished.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressEvent {
+  /// The solc/vyper version selected for this compilation.
+  SolcVersionSelected { version: String },
+  /// A batch of `file_count` source files is about to be compiled.
+  GroupStarted { file_count: usize },
+  /// The batch started by the matching `GroupStarted` has finished.
+  GroupFinished,
+  /// `path` was found unchanged since the last compile and its cached artifacts were reused
+  /// instead of invoking solc again. Derived from the same `dirty_paths`/`reused_paths` the
+  /// incremental cache populates on `CompileOutput` for both `compile_files` and
+  /// `compile_project`.
+  CacheHit { path: String },
+  /// `path` changed (or nothing was cached for it yet) and was recompiled. Same caveat as
+  /// [`Self::CacheHit`].
+  CacheMiss { path: String },
+}
+
+/// Callback invoked for each [`ProgressEvent`] a compilation call emits. Wraps the closure in an
+/// `Arc` so it stays cheap to clone across the worker threads `compile_many` and the source-map
+/// version-bucket pipeline in [`crate::compiler::project_runner`] spawn.
+#[derive(Clone)]
+pub struct Reporter(Arc<dyn Fn(ProgressEvent) + Send + Sync>);
+
+impl Reporter {
+  pub fn new(callback: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+    Self(Arc::new(callback))
+  }
+
+  pub fn report(&self, event: ProgressEvent) {
+    (self.0)(event);
+  }
+}
+
+/// Flat, N-API-friendly projection of [`ProgressEvent`]: `kind` names the variant and only the
+/// fields relevant to it are populated, mirroring how
+/// [`crate::compiler::output::CompileOutputJson`] flattens `CompileOutput`.
+#[napi(object, js_name = "ProgressEvent")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressEventJson {
+  pub kind: String,
+  pub version: Option<String>,
+  pub file_count: Option<u32>,
+  pub path: Option<String>,
+}
+
+impl From<ProgressEvent> for ProgressEventJson {
+  fn from(event: ProgressEvent) -> Self {
+    match event {
+      ProgressEvent::SolcVersionSelected { version } => Self {
+        kind: "solcVersionSelected".to_string(),
+        version: Some(version),
+        file_count: None,
+        path: None,
+      },
+      ProgressEvent::GroupStarted { file_count } => Self {
+        kind: "groupStarted".to_string(),
+        version: None,
+        file_count: Some(file_count as u32),
+        path: None,
+      },
+      ProgressEvent::GroupFinished => Self {
+        kind: "groupFinished".to_string(),
+        version: None,
+        file_count: None,
+        path: None,
+      },
+      ProgressEvent::CacheHit { path } => Self {
+        kind: "cacheHit".to_string(),
+        version: None,
+        file_count: None,
+        path: Some(path),
+      },
+      ProgressEvent::CacheMiss { path } => Self {
+        kind: "cacheMiss".to_string(),
+        version: None,
+        file_count: None,
+        path: Some(path),
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::Mutex;
+
+  #[test]
+  fn reporter_forwards_events_to_the_callback() {
+    let seen: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    let reporter = Reporter::new(move |event| recorder.lock().unwrap().push(event));
+
+    reporter.report(ProgressEvent::GroupStarted { file_count: 3 });
+    reporter.report(ProgressEvent::GroupFinished);
+
+    let recorded = seen.lock().unwrap();
+    assert_eq!(
+      *recorded,
+      vec![
+        ProgressEvent::GroupStarted { file_count: 3 },
+        ProgressEvent::GroupFinished
+      ]
+    );
+  }
+
+  #[test]
+  fn progress_event_json_sets_only_the_relevant_fields() {
+    let json: ProgressEventJson = ProgressEvent::CacheHit {
+      path: "src/Token.sol".to_string(),
+    }
+    .into();
+    assert_eq!(json.kind, "cacheHit");
+    assert_eq!(json.path.as_deref(), Some("src/Token.sol"));
+    assert!(json.version.is_none());
+    assert!(json.file_count.is_none());
+  }
+}
@@ -0,0 +1,343 @@
+use foundry_compilers::artifacts::Settings;
+use semver::Version;
+
+use crate::internal::config::CompilerRestriction;
+use crate::internal::errors::{Error, Result};
+use crate::internal::settings::EvmVersion;
+
+/// Restrictions in `restrictions` whose `path_glob` matches `path`.
+pub(crate) fn matching_restrictions<'a>(
+  path: &str,
+  restrictions: &'a [CompilerRestriction],
+) -> Result<Vec<&'a CompilerRestriction>> {
+  restrictions
+    .iter()
+    .filter_map(|restriction| match path_matches(restriction, path) {
+      Ok(true) => Some(Ok(restriction)),
+      Ok(false) => None,
+      Err(err) => Some(Err(err)),
+    })
+    .collect()
+}
+
+fn path_matches(restriction: &CompilerRestriction, path: &str) -> Result<bool> {
+  let pattern = glob::Pattern::new(&restriction.path_glob).map_err(|err| {
+    Error::new(format!(
+      "Invalid compiler restriction glob `{}`: {err}",
+      restriction.path_glob
+    ))
+  })?;
+  Ok(pattern.matches(path))
+}
+
+/// Reads back the resolved EVM version from a finalised [`Settings`] via its serialised form,
+/// since `Settings::evm_version` is Foundry's own enum rather than this crate's
+/// [`EvmVersion`](crate::internal::settings::EvmVersion).
+fn resolved_evm_version(settings: &Settings) -> Option<EvmVersion> {
+  let value = serde_json::to_value(settings).ok()?;
+  value.get("evmVersion")?.as_str()?.parse().ok()
+}
+
+/// Validates a single restriction against the resolved `version`/`settings` pair, describing the
+/// offending bound in the error message.
+fn validate_one(
+  restriction: &CompilerRestriction,
+  path: &str,
+  version: &Version,
+  settings: &Settings,
+) -> Result<()> {
+  if let Some(req) = &restriction.version_req {
+    if !req.matches(version) {
+      return Err(Error::new(format!(
+        "`{path}` is restricted to solc `{req}` by `{}`, but the resolved version is {version}",
+        restriction.path_glob
+      )));
+    }
+  }
+
+  let runs = settings.optimizer.runs.map(|runs| runs as u64);
+  if let Some(min) = restriction.min_optimizer_runs {
+    if runs.map_or(true, |runs| runs < min) {
+      return Err(Error::new(format!(
+        "`{path}` is restricted to at least {min} optimizer runs by `{}`, but the resolved runs are {runs:?}",
+        restriction.path_glob
+      )));
+    }
+  }
+  if let Some(max) = restriction.max_optimizer_runs {
+    if runs.map_or(false, |runs| runs > max) {
+      return Err(Error::new(format!(
+        "`{path}` is restricted to at most {max} optimizer runs by `{}`, but the resolved runs are {runs:?}",
+        restriction.path_glob
+      )));
+    }
+  }
+
+  let evm_version = resolved_evm_version(settings);
+  if let Some(min) = restriction.min_evm_version {
+    if evm_version.map_or(true, |evm| evm < min) {
+      return Err(Error::new(format!(
+        "`{path}` is restricted to evm version `{min:?}` or newer by `{}`, but the resolved evm version is {evm_version:?}",
+        restriction.path_glob
+      )));
+    }
+  }
+  if let Some(max) = restriction.max_evm_version {
+    if evm_version.map_or(false, |evm| evm > max) {
+      return Err(Error::new(format!(
+        "`{path}` is restricted to evm version `{max:?}` or older by `{}`, but the resolved evm version is {evm_version:?}",
+        restriction.path_glob
+      )));
+    }
+  }
+
+  if let Some(expected) = restriction.via_ir {
+    let actual = settings.via_ir.unwrap_or(false);
+    if actual != expected {
+      return Err(Error::new(format!(
+        "`{path}` requires viaIR={expected} by `{}`, but the resolved settings use viaIR={actual}",
+        restriction.path_glob
+      )));
+    }
+  }
+
+  Ok(())
+}
+
+/// Applies every restriction's exact-valued bounds (`viaIr`, `minOptimizerRuns`/
+/// `maxOptimizerRuns`) onto a clone of `settings`, raising/lowering values that fall outside a
+/// bound rather than only rejecting them. Two restrictions that pin `viaIr` to different values
+/// are a genuine conflict and still fail loudly. Version requirements and EVM version bounds are
+/// ranges rather than single values, so they remain validation-only (see [`validate_one`]) — there
+/// is no single "pin" value to clamp a version range down to.
+pub(crate) fn clamp_settings(settings: &Settings, group: &RestrictionGroup) -> Result<Settings> {
+  let mut clamped = settings.clone();
+
+  for restriction in &group.restrictions {
+    if let Some(expected) = restriction.via_ir {
+      match clamped.via_ir {
+        Some(actual) if actual != expected => {
+          return Err(Error::new(format!(
+            "Restriction `{}` requires viaIR={expected}, but another matching restriction already \
+             pinned viaIR={actual}",
+            restriction.path_glob
+          )));
+        }
+        _ => clamped.via_ir = Some(expected),
+      }
+    }
+
+    if let Some(min) = restriction.min_optimizer_runs {
+      let below_min = clamped.optimizer.runs.map_or(true, |runs| (runs as u64) < min);
+      if below_min {
+        clamped.optimizer.runs = Some(min as usize);
+      }
+    }
+    if let Some(max) = restriction.max_optimizer_runs {
+      let above_max = clamped.optimizer.runs.map_or(false, |runs| (runs as u64) > max);
+      if above_max {
+        clamped.optimizer.runs = Some(max as usize);
+      }
+    }
+  }
+
+  Ok(clamped)
+}
+
+/// Validates `path`'s resolved solc version/settings against every restriction whose glob
+/// matches it, failing on the first restriction that cannot be satisfied.
+pub(crate) fn ensure_satisfied(
+  path: &str,
+  version: &Version,
+  settings: &Settings,
+  restrictions: &[CompilerRestriction],
+) -> Result<()> {
+  for restriction in matching_restrictions(path, restrictions)? {
+    validate_one(restriction, path, version, settings)?;
+  }
+  Ok(())
+}
+
+/// A set of files that share the exact same applicable restrictions, together with that shared
+/// restriction list. Grouping by identical restriction sets is a conservative but always-correct
+/// way to keep incompatible files apart: two files only ever land in the same group when every
+/// restriction either applies to both or neither, so the group's effective constraint really is
+/// the intersection of `restrictions`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RestrictionGroup {
+  pub paths: Vec<String>,
+  pub restrictions: Vec<CompilerRestriction>,
+}
+
+/// Partitions `paths` into [`RestrictionGroup`]s so that files compiled together always share
+/// the exact same set of applicable restrictions.
+pub(crate) fn group_paths(
+  paths: &[String],
+  restrictions: &[CompilerRestriction],
+) -> Result<Vec<RestrictionGroup>> {
+  let mut groups: Vec<(Vec<usize>, RestrictionGroup)> = Vec::new();
+
+  for path in paths {
+    let applicable = matching_restrictions(path, restrictions)?;
+    let indices: Vec<usize> = applicable
+      .iter()
+      .map(|restriction| {
+        restrictions
+          .iter()
+          .position(|candidate| std::ptr::eq(candidate, *restriction))
+          .expect("matched restriction must come from the provided slice")
+      })
+      .collect();
+
+    match groups.iter_mut().find(|(existing, _)| *existing == indices) {
+      Some((_, group)) => group.paths.push(path.clone()),
+      None => groups.push((
+        indices,
+        RestrictionGroup {
+          paths: vec![path.clone()],
+          restrictions: applicable.into_iter().cloned().collect(),
+        },
+      )),
+    }
+  }
+
+  Ok(groups.into_iter().map(|(_, group)| group).collect())
+}
+
+/// Validates a [`RestrictionGroup`] against the solc version/settings a [`CompilerConfig`] has
+/// already resolved. Today every group is checked against the same shared configuration (there is
+/// no per-group solc re-selection yet), so this fails loudly for a group that cannot be satisfied
+/// rather than silently compiling it with the wrong compiler.
+///
+/// [`CompilerConfig`]: crate::internal::config::CompilerConfig
+pub(crate) fn ensure_group_satisfied(
+  version: &Version,
+  settings: &Settings,
+  group: &RestrictionGroup,
+) -> Result<()> {
+  let representative = group
+    .paths
+    .first()
+    .map(String::as_str)
+    .unwrap_or("<unknown>");
+  for restriction in &group.restrictions {
+    validate_one(restriction, representative, version, settings)?;
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn restriction(path_glob: &str) -> CompilerRestriction {
+    CompilerRestriction {
+      path_glob: path_glob.to_string(),
+      version_req: None,
+      min_optimizer_runs: None,
+      max_optimizer_runs: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      via_ir: None,
+    }
+  }
+
+  #[test]
+  fn matching_restrictions_filters_by_glob() {
+    let restrictions = vec![restriction("**/src/core/*.sol"), restriction("**/*.sol")];
+    let matched = matching_restrictions("/project/src/core/Vault.sol", &restrictions)
+      .expect("match restrictions");
+    assert_eq!(matched.len(), 2);
+
+    let matched = matching_restrictions("/project/src/Token.sol", &restrictions)
+      .expect("match restrictions");
+    assert_eq!(matched.len(), 1);
+  }
+
+  #[test]
+  fn ensure_satisfied_rejects_version_outside_requirement() {
+    let restriction = CompilerRestriction {
+      version_req: Some(semver::VersionReq::parse(">=0.8.20").unwrap()),
+      ..restriction("**/*.sol")
+    };
+    let version = Version::new(0, 8, 10);
+    let err = ensure_satisfied("src/A.sol", &version, &Settings::default(), &[restriction])
+      .expect_err("version below requirement should fail");
+    assert!(err.to_string().contains("restricted to solc"));
+  }
+
+  #[test]
+  fn ensure_satisfied_rejects_via_ir_mismatch() {
+    let restriction = CompilerRestriction {
+      via_ir: Some(true),
+      ..restriction("**/*.sol")
+    };
+    let version = Version::new(0, 8, 30);
+    let mut settings = Settings::default();
+    settings.via_ir = Some(false);
+    let err = ensure_satisfied("src/A.sol", &version, &settings, &[restriction])
+      .expect_err("viaIR mismatch should fail");
+    assert!(err.to_string().contains("viaIR"));
+  }
+
+  #[test]
+  fn clamp_settings_raises_optimizer_runs_below_minimum() {
+    let group = RestrictionGroup {
+      paths: vec!["src/A.sol".to_string()],
+      restrictions: vec![CompilerRestriction {
+        min_optimizer_runs: Some(200),
+        ..restriction("**/*.sol")
+      }],
+    };
+    let mut settings = Settings::default();
+    settings.optimizer.runs = Some(1);
+
+    let clamped = clamp_settings(&settings, &group).expect("clamp");
+    assert_eq!(clamped.optimizer.runs, Some(200));
+  }
+
+  #[test]
+  fn clamp_settings_pins_unset_via_ir() {
+    let group = RestrictionGroup {
+      paths: vec!["src/A.sol".to_string()],
+      restrictions: vec![CompilerRestriction {
+        via_ir: Some(true),
+        ..restriction("**/*.sol")
+      }],
+    };
+    let clamped = clamp_settings(&Settings::default(), &group).expect("clamp");
+    assert_eq!(clamped.via_ir, Some(true));
+  }
+
+  #[test]
+  fn clamp_settings_rejects_conflicting_via_ir_pins() {
+    let group = RestrictionGroup {
+      paths: vec!["src/A.sol".to_string()],
+      restrictions: vec![
+        CompilerRestriction {
+          via_ir: Some(true),
+          ..restriction("**/a/*.sol")
+        },
+        CompilerRestriction {
+          via_ir: Some(false),
+          ..restriction("**/*.sol")
+        },
+      ],
+    };
+    let err = clamp_settings(&Settings::default(), &group).expect_err("conflicting pins");
+    assert!(err.to_string().contains("viaIR"));
+  }
+
+  #[test]
+  fn group_paths_splits_files_with_different_restrictions() {
+    let restrictions = vec![restriction("**/src/core/*.sol")];
+    let paths = vec![
+      "/project/src/core/Vault.sol".to_string(),
+      "/project/src/periphery/Router.sol".to_string(),
+    ];
+    let groups = group_paths(&paths, &restrictions).expect("group paths");
+    assert_eq!(groups.len(), 2);
+    assert!(groups.iter().any(|group| group.restrictions.len() == 1));
+    assert!(groups.iter().any(|group| group.restrictions.is_empty()));
+  }
+}
@@ -4,13 +4,16 @@ use std::path::PathBuf;
 use foundry_compilers::artifacts::{
   output_selection::OutputSelection,
   vyper::{VyperOptimizationMode, VyperSettings},
-  Settings,
+  Settings, SolcInput, SolcLanguage, Source, Sources,
 };
 use napi::bindgen_prelude::Result;
+use once_cell::sync::Lazy;
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json;
 
-use crate::internal::errors::map_napi_error;
+use crate::internal::errors::{map_napi_error, napi_error};
+use crate::internal::keccak::keccak256;
 
 /// Rust-facing optional overrides that can be merged into Foundry `Settings`.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -55,6 +58,12 @@ pub struct CompilerSettingsOptions {
   pub debug: Option<DebuggingSettingsOptions>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub libraries: Option<BTreeMap<String, BTreeMap<String, String>>>,
+  /// Keeps the solc JSON `ast` output in the effective `outputSelection` even for a plain compile.
+  /// Not part of solc's own settings schema, so it's excluded from [`CompilerSettingsOptions::overlay`]
+  /// and only consulted by `CompilerConfigBuilder::build` when deciding whether to strip the AST
+  /// entry it would otherwise drop by default.
+  #[serde(skip)]
+  pub include_ast: Option<bool>,
 }
 
 impl CompilerSettingsOptions {
@@ -68,7 +77,12 @@ impl CompilerSettingsOptions {
       "Failed to serialise compiler settings",
     )?;
 
-    merge_settings_json(&mut base_value, overrides);
+    merge_settings_json_with_strategy(
+      &mut base_value,
+      overrides,
+      MergeStrategy::Replace,
+      &SETTINGS_MERGE_STRATEGIES,
+    );
 
     map_napi_error(
       serde_json::from_value(base_value),
@@ -210,6 +224,8 @@ pub struct ModelCheckerSettingsOptions {
   pub show_unsupported: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub show_proved_safe: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bmc_loop_iterations: Option<u32>,
 }
 
 /// JavaScript-facing wrapper around `solc` compiler settings. Everything is optional—unset values
@@ -218,6 +234,14 @@ pub struct ModelCheckerSettingsOptions {
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JsCompilerSettingsOptions {
+  /// Named starting point expanded into a baseline settings object before everything else on
+  /// this object is layered on top (see [`CompilationProfile`]). Lets a caller opt into a
+  /// sensible default (`'production'`, `'debug'`, `'size'`, `'fast'`) without hand-specifying
+  /// every nested optimiser/metadata/debug toggle, while still being free to override individual
+  /// fields alongside it.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[napi(ts_type = "'production' | 'debug' | 'size' | 'fast' | undefined")]
+  pub profile: Option<CompilationProfile>,
   /// Stop the compiler after the specified phase (e.g. `'parsing'`). Handy when you only need
   /// ASTs or syntax validation.
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -261,6 +285,11 @@ pub struct JsCompilerSettingsOptions {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[napi(ts_type = "Record<string, Record<string, string>> | undefined")]
   pub libraries: Option<BTreeMap<String, BTreeMap<String, String>>>,
+  /// Keeps the solc JSON `ast` output in the effective output selection for this compile.
+  /// Defaults to `false`; plain compiles otherwise skip the (expensive) `ast` output unless this
+  /// is set or `outputSelection` already requests it explicitly.
+  #[serde(rename = "includeAst", skip_serializing_if = "Option::is_none")]
+  pub include_ast: Option<bool>,
 }
 
 #[napi(object, js_name = "OptimizerSettings")]
@@ -370,7 +399,8 @@ pub struct JsModelCheckerSettingsOptions {
   #[serde(skip_serializing_if = "BTreeMap::is_empty")]
   #[napi(ts_type = "Record<string, string[]> | undefined")]
   pub contracts: BTreeMap<String, Vec<String>>,
-  /// Model checker engine to use (`None` disables the feature, `Bmc` runs bounded model checking).
+  /// Model checker engine to use (`None` disables the feature, `Bmc` runs bounded model checking,
+  /// `Chc` runs constrained Horn clause analysis, and `All` runs both).
   #[serde(skip_serializing_if = "Option::is_none")]
   #[napi(ts_type = "import('./solc-settings').ModelCheckerEngine | undefined")]
   pub engine: Option<ModelCheckerEngine>,
@@ -401,6 +431,9 @@ pub struct JsModelCheckerSettingsOptions {
   /// Displays properties proved to be safe.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub show_proved_safe: Option<bool>,
+  /// Number of loop unrolling iterations for the `Bmc`/`All` engines.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bmc_loop_iterations: Option<u32>,
 }
 
 fn deserialize_null_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
@@ -411,41 +444,203 @@ where
   Option::<T>::deserialize(deserializer).map(|opt| opt.unwrap_or_default())
 }
 
+/// Controls how [`merge_settings_json_with_strategy`] combines a base array with an override array
+/// for a given field. Plain [`merge_settings_json`] always uses [`MergeStrategy::Replace`]; callers
+/// that want `remappings`-style "appended" semantics go through the `_with_strategy` variant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum MergeStrategy {
+  /// The override array entirely replaces the base array.
+  #[default]
+  Replace,
+  /// The override array is appended after the base array, preserving order and duplicates.
+  Append,
+  /// The override array is appended after the base array, then exact duplicate entries are
+  /// dropped (first occurrence wins).
+  Union,
+}
+
+fn merge_array(
+  existing: &mut Vec<serde_json::Value>,
+  incoming: Vec<serde_json::Value>,
+  strategy: MergeStrategy,
+) {
+  match strategy {
+    MergeStrategy::Replace => *existing = incoming,
+    MergeStrategy::Append => existing.extend(incoming),
+    MergeStrategy::Union => {
+      for item in incoming {
+        if !existing.contains(&item) {
+          existing.push(item);
+        }
+      }
+    }
+  }
+}
+
+/// Per-field [`MergeStrategy`] overrides, keyed by the JSON field name at whatever depth it
+/// appears (e.g. `"remappings"`, `"debugInfo"`). A key found here applies to that field and,
+/// since dynamic keys nested underneath it (like `outputSelection`'s per-contract entries) inherit
+/// it from their parent, to every array nested below it as well.
+pub(crate) type MergeStrategies = BTreeMap<&'static str, MergeStrategy>;
+
+/// Field strategies matching the `CompilerSettingsOptions` doc comments that promise `remappings`
+/// and `outputSelection`/`debugInfo` are extended rather than replaced wholesale.
+static SETTINGS_MERGE_STRATEGIES: Lazy<MergeStrategies> = Lazy::new(|| {
+  BTreeMap::from([
+    ("remappings", MergeStrategy::Append),
+    ("outputSelection", MergeStrategy::Union),
+    ("debugInfo", MergeStrategy::Union),
+  ])
+});
+
 pub(crate) fn merge_settings_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+  merge_settings_json_with_strategy(base, overrides, MergeStrategy::Replace, &MergeStrategies::new());
+}
+
+/// Like [`merge_settings_json`], but array-valued fields consult `field_strategies` (falling back
+/// to `default_strategy`) instead of always being replaced wholesale. A strategy picked up from
+/// `field_strategies` at an object key is inherited by every array nested underneath that key, so
+/// e.g. tagging `"outputSelection"` as [`MergeStrategy::Union`] also unions the per-contract output
+/// arrays nested inside it without needing an entry for every contract name.
+pub(crate) fn merge_settings_json_with_strategy(
+  base: &mut serde_json::Value,
+  overrides: serde_json::Value,
+  default_strategy: MergeStrategy,
+  field_strategies: &MergeStrategies,
+) {
+  merge_value(base, overrides, None, default_strategy, field_strategies);
+}
+
+fn merge_value(
+  base: &mut serde_json::Value,
+  overrides: serde_json::Value,
+  inherited_strategy: Option<MergeStrategy>,
+  default_strategy: MergeStrategy,
+  field_strategies: &MergeStrategies,
+) {
   match (base, overrides) {
     (serde_json::Value::Object(base_map), serde_json::Value::Object(overrides_map)) => {
       for (key, value) in overrides_map {
+        let strategy_for_key = field_strategies
+          .get(key.as_str())
+          .copied()
+          .or(inherited_strategy);
         match base_map.get_mut(&key) {
-          Some(existing) => merge_settings_json(existing, value),
+          Some(existing) => {
+            merge_value(existing, value, strategy_for_key, default_strategy, field_strategies)
+          }
           None => {
             base_map.insert(key, value);
           }
         }
       }
     }
+    (serde_json::Value::Array(existing_arr), serde_json::Value::Array(incoming_arr)) => {
+      merge_array(
+        existing_arr,
+        incoming_arr,
+        inherited_strategy.unwrap_or(default_strategy),
+      );
+    }
     (target, value) => {
       *target = value;
     }
   }
 }
 
+/// Minimum solc release that accepts `viaIR` at all; older compilers reject the settings key
+/// outright rather than silently ignoring it.
+static VIA_IR_VERSION_REQ: Lazy<VersionReq> =
+  Lazy::new(|| VersionReq::parse(">=0.8.13").expect("valid version requirement"));
+
+/// Minimum solc release exposing `--model-checker-engine`, i.e. the version from which
+/// `modelChecker.engine` is interpreted at all. This is a version floor, not a guarantee that the
+/// resolved solc binary actually links an SMT solver (solc never reports that over its CLI/JSON
+/// interface), so a version past this bound can still silently run the model checker in a
+/// degraded mode rather than erroring.
+static MODEL_CHECKER_VERSION_REQ: Lazy<VersionReq> =
+  Lazy::new(|| VersionReq::parse(">=0.8.0").expect("valid version requirement"));
+
+/// Rejects settings combinations `solc_version` can't actually honour, naming the offending field
+/// and the minimum version it requires so a caller gets an actionable error up front instead of a
+/// raw solc failure once compilation actually runs.
+fn validate_settings_for_version(settings: &Settings, solc_version: &Version) -> Result<()> {
+  if settings.via_ir == Some(true) && !VIA_IR_VERSION_REQ.matches(solc_version) {
+    return Err(napi_error(format!(
+      "viaIR requires solc {VIA_IR_VERSION_REQ} (resolved version is {solc_version})"
+    )));
+  }
+
+  // Read back through the serialised form rather than `settings.model_checker` directly: that
+  // field is Foundry's own `ModelCheckerSettings`, not this crate's `ModelCheckerSettingsOptions`.
+  let model_checker_enabled = serde_json::to_value(settings)
+    .ok()
+    .and_then(|value| value.get("modelChecker")?.get("engine").cloned())
+    .is_some_and(|engine| !engine.is_null());
+  if model_checker_enabled && !MODEL_CHECKER_VERSION_REQ.matches(solc_version) {
+    return Err(napi_error(format!(
+      "modelChecker.engine requires solc {MODEL_CHECKER_VERSION_REQ} (resolved version is {solc_version})"
+    )));
+  }
+
+  Ok(())
+}
+
+/// Rejects enabling a model-checking engine without telling solc which SMT solver(s) to run it
+/// with: solc doesn't error on that combination, it just runs the engine with whatever solvers it
+/// happens to have linked in (often none), so the request silently does no model checking at all.
+fn validate_model_checker_solvers(settings: &Settings) -> Result<()> {
+  // Read back through the serialised form for the same reason as `validate_settings_for_version`:
+  // `settings.model_checker` is Foundry's `ModelCheckerSettings`, not our `*Options` type.
+  let Some(model_checker) =
+    serde_json::to_value(settings)
+      .ok()
+      .and_then(|value| value.get("modelChecker").cloned())
+  else {
+    return Ok(());
+  };
+
+  let engine_enabled = model_checker
+    .get("engine")
+    .and_then(|engine| engine.as_str())
+    .is_some_and(|engine| engine != "none");
+  if !engine_enabled {
+    return Ok(());
+  }
+
+  let has_solver = model_checker
+    .get("solvers")
+    .and_then(|solvers| solvers.as_array())
+    .is_some_and(|solvers| !solvers.is_empty());
+  if !has_solver {
+    return Err(napi_error(
+      "modelChecker.engine is set but modelChecker.solvers is empty; specify at least one solver \
+       (e.g. \"chc\" or \"bmc\") or model checking will silently do nothing",
+    ));
+  }
+
+  Ok(())
+}
+
 pub fn merge_settings(
   base: &Settings,
   overrides: Option<&CompilerSettingsOptions>,
+  solc_version: &Version,
 ) -> Result<Settings> {
   match overrides {
     Some(settings) => {
-      let mut merged = settings.clone().overlay(base)?;
-      if let Some(selection) = &settings.output_selection {
-        merged.output_selection = selection.clone().into();
-      }
-      sanitize_settings(&merged)
+      // `outputSelection` is unioned per-contract by `overlay` (see `SETTINGS_MERGE_STRATEGIES`),
+      // so no further post-processing of `merged.output_selection` is needed here.
+      let merged = settings.clone().overlay(base)?;
+      sanitize_settings(&merged, solc_version)
     }
     None => Ok(base.clone()),
   }
 }
 
-pub fn sanitize_settings(settings: &Settings) -> Result<Settings> {
+pub fn sanitize_settings(settings: &Settings, solc_version: &Version) -> Result<Settings> {
+  validate_settings_for_version(settings, solc_version)?;
+  validate_model_checker_solvers(settings)?;
   let mut merged = settings.clone();
   if output_selection_is_effectively_empty(&merged.output_selection) {
     merged.output_selection = default_output_selection();
@@ -453,6 +648,155 @@ pub fn sanitize_settings(settings: &Settings) -> Result<Settings> {
   Ok(merged)
 }
 
+/// Like [`sanitize_settings`], but also returns the non-fatal diagnostics [`validate_settings`]
+/// collects for the *original* (pre-sanitize) settings, so a caller can surface them to the user
+/// alongside the settings that were actually used to compile.
+pub fn sanitize_settings_with_warnings(
+  settings: &Settings,
+  solc_version: &Version,
+) -> Result<(Settings, Vec<SettingsWarning>)> {
+  let warnings = validate_settings(settings);
+  let sanitized = sanitize_settings(settings, solc_version)?;
+  Ok((sanitized, warnings))
+}
+
+/// A self-contradictory or pointless settings combination that doesn't prevent compilation but
+/// likely isn't what the caller meant, e.g. an `optimizer.runs` count set while the optimizer
+/// itself is disabled. Carries a stable `code` so callers can programmatically suppress specific
+/// warnings, plus a human-readable `message` to surface as-is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingsWarning {
+  pub code: &'static str,
+  pub message: String,
+}
+
+/// Flags settings combinations that are individually valid but contradict or defeat each other,
+/// without failing compilation the way [`sanitize_settings`]'s version/solver checks do. Mirrors
+/// rustc's `early_warn`-style non-fatal config diagnostics: every problem found is collected and
+/// returned rather than stopping at the first one.
+pub fn validate_settings(settings: &Settings) -> Vec<SettingsWarning> {
+  let mut warnings = Vec::new();
+  let value = serde_json::to_value(settings).unwrap_or(serde_json::Value::Null);
+
+  if let Some(optimizer) = value.get("optimizer") {
+    let enabled = optimizer
+      .get("enabled")
+      .and_then(serde_json::Value::as_bool)
+      .unwrap_or(false);
+    let runs_set = optimizer
+      .get("runs")
+      .is_some_and(|runs| !runs.is_null());
+    if !enabled && runs_set {
+      warnings.push(SettingsWarning {
+        code: "optimizer-runs-without-enabled",
+        message: "optimizer.runs is set but optimizer.enabled is false, so it has no effect"
+          .to_string(),
+      });
+    }
+  }
+
+  if let Some(metadata) = value.get("metadata") {
+    let bytecode_hash_none = metadata.get("bytecodeHash").and_then(serde_json::Value::as_str)
+      == Some("none");
+    let cbor_metadata_enabled = metadata
+      .get("cborMetadata")
+      .and_then(serde_json::Value::as_bool)
+      .unwrap_or(false);
+    if bytecode_hash_none && cbor_metadata_enabled {
+      warnings.push(SettingsWarning {
+        code: "cbor-metadata-without-bytecode-hash",
+        message: "metadata.cborMetadata is true but metadata.bytecodeHash is \"none\", so the \
+                  embedded CBOR metadata has no hash to reference"
+          .to_string(),
+      });
+    }
+  }
+
+  let stop_after = value.get("stopAfter").and_then(serde_json::Value::as_str);
+
+  if settings.via_ir == Some(true) && stop_after == Some("parsing") {
+    warnings.push(SettingsWarning {
+      code: "via-ir-with-stop-after-parsing",
+      message: "viaIR is enabled but stopAfter is \"parsing\", so compilation never reaches the \
+                via-IR pipeline"
+        .to_string(),
+    });
+  }
+
+  if let Some(model_checker) = value.get("modelChecker") {
+    let engine_enabled = model_checker
+      .get("engine")
+      .and_then(serde_json::Value::as_str)
+      .is_some_and(|engine| engine != "none");
+    let contracts_empty = model_checker
+      .get("contracts")
+      .and_then(serde_json::Value::as_object)
+      .map(|contracts| contracts.is_empty())
+      .unwrap_or(true);
+    let targets_empty = model_checker
+      .get("targets")
+      .and_then(serde_json::Value::as_array)
+      .map(|targets| targets.is_empty())
+      .unwrap_or(true);
+    if engine_enabled && contracts_empty && targets_empty {
+      warnings.push(SettingsWarning {
+        code: "model-checker-engine-without-scope",
+        message: "modelChecker.engine is set but both modelChecker.contracts and \
+                  modelChecker.targets are empty, so solc falls back to its own default scope \
+                  instead of anything specifically selected"
+          .to_string(),
+      });
+    }
+  }
+
+  if stop_after == Some("parsing") && requests_non_ast_output(&settings.output_selection) {
+    warnings.push(SettingsWarning {
+      code: "output-selection-incompatible-with-stop-after",
+      message: "outputSelection requests outputs other than ast, but stopAfter is \"parsing\" so \
+                only the ast output is ever produced"
+        .to_string(),
+    });
+  }
+
+  warnings
+}
+
+/// Whether `selection` requests any output besides the file-level `ast` entry. Used to flag an
+/// `outputSelection`/`stopAfter` combination that can never be satisfied: a `"parsing"` stop never
+/// produces anything past the AST.
+fn requests_non_ast_output(selection: &OutputSelection) -> bool {
+  selection.as_ref().values().any(|file_selection| {
+    file_selection.iter().any(|(contract, outputs)| {
+      if contract.is_empty() {
+        outputs.iter().any(|output| output != "ast")
+      } else {
+        outputs.iter().any(|output| !output.trim().is_empty())
+      }
+    })
+  })
+}
+
+/// Stable content hash of `settings`, independent of incidental serialization order, so a build
+/// layer can skip recompilation when neither sources nor settings have meaningfully changed.
+/// Normalizes `output_selection` through the same emptiness/fallback rule [`sanitize_settings`]
+/// applies first, so an unset selection and one `sanitize_settings` has already defaulted hash
+/// identically. Every other field is included as-is: nothing else in `Settings` is purely
+/// cosmetic, so nothing else is excluded.
+pub fn settings_fingerprint(settings: &Settings) -> [u8; 32] {
+  let mut canonical = settings.clone();
+  if output_selection_is_effectively_empty(&canonical.output_selection) {
+    canonical.output_selection = default_output_selection();
+  }
+
+  // Round-trip through `serde_json::Value` rather than serializing `canonical` directly: `Value`'s
+  // object map is sorted by key (the `preserve_order` feature is not enabled), which canonicalizes
+  // any nested map regardless of what collection type backs it on the foreign `Settings`/
+  // `OutputSelection` structs.
+  let canonical_value = serde_json::to_value(&canonical).unwrap_or(serde_json::Value::Null);
+  let canonical_json = serde_json::to_vec(&canonical_value).unwrap_or_default();
+  keccak256(&canonical_json)
+}
+
 // Default Foundry output selection + file-level ast output
 pub fn default_output_selection() -> OutputSelection {
   let mut selection = OutputSelection::default_output_selection();
@@ -465,6 +809,69 @@ pub fn default_output_selection() -> OutputSelection {
   selection
 }
 
+/// Builds an [`OutputSelection`] that selects exactly `contract_outputs` for every contract
+/// (`"*"`), plus the file-level `ast` output when `include_ast` is `true`. Used to produce the
+/// cheaper `Minimal`/`AbiOnly` selections without requesting Foundry's full default bundle.
+pub fn narrow_output_selection(contract_outputs: &[&str], include_ast: bool) -> OutputSelection {
+  let mut per_contract: BTreeMap<String, Vec<String>> = BTreeMap::new();
+  per_contract.insert(
+    "*".to_string(),
+    contract_outputs.iter().map(|output| output.to_string()).collect(),
+  );
+  if include_ast {
+    per_contract.insert(String::new(), vec!["ast".to_string()]);
+  }
+
+  let mut selection: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+  selection.insert("*".to_string(), per_contract);
+  selection.into()
+}
+
+/// Augments `selection` with `evm.bytecode.sourceMap`/`evm.deployedBytecode.sourceMap` for every
+/// contract already selected, without disturbing any other requested outputs.
+pub fn add_source_map_outputs(selection: &OutputSelection) -> OutputSelection {
+  let mut augmented = selection.as_ref().clone();
+  for file_selection in augmented.values_mut() {
+    let entry = file_selection
+      .entry("*".to_string())
+      .or_insert_with(Vec::new);
+    for output in ["evm.bytecode.sourceMap", "evm.deployedBytecode.sourceMap"] {
+      if !entry.iter().any(|existing| existing == output) {
+        entry.push(output.to_string());
+      }
+    }
+  }
+  augmented.into()
+}
+
+/// Whether `selection` already asks for the file-level `ast` output on any file pattern, as
+/// produced by [`default_output_selection`]/[`narrow_output_selection`]. Used to tell an explicit
+/// `outputSelection` override apart from one that merely inherited the AST entry by default.
+pub fn requests_ast_output(selection: &BTreeMap<String, BTreeMap<String, Vec<String>>>) -> bool {
+  selection.values().any(|file_selection| {
+    file_selection
+      .get("")
+      .is_some_and(|outputs| outputs.iter().any(|output| output == "ast"))
+  })
+}
+
+/// Strips the file-level `ast` output from every file pattern in `selection`, dropping the
+/// now-empty `""` contract entry along with it. Requesting the AST is expensive and most compiles
+/// never read it back, so [`CompilerConfigBuilder::build`](crate::internal::config::CompilerConfigBuilder::build)
+/// calls this on the merged selection unless a caller actually asked for it.
+pub fn strip_unrequested_ast_output(selection: &OutputSelection) -> OutputSelection {
+  let mut stripped = selection.as_ref().clone();
+  for file_selection in stripped.values_mut() {
+    if let Some(outputs) = file_selection.get_mut("") {
+      outputs.retain(|output| output != "ast");
+      if outputs.is_empty() {
+        file_selection.remove("");
+      }
+    }
+  }
+  stripped.into()
+}
+
 pub fn output_selection_is_effectively_empty(selection: &OutputSelection) -> bool {
   let map = selection.as_ref();
   if map.is_empty() {
@@ -478,6 +885,337 @@ pub fn output_selection_is_effectively_empty(selection: &OutputSelection) -> boo
   })
 }
 
+/// A known solc output artifact, following the same typed-set-of-output-kinds idea as rustc's
+/// `OutputType`, instead of the raw `"evm.bytecode.object"`-style strings `outputSelection` is
+/// otherwise built from. Not exhaustive -- [`OutputSelectionBuilder::add_custom`] accepts any
+/// other artifact string solc understands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutputArtifact {
+  Abi,
+  Metadata,
+  Ast,
+  Ir,
+  IrOptimized,
+  StorageLayout,
+  Devdoc,
+  Userdoc,
+  EvmBytecodeObject,
+  EvmBytecodeSourceMap,
+  EvmDeployedBytecodeObject,
+  EvmDeployedBytecodeSourceMap,
+  EvmMethodIdentifiers,
+  EvmGasEstimates,
+}
+
+impl OutputArtifact {
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Abi => "abi",
+      Self::Metadata => "metadata",
+      Self::Ast => "ast",
+      Self::Ir => "ir",
+      Self::IrOptimized => "irOptimized",
+      Self::StorageLayout => "storageLayout",
+      Self::Devdoc => "devdoc",
+      Self::Userdoc => "userdoc",
+      Self::EvmBytecodeObject => "evm.bytecode.object",
+      Self::EvmBytecodeSourceMap => "evm.bytecode.sourceMap",
+      Self::EvmDeployedBytecodeObject => "evm.deployedBytecode.object",
+      Self::EvmDeployedBytecodeSourceMap => "evm.deployedBytecode.sourceMap",
+      Self::EvmMethodIdentifiers => "evm.methodIdentifiers",
+      Self::EvmGasEstimates => "evm.gasEstimates",
+    }
+  }
+}
+
+/// Typed builder over [`OutputSelection`], replacing the hand-built
+/// `BTreeMap<String, BTreeMap<String, Vec<String>>>` tests and callers otherwise need to construct
+/// by hand. `file`/`contract` are solc's own glob-style patterns (`"*"` for "every file"/"every
+/// contract", `""` for the file-level entry outputs like [`OutputArtifact::Ast`] live under).
+#[derive(Clone, Debug, Default)]
+pub struct OutputSelectionBuilder {
+  selection: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+impl OutputSelectionBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Requests `artifact` for `contract` within `file`, creating the file/contract entries as
+  /// needed. A no-op if already requested.
+  pub fn add(self, file: &str, contract: &str, artifact: OutputArtifact) -> Self {
+    self.add_custom(file, contract, artifact.as_str())
+  }
+
+  /// Like [`OutputSelectionBuilder::add`], but takes a raw artifact string instead of an
+  /// [`OutputArtifact`] variant, for outputs this enum doesn't cover yet.
+  pub fn add_custom(mut self, file: &str, contract: &str, artifact: &str) -> Self {
+    let outputs = self
+      .selection
+      .entry(file.to_string())
+      .or_insert_with(BTreeMap::new)
+      .entry(contract.to_string())
+      .or_insert_with(Vec::new);
+    if !outputs.iter().any(|existing| existing == artifact) {
+      outputs.push(artifact.to_string());
+    }
+    self
+  }
+
+  /// Requests `artifact` as a file-level output (the `""` contract entry) within `file`, as
+  /// [`OutputArtifact::Ast`] uses.
+  pub fn for_file(self, file: &str, artifact: OutputArtifact) -> Self {
+    self.add(file, "", artifact)
+  }
+
+  /// Requests `artifact` for `contract` within `file`. An explicit alias for
+  /// [`OutputSelectionBuilder::add`] that reads better when `file`/`contract` aren't `"*"`.
+  pub fn for_contract(self, file: &str, contract: &str, artifact: OutputArtifact) -> Self {
+    self.add(file, contract, artifact)
+  }
+
+  /// Requests `artifact` for every contract in every file (`file = "*"`, `contract = "*"`).
+  pub fn wildcard(self, artifact: OutputArtifact) -> Self {
+    self.add("*", "*", artifact)
+  }
+
+  /// Whether this selection already requests `artifact` for `contract` within `file`.
+  pub fn contains(&self, file: &str, contract: &str, artifact: OutputArtifact) -> bool {
+    self
+      .selection
+      .get(file)
+      .and_then(|by_contract| by_contract.get(contract))
+      .is_some_and(|outputs| outputs.iter().any(|output| output == artifact.as_str()))
+  }
+
+  /// Adds every output `other` requests that this selection doesn't already have (set union),
+  /// leaving everything already present untouched.
+  pub fn union(mut self, other: &OutputSelection) -> Self {
+    for (file, by_contract) in other.as_ref() {
+      for (contract, outputs) in by_contract {
+        for output in outputs {
+          let existing = self
+            .selection
+            .entry(file.clone())
+            .or_insert_with(BTreeMap::new)
+            .entry(contract.clone())
+            .or_insert_with(Vec::new);
+          if !existing.contains(output) {
+            existing.push(output.clone());
+          }
+        }
+      }
+    }
+    self
+  }
+
+  /// Removes every output `other` requests from this selection (set difference), dropping
+  /// now-empty contract/file entries along with it.
+  pub fn difference(mut self, other: &OutputSelection) -> Self {
+    for (file, by_contract) in other.as_ref() {
+      let Some(self_by_contract) = self.selection.get_mut(file) else {
+        continue;
+      };
+      for (contract, outputs) in by_contract {
+        let Some(existing) = self_by_contract.get_mut(contract) else {
+          continue;
+        };
+        existing.retain(|output| !outputs.contains(output));
+        if existing.is_empty() {
+          self_by_contract.remove(contract);
+        }
+      }
+      if self_by_contract.is_empty() {
+        self.selection.remove(file);
+      }
+    }
+    self
+  }
+
+  pub fn build(self) -> OutputSelection {
+    self.selection.into()
+  }
+}
+
+impl From<&OutputSelection> for OutputSelectionBuilder {
+  fn from(selection: &OutputSelection) -> Self {
+    Self {
+      selection: selection.as_ref().clone(),
+    }
+  }
+}
+
+/// Combines `base` and `overlay` into a single [`OutputSelection`] using `strategy`: `Union` (and
+/// `Append`, equivalent here since artifact strings are deduplicated regardless) adds every output
+/// `overlay` requests without dropping anything `base` already had -- the behavior
+/// [`CompilerSettingsOptions::overlay`] uses for `outputSelection` via [`SETTINGS_MERGE_STRATEGIES`]
+/// -- while `Replace` discards `base` entirely in favor of `overlay`, preserving the simpler
+/// wholesale-replacement behavior from before that strategy existed.
+pub(crate) fn merge_output_selections(
+  base: &OutputSelection,
+  overlay: &OutputSelection,
+  strategy: MergeStrategy,
+) -> OutputSelection {
+  match strategy {
+    MergeStrategy::Replace => overlay.as_ref().clone().into(),
+    MergeStrategy::Append | MergeStrategy::Union => {
+      OutputSelectionBuilder::from(base).union(overlay).build()
+    }
+  }
+}
+
+/// Assembles a complete solc Standard JSON input document (`{ language, sources, settings }`)
+/// from `settings` and `sources` (source file path -> contents), ready to hand to an external solc
+/// binary or a verification service. Falls back to [`default_output_selection`] when `settings`'
+/// own output selection is effectively empty, so the emitted document is self-sufficient even if
+/// the caller skipped [`sanitize_settings`].
+pub fn to_standard_json(settings: &Settings, sources: BTreeMap<String, String>) -> serde_json::Value {
+  let mut settings = settings.clone();
+  if output_selection_is_effectively_empty(&settings.output_selection) {
+    settings.output_selection = default_output_selection();
+  }
+
+  let mut solc_sources = Sources::new();
+  for (path, content) in sources {
+    solc_sources.insert(PathBuf::from(path), Source::new(&content));
+  }
+
+  let input = SolcInput::new(SolcLanguage::Solidity, solc_sources, settings);
+  serde_json::to_value(input).expect("SolcInput always serializes to JSON")
+}
+
+/// Parses the `settings` block out of an external solc Standard JSON input document (e.g. one
+/// produced by other tooling or downloaded from a verification service) back into a
+/// [`CompilerSettingsOptions`], honoring the same camelCase/snake_case aliases accepted everywhere
+/// else on this struct. Missing or malformed `settings` are treated as an empty object rather than
+/// an error, matching [`CompilerSettingsOptions`]'s all-optional fields.
+pub fn from_standard_json(standard_json: &serde_json::Value) -> Result<CompilerSettingsOptions> {
+  let settings = standard_json
+    .get("settings")
+    .cloned()
+    .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+  map_napi_error(
+    serde_json::from_value(settings),
+    "Failed to parse standard JSON settings",
+  )
+}
+
+/// Parses a `getopts`-style argument vector into a [`CompilerSettingsOptions`], mirroring how
+/// rustc's `config.rs` maps command-line flags onto its `Config`. Recognizes a small, explicit
+/// set of flags -- `--optimize`, `--optimize-runs <n>`, `--via-ir`, `--evm-version <name>`,
+/// `--metadata-hash <name>`, and `-R`/`--remappings <old>=<new>` (repeatable) -- and feeds
+/// directly into [`merge_settings`] like any other `CompilerSettingsOptions`. An unrecognized
+/// flag, a flag missing its value, or an unknown `EvmVersion`/`BytecodeHash` name is a
+/// [`napi_error`], the last carrying a "did you mean" suggestion computed by edit distance
+/// against the known variant names when one is close enough.
+pub fn parse_cli_overrides(args: &[String]) -> Result<CompilerSettingsOptions> {
+  let mut options = CompilerSettingsOptions::default();
+  let mut remappings = Vec::new();
+  let mut index = 0;
+
+  while index < args.len() {
+    let flag = args[index].as_str();
+    index += 1;
+
+    match flag {
+      "--optimize" => {
+        options
+          .optimizer
+          .get_or_insert_with(OptimizerSettingsOptions::default)
+          .enabled = Some(true);
+      }
+      "--optimize-runs" => {
+        let value = take_flag_value(args, &mut index, flag)?;
+        let runs: u32 = value.parse().map_err(|_| {
+          napi_error(format!(
+            "Invalid value for {flag}: `{value}` is not a valid run count"
+          ))
+        })?;
+        options
+          .optimizer
+          .get_or_insert_with(OptimizerSettingsOptions::default)
+          .runs = Some(runs);
+      }
+      "--via-ir" => options.via_ir = Some(true),
+      "--evm-version" => {
+        let value = take_flag_value(args, &mut index, flag)?;
+        options.evm_version = Some(parse_enum_flag::<EvmVersion>(flag, &value, EvmVersion::VARIANTS)?);
+      }
+      "--metadata-hash" => {
+        let value = take_flag_value(args, &mut index, flag)?;
+        options
+          .metadata
+          .get_or_insert_with(SettingsMetadataOptions::default)
+          .bytecode_hash = Some(parse_enum_flag::<BytecodeHash>(flag, &value, BytecodeHash::VARIANTS)?);
+      }
+      "-R" | "--remappings" => {
+        remappings.push(take_flag_value(args, &mut index, flag)?);
+      }
+      unknown => return Err(napi_error(format!("Unrecognized flag `{unknown}`"))),
+    }
+  }
+
+  if !remappings.is_empty() {
+    options.remappings = Some(remappings);
+  }
+
+  Ok(options)
+}
+
+fn take_flag_value(args: &[String], index: &mut usize, flag: &str) -> Result<String> {
+  let value = args
+    .get(*index)
+    .cloned()
+    .ok_or_else(|| napi_error(format!("{flag} expects a value")))?;
+  *index += 1;
+  Ok(value)
+}
+
+fn parse_enum_flag<T>(flag: &str, value: &str, variants: &'static [&'static str]) -> Result<T>
+where
+  T: std::str::FromStr<Err = String>,
+{
+  value.parse().map_err(|_| {
+    let mut message = format!("Invalid value for {flag}: `{value}`");
+    if let Some(suggestion) = did_you_mean(value, variants) {
+      message.push_str(&format!(" (did you mean `{suggestion}`?)"));
+    }
+    napi_error(message)
+  })
+}
+
+/// Finds the closest string in `variants` to `value` by edit distance, the same "did you mean"
+/// heuristic rustc uses for misspelled edition/feature names. Returns `None` when nothing is
+/// close enough to plausibly be a typo of `value`.
+fn did_you_mean(value: &str, variants: &'static [&'static str]) -> Option<&'static str> {
+  variants
+    .iter()
+    .map(|&variant| (variant, edit_distance(value, variant)))
+    .filter(|(_, distance)| *distance <= 3)
+    .min_by_key(|(_, distance)| *distance)
+    .map(|(variant, _)| variant)
+}
+
+/// Classic Levenshtein distance (insert/delete/substitute, unit cost each) between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+  let mut prev: Vec<usize> = (0..=b.len()).collect();
+  let mut curr = vec![0usize; b.len() + 1];
+
+  for i in 1..=a.len() {
+    curr[0] = i;
+    for j in 1..=b.len() {
+      let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+      curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+    }
+    std::mem::swap(&mut prev, &mut curr);
+  }
+
+  prev[b.len()]
+}
+
 macro_rules! impl_enum_string_traits {
   ($name:ident { $($variant:ident => $value:expr),+ $(,)? }) => {
     impl $name {
@@ -486,6 +1224,10 @@ macro_rules! impl_enum_string_traits {
           $(Self::$variant => $value,)*
         }
       }
+
+      /// Every accepted string value, in declaration order. Used to compute "did you mean"
+      /// suggestions when parsing a value for this type fails.
+      pub(crate) const VARIANTS: &'static [&'static str] = &[$($value),+];
     }
 
     impl ::serde::Serialize for $name {
@@ -574,11 +1316,15 @@ impl_enum_string_traits!(RevertStrings {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ModelCheckerEngine {
   Bmc,
+  Chc,
+  All,
   None,
 }
 
 impl_enum_string_traits!(ModelCheckerEngine {
   Bmc => "bmc",
+  Chc => "chc",
+  All => "all",
   None => "none"
 });
 
@@ -633,7 +1379,9 @@ impl_enum_string_traits!(ModelCheckerInvariantKind {
   Contract => "contract"
 });
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Ordered oldest-to-newest so callers (e.g. [`crate::internal::restrictions`]) can express
+/// "at least"/"at most" bounds with ordinary comparisons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum EvmVersion {
   Byzantium,
   Constantinople,
@@ -660,6 +1408,153 @@ impl_enum_string_traits!(EvmVersion {
   Prague => "prague"
 });
 
+/// Named starting points that expand into a baseline [`CompilerSettingsOptions`], following the
+/// same small-set-of-variants-expands-to-config idea as docker-compose-types profiles. Applied
+/// first by [`TryFrom<&JsCompilerSettingsOptions>`], then overlaid with whatever the rest of the
+/// settings object explicitly sets via [`merge_settings_json`] -- an explicit `optimizer.runs`
+/// still wins over the profile's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompilationProfile {
+  /// Optimizer enabled at 200 runs; metadata trimmed for reproducible, deploy-ready bytecode.
+  Production,
+  /// Verbose revert strings and full debug info for local iteration.
+  Debug,
+  /// Optimizer tuned for bytecode size over gas, with the Yul optimiser enabled.
+  Size,
+  /// Optimizer disabled outright, trading runtime gas for the fastest possible compile.
+  Fast,
+}
+
+impl_enum_string_traits!(CompilationProfile {
+  Production => "production",
+  Debug => "debug",
+  Size => "size",
+  Fast => "fast"
+});
+
+/// Baseline [`CompilerSettingsOptions`] a [`CompilationProfile`] expands into before the user's
+/// own overrides are layered on top.
+fn profile_baseline(profile: CompilationProfile) -> CompilerSettingsOptions {
+  match profile {
+    CompilationProfile::Production => CompilerSettingsOptions {
+      optimizer: Some(OptimizerSettingsOptions {
+        enabled: Some(true),
+        runs: Some(200),
+        ..Default::default()
+      }),
+      metadata: Some(SettingsMetadataOptions {
+        bytecode_hash: Some(BytecodeHash::None),
+        cbor_metadata: Some(false),
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+    CompilationProfile::Debug => CompilerSettingsOptions {
+      debug: Some(DebuggingSettingsOptions {
+        revert_strings: Some(RevertStrings::Debug),
+        debug_info: vec![
+          "location".to_string(),
+          "snippet".to_string(),
+          "ast-id".to_string(),
+        ],
+      }),
+      ..Default::default()
+    },
+    CompilationProfile::Size => CompilerSettingsOptions {
+      optimizer: Some(OptimizerSettingsOptions {
+        enabled: Some(true),
+        runs: Some(1),
+        details: Some(OptimizerDetailsOptions {
+          yul: Some(true),
+          ..Default::default()
+        }),
+      }),
+      ..Default::default()
+    },
+    CompilationProfile::Fast => CompilerSettingsOptions {
+      optimizer: Some(OptimizerSettingsOptions {
+        enabled: Some(false),
+        ..Default::default()
+      }),
+      ..Default::default()
+    },
+  }
+}
+
+/// The [`resolve_profile`] preset names, in the order tried for "did you mean" suggestions.
+const SETTINGS_PRESET_NAMES: &[&str] = &["debug", "release", "size", "verify"];
+
+/// Baseline [`CompilerSettingsOptions`] a [`resolve_profile`] preset name expands into. A separate
+/// namespace from [`CompilationProfile`] (which backs the napi-facing `profile` field): these
+/// presets are meant for Rust callers building a [`Settings`] directly -- e.g. a CLI -- rather
+/// than through the JS bindings, and follow compiler opt-level naming (`debug`/`release`/`size`)
+/// plus a `verify` preset that turns on the model checker.
+fn settings_preset_overrides(preset: &str) -> Result<CompilerSettingsOptions> {
+  match preset {
+    "debug" => Ok(CompilerSettingsOptions {
+      debug: Some(DebuggingSettingsOptions {
+        revert_strings: Some(RevertStrings::Debug),
+        debug_info: vec!["*".to_string()],
+      }),
+      ..Default::default()
+    }),
+    "release" => Ok(CompilerSettingsOptions {
+      optimizer: Some(OptimizerSettingsOptions {
+        enabled: Some(true),
+        runs: Some(10_000),
+        ..Default::default()
+      }),
+      via_ir: Some(true),
+      ..Default::default()
+    }),
+    "size" => Ok(CompilerSettingsOptions {
+      optimizer: Some(OptimizerSettingsOptions {
+        enabled: Some(true),
+        runs: Some(1),
+        details: Some(OptimizerDetailsOptions {
+          yul: Some(true),
+          ..Default::default()
+        }),
+      }),
+      ..Default::default()
+    }),
+    "verify" => Ok(CompilerSettingsOptions {
+      model_checker: Some(ModelCheckerSettingsOptions {
+        engine: Some(ModelCheckerEngine::Chc),
+        solvers: Some(vec![ModelCheckerSolver::Chc]),
+        ..Default::default()
+      }),
+      ..Default::default()
+    }),
+    unknown => {
+      let mut message = format!("Unknown settings profile `{unknown}`");
+      if let Some(suggestion) = did_you_mean(unknown, SETTINGS_PRESET_NAMES) {
+        message.push_str(&format!(" (did you mean `{suggestion}`?)"));
+      }
+      Err(napi_error(message))
+    }
+  }
+}
+
+/// Expands `profile` (one of [`SETTINGS_PRESET_NAMES`]) into its baseline options and layers it
+/// onto `base`, then layers `user_overrides` on top of that using the same merge semantics as
+/// [`CompilerSettingsOptions::overlay`] -- an explicit user override always wins over the
+/// profile's, and the profile always wins over `base`. Mirrors how compilers layer opt-level
+/// presets (`-O0`/`-O2`/`-Os`) over a baseline configuration. Unlike [`merge_settings`], this
+/// doesn't take a `solc_version` and so skips its version-gated validation; callers that need that
+/// should run the result through [`sanitize_settings`] themselves.
+pub fn resolve_profile(
+  base: &Settings,
+  profile: &str,
+  user_overrides: Option<&CompilerSettingsOptions>,
+) -> Result<Settings> {
+  let mut settings = settings_preset_overrides(profile)?.overlay(base)?;
+  if let Some(overrides) = user_overrides {
+    settings = overrides.clone().overlay(&settings)?;
+  }
+  Ok(settings)
+}
+
 impl TryFrom<&JsCompilerSettingsOptions> for CompilerSettingsOptions {
   type Error = napi::Error;
 
@@ -668,10 +1563,30 @@ impl TryFrom<&JsCompilerSettingsOptions> for CompilerSettingsOptions {
       serde_json::to_value(options),
       "Failed to serialise compiler settings",
     )?;
-    map_napi_error(
+    let mut settings: CompilerSettingsOptions = map_napi_error(
       serde_json::from_value(json),
       "Failed to convert compiler settings",
-    )
+    )?;
+    settings.include_ast = options.include_ast;
+
+    if let Some(profile) = options.profile {
+      let mut merged = map_napi_error(
+        serde_json::to_value(profile_baseline(profile)),
+        "Failed to serialise compilation profile",
+      )?;
+      let overrides = map_napi_error(
+        serde_json::to_value(&settings),
+        "Failed to serialise compiler settings",
+      )?;
+      merge_settings_json(&mut merged, overrides);
+      settings = map_napi_error(
+        serde_json::from_value(merged),
+        "Failed to parse compiler settings",
+      )?;
+      settings.include_ast = options.include_ast;
+    }
+
+    Ok(settings)
   }
 }
 
@@ -697,7 +1612,7 @@ mod tests {
       &base.output_selection
     ));
 
-    let sanitised = sanitize_settings(&base).expect("sanitize");
+    let sanitised = sanitize_settings(&base, &Version::new(0, 8, 30)).expect("sanitize");
     assert!(
       !output_selection_is_effectively_empty(&sanitised.output_selection),
       "sanitised selection should fall back to defaults"
@@ -715,7 +1630,7 @@ mod tests {
       "ast output selection should be considered non-empty"
     );
 
-    let sanitised = sanitize_settings(&settings).expect("sanitize");
+    let sanitised = sanitize_settings(&settings, &Version::new(0, 8, 30)).expect("sanitize");
     assert_eq!(
       sanitised.stop_after.as_deref(),
       Some("parsing"),
@@ -727,10 +1642,261 @@ mod tests {
     );
   }
 
+  #[test]
+  fn settings_fingerprint_is_stable_for_equal_values() {
+    let first = Settings::default();
+    let second = Settings::default();
+    assert_eq!(
+      serde_json::to_value(&first).unwrap(),
+      serde_json::to_value(&second).unwrap()
+    );
+    assert_eq!(settings_fingerprint(&first), settings_fingerprint(&second));
+  }
+
+  #[test]
+  fn settings_fingerprint_changes_when_settings_change() {
+    let mut base = Settings::default();
+    let baseline = settings_fingerprint(&base);
+
+    base.via_ir = Some(true);
+    let changed = settings_fingerprint(&base);
+
+    assert_ne!(baseline, changed);
+  }
+
+  #[test]
+  fn settings_fingerprint_unifies_empty_and_sanitized_output_selection() {
+    let mut empty_selection = Settings::default();
+    empty_selection.output_selection = OutputSelection::default();
+    assert!(output_selection_is_effectively_empty(
+      &empty_selection.output_selection
+    ));
+
+    let sanitized = sanitize_settings(&empty_selection, &Version::new(0, 8, 30)).expect("sanitize");
+
+    assert_eq!(
+      settings_fingerprint(&empty_selection),
+      settings_fingerprint(&sanitized),
+      "an effectively-empty selection and its sanitized default should hash identically"
+    );
+  }
+
+  #[test]
+  fn validate_settings_is_empty_for_default_settings() {
+    assert!(validate_settings(&Settings::default()).is_empty());
+  }
+
+  #[test]
+  fn validate_settings_flags_optimizer_runs_without_enabled() {
+    let mut settings = Settings::default();
+    let mut value = serde_json::to_value(&settings).unwrap();
+    value["optimizer"]["runs"] = json!(200);
+    settings = serde_json::from_value(value).unwrap();
+
+    let warnings = validate_settings(&settings);
+    assert!(warnings.iter().any(|w| w.code == "optimizer-runs-without-enabled"));
+  }
+
+  #[test]
+  fn validate_settings_flags_cbor_metadata_without_bytecode_hash() {
+    let mut settings = Settings::default();
+    let mut value = serde_json::to_value(&settings).unwrap();
+    value["metadata"]["bytecodeHash"] = json!("none");
+    value["metadata"]["cborMetadata"] = json!(true);
+    settings = serde_json::from_value(value).unwrap();
+
+    let warnings = validate_settings(&settings);
+    assert!(warnings.iter().any(|w| w.code == "cbor-metadata-without-bytecode-hash"));
+  }
+
+  #[test]
+  fn validate_settings_flags_via_ir_with_stop_after_parsing() {
+    let mut settings = Settings::default();
+    settings.via_ir = Some(true);
+    settings.stop_after = Some("parsing".to_string());
+
+    let warnings = validate_settings(&settings);
+    assert!(warnings.iter().any(|w| w.code == "via-ir-with-stop-after-parsing"));
+  }
+
+  #[test]
+  fn validate_settings_flags_model_checker_engine_without_scope() {
+    let mut settings = Settings::default();
+    let mut value = serde_json::to_value(&settings).unwrap();
+    value["modelChecker"] = json!({ "engine": "chc" });
+    settings = serde_json::from_value(value).unwrap();
+
+    let warnings = validate_settings(&settings);
+    assert!(warnings.iter().any(|w| w.code == "model-checker-engine-without-scope"));
+  }
+
+  #[test]
+  fn validate_settings_flags_output_selection_incompatible_with_stop_after() {
+    let mut settings = Settings::default();
+    settings.stop_after = Some("parsing".to_string());
+    settings.output_selection = narrow_output_selection(&["abi"], false);
+
+    let warnings = validate_settings(&settings);
+    assert!(warnings
+      .iter()
+      .any(|w| w.code == "output-selection-incompatible-with-stop-after"));
+  }
+
+  #[test]
+  fn sanitize_settings_with_warnings_returns_both_sanitized_settings_and_warnings() {
+    let mut settings = Settings::default();
+    settings.via_ir = Some(true);
+    settings.stop_after = Some("parsing".to_string());
+
+    let (sanitized, warnings) =
+      sanitize_settings_with_warnings(&settings, &Version::new(0, 8, 30)).expect("sanitize");
+    assert!(!output_selection_is_effectively_empty(&sanitized.output_selection));
+    assert!(warnings.iter().any(|w| w.code == "via-ir-with-stop-after-parsing"));
+  }
+
+  #[test]
+  fn narrow_output_selection_excludes_ast_by_default() {
+    let selection = narrow_output_selection(&["abi"], false);
+    let per_contract = selection.as_ref().get("*").expect("wildcard file entry");
+    assert_eq!(per_contract.get("*"), Some(&vec!["abi".to_string()]));
+    assert!(!per_contract.contains_key(""));
+  }
+
+  #[test]
+  fn narrow_output_selection_includes_ast_when_requested() {
+    let selection = narrow_output_selection(&["abi"], true);
+    let per_contract = selection.as_ref().get("*").expect("wildcard file entry");
+    assert_eq!(per_contract.get(""), Some(&vec!["ast".to_string()]));
+  }
+
+  #[test]
+  fn output_selection_builder_adds_contract_and_file_level_outputs() {
+    let selection = OutputSelectionBuilder::new()
+      .wildcard(OutputArtifact::Abi)
+      .for_contract("Example.sol", "Example", OutputArtifact::EvmBytecodeObject)
+      .for_file("Example.sol", OutputArtifact::Ast)
+      .build();
+
+    let as_ref = selection.as_ref();
+    assert_eq!(
+      as_ref.get("*").and_then(|by_contract| by_contract.get("*")),
+      Some(&vec!["abi".to_string()])
+    );
+    assert_eq!(
+      as_ref
+        .get("Example.sol")
+        .and_then(|by_contract| by_contract.get("Example")),
+      Some(&vec!["evm.bytecode.object".to_string()])
+    );
+    assert_eq!(
+      as_ref
+        .get("Example.sol")
+        .and_then(|by_contract| by_contract.get("")),
+      Some(&vec!["ast".to_string()])
+    );
+  }
+
+  #[test]
+  fn output_selection_builder_add_is_idempotent() {
+    let selection = OutputSelectionBuilder::new()
+      .wildcard(OutputArtifact::Abi)
+      .wildcard(OutputArtifact::Abi)
+      .build();
+    assert_eq!(
+      selection.as_ref().get("*").and_then(|by_contract| by_contract.get("*")),
+      Some(&vec!["abi".to_string()])
+    );
+  }
+
+  #[test]
+  fn output_selection_builder_contains_reports_requested_outputs() {
+    let builder = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi);
+    assert!(builder.contains("*", "*", OutputArtifact::Abi));
+    assert!(!builder.contains("*", "*", OutputArtifact::Metadata));
+  }
+
+  #[test]
+  fn output_selection_builder_union_adds_without_dropping_existing() {
+    let base = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi).build();
+    let extra = OutputSelectionBuilder::new().wildcard(OutputArtifact::Metadata).build();
+
+    let merged = OutputSelectionBuilder::from(&base).union(&extra).build();
+    let outputs = merged.as_ref().get("*").and_then(|by_contract| by_contract.get("*"));
+    assert_eq!(
+      outputs,
+      Some(&vec!["abi".to_string(), "metadata".to_string()])
+    );
+  }
+
+  #[test]
+  fn output_selection_builder_difference_removes_requested_outputs() {
+    let base = OutputSelectionBuilder::new()
+      .wildcard(OutputArtifact::Abi)
+      .wildcard(OutputArtifact::Metadata)
+      .build();
+    let remove = OutputSelectionBuilder::new().wildcard(OutputArtifact::Metadata).build();
+
+    let remaining = OutputSelectionBuilder::from(&base).difference(&remove).build();
+    assert_eq!(
+      remaining.as_ref().get("*").and_then(|by_contract| by_contract.get("*")),
+      Some(&vec!["abi".to_string()])
+    );
+  }
+
+  #[test]
+  fn output_selection_builder_difference_drops_now_empty_entries() {
+    let base = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi).build();
+    let remove = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi).build();
+
+    let remaining = OutputSelectionBuilder::from(&base).difference(&remove).build();
+    assert!(remaining.as_ref().is_empty());
+  }
+
+  #[test]
+  fn merge_output_selections_unions_by_default_strategy() {
+    let base = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi).build();
+    let overlay = OutputSelectionBuilder::new().wildcard(OutputArtifact::Metadata).build();
+
+    let merged = merge_output_selections(&base, &overlay, MergeStrategy::Union);
+    let outputs = merged.as_ref().get("*").and_then(|by_contract| by_contract.get("*"));
+    assert_eq!(
+      outputs,
+      Some(&vec!["abi".to_string(), "metadata".to_string()])
+    );
+  }
+
+  #[test]
+  fn merge_output_selections_replace_discards_base() {
+    let base = OutputSelectionBuilder::new().wildcard(OutputArtifact::Abi).build();
+    let overlay = OutputSelectionBuilder::new().wildcard(OutputArtifact::Metadata).build();
+
+    let merged = merge_output_selections(&base, &overlay, MergeStrategy::Replace);
+    let outputs = merged.as_ref().get("*").and_then(|by_contract| by_contract.get("*"));
+    assert_eq!(outputs, Some(&vec!["metadata".to_string()]));
+  }
+
+  #[test]
+  fn add_source_map_outputs_appends_without_dropping_existing_outputs() {
+    let selection = narrow_output_selection(&["abi"], false);
+    let augmented = add_source_map_outputs(&selection);
+    let per_contract = augmented.as_ref().get("*").expect("wildcard file entry");
+    let outputs = per_contract.get("*").expect("wildcard contract entry");
+    assert!(outputs.contains(&"abi".to_string()));
+    assert!(outputs.contains(&"evm.bytecode.sourceMap".to_string()));
+    assert!(outputs.contains(&"evm.deployedBytecode.sourceMap".to_string()));
+  }
+
+  #[test]
+  fn add_source_map_outputs_is_idempotent() {
+    let once = add_source_map_outputs(&narrow_output_selection(&["abi"], false));
+    let twice = add_source_map_outputs(&once);
+    assert_eq!(once, twice);
+  }
+
   #[test]
   fn merge_preserves_base_when_no_overrides() {
     let base = Settings::default();
-    let merged = merge_settings(&base, None).expect("merge");
+    let merged = merge_settings(&base, None, &Version::new(0, 8, 30)).expect("merge");
     assert_eq!(
       serde_json::to_value(&base).unwrap(),
       serde_json::to_value(&merged).unwrap()
@@ -744,13 +1910,65 @@ mod tests {
     let selection = OutputSelection::ast_output_selection();
     overrides.output_selection = Some(selection.as_ref().clone());
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("merge");
+    let merged = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge");
     assert_eq!(
       merged.output_selection, selection,
       "merge should replace base output selection with override"
     );
   }
 
+  #[test]
+  fn merge_appends_remappings_instead_of_replacing() {
+    let mut base_json = serde_json::to_value(Settings::default()).expect("serialize base settings");
+    base_json["remappings"] = json!(["a/=a/"]);
+    let base: Settings = serde_json::from_value(base_json).expect("parse base settings");
+
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.remappings = Some(vec!["b/=b/".to_string()]);
+
+    let merged = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge");
+    let remapped: Vec<String> = merged.remappings.iter().map(|remapping| remapping.to_string()).collect();
+    assert_eq!(
+      remapped,
+      vec!["a/=a/".to_string(), "b/=b/".to_string()],
+      "override remappings should be appended after the base remappings, not replace them"
+    );
+  }
+
+  #[test]
+  fn merge_unions_output_selection_arrays_and_keeps_untouched_contracts() {
+    let mut base_json = serde_json::to_value(Settings::default()).expect("serialize base settings");
+    base_json["outputSelection"] = json!({
+      "Base.sol": { "*": ["abi"] },
+      "Other.sol": { "*": ["abi"] },
+    });
+    let base: Settings = serde_json::from_value(base_json).expect("parse base settings");
+
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.output_selection = Some(BTreeMap::from([(
+      "Base.sol".to_string(),
+      BTreeMap::from([("*".to_string(), vec!["abi".to_string(), "metadata".to_string()])]),
+    )]));
+
+    let merged = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge");
+    let selection = merged.output_selection.as_ref();
+
+    let base_outputs = selection
+      .get("Base.sol")
+      .and_then(|contracts| contracts.get("*"))
+      .expect("Base.sol wildcard outputs");
+    assert_eq!(
+      base_outputs,
+      &vec!["abi".to_string(), "metadata".to_string()],
+      "duplicate outputs between base and override should be deduped, not doubled"
+    );
+
+    assert!(
+      selection.contains_key("Other.sol"),
+      "contracts not mentioned in the override should survive the merge"
+    );
+  }
+
   #[test]
   fn merge_applies_overrides() {
     let base = Settings::default();
@@ -769,6 +1987,7 @@ mod tests {
     overrides.model_checker = Some(ModelCheckerSettingsOptions {
       engine: Some(ModelCheckerEngine::Bmc),
       timeout: Some(1),
+      solvers: Some(vec![ModelCheckerSolver::Bmc]),
       ..Default::default()
     });
     overrides.metadata = Some(SettingsMetadataOptions {
@@ -793,7 +2012,7 @@ mod tests {
       )]),
     )]));
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("merge");
+    let merged = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge");
 
     let as_json = serde_json::to_value(&merged).expect("serialize settings");
 
@@ -816,4 +2035,315 @@ mod tests {
       json!("0x0000000000000000000000000000000000000001")
     );
   }
+
+  #[test]
+  fn merge_rejects_via_ir_below_minimum_version() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.via_ir = Some(true);
+
+    let err = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 12))
+      .expect_err("viaIR below 0.8.13 should be rejected");
+    assert!(err.reason.contains("viaIR"));
+    assert!(err.reason.contains("0.8.13"));
+  }
+
+  #[test]
+  fn merge_rejects_model_checker_engine_below_minimum_version() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::Bmc),
+      ..Default::default()
+    });
+
+    let err = merge_settings(&base, Some(&overrides), &Version::new(0, 7, 6))
+      .expect_err("modelChecker.engine below 0.8.0 should be rejected");
+    assert!(err.reason.contains("modelChecker.engine"));
+  }
+
+  #[test]
+  fn production_profile_expands_into_optimizer_and_metadata() {
+    let options = JsCompilerSettingsOptions {
+      profile: Some(CompilationProfile::Production),
+      ..Default::default()
+    };
+    let settings = CompilerSettingsOptions::try_from(&options).expect("convert profile");
+
+    let optimizer = settings.optimizer.expect("optimizer set by profile");
+    assert_eq!(optimizer.enabled, Some(true));
+    assert_eq!(optimizer.runs, Some(200));
+    let metadata = settings.metadata.expect("metadata set by profile");
+    assert_eq!(metadata.bytecode_hash, Some(BytecodeHash::None));
+    assert_eq!(metadata.cbor_metadata, Some(false));
+  }
+
+  #[test]
+  fn explicit_override_wins_over_profile_baseline() {
+    let options = JsCompilerSettingsOptions {
+      profile: Some(CompilationProfile::Production),
+      optimizer: Some(JsOptimizerSettingsOptions {
+        runs: Some(999),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    let settings = CompilerSettingsOptions::try_from(&options).expect("convert profile");
+
+    let optimizer = settings.optimizer.expect("optimizer set");
+    assert_eq!(optimizer.runs, Some(999), "explicit override should win");
+    assert_eq!(
+      optimizer.enabled,
+      Some(true),
+      "profile-only fields should still apply"
+    );
+  }
+
+  #[test]
+  fn resolve_profile_applies_debug_preset() {
+    let settings =
+      resolve_profile(&Settings::default(), "debug", None).expect("resolve debug profile");
+    let as_json = serde_json::to_value(&settings).expect("serialize settings");
+    assert_eq!(as_json["debug"]["revertStrings"], json!("debug"));
+    assert_eq!(as_json["debug"]["debugInfo"], json!(["*"]));
+  }
+
+  #[test]
+  fn resolve_profile_applies_release_preset() {
+    let settings =
+      resolve_profile(&Settings::default(), "release", None).expect("resolve release profile");
+    let as_json = serde_json::to_value(&settings).expect("serialize settings");
+    assert_eq!(as_json["optimizer"]["enabled"], json!(true));
+    assert_eq!(as_json["optimizer"]["runs"], json!(10_000));
+    assert_eq!(as_json["viaIR"], json!(true));
+  }
+
+  #[test]
+  fn resolve_profile_applies_size_preset() {
+    let settings =
+      resolve_profile(&Settings::default(), "size", None).expect("resolve size profile");
+    let as_json = serde_json::to_value(&settings).expect("serialize settings");
+    assert_eq!(as_json["optimizer"]["runs"], json!(1));
+    assert_eq!(as_json["optimizer"]["details"]["yul"], json!(true));
+  }
+
+  #[test]
+  fn resolve_profile_applies_verify_preset() {
+    let settings =
+      resolve_profile(&Settings::default(), "verify", None).expect("resolve verify profile");
+    let as_json = serde_json::to_value(&settings).expect("serialize settings");
+    assert_eq!(as_json["modelChecker"]["engine"], json!("chc"));
+    assert_eq!(as_json["modelChecker"]["solvers"], json!(["chc"]));
+  }
+
+  #[test]
+  fn resolve_profile_user_overrides_win_over_preset() {
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.optimizer = Some(OptimizerSettingsOptions {
+      runs: Some(999),
+      ..Default::default()
+    });
+
+    let settings = resolve_profile(&Settings::default(), "release", Some(&overrides))
+      .expect("resolve release profile with overrides");
+    let as_json = serde_json::to_value(&settings).expect("serialize settings");
+    assert_eq!(
+      as_json["optimizer"]["runs"],
+      json!(999),
+      "explicit user override should win"
+    );
+    assert_eq!(
+      as_json["optimizer"]["enabled"],
+      json!(true),
+      "preset-only fields should still apply"
+    );
+  }
+
+  #[test]
+  fn resolve_profile_rejects_unknown_preset_with_suggestion() {
+    let err =
+      resolve_profile(&Settings::default(), "relese", None).expect_err("unknown preset");
+    assert!(err.reason.contains("Unknown settings profile"));
+    assert!(err.reason.contains("did you mean `release`?"), "{}", err.reason);
+  }
+
+  #[test]
+  fn to_standard_json_assembles_language_sources_and_settings() {
+    let settings = Settings::default();
+    let sources = BTreeMap::from([(
+      "Contract.sol".to_string(),
+      "pragma solidity ^0.8.0; contract Contract {}".to_string(),
+    )]);
+
+    let document = to_standard_json(&settings, sources);
+    assert_eq!(document["language"], json!("Solidity"));
+    assert!(document["sources"]["Contract.sol"]["content"]
+      .as_str()
+      .unwrap()
+      .contains("contract Contract"));
+    assert!(
+      !output_selection_is_effectively_empty(
+        &serde_json::from_value(document["settings"]["outputSelection"].clone())
+          .expect("parse output selection")
+      ),
+      "empty output selection should be backfilled with the default"
+    );
+  }
+
+  #[test]
+  fn from_standard_json_parses_settings_block() {
+    let document = json!({
+      "language": "Solidity",
+      "sources": {},
+      "settings": {
+        "viaIR": true,
+        "remappings": ["lib/=lib/"],
+      }
+    });
+
+    let settings = from_standard_json(&document).expect("parse standard json");
+    assert_eq!(settings.via_ir, Some(true));
+    assert_eq!(settings.remappings, Some(vec!["lib/=lib/".to_string()]));
+  }
+
+  #[test]
+  fn from_standard_json_treats_missing_settings_as_empty() {
+    let document = json!({ "language": "Solidity", "sources": {} });
+    let settings = from_standard_json(&document).expect("parse standard json");
+    assert!(settings.via_ir.is_none());
+  }
+
+  fn cli_args(flags: &[&str]) -> Vec<String> {
+    flags.iter().map(|flag| flag.to_string()).collect()
+  }
+
+  #[test]
+  fn parse_cli_overrides_maps_known_flags() {
+    let options = parse_cli_overrides(&cli_args(&[
+      "--optimize",
+      "--optimize-runs",
+      "200",
+      "--via-ir",
+      "--evm-version",
+      "prague",
+      "--metadata-hash",
+      "none",
+      "-R",
+      "lib/=lib/",
+    ]))
+    .expect("parse cli overrides");
+
+    let optimizer = options.optimizer.expect("optimizer options");
+    assert_eq!(optimizer.enabled, Some(true));
+    assert_eq!(optimizer.runs, Some(200));
+    assert_eq!(options.via_ir, Some(true));
+    assert_eq!(options.evm_version, Some(EvmVersion::Prague));
+    assert_eq!(
+      options.metadata.expect("metadata options").bytecode_hash,
+      Some(BytecodeHash::None)
+    );
+    assert_eq!(options.remappings, Some(vec!["lib/=lib/".to_string()]));
+  }
+
+  #[test]
+  fn parse_cli_overrides_accumulates_repeated_remappings() {
+    let options = parse_cli_overrides(&cli_args(&["-R", "a/=a/", "-R", "b/=b/"]))
+      .expect("parse cli overrides");
+    assert_eq!(
+      options.remappings,
+      Some(vec!["a/=a/".to_string(), "b/=b/".to_string()])
+    );
+  }
+
+  #[test]
+  fn parse_cli_overrides_rejects_unknown_flag() {
+    let err = parse_cli_overrides(&cli_args(&["--not-a-flag"])).unwrap_err();
+    assert!(err.reason.contains("Unrecognized flag"));
+  }
+
+  #[test]
+  fn parse_cli_overrides_rejects_flag_missing_value() {
+    let err = parse_cli_overrides(&cli_args(&["--evm-version"])).unwrap_err();
+    assert!(err.reason.contains("expects a value"));
+  }
+
+  #[test]
+  fn parse_cli_overrides_suggests_close_evm_version() {
+    let err = parse_cli_overrides(&cli_args(&["--evm-version", "pragu"])).unwrap_err();
+    assert!(err.reason.contains("did you mean `prague`?"), "{}", err.reason);
+  }
+
+  #[test]
+  fn parse_cli_overrides_suggests_close_bytecode_hash() {
+    let err = parse_cli_overrides(&cli_args(&["--metadata-hash", "non"])).unwrap_err();
+    assert!(err.reason.contains("did you mean `none`?"), "{}", err.reason);
+  }
+
+  #[test]
+  fn edit_distance_matches_known_values() {
+    assert_eq!(edit_distance("prague", "prague"), 0);
+    assert_eq!(edit_distance("pragu", "prague"), 1);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+  }
+
+  #[test]
+  fn model_checker_engine_round_trips_chc_and_all() {
+    assert_eq!(
+      serde_json::from_value::<ModelCheckerEngine>(json!("chc")).expect("parse chc"),
+      ModelCheckerEngine::Chc
+    );
+    assert_eq!(
+      serde_json::from_value::<ModelCheckerEngine>(json!("all")).expect("parse all"),
+      ModelCheckerEngine::All
+    );
+    assert_eq!(serde_json::to_value(ModelCheckerEngine::Chc).unwrap(), json!("chc"));
+    assert_eq!(serde_json::to_value(ModelCheckerEngine::All).unwrap(), json!("all"));
+  }
+
+  #[test]
+  fn merge_rejects_model_checker_engine_without_solvers() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::Chc),
+      ..Default::default()
+    });
+
+    let err = merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30))
+      .expect_err("engine without solvers should be rejected");
+    assert!(err.reason.contains("modelChecker.solvers"));
+  }
+
+  #[test]
+  fn merge_accepts_model_checker_engine_with_solvers() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::All),
+      solvers: Some(vec![ModelCheckerSolver::Chc, ModelCheckerSolver::Bmc]),
+      ..Default::default()
+    });
+
+    let merged =
+      merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge succeeds");
+    let as_json = serde_json::to_value(&merged).expect("serialize settings");
+    assert_eq!(as_json["modelChecker"]["engine"], json!("all"));
+  }
+
+  #[test]
+  fn merge_applies_bmc_loop_iterations() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::Bmc),
+      solvers: Some(vec![ModelCheckerSolver::Bmc]),
+      bmc_loop_iterations: Some(5),
+      ..Default::default()
+    });
+
+    let merged =
+      merge_settings(&base, Some(&overrides), &Version::new(0, 8, 30)).expect("merge succeeds");
+    let as_json = serde_json::to_value(&merged).expect("serialize settings");
+    assert_eq!(as_json["modelChecker"]["bmcLoopIterations"], json!(5));
+  }
 }
@@ -1,12 +1,16 @@
+use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
 use log::error;
-use semver::Version;
+use semver::{Version, VersionReq};
 
 use foundry_compilers::solc::{Solc, SolcLanguage};
 use napi::{bindgen_prelude::AsyncTask, Env, Task};
 
+use super::config::{max_evm_version_for_solc, SolcConfig};
 use super::errors::{map_err_with_context, to_napi_result, Error, Result};
+use super::pragma;
+use super::settings::EvmVersion;
 
 const LOG_TARGET: &str = "tevm::solc";
 
@@ -25,23 +29,186 @@ pub(crate) fn default_version() -> Result<Version> {
   parse_version(DEFAULT_SOLC_VERSION)
 }
 
+/// Scans `source` for `pragma solidity` declarations and combines them into a single
+/// [`VersionReq`], delegating to the same extraction [`pragma`] uses for per-project detection.
+/// A source with no pragma at all imposes [`DEFAULT_SOLC_VERSION`] rather than leaving the
+/// requirement unconstrained, so a single-file caller still gets a deterministic version.
+pub(crate) fn detect_version(source: &str) -> Result<VersionReq> {
+  let requirements = pragma::extract_requirements(source)?;
+  if requirements.is_empty() {
+    return VersionReq::parse(&format!("={DEFAULT_SOLC_VERSION}"))
+      .map_err(|err| Error::new(format!("Failed to build default solc version requirement: {err}")));
+  }
+  pragma::merge_requirements(&requirements)
+}
+
+/// Clamps `evm` down to the newest EVM target `version` actually understands, reusing the same
+/// per-release support thresholds [`crate::internal::config`] applies when normalizing an
+/// `evmVersion` settings override (Constantinople at 0.4.21, Petersburg at 0.5.5, Istanbul at
+/// 0.5.14, Berlin at 0.8.5, London at 0.8.7, and so on). Never errors: a target the compiler
+/// can't produce is silently downgraded to its highest supported equivalent so compiling an
+/// older pragma with a newer default `evmVersion` doesn't fail outright.
+pub(crate) fn normalize_evm_version(version: &Version, evm: EvmVersion) -> EvmVersion {
+  evm.min(max_evm_version_for_solc(version))
+}
+
+fn find_max_installed_matching(requirement: &VersionReq) -> Option<Version> {
+  Solc::installed_versions()
+    .into_iter()
+    .filter(|version| requirement.matches(version))
+    .max()
+}
+
+/// Picks the highest installed solc release satisfying `requirement`, the single-version
+/// counterpart to [`pragma::resolve_version`] for callers that already have a merged
+/// [`VersionReq`] (e.g. from [`detect_version`]) rather than a set of per-file groups. Never
+/// downloads: this is the offline-safe half of version resolution, used whenever
+/// `CompilerConfig::offline_mode` is set (see [`super::graph::resolve_compilation_buckets`]).
+pub(crate) fn ensure_compatible(requirement: &VersionReq) -> Result<Solc> {
+  let version = find_max_installed_matching(requirement)
+    .ok_or_else(|| no_match_error(requirement))?;
+  ensure_installed(&version)
+}
+
+/// Describes a failed [`ensure_compatible`] lookup with the requirement searched for and the
+/// full set of installed versions that were checked against it, so a sandboxed/offline caller
+/// can tell at a glance whether the fix is `installSolcVersion` or a remapping/pragma mistake.
+fn no_match_error(requirement: &VersionReq) -> Error {
+  Error::missing_solc_version(
+    requirement.to_string(),
+    format!(
+      "No installed solc version satisfies requirement `{requirement}`. Searched {} and found: {}. \
+       Call installSolcVersion first.",
+      svm_install_dir().display(),
+      describe_installed_versions()
+    ),
+  )
+}
+
+/// Where `svm` persists installed solc releases: `$SVM_HOME` when set, otherwise its documented
+/// default of `~/.svm`. Purely descriptive -- `Solc::installed_versions`/`find_svm_installed_version`
+/// resolve this themselves and are the actual source of truth -- this just lets a "not installed"
+/// error name the directory a sandboxed/offline caller should go check.
+fn svm_install_dir() -> PathBuf {
+  if let Some(home) = std::env::var_os("SVM_HOME") {
+    return PathBuf::from(home);
+  }
+  std::env::var_os("HOME")
+    .map(PathBuf::from)
+    .unwrap_or_else(|| PathBuf::from("."))
+    .join(".svm")
+}
+
+fn describe_installed_versions() -> String {
+  let installed = Solc::installed_versions();
+  if installed.is_empty() {
+    return "no solc versions installed".to_string();
+  }
+  installed
+    .iter()
+    .map(|version| version.to_string())
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Like [`ensure_compatible`], but falls back to resolving and installing the newest published
+/// release satisfying `requirement` from the upstream SVM release list when nothing installed
+/// matches. Installation is guarded by [`install_mutex`] and re-checks for an installed match
+/// after acquiring the lock, so two concurrent callers racing on the same requirement don't
+/// download the same release twice.
+pub(crate) fn ensure_installed_req(requirement: &VersionReq) -> Result<Solc> {
+  if let Some(version) = find_max_installed_matching(requirement) {
+    return ensure_installed(&version);
+  }
+
+  let _guard = install_mutex()
+    .lock()
+    .map_err(|err| Error::new(format!("Solc install mutex poisoned: {err}")))?;
+
+  if let Some(version) = find_max_installed_matching(requirement) {
+    return ensure_installed(&version);
+  }
+
+  map_err_with_context(
+    Solc::find_or_install(requirement),
+    format!("Failed to resolve and install a solc version satisfying `{requirement}`"),
+  )
+}
+
 pub(crate) fn ensure_installed(version: &Version) -> Result<Solc> {
-  if let Some(solc) = find_installed_version(version)? {
+  ensure_installed_with_args(version, &[])
+}
+
+/// Like [`ensure_installed`], but wraps the resolved binary with `extra_args` (e.g.
+/// `["docker", "run", "--rm", "ethereum/solc:0.8.20"]` to run solc through a container, or
+/// `["--eof-version", "1"]` for a custom flag) prepended ahead of whatever arguments the
+/// compiler generates internally, matching `Solc::new_with_args` semantics: when solc is
+/// actually a wrapper binary, the wrapper's own arguments must come first so the wrapped
+/// executable sees them as its leading tokens rather than trailing flags meant for solc itself.
+pub(crate) fn ensure_installed_with_args(version: &Version, extra_args: &[String]) -> Result<Solc> {
+  if let Some(solc) = find_installed_version_with_args(version, extra_args)? {
     return Ok(solc);
   }
-  error!(target: LOG_TARGET, "Solc {} is not installed. Call installSolcVersion first.", version);
-  Err(Error::new(format!(
-    "Solc {} is not installed. Call installSolcVersion first.",
-    version
-  )))
+  let message = format!(
+    "Solc {} is not installed. Searched {} and found: {}. Call installSolcVersion first.",
+    version,
+    svm_install_dir().display(),
+    describe_installed_versions()
+  );
+  error!(target: LOG_TARGET, "{}", message);
+  Err(Error::compiler_not_installed(message))
+}
+
+/// Wraps `path` as a [`Solc`] directly, with no arguments prepended -- the explicit-path
+/// counterpart to [`ensure_installed`]'s installed-version lookup, used when a caller configures
+/// `SolcConfig::path` (see `SolcConfigOptions::path`) to point at their own binary instead of one
+/// resolved from the svm install directory. Unlike `ensure_installed`, this never fails: solc
+/// binary errors (missing file, wrong permissions, version mismatch with the rest of the config)
+/// only surface once the returned `Solc` is actually invoked.
+fn solc_at_path(path: &Path) -> Solc {
+  Solc::new_with_args(path.to_string_lossy().into_owned(), Vec::new())
+}
+
+/// Resolves the [`Solc`] a [`SolcConfig`] describes: an explicit `config.path` always wins and is
+/// used as-is, bypassing the installed-version lookup entirely; otherwise falls back to
+/// [`ensure_installed`], which -- regardless of `AstConfig::offline`/`CompilerConfig::offline_mode`
+/// -- only ever resolves against an already-installed release and never downloads.
+pub(crate) fn ensure_installed_for(config: &SolcConfig) -> Result<Solc> {
+  if let Some(path) = &config.path {
+    return Ok(solc_at_path(path));
+  }
+  ensure_installed(&config.version)
 }
 
 pub(crate) fn find_installed_version(version: &Version) -> Result<Option<Solc>> {
+  find_installed_version_with_args(version, &[])
+}
+
+/// Like [`find_installed_version`], but threads `extra_args` onto the returned [`Solc`] (see
+/// [`ensure_installed_with_args`]). Two differently-argued lookups for the same `version` return
+/// distinct `Solc` values, so a caller that keys a cache off the result must fold `extra_args`
+/// into that key alongside the version to avoid two wrapper configurations colliding.
+pub(crate) fn find_installed_version_with_args(
+  version: &Version,
+  extra_args: &[String],
+) -> Result<Option<Solc>> {
   let maybe_solc = map_err_with_context(
     Solc::find_svm_installed_version(version),
     "Failed to inspect solc versions",
   )?;
-  Ok(maybe_solc)
+  Ok(maybe_solc.map(|solc| with_extra_args(solc, extra_args)))
+}
+
+/// Prepends `extra_args` ahead of whatever args `solc` already carries, preserving every other
+/// resolved field (version, base/allow/include paths).
+fn with_extra_args(mut solc: Solc, extra_args: &[String]) -> Solc {
+  if extra_args.is_empty() {
+    return solc;
+  }
+  let mut combined = extra_args.to_vec();
+  combined.append(&mut solc.args);
+  solc.args = combined;
+  solc
 }
 
 pub(crate) fn is_version_installed(version: &Version) -> Result<bool> {
@@ -63,6 +230,17 @@ pub struct InstallSolcTask {
   pub(crate) version: Version,
 }
 
+/// Async counterpart of [`ensure_installed_req`]: resolves and installs the newest release
+/// satisfying `requirement` off the main thread, so JS callers can `await` auto-installation
+/// without first pinning the exact patch version themselves.
+pub(crate) fn install_req_async(requirement: VersionReq) -> AsyncTask<InstallReqTask> {
+  AsyncTask::new(InstallReqTask { requirement })
+}
+
+pub struct InstallReqTask {
+  pub(crate) requirement: VersionReq,
+}
+
 fn install_mutex() -> &'static Mutex<()> {
   static INSTALL_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
   INSTALL_MUTEX.get_or_init(|| Mutex::new(()))
@@ -94,6 +272,19 @@ impl Task for InstallSolcTask {
   }
 }
 
+impl Task for InstallReqTask {
+  type Output = Version;
+  type JsValue = String;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    to_napi_result(ensure_installed_req(&self.requirement)).map(|solc| solc.version)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(output.to_string())
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -127,6 +318,17 @@ mod tests {
     );
   }
 
+  #[test]
+  fn ensure_installed_error_lists_where_it_searched() {
+    let version = Version::new(0, 0, 0);
+    let err = ensure_installed(&version).unwrap_err();
+    assert!(
+      err.to_string().contains("Searched ") && err.to_string().contains(".svm"),
+      "unexpected message: {}",
+      err
+    );
+  }
+
   #[test]
   fn find_installed_version_returns_none_for_missing_version() {
     let version = Version::new(0, 0, 0);
@@ -139,4 +341,103 @@ mod tests {
     let version = Version::new(0, 0, 0);
     assert!(!is_version_installed(&version).expect("is installed"));
   }
+
+  #[test]
+  fn detect_version_falls_back_to_default_without_pragma() {
+    let req = detect_version("contract C {}").expect("detect version");
+    assert_eq!(req, VersionReq::parse("=0.8.30").unwrap());
+  }
+
+  #[test]
+  fn detect_version_merges_source_pragma() {
+    let req = detect_version("pragma solidity ^0.8.20;\ncontract C {}").expect("detect version");
+    assert!(req.matches(&Version::new(0, 8, 25)));
+    assert!(!req.matches(&Version::new(0, 9, 0)));
+  }
+
+  #[test]
+  fn ensure_compatible_errors_when_no_installed_version_matches() {
+    let req = VersionReq::parse("=0.0.0").unwrap();
+    let err = ensure_compatible(&req).unwrap_err();
+    assert!(
+      err.to_string().contains("No installed solc version satisfies"),
+      "unexpected message: {}",
+      err
+    );
+  }
+
+  #[test]
+  fn normalize_evm_version_clamps_to_what_solc_supports() {
+    let version = Version::new(0, 8, 5);
+    assert_eq!(
+      normalize_evm_version(&version, EvmVersion::London),
+      EvmVersion::Berlin
+    );
+  }
+
+  #[test]
+  fn normalize_evm_version_passes_through_supported_target() {
+    let version = Version::new(0, 8, 20);
+    assert_eq!(
+      normalize_evm_version(&version, EvmVersion::Shanghai),
+      EvmVersion::Shanghai
+    );
+  }
+
+  #[test]
+  fn with_extra_args_prepends_ahead_of_existing_args() {
+    let solc = Solc::new_with_args("solc", vec!["--base-path".to_string(), "src".to_string()]);
+    let wrapped = with_extra_args(solc, &["docker".to_string(), "run".to_string()]);
+    assert_eq!(wrapped.args, vec!["docker", "run", "--base-path", "src"]);
+  }
+
+  #[test]
+  fn with_extra_args_is_a_no_op_when_empty() {
+    let solc = Solc::new_with_args("solc", vec!["--base-path".to_string()]);
+    let wrapped = with_extra_args(solc, &[]);
+    assert_eq!(wrapped.args, vec!["--base-path"]);
+  }
+
+  #[test]
+  fn find_installed_version_with_args_returns_none_for_missing_version() {
+    let version = Version::new(0, 0, 0);
+    let result =
+      find_installed_version_with_args(&version, &["docker".to_string()]).expect("find version");
+    assert!(result.is_none());
+  }
+
+  #[test]
+  fn ensure_installed_req_reuses_an_already_installed_version() {
+    let version = Version::new(0, 8, 30);
+    if find_installed_version(&version).unwrap().is_none() {
+      return;
+    }
+    let req = VersionReq::parse("=0.8.30").unwrap();
+    let solc = ensure_installed_req(&req).expect("ensure installed req");
+    assert_eq!(solc.version, version);
+  }
+
+  #[test]
+  fn ensure_installed_for_prefers_an_explicit_path_over_version_lookup() {
+    let config = SolcConfig {
+      version: Version::new(0, 0, 0),
+      settings: Default::default(),
+      language: SolcLanguage::Solidity,
+      path: Some(std::path::PathBuf::from("/definitely/not/a/real/solc")),
+    };
+    let solc = ensure_installed_for(&config).expect("explicit path bypasses version lookup");
+    assert!(solc.args.is_empty());
+  }
+
+  #[test]
+  fn ensure_installed_for_falls_back_to_version_lookup_without_a_path() {
+    let config = SolcConfig {
+      version: Version::new(0, 0, 0),
+      settings: Default::default(),
+      language: SolcLanguage::Solidity,
+      path: None,
+    };
+    let err = ensure_installed_for(&config).unwrap_err();
+    assert!(err.to_string().contains("is not installed"));
+  }
 }
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use semver::Version;
+use serde_json::{json, Value};
+
+use crate::compiler::output::SourceArtifacts;
+use crate::contract::ContractState;
+use crate::internal::errors::{Error, Result};
+
+/// Schema version Truffle itself stamps on every `build/contracts/*.json` artifact. See
+/// <https://github.com/trufflesuite/truffle/blob/develop/packages/contract-schema/spec/contract-object.spec.json>
+/// for the shape this mirrors.
+const TRUFFLE_SCHEMA_VERSION: &str = "3.4.16";
+
+/// Builds the Truffle-shaped artifact JSON for a single contract. Unlike Hardhat's artifact,
+/// Truffle keys everything by bare contract name (no per-source nesting) and embeds the defining
+/// source's AST, compiler identity, and a `networks` map for deployment bookkeeping.
+pub(crate) fn truffle_artifact_json(
+  name: &str,
+  state: &ContractState,
+  solc_version: Option<&Version>,
+  source_ast: Option<&Value>,
+) -> Value {
+  json!({
+    "contractName": name,
+    "abi": state.abi.clone().unwrap_or_else(|| Value::Array(Vec::new())),
+    "bytecode": state
+      .creation_bytecode
+      .as_ref()
+      .map(|bytecode| bytecode.to_hex())
+      .unwrap_or_else(|| "0x".to_string()),
+    "deployedBytecode": state
+      .deployed_bytecode
+      .as_ref()
+      .map(|bytecode| bytecode.to_hex())
+      .unwrap_or_else(|| "0x".to_string()),
+    "sourceMap": state.creation_source_map.clone().unwrap_or_default(),
+    "deployedSourceMap": state.deployed_source_map.clone().unwrap_or_default(),
+    "sourcePath": state.source_path.clone().unwrap_or_default(),
+    "ast": source_ast.cloned().unwrap_or(Value::Null),
+    "compiler": {
+      "name": "solc",
+      "version": solc_version.map(Version::to_string).unwrap_or_default(),
+    },
+    "networks": {},
+    "schemaVersion": TRUFFLE_SCHEMA_VERSION,
+  })
+}
+
+/// Writes one Truffle-shaped `<ContractName>.json` file per compiled contract directly under
+/// `artifacts_dir`, mirroring `truffle compile`'s flat `build/contracts/` layout -- contracts are
+/// keyed only by name, not nested by the source file that defines them. Truffle itself has this
+/// same limitation: two contracts sharing a name across files silently overwrite each other.
+pub(crate) fn write_artifacts(
+  artifacts_dir: &Path,
+  artifacts: &BTreeMap<String, SourceArtifacts>,
+) -> Result<()> {
+  fs::create_dir_all(artifacts_dir).map_err(|err| {
+    Error::new(format!(
+      "Failed to prepare Truffle artifacts directory {}: {err}",
+      artifacts_dir.display()
+    ))
+  })?;
+
+  for source in artifacts.values() {
+    for (name, contract) in &source.contracts {
+      let payload = truffle_artifact_json(
+        name,
+        contract.state(),
+        source.solc_version.as_ref(),
+        source.ast.as_ref(),
+      );
+      let path = artifacts_dir.join(format!("{name}.json"));
+      let contents = serde_json::to_string_pretty(&payload)
+        .map_err(|err| Error::new(format!("Failed to serialise Truffle artifact {name}: {err}")))?;
+      fs::write(&path, contents)
+        .map_err(|err| Error::new(format!("Failed to write Truffle artifact {}: {err}", path.display())))?;
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn truffle_artifact_json_uses_flat_contract_schema() {
+    let mut state = ContractState::new("Sample");
+    state.abi = Some(json!([{"type": "function", "name": "greet"}]));
+    state.creation_bytecode = crate::contract::ContractBytecode::from_hex_string("0x6000").ok();
+    state.deployed_bytecode = crate::contract::ContractBytecode::from_hex_string("0x6001").ok();
+    state.creation_source_map = Some("0:1:0".to_string());
+    state.source_path = Some("contracts/Sample.sol".to_string());
+
+    let version = Version::parse("0.8.19").unwrap();
+    let ast = json!({"nodeType": "SourceUnit"});
+    let payload = truffle_artifact_json("Sample", &state, Some(&version), Some(&ast));
+
+    assert_eq!(payload["contractName"], "Sample");
+    assert_eq!(payload["bytecode"], "0x6000");
+    assert_eq!(payload["deployedBytecode"], "0x6001");
+    assert_eq!(payload["sourceMap"], "0:1:0");
+    assert_eq!(payload["deployedSourceMap"], "");
+    assert_eq!(payload["sourcePath"], "contracts/Sample.sol");
+    assert_eq!(payload["ast"]["nodeType"], "SourceUnit");
+    assert_eq!(payload["compiler"]["name"], "solc");
+    assert_eq!(payload["compiler"]["version"], "0.8.19");
+    assert_eq!(payload["networks"], json!({}));
+  }
+
+  #[test]
+  fn truffle_artifact_json_defaults_missing_fields() {
+    let state = ContractState::new("Interface");
+    let payload = truffle_artifact_json("Interface", &state, None, None);
+
+    assert_eq!(payload["bytecode"], "0x");
+    assert_eq!(payload["deployedBytecode"], "0x");
+    assert_eq!(payload["sourceMap"], "");
+    assert_eq!(payload["ast"], Value::Null);
+    assert_eq!(payload["compiler"]["version"], "");
+  }
+}
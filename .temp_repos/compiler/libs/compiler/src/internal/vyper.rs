@@ -1,21 +1,109 @@
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use log::error;
+use semver::Version;
 
 use foundry_compilers::compilers::vyper::Vyper;
+use napi::{bindgen_prelude::AsyncTask, Env, Task};
+
+use crate::internal::errors::{map_err_with_context, to_napi_result, Error, Result};
 
-use crate::internal::errors::{Error, Result};
+const LOG_TARGET: &str = "tevm::vyper";
 
 pub fn default_path() -> PathBuf {
   PathBuf::from("vyper")
 }
 
-pub fn ensure_installed(path: Option<PathBuf>) -> Result<Vyper> {
+pub(crate) fn parse_version(version: &str) -> Result<Version> {
+  let trimmed = version.trim().trim_start_matches('v');
+  map_err_with_context(Version::parse(trimmed), "Failed to parse vyper version")
+}
+
+/// Constructs a Vyper compiler from `path` (or [`default_path`] to resolve `vyper` off `PATH`)
+/// and, when `version` is supplied, rejects it unless the binary's own `vyper --version` output
+/// matches -- mirroring how `solc::ensure_installed` only ever hands back the exact release a
+/// caller asked for.
+pub fn ensure_installed(path: Option<PathBuf>, version: Option<&Version>) -> Result<Vyper> {
   let candidate = path.unwrap_or_else(default_path);
-  Vyper::new(candidate.clone()).map_err(|err| {
+  let vyper = Vyper::new(candidate.clone()).map_err(|err| {
     Error::new(format!(
       "Failed to initialise Vyper compiler at {}: {err}. Ensure `vyper` is installed and available on your PATH.",
       candidate.display()
     ))
-  })
+  })?;
+
+  if let Some(expected) = version {
+    let actual = map_err_with_context(
+      vyper.version(),
+      format!("Failed to query vyper version at {}", candidate.display()),
+    )?;
+    if &actual != expected {
+      return Err(Error::new(format!(
+        "vyper at {} reports version {actual}, expected {expected}. Call installVyperVersion first.",
+        candidate.display()
+      )));
+    }
+  }
+
+  Ok(vyper)
+}
+
+pub(crate) fn find_installed_version(version: &Version) -> Result<Option<Vyper>> {
+  map_err_with_context(
+    Vyper::find_installed_version(version),
+    "Failed to inspect vyper versions",
+  )
+}
+
+pub(crate) fn is_version_installed(version: &Version) -> Result<bool> {
+  find_installed_version(version).map(|maybe| maybe.is_some())
+}
+
+pub(crate) fn install_version(version: &Version) -> Result<()> {
+  map_err_with_context(
+    Vyper::blocking_install(version).map(|_| ()),
+    "Failed to install vyper version",
+  )
+}
+
+pub(crate) fn install_async(version: Version) -> AsyncTask<InstallVyperTask> {
+  AsyncTask::new(InstallVyperTask { version })
+}
+
+pub struct InstallVyperTask {
+  pub(crate) version: Version,
+}
+
+fn install_mutex() -> &'static Mutex<()> {
+  static INSTALL_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
+  INSTALL_MUTEX.get_or_init(|| Mutex::new(()))
+}
+
+impl Task for InstallVyperTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let _guard = to_napi_result(
+      install_mutex()
+        .lock()
+        .map_err(|err| Error::new(format!("Vyper install mutex poisoned: {err}"))),
+    )?;
+
+    if to_napi_result(find_installed_version(&self.version))?.is_some() {
+      return Ok(());
+    }
+    to_napi_result(map_err_with_context(
+      Vyper::blocking_install(&self.version),
+      "Failed to install vyper version",
+    ))
+    .map(|_| ())
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -31,6 +119,31 @@ mod tests {
   #[test]
   fn ensure_installed_errors_for_missing_binary() {
     let path = PathBuf::from("/definitely/missing/vyper");
-    assert!(ensure_installed(Some(path)).is_err());
+    assert!(ensure_installed(Some(path), None).is_err());
+  }
+
+  #[test]
+  fn parse_version_strips_whitespace_and_prefix() {
+    let parsed = parse_version(" v0.3.10 ").expect("parse version");
+    assert_eq!(parsed, Version::new(0, 3, 10));
+  }
+
+  #[test]
+  fn parse_version_rejects_invalid_input() {
+    let err = parse_version("abc").unwrap_err();
+    assert!(err.to_string().contains("Failed to parse vyper version"));
+  }
+
+  #[test]
+  fn find_installed_version_returns_none_for_missing_version() {
+    let version = Version::new(0, 0, 0);
+    let result = find_installed_version(&version).expect("find version");
+    assert!(result.is_none());
+  }
+
+  #[test]
+  fn is_version_installed_false_for_missing_version() {
+    let version = Version::new(0, 0, 0);
+    assert!(!is_version_installed(&version).expect("is installed"));
   }
 }
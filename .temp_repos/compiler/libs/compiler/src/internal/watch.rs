@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use log::info;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::compiler::core::{compile_as, State};
+use crate::compiler::output::CompileOutput;
+use crate::compiler::CompilationInput;
+use crate::internal::config::{CompilerConfig, CompilerLanguage};
+use crate::internal::errors::{Error, Result};
+use crate::internal::graph;
+
+const LOG_TARGET: &str = "tevm::watch";
+
+/// How long to wait after the first filesystem event before recompiling, coalescing a burst of
+/// saves (e.g. a formatter rewriting several files) into a single recompile instead of one per
+/// file.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// A running filesystem watcher started by [`start`]. Dropping it (or calling [`Self::stop`])
+/// stops the background debounce thread and tears down the underlying `notify` watcher.
+pub(crate) struct WatchSession {
+  stop_flag: Arc<AtomicBool>,
+  _watcher: RecommendedWatcher,
+  worker: Option<thread::JoinHandle<()>>,
+}
+
+impl WatchSession {
+  /// Stop watching and join the background debounce thread. Safe to call more than once.
+  pub fn stop(&mut self) {
+    self.stop_flag.store(true, Ordering::SeqCst);
+    if let Some(worker) = self.worker.take() {
+      let _ = worker.join();
+    }
+  }
+}
+
+impl Drop for WatchSession {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
+
+/// Watches `entry_paths` and every file they transitively import for changes, recompiling
+/// `entry_paths` through [`compile_as`] (so the incremental cache in `state` is reused the same
+/// way a normal `compile_sources` call would) and invoking `on_recompile` with
+/// each fresh [`CompileOutput`]. `on_recompile` runs on a dedicated background thread, not the
+/// thread that called `start`.
+pub(crate) fn start(
+  state: State,
+  config: CompilerConfig,
+  entry_paths: Vec<PathBuf>,
+  language_override: Option<CompilerLanguage>,
+  on_recompile: impl Fn(Result<CompileOutput>) + Send + Sync + 'static,
+) -> Result<WatchSession> {
+  if entry_paths.is_empty() {
+    return Err(Error::new("watch requires at least one path."));
+  }
+
+  let watched = graph::discover_transitive_sources(&entry_paths, &config.remappings);
+
+  let (tx, rx) = mpsc::channel();
+  let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+    if let Ok(event) = event {
+      if is_relevant(&event) {
+        let _ = tx.send(());
+      }
+    }
+  })
+  .map_err(|err| Error::with_context("Failed to start filesystem watcher", err))?;
+
+  for path in &watched {
+    watcher
+      .watch(path, RecursiveMode::NonRecursive)
+      .map_err(|err| {
+        Error::with_context(format!("Failed to watch {}", path.display()), err)
+      })?;
+  }
+
+  info!(
+    target: LOG_TARGET,
+    "watching {} file(s) for changes",
+    watched.len()
+  );
+
+  let stop_flag = Arc::new(AtomicBool::new(false));
+  let worker_stop_flag = stop_flag.clone();
+  let worker = thread::spawn(move || {
+    while !worker_stop_flag.load(Ordering::SeqCst) {
+      match rx.recv_timeout(Duration::from_millis(100)) {
+        Ok(()) => {
+          // Drain any further events within the debounce window so a burst of saves triggers a
+          // single recompile instead of one per file.
+          while rx.recv_timeout(DEBOUNCE).is_ok() {}
+          if worker_stop_flag.load(Ordering::SeqCst) {
+            break;
+          }
+          let input = CompilationInput::FilePaths {
+            paths: entry_paths.clone(),
+            language_override,
+          };
+          on_recompile(compile_as(&state, &config, input));
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => continue,
+        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+      }
+    }
+  });
+
+  Ok(WatchSession {
+    stop_flag,
+    _watcher: watcher,
+    worker: Some(worker),
+  })
+}
+
+/// Filters `notify` events down to the ones that should trigger a recompile: content changes,
+/// new files, and removals. Metadata-only events (e.g. permission changes) and access events are
+/// ignored, the same kinds of noise a naive `mtime` poll would otherwise react to.
+fn is_relevant(event: &notify::Event) -> bool {
+  matches!(
+    event.kind,
+    notify::EventKind::Modify(_) | notify::EventKind::Create(_) | notify::EventKind::Remove(_)
+  )
+}
@@ -7,7 +7,12 @@ mod contract;
 mod internal;
 
 pub use ast::{
-  Ast, FragmentTarget as AstFragmentTarget, SourceTarget as AstSourceTarget, State as AstState,
+  decode_source_map, Ast, CoverageMap as AstCoverageMap, CoverageProbe as AstCoverageProbe,
+  CoverageProbeKind as AstCoverageProbeKind, CoverageSiteKind as AstCoverageSiteKind,
+  FragmentTarget as AstFragmentTarget, NodeKind as AstNodeKind, NodeLocator as AstNodeLocator,
+  NodeSelector as AstNodeSelector, NodeVisibility as AstNodeVisibility,
+  QueryMatch as AstQueryMatch, Repl as AstRepl, ReplOutcome as AstReplOutcome,
+  SourceTarget as AstSourceTarget, State as AstState, VisitAction as AstVisitAction,
 };
 pub use compiler::{
   core::{
@@ -15,19 +20,25 @@ pub use compiler::{
     State as CompilerState,
   },
   output::{
-    from_standard_json, into_core_compile_output, CompilerError, JsCompileOutput,
-    JsSourceArtifacts, SecondarySourceLocation, SeverityLevel, SourceLocation,
+    from_standard_json, from_standard_json_with_selection, into_core_compile_output,
+    into_core_compile_output_with_selection, CompilerError, JsCompileOutput, JsSourceArtifacts,
+    SecondarySourceLocation, SeverityLevel, SourceLocation,
   },
   CompilationInput, Compiler,
 };
 pub use contract::{
-  Contract as ContractOutput, ContractBytecode, ContractState, ImmutableSlot, JsContract,
-  JsContractState,
+  ArtifactFieldSelection, Contract as ContractOutput, ContractBytecode, ContractState,
+  ExtraOutputMode, ExtraOutputValues, ImmutableSlot, JsArtifactFieldSelection, JsContract,
+  JsContractState, SourceMapEntry, WrittenArtifact,
 };
 pub use internal::config::{
-  AstConfig, AstConfigOptions, CompilerConfig, CompilerConfigOptions, JsAstConfigOptions,
-  JsCompilerConfigOptions, ResolveConflictStrategy, SolcConfig, SolcConfigOptions,
+  ArtifactFormat, ArtifactOutputFormat, AstConfig, AstConfigOptions, CompilerConfig,
+  CompilerConfigOptions, CompilerRestriction, JsArtifactFormat, JsArtifactOutputFormat,
+  JsAstConfigOptions, JsCompilerConfigOptions, JsCompilerRestriction, JsOutputMode,
+  JsSeverityOverride, OutputMode, ResolveConflictStrategy, SeverityOverride,
+  SeverityOverrideLevel, SolcConfig, SolcConfigOptions,
 };
 pub use internal::errors::{Error, Result};
 pub use internal::path::ProjectPaths;
+pub use internal::report::{ProgressEvent, ProgressEventJson, Reporter};
 pub use internal::settings::{CompilerSettingsOptions, JsCompilerSettingsOptions};